@@ -0,0 +1,83 @@
+//! Benchmarks of the lexer, the parser and the VM on the programs in
+//! `benches/programs`: `cargo bench`, or `cargo bench -- vm/fib` for one.
+//!
+//! Lexing is measured in bytes per second and parsing in tokens per
+//! second, over each program repeated until it is about 100 KB, with its
+//! functions renamed apart. The VM is measured in IR instructions per
+//! second, running `main` of the optimized program on a fresh VM.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use js_compiler::{ir, lexer, optimizer, parser, vm};
+use std::hint::black_box;
+
+const PROGRAMS: &[(&str, &str)] = &[
+    ("fib", include_str!("programs/fib.js")),
+    ("strings", include_str!("programs/strings.js")),
+    ("loops", include_str!("programs/loops.js")),
+];
+
+/// `source` repeated to about 100 KB, each copy's functions renamed by a
+/// suffix so that the copies declare different names.
+fn large(source: &str) -> String {
+    let ast = parser::parse(lexer::tokenize(source));
+    let names = ast.function_names();
+    let mut out = String::new();
+    for copy in 0.. {
+        if out.len() >= 100_000 {
+            break;
+        }
+        let mut renamed = source.to_string();
+        for name in &names {
+            renamed = renamed
+                .replace(&format!("{}(", name), &format!("{}_{}(", name, copy))
+                .replace(&format!("{} (", name), &format!("{}_{} (", name, copy));
+        }
+        out.push_str(&renamed);
+        out.push('\n');
+    }
+    out
+}
+
+fn lexer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lexer");
+    for &(name, source) in PROGRAMS {
+        let source = large(source);
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        group.bench_function(name, |b| b.iter(|| lexer::tokenize(black_box(&source))));
+    }
+    group.finish();
+}
+
+fn parser(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parser");
+    for &(name, source) in PROGRAMS {
+        let source = large(source);
+        let tokens = lexer::tokenize(&source);
+        group.throughput(Throughput::Elements(tokens.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter_batched(|| tokens.clone(), parser::parse, BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+fn vm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("vm");
+    for &(name, source) in PROGRAMS {
+        let module = optimizer::optimize(ir::lower_ast(parser::parse(lexer::tokenize(source))));
+        let mut counted = vm::VM::new(module.clone());
+        counted.execute_function("main", vec![]);
+        group.throughput(Throughput::Elements(counted.instruction_count()));
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || vm::VM::new(module.clone()),
+                |mut vm| vm.execute_function("main", vec![]),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lexer, parser, vm);
+criterion_main!(benches);
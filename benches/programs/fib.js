@@ -0,0 +1,11 @@
+// Calls and arithmetic
+function fib(n) {
+    if (n < 2) {
+        return n;
+    }
+    return fib(n - 1) + fib(n - 2);
+}
+
+function main() {
+    return fib(20);
+}
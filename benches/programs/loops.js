@@ -0,0 +1,25 @@
+// Loops, as recursion: a counter and arithmetic on locals each iteration
+function step(i) {
+    if (i % 3 === 0) {
+        return i * 2;
+    }
+    return i - 1;
+}
+
+function sum(i, total) {
+    if (i === 0) {
+        return total;
+    }
+    return sum(i - 1, total + step(i) % 7);
+}
+
+function repeat(times, total) {
+    if (times === 0) {
+        return total;
+    }
+    return repeat(times - 1, total + sum(200, times));
+}
+
+function main() {
+    return repeat(50, 0);
+}
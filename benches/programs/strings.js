@@ -0,0 +1,32 @@
+// Building a string a piece at a time, and comparing strings
+function piece(n) {
+    if (n % 2 === 0) {
+        return "ab";
+    }
+    return "cd";
+}
+
+function build(text, n) {
+    if (n === 0) {
+        return text;
+    }
+    return build(text + piece(n) + n, n - 1);
+}
+
+function count(text, n, same) {
+    if (n === 0) {
+        return same;
+    }
+    if (text === build("", 20)) {
+        return count(text, n - 1, same + 1);
+    }
+    return count(text, n - 1, same);
+}
+
+function main() {
+    let long = build("", 400);
+    if (long === long) {
+        return count(build("", 20), 50, 0);
+    }
+    return 0;
+}
@@ -0,0 +1,151 @@
+//! `#[js_native]`, which turns a plain Rust function into a native the
+//! VM can call, with the `macros` feature of `js-compiler`:
+//!
+//! ```ignore
+//! use js_compiler::vm::js_native;
+//!
+//! #[js_native]
+//! fn repeat(text: &str, times: f64) -> String {
+//!     text.repeat(times as usize)
+//! }
+//!
+//! repeat::register(&mut vm); // scripts can call repeat("ab", 2)
+//! ```
+//!
+//! Next to the function, which stays as it is, the macro adds a module of
+//! the same name holding:
+//!
+//! - `NAME`: the name scripts call it by. It is the function's own name
+//!   unless `#[js_native(name = "...")]` says otherwise.
+//! - `native`: the function as a `vm::NativeFunction`.
+//! - `register(vm)`: registers `native` with a VM under `NAME`.
+//!
+//! Arguments convert through `vm::FromValue`; a `&str` or `&T` argument
+//! converts to an owned `String` or `T` and is passed by reference. The
+//! result converts through `Into<Value>`, and a function with no result
+//! returns undefined. A function returning a `Result` has its `Err`
+//! raised as a VM error, as an argument of the wrong type is.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Error, FnArg, ItemFn, LitStr, ReturnType, Type};
+
+#[proc_macro_attribute]
+pub fn js_native(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(item as ItemFn);
+    let mut name = None;
+    let options = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else {
+            Err(meta.error("the only option is `name`"))
+        }
+    });
+    parse_macro_input!(attr with options);
+    expand(&function, name)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(function: &ItemFn, name: Option<String>) -> syn::Result<TokenStream2> {
+    let signature = &function.sig;
+    if !signature.generics.params.is_empty() {
+        let message = "a native cannot be generic";
+        return Err(Error::new(signature.generics.span(), message));
+    }
+    if let Some(asyncness) = signature.asyncness {
+        return Err(Error::new(asyncness.span(), "a native cannot be async"));
+    }
+    let ident = &signature.ident;
+    let name = name.unwrap_or_else(|| ident.to_string());
+    let vm = quote!(::js_compiler::vm);
+
+    let mut conversions = Vec::new();
+    let mut params = Vec::new();
+    for (index, input) in signature.inputs.iter().enumerate() {
+        let FnArg::Typed(input) = input else {
+            return Err(Error::new(input.span(), "a native cannot take `self`"));
+        };
+        let arg = format_ident!("arg{}", index);
+        // A reference is to an owned copy of the argument
+        let (ty, pass, mutability) = match &*input.ty {
+            Type::Reference(reference) => {
+                let ty = match &*reference.elem {
+                    Type::Path(path) if path.path.is_ident("str") => {
+                        quote!(::std::string::String)
+                    }
+                    elem => quote!(#elem),
+                };
+                match reference.mutability {
+                    Some(_) => (ty, quote!(&mut #arg), quote!(mut)),
+                    None => (ty, quote!(&#arg), quote!()),
+                }
+            }
+            ty => (quote!(#ty), quote!(#arg), quote!()),
+        };
+        conversions.push(quote! {
+            let #mutability #arg: #ty = match args.get::<#ty>(#index) {
+                Ok(value) => value,
+                Err(error) => context.raise_host_error(NAME, error),
+            };
+        });
+        params.push(pass);
+    }
+
+    let call = quote!(super::#ident(#(#params),*));
+    let result = match &signature.output {
+        ReturnType::Default => quote! {
+            #call;
+            #vm::Value::Undefined
+        },
+        ReturnType::Type(_, ty) if is_result(ty) => quote! {
+            match #call {
+                Ok(value) => value.into(),
+                Err(error) => context.raise_host_error(NAME, error),
+            }
+        },
+        ReturnType::Type(..) => quote!(#call.into()),
+    };
+
+    let vis = &function.vis;
+    let doc = format!("`{}` as the native `{}`.", ident, name);
+    Ok(quote! {
+        #function
+
+        #[doc = #doc]
+        #vis mod #ident {
+            #[allow(unused_imports)]
+            use super::*;
+
+            pub const NAME: &str = #name;
+
+            pub fn native(
+                context: &mut #vm::VMContext,
+                args: ::std::vec::Vec<#vm::Value>,
+            ) -> #vm::Value {
+                let args = #vm::Arguments::new(args);
+                #(#conversions)*
+                #result
+            }
+
+            pub fn register(vm: &mut #vm::VM) {
+                vm.register_native(NAME, native);
+            }
+        }
+    })
+}
+
+/// Whether `ty` is written as a `Result`.
+fn is_result(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Result"),
+        _ => false,
+    }
+}
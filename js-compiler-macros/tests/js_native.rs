@@ -0,0 +1,68 @@
+use js_compiler::diagnostics::Diagnostic;
+use js_compiler::vm::{Value, VM};
+use js_compiler::{ir, lexer, parser};
+use js_compiler_macros::js_native;
+use std::panic::{self, AssertUnwindSafe};
+
+#[js_native]
+fn hypot(x: f64, y: f64) -> f64 {
+    x.hypot(y)
+}
+
+#[js_native(name = "text.repeat")]
+fn repeat(text: &str, times: i64) -> String {
+    text.repeat(times as usize)
+}
+
+#[js_native]
+fn parse(text: String) -> Result<f64, String> {
+    text.parse()
+        .map_err(|_| format!("`{}` is not a number", text))
+}
+
+#[js_native]
+fn nothing() {}
+
+fn vm(source: &str) -> VM {
+    let mut vm = VM::new(ir::lower_ast(parser::parse(lexer::tokenize(source))));
+    hypot::register(&mut vm);
+    repeat::register(&mut vm);
+    parse::register(&mut vm);
+    nothing::register(&mut vm);
+    vm
+}
+
+fn error(vm: &mut VM, function: &str, args: Vec<Value>) -> String {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| vm.execute_function(function, args)));
+    vm.reset_stack();
+    Diagnostic::from_panic(result.unwrap_err()).message
+}
+
+#[test]
+fn test_natives_convert_arguments_and_results() {
+    let mut vm = vm("function main() { return hypot(3, 4) + parse(\"1.5\"); }\n\
+                     function twice(s) { return text.repeat(s, 2); }\n\
+                     function none() { return nothing(); }");
+    assert_eq!(vm.execute_function("main", vec![]), Value::Number(6.5));
+    assert_eq!(
+        vm.execute_function("twice", vec![Value::String("ab".into())]),
+        Value::String("abab".into())
+    );
+    assert_eq!(vm.execute_function("none", vec![]), Value::Undefined);
+    assert_eq!(repeat::NAME, "text.repeat");
+    assert_eq!(hypot(6.0, 8.0), 10.0);
+}
+
+#[test]
+fn test_natives_raise_errors() {
+    let mut vm = vm("function bad(x) { return hypot(x, 1); }\n\
+                     function nan(s) { return parse(s); }");
+    assert_eq!(
+        error(&mut vm, "bad", vec![Value::Boolean(true)]),
+        "hypot: argument 1 should be a number, not a boolean"
+    );
+    assert_eq!(
+        error(&mut vm, "nan", vec![Value::String("x".into())]),
+        "parse: `x` is not a number"
+    );
+}
@@ -0,0 +1,273 @@
+//! Semantic checks: mistakes the parser lets through that make a program
+//! wrong however it runs. Lowering stops at the first of some of these, and
+//! misses others; these checks find all of them, for `check` to list at
+//! once.
+//!
+//! - a function or extern declared twice, as they share one namespace;
+//! - a parameter named twice in one function;
+//! - an extern declared inside a function;
+//! - a call to an extern with a number of arguments it does not take.
+
+use crate::diagnostics::{Diagnostic, Span};
+use crate::parser::{ExprId, Expression, Statement, StmtId, AST};
+use std::collections::HashMap;
+
+/// The errors in `ast`, parsed from `source`, in source order.
+pub fn check(ast: &AST, source: &str) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        ast,
+        source,
+        externs: HashMap::new(),
+        errors: Vec::new(),
+    };
+    let mut declared: HashMap<&str, usize> = HashMap::new();
+    for &statement in &ast.statements {
+        let (line, statement) = located(ast, statement, 0);
+        let name = match statement {
+            Statement::FunctionDeclaration { name, params, .. } => {
+                checker.parameters(line, name, params);
+                name
+            }
+            Statement::ExternDeclaration {
+                name,
+                param_types,
+                variadic,
+                ..
+            } => {
+                checker.externs.insert(name, (param_types.len(), *variadic));
+                name
+            }
+            _ => continue,
+        };
+        match declared.get(name.as_str()) {
+            Some(&first_line) => {
+                let error = Diagnostic::error(format!("`{}` is declared twice", name))
+                    .with_label(Span::word(source, line, name), "declared again here")
+                    .with_label(Span::word(source, first_line, name), "first declared here")
+                    .with_note("functions and externs share one namespace; rename one of them");
+                checker.errors.push(error);
+            }
+            None => {
+                declared.insert(name, line);
+            }
+        }
+    }
+
+    for &statement in &ast.statements {
+        let (line, statement) = located(ast, statement, 0);
+        match statement {
+            Statement::FunctionDeclaration { body, .. } => checker.statements(line, body),
+            Statement::Let { initializer, .. } => checker.expression(line, *initializer),
+            Statement::ExternDeclaration { .. } => {}
+            statement => checker.statement(line, statement),
+        }
+    }
+
+    let mut errors = checker.errors;
+    errors.sort_by_key(|error| {
+        let span = error.labels[0].span;
+        (span.line, span.column)
+    });
+    errors
+}
+
+/// The line a statement starts on and the statement inside its location,
+/// or `line` for one without.
+fn located(ast: &AST, statement: StmtId, line: usize) -> (usize, &Statement) {
+    match &ast[statement] {
+        Statement::Located {
+            line, statement, ..
+        } => (*line, &ast[*statement]),
+        statement => (line, statement),
+    }
+}
+
+struct Checker<'a> {
+    ast: &'a AST,
+    source: &'a str,
+    /// Number of parameters of each extern, and whether it takes more.
+    externs: HashMap<&'a str, (usize, bool)>,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> Checker<'a> {
+    fn parameters(&mut self, line: usize, function: &str, params: &[String]) {
+        for (i, param) in params.iter().enumerate() {
+            if params[..i].contains(param) {
+                let error = Diagnostic::error(format!(
+                    "Parameter `{}` of `{}` is declared twice",
+                    param, function
+                ))
+                .with_label(Span::line(line), "");
+                self.errors.push(error);
+            }
+        }
+    }
+
+    fn statements(&mut self, line: usize, statements: &[StmtId]) {
+        for &statement in statements {
+            let (line, statement) = located(self.ast, statement, line);
+            self.statement(line, statement);
+        }
+    }
+
+    fn statement(&mut self, line: usize, statement: &Statement) {
+        match statement {
+            Statement::Let { initializer, .. } => self.expression(line, *initializer),
+            Statement::Return(value) => {
+                if let Some(value) = value {
+                    self.expression(line, *value);
+                }
+            }
+            Statement::ExpressionStatement(expression) => self.expression(line, *expression),
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.expression(line, *condition);
+                self.statements(line, then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.statements(line, else_branch);
+                }
+            }
+            Statement::While { condition, body } => {
+                self.expression(line, *condition);
+                self.statements(line, body);
+            }
+            Statement::For {
+                initializer,
+                condition,
+                update,
+                body,
+            } => {
+                if let Some(initializer) = initializer {
+                    self.statement(line, &self.ast[*initializer]);
+                }
+                if let Some(condition) = condition {
+                    self.expression(line, *condition);
+                }
+                self.statements(line, body);
+                if let Some(update) = update {
+                    self.statement(line, &self.ast[*update]);
+                }
+            }
+            Statement::Block(statements) => self.statements(line, statements),
+            Statement::Located {
+                line, statement, ..
+            } => self.statement(*line, &self.ast[*statement]),
+            // A closure
+            Statement::FunctionDeclaration { name, params, body } => {
+                self.parameters(line, name, params);
+                self.statements(line, body);
+            }
+            Statement::ExternDeclaration { name, .. } => {
+                let error = Diagnostic::error(format!("`{}` is declared inside a function", name))
+                    .with_label(Span::word(self.source, line, name), "")
+                    .with_note("externs can only be declared at the top level");
+                self.errors.push(error);
+            }
+        }
+    }
+
+    fn expression(&mut self, line: usize, expression: ExprId) {
+        match &self.ast[expression] {
+            Expression::FunctionCall { name, arguments } => {
+                if let Some(&(params, variadic)) = self.externs.get(name.as_str()) {
+                    let given = arguments.len();
+                    if given < params || (given > params && !variadic) {
+                        self.arity(line, name, params, variadic, given);
+                    }
+                }
+                for &argument in arguments {
+                    self.expression(line, argument);
+                }
+            }
+            Expression::Member { object, .. } => self.expression(line, *object),
+            Expression::MethodCall {
+                object, arguments, ..
+            } => {
+                self.expression(line, *object);
+                for &argument in arguments {
+                    self.expression(line, argument);
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                self.expression(line, *left);
+                self.expression(line, *right);
+            }
+            Expression::UnaryOp { expr, .. } => self.expression(line, *expr),
+            Expression::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                self.expression(line, *condition);
+                self.expression(line, *then_expr);
+                self.expression(line, *else_expr);
+            }
+            Expression::Identifier(_)
+            | Expression::Number(_)
+            | Expression::String(_)
+            | Expression::Boolean(_)
+            | Expression::Null => {}
+        }
+    }
+
+    /// Report a call to the extern `name` with `given` arguments.
+    fn arity(&mut self, line: usize, name: &str, params: usize, variadic: bool, given: usize) {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        let error = Diagnostic::error(format!(
+            "`{}` takes {}{} argument{}, but {} {} given",
+            name,
+            if variadic { "at least " } else { "" },
+            params,
+            plural(params),
+            given,
+            if given == 1 { "is" } else { "are" }
+        ))
+        .with_label(Span::word(self.source, line, name), "")
+        .with_note("C functions need exactly the arguments they declare");
+        self.errors.push(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer, parser};
+
+    fn errors(source: &str) -> Vec<String> {
+        let ast = parser::parse(lexer::tokenize(source));
+        check(&ast, source).iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn test_errors() {
+        let source = "extern function puts(string): int;\n\
+                      extern function printf(string, ...): int;\n\
+                      function f(a, b, a) {\n\
+                      extern function g(): int;\n\
+                      puts();\n\
+                      return printf(\"%d\", puts(\"x\", a));\n\
+                      }\n\
+                      function puts(x) {}";
+        assert_eq!(
+            errors(source),
+            [
+                "3: Parameter `a` of `f` is declared twice",
+                "4:17: `g` is declared inside a function",
+                "5:1: `puts` takes 1 argument, but 0 are given",
+                "6:21: `puts` takes 1 argument, but 2 are given",
+                "8:10: `puts` is declared twice",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_program() {
+        let source = "extern function printf(string, ...): int;\n\
+                      function main(a, b) { printf(\"%d %d\", a, b); printf(\"\"); return a; }";
+        assert!(errors(source).is_empty());
+    }
+}
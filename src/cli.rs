@@ -0,0 +1,955 @@
+//! Command-line parsing: a subcommand, an optional input file and flags in
+//! `--flag` or `--flag=value` form, each accepted only by the subcommands
+//! it means something to. The project file gives the defaults.
+
+use crate::config::Config;
+use js_compiler::codegen::{self, Arch, Target, TargetOs, TargetSpec, WasmHost, WasmValues};
+use js_compiler::debug::{Breakpoint, TraceOptions};
+use js_compiler::optimizer::OptLevel;
+use js_compiler::vm;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Run `main` in the VM.
+    Run,
+    /// Compile to a native executable.
+    Build,
+    /// Lex, parse, check, lower and verify without running or writing
+    /// anything.
+    Check,
+    /// Write the generated code for a target.
+    Emit,
+    /// List the IR of a source or `.jsbc` file.
+    Disasm,
+    /// Reprint source files in the standard layout.
+    Fmt,
+    /// Warn about code that compiles but is likely wrong.
+    Lint,
+    /// Run the `*.test.js` files under a directory.
+    Test,
+    /// Time repeated runs of `main`.
+    Bench,
+    /// Evaluate snippets typed at a prompt.
+    Repl,
+    /// Work with recorded traces: `debug diff a.json b.json`.
+    Debug,
+    Help,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "run" => Some(Command::Run),
+            "build" => Some(Command::Build),
+            "check" => Some(Command::Check),
+            "emit" => Some(Command::Emit),
+            "disasm" => Some(Command::Disasm),
+            "fmt" => Some(Command::Fmt),
+            "lint" => Some(Command::Lint),
+            "test" => Some(Command::Test),
+            "bench" => Some(Command::Bench),
+            "repl" => Some(Command::Repl),
+            "debug" => Some(Command::Debug),
+            "help" | "--help" | "-h" => Some(Command::Help),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Command::Run => "run",
+            Command::Build => "build",
+            Command::Check => "check",
+            Command::Emit => "emit",
+            Command::Disasm => "disasm",
+            Command::Fmt => "fmt",
+            Command::Lint => "lint",
+            Command::Test => "test",
+            Command::Bench => "bench",
+            Command::Repl => "repl",
+            Command::Debug => "debug",
+            Command::Help => "help",
+        }
+    }
+}
+
+/// Compilation stage whose output `--emit` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Tokens,
+    Ast,
+    /// The source minified, for `--emit=min-js`.
+    MinJs,
+    /// The IR after optimization, as the backends see it.
+    Ir,
+    /// x64 or ARM64 assembly: the target's when building or emitting for
+    /// one of them, the host's otherwise.
+    Asm,
+    Wat,
+    /// The IR after optimization, saved for `disasm`.
+    Jsbc,
+}
+
+impl Stage {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "tokens" => Some(Stage::Tokens),
+            "ast" => Some(Stage::Ast),
+            "min-js" => Some(Stage::MinJs),
+            "ir" => Some(Stage::Ir),
+            "asm" => Some(Stage::Asm),
+            "wat" => Some(Stage::Wat),
+            "jsbc" => Some(Stage::Jsbc),
+            _ => None,
+        }
+    }
+}
+
+/// How `run --debug` writes the execution trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// A page stepping through the trace.
+    Html,
+    /// The whole trace as one JSON object.
+    Json,
+    /// A JSON object per executed instruction, one per line.
+    Ndjson,
+    /// Instructions executed per call stack, for flamegraph tools.
+    Folded,
+    /// The call graph, for Graphviz.
+    Dot,
+}
+
+impl TraceFormat {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "html" => Some(TraceFormat::Html),
+            "json" => Some(TraceFormat::Json),
+            "ndjson" => Some(TraceFormat::Ndjson),
+            "folded" => Some(TraceFormat::Folded),
+            "dot" => Some(TraceFormat::Dot),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            TraceFormat::Html => "html",
+            TraceFormat::Json => "json",
+            TraceFormat::Ndjson => "ndjson",
+            TraceFormat::Folded => "folded",
+            TraceFormat::Dot => "dot",
+        }
+    }
+}
+
+const COMMANDS: &[(Command, &str)] = &[
+    (
+        Command::Run,
+        "Run main in the VM (the built-in example without a file)",
+    ),
+    (
+        Command::Build,
+        "Compile to a native executable, linked with `cc`",
+    ),
+    (
+        Command::Check,
+        "Report errors and lint warnings and verify the IR, running and writing nothing",
+    ),
+    (
+        Command::Emit,
+        "Write assembly, wat, LLVM IR or an object file",
+    ),
+    (
+        Command::Disasm,
+        "List each function's IR with jump targets and stack effects, from a .js or .jsbc file",
+    ),
+    (
+        Command::Fmt,
+        "Reformat files in place, or stdin to stdout without one",
+    ),
+    (
+        Command::Lint,
+        "Warn about unused names, unreachable code and names never declared",
+    ),
+    (
+        Command::Test,
+        "Run main in each *.test.js file under a directory (. by default), with assert available",
+    ),
+    (
+        Command::Bench,
+        "Time main over repeated runs in the VM, and with the JIT if asked",
+    ),
+    (
+        Command::Repl,
+        "Evaluate statements typed at a prompt, keeping their functions and variables",
+    ),
+    (
+        Command::Debug,
+        "diff a.json b.json: report where two traces from run --trace-format=json or ndjson first differ",
+    ),
+    (Command::Help, "Print this message"),
+];
+
+use Command::{Bench, Build, Check, Disasm, Emit, Fmt, Lint, Run, Test};
+
+/// Every flag: its name, the value it takes (empty for a switch), the
+/// subcommands accepting it and its help text.
+const FLAGS: &[(&str, &str, &[Command], &str)] = &[
+    (
+        "-O",
+        "<0|1|2>",
+        &[Run, Build, Check, Emit, Disasm, Test, Bench],
+        "Optimization level; 2 adds global value numbering",
+    ),
+    (
+        "-v",
+        "",
+        &[Run, Build, Check, Emit, Disasm, Test, Bench],
+        "Log what each compilation phase produced to stderr; -vv adds a line per function",
+    ),
+    (
+        "--timings",
+        "",
+        &[Run, Build, Check, Emit, Disasm],
+        "Print how long each phase took: lexing, parsing, lowering, each optimizer pass, codegen and running",
+    ),
+    (
+        "--emit",
+        "<stage>[=<file>],...",
+        &[Run, Build, Check, Emit, Disasm],
+        "Print tokens, ast, min-js (minified source), ir, asm, wat or jsbc (the IR for disasm), or write them to a file",
+    ),
+    (
+        "--output",
+        "<file>",
+        &[Run, Build, Emit],
+        "Where to write the code, executable or debug trace; - for stdout. Also -o <file>",
+    ),
+    (
+        "--out-dir",
+        "<dir>",
+        &[Build, Emit],
+        "Directory for the code and executable when not given by --output",
+    ),
+    (
+        "--watch",
+        "",
+        &[Run, Build, Check, Emit, Disasm, Lint],
+        "Repeat the command whenever the input file changes",
+    ),
+    (
+        "--check",
+        "",
+        &[Fmt],
+        "List the files that would be reformatted instead, failing if there are any",
+    ),
+    (
+        "--debug",
+        "",
+        &[Run],
+        "Write the execution trace to debug_output.html",
+    ),
+    (
+        "--trace-format",
+        "<html|json|ndjson|folded|dot>",
+        &[Run],
+        "Write the trace as a page, as JSON for other tools to read, as folded stacks for flamegraphs or as a call graph for Graphviz; implies --debug",
+    ),
+    (
+        "--trace-functions",
+        "<name,...>",
+        &[Run],
+        "Trace only the instructions of these functions; implies --debug",
+    ),
+    (
+        "--trace-calls",
+        "",
+        &[Run],
+        "Trace only calls and returns; implies --debug",
+    ),
+    (
+        "--trace-every",
+        "<n>",
+        &[Run],
+        "Trace one instruction out of every n; implies --debug",
+    ),
+    (
+        "--trace-last",
+        "<n>",
+        &[Run],
+        "Keep only the last n instructions of the trace; implies --debug",
+    ),
+    (
+        "--break",
+        "<function[:ip][ if local == value]>",
+        &[Run],
+        "Mark where the page stops playing: a function's first instruction or the one given, when a local has a value if given; implies --debug",
+    ),
+    (
+        "--profile",
+        "",
+        &[Run],
+        "Sample the call stack as the program runs and print the time spent in each function",
+    ),
+    (
+        "--profile-interval",
+        "<n>",
+        &[Run],
+        "Sample every n instructions instead of every 100; implies --profile",
+    ),
+    (
+        "--jit",
+        "",
+        &[Run, Bench],
+        "Run main as machine code in process and compare with the VM",
+    ),
+    (
+        "--iterations",
+        "<n>",
+        &[Bench],
+        "How many times bench runs main (10 by default)",
+    ),
+    (
+        "--target",
+        "<x64|arm64|wasm|llvm|cranelift>",
+        &[Build, Emit],
+        "Backend; build defaults to the host's",
+    ),
+    (
+        "--triple",
+        "<triple>",
+        &[Build, Emit],
+        "Target triple, picking the backend, OS and ABI",
+    ),
+    (
+        "--os",
+        "<linux|macos|windows>",
+        &[Build, Emit],
+        "OS flavor of native code (defaults to the host)",
+    ),
+    (
+        "--asm-syntax",
+        "<att|intel>",
+        &[Build, Emit],
+        "Dialect of x64 assembly",
+    ),
+    (
+        "--pic",
+        "",
+        &[Build, Emit],
+        "Generate position-independent code",
+    ),
+    (
+        "-g",
+        "",
+        &[Build, Emit],
+        "Emit DWARF line info and frame descriptions",
+    ),
+    (
+        "--annotate",
+        "",
+        &[Build, Emit],
+        "Interleave the JS source and IR with the output as comments",
+    ),
+    (
+        "--emit-obj",
+        "",
+        &[Emit],
+        "Write an object file, or a binary .wasm module",
+    ),
+    (
+        "--source-map",
+        "",
+        &[Emit],
+        "Write a source map to <output>.map, mapping assembly or Wasm back to JS lines",
+    ),
+    (
+        "--c-abi",
+        "",
+        &[Emit],
+        "Export the JS functions as C functions on doubles",
+    ),
+    (
+        "--wasi",
+        "",
+        &[Emit],
+        "Target WASI hosts instead of JavaScript ones",
+    ),
+    (
+        "--wasm-gc",
+        "",
+        &[Emit],
+        "Represent Wasm values as GC references",
+    ),
+    (
+        "--export",
+        "<name,...>",
+        &[Emit],
+        "Functions to export from the Wasm module",
+    ),
+];
+
+/// Usage text listing the subcommands and flags.
+pub fn usage() -> String {
+    let mut text =
+        String::from("Usage: js-compiler <command> [file.js ...] [flags]\n\nCommands:\n");
+    for (command, help) in COMMANDS {
+        text.push_str(&format!("  {:<8}{}\n", command.name(), help));
+    }
+    text.push_str("\nFlags:\n");
+    for (name, value, commands, help) in FLAGS {
+        let flag = match (*name, value.is_empty()) {
+            (_, true) => name.to_string(),
+            ("-O", false) => format!("-O{}", value),
+            (_, false) => format!("{}={}", name, value),
+        };
+        let commands: Vec<&str> = commands.iter().map(|c| c.name()).collect();
+        text.push_str(&format!(
+            "  {}\n      {} ({})\n",
+            flag,
+            help,
+            commands.join(", ")
+        ));
+    }
+    text
+}
+
+/// A parsed command line.
+#[derive(Debug, Clone)]
+pub struct Cli {
+    pub command: Command,
+    pub input: Option<String>,
+    /// Every input file: `fmt` formats each, and the commands compiling a
+    /// program link them into one module. `input` is the first.
+    pub files: Vec<String>,
+    pub opt_level: OptLevel,
+    /// Stages to print, with the file to write each to instead of stdout.
+    pub emit: Vec<(Stage, Option<String>)>,
+    /// `-o`: the generated code for `emit`, the executable for `build` and
+    /// the debug trace for `run`; `-` is stdout.
+    pub output: Option<String>,
+    /// Where `build` and `emit` write without `-o`; beside the input if
+    /// `None`.
+    pub out_dir: Option<PathBuf>,
+    pub watch: bool,
+    /// 1 for `-v`, 2 for `-vv`.
+    pub verbosity: u8,
+    pub timings: bool,
+    /// `fmt --check`.
+    pub check: bool,
+    pub debug: bool,
+    pub trace_format: TraceFormat,
+    pub trace_options: TraceOptions,
+    /// Instructions between the samples of `run --profile`.
+    pub profile: Option<u64>,
+    pub jit: bool,
+    /// `bench --iterations`.
+    pub iterations: u32,
+    /// Backend for `build` and `emit`; `Target::None` otherwise.
+    pub target: Target,
+    pub target_spec: TargetSpec,
+    pub x64_syntax: codegen::X64Syntax,
+    pub pic: bool,
+    pub debug_info: bool,
+    pub annotate: bool,
+    pub emit_object: bool,
+    pub source_map: bool,
+    pub c_abi: bool,
+    pub wasm_host: WasmHost,
+    pub wasm_values: WasmValues,
+    pub exports: Vec<String>,
+    /// The natives the VM provides, if not just `print`.
+    pub builtins: Option<Vec<String>>,
+    pub vm_limits: vm::Limits,
+}
+
+/// Parse the arguments after the program name, taking what they leave out
+/// from `config`.
+pub fn parse(args: &[String], config: &Config) -> Result<Cli, String> {
+    let (command, rest) = match args.split_first() {
+        Some((name, rest)) => (
+            Command::from_name(name).ok_or_else(|| format!("Unknown command `{}`", name))?,
+            rest,
+        ),
+        None => return Err("No command given".to_string()),
+    };
+
+    let mut cli = Cli {
+        command,
+        input: None,
+        files: Vec::new(),
+        opt_level: config.opt_level.unwrap_or_default(),
+        emit: Vec::new(),
+        output: None,
+        out_dir: config.out_dir.clone(),
+        watch: false,
+        verbosity: 0,
+        timings: false,
+        check: false,
+        debug: false,
+        trace_format: TraceFormat::Html,
+        trace_options: TraceOptions::default(),
+        profile: None,
+        jit: false,
+        iterations: 10,
+        target: Target::None,
+        target_spec: TargetSpec::host(),
+        x64_syntax: codegen::X64Syntax::default(),
+        pic: false,
+        debug_info: false,
+        annotate: false,
+        emit_object: false,
+        source_map: false,
+        c_abi: false,
+        wasm_host: WasmHost::JavaScript,
+        wasm_values: WasmValues::NanBoxed,
+        exports: Vec::new(),
+        builtins: config.builtins.clone(),
+        vm_limits: config.limits,
+    };
+    if command == Command::Help {
+        return Ok(cli);
+    }
+
+    let mut target = None;
+    let mut triple = None;
+    let mut os = None;
+    let mut args = rest.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            match &cli.input {
+                Some(input)
+                    if !matches!(
+                        command,
+                        Fmt | Run | Build | Check | Emit | Bench | Command::Debug
+                    ) =>
+                {
+                    return Err(format!("Two input files given: {} and {}", input, arg))
+                }
+                Some(_) => {}
+                None => cli.input = Some(arg.clone()),
+            }
+            cli.files.push(arg.clone());
+            continue;
+        }
+
+        // `-o` takes its value from the next argument, so `-o -` works
+        let (name, value) = match arg.strip_prefix("-O") {
+            _ if arg == "-o" => (
+                "--output",
+                Some(args.next().ok_or("-o needs a file name")?.as_str()),
+            ),
+            _ if arg == "-vv" => ("-v", None),
+            Some(level) => ("-O", Some(level)),
+            None => match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg.as_str(), None),
+            },
+        };
+        let Some((_, placeholder, commands, _)) = FLAGS.iter().find(|flag| flag.0 == name) else {
+            return Err(format!("Unknown flag `{}`", name));
+        };
+        if !commands.contains(&command) {
+            return Err(format!("`{}` does not take {}", command.name(), name));
+        }
+        let value = match (value, placeholder.is_empty()) {
+            (None, true) => "",
+            (Some(value), false) => value,
+            (Some(_), true) => return Err(format!("{} takes no value", name)),
+            (None, false) => {
+                return Err(format!("{} needs a value: {}={}", name, name, placeholder))
+            }
+        };
+
+        match name {
+            "-O" => {
+                cli.opt_level = OptLevel::from_flag(arg)
+                    .ok_or_else(|| format!("Unknown optimization level `{}`", arg))?
+            }
+            "--emit" => {
+                for stage in value.split(',').filter(|stage| !stage.is_empty()) {
+                    let (name, file) = match stage.split_once('=') {
+                        Some((name, file)) => (name, Some(file.to_string())),
+                        None => (stage, None),
+                    };
+                    let stage = Stage::from_name(name)
+                        .ok_or_else(|| format!("Unknown stage `{}` for --emit", name))?;
+                    cli.emit.push((stage, file));
+                }
+            }
+            "--output" => cli.output = Some(value.to_string()),
+            "--out-dir" => cli.out_dir = Some(PathBuf::from(value)),
+            "--watch" => cli.watch = true,
+            "--timings" => cli.timings = true,
+            "-v" => cli.verbosity = cli.verbosity.max(if arg == "-vv" { 2 } else { 1 }),
+            "--check" => cli.check = true,
+            "--debug" => cli.debug = true,
+            "--trace-format" => {
+                cli.trace_format = TraceFormat::from_name(value)
+                    .ok_or_else(|| format!("Unknown trace format `{}`", value))?;
+                cli.debug = true;
+            }
+            "--trace-functions" => {
+                let names = value.split(',').filter(|name| !name.is_empty());
+                cli.trace_options
+                    .functions
+                    .get_or_insert_with(Vec::new)
+                    .extend(names.map(String::from));
+                cli.debug = true;
+            }
+            "--trace-calls" => {
+                cli.trace_options.calls_only = true;
+                cli.debug = true;
+            }
+            "--trace-every" => {
+                cli.trace_options.sample_every =
+                    value.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                        format!("--trace-every needs a positive number, not `{}`", value)
+                    })?;
+                cli.debug = true;
+            }
+            "--trace-last" => {
+                cli.trace_options.last =
+                    Some(value.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                        format!("--trace-last needs a positive number, not `{}`", value)
+                    })?);
+                cli.debug = true;
+            }
+            "--break" => {
+                cli.trace_options
+                    .breakpoints
+                    .push(Breakpoint::parse(value)?);
+                cli.debug = true;
+            }
+            "--profile" => {
+                cli.profile.get_or_insert(100);
+            }
+            "--profile-interval" => {
+                cli.profile = Some(value.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                    format!(
+                        "--profile-interval needs a positive number, not `{}`",
+                        value
+                    )
+                })?)
+            }
+            "--jit" => cli.jit = true,
+            "--iterations" => {
+                cli.iterations = value.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                    format!("--iterations needs a positive number, not `{}`", value)
+                })?
+            }
+            "--target" => {
+                target = Some(
+                    Target::from_name(value)
+                        .filter(|target| !matches!(target, Target::None))
+                        .ok_or_else(|| format!("Unknown target `{}`", value))?,
+                )
+            }
+            "--triple" => {
+                triple = Some(
+                    TargetSpec::from_triple(value)
+                        .ok_or_else(|| format!("Unknown target triple `{}`", value))?,
+                )
+            }
+            "--os" => {
+                os = Some(
+                    TargetOs::from_name(value)
+                        .ok_or_else(|| format!("Unknown target OS `{}`", value))?,
+                )
+            }
+            "--asm-syntax" => {
+                cli.x64_syntax = codegen::X64Syntax::from_name(value)
+                    .ok_or_else(|| format!("Unknown assembly syntax `{}`", value))?
+            }
+            "--pic" => cli.pic = true,
+            "-g" => cli.debug_info = true,
+            "--annotate" => cli.annotate = true,
+            "--emit-obj" => cli.emit_object = true,
+            "--source-map" => cli.source_map = true,
+            "--c-abi" => cli.c_abi = true,
+            "--wasi" => cli.wasm_host = WasmHost::Wasi,
+            "--wasm-gc" => cli.wasm_values = WasmValues::GcReferences,
+            "--export" => cli.exports.extend(
+                value
+                    .split(',')
+                    .filter(|name| !name.is_empty())
+                    .map(String::from),
+            ),
+            _ => unreachable!("Flag {} is listed but not handled", name),
+        }
+    }
+
+    match (command, cli.output.as_deref()) {
+        (Command::Run, Some(_)) if !cli.debug => {
+            return Err("run only writes output with --debug".to_string())
+        }
+        (Command::Build, Some("-")) => {
+            return Err("build cannot write an executable to stdout".to_string())
+        }
+        _ => {}
+    }
+    if command == Command::Debug {
+        // The first word names what to do; `diff` is all there is
+        match &cli.files[..] {
+            [action, _, _] if action == "diff" => {
+                cli.files.remove(0);
+                cli.input = Some(cli.files[0].clone());
+            }
+            _ => return Err("debug needs `diff <a.json> <b.json>`".to_string()),
+        }
+    }
+    if cli.jit && cli.profile.is_some() {
+        return Err("--profile samples the VM, which --jit does not use".to_string());
+    }
+    if cli.watch && cli.input.is_none() {
+        return Err("--watch needs an input file".to_string());
+    }
+
+    if matches!(command, Command::Build | Command::Emit) {
+        // A triple alone selects the backend for its architecture, then
+        // the project's target applies, and building defaults to the host's
+        cli.target = match (target, triple) {
+            (Some(target), _) => target,
+            (None, Some(spec)) => spec.arch.target(),
+            (None, None) => match &config.target {
+                Some(target) => target.clone(),
+                None if command == Command::Build => Arch::host().target(),
+                None => return Err("emit needs --target or --triple".to_string()),
+            },
+        };
+        if command == Command::Build
+            && !matches!(cli.target, Target::X64 | Target::ARM64 | Target::Cranelift)
+        {
+            return Err(format!(
+                "The {} target does not build executables; use emit",
+                cli.target.name()
+            ));
+        }
+        let arch = match cli.target {
+            Target::X64 => Arch::X86_64,
+            Target::ARM64 => Arch::Aarch64,
+            _ => Arch::host(),
+        };
+        cli.target_spec = match (triple, os) {
+            (Some(_), Some(_)) => return Err("--os cannot be combined with --triple".to_string()),
+            (Some(spec), None)
+                if spec.arch != arch && matches!(cli.target, Target::X64 | Target::ARM64) =>
+            {
+                return Err(format!(
+                    "The {} target cannot generate code for {}",
+                    cli.target.name(),
+                    spec.triple()
+                ))
+            }
+            (Some(spec), None) => spec,
+            (None, os) => TargetSpec::new(arch, os.unwrap_or_default()),
+        };
+        // Whether given by a triple or by --os, before any code is generated
+        if let Some(message) = cli.target.unsupported(&cli.target_spec) {
+            return Err(message);
+        }
+    }
+
+    if cli.source_map {
+        match cli.target {
+            Target::Wasm => {}
+            Target::X64 | Target::ARM64 if cli.emit_object => {
+                return Err("--source-map maps assembly, not object files".to_string())
+            }
+            Target::X64 | Target::ARM64 => {}
+            _ => return Err("--source-map needs the x64, arm64 or wasm target".to_string()),
+        }
+        if cli.output.as_deref() == Some("-") {
+            return Err("--source-map needs an output file, not stdout".to_string());
+        }
+    }
+
+    Ok(cli)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line: &str) -> Result<Cli, String> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse(&args, &Config::default())
+    }
+
+    #[test]
+    fn test_subcommands_and_flags() {
+        let cli = parse_line("run program.js -O2 --debug").unwrap();
+        assert_eq!(cli.command, Command::Run);
+        assert_eq!(cli.input.as_deref(), Some("program.js"));
+        assert_eq!(cli.opt_level, OptLevel::O2);
+        assert!(cli.debug);
+
+        let cli = parse_line("emit program.js --triple=aarch64-apple-darwin --export=a,b").unwrap();
+        assert!(matches!(cli.target, Target::ARM64));
+        assert_eq!(cli.target_spec.os, TargetOs::MacOs);
+        assert_eq!(cli.exports, ["a", "b"]);
+
+        let cli = parse_line("check program.js --emit=tokens,ir=program.ir").unwrap();
+        assert_eq!(
+            cli.emit,
+            [
+                (Stage::Tokens, None),
+                (Stage::Ir, Some("program.ir".to_string()))
+            ]
+        );
+
+        let cli = parse_line("build program.js").unwrap();
+        assert_eq!(cli.target_spec.arch, Arch::host());
+        assert_eq!(parse_line("help").unwrap().command, Command::Help);
+
+        let cli = parse_line("emit program.js --target=wasm -o -").unwrap();
+        assert_eq!(cli.output.as_deref(), Some("-"));
+        let cli = parse_line("run program.js --trace-format=ndjson").unwrap();
+        assert!(cli.debug);
+        assert_eq!(cli.trace_format, TraceFormat::Ndjson);
+        let cli = parse_line("run program.js --trace-functions=fib --trace-calls --trace-every=10")
+            .unwrap();
+        assert!(cli.debug);
+        assert_eq!(cli.trace_options.functions.unwrap(), ["fib"]);
+        assert!(cli.trace_options.calls_only);
+        assert_eq!(cli.trace_options.sample_every, 10);
+        let cli = parse_line("run program.js --trace-last=1000").unwrap();
+        assert_eq!(cli.trace_options.last, Some(1000));
+        assert_eq!(
+            parse_line("run program.js --profile").unwrap().profile,
+            Some(100)
+        );
+        let cli = parse_line("run program.js --profile-interval=10 --profile").unwrap();
+        assert_eq!(cli.profile, Some(10));
+        let args = ["run", "program.js", "--break=fib if n == 3"].map(String::from);
+        let cli = parse(&args, &Config::default()).unwrap();
+        assert!(cli.debug);
+        assert_eq!(cli.trace_options.breakpoints[0].function, "fib");
+        let cli = parse_line("run program.js --debug --output=trace.html").unwrap();
+        assert_eq!(cli.output.as_deref(), Some("trace.html"));
+        assert!(parse_line("check program.js --watch").unwrap().watch);
+        assert_eq!(parse_line("check program.js -vv").unwrap().verbosity, 2);
+        let cli = parse_line("disasm program.js -O1 --emit=jsbc=program.jsbc").unwrap();
+        assert_eq!(cli.command, Command::Disasm);
+        assert_eq!(cli.emit, [(Stage::Jsbc, Some("program.jsbc".to_string()))]);
+        let cli = parse_line("fmt a.js b.js --check").unwrap();
+        assert_eq!(cli.files, ["a.js", "b.js"]);
+        assert!(cli.check);
+        let cli = parse_line("run main.js util.js").unwrap();
+        assert_eq!(cli.input.as_deref(), Some("main.js"));
+        assert_eq!(cli.files, ["main.js", "util.js"]);
+        assert_eq!(
+            parse_line("lint a.js --watch").unwrap().command,
+            Command::Lint
+        );
+        assert_eq!(parse_line("test tests -O2").unwrap().command, Command::Test);
+        let cli = parse_line("debug diff a.json b.ndjson").unwrap();
+        assert_eq!(cli.command, Command::Debug);
+        assert_eq!(cli.files, ["a.json", "b.ndjson"]);
+        assert_eq!(
+            parse_line("bench a.js --iterations=3 --jit")
+                .unwrap()
+                .iterations,
+            3
+        );
+        let cli = parse_line("check a.js --emit=min-js=a.min.js").unwrap();
+        assert_eq!(cli.emit, [(Stage::MinJs, Some("a.min.js".to_string()))]);
+    }
+
+    #[test]
+    fn test_rejected_command_lines() {
+        let error = |line| parse_line(line).unwrap_err();
+        assert_eq!(error(""), "No command given");
+        assert_eq!(error("program.js"), "Unknown command `program.js`");
+        assert_eq!(error("run --target=x64"), "`run` does not take --target");
+        assert_eq!(
+            error("emit a.js --frobnicate"),
+            "Unknown flag `--frobnicate`"
+        );
+        assert_eq!(
+            error("emit a.js --pic=yes --target=x64"),
+            "--pic takes no value"
+        );
+        assert_eq!(
+            error("emit a.js --target"),
+            "--target needs a value: --target=<x64|arm64|wasm|llvm|cranelift>"
+        );
+        assert_eq!(error("emit a.js"), "emit needs --target or --triple");
+        assert_eq!(
+            error("run a.js --emit=bytecode"),
+            "Unknown stage `bytecode` for --emit"
+        );
+        assert_eq!(
+            error("build a.js --target=wasm"),
+            "The wasm target does not build executables; use emit"
+        );
+        assert_eq!(
+            error("disasm a.js b.js"),
+            "Two input files given: a.js and b.js"
+        );
+        assert_eq!(
+            error("run a.js -o trace.html"),
+            "run only writes output with --debug"
+        );
+        assert_eq!(error("check a.js -o out"), "`check` does not take --output");
+        assert_eq!(
+            error("build a.js -o -"),
+            "build cannot write an executable to stdout"
+        );
+        assert_eq!(error("emit a.js --target=x64 -o"), "-o needs a file name");
+        assert_eq!(error("run --watch"), "--watch needs an input file");
+        assert_eq!(
+            error("debug a.json b.json"),
+            "debug needs `diff <a.json> <b.json>`"
+        );
+        assert_eq!(
+            error("emit a.js --triple=aarch64-pc-windows-msvc"),
+            "The arm64 backend does not support Windows"
+        );
+        assert_eq!(
+            error("build a.js --target=arm64 --os=windows"),
+            "The arm64 backend does not support Windows"
+        );
+        assert_eq!(
+            error("run a.js --jit --profile"),
+            "--profile samples the VM, which --jit does not use"
+        );
+        assert_eq!(
+            error("bench a.js --iterations=0"),
+            "--iterations needs a positive number, not `0`"
+        );
+        assert_eq!(
+            error("emit a.js --target=llvm --source-map"),
+            "--source-map needs the x64, arm64 or wasm target"
+        );
+        assert_eq!(
+            error("emit a.js --target=wasm --source-map -o -"),
+            "--source-map needs an output file, not stdout"
+        );
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = Config {
+            target: Some(Target::Wasm),
+            opt_level: Some(OptLevel::O2),
+            out_dir: Some(PathBuf::from("build")),
+            ..Config::default()
+        };
+        let parse_line = |line: &str| {
+            let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+            parse(&args, &config).unwrap()
+        };
+        let cli = parse_line("emit a.js");
+        assert!(matches!(cli.target, Target::Wasm));
+        assert_eq!(cli.opt_level, OptLevel::O2);
+        assert_eq!(cli.out_dir, Some(PathBuf::from("build")));
+
+        let cli = parse_line("emit a.js --triple=aarch64-unknown-linux-gnu -O0 --out-dir=out");
+        assert!(matches!(cli.target, Target::ARM64));
+        assert_eq!(cli.opt_level, OptLevel::O0);
+        assert_eq!(cli.out_dir, Some(PathBuf::from("out")));
+    }
+}
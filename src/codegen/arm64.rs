@@ -1,53 +1,245 @@
-use super::CodeGenerator;
-use crate::ir::{BinaryOp, Constant, IRFunction, IRInstruction, IRModule, UnaryOp};
+use super::source_map::LineTable;
+use super::{
+    assembler, closures_unsupported, globals_unsupported, objects_unsupported, runtime, Arch,
+    CallingConvention, CodeGenerator, CodegenOptions, Target, TargetOs,
+};
+use crate::diagnostics::Diagnostic;
+use crate::ir::{
+    BinaryOp, CType, Constant, ExternFunction, FunctionTypes, IRFunction, IRInstruction, IRModule,
+    UnaryOp,
+};
+use crate::parallel;
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::io;
+
+/// Registers for the first eight arguments (AAPCS64, also on Apple).
+const ARGUMENT_REGISTERS: [&str; 8] = ["x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7"];
+/// Registers for the first eight `double` arguments of C functions.
+const FLOAT_ARGUMENT_REGISTERS: [&str; 8] = ["d0", "d1", "d2", "d3", "d4", "d5", "d6", "d7"];
 
 pub struct ARM64Generator {
+    options: CodegenOptions,
     output: String,
+    line_table: LineTable,
+    current_function: String,
     string_literals: Vec<String>,
     float_literals: Vec<f64>,
     local_offsets: HashMap<String, i32>,
     current_stack_size: i32,
-    label_counter: usize,
+    next_local_label: usize,
+    externs: Vec<ExternFunction>,
+    /// Names of the module's functions, which `Call` indexes.
+    functions: Vec<String>,
+    /// The module's constants, which `PushConst` indexes.
+    constants: Vec<Constant>,
+    /// Types inferred for each function of the module not generated yet.
+    types: HashMap<String, FunctionTypes>,
+    /// Whether type inference showed the current instruction's operands
+    /// are all numbers.
+    operands_are_numbers: bool,
+    /// Source line of the instruction being generated, for errors.
+    line: Option<usize>,
+}
+
+impl Default for ARM64Generator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ARM64Generator {
     pub fn new() -> Self {
+        Self::with_options(CodegenOptions::default())
+    }
+
+    pub fn with_options(mut options: CodegenOptions) -> Self {
+        options.target = options.target.with_arch(Arch::Aarch64);
+        if let Some(message) = Target::ARM64.unsupported(&options.target) {
+            Diagnostic::error(message).raise();
+        }
         Self {
+            options,
             output: String::new(),
+            line_table: LineTable::default(),
+            current_function: String::new(),
             string_literals: Vec::new(),
             float_literals: Vec::new(),
             local_offsets: HashMap::new(),
             current_stack_size: 0,
-            label_counter: 0,
+            next_local_label: 0,
+            externs: Vec::new(),
+            functions: Vec::new(),
+            constants: Vec::new(),
+            types: HashMap::new(),
+            operands_are_numbers: false,
+            line: None,
         }
     }
 
     fn reset_state(&mut self) {
         self.local_offsets.clear();
         self.current_stack_size = 0;
+        self.line = None;
+    }
+
+    /// Global symbol for a JS function.
+    fn symbol(&self, name: &str) -> String {
+        format!("{}{}", self.options.target.symbol_prefix(), name)
+    }
+
+    /// Assembler-local label name.
+    fn local(&self, name: &str) -> String {
+        format!("{}{}", self.options.target.local_prefix(), name)
+    }
+
+    /// IR labels restart in every function, so qualify them with its name.
+    fn label(&self, label: &str) -> String {
+        self.local(&format!("L{}_{}", self.current_function, label))
+    }
+
+    /// Page and page-offset relocation operands for `symbol`.
+    fn page_relocs(&self, symbol: &str) -> (String, String) {
+        match self.options.target.os {
+            TargetOs::Linux | TargetOs::Windows => {
+                (symbol.to_string(), format!(":lo12:{}", symbol))
+            }
+            TargetOs::MacOs => (format!("{}@PAGE", symbol), format!("{}@PAGEOFF", symbol)),
+        }
+    }
+
+    /// Label of a string (`LC`) or number (`LCD`) literal. GOT relocations
+    /// need a real symbol rather than a section offset, so PIC code names
+    /// literals with a `js.` prefix no JS identifier can clash with.
+    fn literal(&self, name: &str) -> String {
+        if self.options.pic {
+            format!("js.{}", name)
+        } else {
+            self.local(name)
+        }
+    }
+
+    /// Page and page-offset operands loading the GOT entry for `symbol`.
+    fn got_relocs(&self, symbol: &str) -> (String, String) {
+        match self.options.target.os {
+            TargetOs::Linux | TargetOs::Windows => {
+                (format!(":got:{}", symbol), format!(":got_lo12:{}", symbol))
+            }
+            TargetOs::MacOs => (
+                format!("{}@GOTPAGE", symbol),
+                format!("{}@GOTPAGEOFF", symbol),
+            ),
+        }
+    }
+
+    /// Load the address of a literal into x0.
+    fn generate_literal_address(&mut self, literal: &str) {
+        if self.options.pic {
+            let (page, offset) = self.got_relocs(literal);
+            writeln!(self.output, "	adrp x0, {}", page).unwrap();
+            writeln!(self.output, "	ldr x0, [x0, {}]", offset).unwrap();
+        } else {
+            let (page, offset) = self.page_relocs(literal);
+            writeln!(self.output, "	adrp x0, {}", page).unwrap();
+            writeln!(self.output, "	add x0, x0, {}", offset).unwrap();
+        }
+    }
+
+    /// Declare the global function `name` and start it.
+    fn generate_function_header(&mut self, name: &str) {
+        let symbol = self.symbol(name);
+        writeln!(self.output, "\t.global {}", symbol).unwrap();
+        if self.options.target.os == TargetOs::Linux {
+            writeln!(self.output, "\t.type {}, %function", symbol).unwrap();
+        }
+        writeln!(self.output, "\t.p2align 2").unwrap();
+        writeln!(self.output, "{}:", symbol).unwrap();
     }
 
-    fn next_label(&mut self) -> String {
-        self.label_counter += 1;
-        format!(".L{}", self.label_counter)
+    /// The C `main`: call the JS `main`, whose result comes back in x0 where
+    /// `jsrt_exit` takes it, and exit with the status it gives.
+    fn generate_entry_point(&mut self) {
+        self.generate_function_header("main");
+        writeln!(self.output, "\tstp fp, lr, [sp, #-16]!").unwrap();
+        writeln!(self.output, "\tmov fp, sp").unwrap();
+        writeln!(self.output, "\tbl {}", self.symbol(runtime::JS_MAIN)).unwrap();
+        writeln!(self.output, "\tbl {}", self.symbol(runtime::EXIT_FUNCTION)).unwrap();
+    }
+
+    /// C-ABI wrapper for `function`, taking and returning `double`s. It moves
+    /// the arguments to the general-purpose registers JS functions take
+    /// values in, replacing NaNs that would read as boxed values, then
+    /// converts the result to a number.
+    fn generate_c_wrapper(&mut self, function: &IRFunction) {
+        self.generate_function_header(&function.name);
+        writeln!(self.output, "\tstp fp, lr, [sp, #-16]!").unwrap();
+        writeln!(self.output, "\tmov fp, sp").unwrap();
+
+        let argc = function.params.len();
+        let stack_argc = argc.saturating_sub(FLOAT_ARGUMENT_REGISTERS.len());
+        let reserved = (stack_argc * 8).next_multiple_of(16);
+        if reserved > 0 {
+            writeln!(self.output, "\tsub sp, sp, #{}", reserved).unwrap();
+        }
+        writeln!(self.output, "\tmov x9, #{:#x}", runtime::MAX_NUMBER).unwrap();
+        writeln!(self.output, "\tmov x10, #{:#x}", runtime::NAN).unwrap();
+        for (register, float_register) in ARGUMENT_REGISTERS
+            .iter()
+            .zip(FLOAT_ARGUMENT_REGISTERS)
+            .take(argc)
+        {
+            writeln!(self.output, "\tfmov {}, {}", register, float_register).unwrap();
+            self.generate_canonical_nan(register);
+        }
+        // Copy the stack arguments down to where the JS function finds them
+        for j in 0..stack_argc {
+            writeln!(self.output, "\tldr x11, [fp, #{}]", 16 + j * 8).unwrap();
+            self.generate_canonical_nan("x11");
+            writeln!(self.output, "\tstr x11, [sp, #{}]", j * 8).unwrap();
+        }
+
+        let symbol = self.symbol(&self.options.function_name(&function.name));
+        writeln!(self.output, "\tbl {}", symbol).unwrap();
+        self.generate_runtime_call("jsrt_to_number");
+        writeln!(self.output, "\tmov sp, fp").unwrap();
+        writeln!(self.output, "\tldp fp, lr, [sp], #16").unwrap();
+        writeln!(self.output, "\tret").unwrap();
+    }
+
+    /// Replace the double bits in `register` with the canonical NaN if they
+    /// lie above `MAX_NUMBER`, given x9 = `MAX_NUMBER` and x10 = `NAN`.
+    fn generate_canonical_nan(&mut self, register: &str) {
+        writeln!(self.output, "\tcmp {}, x9", register).unwrap();
+        writeln!(self.output, "\tcsel {}, {}, x10, ls", register, register).unwrap();
     }
 
     fn generate_function(&mut self, function: &IRFunction) {
         self.reset_state();
+        self.current_function = function.name.clone();
 
-        // Function header
-        writeln!(self.output, "\t.global _{}", function.name).unwrap();
-        writeln!(self.output, "\t.p2align 2").unwrap();
-        writeln!(self.output, "_{}:", function.name).unwrap();
+        let symbol = self.options.function_name(&function.name);
+        self.generate_function_header(&symbol);
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_startproc").unwrap();
+            if let Some(IRInstruction::Line(line, _)) = function.instructions().first() {
+                writeln!(self.output, "\t.loc 1 {}", line).unwrap();
+            }
+        }
 
         // Function prologue
         writeln!(self.output, "\tstp fp, lr, [sp, #-16]!").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_def_cfa_offset 16").unwrap();
+            writeln!(self.output, "\t.cfi_offset w30, -8").unwrap();
+            writeln!(self.output, "\t.cfi_offset w29, -16").unwrap();
+        }
         writeln!(self.output, "\tmov fp, sp").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_def_cfa w29, 16").unwrap();
+        }
 
         // Allocate stack frame
-        let frame_size = ((function.max_locals * 8 + 15) / 16) * 16;
+        let frame_size = (function.max_locals * 8).div_ceil(16) * 16;
         if frame_size > 0 {
             writeln!(self.output, "\tsub sp, sp, #{}", frame_size).unwrap();
         }
@@ -61,28 +253,40 @@ impl ARM64Generator {
 
         // Store parameters in their slots
         for (i, param) in function.params.iter().enumerate() {
-            let param_reg = match i {
-                0 => "x0",
-                1 => "x1",
-                2 => "x2",
-                3 => "x3",
-                4 => "x4",
-                5 => "x5",
-                6 => "x6",
-                7 => "x7",
-                _ => panic!("Too many parameters"),
-            };
-            let offset = self.allocate_local(param);
-            writeln!(self.output, "\tstr {}, [fp, #{}]", param_reg, offset).unwrap();
+            match ARGUMENT_REGISTERS.get(i) {
+                Some(param_reg) => {
+                    let offset = self.allocate_local(param);
+                    writeln!(self.output, "\tstr {}, [fp, #{}]", param_reg, offset).unwrap();
+                }
+                // The rest were passed on the stack above the frame record,
+                // where they stay
+                None => {
+                    let offset = 16 + (i - ARGUMENT_REGISTERS.len()) as i32 * 8;
+                    self.local_offsets.insert(param.clone(), offset);
+                }
+            }
         }
 
         // Generate code for instructions
-        for instruction in &function.instructions {
+        let types = match self.types.remove(&function.name) {
+            Some(types) => types,
+            None => function.infer_types(&self.constants),
+        };
+        for (i, instruction) in function.instructions().iter().enumerate() {
+            self.operands_are_numbers = types.operands_are_numbers(i, instruction);
             self.generate_instruction(instruction);
         }
+        self.operands_are_numbers = false;
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_endproc").unwrap();
+        }
     }
 
     fn generate_epilogue(&mut self) {
+        // The frame is only torn down on this path; code after `ret` still has it
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_remember_state").unwrap();
+        }
         // Restore callee-saved registers
         writeln!(self.output, "\tldp x27, x28, [sp], #16").unwrap();
         writeln!(self.output, "\tldp x25, x26, [sp], #16").unwrap();
@@ -91,7 +295,18 @@ impl ARM64Generator {
         writeln!(self.output, "\tldp x19, x20, [sp], #16").unwrap();
         writeln!(self.output, "\tmov sp, fp").unwrap();
         writeln!(self.output, "\tldp fp, lr, [sp], #16").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_def_cfa sp, 0").unwrap();
+        }
         writeln!(self.output, "\tret").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_restore_state").unwrap();
+        }
+    }
+
+    /// Whether to emit DWARF line info and frame descriptions.
+    fn debug_info(&self) -> bool {
+        self.options.source_file.is_some()
     }
 
     fn allocate_local(&mut self, name: &str) -> i32 {
@@ -102,21 +317,51 @@ impl ARM64Generator {
     }
 
     fn generate_instruction(&mut self, instruction: &IRInstruction) {
+        if let Some(comment) =
+            self.options
+                .annotation(instruction, &self.constants, &self.functions, "//", "\t")
+        {
+            writeln!(self.output, "{}", comment).unwrap();
+        }
         match instruction {
-            IRInstruction::PushConst(constant) => self.generate_push_const(constant),
+            IRInstruction::PushConst(index) => {
+                let constant = self.constants[*index as usize].clone();
+                self.generate_push_const(&constant)
+            }
             IRInstruction::Load(name) => self.generate_load(name),
             IRInstruction::Store(name) => self.generate_store(name),
             IRInstruction::Binary(op) => self.generate_binary_op(op),
             IRInstruction::Unary(op) => self.generate_unary_op(op),
-            IRInstruction::Call(name, argc) => self.generate_call(name, *argc),
+            IRInstruction::Call(id, argc) => {
+                let name = self.functions[*id as usize].clone();
+                self.generate_call(&name, *argc)
+            }
+            IRInstruction::CallNative(name, argc) => self.generate_call(name, *argc),
+            IRInstruction::LoadUpvalue(_)
+            | IRInstruction::StoreUpvalue(_)
+            | IRInstruction::MakeClosure(..) => closures_unsupported(Target::ARM64),
+            IRInstruction::GetProperty(_) => objects_unsupported(Target::ARM64),
             IRInstruction::Return(has_value) => self.generate_return(*has_value),
             IRInstruction::Jump(label) => self.generate_jump(label),
             IRInstruction::JumpIf(label) => self.generate_jump_if(label),
-            IRInstruction::Label(label) => writeln!(self.output, "{}:", label).unwrap(),
-            IRInstruction::Pop => writeln!(self.output, "\tadd sp, sp, #8").unwrap(),
+            IRInstruction::Label(label) => {
+                let label = self.label(label);
+                writeln!(self.output, "{}:", label).unwrap()
+            }
+            // Operand stack slots are 16 bytes wide so sp stays aligned
+            IRInstruction::Pop => writeln!(self.output, "\tadd sp, sp, #16").unwrap(),
             IRInstruction::Dup => {
                 writeln!(self.output, "\tldr x0, [sp]").unwrap();
-                writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+                writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
+            }
+            IRInstruction::Line(line, _) => {
+                self.line = Some(*line);
+                if self.debug_info() {
+                    writeln!(self.output, "\t.loc 1 {}", line).unwrap();
+                }
+                if self.options.source_map {
+                    self.line_table.mark(&self.output, Some(*line));
+                }
             }
         }
     }
@@ -126,23 +371,36 @@ impl ARM64Generator {
             Constant::Number(n) => {
                 let idx = self.float_literals.len();
                 self.float_literals.push(*n);
-                writeln!(self.output, "\tadrp x0, .LCD{}@PAGE", idx).unwrap();
-                writeln!(self.output, "\tldr d0, [x0, .LCD{}@PAGEOFF]", idx).unwrap();
-                writeln!(self.output, "\tstr d0, [sp, #-8]!").unwrap();
+                let literal = self.literal(&format!("LCD{}", idx));
+                if self.options.pic {
+                    self.generate_literal_address(&literal);
+                    writeln!(self.output, "\tldr d0, [x0]").unwrap();
+                } else {
+                    let (page, offset) = self.page_relocs(&literal);
+                    writeln!(self.output, "\tadrp x0, {}", page).unwrap();
+                    writeln!(self.output, "\tldr d0, [x0, {}]", offset).unwrap();
+                }
+                writeln!(self.output, "\tstr d0, [sp, #-16]!").unwrap();
             }
             Constant::String(s) => {
                 let idx = self.string_literals.len();
                 self.string_literals.push(s.clone());
-                writeln!(self.output, "\tadrp x0, .LC{}@PAGE", idx).unwrap();
-                writeln!(self.output, "\tadd x0, x0, .LC{}@PAGEOFF", idx).unwrap();
-                writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+                let literal = self.literal(&format!("LC{}", idx));
+                self.generate_literal_address(&literal);
+                writeln!(self.output, "\tmov x9, #{:#x}", runtime::STRING_TAG).unwrap();
+                writeln!(self.output, "\torr x0, x0, x9").unwrap();
+                writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
             }
             Constant::Boolean(b) => {
-                writeln!(self.output, "\tmov x0, #{}", if *b { 1 } else { 0 }).unwrap();
-                writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+                writeln!(self.output, "\tmov x0, #{:#x}", runtime::FALSE).unwrap();
+                if *b {
+                    writeln!(self.output, "\torr x0, x0, #1").unwrap();
+                }
+                writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
             }
             Constant::Null => {
-                writeln!(self.output, "\tstr xzr, [sp, #-8]!").unwrap();
+                writeln!(self.output, "\tmov x0, #{:#x}", runtime::NULL).unwrap();
+                writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
             }
         }
     }
@@ -150,136 +408,373 @@ impl ARM64Generator {
     fn generate_load(&mut self, name: &str) {
         if let Some(&offset) = self.local_offsets.get(name) {
             writeln!(self.output, "\tldr x0, [fp, #{}]", offset).unwrap();
-            writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+            writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
         } else {
-            panic!("Undefined variable: {}", name);
+            globals_unsupported(Target::ARM64, name, self.line);
         }
     }
 
     fn generate_store(&mut self, name: &str) {
-        let offset = self.local_offsets.get(name).cloned().unwrap_or_else(|| {
-            let offset = self.allocate_local(name);
-            offset
-        });
-        writeln!(self.output, "\tldr x0, [sp], #8").unwrap();
+        let offset = self
+            .local_offsets
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| self.allocate_local(name));
+        writeln!(self.output, "\tldr x0, [sp], #16").unwrap();
         writeln!(self.output, "\tstr x0, [fp, #{}]", offset).unwrap();
     }
 
     fn generate_binary_op(&mut self, op: &BinaryOp) {
-        writeln!(self.output, "\tldr x1, [sp], #8").unwrap(); // right operand
-        writeln!(self.output, "\tldr x0, [sp], #8").unwrap(); // left operand
-
-        match op {
-            BinaryOp::Add => writeln!(self.output, "\tadd x0, x0, x1").unwrap(),
-            BinaryOp::Sub => writeln!(self.output, "\tsub x0, x0, x1").unwrap(),
-            BinaryOp::Mul => writeln!(self.output, "\tmul x0, x0, x1").unwrap(),
-            BinaryOp::Div => {
-                writeln!(self.output, "\tsdiv x0, x0, x1").unwrap();
-            }
-            BinaryOp::Eq => {
-                writeln!(self.output, "\tcmp x0, x1").unwrap();
-                writeln!(self.output, "\tcset x0, eq").unwrap();
-            }
-            BinaryOp::Lt => {
-                writeln!(self.output, "\tcmp x0, x1").unwrap();
-                writeln!(self.output, "\tcset x0, lt").unwrap();
-            }
-            BinaryOp::Gt => {
-                writeln!(self.output, "\tcmp x0, x1").unwrap();
-                writeln!(self.output, "\tcset x0, gt").unwrap();
-            }
-            BinaryOp::Le => {
-                writeln!(self.output, "\tcmp x0, x1").unwrap();
-                writeln!(self.output, "\tcset x0, le").unwrap();
-            }
-            BinaryOp::Ge => {
-                writeln!(self.output, "\tcmp x0, x1").unwrap();
-                writeln!(self.output, "\tcset x0, ge").unwrap();
-            }
-            BinaryOp::And => writeln!(self.output, "\tand x0, x0, x1").unwrap(),
-            BinaryOp::Or => writeln!(self.output, "\torr x0, x0, x1").unwrap(),
+        writeln!(self.output, "\tldr x1, [sp], #16").unwrap(); // right operand
+        writeln!(self.output, "\tldr x0, [sp], #16").unwrap(); // left operand
+
+        // There is no floating-point remainder instruction, so `%` always calls fmod
+        if matches!(op, BinaryOp::And | BinaryOp::Or | BinaryOp::Mod) {
+            self.generate_runtime_call(runtime::binary_function(op));
+        } else {
+            self.generate_dispatch(&["x0", "x1"], runtime::binary_function(op), |g| {
+                // Both operands are numbers
+                writeln!(g.output, "\tfmov d0, x0").unwrap();
+                writeln!(g.output, "\tfmov d1, x1").unwrap();
+                let cond = match op {
+                    BinaryOp::Add => Err("fadd"),
+                    BinaryOp::Sub => Err("fsub"),
+                    BinaryOp::Mul => Err("fmul"),
+                    BinaryOp::Div => Err("fdiv"),
+                    // These conditions are all false when fcmp reports unordered (NaN)
+                    BinaryOp::Eq | BinaryOp::StrictEq => Ok("eq"),
+                    BinaryOp::Lt => Ok("mi"),
+                    BinaryOp::Gt => Ok("gt"),
+                    BinaryOp::Le => Ok("ls"),
+                    BinaryOp::Ge => Ok("ge"),
+                    // ...and this one is true
+                    BinaryOp::Neq => Ok("ne"),
+                    BinaryOp::And | BinaryOp::Or | BinaryOp::Mod => unreachable!(),
+                };
+                match cond {
+                    Ok(cond) => {
+                        writeln!(g.output, "\tfcmp d0, d1").unwrap();
+                        writeln!(g.output, "\tcset x0, {}", cond).unwrap();
+                        g.generate_bool_from_x0();
+                    }
+                    Err(cmd) => {
+                        // Operations on canonical NaNs only produce canonical NaNs
+                        writeln!(g.output, "\t{} d0, d0, d1", cmd).unwrap();
+                        writeln!(g.output, "\tfmov x0, d0").unwrap();
+                    }
+                }
+            });
         }
-        writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+        writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
+    }
+
+    /// Set `dst` to 1 if the double in `src` is truthy (neither +-0.0 nor NaN), else 0.
+    fn generate_truthy(&mut self, src: &str, dst: &str) {
+        writeln!(self.output, "\tfcmp {}, #0.0", src).unwrap();
+        writeln!(self.output, "\tcset {}, ne", dst).unwrap();
+        writeln!(self.output, "\tcsel {}, {}, xzr, vc", dst, dst).unwrap();
+    }
+
+    /// Turn the flag 1/0 in x0 into a boolean value.
+    fn generate_bool_from_x0(&mut self) {
+        writeln!(self.output, "\tmov x9, #{:#x}", runtime::FALSE).unwrap();
+        writeln!(self.output, "\torr x0, x0, x9").unwrap();
     }
 
     fn generate_unary_op(&mut self, op: &UnaryOp) {
-        writeln!(self.output, "\tldr x0, [sp], #8").unwrap();
-        match op {
-            UnaryOp::Neg => {
-                writeln!(self.output, "\tneg x0, x0").unwrap();
-            }
-            UnaryOp::Not => {
-                writeln!(self.output, "\tcmp x0, #0").unwrap();
-                writeln!(self.output, "\tcset x0, eq").unwrap();
+        writeln!(self.output, "\tldr x0, [sp], #16").unwrap();
+        self.generate_dispatch(&["x0"], runtime::unary_function(op), |g| {
+            writeln!(g.output, "\tfmov d0, x0").unwrap();
+            match op {
+                UnaryOp::Neg => {
+                    writeln!(g.output, "\tfneg d0, d0").unwrap();
+                    writeln!(g.output, "\tfmov x0, d0").unwrap();
+                }
+                UnaryOp::Not => {
+                    g.generate_truthy("d0", "x0");
+                    writeln!(g.output, "\teor x0, x0, #1").unwrap();
+                    g.generate_bool_from_x0();
+                }
             }
+        });
+        writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
+    }
+
+    /// Fresh assembler-local label within the current function.
+    fn local_label(&mut self) -> String {
+        self.next_local_label += 1;
+        let name = format!("L{}.{}", self.current_function, self.next_local_label);
+        self.local(&name)
+    }
+
+    /// Leave `fast(operands)` in x0 when all `operands` are numbers, and the
+    /// result of the runtime function `slow` otherwise. The operands are
+    /// x0 (and x1), which are also the runtime's argument registers.
+    /// Operands known to be numbers skip the checks.
+    fn generate_dispatch(&mut self, operands: &[&str], slow: &str, fast: impl FnOnce(&mut Self)) {
+        if self.operands_are_numbers {
+            fast(self);
+            return;
+        }
+        let slow_path = self.local_label();
+        let done = self.local_label();
+        writeln!(self.output, "\tmov x9, #{:#x}", runtime::MAX_NUMBER).unwrap();
+        for operand in operands {
+            writeln!(self.output, "\tcmp {}, x9", operand).unwrap();
+            writeln!(self.output, "\tb.hi {}", slow_path).unwrap();
         }
-        writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+        fast(self);
+        writeln!(self.output, "\tb {}", done).unwrap();
+        writeln!(self.output, "{}:", slow_path).unwrap();
+        self.generate_runtime_call(slow);
+        writeln!(self.output, "{}:", done).unwrap();
+    }
+
+    /// Call a runtime function on the values in x0 (and x1), leaving its
+    /// result in x0.
+    fn generate_runtime_call(&mut self, name: &str) {
+        let symbol = self.symbol(name);
+        writeln!(self.output, "\tbl {}", symbol).unwrap();
     }
 
     fn generate_call(&mut self, name: &str, argc: u16) {
-        // Set up arguments
-        for i in (0..argc).rev() {
-            let reg = match i {
-                0 => "x0",
-                1 => "x1",
-                2 => "x2",
-                3 => "x3",
-                4 => "x4",
-                5 => "x5",
-                6 => "x6",
-                7 => "x7",
-                _ => panic!("Too many arguments in call to {}", name),
+        if runtime::is_builtin(name) {
+            self.generate_print(argc);
+            return;
+        }
+        if let Some(function) = self.externs.iter().find(|f| f.name == name) {
+            let function = function.clone();
+            self.generate_extern_call(&function, argc);
+            return;
+        }
+
+        // Arguments sit on the operand stack, the last one on top. The first
+        // eight go in registers, the rest in 8-byte stack slots below them
+        let argument_offset = |i: usize| (argc as usize - 1 - i) * 16;
+        for (i, reg) in ARGUMENT_REGISTERS.iter().enumerate().take(argc as usize) {
+            writeln!(self.output, "\tldr {}, [sp, #{}]", reg, argument_offset(i)).unwrap();
+        }
+        let stack_arguments = (argc as usize).saturating_sub(ARGUMENT_REGISTERS.len());
+        let reserved = (stack_arguments * 8).next_multiple_of(16);
+        if reserved > 0 {
+            writeln!(self.output, "\tsub sp, sp, #{}", reserved).unwrap();
+        }
+        for j in 0..stack_arguments {
+            let offset = reserved + argument_offset(ARGUMENT_REGISTERS.len() + j);
+            writeln!(self.output, "\tldr x9, [sp, #{}]", offset).unwrap();
+            writeln!(self.output, "\tstr x9, [sp, #{}]", j * 8).unwrap();
+        }
+
+        let symbol = self.symbol(&self.options.function_name(name));
+        writeln!(self.output, "\tbl {}", symbol).unwrap();
+
+        // Drop the stack arguments along with the operand stack slots
+        let popped = reserved + argc as usize * 16;
+        if popped > 0 {
+            writeln!(self.output, "\tadd sp, sp, #{}", popped).unwrap();
+        }
+        writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
+    }
+
+    /// Call a C function. Each argument is converted in place on the
+    /// operand stack, then integers and pointers go in x0-x7, doubles in
+    /// d0-d7 and the rest in 8-byte stack slots. Apple passes all variadic
+    /// arguments on the stack.
+    fn generate_extern_call(&mut self, function: &ExternFunction, argc: u16) {
+        let argc = argc as usize;
+        let argument_offset = |i: usize| (argc - 1 - i) * 16;
+        let types: Vec<Option<CType>> =
+            (0..argc).map(|i| function.params.get(i).copied()).collect();
+        for (i, &ty) in types.iter().enumerate() {
+            writeln!(self.output, "\tldr x0, [sp, #{}]", argument_offset(i)).unwrap();
+            self.generate_runtime_call(runtime::to_c_function(ty));
+            if ty == Some(CType::Double) {
+                writeln!(self.output, "\tfmov x0, d0").unwrap();
+            }
+            writeln!(self.output, "\tstr x0, [sp, #{}]", argument_offset(i)).unwrap();
+        }
+
+        let (mut next_register, mut next_float_register) = (0, 0);
+        let mut stack_arguments = Vec::new();
+        for (i, &ty) in types.iter().enumerate() {
+            let register = if ty.is_none()
+                && self.options.target.calling_convention() == CallingConvention::DarwinAarch64
+            {
+                None
+            } else if ty == Some(CType::Double) {
+                next_float_register += 1;
+                FLOAT_ARGUMENT_REGISTERS.get(next_float_register - 1)
+            } else {
+                next_register += 1;
+                ARGUMENT_REGISTERS.get(next_register - 1)
             };
-            writeln!(self.output, "\tldr {}, [sp], #8", reg).unwrap();
+            match register {
+                Some(register) => {
+                    writeln!(
+                        self.output,
+                        "\tldr {}, [sp, #{}]",
+                        register,
+                        argument_offset(i)
+                    )
+                    .unwrap();
+                }
+                None => stack_arguments.push(argument_offset(i)),
+            }
+        }
+        let reserved = (stack_arguments.len() * 8).next_multiple_of(16);
+        if reserved > 0 {
+            writeln!(self.output, "\tsub sp, sp, #{}", reserved).unwrap();
+        }
+        for (j, offset) in stack_arguments.iter().enumerate() {
+            writeln!(self.output, "\tldr x9, [sp, #{}]", reserved + offset).unwrap();
+            writeln!(self.output, "\tstr x9, [sp, #{}]", j * 8).unwrap();
+        }
+
+        // The linker routes calls into shared libraries through the PLT
+        self.generate_runtime_call(&function.name);
+        let popped = reserved + argc * 16;
+        if popped > 0 {
+            writeln!(self.output, "\tadd sp, sp, #{}", popped).unwrap();
         }
 
-        writeln!(self.output, "\tbl _{}", name).unwrap();
-        writeln!(self.output, "\tstr x0, [sp, #-8]!").unwrap();
+        // Results come back in w0, x0 or d0, where the conversions take them
+        match runtime::from_c_function(function.returns) {
+            Some(convert) => self.generate_runtime_call(convert),
+            None => writeln!(self.output, "\tmov x0, #{:#x}", runtime::UNDEFINED).unwrap(),
+        }
+        writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
+    }
+
+    /// `print(a, b, ...)` prints each argument through the runtime, separated
+    /// by spaces and terminated by a newline.
+    fn generate_print(&mut self, argc: u16) {
+        if argc == 0 {
+            let symbol = self.symbol("jsrt_print_newline");
+            writeln!(self.output, "\tbl {}", symbol).unwrap();
+        }
+        for i in 0..argc {
+            // The first argument was pushed first, so it sits deepest
+            let offset = (argc - 1 - i) as u32 * 16;
+            let separator = if i + 1 == argc { b'\n' } else { b' ' };
+            writeln!(self.output, "\tldr x0, [sp, #{}]", offset).unwrap();
+            writeln!(self.output, "\tmov w1, #{}", separator).unwrap();
+            let symbol = self.symbol("jsrt_print_value");
+            writeln!(self.output, "\tbl {}", symbol).unwrap();
+        }
+        if argc > 0 {
+            writeln!(self.output, "\tadd sp, sp, #{}", argc as u32 * 16).unwrap();
+        }
+
+        writeln!(self.output, "\tmov x0, #{:#x}", runtime::UNDEFINED).unwrap();
+        writeln!(self.output, "\tstr x0, [sp, #-16]!").unwrap();
     }
 
     fn generate_return(&mut self, has_value: bool) {
         if has_value {
-            writeln!(self.output, "\tldr x0, [sp], #8").unwrap();
+            writeln!(self.output, "\tldr x0, [sp], #16").unwrap();
+        } else {
+            writeln!(self.output, "\tmov x0, #{:#x}", runtime::UNDEFINED).unwrap();
         }
         self.generate_epilogue();
     }
 
     fn generate_jump(&mut self, label: &str) {
+        let label = self.label(label);
         writeln!(self.output, "\tb {}", label).unwrap();
     }
 
     fn generate_jump_if(&mut self, label: &str) {
-        writeln!(self.output, "\tldr x0, [sp], #8").unwrap();
-        writeln!(self.output, "\tcmp x0, #0").unwrap();
-        writeln!(self.output, "\tb.ne {}", label).unwrap();
+        let label = self.label(label);
+        let boxed = self.local_label();
+        writeln!(self.output, "\tldr x0, [sp], #16").unwrap();
+        writeln!(self.output, "\tmov x9, #{:#x}", runtime::MAX_NUMBER).unwrap();
+        writeln!(self.output, "\tcmp x0, x9").unwrap();
+        writeln!(self.output, "\tb.hi {}", boxed).unwrap();
+        writeln!(self.output, "\tfmov d0, x0").unwrap();
+        self.generate_truthy("d0", "x0");
+        writeln!(self.output, "\tcbnz x0, {}", label).unwrap();
+        let done = self.local_label();
+        writeln!(self.output, "\tb {}", done).unwrap();
+        writeln!(self.output, "{}:", boxed).unwrap();
+        self.generate_runtime_call("jsrt_truthy");
+        writeln!(self.output, "\tcbnz x0, {}", label).unwrap();
+        writeln!(self.output, "{}:", done).unwrap();
     }
 }
 
 impl CodeGenerator for ARM64Generator {
-    fn generate(&mut self, module: IRModule) -> String {
-        // Data section for constants
-        writeln!(self.output, "\t.section __DATA,__data").unwrap();
+    fn begin_module(&mut self, module: &IRModule, out: &mut dyn io::Write) -> io::Result<()> {
+        if let Some(file) = self.options.file_directive() {
+            writeln!(self.output, "{}", file).unwrap();
+        }
+        writeln!(self.output, "\t{}", self.options.target.text_section()).unwrap();
+        self.externs = module.externs.clone();
+        self.functions = module.function_names();
+        self.constants = module.constants.clone();
+        // Inferring types is the work on each function that needs no state
+        // of the generator, so it runs for all of them at once
+        let types = parallel::map(&module.functions, |function| {
+            function.infer_types(&module.constants)
+        });
+        let names = module
+            .functions
+            .iter()
+            .map(|function| function.name.clone());
+        self.types = names.zip(types).collect();
+        self.flush(out)
+    }
+
+    fn emit_function(&mut self, function: &IRFunction, out: &mut dyn io::Write) -> io::Result<()> {
+        self.generate_function(function);
+        if self.options.source_map {
+            self.line_table.mark(&self.output, None);
+        }
+        if self.options.c_abi {
+            if function.name != "main" {
+                self.generate_c_wrapper(function);
+            }
+        } else if function.name == "main" {
+            self.generate_entry_point();
+        }
+        self.flush(out)
+    }
+
+    /// Emit the literals the functions collected, after their code.
+    fn finish(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        writeln!(self.output, "\t{}", self.options.target.data_section()).unwrap();
 
         // Add string literals
         for (i, s) in self.string_literals.iter().enumerate() {
-            writeln!(self.output, ".LC{}:", i).unwrap();
-            writeln!(self.output, "\t.asciz \"{}\"", s).unwrap();
+            writeln!(self.output, "{}:", self.literal(&format!("LC{}", i))).unwrap();
+            writeln!(self.output, "\t.asciz {}", assembler::quote(s)).unwrap();
         }
 
-        // Add float literals
+        // Add float literals, aligned for 8-byte loads after the strings
+        if !self.float_literals.is_empty() {
+            writeln!(self.output, "\t.p2align 3").unwrap();
+        }
         for (i, f) in self.float_literals.iter().enumerate() {
-            writeln!(self.output, ".LCD{}:", i).unwrap();
+            writeln!(self.output, "{}:", self.literal(&format!("LCD{}", i))).unwrap();
             writeln!(self.output, "\t.double {}", f).unwrap();
         }
 
-        // Text section for code
-        writeln!(self.output, "\t.section __TEXT,__text").unwrap();
-
-        // Generate code for each function
-        for function in module.functions {
-            self.generate_function(&function);
+        // Mark the stack non-executable for GNU ld
+        if self.options.target.os == TargetOs::Linux {
+            writeln!(self.output, "\t.section .note.GNU-stack,\"\",%progbits").unwrap();
         }
+        self.flush(out)
+    }
 
-        self.output.clone()
+    fn line_table(&self) -> Option<&LineTable> {
+        self.options.source_map.then_some(&self.line_table)
+    }
+}
+
+impl ARM64Generator {
+    /// Write the assembly generated so far to `out`.
+    fn flush(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        let assembly = std::mem::take(&mut self.output);
+        self.line_table.flushed(&assembly);
+        out.write_all(assembly.as_bytes())
     }
 }
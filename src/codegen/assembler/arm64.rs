@@ -0,0 +1,641 @@
+use super::{Assembler, FixupKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// 32-bit `w` register.
+    W,
+    /// 64-bit `x` register, including `sp`.
+    X,
+    /// 64-bit floating-point `d` register.
+    D,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Register {
+    number: u32,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Register(Register),
+    Immediate(i64),
+    Float(f64),
+    /// `[base, #offset]`, with `!` for pre-indexing.
+    Memory {
+        base: u32,
+        offset: i64,
+        pre_index: bool,
+    },
+    /// `[base, :lo12:symbol]` or `[base, symbol@PAGEOFF]`.
+    MemoryPageOffset {
+        base: u32,
+        symbol: String,
+    },
+    /// `[base, :got_lo12:symbol]` or `[base, symbol@GOTPAGEOFF]`.
+    MemoryGotOffset {
+        base: u32,
+        symbol: String,
+    },
+    /// `:lo12:symbol` or `symbol@PAGEOFF`.
+    PageOffset(String),
+    /// `:got:symbol` or `symbol@GOTPAGE`, the page of a GOT entry for `adrp`.
+    GotPage(String),
+    /// A label, or `symbol@PAGE` for `adrp`.
+    Symbol(String),
+}
+
+fn register(name: &str) -> Option<Register> {
+    let (kind, number) = match name {
+        "sp" | "xzr" => (Kind::X, 31),
+        "wzr" => (Kind::W, 31),
+        "fp" => (Kind::X, 29),
+        "lr" => (Kind::X, 30),
+        _ => {
+            let kind = match name.chars().next()? {
+                'x' => Kind::X,
+                'w' => Kind::W,
+                'd' => Kind::D,
+                _ => return None,
+            };
+            let number: u32 = name[1..].parse().ok()?;
+            if number > 31 || (number == 31 && kind != Kind::D) {
+                return None;
+            }
+            (kind, number)
+        }
+    };
+    Some(Register { number, kind })
+}
+
+fn parse_immediate(text: &str) -> Option<i64> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()? as i64,
+        None => digits.parse::<i64>().ok()?,
+    };
+    Some(if negative { -value } else { value })
+}
+
+fn page_offset_symbol(text: &str) -> Option<&str> {
+    text.strip_prefix(":lo12:")
+        .or_else(|| text.strip_suffix("@PAGEOFF"))
+}
+
+fn got_offset_symbol(text: &str) -> Option<&str> {
+    text.strip_prefix(":got_lo12:")
+        .or_else(|| text.strip_suffix("@GOTPAGEOFF"))
+}
+
+fn parse_operand(text: &str) -> Operand {
+    if let Some(value) = text.strip_prefix('#') {
+        if let Some(value) = parse_immediate(value) {
+            return Operand::Immediate(value);
+        }
+        let value: f64 = value
+            .parse()
+            .unwrap_or_else(|_| panic!("Malformed immediate: {}", text));
+        return Operand::Float(value);
+    }
+    if let Some(inner) = text.strip_prefix('[') {
+        let (inner, pre_index) = match inner.strip_suffix("]!") {
+            Some(inner) => (inner, true),
+            None => (
+                inner
+                    .strip_suffix(']')
+                    .expect("Unterminated memory operand"),
+                false,
+            ),
+        };
+        let mut parts = inner.split(',').map(str::trim);
+        let base = match parts.next().and_then(register) {
+            Some(Register {
+                number,
+                kind: Kind::X,
+            }) => number,
+            _ => panic!("Unsupported memory operand: {}", text),
+        };
+        let offset = match parts.next() {
+            None => 0,
+            Some(part) => {
+                if let Some(symbol) = page_offset_symbol(part) {
+                    return Operand::MemoryPageOffset {
+                        base,
+                        symbol: symbol.to_string(),
+                    };
+                }
+                if let Some(symbol) = got_offset_symbol(part) {
+                    return Operand::MemoryGotOffset {
+                        base,
+                        symbol: symbol.to_string(),
+                    };
+                }
+                part.strip_prefix('#')
+                    .and_then(parse_immediate)
+                    .unwrap_or_else(|| panic!("Malformed memory offset: {}", text))
+            }
+        };
+        return Operand::Memory {
+            base,
+            offset,
+            pre_index,
+        };
+    }
+    if let Some(register) = register(text) {
+        return Operand::Register(register);
+    }
+    if let Some(symbol) = page_offset_symbol(text) {
+        return Operand::PageOffset(symbol.to_string());
+    }
+    if let Some(symbol) = text
+        .strip_prefix(":got:")
+        .or_else(|| text.strip_suffix("@GOTPAGE"))
+    {
+        return Operand::GotPage(symbol.to_string());
+    }
+    let symbol = text.strip_suffix("@PAGE").unwrap_or(text);
+    Operand::Symbol(symbol.to_string())
+}
+
+fn condition_code(name: &str) -> Option<u32> {
+    Some(match name {
+        "eq" => 0x0,
+        "ne" => 0x1,
+        "cs" | "hs" => 0x2,
+        "cc" | "lo" => 0x3,
+        "mi" => 0x4,
+        "pl" => 0x5,
+        "vs" => 0x6,
+        "vc" => 0x7,
+        "hi" => 0x8,
+        "ls" => 0x9,
+        "ge" => 0xA,
+        "lt" => 0xB,
+        "gt" => 0xC,
+        "le" => 0xD,
+        "al" => 0xE,
+        _ => return None,
+    })
+}
+
+/// The 8-bit `fmov` immediate that expands to `value`, if there is one.
+fn float_immediate(value: f64) -> Option<u32> {
+    (0..256u64)
+        .find(|&imm8| {
+            let b = (imm8 >> 6) & 1;
+            let expanded = ((imm8 >> 7) << 63)
+                | ((b ^ 1) << 62)
+                | (if b == 1 { 0xFF << 54 } else { 0 })
+                | (((imm8 >> 4) & 3) << 52)
+                | ((imm8 & 0xF) << 48);
+            expanded == value.to_bits()
+        })
+        .map(|imm8| imm8 as u32)
+}
+
+/// The `N:immr:imms` fields of a logical immediate, if `value` is a
+/// replicated, rotated run of ones.
+fn logical_immediate(value: u64, wide: bool) -> Option<u32> {
+    let value = if wide {
+        value
+    } else {
+        (value & 0xFFFF_FFFF) | (value << 32)
+    };
+    if value == 0 || value == u64::MAX {
+        return None;
+    }
+    let mut size = 64;
+    while size > 2 {
+        let half = size / 2;
+        let mask = (1u64 << half) - 1;
+        if (value & mask) != ((value >> half) & mask) {
+            break;
+        }
+        size = half;
+    }
+    let mask = if size == 64 {
+        u64::MAX
+    } else {
+        (1u64 << size) - 1
+    };
+    let element = value & mask;
+    let ones = element.count_ones();
+    // Find the rotation that turns the element into a run of low ones
+    for rotation in 0..size {
+        let rotated = if rotation == 0 {
+            element
+        } else {
+            ((element >> rotation) | (element << (size - rotation))) & mask
+        };
+        if rotated == (1u64 << ones) - 1 {
+            let immr = (size - rotation) % size;
+            let imms = ((!(size * 2 - 1)) & 0x3F) | (ones - 1) as u64;
+            let n = u32::from(size == 64);
+            return Some((n << 12) | ((immr as u32) << 6) | imms as u32);
+        }
+    }
+    None
+}
+
+fn emit(asm: &mut Assembler, word: u32) {
+    asm.emit(&word.to_le_bytes());
+}
+
+fn sf(register: &Register) -> u32 {
+    u32::from(register.kind != Kind::W) << 31
+}
+
+/// Patch the offset field of a local branch, `delta` in instructions.
+pub(super) fn patch_branch(text: &mut [u8], offset: u64, kind: FixupKind, delta: i64) {
+    let offset = offset as usize;
+    let mut word = u32::from_le_bytes(text[offset..offset + 4].try_into().unwrap());
+    word |= match kind {
+        FixupKind::Arm64Branch26 => (delta as u32) & 0x03FF_FFFF,
+        _ => ((delta as u32) & 0x7FFFF) << 5,
+    };
+    text[offset..offset + 4].copy_from_slice(&word.to_le_bytes());
+}
+
+/// Patch the instruction at `offset` in an image to refer to `target`, an
+/// offset into the same image. Page-relative references only hold while the
+/// image is loaded at a page-aligned address. An image has no GOT, so GOT
+/// loads are relaxed into computing the address of `target` directly.
+pub(super) fn patch_image_reference(bytes: &mut [u8], offset: u64, kind: FixupKind, target: u64) {
+    if kind == FixupKind::Arm64Call {
+        let delta = (target as i64 - offset as i64) >> 2;
+        return patch_branch(bytes, offset, FixupKind::Arm64Branch26, delta);
+    }
+    let offset = offset as usize;
+    let mut word = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+    if kind == FixupKind::Arm64GotPageOffsetLoad64 {
+        // ldr xt, [xn, entry] becomes add xt, xn, #lo12 with the same registers
+        word = 0x9100_0000 | (word & 0x3FF);
+    }
+    word |= match kind {
+        FixupKind::Arm64Page | FixupKind::Arm64GotPage => {
+            let pages = (target >> 12) as i64 - (offset >> 12) as i64;
+            let low = (pages as u32) & 0x3;
+            let high = ((pages >> 2) as u32) & 0x7FFFF;
+            (low << 29) | (high << 5)
+        }
+        FixupKind::Arm64PageOffset | FixupKind::Arm64GotPageOffsetLoad64 => {
+            ((target & 0xFFF) as u32) << 10
+        }
+        FixupKind::Arm64PageOffsetLoad64 => (((target & 0xFFF) >> 3) as u32) << 10,
+        _ => unreachable!("{:?} is not an ARM64 symbol reference", kind),
+    };
+    bytes[offset..offset + 4].copy_from_slice(&word.to_le_bytes());
+}
+
+fn emit_branch(asm: &mut Assembler, word: u32, target: &Operand, kind: FixupKind) {
+    let target = match target {
+        Operand::Symbol(symbol) => symbol,
+        _ => panic!("Unsupported branch target: {:?}", target),
+    };
+    let start = asm.position();
+    asm.fixup(start, 0, target, kind, 0);
+    emit(asm, word);
+}
+
+/// Encode a load or store of `rt` through a memory operand, with an optional
+/// post-index immediate.
+fn emit_load_store(
+    asm: &mut Assembler,
+    load: bool,
+    rt: &Register,
+    memory: &Operand,
+    post: Option<i64>,
+) {
+    // size:V bits of the unsigned-offset form and the access size in bytes
+    let (base_word, scale) = match rt.kind {
+        Kind::X => (0xF900_0000, 8),
+        Kind::D => (0xFD00_0000, 8),
+        Kind::W => (0xB900_0000, 4),
+    };
+    let load_bit = u32::from(load) << 22;
+    match memory {
+        Operand::MemoryPageOffset { base, symbol } => {
+            let start = asm.position();
+            let kind = match scale {
+                8 => FixupKind::Arm64PageOffsetLoad64,
+                _ => panic!("Unsupported page-offset access size"),
+            };
+            asm.fixup(start, 0, symbol, kind, 0);
+            emit(asm, base_word | load_bit | (base << 5) | rt.number);
+        }
+        Operand::MemoryGotOffset { base, symbol } => {
+            // GOT entries are addresses, loaded into an X register
+            if !load || rt.kind != Kind::X {
+                panic!("GOT entries can only be loaded into X registers");
+            }
+            let start = asm.position();
+            asm.fixup(start, 0, symbol, FixupKind::Arm64GotPageOffsetLoad64, 0);
+            emit(asm, base_word | load_bit | (base << 5) | rt.number);
+        }
+        Operand::Memory {
+            base,
+            offset,
+            pre_index,
+        } => {
+            // The unscaled and indexed forms share the top bits of the unsigned form
+            let unscaled = base_word & !0x0100_0000;
+            let (offset, index_bits) = match (post, pre_index) {
+                (Some(post), false) if *offset == 0 => (post, Some(0x400)),
+                (None, true) => (*offset, Some(0xC00)),
+                (None, false) => (*offset, None),
+                _ => panic!("Unsupported addressing mode"),
+            };
+            let word = match index_bits {
+                Some(bits) => {
+                    unscaled | load_bit | (((offset as u32) & 0x1FF) << 12) | bits | (base << 5)
+                }
+                None if offset >= 0 && offset % scale == 0 && offset / scale < 4096 => {
+                    base_word | load_bit | (((offset / scale) as u32) << 10) | (base << 5)
+                }
+                None if (-256..256).contains(&offset) => {
+                    unscaled | load_bit | (((offset as u32) & 0x1FF) << 12) | (base << 5)
+                }
+                None => panic!("Memory offset out of range: {}", offset),
+            };
+            emit(asm, word | rt.number);
+        }
+        _ => panic!("Unsupported memory operand: {:?}", memory),
+    }
+}
+
+fn emit_pair(
+    asm: &mut Assembler,
+    load: bool,
+    rt: &Register,
+    rt2: &Register,
+    memory: &Operand,
+    post: Option<i64>,
+) {
+    let (base, offset, mode) = match (memory, post) {
+        (
+            Operand::Memory {
+                base,
+                offset: 0,
+                pre_index: false,
+            },
+            Some(post),
+        ) => (base, post, 0x0080_0000),
+        (
+            Operand::Memory {
+                base,
+                offset,
+                pre_index: true,
+            },
+            None,
+        ) => (base, *offset, 0x0180_0000),
+        (
+            Operand::Memory {
+                base,
+                offset,
+                pre_index: false,
+            },
+            None,
+        ) => (base, *offset, 0x0100_0000),
+        _ => panic!("Unsupported pair addressing: {:?}", memory),
+    };
+    let word = 0xA800_0000
+        | mode
+        | (u32::from(load) << 22)
+        | ((((offset / 8) as u32) & 0x7F) << 15)
+        | (rt2.number << 10)
+        | (base << 5)
+        | rt.number;
+    emit(asm, word);
+}
+
+/// `add`/`sub` with a 12-bit immediate, optionally shifted left by 12.
+fn emit_add_immediate(
+    asm: &mut Assembler,
+    subtract: bool,
+    rd: &Register,
+    rn: &Register,
+    value: i64,
+) {
+    let (subtract, value) = if value < 0 {
+        (!subtract, -value)
+    } else {
+        (subtract, value)
+    };
+    let (shift, value) = if value < 4096 {
+        (0, value)
+    } else if value % 4096 == 0 && value / 4096 < 4096 {
+        (1 << 22, value / 4096)
+    } else {
+        panic!("Immediate out of range for add/sub: {}", value)
+    };
+    let word = sf(rd)
+        | 0x1100_0000
+        | (u32::from(subtract) << 30)
+        | shift
+        | ((value as u32) << 10)
+        | (rn.number << 5)
+        | rd.number;
+    emit(asm, word);
+}
+
+/// `movz` of a value with a single non-zero 16-bit chunk, `movn` of one
+/// whose complement has one.
+fn emit_move_immediate(asm: &mut Assembler, rd: &Register, value: i64) {
+    let width = if rd.kind == Kind::W { 32 } else { 64 };
+    let value = value as u64 & if width == 32 { 0xFFFF_FFFF } else { u64::MAX };
+    let inverted = !value & if width == 32 { 0xFFFF_FFFF } else { u64::MAX };
+    for (opcode, candidate) in [(0x5280_0000, value), (0x1280_0000, inverted)] {
+        for hw in 0..width / 16 {
+            if candidate & !(0xFFFF << (hw * 16)) == 0 {
+                let imm16 = ((candidate >> (hw * 16)) & 0xFFFF) as u32;
+                emit(
+                    asm,
+                    sf(rd) | opcode | ((hw as u32) << 21) | (imm16 << 5) | rd.number,
+                );
+                return;
+            }
+        }
+    }
+    if let Some(bits) = logical_immediate(value, width == 64) {
+        // orr rd, zr, #imm
+        emit(
+            asm,
+            sf(rd) | 0x3200_0000 | (bits << 10) | (31 << 5) | rd.number,
+        );
+        return;
+    }
+    panic!("Immediate cannot be moved in one instruction: {:#x}", value);
+}
+
+/// Encode one ARM64 instruction.
+pub(super) fn encode(asm: &mut Assembler, mnemonic: &str, operands: &[&str]) {
+    let operands: Vec<Operand> = operands.iter().map(|op| parse_operand(op)).collect();
+    let unsupported =
+        || -> ! { panic!("Unsupported ARM64 instruction: {} {:?}", mnemonic, operands) };
+    use Operand::{Float, GotPage, Immediate, PageOffset, Register as Reg, Symbol};
+
+    match (mnemonic, operands.as_slice()) {
+        ("ret", []) => emit(asm, 0xD65F_03C0),
+        ("b", [target]) => emit_branch(asm, 0x1400_0000, target, FixupKind::Arm64Branch26),
+        ("bl", [target]) => emit_branch(asm, 0x9400_0000, target, FixupKind::Arm64Call),
+        ("cbz" | "cbnz", [Reg(rt), target]) => {
+            let word = sf(rt) | 0x3400_0000 | (u32::from(mnemonic == "cbnz") << 24) | rt.number;
+            emit_branch(asm, word, target, FixupKind::Arm64Branch19);
+        }
+        (m, [target]) if m.starts_with("b.") => {
+            let cond = condition_code(&m[2..]).unwrap_or_else(|| unsupported());
+            emit_branch(asm, 0x5400_0000 | cond, target, FixupKind::Arm64Branch19);
+        }
+        ("adrp", [Reg(rd), Symbol(symbol)]) => {
+            let start = asm.position();
+            asm.fixup(start, 0, symbol, FixupKind::Arm64Page, 0);
+            emit(asm, 0x9000_0000 | rd.number);
+        }
+        ("adrp", [Reg(rd), GotPage(symbol)]) => {
+            let start = asm.position();
+            asm.fixup(start, 0, symbol, FixupKind::Arm64GotPage, 0);
+            emit(asm, 0x9000_0000 | rd.number);
+        }
+
+        ("stp" | "ldp", [Reg(rt), Reg(rt2), memory]) => {
+            emit_pair(asm, mnemonic == "ldp", rt, rt2, memory, None)
+        }
+        ("stp" | "ldp", [Reg(rt), Reg(rt2), memory, Immediate(post)]) => {
+            emit_pair(asm, mnemonic == "ldp", rt, rt2, memory, Some(*post))
+        }
+        ("str" | "ldr", [Reg(rt), memory]) => {
+            emit_load_store(asm, mnemonic == "ldr", rt, memory, None)
+        }
+        ("str" | "ldr", [Reg(rt), memory, Immediate(post)]) => {
+            emit_load_store(asm, mnemonic == "ldr", rt, memory, Some(*post))
+        }
+
+        // mov to or from sp is an alias of add #0
+        ("mov", [Reg(rd), Reg(rn)])
+            if rd.kind != Kind::D && (rd.number == 31 || rn.number == 31) =>
+        {
+            emit_add_immediate(asm, false, rd, rn, 0)
+        }
+        ("mov", [Reg(rd), Reg(rm)]) if rd.kind != Kind::D => {
+            // orr rd, zr, rm
+            emit(
+                asm,
+                sf(rd) | 0x2A00_0000 | (rm.number << 16) | (31 << 5) | rd.number,
+            )
+        }
+        ("mov" | "movz", [Reg(rd), Immediate(value)]) => emit_move_immediate(asm, rd, *value),
+        ("add" | "sub", [Reg(rd), Reg(rn), Immediate(value)]) => {
+            emit_add_immediate(asm, mnemonic == "sub", rd, rn, *value)
+        }
+        ("add", [Reg(rd), Reg(rn), PageOffset(symbol)]) => {
+            let start = asm.position();
+            asm.fixup(start, 0, symbol, FixupKind::Arm64PageOffset, 0);
+            emit(asm, sf(rd) | 0x1100_0000 | (rn.number << 5) | rd.number);
+        }
+        (
+            "add" | "sub" | "and" | "orr" | "eor" | "mul" | "sdiv" | "udiv",
+            [Reg(rd), Reg(rn), Reg(rm)],
+        ) if rd.kind != Kind::D => {
+            let opcode = match mnemonic {
+                "add" => 0x0B00_0000,
+                "sub" => 0x4B00_0000,
+                "and" => 0x0A00_0000,
+                "orr" => 0x2A00_0000,
+                "eor" => 0x4A00_0000,
+                "mul" => 0x1B00_7C00,
+                "sdiv" => 0x1AC0_0C00,
+                _ => 0x1AC0_0800,
+            };
+            emit(
+                asm,
+                sf(rd) | opcode | (rm.number << 16) | (rn.number << 5) | rd.number,
+            );
+        }
+        ("cmp", [Reg(rn), Reg(rm)]) => {
+            // subs zr, rn, rm
+            emit(
+                asm,
+                sf(rn) | 0x6B00_0000 | (rm.number << 16) | (rn.number << 5) | 31,
+            );
+        }
+        ("and" | "orr" | "eor", [Reg(rd), Reg(rn), Immediate(value)]) => {
+            let bits = logical_immediate(*value as u64, rd.kind == Kind::X)
+                .unwrap_or_else(|| panic!("Not a logical immediate: {}", value));
+            let opcode = match mnemonic {
+                "and" => 0x1200_0000,
+                "orr" => 0x3200_0000,
+                _ => 0x5200_0000,
+            };
+            emit(
+                asm,
+                sf(rd) | opcode | (bits << 10) | (rn.number << 5) | rd.number,
+            );
+        }
+        ("cset", [Reg(rd), Symbol(cond)]) => {
+            // csinc rd, zr, zr, !cond
+            let cond = condition_code(cond).unwrap_or_else(|| unsupported()) ^ 1;
+            emit(
+                asm,
+                sf(rd) | 0x1A80_0400 | (31 << 16) | (cond << 12) | (31 << 5) | rd.number,
+            );
+        }
+        ("csel", [Reg(rd), Reg(rn), Reg(rm), Symbol(cond)]) => {
+            let cond = condition_code(cond).unwrap_or_else(|| unsupported());
+            emit(
+                asm,
+                sf(rd)
+                    | 0x1A80_0000
+                    | (rm.number << 16)
+                    | (cond << 12)
+                    | (rn.number << 5)
+                    | rd.number,
+            );
+        }
+
+        // Scalar double-precision operations
+        ("fadd" | "fsub" | "fmul" | "fdiv", [Reg(rd), Reg(rn), Reg(rm)]) => {
+            let opcode = match mnemonic {
+                "fmul" => 0x1E60_0800,
+                "fdiv" => 0x1E60_1800,
+                "fadd" => 0x1E60_2800,
+                _ => 0x1E60_3800,
+            };
+            emit(
+                asm,
+                opcode | (rm.number << 16) | (rn.number << 5) | rd.number,
+            );
+        }
+        ("fneg", [Reg(rd), Reg(rn)]) => emit(asm, 0x1E61_4000 | (rn.number << 5) | rd.number),
+        ("fcmp", [Reg(rn), Reg(rm)]) => {
+            emit(asm, 0x1E60_2000 | (rm.number << 16) | (rn.number << 5))
+        }
+        ("fcmp", [Reg(rn), Float(value)]) if *value == 0.0 => {
+            emit(asm, 0x1E60_2008 | (rn.number << 5))
+        }
+        ("fmov", [Reg(rd), Float(value)]) => {
+            let imm8 = float_immediate(*value).unwrap_or_else(|| unsupported());
+            emit(asm, 0x1E60_1000 | (imm8 << 13) | rd.number);
+        }
+        ("fmov", [Reg(rd), Reg(rn)]) => {
+            let word = match (rd.kind, rn.kind) {
+                (Kind::D, Kind::X) => 0x9E67_0000,
+                (Kind::X, Kind::D) => 0x9E66_0000,
+                (Kind::D, Kind::D) => 0x1E60_4000,
+                _ => unsupported(),
+            };
+            emit(asm, word | (rn.number << 5) | rd.number);
+        }
+        ("scvtf" | "ucvtf", [Reg(rd), Reg(rn)]) => {
+            let opcode = if mnemonic == "scvtf" {
+                0x1E62_0000
+            } else {
+                0x1E63_0000
+            };
+            emit(asm, sf(rn) | opcode | (rn.number << 5) | rd.number);
+        }
+        _ => unsupported(),
+    }
+}
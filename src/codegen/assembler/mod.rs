@@ -0,0 +1,696 @@
+//! Built-in assembler that turns the assembly emitted by the native backends
+//! into a relocatable object file (ELF, Mach-O or COFF), so no external
+//! assembler is needed. It only understands the instructions and directives
+//! the backends actually generate.
+
+mod arm64;
+mod x64;
+
+use super::{CodegenOptions, Target, TargetOs};
+use object::write::{Object, Relocation, StandardSection, Symbol, SymbolId, SymbolSection};
+use object::{
+    elf, macho, Architecture, BinaryFormat, Endianness, RelocationEncoding, RelocationFlags,
+    RelocationKind, SectionKind, SymbolFlags, SymbolKind, SymbolScope,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Text,
+    Data,
+}
+
+/// A reference to a label whose address is only known once everything is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixupKind {
+    /// x64 `jmp`/`jcc` rel32 to a label in the same function.
+    X64Branch,
+    /// x64 `call` rel32, left to the linker.
+    X64Call,
+    /// x64 `sym(%rip)` disp32, left to the linker.
+    X64RipRelative,
+    /// ARM64 `b` imm26 to a local label.
+    Arm64Branch26,
+    /// ARM64 `cbnz`/`b.cond` imm19 to a local label.
+    Arm64Branch19,
+    /// ARM64 `bl` imm26, left to the linker.
+    Arm64Call,
+    /// ARM64 `adrp` page of a symbol.
+    Arm64Page,
+    /// ARM64 `add` low 12 bits of a symbol.
+    Arm64PageOffset,
+    /// ARM64 64-bit `ldr` scaled low 12 bits of a symbol.
+    Arm64PageOffsetLoad64,
+    /// ARM64 `adrp` page of the GOT entry for a symbol.
+    Arm64GotPage,
+    /// ARM64 64-bit `ldr` of the GOT entry for a symbol.
+    Arm64GotPageOffsetLoad64,
+}
+
+#[derive(Debug, Clone)]
+struct Fixup {
+    /// Offset of the patched field in the text section.
+    offset: u64,
+    /// Offset of the instruction containing the field.
+    instruction: u64,
+    target: String,
+    kind: FixupKind,
+    addend: i64,
+}
+
+/// Machine code and data collected while reading the assembly.
+#[derive(Default)]
+struct Assembler {
+    text: Vec<u8>,
+    data: Vec<u8>,
+    in_data: bool,
+    labels: HashMap<String, (Section, u64)>,
+    globals: Vec<String>,
+    fixups: Vec<Fixup>,
+    gnu_stack: bool,
+}
+
+impl Assembler {
+    fn emit(&mut self, bytes: &[u8]) {
+        if self.in_data {
+            self.data.extend_from_slice(bytes);
+        } else {
+            self.text.extend_from_slice(bytes);
+        }
+    }
+
+    fn position(&self) -> u64 {
+        if self.in_data {
+            self.data.len() as u64
+        } else {
+            self.text.len() as u64
+        }
+    }
+
+    /// Record a fixup for a field starting `field` bytes into an instruction
+    /// that begins at `instruction`.
+    fn fixup(&mut self, instruction: u64, field: u64, target: &str, kind: FixupKind, addend: i64) {
+        self.fixups.push(Fixup {
+            offset: instruction + field,
+            instruction,
+            target: target.to_string(),
+            kind,
+            addend,
+        });
+    }
+
+    fn directive(&mut self, line: &str) {
+        let (name, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim();
+        match name {
+            ".globl" | ".global" => self.globals.push(args.to_string()),
+            ".text" => self.in_data = false,
+            ".data" => self.in_data = true,
+            ".section" => {
+                let section = args.split(',').next().unwrap_or("").trim();
+                match section {
+                    ".text" | "__TEXT" => self.in_data = false,
+                    ".data" | "__DATA" => self.in_data = true,
+                    ".note.GNU-stack" => self.gnu_stack = true,
+                    _ => panic!("Unsupported section: {}", args),
+                }
+            }
+            ".asciz" => {
+                let string =
+                    unquote(args).unwrap_or_else(|| panic!("Malformed string literal: {}", args));
+                self.emit(&string);
+                self.emit(&[0]);
+            }
+            ".double" => {
+                let value: f64 = args
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Malformed double literal: {}", args));
+                self.emit(&value.to_bits().to_le_bytes());
+            }
+            ".p2align" => {
+                let power: u32 = args.parse().expect("Malformed alignment");
+                self.align(1 << power);
+            }
+            // Symbol types come from the section a symbol is defined in
+            ".type" | ".def" => {}
+            // Debug info is only kept when an external assembler is used
+            ".file" | ".loc" => {}
+            _ if name.starts_with(".cfi_") => {}
+            _ => panic!("Unsupported directive: {}", line),
+        }
+    }
+
+    /// Patch branches to labels in the text section and return the fixups
+    /// that must become relocations.
+    fn resolve_local_branches(&mut self) -> Vec<Fixup> {
+        let mut relocations = Vec::new();
+        for fixup in std::mem::take(&mut self.fixups) {
+            let local_target = match self.labels.get(&fixup.target) {
+                Some(&(Section::Text, offset)) => Some(offset),
+                _ => None,
+            };
+            match fixup.kind {
+                FixupKind::X64Branch => {
+                    let target =
+                        local_target.unwrap_or_else(|| panic!("Undefined label: {}", fixup.target));
+                    let delta = target as i64 - (fixup.offset as i64 + 4);
+                    let field = &mut self.text[fixup.offset as usize..fixup.offset as usize + 4];
+                    field.copy_from_slice(&(delta as i32).to_le_bytes());
+                }
+                FixupKind::Arm64Branch26 | FixupKind::Arm64Branch19 => {
+                    let target =
+                        local_target.unwrap_or_else(|| panic!("Undefined label: {}", fixup.target));
+                    let delta = (target as i64 - fixup.instruction as i64) >> 2;
+                    arm64::patch_branch(&mut self.text, fixup.instruction, fixup.kind, delta);
+                }
+                _ => relocations.push(fixup),
+            }
+        }
+        relocations
+    }
+
+    fn align(&mut self, alignment: u64) {
+        while !self.position().is_multiple_of(alignment) {
+            // Text is only aligned between functions, so padding is never executed
+            self.emit(&[0]);
+        }
+    }
+}
+
+/// `text` as the quoted operand of `.asciz`. Quotes, backslashes and bytes
+/// outside printable ASCII are written as octal escapes, which GNU as and
+/// `unquote` both read.
+pub(crate) fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for &byte in text.as_bytes() {
+        match byte {
+            b'"' | b'\\' => quoted.push_str(&format!("\\{:03o}", byte)),
+            b' '..=b'~' => quoted.push(byte as char),
+            _ => quoted.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The bytes of a string literal `quote` wrote, or `None` if malformed.
+fn unquote(literal: &str) -> Option<Vec<u8>> {
+    let mut chars = literal.strip_prefix('"')?.strip_suffix('"')?.bytes();
+    let mut bytes = Vec::new();
+    while let Some(byte) = chars.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        let octal = [chars.next()?, chars.next()?, chars.next()?];
+        let octal = std::str::from_utf8(&octal).ok()?;
+        bytes.push(u8::from_str_radix(octal, 8).ok()?);
+    }
+    Some(bytes)
+}
+
+/// Drop a trailing comment that starts outside any string literal.
+fn strip_comment<'a>(line: &'a str, prefix: &str) -> &'a str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        if c == '"' {
+            in_string = !in_string;
+        } else if !in_string && line[i..].starts_with(prefix) {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+/// Split an operand list on commas that are not inside `()` or `[]`.
+fn split_operands(operands: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in operands.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(operands[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = operands[start..].trim();
+    if !last.is_empty() {
+        result.push(last);
+    }
+    result
+}
+
+/// Assemble the output of the x64 or ARM64 backend into an object file for
+/// `options.target`.
+pub fn assemble(assembly: &str, target: &Target, options: &CodegenOptions) -> Vec<u8> {
+    let architecture = match target {
+        Target::X64 => Architecture::X86_64,
+        Target::ARM64 => Architecture::Aarch64,
+        _ => panic!("Object files can only be emitted for native targets"),
+    };
+    let mut asm = read_assembly(assembly, target);
+    let relocations = asm.resolve_local_branches();
+    write_object(asm, relocations, architecture, options.target.os)
+}
+
+/// Machine code and data laid out in one block of memory, ready to be copied
+/// to any page-aligned address and executed in process by the JIT.
+pub struct Image {
+    pub bytes: Vec<u8>,
+    /// Offsets of the global symbols into `bytes`.
+    pub symbols: HashMap<String, usize>,
+}
+
+/// Assemble the output of a native backend into an [`Image`]. References to
+/// symbols the assembly does not define are bound to `externals`, the
+/// addresses of functions in this process.
+pub fn assemble_image(
+    assembly: &str,
+    target: &Target,
+    externals: &HashMap<String, usize>,
+) -> Image {
+    let mut asm = read_assembly(assembly, target);
+    let relocations = asm.resolve_local_branches();
+
+    // Text, then data, then a stub per external function: a 32-bit
+    // displacement cannot reach code outside the image
+    let mut bytes = std::mem::take(&mut asm.text);
+    bytes.resize(bytes.len().next_multiple_of(16), 0);
+    let data_start = bytes.len();
+    bytes.extend_from_slice(&asm.data);
+    let mut stubs: HashMap<String, usize> = HashMap::new();
+
+    for fixup in relocations {
+        let target_offset = match asm.labels.get(&fixup.target) {
+            Some(&(Section::Text, offset)) => offset as usize,
+            Some(&(Section::Data, offset)) => data_start + offset as usize,
+            None => *stubs.entry(fixup.target.clone()).or_insert_with(|| {
+                let address = *externals
+                    .get(&fixup.target)
+                    .unwrap_or_else(|| panic!("Undefined symbol: {}", fixup.target));
+                bytes.resize(bytes.len().next_multiple_of(16), 0);
+                let stub = bytes.len();
+                match fixup.kind {
+                    // jmp *0(%rip) followed by the absolute address
+                    FixupKind::X64Call => bytes.extend_from_slice(&[0xFF, 0x25, 0, 0, 0, 0]),
+                    // ldr x16, #8; br x16 followed by the absolute address
+                    FixupKind::Arm64Call => {
+                        bytes.extend_from_slice(&0x5800_0050u32.to_le_bytes());
+                        bytes.extend_from_slice(&0xD61F_0200u32.to_le_bytes());
+                    }
+                    kind => panic!("{:?} to an undefined symbol", kind),
+                }
+                bytes.extend_from_slice(&(address as u64).to_le_bytes());
+                stub
+            }),
+        };
+        match fixup.kind {
+            FixupKind::X64Call | FixupKind::X64RipRelative => {
+                let delta = target_offset as i64 + fixup.addend - fixup.offset as i64;
+                let field = &mut bytes[fixup.offset as usize..fixup.offset as usize + 4];
+                field.copy_from_slice(&(delta as i32).to_le_bytes());
+            }
+            kind => arm64::patch_image_reference(
+                &mut bytes,
+                fixup.offset,
+                kind,
+                (target_offset as i64 + fixup.addend) as u64,
+            ),
+        }
+    }
+
+    let symbols = asm
+        .globals
+        .iter()
+        .map(|name| {
+            let offset = match asm.labels.get(name) {
+                Some(&(Section::Text, offset)) => offset as usize,
+                Some(&(Section::Data, offset)) => data_start + offset as usize,
+                None => panic!("Global symbol is never defined: {}", name),
+            };
+            (name.clone(), offset)
+        })
+        .collect();
+    Image { bytes, symbols }
+}
+
+fn read_assembly(assembly: &str, target: &Target) -> Assembler {
+    let comment = match target {
+        Target::ARM64 => "//",
+        _ => "#",
+    };
+
+    let mut asm = Assembler::default();
+    for line in assembly.lines() {
+        let line = strip_comment(line, comment).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(label) = line.strip_suffix(':') {
+            let section = if asm.in_data {
+                Section::Data
+            } else {
+                Section::Text
+            };
+            let position = asm.position();
+            if asm
+                .labels
+                .insert(label.to_string(), (section, position))
+                .is_some()
+            {
+                panic!("Label defined more than once: {}", label);
+            }
+        } else if line.starts_with('.') {
+            asm.directive(line);
+        } else {
+            let (mnemonic, operands) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let operands = split_operands(operands);
+            match target {
+                Target::X64 => x64::encode(&mut asm, mnemonic, &operands),
+                _ => arm64::encode(&mut asm, mnemonic, &operands),
+            }
+        }
+    }
+    asm
+}
+
+fn write_object(
+    asm: Assembler,
+    relocations: Vec<Fixup>,
+    architecture: Architecture,
+    os: TargetOs,
+) -> Vec<u8> {
+    let format = match os {
+        TargetOs::Linux => BinaryFormat::Elf,
+        TargetOs::MacOs => BinaryFormat::MachO,
+        TargetOs::Windows => BinaryFormat::Coff,
+    };
+    let mut object = Object::new(format, architecture, Endianness::Little);
+    // Names in the assembly are already decorated for the target
+    object.set_mangling(object::write::Mangling::None);
+
+    let text = object.section_id(StandardSection::Text);
+    object.append_section_data(text, &asm.text, 16);
+    let data = if asm.data.is_empty() {
+        None
+    } else {
+        let data = object.section_id(StandardSection::Data);
+        object.append_section_data(data, &asm.data, 8);
+        Some(data)
+    };
+    if asm.gnu_stack {
+        object.add_section(
+            Vec::new(),
+            b".note.GNU-stack".to_vec(),
+            SectionKind::Elf(elf::SHT_PROGBITS),
+        );
+    }
+
+    let mut symbols: HashMap<String, SymbolId> = HashMap::new();
+    for name in &asm.globals {
+        let &(section, offset) = asm
+            .labels
+            .get(name)
+            .unwrap_or_else(|| panic!("Global symbol is never defined: {}", name));
+        let id = object.add_symbol(Symbol {
+            name: name.as_bytes().to_vec(),
+            value: offset,
+            size: 0,
+            kind: if section == Section::Text {
+                SymbolKind::Text
+            } else {
+                SymbolKind::Data
+            },
+            scope: SymbolScope::Dynamic,
+            weak: false,
+            section: SymbolSection::Section(if section == Section::Text {
+                text
+            } else {
+                data.unwrap()
+            }),
+            flags: SymbolFlags::None,
+        });
+        symbols.insert(name.clone(), id);
+    }
+
+    for fixup in relocations {
+        let symbol = *symbols.entry(fixup.target.clone()).or_insert_with(|| {
+            let (kind, scope, section, value) = match asm.labels.get(&fixup.target) {
+                Some(&(Section::Text, offset)) => (
+                    SymbolKind::Label,
+                    SymbolScope::Compilation,
+                    SymbolSection::Section(text),
+                    offset,
+                ),
+                Some(&(Section::Data, offset)) => (
+                    SymbolKind::Data,
+                    SymbolScope::Compilation,
+                    SymbolSection::Section(data.unwrap()),
+                    offset,
+                ),
+                // Defined by another object, such as the runtime library
+                None => (
+                    SymbolKind::Text,
+                    SymbolScope::Dynamic,
+                    SymbolSection::Undefined,
+                    0,
+                ),
+            };
+            object.add_symbol(Symbol {
+                name: fixup.target.as_bytes().to_vec(),
+                value,
+                size: 0,
+                kind,
+                scope,
+                weak: false,
+                section,
+                flags: SymbolFlags::None,
+            })
+        });
+
+        object
+            .add_relocation(
+                text,
+                Relocation {
+                    offset: fixup.offset,
+                    symbol,
+                    addend: fixup.addend,
+                    flags: relocation_flags(fixup.kind, format),
+                },
+            )
+            .expect("Unsupported relocation");
+    }
+
+    object.write().expect("Failed to write object file")
+}
+
+fn relocation_flags(kind: FixupKind, format: BinaryFormat) -> RelocationFlags {
+    let generic = |kind, encoding, size| RelocationFlags::Generic {
+        kind,
+        encoding,
+        size,
+    };
+    match (kind, format) {
+        (FixupKind::X64Call, _) => {
+            generic(RelocationKind::Relative, RelocationEncoding::X86Branch, 32)
+        }
+        (FixupKind::X64RipRelative, _) => generic(
+            RelocationKind::Relative,
+            RelocationEncoding::X86RipRelative,
+            32,
+        ),
+        (FixupKind::Arm64Call, _) => generic(
+            RelocationKind::Relative,
+            RelocationEncoding::AArch64Call,
+            26,
+        ),
+        (FixupKind::Arm64Page, BinaryFormat::MachO) => RelocationFlags::MachO {
+            r_type: macho::ARM64_RELOC_PAGE21,
+            r_pcrel: true,
+            r_length: 2,
+        },
+        (FixupKind::Arm64PageOffset | FixupKind::Arm64PageOffsetLoad64, BinaryFormat::MachO) => {
+            RelocationFlags::MachO {
+                r_type: macho::ARM64_RELOC_PAGEOFF12,
+                r_pcrel: false,
+                r_length: 2,
+            }
+        }
+        (FixupKind::Arm64GotPage, BinaryFormat::MachO) => RelocationFlags::MachO {
+            r_type: macho::ARM64_RELOC_GOT_LOAD_PAGE21,
+            r_pcrel: true,
+            r_length: 2,
+        },
+        (FixupKind::Arm64GotPageOffsetLoad64, BinaryFormat::MachO) => RelocationFlags::MachO {
+            r_type: macho::ARM64_RELOC_GOT_LOAD_PAGEOFF12,
+            r_pcrel: false,
+            r_length: 2,
+        },
+        (FixupKind::Arm64Page, _) => RelocationFlags::Elf {
+            r_type: elf::R_AARCH64_ADR_PREL_PG_HI21,
+        },
+        (FixupKind::Arm64PageOffset, _) => RelocationFlags::Elf {
+            r_type: elf::R_AARCH64_ADD_ABS_LO12_NC,
+        },
+        (FixupKind::Arm64PageOffsetLoad64, _) => RelocationFlags::Elf {
+            r_type: elf::R_AARCH64_LDST64_ABS_LO12_NC,
+        },
+        (FixupKind::Arm64GotPage, _) => RelocationFlags::Elf {
+            r_type: elf::R_AARCH64_ADR_GOT_PAGE,
+        },
+        (FixupKind::Arm64GotPageOffsetLoad64, _) => RelocationFlags::Elf {
+            r_type: elf::R_AARCH64_LD64_GOT_LO12_NC,
+        },
+        (kind, _) => unreachable!("{:?} is resolved by the assembler", kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::TargetSpec;
+
+    fn machine_code(target: Target, assembly: &str) -> Vec<u8> {
+        let mut asm = read_assembly(assembly, &target);
+        asm.resolve_local_branches();
+        asm.text
+    }
+
+    fn words(code: &[u8]) -> Vec<u32> {
+        code.chunks(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_x64_encodings_match_gnu_as() {
+        let code = machine_code(
+            Target::X64,
+            "\tpush %rbp\n\tmov %rsp, %rbp\n\tpush %r12\n\tmov -8(%rbp), %rax\n\
+             \tmov (%rsp), %rax\n\tmovq %rax, %xmm0\n\tucomisd %xmm1, %xmm0\n\
+             \tsetnp %cl\n\tand $-16, %rsp\n\tbtc $63, %rax\n\tret",
+        );
+        let expected = [
+            0x55, 0x48, 0x89, 0xE5, 0x41, 0x54, 0x48, 0x8B, 0x45, 0xF8, 0x48, 0x8B, 0x04, 0x24,
+            0x66, 0x48, 0x0F, 0x6E, 0xC0, 0x66, 0x0F, 0x2E, 0xC1, 0x0F, 0x9B, 0xC1, 0x48, 0x83,
+            0xE4, 0xF0, 0x48, 0x0F, 0xBA, 0xF8, 0x3F, 0xC3,
+        ];
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn test_string_literals_are_escaped() {
+        let text = "say \"hi\" # \\ \t\n é";
+        let quoted = quote(text);
+        assert!(quoted.is_ascii() && !quoted.contains(['\t', '\n']));
+        // The comment marker is inside the literal, so it stays
+        let asm = read_assembly(&format!(".data\n\t.asciz {}", quoted), &Target::X64);
+        assert_eq!(asm.data, [text.as_bytes(), &[0]].concat());
+        assert_eq!(unquote(r#""\1""#), None);
+    }
+
+    #[test]
+    fn test_x64_branches_and_relocations() {
+        let mut asm = read_assembly(
+            "\tjmp .Ldone\n\tcall helper\n.Ldone:\n\tmov .LCD0(%rip), %rax\n\tret",
+            &Target::X64,
+        );
+        let relocations = asm.resolve_local_branches();
+        // jmp over the 5-byte call
+        assert_eq!(&asm.text[..5], &[0xE9, 0x05, 0x00, 0x00, 0x00]);
+        assert_eq!(relocations.len(), 2);
+        assert_eq!(relocations[0].kind, FixupKind::X64Call);
+        assert_eq!(relocations[0].offset, 6);
+        assert_eq!(relocations[1].kind, FixupKind::X64RipRelative);
+        assert_eq!(relocations[1].offset, 13);
+        assert_eq!(relocations[1].addend, -4);
+    }
+
+    #[test]
+    fn test_arm64_encodings() {
+        let code = machine_code(
+            Target::ARM64,
+            "\tstp fp, lr, [sp, #-16]!\n\tmov fp, sp\n\tstr d0, [sp, #-16]!\n\
+             \tldr x0, [sp], #16\n\tstr x0, [fp, #-8]\n\tfadd d0, d0, d1\n\
+             \tcset x0, mi\n\teor x0, x0, #1\n\tfmov d0, #1.0\n\
+             \tmov x0, #0x7ff8000000000000\n\tcbnz x0, .Lend\n\tb .Lend\n.Lend:\n\tret",
+        );
+        assert_eq!(
+            words(&code),
+            [
+                0xA9BF7BFD, 0x910003FD, 0xFC1F0FE0, 0xF84107E0, 0xF81F83A0, 0x1E612800, 0x9A9F57E0,
+                0xD2400000, 0x1E6E1000, 0xD2EFFF00, 0xB5000040, 0x14000001, 0xD65F03C0,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_x64_image_calls_externals_through_stubs() {
+        let externals = HashMap::from([("helper".to_string(), 0x1122_3344_5566_7788)]);
+        let image = assemble_image(
+            "\t.globl main\nmain:\n\tcall helper@PLT\n\tret\n",
+            &Target::X64,
+            &externals,
+        );
+        assert_eq!(image.symbols["main"], 0);
+        // call rel32 to the stub at offset 16
+        assert_eq!(&image.bytes[..6], &[0xE8, 0x0B, 0x00, 0x00, 0x00, 0xC3]);
+        assert_eq!(&image.bytes[16..22], &[0xFF, 0x25, 0, 0, 0, 0]);
+        assert_eq!(&image.bytes[22..], &0x1122_3344_5566_7788u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_arm64_image_resolves_pages_and_stubs() {
+        let externals = HashMap::from([("helper".to_string(), 0x1122_3344_5566_7788)]);
+        let image = assemble_image(
+            "\t.globl main\nmain:\n\tadrp x0, .LC0@PAGE\n\tadd x0, x0, .LC0@PAGEOFF\n\
+             \tbl helper\n\tret\n\t.section .data\n.LC0:\n\t.asciz \"hi\"\n",
+            &Target::ARM64,
+            &externals,
+        );
+        // Data at offset 16 is on the same page; the stub follows it at 32
+        assert_eq!(
+            words(&image.bytes[..16]),
+            [0x90000000, 0x91004000, 0x94000006, 0xD65F03C0]
+        );
+        assert_eq!(words(&image.bytes[32..40]), [0x58000050, 0xD61F0200]);
+    }
+
+    #[test]
+    fn test_arm64_image_relaxes_got_loads() {
+        let image = assemble_image(
+            "\t.globl main\nmain:\n\tadrp x1, :got:js.LC0\n\tldr x1, [x1, :got_lo12:js.LC0]\n\
+             \tret\n\t.section .data\njs.LC0:\n\t.asciz \"hi\"\n",
+            &Target::ARM64,
+            &HashMap::new(),
+        );
+        // The GOT load becomes add x1, x1, #16
+        assert_eq!(
+            words(&image.bytes[..12]),
+            [0x90000001, 0x91004021, 0xD65F03C0]
+        );
+    }
+
+    #[test]
+    fn test_object_formats() {
+        let source = "\t.globl main\nmain:\n\tcall jsrt_print_newline\n\tret\n";
+        let linux = CodegenOptions {
+            target: TargetSpec::from_triple("x86_64-unknown-linux-gnu").unwrap(),
+            ..Default::default()
+        };
+        let elf = assemble(source, &Target::X64, &linux);
+        assert_eq!(&elf[..4], b"\x7fELF");
+
+        let macos = CodegenOptions {
+            target: TargetSpec::from_triple("aarch64-apple-darwin").unwrap(),
+            ..Default::default()
+        };
+        let macho = assemble("\t.globl _main\n_main:\n\tret\n", &Target::ARM64, &macos);
+        assert_eq!(&macho[..4], &0xFEEDFACFu32.to_le_bytes());
+    }
+}
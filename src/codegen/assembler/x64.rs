@@ -0,0 +1,454 @@
+use super::{Assembler, FixupKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Size {
+    Byte,
+    Dword,
+    Qword,
+    Xmm,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Register {
+    number: u8,
+    size: Size,
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Register(Register),
+    Immediate(i64),
+    Memory { base: u8, displacement: i32 },
+    RipRelative(String),
+    Symbol(String),
+}
+
+fn register(name: &str) -> Option<Register> {
+    const QWORD: [&str; 16] = [
+        "rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12",
+        "r13", "r14", "r15",
+    ];
+    const DWORD: [&str; 16] = [
+        "eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "r8d", "r9d", "r10d", "r11d",
+        "r12d", "r13d", "r14d", "r15d",
+    ];
+    const BYTE: [&str; 4] = ["al", "cl", "dl", "bl"];
+
+    let find = |names: &[&str], size| {
+        names
+            .iter()
+            .position(|&n| n == name)
+            .map(|number| Register {
+                number: number as u8,
+                size,
+            })
+    };
+    if let Some(number) = name.strip_prefix("xmm") {
+        let number: u8 = number.parse().ok()?;
+        return (number < 16).then_some(Register {
+            number,
+            size: Size::Xmm,
+        });
+    }
+    find(&QWORD, Size::Qword)
+        .or_else(|| find(&DWORD, Size::Dword))
+        .or_else(|| find(&BYTE, Size::Byte))
+}
+
+fn parse_immediate(text: &str) -> i64 {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    // movabs prints double bits as unsigned, so accept the full u64 range
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => digits.parse::<u64>(),
+    }
+    .unwrap_or_else(|_| panic!("Malformed immediate: {}", text)) as i64;
+    if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    }
+}
+
+fn parse_operand(text: &str) -> Operand {
+    if let Some(name) = text.strip_prefix('%') {
+        return Operand::Register(
+            register(name).unwrap_or_else(|| panic!("Unknown register: {}", text)),
+        );
+    }
+    if let Some(value) = text.strip_prefix('$') {
+        return Operand::Immediate(parse_immediate(value));
+    }
+    if let Some((displacement, base)) = text.strip_suffix(')').and_then(|t| t.split_once('(')) {
+        if base == "%rip" {
+            return Operand::RipRelative(displacement.to_string());
+        }
+        let base = match parse_operand(base) {
+            Operand::Register(Register {
+                number,
+                size: Size::Qword,
+            }) => number,
+            _ => panic!("Unsupported memory operand: {}", text),
+        };
+        let displacement = if displacement.is_empty() {
+            0
+        } else {
+            parse_immediate(displacement) as i32
+        };
+        return Operand::Memory { base, displacement };
+    }
+    Operand::Symbol(text.to_string())
+}
+
+fn condition_code(name: &str) -> Option<u8> {
+    Some(match name {
+        "o" => 0x0,
+        "no" => 0x1,
+        "b" | "nae" => 0x2,
+        "ae" | "nb" => 0x3,
+        "e" | "z" => 0x4,
+        "ne" | "nz" => 0x5,
+        "be" => 0x6,
+        "a" => 0x7,
+        "s" => 0x8,
+        "ns" => 0x9,
+        "p" => 0xA,
+        "np" => 0xB,
+        "l" => 0xC,
+        "ge" => 0xD,
+        "le" => 0xE,
+        "g" => 0xF,
+        _ => return None,
+    })
+}
+
+/// An instruction with a ModRM operand, assembled as
+/// `[prefix] [REX] opcode ModRM [SIB] [disp] [immediate]`.
+struct ModRm<'a> {
+    prefix: Option<u8>,
+    wide: bool,
+    opcode: &'a [u8],
+    reg: u8,
+    rm: &'a Operand,
+    immediate: &'a [u8],
+}
+
+fn emit_modrm(asm: &mut Assembler, encoding: ModRm) {
+    let start = asm.position();
+    let mut bytes = Vec::new();
+    if let Some(prefix) = encoding.prefix {
+        bytes.push(prefix);
+    }
+
+    let rm_number = match encoding.rm {
+        Operand::Register(register) => register.number,
+        Operand::Memory { base, .. } => *base,
+        _ => 0,
+    };
+    let rex = (u8::from(encoding.wide) << 3) | ((encoding.reg >> 3) << 2) | (rm_number >> 3);
+    if rex != 0 {
+        bytes.push(0x40 | rex);
+    }
+    bytes.extend_from_slice(encoding.opcode);
+
+    let reg = (encoding.reg & 7) << 3;
+    match encoding.rm {
+        Operand::Register(register) => bytes.push(0xC0 | reg | (register.number & 7)),
+        Operand::Memory { base, displacement } => {
+            // %rbp/%r13 have no disp-less form, %rsp/%r12 need a SIB byte
+            let base = base & 7;
+            let (mode, disp): (u8, Vec<u8>) = if *displacement == 0 && base != 5 {
+                (0x00, vec![])
+            } else if i8::try_from(*displacement).is_ok() {
+                (0x40, vec![*displacement as u8])
+            } else {
+                (0x80, displacement.to_le_bytes().to_vec())
+            };
+            bytes.push(mode | reg | base);
+            if base == 4 {
+                bytes.push(0x24);
+            }
+            bytes.extend(disp);
+        }
+        Operand::RipRelative(symbol) => {
+            bytes.push(reg | 5);
+            // The CPU adds the address of the next instruction, which ends
+            // after any immediate that follows the displacement
+            let addend = -4 - encoding.immediate.len() as i64;
+            asm.fixup(
+                start,
+                bytes.len() as u64,
+                symbol,
+                FixupKind::X64RipRelative,
+                addend,
+            );
+            bytes.extend_from_slice(&[0; 4]);
+        }
+        _ => panic!("Unsupported operand: {:?}", encoding.rm),
+    }
+
+    bytes.extend_from_slice(encoding.immediate);
+    asm.emit(&bytes);
+}
+
+/// Opcode prefix and REX.B for instructions that encode a register in the opcode byte.
+fn emit_opcode_register(asm: &mut Assembler, wide: bool, opcode: u8, register: u8, tail: &[u8]) {
+    let mut bytes = Vec::new();
+    let rex = (u8::from(wide) << 3) | (register >> 3);
+    if rex != 0 {
+        bytes.push(0x40 | rex);
+    }
+    bytes.push(opcode + (register & 7));
+    bytes.extend_from_slice(tail);
+    asm.emit(&bytes);
+}
+
+fn emit_rel32(asm: &mut Assembler, opcode: &[u8], target: &Operand, kind: FixupKind) {
+    let target = match target {
+        Operand::Symbol(symbol) => symbol,
+        _ => panic!("Unsupported branch target: {:?}", target),
+    };
+    let start = asm.position();
+    asm.fixup(start, opcode.len() as u64, target, kind, -4);
+    asm.emit(opcode);
+    asm.emit(&[0; 4]);
+}
+
+/// Encode one AT&T-syntax instruction (`mnemonic src, dst`).
+pub(super) fn encode(asm: &mut Assembler, mnemonic: &str, operands: &[&str]) {
+    let operands: Vec<Operand> = operands.iter().map(|op| parse_operand(op)).collect();
+    let unsupported =
+        || -> ! { panic!("Unsupported x64 instruction: {} {:?}", mnemonic, operands) };
+
+    match (mnemonic, operands.as_slice()) {
+        ("ret", []) => asm.emit(&[0xC3]),
+        ("push", [Operand::Register(r)]) => emit_opcode_register(asm, false, 0x50, r.number, &[]),
+        ("pop", [Operand::Register(r)]) => emit_opcode_register(asm, false, 0x58, r.number, &[]),
+        ("push", [Operand::Immediate(value)]) => match i8::try_from(*value) {
+            Ok(byte) => asm.emit(&[0x6A, byte as u8]),
+            Err(_) => {
+                asm.emit(&[0x68]);
+                asm.emit(&(*value as i32).to_le_bytes());
+            }
+        },
+
+        // Calls are always relocated through the PLT, so `sym@PLT` is just `sym`
+        ("call", [Operand::Symbol(symbol)]) if symbol.ends_with("@PLT") => {
+            let target = Operand::Symbol(symbol.trim_end_matches("@PLT").to_string());
+            emit_rel32(asm, &[0xE8], &target, FixupKind::X64Call)
+        }
+        ("call", [target]) => emit_rel32(asm, &[0xE8], target, FixupKind::X64Call),
+        ("jmp", [target]) => emit_rel32(asm, &[0xE9], target, FixupKind::X64Branch),
+        (m, [target]) if m.starts_with('j') => {
+            let cc = condition_code(&m[1..]).unwrap_or_else(|| unsupported());
+            emit_rel32(asm, &[0x0F, 0x80 + cc], target, FixupKind::X64Branch);
+        }
+        (m, [Operand::Register(r)]) if m.starts_with("set") && r.size == Size::Byte => {
+            let cc = condition_code(&m[3..]).unwrap_or_else(|| unsupported());
+            emit_modrm(
+                asm,
+                ModRm {
+                    prefix: None,
+                    wide: false,
+                    opcode: &[0x0F, 0x90 + cc],
+                    reg: 0,
+                    rm: &operands[0],
+                    immediate: &[],
+                },
+            );
+        }
+
+        // movq between a general-purpose and an SSE register
+        ("movq" | "mov", [Operand::Register(src), Operand::Register(dst)])
+            if (src.size == Size::Xmm) != (dst.size == Size::Xmm) =>
+        {
+            let (xmm, gpr, opcode) = if dst.size == Size::Xmm {
+                (dst, &operands[0], 0x6E)
+            } else {
+                (src, &operands[1], 0x7E)
+            };
+            emit_modrm(
+                asm,
+                ModRm {
+                    prefix: Some(0x66),
+                    wide: true,
+                    opcode: &[0x0F, opcode],
+                    reg: xmm.number,
+                    rm: gpr,
+                    immediate: &[],
+                },
+            );
+        }
+        ("movabs", [Operand::Immediate(value), Operand::Register(dst)]) => {
+            emit_opcode_register(asm, true, 0xB8, dst.number, &value.to_le_bytes())
+        }
+        ("mov" | "movq", [Operand::Immediate(value), dst]) => {
+            let wide = !matches!(dst, Operand::Register(r) if r.size == Size::Dword);
+            match (i32::try_from(*value), dst) {
+                (Ok(value), _) => emit_modrm(
+                    asm,
+                    ModRm {
+                        prefix: None,
+                        wide,
+                        opcode: &[0xC7],
+                        reg: 0,
+                        rm: dst,
+                        immediate: &value.to_le_bytes(),
+                    },
+                ),
+                (Err(_), Operand::Register(r)) => {
+                    emit_opcode_register(asm, true, 0xB8, r.number, &value.to_le_bytes())
+                }
+                _ => unsupported(),
+            }
+        }
+        ("mov" | "movq", [Operand::Register(src), dst]) => emit_modrm(
+            asm,
+            ModRm {
+                prefix: None,
+                wide: src.size == Size::Qword,
+                opcode: &[0x89],
+                reg: src.number,
+                rm: dst,
+                immediate: &[],
+            },
+        ),
+        ("mov" | "movq", [src, Operand::Register(dst)]) => emit_modrm(
+            asm,
+            ModRm {
+                prefix: None,
+                wide: dst.size == Size::Qword,
+                opcode: &[0x8B],
+                reg: dst.number,
+                rm: src,
+                immediate: &[],
+            },
+        ),
+        ("lea" | "leaq", [src, Operand::Register(dst)]) => emit_modrm(
+            asm,
+            ModRm {
+                prefix: None,
+                wide: true,
+                opcode: &[0x8D],
+                reg: dst.number,
+                rm: src,
+                immediate: &[],
+            },
+        ),
+        ("movzx" | "movzbl", [src @ Operand::Register(_), Operand::Register(dst)]) => emit_modrm(
+            asm,
+            ModRm {
+                prefix: None,
+                wide: dst.size == Size::Qword,
+                opcode: &[0x0F, 0xB6],
+                reg: dst.number,
+                rm: src,
+                immediate: &[],
+            },
+        ),
+
+        // Integer ALU operations: (register form opcode, immediate form extension)
+        ("add" | "or" | "and" | "sub" | "xor" | "cmp", [src, dst]) => {
+            let (opcode, extension) = match mnemonic {
+                "add" => (0x01, 0),
+                "or" => (0x09, 1),
+                "and" => (0x21, 4),
+                "sub" => (0x29, 5),
+                "xor" => (0x31, 6),
+                _ => (0x39, 7),
+            };
+            let size = match (src, dst) {
+                (_, Operand::Register(r)) | (Operand::Register(r), _) => r.size,
+                _ => Size::Qword,
+            };
+            match src {
+                Operand::Immediate(value) => {
+                    let (opcode, immediate) = match i8::try_from(*value) {
+                        Ok(byte) => (0x83, vec![byte as u8]),
+                        Err(_) => (0x81, (*value as i32).to_le_bytes().to_vec()),
+                    };
+                    emit_modrm(
+                        asm,
+                        ModRm {
+                            prefix: None,
+                            wide: size == Size::Qword,
+                            opcode: &[opcode],
+                            reg: extension,
+                            rm: dst,
+                            immediate: &immediate,
+                        },
+                    );
+                }
+                Operand::Register(r) => emit_modrm(
+                    asm,
+                    ModRm {
+                        prefix: None,
+                        wide: size == Size::Qword,
+                        // The byte forms are one less than the wide forms
+                        opcode: &[if size == Size::Byte {
+                            opcode - 1
+                        } else {
+                            opcode
+                        }],
+                        reg: r.number,
+                        rm: dst,
+                        immediate: &[],
+                    },
+                ),
+                _ => unsupported(),
+            }
+        }
+        ("btc", [Operand::Immediate(bit), dst]) => emit_modrm(
+            asm,
+            ModRm {
+                prefix: None,
+                wide: true,
+                opcode: &[0x0F, 0xBA],
+                reg: 7,
+                rm: dst,
+                immediate: &[*bit as u8],
+            },
+        ),
+
+        // SSE2 scalar double operations, `op src, dst`
+        ("cvtsi2sd", [src @ Operand::Register(gpr), Operand::Register(dst)]) => emit_modrm(
+            asm,
+            ModRm {
+                prefix: Some(0xF2),
+                wide: gpr.size == Size::Qword,
+                opcode: &[0x0F, 0x2A],
+                reg: dst.number,
+                rm: src,
+                immediate: &[],
+            },
+        ),
+        (
+            "addsd" | "subsd" | "mulsd" | "divsd" | "ucomisd" | "xorpd",
+            [src, Operand::Register(dst)],
+        ) => {
+            let (prefix, opcode) = match mnemonic {
+                "addsd" => (0xF2, 0x58),
+                "mulsd" => (0xF2, 0x59),
+                "subsd" => (0xF2, 0x5C),
+                "divsd" => (0xF2, 0x5E),
+                "ucomisd" => (0x66, 0x2E),
+                _ => (0x66, 0x57),
+            };
+            emit_modrm(
+                asm,
+                ModRm {
+                    prefix: Some(prefix),
+                    wide: false,
+                    opcode: &[0x0F, opcode],
+                    reg: dst.number,
+                    rm: src,
+                    immediate: &[],
+                },
+            );
+        }
+        _ => unsupported(),
+    }
+}
@@ -0,0 +1,760 @@
+//! Cranelift backend: compiles the stack IR to machine code for the host in
+//! process and writes it as a relocatable object, which links against the
+//! runtime library like the output of the handwritten backends.
+
+use super::{closures_unsupported, objects_unsupported, runtime, CodeGenerator, Target};
+use crate::ir::{
+    BinaryOp, CType, Constant, ExternFunction, IRFunction, IRInstruction, IRModule, UnaryOp,
+};
+use cranelift_codegen::ir::condcodes::{FloatCC, IntCC};
+use cranelift_codegen::ir::{
+    types, AbiParam, Block, InstBuilder, MemFlagsData, Signature, Type, Value,
+};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_codegen::Context;
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_module::{DataDescription, DataId, FuncId, Linkage, Module};
+use cranelift_object::{ObjectBuilder, ObjectModule};
+use std::collections::HashMap;
+use std::io;
+
+/// Compile `module` into an object file for the host.
+pub fn compile_object(module: &IRModule) -> Result<Vec<u8>, String> {
+    let mut compiler = Compiler::new()?;
+    compiler.constants = module.constants.clone();
+    compiler.names = module.function_names();
+    compiler.declare_externs(&module.externs)?;
+    for function in &module.functions {
+        compiler.declare_function(&function.name, function.params.len())?;
+    }
+    for function in &module.functions {
+        compiler.define_function(function)?;
+    }
+    if module
+        .functions
+        .iter()
+        .any(|function| function.name == "main")
+    {
+        compiler.define_entry_point()?;
+    }
+    let product = compiler.module.finish();
+    product.emit().map_err(|e| e.to_string())
+}
+
+/// Writes the Cranelift IR of each function, for inspection.
+#[derive(Default)]
+pub struct CraneliftTextGenerator {
+    compiler: Option<Compiler>,
+}
+
+impl CraneliftTextGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn compiler(&mut self) -> &mut Compiler {
+        self.compiler
+            .as_mut()
+            .expect("begin_module must be called first")
+    }
+}
+
+impl CodeGenerator for CraneliftTextGenerator {
+    fn begin_module(&mut self, module: &IRModule, _out: &mut dyn io::Write) -> io::Result<()> {
+        let compiler = Compiler::new().and_then(|mut compiler| {
+            compiler.constants = module.constants.clone();
+            compiler.names = module.function_names();
+            compiler.declare_externs(&module.externs)?;
+            for function in &module.functions {
+                compiler.declare_function(&function.name, function.params.len())?;
+            }
+            Ok(compiler)
+        });
+        self.compiler =
+            Some(compiler.unwrap_or_else(|e| panic!("Cranelift compilation failed: {}", e)));
+        Ok(())
+    }
+
+    fn emit_function(&mut self, function: &IRFunction, out: &mut dyn io::Write) -> io::Result<()> {
+        let compiler = self.compiler();
+        compiler
+            .translate(function)
+            .unwrap_or_else(|e| panic!("Cranelift compilation failed: {}", e));
+        writeln!(out, "{}", compiler.context.func.display())?;
+        compiler.module.clear_context(&mut compiler.context);
+        Ok(())
+    }
+
+    fn finish(&mut self, _out: &mut dyn io::Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Compiler {
+    module: ObjectModule,
+    context: Context,
+    builder_context: FunctionBuilderContext,
+    functions: HashMap<String, FuncId>,
+    strings: HashMap<String, DataId>,
+    /// C functions declared with `extern function`.
+    externs: HashMap<String, ExternFunction>,
+    /// The module's constants, which `PushConst` indexes.
+    constants: Vec<Constant>,
+    /// Names of the module's functions, which `Call` indexes.
+    names: Vec<String>,
+}
+
+/// Cranelift type of a C type; `void` has none.
+fn c_type(ty: CType) -> Option<Type> {
+    match ty {
+        CType::Int => Some(types::I32),
+        CType::Long | CType::String => Some(types::I64),
+        CType::Double => Some(types::F64),
+        CType::Void => None,
+    }
+}
+
+impl Compiler {
+    fn new() -> Result<Self, String> {
+        let mut flags = settings::builder();
+        flags.set("is_pic", "true").map_err(|e| e.to_string())?;
+        flags.set("opt_level", "speed").map_err(|e| e.to_string())?;
+        let isa = cranelift_native::builder()?
+            .finish(settings::Flags::new(flags))
+            .map_err(|e| e.to_string())?;
+        let builder = ObjectBuilder::new(isa, "js", cranelift_module::default_libcall_names())
+            .map_err(|e| e.to_string())?;
+        let module = ObjectModule::new(builder);
+        let context = module.make_context();
+        Ok(Self {
+            module,
+            context,
+            builder_context: FunctionBuilderContext::new(),
+            functions: HashMap::new(),
+            strings: HashMap::new(),
+            externs: HashMap::new(),
+            constants: Vec::new(),
+            names: Vec::new(),
+        })
+    }
+
+    /// JS functions take and return native values (see `runtime.rs`) as
+    /// doubles, like C `double f(double, ...)`.
+    fn signature(&self, argc: usize) -> Signature {
+        let mut signature = self.module.make_signature();
+        signature.params = vec![AbiParam::new(types::F64); argc];
+        signature.returns.push(AbiParam::new(types::F64));
+        signature
+    }
+
+    fn declare_function(&mut self, name: &str, argc: usize) -> Result<FuncId, String> {
+        let signature = self.signature(argc);
+        let id = self
+            .module
+            .declare_function(runtime::function_name(name), Linkage::Export, &signature)
+            .map_err(|e| e.to_string())?;
+        self.functions.insert(name.to_string(), id);
+        Ok(id)
+    }
+
+    /// Cranelift has no variadic calls, so only fixed-arity C functions can
+    /// be called.
+    fn declare_externs(&mut self, externs: &[ExternFunction]) -> Result<(), String> {
+        for function in externs {
+            if function.variadic {
+                return Err(format!(
+                    "Cranelift cannot call the variadic C function {}",
+                    function.name
+                ));
+            }
+            self.externs.insert(function.name.clone(), function.clone());
+        }
+        Ok(())
+    }
+
+    /// Signature of a C function with the given parameter and result types.
+    fn c_signature(&self, params: &[Type], returns: Option<Type>) -> Signature {
+        let mut signature = self.module.make_signature();
+        signature.params = params.iter().map(|&ty| AbiParam::new(ty)).collect();
+        signature.returns.extend(returns.map(AbiParam::new));
+        signature
+    }
+
+    /// Import the C function `function` and the runtime functions that
+    /// convert its arguments and result.
+    fn import_extern(
+        &mut self,
+        function: &ExternFunction,
+        runtime_functions: &mut HashMap<&'static str, FuncId>,
+    ) -> Result<FuncId, String> {
+        for &ty in &function.params {
+            let name = runtime::to_c_function(Some(ty));
+            let returns = if ty == CType::Double {
+                types::F64
+            } else {
+                types::I64
+            };
+            let signature = self.c_signature(&[types::I64], Some(returns));
+            runtime_functions.insert(name, self.import_function(name, &signature)?);
+        }
+        if let Some(name) = runtime::from_c_function(function.returns) {
+            let params = [c_type(function.returns).unwrap()];
+            let signature = self.c_signature(&params, Some(types::I64));
+            runtime_functions.insert(name, self.import_function(name, &signature)?);
+        }
+        let params: Vec<Type> = function
+            .params
+            .iter()
+            .filter_map(|&ty| c_type(ty))
+            .collect();
+        let signature = self.c_signature(&params, c_type(function.returns));
+        self.import_function(&function.name, &signature)
+    }
+
+    /// A function defined elsewhere, such as the runtime library.
+    fn import_function(&mut self, name: &str, signature: &Signature) -> Result<FuncId, String> {
+        if let Some(&id) = self.functions.get(name) {
+            return Ok(id);
+        }
+        let id = self
+            .module
+            .declare_function(name, Linkage::Import, signature)
+            .map_err(|e| e.to_string())?;
+        self.functions.insert(name.to_string(), id);
+        Ok(id)
+    }
+
+    fn string(&mut self, s: &str) -> Result<DataId, String> {
+        if let Some(&id) = self.strings.get(s) {
+            return Ok(id);
+        }
+        let id = self
+            .module
+            .declare_anonymous_data(false, false)
+            .map_err(|e| e.to_string())?;
+        let mut data = DataDescription::new();
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.push(0);
+        data.define(bytes.into_boxed_slice());
+        self.module
+            .define_data(id, &data)
+            .map_err(|e| e.to_string())?;
+        self.strings.insert(s.to_string(), id);
+        Ok(id)
+    }
+
+    fn define_function(&mut self, function: &IRFunction) -> Result<(), String> {
+        self.translate(function)?;
+        let id = self.functions[&function.name];
+        self.module
+            .define_function(id, &mut self.context)
+            .map_err(|e| format!("{}: {}", function.name, e))?;
+        self.module.clear_context(&mut self.context);
+        Ok(())
+    }
+
+    /// The C `main`: call the JS `main`, then exit with the status its
+    /// result gives.
+    fn define_entry_point(&mut self) -> Result<(), String> {
+        let js_main = self.functions["main"];
+        let exit = {
+            let signature = self.c_signature(&[types::I64], None);
+            self.import_function(runtime::EXIT_FUNCTION, &signature)?
+        };
+        let signature = self.c_signature(&[], Some(types::I32));
+        let id = self
+            .module
+            .declare_function("main", Linkage::Export, &signature)
+            .map_err(|e| e.to_string())?;
+
+        self.context.func.signature = signature;
+        let mut builder = FunctionBuilder::new(&mut self.context.func, &mut self.builder_context);
+        let entry = builder.create_block();
+        builder.switch_to_block(entry);
+        let js_main = self.module.declare_func_in_func(js_main, builder.func);
+        let exit = self.module.declare_func_in_func(exit, builder.func);
+        let call = builder.ins().call(js_main, &[]);
+        let result = builder.inst_results(call)[0];
+        let bits = builder
+            .ins()
+            .bitcast(types::I64, MemFlagsData::new(), result);
+        builder.ins().call(exit, &[bits]);
+        let status = builder.ins().iconst(types::I32, 0);
+        builder.ins().return_(&[status]);
+        builder.seal_all_blocks();
+        builder.finalize(self.module.target_config());
+
+        self.module
+            .define_function(id, &mut self.context)
+            .map_err(|e| format!("main: {}", e))?;
+        self.module.clear_context(&mut self.context);
+        Ok(())
+    }
+
+    /// Translate `function` into `self.context.func`.
+    fn translate(&mut self, function: &IRFunction) -> Result<(), String> {
+        self.context.func.signature = self.signature(function.params.len());
+
+        // Resolve everything the body refers to before borrowing the context
+        let print_value = {
+            let mut signature = self.module.make_signature();
+            signature.params = vec![AbiParam::new(types::I64), AbiParam::new(types::I32)];
+            self.import_function("jsrt_print_value", &signature)?
+        };
+        let print_newline = {
+            let signature = self.module.make_signature();
+            self.import_function("jsrt_print_newline", &signature)?
+        };
+        let instructions = function.instructions();
+        let mut callees = HashMap::new();
+        let mut strings = HashMap::new();
+        let mut runtime_functions = HashMap::new();
+        for instruction in &instructions {
+            let (name, argc) = match instruction {
+                IRInstruction::Binary(op) => (runtime::binary_function(op), 2),
+                IRInstruction::Unary(op) => (runtime::unary_function(op), 1),
+                IRInstruction::JumpIf(_) => ("jsrt_truthy", 1),
+                _ => continue,
+            };
+            let mut signature = self.module.make_signature();
+            signature.params = vec![AbiParam::new(types::I64); argc];
+            signature.returns.push(AbiParam::new(types::I64));
+            runtime_functions.insert(name, self.import_function(name, &signature)?);
+        }
+        let mut externs = HashMap::new();
+        for instruction in &instructions {
+            match instruction {
+                IRInstruction::Call(id, argc) => {
+                    let name = self.names[*id as usize].clone();
+                    let signature = self.signature(*argc as usize);
+                    callees.insert(name.clone(), self.import_function(&name, &signature)?);
+                }
+                IRInstruction::CallNative(name, _) if self.externs.contains_key(name) => {
+                    let extern_function = self.externs[name].clone();
+                    let id = self.import_extern(&extern_function, &mut runtime_functions)?;
+                    callees.insert(name.clone(), id);
+                    externs.insert(name.clone(), extern_function);
+                }
+                IRInstruction::CallNative(name, argc) if !runtime::is_builtin(name) => {
+                    let signature = self.signature(*argc as usize);
+                    callees.insert(name.clone(), self.import_function(name, &signature)?);
+                }
+                IRInstruction::PushConst(index) => {
+                    if let Constant::String(s) = &self.constants[*index as usize] {
+                        let s = s.clone();
+                        let id = self.string(&s)?;
+                        strings.insert(s, id);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut builder = FunctionBuilder::new(&mut self.context.func, &mut self.builder_context);
+        let entry = builder.create_block();
+        builder.append_block_params_for_function_params(entry);
+        builder.switch_to_block(entry);
+
+        // Variables start out undefined; parameters take their arguments
+        let undefined = builder.ins().f64const(f64::from_bits(runtime::UNDEFINED));
+        let mut variables = HashMap::new();
+        for instruction in &instructions {
+            if let IRInstruction::Load(name) | IRInstruction::Store(name) = instruction {
+                variables.entry(name.clone()).or_insert_with(|| {
+                    let variable = builder.declare_var(types::F64);
+                    builder.def_var(variable, undefined);
+                    variable
+                });
+            }
+        }
+        for (i, param) in function.params.iter().enumerate() {
+            let variable = *variables
+                .entry(param.clone())
+                .or_insert_with(|| builder.declare_var(types::F64));
+            let argument = builder.block_params(entry)[i];
+            builder.def_var(variable, argument);
+        }
+
+        // One variable per operand stack slot; the frontend builds the SSA
+        let slots: Vec<Variable> = (0..function.max_stack_depth())
+            .map(|_| builder.declare_var(types::F64))
+            .collect();
+        let labels: HashMap<&str, Block> = instructions
+            .iter()
+            .filter_map(|inst| match inst {
+                IRInstruction::Label(label) => Some((label.as_str(), builder.create_block())),
+                _ => None,
+            })
+            .collect();
+
+        let mut translator = Translator {
+            builder,
+            slots,
+            variables,
+            labels,
+            module: &mut self.module,
+            constants: &self.constants,
+            names: &self.names,
+            callees,
+            externs,
+            strings,
+            runtime_functions,
+            print_value,
+            print_newline,
+        };
+
+        // Whether the current block still needs a terminator
+        let mut open = true;
+        for (instruction, depth) in instructions.iter().zip(function.stack_depths()) {
+            if let IRInstruction::Label(label) = instruction {
+                let block = translator.labels[label.as_str()];
+                if open {
+                    translator.builder.ins().jump(block, &[]);
+                }
+                translator.builder.switch_to_block(block);
+                open = true;
+                continue;
+            }
+            let Some(depth) = depth else {
+                continue;
+            };
+            if !open {
+                // Code after a terminator only runs when jumped to
+                let block = translator.builder.create_block();
+                translator.builder.switch_to_block(block);
+            }
+            open = translator.translate_instruction(instruction, depth);
+        }
+        if open {
+            let undefined = translator
+                .builder
+                .ins()
+                .f64const(f64::from_bits(runtime::UNDEFINED));
+            translator.builder.ins().return_(&[undefined]);
+        }
+
+        let config = translator.module.target_config();
+        translator.builder.seal_all_blocks();
+        translator.builder.finalize(config);
+        Ok(())
+    }
+}
+
+struct Translator<'a> {
+    builder: FunctionBuilder<'a>,
+    slots: Vec<Variable>,
+    variables: HashMap<String, Variable>,
+    labels: HashMap<&'a str, Block>,
+    module: &'a mut ObjectModule,
+    constants: &'a [Constant],
+    names: &'a [String],
+    callees: HashMap<String, FuncId>,
+    /// C functions this function calls.
+    externs: HashMap<String, ExternFunction>,
+    strings: HashMap<String, DataId>,
+    /// Runtime functions for operands that are not numbers.
+    runtime_functions: HashMap<&'static str, FuncId>,
+    print_value: FuncId,
+    print_newline: FuncId,
+}
+
+impl Translator<'_> {
+    fn get(&mut self, depth: usize) -> Value {
+        self.builder.use_var(self.slots[depth])
+    }
+
+    fn set(&mut self, depth: usize, value: Value) {
+        self.builder.def_var(self.slots[depth], value);
+    }
+
+    fn bits(&mut self, value: Value) -> Value {
+        self.builder
+            .ins()
+            .bitcast(types::I64, MemFlagsData::new(), value)
+    }
+
+    /// Native value with the given bits.
+    fn constant(&mut self, bits: u64) -> Value {
+        self.builder.ins().f64const(f64::from_bits(bits))
+    }
+
+    /// Call a runtime function on native values, returning its `i64` result.
+    fn runtime_call(&mut self, name: &str, operands: &[Value]) -> Value {
+        let args: Vec<Value> = operands.iter().map(|&operand| self.bits(operand)).collect();
+        self.call_function(name, &args)
+    }
+
+    /// Value of type `ty` (a native value or a flag): `fast` when all
+    /// `operands` are numbers, else the result of the runtime function `slow`.
+    fn dispatch(
+        &mut self,
+        operands: &[Value],
+        slow: &str,
+        ty: Type,
+        fast: impl FnOnce(&mut Self) -> Value,
+    ) -> Value {
+        let limit = self
+            .builder
+            .ins()
+            .iconst(types::I64, runtime::MAX_NUMBER as i64);
+        let mut all_numbers = self.builder.ins().iconst(types::I8, 1);
+        for &operand in operands {
+            let bits = self.bits(operand);
+            let is_number = self
+                .builder
+                .ins()
+                .icmp(IntCC::UnsignedLessThanOrEqual, bits, limit);
+            all_numbers = self.builder.ins().band(all_numbers, is_number);
+        }
+        let fast_block = self.builder.create_block();
+        let slow_block = self.builder.create_block();
+        let done = self.builder.create_block();
+        self.builder.append_block_param(done, ty);
+        self.builder
+            .ins()
+            .brif(all_numbers, fast_block, &[], slow_block, &[]);
+
+        self.builder.switch_to_block(fast_block);
+        let value = fast(self);
+        self.builder.ins().jump(done, &[value.into()]);
+
+        self.builder.switch_to_block(slow_block);
+        let result = self.runtime_call(slow, operands);
+        let value = if ty == types::F64 {
+            self.builder
+                .ins()
+                .bitcast(types::F64, MemFlagsData::new(), result)
+        } else {
+            self.builder.ins().icmp_imm_u(IntCC::NotEqual, result, 0)
+        };
+        self.builder.ins().jump(done, &[value.into()]);
+
+        self.builder.switch_to_block(done);
+        self.builder.block_params(done)[0]
+    }
+
+    /// Flag that is set when `value` is truthy.
+    fn truthy(&mut self, value: Value) -> Value {
+        self.dispatch(&[value], "jsrt_truthy", types::I8, |t| {
+            // Numbers are truthy unless +-0.0 or NaN
+            let zero = t.builder.ins().f64const(0.0);
+            t.builder.ins().fcmp(FloatCC::OrderedNotEqual, value, zero)
+        })
+    }
+
+    /// Convert a flag to a boolean value.
+    fn bool_from_flag(&mut self, flag: Value) -> Value {
+        let bit = self.builder.ins().uextend(types::I64, flag);
+        let bits = self.builder.ins().bor_imm_u(bit, runtime::FALSE as i64);
+        self.builder
+            .ins()
+            .bitcast(types::F64, MemFlagsData::new(), bits)
+    }
+
+    /// Emit one instruction, with `depth` values on the operand stack.
+    /// Returns false when it terminated the current block.
+    fn translate_instruction(&mut self, instruction: &IRInstruction, depth: usize) -> bool {
+        match instruction {
+            IRInstruction::PushConst(index) => {
+                let value = match &self.constants[*index as usize] {
+                    Constant::Number(n) => self.builder.ins().f64const(*n),
+                    Constant::Boolean(true) => self.constant(runtime::TRUE),
+                    Constant::Boolean(false) => self.constant(runtime::FALSE),
+                    Constant::Null => self.constant(runtime::NULL),
+                    Constant::String(s) => {
+                        let data = self
+                            .module
+                            .declare_data_in_func(self.strings[s], self.builder.func);
+                        let address = self.builder.ins().symbol_value(types::I64, data);
+                        let bits = self
+                            .builder
+                            .ins()
+                            .bor_imm_u(address, runtime::STRING_TAG as i64);
+                        self.builder
+                            .ins()
+                            .bitcast(types::F64, MemFlagsData::new(), bits)
+                    }
+                };
+                self.set(depth, value);
+            }
+            IRInstruction::Load(name) => {
+                let value = self.builder.use_var(self.variables[name]);
+                self.set(depth, value);
+            }
+            IRInstruction::Store(name) => {
+                let value = self.get(depth - 1);
+                self.builder.def_var(self.variables[name], value);
+            }
+            IRInstruction::LoadUpvalue(_)
+            | IRInstruction::StoreUpvalue(_)
+            | IRInstruction::MakeClosure(..) => closures_unsupported(Target::Cranelift),
+            IRInstruction::GetProperty(_) => objects_unsupported(Target::Cranelift),
+            IRInstruction::Binary(op) => {
+                let left = self.get(depth - 2);
+                let right = self.get(depth - 1);
+                let value = self.translate_binary_op(op, left, right);
+                self.set(depth - 2, value);
+            }
+            IRInstruction::Unary(op) => {
+                let operand = self.get(depth - 1);
+                let function = runtime::unary_function(op);
+                let value = self.dispatch(&[operand], function, types::F64, |t| match op {
+                    UnaryOp::Neg => t.builder.ins().fneg(operand),
+                    UnaryOp::Not => {
+                        // Falsy: equal to zero, or unordered (NaN)
+                        let zero = t.builder.ins().f64const(0.0);
+                        let flag = t
+                            .builder
+                            .ins()
+                            .fcmp(FloatCC::UnorderedOrEqual, operand, zero);
+                        t.bool_from_flag(flag)
+                    }
+                });
+                self.set(depth - 1, value);
+            }
+            IRInstruction::Call(id, argc) => {
+                let name = self.names[*id as usize].clone();
+                self.translate_call(&name, *argc, depth);
+            }
+            IRInstruction::CallNative(name, argc) => self.translate_call(name, *argc, depth),
+            IRInstruction::Return(has_value) => {
+                let value = if *has_value {
+                    self.get(depth - 1)
+                } else {
+                    self.constant(runtime::UNDEFINED)
+                };
+                self.builder.ins().return_(&[value]);
+                return false;
+            }
+            IRInstruction::Jump(label) => {
+                let block = self.labels[label.as_str()];
+                self.builder.ins().jump(block, &[]);
+                return false;
+            }
+            IRInstruction::JumpIf(label) => {
+                let value = self.get(depth - 1);
+                let flag = self.truthy(value);
+                let target = self.labels[label.as_str()];
+                let fallthrough = self.builder.create_block();
+                self.builder.ins().brif(flag, target, &[], fallthrough, &[]);
+                self.builder.switch_to_block(fallthrough);
+            }
+            IRInstruction::Label(_) | IRInstruction::Pop | IRInstruction::Line(..) => {}
+            IRInstruction::Dup => {
+                let value = self.get(depth - 1);
+                self.set(depth, value);
+            }
+        }
+        true
+    }
+
+    fn translate_binary_op(&mut self, op: &BinaryOp, left: Value, right: Value) -> Value {
+        let function = runtime::binary_function(op);
+        // Cranelift has no floating-point remainder, so `%` always calls fmod
+        if matches!(op, BinaryOp::And | BinaryOp::Or | BinaryOp::Mod) {
+            let result = self.runtime_call(function, &[left, right]);
+            return self
+                .builder
+                .ins()
+                .bitcast(types::F64, MemFlagsData::new(), result);
+        }
+        self.dispatch(&[left, right], function, types::F64, |t| {
+            // Ordered comparisons are false when either side is NaN
+            let condition = match op {
+                BinaryOp::Add => return t.builder.ins().fadd(left, right),
+                BinaryOp::Sub => return t.builder.ins().fsub(left, right),
+                BinaryOp::Mul => return t.builder.ins().fmul(left, right),
+                BinaryOp::Div => return t.builder.ins().fdiv(left, right),
+                BinaryOp::Eq | BinaryOp::StrictEq => FloatCC::Equal,
+                // True when either side is NaN
+                BinaryOp::Neq => FloatCC::NotEqual,
+                BinaryOp::Lt => FloatCC::LessThan,
+                BinaryOp::Gt => FloatCC::GreaterThan,
+                BinaryOp::Le => FloatCC::LessThanOrEqual,
+                BinaryOp::Ge => FloatCC::GreaterThanOrEqual,
+                BinaryOp::And | BinaryOp::Or | BinaryOp::Mod => unreachable!(),
+            };
+            let flag = t.builder.ins().fcmp(condition, left, right);
+            t.bool_from_flag(flag)
+        })
+    }
+
+    /// Call the runtime function `name`, returning its only result.
+    fn call_function(&mut self, name: &str, args: &[Value]) -> Value {
+        let callee = self
+            .module
+            .declare_func_in_func(self.runtime_functions[name], self.builder.func);
+        let call = self.builder.ins().call(callee, args);
+        self.builder.inst_results(call)[0]
+    }
+
+    /// Call `name` with the `argc` arguments below `depth`, leaving the
+    /// result in the first one's slot.
+    fn translate_call(&mut self, name: &str, argc: u16, depth: usize) {
+        let base = depth - argc as usize;
+        let args: Vec<Value> = (base..depth).map(|slot| self.get(slot)).collect();
+        let value = if runtime::is_builtin(name) {
+            self.translate_print(&args)
+        } else if let Some(function) = self.externs.get(name).cloned() {
+            self.translate_extern_call(&function, &args)
+        } else {
+            let callee = self
+                .module
+                .declare_func_in_func(self.callees[name], self.builder.func);
+            let call = self.builder.ins().call(callee, &args);
+            self.builder.inst_results(call)[0]
+        };
+        self.set(base, value);
+    }
+
+    /// Call a C function, converting the arguments to its parameter types
+    /// and the result back into a value.
+    fn translate_extern_call(&mut self, function: &ExternFunction, args: &[Value]) -> Value {
+        let mut c_args = Vec::new();
+        for (&arg, &ty) in args.iter().zip(&function.params) {
+            let bits = self.bits(arg);
+            let converted = self.call_function(runtime::to_c_function(Some(ty)), &[bits]);
+            c_args.push(if ty == CType::Int {
+                self.builder.ins().ireduce(types::I32, converted)
+            } else {
+                converted
+            });
+        }
+        let callee = self
+            .module
+            .declare_func_in_func(self.callees[&function.name], self.builder.func);
+        let call = self.builder.ins().call(callee, &c_args);
+        let Some(convert) = runtime::from_c_function(function.returns) else {
+            return self.constant(runtime::UNDEFINED);
+        };
+        let result = self.builder.inst_results(call)[0];
+        let bits = self.call_function(convert, &[result]);
+        self.builder
+            .ins()
+            .bitcast(types::F64, MemFlagsData::new(), bits)
+    }
+
+    /// `print(a, b, ...)` prints each argument through the runtime, separated
+    /// by spaces and terminated by a newline. Returns undefined.
+    fn translate_print(&mut self, args: &[Value]) -> Value {
+        if args.is_empty() {
+            let callee = self
+                .module
+                .declare_func_in_func(self.print_newline, self.builder.func);
+            self.builder.ins().call(callee, &[]);
+        }
+        let callee = self
+            .module
+            .declare_func_in_func(self.print_value, self.builder.func);
+        for (i, &arg) in args.iter().enumerate() {
+            let separator = if i + 1 == args.len() { b'\n' } else { b' ' };
+            let bits = self
+                .builder
+                .ins()
+                .bitcast(types::I64, MemFlagsData::new(), arg);
+            let separator = self.builder.ins().iconst(types::I32, separator as i64);
+            self.builder.ins().call(callee, &[bits, separator]);
+        }
+        self.constant(runtime::UNDEFINED)
+    }
+}
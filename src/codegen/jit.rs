@@ -0,0 +1,688 @@
+//! JIT execution: assembles the output of the native backend for the host
+//! (x64 or ARM64) into executable memory and calls the compiled functions in
+//! process.
+
+use super::{assembler, generate_code, runtime, CodegenOptions, Target, TargetSpec};
+use crate::ir::{BinaryOp, IRModule, UnaryOp};
+use crate::vm::{Value, VM};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::io::Write;
+
+/// Most arguments a call into compiled code can pass.
+pub const MAX_ARGS: usize = 16;
+
+thread_local! {
+    /// Text compiled code printed on this thread, when captured instead of
+    /// going to stdout.
+    static OUTPUT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// A module compiled to machine code in this process.
+pub struct JitModule {
+    memory: ExecutableMemory,
+    /// Offset into the code and arity of each JS function.
+    functions: HashMap<String, (usize, usize)>,
+}
+
+impl JitModule {
+    pub fn compile(module: IRModule) -> Self {
+        let target = host_target();
+        let options = CodegenOptions::default();
+        let arities: Vec<(String, usize)> = module
+            .functions
+            .iter()
+            .map(|function| (function.name.clone(), function.params.len()))
+            .collect();
+
+        let mut externals = runtime_functions(&options.target);
+        for function in &module.externs {
+            externals.insert(
+                symbol(&function.name, &options.target),
+                c_function(&function.name),
+            );
+        }
+
+        let assembly = generate_code(module, target.clone(), &options).unwrap();
+        let image = assembler::assemble_image(&assembly, &target, &externals);
+        let memory = ExecutableMemory::new(&image.bytes);
+
+        let functions = arities
+            .into_iter()
+            .map(|(name, arity)| {
+                let offset = image.symbols[&symbol(runtime::function_name(&name), &options.target)];
+                (name, (offset, arity))
+            })
+            .collect();
+        Self { memory, functions }
+    }
+
+    /// Call the JS function `name`. Arguments and the result are native
+    /// values, as described in `runtime.rs`.
+    pub fn call(&self, name: &str, args: &[u64]) -> u64 {
+        let &(offset, arity) = self
+            .functions
+            .get(name)
+            .unwrap_or_else(|| panic!("Undefined function: {}", name));
+        if args.len() != arity {
+            panic!("{} takes {} arguments, got {}", name, arity, args.len());
+        }
+        if arity > MAX_ARGS {
+            panic!(
+                "{} takes {} arguments, but JIT calls pass at most {}",
+                name, arity, MAX_ARGS
+            );
+        }
+        let address = self.memory.address() + offset;
+
+        // SAFETY: `address` is the entry of a compiled function taking
+        // `arity` values and returning one, in memory that lives as long as
+        // `self`. The x64 backend passes values in SSE registers and the
+        // ARM64 one in general-purpose registers.
+        let result = unsafe {
+            if cfg!(target_arch = "x86_64") {
+                let args: Vec<f64> = args.iter().map(|&a| f64::from_bits(a)).collect();
+                call_with::<f64>(address, &args).to_bits()
+            } else {
+                call_with::<u64>(address, args)
+            }
+        };
+        std::io::stdout().flush().unwrap();
+        result
+    }
+
+    /// Keep what compiled code prints on this thread, for `take_output`,
+    /// instead of writing it to stdout.
+    pub fn capture_output(&self) {
+        OUTPUT.with(|output| {
+            output.borrow_mut().get_or_insert_with(String::new);
+        });
+    }
+
+    /// Output captured on this thread since the last call; empty unless
+    /// `capture_output` was called.
+    pub fn take_output(&self) -> String {
+        OUTPUT.with(|output| {
+            output
+                .borrow_mut()
+                .as_mut()
+                .map(std::mem::take)
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Call the function at `address`, passing and returning values as `T`.
+///
+/// # Safety
+/// `address` must be a function taking `args.len()` `T`s, at most
+/// `MAX_ARGS`, and returning one.
+unsafe fn call_with<T: Copy>(address: usize, args: &[T]) -> T {
+    use std::mem::transmute_copy;
+    // One arm for each number of arguments, casting `address` to a function
+    // taking that many
+    macro_rules! arms {
+        ($([$($arg:ident)*])*) => {
+            match *args {
+                $([$($arg),*] => {
+                    transmute_copy::<usize, extern "C" fn($(arms!(@type $arg)),*) -> T>(&address)(
+                        $($arg),*
+                    )
+                })*
+                _ => unreachable!("JIT calls take at most {} arguments", MAX_ARGS),
+            }
+        };
+        (@type $arg:ident) => { T };
+    }
+    arms! {
+        []
+        [a]
+        [a b]
+        [a b c]
+        [a b c d]
+        [a b c d e]
+        [a b c d e f]
+        [a b c d e f g]
+        [a b c d e f g h]
+        [a b c d e f g h i]
+        [a b c d e f g h i j]
+        [a b c d e f g h i j k]
+        [a b c d e f g h i j k l]
+        [a b c d e f g h i j k l m]
+        [a b c d e f g h i j k l m n]
+        [a b c d e f g h i j k l m n o]
+        [a b c d e f g h i j k l m n o p]
+    }
+}
+
+/// Backend whose output runs on this machine.
+fn host_target() -> Target {
+    if cfg!(target_arch = "x86_64") {
+        Target::X64
+    } else if cfg!(target_arch = "aarch64") {
+        Target::ARM64
+    } else {
+        panic!("The JIT only supports x86_64 and ARM64 hosts")
+    }
+}
+
+/// Name the native backends give a function on `target`.
+fn symbol(name: &str, target: &TargetSpec) -> String {
+    format!("{}{}", target.symbol_prefix(), name)
+}
+
+/// The runtime library, implemented here on top of the VM's operators
+/// instead of linking `runtime.c`.
+fn runtime_functions(target: &TargetSpec) -> HashMap<String, usize> {
+    type Binary = extern "C" fn(u64, u64) -> u64;
+    type Unary = extern "C" fn(u64) -> u64;
+    let functions = [
+        (
+            "jsrt_print_value",
+            jsrt_print_value as extern "C" fn(u64, i32) as usize,
+        ),
+        (
+            "jsrt_print_newline",
+            jsrt_print_newline as extern "C" fn() as usize,
+        ),
+        (
+            runtime::EXIT_FUNCTION,
+            jsrt_exit as extern "C" fn(u64) as usize,
+        ),
+        ("jsrt_truthy", jsrt_truthy as Unary as usize),
+        ("jsrt_add", jsrt_add as Binary as usize),
+        ("jsrt_sub", jsrt_sub as Binary as usize),
+        ("jsrt_mul", jsrt_mul as Binary as usize),
+        ("jsrt_div", jsrt_div as Binary as usize),
+        ("jsrt_mod", jsrt_mod as Binary as usize),
+        ("jsrt_eq", jsrt_eq as Binary as usize),
+        ("jsrt_ne", jsrt_ne as Binary as usize),
+        ("jsrt_strict_eq", jsrt_strict_eq as Binary as usize),
+        ("jsrt_lt", jsrt_lt as Binary as usize),
+        ("jsrt_gt", jsrt_gt as Binary as usize),
+        ("jsrt_le", jsrt_le as Binary as usize),
+        ("jsrt_ge", jsrt_ge as Binary as usize),
+        ("jsrt_and", jsrt_and as Binary as usize),
+        ("jsrt_or", jsrt_or as Binary as usize),
+        ("jsrt_neg", jsrt_neg as Unary as usize),
+        ("jsrt_not", jsrt_not as Unary as usize),
+        (
+            "jsrt_to_c_int",
+            jsrt_to_c_int as extern "C" fn(u64) -> i64 as usize,
+        ),
+        (
+            "jsrt_to_number",
+            jsrt_to_number as extern "C" fn(u64) -> f64 as usize,
+        ),
+        (
+            "jsrt_to_c_string",
+            jsrt_to_c_string as extern "C" fn(u64) -> *const c_char as usize,
+        ),
+        (
+            "jsrt_to_c_vararg",
+            jsrt_to_c_vararg as extern "C" fn(u64) -> i64 as usize,
+        ),
+        (
+            "jsrt_from_c_int",
+            jsrt_from_c_int as extern "C" fn(i32) -> u64 as usize,
+        ),
+        (
+            "jsrt_from_c_long",
+            jsrt_from_c_long as extern "C" fn(i64) -> u64 as usize,
+        ),
+        (
+            "jsrt_from_c_double",
+            jsrt_from_c_double as extern "C" fn(f64) -> u64 as usize,
+        ),
+        (
+            "jsrt_from_c_string",
+            jsrt_from_c_string as extern "C" fn(*const c_char) -> u64 as usize,
+        ),
+    ];
+    functions
+        .into_iter()
+        .map(|(name, address)| (symbol(name, target), address))
+        .collect()
+}
+
+/// Address of the C function `name` in this process, such as one from libc.
+#[cfg(unix)]
+fn c_function(name: &str) -> usize {
+    extern "C" {
+        fn dlsym(handle: *mut std::ffi::c_void, symbol: *const c_char) -> *mut std::ffi::c_void;
+    }
+    // RTLD_DEFAULT searches every object loaded into the process
+    let default = if cfg!(target_os = "macos") { -2 } else { 0 };
+    let symbol = CString::new(name).unwrap();
+    // SAFETY: `symbol` is a NUL-terminated string
+    let address = unsafe { dlsym(default as *mut _, symbol.as_ptr()) };
+    if address.is_null() {
+        panic!("Undefined C function: {}", name);
+    }
+    address as usize
+}
+
+#[cfg(not(unix))]
+fn c_function(name: &str) -> usize {
+    panic!("The JIT cannot call the C function {} on this host", name)
+}
+
+/// A value of the native representation, as described in `runtime.rs`, as
+/// the VM holds it.
+fn decode(value: u64) -> Value {
+    if value <= runtime::MAX_NUMBER {
+        return Value::Number(f64::from_bits(value));
+    }
+    match value & !runtime::PAYLOAD_MASK {
+        runtime::STRING_TAG => {
+            let pointer = (value & runtime::PAYLOAD_MASK) as *const c_char;
+            // SAFETY: strings point to NUL-terminated UTF-8 that is never freed
+            Value::String(unsafe { CStr::from_ptr(pointer) }.to_str().unwrap().into())
+        }
+        runtime::FALSE => Value::Boolean(value & 1 != 0),
+        runtime::NULL => Value::Null,
+        _ => Value::Undefined,
+    }
+}
+
+/// The native representation of a value the VM's operators made, which
+/// are never objects or functions.
+fn encode(value: Value) -> u64 {
+    match value {
+        Value::Number(n) => number(n),
+        Value::String(s) => string(s.to_string()),
+        Value::Boolean(b) => boolean(b),
+        Value::Null => runtime::NULL,
+        Value::Undefined => runtime::UNDEFINED,
+        Value::Object(_) | Value::Closure { .. } => {
+            unreachable!("Compiled code has no objects or functions as values")
+        }
+    }
+}
+
+fn number(n: f64) -> u64 {
+    if n.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        n.to_bits()
+    }
+}
+
+fn boolean(b: bool) -> u64 {
+    if b {
+        runtime::TRUE
+    } else {
+        runtime::FALSE
+    }
+}
+
+/// A string value owning a leaked copy of `text`.
+fn string(text: String) -> u64 {
+    let pointer = CString::new(text).unwrap().into_raw() as u64;
+    runtime::STRING_TAG | (pointer & runtime::PAYLOAD_MASK)
+}
+
+/// Format a value the way the VM prints it.
+pub fn to_string(value: u64) -> String {
+    VM::to_string(&decode(value))
+}
+
+fn to_number(value: u64) -> f64 {
+    VM::to_number(&decode(value))
+}
+
+// The operators are the VM's, so that compiled code and the VM agree
+fn binary(op: BinaryOp, left: u64, right: u64) -> u64 {
+    encode(VM::binary(op, decode(left), decode(right)))
+}
+
+extern "C" fn jsrt_truthy(value: u64) -> u64 {
+    VM::to_boolean(&decode(value)) as u64
+}
+
+extern "C" fn jsrt_add(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Add, left, right)
+}
+
+extern "C" fn jsrt_sub(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Sub, left, right)
+}
+
+extern "C" fn jsrt_mul(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Mul, left, right)
+}
+
+extern "C" fn jsrt_div(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Div, left, right)
+}
+
+extern "C" fn jsrt_mod(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Mod, left, right)
+}
+
+extern "C" fn jsrt_eq(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Eq, left, right)
+}
+
+extern "C" fn jsrt_ne(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Neq, left, right)
+}
+
+extern "C" fn jsrt_strict_eq(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::StrictEq, left, right)
+}
+
+extern "C" fn jsrt_lt(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Lt, left, right)
+}
+
+extern "C" fn jsrt_gt(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Gt, left, right)
+}
+
+extern "C" fn jsrt_le(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Le, left, right)
+}
+
+extern "C" fn jsrt_ge(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Ge, left, right)
+}
+
+extern "C" fn jsrt_and(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::And, left, right)
+}
+
+extern "C" fn jsrt_or(left: u64, right: u64) -> u64 {
+    binary(BinaryOp::Or, left, right)
+}
+
+extern "C" fn jsrt_neg(value: u64) -> u64 {
+    encode(VM::unary(UnaryOp::Neg, decode(value)))
+}
+
+extern "C" fn jsrt_not(value: u64) -> u64 {
+    encode(VM::unary(UnaryOp::Not, decode(value)))
+}
+
+extern "C" fn jsrt_to_c_int(value: u64) -> i64 {
+    let n = to_number(value);
+    // NaN fails the comparison too
+    if n.abs() < 9223372036854775808.0 {
+        n as i64
+    } else {
+        0
+    }
+}
+
+extern "C" fn jsrt_to_number(value: u64) -> f64 {
+    to_number(value)
+}
+
+extern "C" fn jsrt_to_c_string(value: u64) -> *const c_char {
+    match decode(value) {
+        Value::String(_) => (value & runtime::PAYLOAD_MASK) as *const c_char,
+        _ => CString::new(to_string(value)).unwrap().into_raw(),
+    }
+}
+
+extern "C" fn jsrt_to_c_vararg(value: u64) -> i64 {
+    match decode(value) {
+        Value::String(_) => (value & runtime::PAYLOAD_MASK) as i64,
+        _ => jsrt_to_c_int(value),
+    }
+}
+
+extern "C" fn jsrt_from_c_int(i: i32) -> u64 {
+    number(i as f64)
+}
+
+extern "C" fn jsrt_from_c_long(i: i64) -> u64 {
+    number(i as f64)
+}
+
+extern "C" fn jsrt_from_c_double(d: f64) -> u64 {
+    number(d)
+}
+
+extern "C" fn jsrt_from_c_string(chars: *const c_char) -> u64 {
+    if chars.is_null() {
+        runtime::NULL
+    } else {
+        runtime::STRING_TAG | (chars as u64 & runtime::PAYLOAD_MASK)
+    }
+}
+
+/// Print one argument of `print(...)` the way the VM does, followed by
+/// `separator`.
+extern "C" fn jsrt_print_value(value: u64, separator: i32) {
+    write_output(&format!("{}{}", to_string(value), separator as u8 as char));
+}
+
+extern "C" fn jsrt_print_newline() {
+    write_output("\n");
+}
+
+/// Write printed text to stdout, or keep it if this thread captures output.
+fn write_output(text: &str) {
+    OUTPUT.with(|output| match &mut *output.borrow_mut() {
+        Some(output) => output.push_str(text),
+        None => print!("{}", text),
+    })
+}
+
+/// Exit with the status `value` gives, for the generated C `main`; calls into
+/// the JIT start at the JS functions instead.
+extern "C" fn jsrt_exit(value: u64) {
+    std::io::stdout().flush().unwrap();
+    std::process::exit(decode(value).exit_status())
+}
+
+/// Pages holding a copy of an [`assembler::Image`], mapped read + execute.
+struct ExecutableMemory {
+    pointer: *mut u8,
+    /// Only unmapped on Unix, the only hosts with a mapping.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    length: usize,
+}
+
+#[cfg(unix)]
+mod mman {
+    use std::ffi::{c_int, c_void};
+
+    pub const PROT_READ: c_int = 1;
+    pub const PROT_WRITE: c_int = 2;
+    pub const PROT_EXEC: c_int = 4;
+    pub const MAP_PRIVATE: c_int = 2;
+    #[cfg(target_os = "macos")]
+    pub const MAP_ANONYMOUS: c_int = 0x1000;
+    #[cfg(not(target_os = "macos"))]
+    pub const MAP_ANONYMOUS: c_int = 0x20;
+    /// Apple Silicon only allows pages that are both writable and executable
+    /// when they are mapped for JIT use; each thread then toggles whether it
+    /// may write to them.
+    #[cfg(target_os = "macos")]
+    pub const MAP_JIT: c_int = 0x800;
+
+    extern "C" {
+        pub fn mmap(
+            addr: *mut c_void,
+            len: usize,
+            prot: c_int,
+            flags: c_int,
+            fd: c_int,
+            offset: i64,
+        ) -> *mut c_void;
+        pub fn mprotect(addr: *mut c_void, len: usize, prot: c_int) -> c_int;
+        pub fn munmap(addr: *mut c_void, len: usize) -> c_int;
+    }
+
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    extern "C" {
+        pub fn pthread_jit_write_protect_np(enabled: c_int);
+        pub fn sys_icache_invalidate(start: *mut c_void, len: usize);
+    }
+}
+
+#[cfg(all(unix, not(all(target_os = "macos", target_arch = "aarch64"))))]
+impl ExecutableMemory {
+    fn new(bytes: &[u8]) -> Self {
+        use mman::*;
+
+        let length = bytes.len().max(1);
+        // SAFETY: a fresh anonymous mapping is written while it is writable
+        // and only made executable afterwards
+        unsafe {
+            let pointer = mmap(
+                std::ptr::null_mut(),
+                length,
+                PROT_READ | PROT_WRITE,
+                MAP_PRIVATE | MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if pointer as isize == -1 {
+                panic!("Failed to map JIT memory");
+            }
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), pointer as *mut u8, bytes.len());
+            // On ARM64 Linux the kernel makes the instruction cache coherent
+            // when the pages become executable
+            if mprotect(pointer, length, PROT_READ | PROT_EXEC) != 0 {
+                panic!("Failed to make JIT memory executable");
+            }
+            Self {
+                pointer: pointer as *mut u8,
+                length,
+            }
+        }
+    }
+}
+
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+impl ExecutableMemory {
+    fn new(bytes: &[u8]) -> Self {
+        use mman::*;
+
+        let length = bytes.len().max(1);
+        // SAFETY: this thread only writes the mapping while writes are
+        // enabled, and flushes the instruction cache before running it
+        unsafe {
+            let pointer = mmap(
+                std::ptr::null_mut(),
+                length,
+                PROT_READ | PROT_WRITE | PROT_EXEC,
+                MAP_PRIVATE | MAP_ANONYMOUS | MAP_JIT,
+                -1,
+                0,
+            );
+            if pointer as isize == -1 {
+                panic!("Failed to map JIT memory");
+            }
+            pthread_jit_write_protect_np(0);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), pointer as *mut u8, bytes.len());
+            pthread_jit_write_protect_np(1);
+            sys_icache_invalidate(pointer, length);
+            Self {
+                pointer: pointer as *mut u8,
+                length,
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl ExecutableMemory {
+    fn new(_bytes: &[u8]) -> Self {
+        panic!("The JIT is only supported on Unix hosts")
+    }
+}
+
+impl ExecutableMemory {
+    fn address(&self) -> usize {
+        self.pointer as usize
+    }
+}
+
+impl Drop for ExecutableMemory {
+    fn drop(&mut self) {
+        // SAFETY: the mapping is not referenced once its module is gone
+        #[cfg(unix)]
+        unsafe {
+            mman::munmap(self.pointer as *mut _, self.length);
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    unix,
+    any(
+        all(target_arch = "x86_64", feature = "x64"),
+        all(target_arch = "aarch64", feature = "arm64")
+    )
+))]
+mod tests {
+    use super::*;
+
+    fn compile(source: &str) -> JitModule {
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        JitModule::compile(module)
+    }
+
+    #[test]
+    fn test_jit_calls_compiled_functions() {
+        let jit = compile(
+            "function add(a, b) { return a + b; }
+             function pick(c, a, b) { if (c) { return a; } return b; }
+             function main() { print(true, null); return add(true, add(true, true)); }",
+        );
+        let n = |x: f64| x.to_bits();
+        assert_eq!(jit.call("main", &[]), n(3.0));
+        assert_eq!(jit.call("add", &[n(1.5), n(2.25)]), n(3.75));
+        assert_eq!(jit.call("add", &[runtime::TRUE, runtime::NULL]), n(1.0));
+        assert_eq!(jit.call("pick", &[n(0.0), n(1.0), n(2.0)]), n(2.0));
+        assert_eq!(jit.call("pick", &[n(f64::NAN), n(1.0), n(2.0)]), n(2.0));
+        assert_eq!(jit.call("pick", &[n(-4.0), n(1.0), n(2.0)]), n(1.0));
+        assert_eq!(jit.call("pick", &[runtime::FALSE, n(1.0), n(2.0)]), n(2.0));
+        assert_eq!(
+            jit.call("pick", &[runtime::TRUE, runtime::NULL, n(2.0)]),
+            runtime::NULL
+        );
+    }
+
+    #[test]
+    fn test_jit_calls_with_stack_arguments() {
+        let params: Vec<String> = (0..MAX_ARGS).map(|i| format!("p{}", i)).collect();
+        let jit = compile(&format!(
+            "function last({}) {{ return p{} - p0 + \"\" + p1; }}",
+            params.join(", "),
+            MAX_ARGS - 1
+        ));
+        let args: Vec<u64> = (0..MAX_ARGS).map(|i| (i as f64 * 2.0).to_bits()).collect();
+        assert_eq!(to_string(jit.call("last", &args)), "302");
+    }
+
+    #[test]
+    fn test_jit_captures_output() {
+        let jit = compile(r#"function main() { print(1 / 0, "1" == 1, "1e21" * 1); }"#);
+        jit.capture_output();
+        jit.call("main", &[]);
+        assert_eq!(jit.take_output(), "Infinity true 1e+21\n");
+        assert_eq!(jit.take_output(), "");
+    }
+
+    #[test]
+    fn test_jit_calls_c_functions() {
+        let jit = compile(
+            "extern function abs(int): int;
+             extern function strlen(string): long;
+             extern function ldexp(double, int): double;
+             extern function getenv(string): string;
+             function main() {
+                 let t = true; let four = t + t + t + t;
+                 return abs(-four) + ldexp(four, -t) + strlen(t) + getenv(null);
+             }",
+        );
+        assert_eq!(jit.call("main", &[]), 10.0f64.to_bits());
+    }
+}
@@ -0,0 +1,674 @@
+use super::{closures_unsupported, objects_unsupported, runtime, CodeGenerator, Target};
+use crate::ir::{
+    BinaryOp, CType, Constant, ExternFunction, IRFunction, IRInstruction, IRModule, UnaryOp,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::io;
+
+/// Runtime functions converting between native values and C types at calls
+/// to extern functions.
+const C_CONVERSIONS: [&str; 8] = [
+    "declare i64 @jsrt_to_c_int(i64)",
+    "declare double @jsrt_to_number(i64)",
+    "declare ptr @jsrt_to_c_string(i64)",
+    "declare i64 @jsrt_to_c_vararg(i64)",
+    "declare i64 @jsrt_from_c_int(i32)",
+    "declare i64 @jsrt_from_c_long(i64)",
+    "declare i64 @jsrt_from_c_double(double)",
+    "declare i64 @jsrt_from_c_string(ptr)",
+];
+
+/// LLVM type of a C type.
+fn c_type(ty: CType) -> &'static str {
+    match ty {
+        CType::Int => "i32",
+        CType::Long => "i64",
+        CType::Double => "double",
+        CType::String => "ptr",
+        CType::Void => "void",
+    }
+}
+
+/// Lowers the stack IR to textual LLVM IR. Every value is a `double` holding
+/// the bits of a native value (see `runtime.rs`); operand stack slots and
+/// variables live in `alloca`s, which LLVM's `mem2reg` pass turns into SSA
+/// registers.
+pub struct LlvmGenerator {
+    output: String,
+    string_literals: Vec<String>,
+    /// Functions called but not defined in the module, with their arity.
+    external_functions: BTreeMap<String, u16>,
+    /// Runtime functions called for operands that are not numbers.
+    runtime_functions: BTreeSet<(&'static str, usize)>,
+    /// C functions declared with `extern function`.
+    externs: Vec<ExternFunction>,
+    /// The module's constants, which `PushConst` indexes.
+    constants: Vec<Constant>,
+    /// Functions defined in the module, which `Call` indexes.
+    defined: Vec<String>,
+    next_temporary: usize,
+    next_block: usize,
+}
+
+impl Default for LlvmGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LlvmGenerator {
+    pub fn new() -> Self {
+        Self {
+            output: String::new(),
+            string_literals: Vec::new(),
+            external_functions: BTreeMap::new(),
+            runtime_functions: BTreeSet::new(),
+            externs: Vec::new(),
+            constants: Vec::new(),
+            defined: Vec::new(),
+            next_temporary: 0,
+            next_block: 0,
+        }
+    }
+
+    fn temporary(&mut self) -> String {
+        self.next_temporary += 1;
+        format!("%t{}", self.next_temporary)
+    }
+
+    fn block(&mut self) -> String {
+        self.next_block += 1;
+        format!("b{}", self.next_block)
+    }
+
+    fn slot(depth: usize) -> String {
+        format!("%s{}", depth)
+    }
+
+    fn variable(name: &str) -> String {
+        format!("%v.{}", name)
+    }
+
+    fn load(&mut self, from: &str) -> String {
+        let value = self.temporary();
+        writeln!(self.output, "  {} = load double, ptr {}", value, from).unwrap();
+        value
+    }
+
+    fn store(&mut self, value: &str, to: &str) {
+        writeln!(self.output, "  store double {}, ptr {}", value, to).unwrap();
+    }
+
+    /// Double literal with the bits of a native value.
+    fn constant(bits: u64) -> String {
+        format!("0x{:016X}", bits)
+    }
+
+    fn bits(&mut self, value: &str) -> String {
+        let bits = self.temporary();
+        writeln!(self.output, "  {} = bitcast double {} to i64", bits, value).unwrap();
+        bits
+    }
+
+    /// Call a runtime function on native values, returning its `i64` result.
+    fn runtime_call(&mut self, name: &'static str, operands: &[&str]) -> String {
+        self.runtime_functions.insert((name, operands.len()));
+        let args: Vec<String> = operands
+            .iter()
+            .map(|operand| {
+                let bits = self.bits(operand);
+                format!("i64 {}", bits)
+            })
+            .collect();
+        let result = self.temporary();
+        writeln!(
+            self.output,
+            "  {} = call i64 @{}({})",
+            result,
+            name,
+            args.join(", ")
+        )
+        .unwrap();
+        result
+    }
+
+    /// Value of type `ty` (`double` or `i1`): `fast` when all `operands` are
+    /// numbers, else the result of the runtime function `slow`.
+    fn dispatch(
+        &mut self,
+        operands: &[&str],
+        slow: &'static str,
+        ty: &str,
+        fast: impl FnOnce(&mut Self) -> String,
+    ) -> String {
+        let mut all_numbers = "true".to_string();
+        for operand in operands {
+            let bits = self.bits(operand);
+            let is_number = self.temporary();
+            writeln!(
+                self.output,
+                "  {} = icmp ule i64 {}, {}",
+                is_number,
+                bits,
+                runtime::MAX_NUMBER as i64
+            )
+            .unwrap();
+            let both = self.temporary();
+            writeln!(
+                self.output,
+                "  {} = and i1 {}, {}",
+                both, all_numbers, is_number
+            )
+            .unwrap();
+            all_numbers = both;
+        }
+        let (fast_block, slow_block, done) = (self.block(), self.block(), self.block());
+        writeln!(
+            self.output,
+            "  br i1 {}, label %{}, label %{}",
+            all_numbers, fast_block, slow_block
+        )
+        .unwrap();
+
+        writeln!(self.output, "{}:", fast_block).unwrap();
+        let fast_value = fast(self);
+        writeln!(self.output, "  br label %{}", done).unwrap();
+
+        writeln!(self.output, "{}:", slow_block).unwrap();
+        let result = self.runtime_call(slow, operands);
+        let slow_value = self.temporary();
+        if ty == "i1" {
+            writeln!(self.output, "  {} = icmp ne i64 {}, 0", slow_value, result).unwrap();
+        } else {
+            writeln!(
+                self.output,
+                "  {} = bitcast i64 {} to double",
+                slow_value, result
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "  br label %{}", done).unwrap();
+
+        writeln!(self.output, "{}:", done).unwrap();
+        let value = self.temporary();
+        writeln!(
+            self.output,
+            "  {} = phi {} [ {}, %{} ], [ {}, %{} ]",
+            value, ty, fast_value, fast_block, slow_value, slow_block
+        )
+        .unwrap();
+        value
+    }
+
+    /// `i1` that is true when `value` is truthy.
+    fn truthy(&mut self, value: &str) -> String {
+        self.dispatch(&[value], "jsrt_truthy", "i1", |g| {
+            // Numbers are truthy unless +-0.0 or NaN
+            let flag = g.temporary();
+            writeln!(g.output, "  {} = fcmp one double {}, 0.0", flag, value).unwrap();
+            flag
+        })
+    }
+
+    /// Convert an `i1` flag to a boolean value.
+    fn bool_from_flag(&mut self, flag: &str) -> String {
+        let bit = self.temporary();
+        writeln!(self.output, "  {} = zext i1 {} to i64", bit, flag).unwrap();
+        let bits = self.temporary();
+        writeln!(
+            self.output,
+            "  {} = or i64 {}, {}",
+            bits,
+            bit,
+            runtime::FALSE as i64
+        )
+        .unwrap();
+        let value = self.temporary();
+        writeln!(self.output, "  {} = bitcast i64 {} to double", value, bits).unwrap();
+        value
+    }
+
+    /// The C `main`: call the JS `main`, then exit with the status its
+    /// result gives.
+    fn generate_entry_point(&mut self) {
+        writeln!(self.output, "define i32 @main() {{").unwrap();
+        writeln!(self.output, "entry:").unwrap();
+        writeln!(
+            self.output,
+            "  %result = call double @{}()",
+            runtime::JS_MAIN
+        )
+        .unwrap();
+        writeln!(self.output, "  %bits = bitcast double %result to i64").unwrap();
+        writeln!(
+            self.output,
+            "  call void @{}(i64 %bits)",
+            runtime::EXIT_FUNCTION
+        )
+        .unwrap();
+        writeln!(self.output, "  unreachable").unwrap();
+        writeln!(self.output, "}}\n").unwrap();
+    }
+
+    fn generate_function(&mut self, function: &IRFunction) {
+        self.next_temporary = 0;
+        self.next_block = 0;
+
+        let params: Vec<String> = (0..function.params.len())
+            .map(|i| format!("double %p{}", i))
+            .collect();
+        writeln!(
+            self.output,
+            "define double @{}({}) {{",
+            runtime::function_name(&function.name),
+            params.join(", ")
+        )
+        .unwrap();
+        writeln!(self.output, "entry:").unwrap();
+
+        // One alloca per operand stack slot and per variable
+        let depths = function.stack_depths();
+        for depth in 0..function.max_stack_depth() {
+            writeln!(self.output, "  {} = alloca double", Self::slot(depth)).unwrap();
+        }
+        let mut variables: Vec<&str> = function.params.iter().map(String::as_str).collect();
+        for instruction in function.block_instructions() {
+            if let IRInstruction::Load(name) | IRInstruction::Store(name) = instruction {
+                if !variables.contains(&name.as_str()) {
+                    variables.push(name);
+                }
+            }
+        }
+        for name in &variables {
+            writeln!(self.output, "  {} = alloca double", Self::variable(name)).unwrap();
+        }
+        for (i, param) in function.params.iter().enumerate() {
+            self.store(&format!("%p{}", i), &Self::variable(param));
+        }
+
+        // Whether the current block still needs a terminator
+        let mut open = true;
+        for (instruction, depth) in function.instructions().iter().zip(&depths) {
+            if let IRInstruction::Label(label) = instruction {
+                if open {
+                    writeln!(self.output, "  br label %L{}", label).unwrap();
+                }
+                writeln!(self.output, "L{}:", label).unwrap();
+                open = true;
+                continue;
+            }
+            let Some(depth) = *depth else {
+                continue;
+            };
+            if !open {
+                // Code after a terminator only runs when jumped to
+                let block = self.block();
+                writeln!(self.output, "{}:", block).unwrap();
+            }
+            open = self.generate_instruction(instruction, depth);
+        }
+        if open {
+            writeln!(
+                self.output,
+                "  ret double {}",
+                Self::constant(runtime::UNDEFINED)
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "}}\n").unwrap();
+    }
+
+    /// Emit one instruction, with `depth` values on the operand stack.
+    /// Returns false when it terminated the current block.
+    fn generate_instruction(&mut self, instruction: &IRInstruction, depth: usize) -> bool {
+        match instruction {
+            IRInstruction::PushConst(index) => {
+                let constant = self.constants[*index as usize].clone();
+                let value = self.generate_const(&constant);
+                self.store(&value, &Self::slot(depth));
+            }
+            IRInstruction::Load(name) => {
+                let value = self.load(&Self::variable(name));
+                self.store(&value, &Self::slot(depth));
+            }
+            IRInstruction::Store(name) => {
+                let value = self.load(&Self::slot(depth - 1));
+                self.store(&value, &Self::variable(name));
+            }
+            IRInstruction::LoadUpvalue(_)
+            | IRInstruction::StoreUpvalue(_)
+            | IRInstruction::MakeClosure(..) => closures_unsupported(Target::LlvmIr),
+            IRInstruction::GetProperty(_) => objects_unsupported(Target::LlvmIr),
+            IRInstruction::Binary(op) => {
+                let left = self.load(&Self::slot(depth - 2));
+                let right = self.load(&Self::slot(depth - 1));
+                let value = self.generate_binary_op(op, &left, &right);
+                self.store(&value, &Self::slot(depth - 2));
+            }
+            IRInstruction::Unary(op) => {
+                let operand = self.load(&Self::slot(depth - 1));
+                let value = self.dispatch(
+                    &[&operand],
+                    runtime::unary_function(op),
+                    "double",
+                    |g| match op {
+                        UnaryOp::Neg => {
+                            let value = g.temporary();
+                            writeln!(g.output, "  {} = fneg double {}", value, operand).unwrap();
+                            value
+                        }
+                        UnaryOp::Not => {
+                            let flag = g.temporary();
+                            writeln!(g.output, "  {} = fcmp ueq double {}, 0.0", flag, operand)
+                                .unwrap();
+                            g.bool_from_flag(&flag)
+                        }
+                    },
+                );
+                self.store(&value, &Self::slot(depth - 1));
+            }
+            IRInstruction::Call(id, argc) => {
+                let name = self.defined[*id as usize].clone();
+                self.generate_call(&name, *argc, depth)
+            }
+            IRInstruction::CallNative(name, argc) => self.generate_call(name, *argc, depth),
+            IRInstruction::Return(has_value) => {
+                let value = if *has_value {
+                    self.load(&Self::slot(depth - 1))
+                } else {
+                    Self::constant(runtime::UNDEFINED)
+                };
+                writeln!(self.output, "  ret double {}", value).unwrap();
+                return false;
+            }
+            IRInstruction::Jump(label) => {
+                writeln!(self.output, "  br label %L{}", label).unwrap();
+                return false;
+            }
+            IRInstruction::JumpIf(label) => {
+                let value = self.load(&Self::slot(depth - 1));
+                let flag = self.truthy(&value);
+                let fallthrough = self.block();
+                writeln!(
+                    self.output,
+                    "  br i1 {}, label %L{}, label %{}",
+                    flag, label, fallthrough
+                )
+                .unwrap();
+                writeln!(self.output, "{}:", fallthrough).unwrap();
+            }
+            IRInstruction::Label(_) | IRInstruction::Pop | IRInstruction::Line(..) => {}
+            IRInstruction::Dup => {
+                let value = self.load(&Self::slot(depth - 1));
+                self.store(&value, &Self::slot(depth));
+            }
+        }
+        true
+    }
+
+    fn generate_const(&mut self, constant: &Constant) -> String {
+        match constant {
+            // Hexadecimal doubles are exact
+            Constant::Number(n) => format!("0x{:016X}", n.to_bits()),
+            Constant::String(s) => {
+                let index = self.string_literals.len();
+                self.string_literals.push(s.clone());
+                let address = self.temporary();
+                writeln!(
+                    self.output,
+                    "  {} = ptrtoint ptr @.str.{} to i64",
+                    address, index
+                )
+                .unwrap();
+                let bits = self.temporary();
+                writeln!(
+                    self.output,
+                    "  {} = or i64 {}, {}",
+                    bits,
+                    address,
+                    runtime::STRING_TAG as i64
+                )
+                .unwrap();
+                let value = self.temporary();
+                writeln!(self.output, "  {} = bitcast i64 {} to double", value, bits).unwrap();
+                value
+            }
+            Constant::Boolean(true) => Self::constant(runtime::TRUE),
+            Constant::Boolean(false) => Self::constant(runtime::FALSE),
+            Constant::Null => Self::constant(runtime::NULL),
+        }
+    }
+
+    fn generate_binary_op(&mut self, op: &BinaryOp, left: &str, right: &str) -> String {
+        let function = runtime::binary_function(op);
+        let (cmd, comparison) = match op {
+            BinaryOp::Add => ("fadd", false),
+            BinaryOp::Sub => ("fsub", false),
+            BinaryOp::Mul => ("fmul", false),
+            BinaryOp::Div => ("fdiv", false),
+            // frem is C's fmod, which truncates like `%`
+            BinaryOp::Mod => ("frem", false),
+            // Ordered comparisons are false when either side is NaN
+            BinaryOp::Eq | BinaryOp::StrictEq => ("fcmp oeq", true),
+            BinaryOp::Lt => ("fcmp olt", true),
+            BinaryOp::Gt => ("fcmp ogt", true),
+            BinaryOp::Le => ("fcmp ole", true),
+            BinaryOp::Ge => ("fcmp oge", true),
+            // ...and unordered ones true
+            BinaryOp::Neq => ("fcmp une", true),
+            BinaryOp::And | BinaryOp::Or => {
+                let result = self.runtime_call(function, &[left, right]);
+                let value = self.temporary();
+                writeln!(
+                    self.output,
+                    "  {} = bitcast i64 {} to double",
+                    value, result
+                )
+                .unwrap();
+                return value;
+            }
+        };
+        self.dispatch(&[left, right], function, "double", |g| {
+            let value = g.temporary();
+            writeln!(g.output, "  {} = {} double {}, {}", value, cmd, left, right).unwrap();
+            if comparison {
+                g.bool_from_flag(&value)
+            } else {
+                value
+            }
+        })
+    }
+
+    /// Call a C function, converting the arguments to its parameter types
+    /// (variadic ones to `i64`) and the result back into a value.
+    /// Call `name` with the `argc` arguments below `depth`, leaving the
+    /// result in the first one's slot.
+    fn generate_call(&mut self, name: &str, argc: u16, depth: usize) {
+        let base = depth - argc as usize;
+        let args: Vec<String> = (base..depth)
+            .map(|slot| self.load(&Self::slot(slot)))
+            .collect();
+        let extern_function = self.externs.iter().find(|f| f.name == name).cloned();
+        let value = if runtime::is_builtin(name) {
+            self.generate_print(&args)
+        } else if let Some(function) = extern_function {
+            self.generate_extern_call(&function, &args)
+        } else {
+            let args: Vec<String> = args.iter().map(|a| format!("double {}", a)).collect();
+            let value = self.temporary();
+            writeln!(
+                self.output,
+                "  {} = call double @{}({})",
+                value,
+                runtime::function_name(name),
+                args.join(", ")
+            )
+            .unwrap();
+            self.external_functions.insert(name.to_string(), argc);
+            value
+        };
+        self.store(&value, &Self::slot(base));
+    }
+
+    fn generate_extern_call(&mut self, function: &ExternFunction, args: &[String]) -> String {
+        let mut c_args = Vec::new();
+        for (i, arg) in args.iter().enumerate() {
+            let ty = function.params.get(i).copied();
+            let convert = runtime::to_c_function(ty);
+            let bits = self.bits(arg);
+            let result_type = match ty {
+                Some(CType::Int) | None => "i64",
+                Some(ty) => c_type(ty),
+            };
+            let converted = self.temporary();
+            writeln!(
+                self.output,
+                "  {} = call {} @{}(i64 {})",
+                converted, result_type, convert, bits
+            )
+            .unwrap();
+            let converted = if ty == Some(CType::Int) {
+                let truncated = self.temporary();
+                writeln!(
+                    self.output,
+                    "  {} = trunc i64 {} to i32",
+                    truncated, converted
+                )
+                .unwrap();
+                truncated
+            } else {
+                converted
+            };
+            c_args.push(format!("{} {}", ty.map_or("i64", c_type), converted));
+        }
+
+        // Variadic calls need the function type spelled out
+        let returns = c_type(function.returns);
+        let callee = if function.variadic {
+            let mut params: Vec<&str> = function.params.iter().map(|&ty| c_type(ty)).collect();
+            params.push("...");
+            format!("{} ({})", returns, params.join(", "))
+        } else {
+            returns.to_string()
+        };
+        let call = format!("call {} @{}({})", callee, function.name, c_args.join(", "));
+        let Some(convert) = runtime::from_c_function(function.returns) else {
+            writeln!(self.output, "  {}", call).unwrap();
+            return Self::constant(runtime::UNDEFINED);
+        };
+        let result = self.temporary();
+        writeln!(self.output, "  {} = {}", result, call).unwrap();
+        let bits = self.temporary();
+        writeln!(
+            self.output,
+            "  {} = call i64 @{}({} {})",
+            bits, convert, returns, result
+        )
+        .unwrap();
+        let value = self.temporary();
+        writeln!(self.output, "  {} = bitcast i64 {} to double", value, bits).unwrap();
+        value
+    }
+
+    /// `print(a, b, ...)` prints each argument through the runtime, separated
+    /// by spaces and terminated by a newline. Returns undefined.
+    fn generate_print(&mut self, args: &[String]) -> String {
+        if args.is_empty() {
+            writeln!(self.output, "  call void @jsrt_print_newline()").unwrap();
+        }
+        for (i, arg) in args.iter().enumerate() {
+            let separator = if i + 1 == args.len() { b'\n' } else { b' ' };
+            let bits = self.temporary();
+            writeln!(self.output, "  {} = bitcast double {} to i64", bits, arg).unwrap();
+            writeln!(
+                self.output,
+                "  call void @jsrt_print_value(i64 {}, i32 {})",
+                bits, separator
+            )
+            .unwrap();
+        }
+        Self::constant(runtime::UNDEFINED)
+    }
+}
+
+impl CodeGenerator for LlvmGenerator {
+    fn begin_module(&mut self, module: &IRModule, out: &mut dyn io::Write) -> io::Result<()> {
+        self.defined = module.function_names();
+        self.externs = module.externs.clone();
+        self.constants = module.constants.clone();
+        writeln!(out, "; Generated by js-compiler\n")
+    }
+
+    fn emit_function(&mut self, function: &IRFunction, out: &mut dyn io::Write) -> io::Result<()> {
+        self.generate_function(function);
+        if function.name == "main" {
+            self.generate_entry_point();
+        }
+        out.write_all(std::mem::take(&mut self.output).as_bytes())
+    }
+
+    /// Emit the string literals and declarations the functions collected;
+    /// LLVM resolves globals regardless of their order in the module.
+    fn finish(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        // String literals as NUL-terminated byte arrays
+        for (i, s) in self.string_literals.iter().enumerate() {
+            let bytes: String = s.bytes().map(|b| format!("\\{:02X}", b)).collect();
+            writeln!(
+                self.output,
+                "@.str.{} = private unnamed_addr constant [{} x i8] c\"{}\\00\"",
+                i,
+                s.len() + 1,
+                bytes
+            )
+            .unwrap();
+        }
+        if !self.string_literals.is_empty() {
+            writeln!(self.output).unwrap();
+        }
+
+        // Runtime library and functions defined elsewhere
+        writeln!(self.output, "declare void @jsrt_print_value(i64, i32)").unwrap();
+        writeln!(self.output, "declare void @jsrt_print_newline()").unwrap();
+        if self.defined.iter().any(|name| name == "main") {
+            writeln!(self.output, "declare void @{}(i64)", runtime::EXIT_FUNCTION).unwrap();
+        }
+        for (name, argc) in &self.runtime_functions {
+            let params = vec!["i64"; *argc];
+            writeln!(self.output, "declare i64 @{}({})", name, params.join(", ")).unwrap();
+        }
+        if !self.externs.is_empty() {
+            for declaration in C_CONVERSIONS {
+                writeln!(self.output, "{}", declaration).unwrap();
+            }
+        }
+        for function in &self.externs {
+            let mut params: Vec<&str> = function.params.iter().map(|&ty| c_type(ty)).collect();
+            if function.variadic {
+                params.push("...");
+            }
+            writeln!(
+                self.output,
+                "declare {} @{}({})",
+                c_type(function.returns),
+                function.name,
+                params.join(", ")
+            )
+            .unwrap();
+        }
+        for (name, argc) in &self.external_functions {
+            if self.defined.contains(name) {
+                continue;
+            }
+            let params = vec!["double"; *argc as usize];
+            writeln!(
+                self.output,
+                "declare double @{}({})",
+                name,
+                params.join(", ")
+            )
+            .unwrap();
+        }
+        out.write_all(std::mem::take(&mut self.output).as_bytes())
+    }
+}
@@ -1,92 +1,735 @@
+#[cfg(feature = "arm64")]
 pub mod arm64;
+pub mod assembler;
+#[cfg(feature = "cranelift")]
+pub mod cranelift;
+pub mod jit;
+#[cfg(feature = "llvm")]
+pub mod llvm;
+pub mod runtime;
+pub mod source_map;
+pub mod structurizer;
+pub mod toolchain;
+#[cfg(feature = "wasm")]
 pub mod wasm;
+#[cfg(feature = "x64")]
 pub mod x64;
 
-use crate::ir::IRModule;
+use crate::diagnostics::{Diagnostic, Span};
+use crate::ir::{Constant, IRFunction, IRInstruction, IRModule};
+use source_map::LineTable;
+use std::borrow::Cow;
+use std::io;
 
+/// A backend, driven one function at a time: `begin_module`, then
+/// `emit_function` for each function of the module in order, then `finish`.
+/// Each writes its code to `out` as it goes, so only one function's code is
+/// held in memory; what needs the whole module, such as literal pools and
+/// exports, comes at the end. A generator handles a single module.
 pub trait CodeGenerator {
-    fn generate(&mut self, module: IRModule) -> String;
+    /// Start `module`, writing any header. Its functions are passed to
+    /// `emit_function` next.
+    fn begin_module(&mut self, module: &IRModule, out: &mut dyn io::Write) -> io::Result<()>;
+
+    fn emit_function(&mut self, function: &IRFunction, out: &mut dyn io::Write) -> io::Result<()>;
+
+    /// End the module, writing what its functions collected.
+    fn finish(&mut self, out: &mut dyn io::Write) -> io::Result<()>;
+
+    /// Generate all of `module` into `out`.
+    fn generate_to(&mut self, module: &IRModule, out: &mut dyn io::Write) -> io::Result<()> {
+        self.begin_module(module, out)?;
+        for function in &module.functions {
+            self.emit_function(function, out)?;
+        }
+        self.finish(out)
+    }
+
+    /// Where the code of each JS line went, for generators asked to record
+    /// it with `CodegenOptions::source_map`.
+    fn line_table(&self) -> Option<&LineTable> {
+        None
+    }
+
+    /// Generate all of `module` as a string.
+    fn generate(&mut self, module: IRModule) -> String {
+        let mut out = Vec::new();
+        self.generate_to(&module, &mut out)
+            .expect("Writing to memory cannot fail");
+        String::from_utf8(out).expect("Generated code is UTF-8")
+    }
 }
 
-pub fn generate_code(module: IRModule, target: Target) -> Option<String> {
+/// The code generator for `target`, or `None` for the VM.
+// Only the handwritten backends take options
+#[cfg_attr(
+    not(any(feature = "x64", feature = "arm64", feature = "wasm")),
+    allow(unused_variables)
+)]
+pub fn generator(target: Target, options: &CodegenOptions) -> Option<Box<dyn CodeGenerator>> {
     match target {
-        Target::X64 => {
-            let mut generator = x64::X64Generator::new();
-            Some(generator.generate(module))
-        }
-        Target::ARM64 => {
-            let mut generator = arm64::ARM64Generator::new();
-            Some(generator.generate(module))
-        }
-        Target::Wasm => {
-            let mut generator = wasm::WasmGenerator::new();
-            Some(generator.generate(module))
-        }
+        #[cfg(feature = "x64")]
+        Target::X64 => Some(Box::new(x64::X64Generator::with_options(options.clone()))),
+        #[cfg(feature = "arm64")]
+        Target::ARM64 => Some(Box::new(arm64::ARM64Generator::with_options(
+            options.clone(),
+        ))),
+        #[cfg(feature = "wasm")]
+        Target::Wasm => Some(Box::new(wasm::WasmGenerator::with_options(options.clone()))),
+        #[cfg(feature = "llvm")]
+        Target::LlvmIr => Some(Box::new(llvm::LlvmGenerator::new())),
+        #[cfg(feature = "cranelift")]
+        Target::Cranelift => Some(Box::new(cranelift::CraneliftTextGenerator::new())),
         Target::None => None,
+        // Backends left out of this build
+        #[allow(unreachable_patterns)]
+        target => Diagnostic::error(format!(
+            "The {} target requires the `{}` feature",
+            target.name(),
+            target.name()
+        ))
+        .raise(),
     }
 }
 
+/// Generate `module` for `target` as a string, or `None` for the VM.
+pub fn generate_code(module: IRModule, target: Target, options: &CodegenOptions) -> Option<String> {
+    generator(target, options).map(|mut generator| generator.generate(module))
+}
+
+/// Raise the error for a closure's instructions, which only the VM runs:
+/// native values have no representation of a closure yet.
+#[cfg_attr(
+    not(any(
+        feature = "x64",
+        feature = "arm64",
+        feature = "wasm",
+        feature = "llvm",
+        feature = "cranelift"
+    )),
+    allow(dead_code)
+)]
+fn closures_unsupported(target: Target) -> ! {
+    Diagnostic::error(format!(
+        "The {} backend does not support closures",
+        target.name()
+    ))
+    .with_note("functions declared inside functions run only in the VM")
+    .raise()
+}
+
+/// Raise the error for loading `name` where the function declares no such
+/// variable, which only the VM runs: it reads a global, and the native
+/// backends have none.
+#[cfg_attr(not(any(feature = "x64", feature = "arm64")), allow(dead_code))]
+fn globals_unsupported(target: Target, name: &str, line: Option<usize>) -> ! {
+    let diagnostic = Diagnostic::error(format!(
+        "The {} backend does not support global variables",
+        target.name()
+    ))
+    .with_note(format!(
+        "`{}` is not declared in the function; only the VM reads globals",
+        name
+    ));
+    match line {
+        Some(line) => diagnostic.with_label(Span::line(line), ""),
+        None => diagnostic,
+    }
+    .raise()
+}
+
+/// Raise the error for reading a property of a value, which only the VM
+/// runs: native values have no representation of an object yet.
+#[cfg_attr(
+    not(any(
+        feature = "x64",
+        feature = "arm64",
+        feature = "wasm",
+        feature = "llvm",
+        feature = "cranelift"
+    )),
+    allow(dead_code)
+)]
+fn objects_unsupported(target: Target) -> ! {
+    Diagnostic::error(format!(
+        "The {} backend does not support objects",
+        target.name()
+    ))
+    .with_note("properties of values are read only in the VM")
+    .raise()
+}
+
+/// Compile `module` with Cranelift to an object file for the host.
+#[cfg(feature = "cranelift")]
+pub fn cranelift_object(module: &IRModule) -> Vec<u8> {
+    cranelift::compile_object(module)
+        .unwrap_or_else(|e| panic!("Cranelift compilation failed: {}", e))
+}
+
+/// Compile `module` with Cranelift to an object file for the host.
+#[cfg(not(feature = "cranelift"))]
+pub fn cranelift_object(_module: &IRModule) -> Vec<u8> {
+    panic!("The cranelift target requires the `cranelift` feature")
+}
+
+/// Encode the Wasm backend's text output as a validated binary module.
+#[cfg(feature = "wasm")]
+pub fn wasm_binary(wat: &str) -> Result<Vec<u8>, String> {
+    wasm::encode_binary(wat)
+}
+
+/// Encode the Wasm backend's text output as a validated binary module.
+#[cfg(not(feature = "wasm"))]
+pub fn wasm_binary(_wat: &str) -> Result<Vec<u8>, String> {
+    panic!("The wasm target requires the `wasm` feature")
+}
+
 #[derive(Debug, Clone)]
 pub enum Target {
     X64,
     ARM64,
     Wasm,
+    /// Textual LLVM IR, for LLVM's optimizer and code generators.
+    LlvmIr,
+    /// Machine code for the host, compiled in process by Cranelift.
+    Cranelift,
     None, // Added for VM-only execution
 }
 
+impl Target {
+    /// The target `--target=<name>` selects.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "x64" => Some(Target::X64),
+            "arm64" => Some(Target::ARM64),
+            "wasm" => Some(Target::Wasm),
+            "llvm" => Some(Target::LlvmIr),
+            "cranelift" => Some(Target::Cranelift),
+            "vm" => Some(Target::None),
+            _ => None,
+        }
+    }
+
+    /// Name of the target on the command line, which is also the cargo
+    /// feature that builds its backend.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Target::X64 => "x64",
+            Target::ARM64 => "arm64",
+            Target::Wasm => "wasm",
+            Target::LlvmIr => "llvm",
+            Target::Cranelift => "cranelift",
+            Target::None => "vm",
+        }
+    }
+
+    /// Why the backend cannot generate code for `spec`, if it cannot: the
+    /// ARM64 backend has no Windows calling convention.
+    pub fn unsupported(&self, spec: &TargetSpec) -> Option<String> {
+        match (self, spec.os) {
+            (Target::ARM64, TargetOs::Windows) => {
+                Some("The arm64 backend does not support Windows".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Operating system the generated assembly is meant to be assembled and linked on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetOs {
+    Linux,
+    MacOs,
+    Windows,
+}
+
+impl TargetOs {
+    pub fn host() -> Self {
+        if cfg!(target_os = "macos") {
+            TargetOs::MacOs
+        } else if cfg!(target_os = "windows") {
+            TargetOs::Windows
+        } else {
+            TargetOs::Linux
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linux" => Some(TargetOs::Linux),
+            "macos" | "darwin" => Some(TargetOs::MacOs),
+            "windows" => Some(TargetOs::Windows),
+            _ => None,
+        }
+    }
+}
+
+impl Default for TargetOs {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+/// Instruction set of a native target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    X86_64,
+    Aarch64,
+}
+
+impl Arch {
+    pub fn host() -> Self {
+        if cfg!(target_arch = "aarch64") {
+            Arch::Aarch64
+        } else {
+            Arch::X86_64
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "x86_64" | "amd64" => Some(Arch::X86_64),
+            "aarch64" | "arm64" => Some(Arch::Aarch64),
+            _ => None,
+        }
+    }
+
+    /// The backend generating code for this architecture.
+    pub fn target(self) -> Target {
+        match self {
+            Arch::X86_64 => Target::X64,
+            Arch::Aarch64 => Target::ARM64,
+        }
+    }
+}
+
+/// C library and ABI flavor: the environment component of a triple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abi {
+    Gnu,
+    Musl,
+    Msvc,
+    /// Apple's, which triples leave implicit.
+    Apple,
+}
+
+impl Abi {
+    /// The ABI a triple naming only `os` means.
+    fn default_for(os: TargetOs) -> Self {
+        match os {
+            TargetOs::Linux => Abi::Gnu,
+            TargetOs::MacOs => Abi::Apple,
+            TargetOs::Windows => Abi::Msvc,
+        }
+    }
+}
+
+/// How arguments are passed to C functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallingConvention {
+    /// x86_64 System V: six integer and eight SSE argument registers.
+    SystemV,
+    /// Microsoft x64: four argument registers, assigned by position, and
+    /// 32 bytes of shadow space.
+    Win64,
+    /// AAPCS64: eight integer and eight FP argument registers.
+    Aapcs64,
+    /// Apple's AAPCS64 variant, which passes variadic arguments on the stack.
+    DarwinAarch64,
+}
+
+/// Architecture, OS and ABI that native code is generated for, as named by
+/// a target triple such as `x86_64-unknown-linux-gnu` or
+/// `aarch64-apple-darwin`. It decides the object format's section names and
+/// symbol conventions and the calling convention of C calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetSpec {
+    pub arch: Arch,
+    pub os: TargetOs,
+    pub abi: Abi,
+}
+
+impl TargetSpec {
+    /// `os` on `arch` with its usual ABI.
+    pub fn new(arch: Arch, os: TargetOs) -> Self {
+        Self {
+            arch,
+            os,
+            abi: Abi::default_for(os),
+        }
+    }
+
+    pub fn host() -> Self {
+        Self::new(Arch::host(), TargetOs::host())
+    }
+
+    /// Parse `<arch>-<vendor>-<os>[-<abi>]`; the vendor may be left out, as
+    /// in `aarch64-linux-gnu`, and so may the ABI.
+    pub fn from_triple(triple: &str) -> Option<Self> {
+        let mut parts = triple.split('-');
+        let arch = Arch::from_name(parts.next()?)?;
+        let rest: Vec<&str> = parts.collect();
+        let os_index = rest.iter().position(|part| {
+            ["linux", "darwin", "macos", "windows"]
+                .iter()
+                .any(|os| part.starts_with(os))
+        })?;
+        // Only a vendor may come before the OS
+        if os_index > 1 {
+            return None;
+        }
+        let os = match rest[os_index] {
+            os if os.starts_with("linux") => TargetOs::Linux,
+            os if os.starts_with("windows") => TargetOs::Windows,
+            _ => TargetOs::MacOs,
+        };
+        let abi = match &rest[os_index + 1..] {
+            [] => Abi::default_for(os),
+            [abi] if abi.starts_with("gnu") => Abi::Gnu,
+            [abi] if abi.starts_with("musl") => Abi::Musl,
+            ["msvc"] => Abi::Msvc,
+            _ => return None,
+        };
+        match (os, abi) {
+            (TargetOs::Linux, Abi::Gnu | Abi::Musl)
+            | (TargetOs::Windows, Abi::Gnu | Abi::Msvc)
+            | (TargetOs::MacOs, Abi::Apple) => Some(Self { arch, os, abi }),
+            _ => None,
+        }
+    }
+
+    /// The canonical triple, which `from_triple` reads back.
+    pub fn triple(&self) -> String {
+        let arch = match self.arch {
+            Arch::X86_64 => "x86_64",
+            Arch::Aarch64 => "aarch64",
+        };
+        let rest = match (self.os, self.abi) {
+            (TargetOs::Linux, Abi::Musl) => "unknown-linux-musl",
+            (TargetOs::Linux, _) => "unknown-linux-gnu",
+            (TargetOs::MacOs, _) => "apple-darwin",
+            (TargetOs::Windows, Abi::Gnu) => "pc-windows-gnu",
+            (TargetOs::Windows, _) => "pc-windows-msvc",
+        };
+        format!("{}-{}", arch, rest)
+    }
+
+    /// The same OS and ABI on `arch`.
+    pub fn with_arch(self, arch: Arch) -> Self {
+        Self { arch, ..self }
+    }
+
+    pub fn calling_convention(&self) -> CallingConvention {
+        match (self.arch, self.os) {
+            (Arch::X86_64, TargetOs::Windows) => CallingConvention::Win64,
+            (Arch::X86_64, _) => CallingConvention::SystemV,
+            (Arch::Aarch64, TargetOs::MacOs) => CallingConvention::DarwinAarch64,
+            (Arch::Aarch64, _) => CallingConvention::Aapcs64,
+        }
+    }
+
+    /// Prefix of C symbols: Mach-O adds `_`.
+    pub fn symbol_prefix(&self) -> &'static str {
+        match self.os {
+            TargetOs::Linux | TargetOs::Windows => "",
+            TargetOs::MacOs => "_",
+        }
+    }
+
+    /// Prefix of assembler-local labels (`.L` on ELF and COFF, `L` on
+    /// Mach-O), before the `L`.
+    pub fn local_prefix(&self) -> &'static str {
+        match self.os {
+            TargetOs::Linux | TargetOs::Windows => ".",
+            TargetOs::MacOs => "",
+        }
+    }
+
+    /// Directive switching to the section for code.
+    pub fn text_section(&self) -> &'static str {
+        match self.os {
+            TargetOs::Linux | TargetOs::Windows => ".text",
+            TargetOs::MacOs => ".section __TEXT,__text",
+        }
+    }
+
+    /// Directive switching to the section for writable data.
+    pub fn data_section(&self) -> &'static str {
+        match self.os {
+            TargetOs::Linux | TargetOs::Windows => ".data",
+            TargetOs::MacOs => ".section __DATA,__data",
+        }
+    }
+}
+
+impl Default for TargetSpec {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+/// Environment a Wasm module runs in, which decides what it imports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WasmHost {
+    /// A JavaScript embedder providing the `console` imports.
+    #[default]
+    JavaScript,
+    /// A standalone runtime such as wasmtime, through `wasi_snapshot_preview1`.
+    Wasi,
+}
+
+/// How the Wasm backend represents JavaScript values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WasmValues {
+    /// `i64` holding the bits of an f64, with strings, booleans, null and
+    /// undefined boxed in NaN payloads.
+    #[default]
+    NanBoxed,
+    /// `anyref`: GC structs for numbers and strings, `i31ref` for booleans.
+    GcReferences,
+}
+
+/// Assembly dialect of the x64 backend's output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum X64Syntax {
+    /// `mov 8(%rsp), %rax`, the GNU as default.
+    #[default]
+    Att,
+    /// `mov rax, [rsp + 8]` under `.intel_syntax noprefix`.
+    Intel,
+}
+
+impl X64Syntax {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "att" => Some(X64Syntax::Att),
+            "intel" => Some(X64Syntax::Intel),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CodegenOptions {
+    /// OS and ABI of native code. The x64 and ARM64 backends generate code
+    /// for their own architecture whatever `target.arch` says.
+    pub target: TargetSpec,
+    /// Dialect of x64 assembly; the built-in assembler only reads AT&T.
+    pub x64_syntax: X64Syntax,
+    pub wasm_host: WasmHost,
+    pub wasm_values: WasmValues,
+    /// Source file that native assembly describes in DWARF line info and
+    /// frame descriptions; `None` leaves debug info out.
+    pub source_file: Option<String>,
+    /// JS source to interleave with x64, ARM64 and wat output as comments,
+    /// along with the IR instruction each piece of code comes from; `None`
+    /// leaves the output unannotated.
+    pub annotated_source: Option<String>,
+    /// Record where the code of each JS line goes in x64, ARM64 and Wasm
+    /// output, for `CodeGenerator::line_table`. Wasm code also starts each
+    /// line with a `nop` to find it by in the binary.
+    pub source_map: bool,
+    /// Emit position-independent x64 and ARM64 code: calls go through the
+    /// PLT and ARM64 literals are loaded through the GOT, so the output
+    /// links into PIE executables and shared libraries.
+    pub pic: bool,
+    /// Wrap each JS function but `main` in x64 and ARM64 code in a C
+    /// function of the same name taking and returning `double`s, and leave
+    /// out the C `main`, so the output links into C or Rust programs as a
+    /// library. The compiled functions themselves become `js_<name>`.
+    pub c_abi: bool,
+}
+
+impl CodegenOptions {
+    /// Symbol of the compiled JS function `name`, which keeps its own name
+    /// unless a C `main` or C-ABI wrapper takes it.
+    #[cfg_attr(not(any(feature = "x64", feature = "arm64")), allow(dead_code))]
+    fn function_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if self.c_abi {
+            Cow::Owned(format!("js_{}", name))
+        } else {
+            Cow::Borrowed(runtime::function_name(name))
+        }
+    }
+
+    /// `.file` directive naming the source file for `.loc` directives.
+    #[cfg_attr(not(any(feature = "x64", feature = "arm64")), allow(dead_code))]
+    fn file_directive(&self) -> Option<String> {
+        self.source_file.as_ref().map(|path| {
+            let escaped = path.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("\t.file 1 \"{}\"", escaped)
+        })
+    }
+
+    /// Comment introducing the code generated for `instruction` in annotated
+    /// output: the JS source line a `Line` marker starts, or the IR
+    /// instruction itself, which is indented like the code it precedes.
+    #[cfg_attr(
+        not(any(feature = "x64", feature = "arm64", feature = "wasm")),
+        allow(dead_code)
+    )]
+    fn annotation(
+        &self,
+        instruction: &IRInstruction,
+        constants: &[Constant],
+        functions: &[String],
+        comment: &str,
+        indent: &str,
+    ) -> Option<String> {
+        let source = self.annotated_source.as_ref()?;
+        match instruction {
+            // Labels already show up in the output
+            IRInstruction::Label(_) => None,
+            IRInstruction::Line(line, _) => {
+                let text = line
+                    .checked_sub(1)
+                    .and_then(|index| source.lines().nth(index))
+                    .unwrap_or("");
+                Some(format!("{} {}: {}", comment, line, text.trim()))
+            }
+            _ => Some(format!(
+                "{}{} {}",
+                indent,
+                comment,
+                instruction.describe(constants, functions)
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ir::{BinaryOp, Constant, IRFunction, IRInstruction};
+    #[cfg(any(feature = "x64", feature = "arm64"))]
+    use crate::ir::Constant;
+    #[cfg(any(feature = "x64", feature = "arm64", feature = "wasm"))]
+    use crate::ir::{BinaryOp, IRFunction, IRInstruction};
 
+    #[test]
+    fn test_target_names() {
+        for name in ["x64", "arm64", "wasm", "llvm", "cranelift", "vm"] {
+            assert_eq!(Target::from_name(name).unwrap().name(), name);
+        }
+        assert!(Target::from_name("riscv").is_none());
+    }
+
+    #[test]
+    fn test_target_triples() {
+        let spec = TargetSpec::from_triple("aarch64-linux-gnu").unwrap();
+        assert_eq!(spec, TargetSpec::new(Arch::Aarch64, TargetOs::Linux));
+        assert_eq!(spec.triple(), "aarch64-unknown-linux-gnu");
+        assert_eq!(spec.calling_convention(), CallingConvention::Aapcs64);
+
+        let spec = TargetSpec::from_triple("arm64-apple-macosx14.0.0").unwrap();
+        assert_eq!(spec.triple(), "aarch64-apple-darwin");
+        assert_eq!(spec.calling_convention(), CallingConvention::DarwinAarch64);
+        assert_eq!(spec.symbol_prefix(), "_");
+
+        let spec = TargetSpec::from_triple("x86_64-pc-windows-gnu").unwrap();
+        assert_eq!((spec.os, spec.abi), (TargetOs::Windows, Abi::Gnu));
+        assert_eq!(spec.calling_convention(), CallingConvention::Win64);
+        assert_eq!(
+            TargetSpec::from_triple("x86_64-unknown-linux-musl")
+                .unwrap()
+                .abi,
+            Abi::Musl
+        );
+
+        for triple in ["x86_64-pc-windows-msvc", "x86_64-apple-darwin"] {
+            assert_eq!(TargetSpec::from_triple(triple).unwrap().triple(), triple);
+        }
+        for triple in [
+            "riscv64-unknown-linux-gnu",
+            "x86_64",
+            "x86_64-apple-darwin-msvc",
+        ] {
+            assert!(TargetSpec::from_triple(triple).is_none(), "{}", triple);
+        }
+    }
+
+    #[cfg(feature = "x64")]
     #[test]
     fn test_x64_generation() {
-        let function = IRFunction {
-            name: "test".to_string(),
-            params: vec![],
-            max_stack: 2,
-            max_locals: 0,
-            instructions: vec![
-                IRInstruction::PushConst(Constant::Number(5.0)),
-                IRInstruction::PushConst(Constant::Number(3.0)),
+        let function = function_with(
+            "test",
+            vec![],
+            2,
+            0,
+            vec![
+                IRInstruction::PushConst(0),
+                IRInstruction::PushConst(1),
                 IRInstruction::Binary(BinaryOp::Add),
                 IRInstruction::Return(true),
             ],
-            exception_table: vec![],
-        };
+        );
 
         let module = IRModule {
             functions: vec![function],
             constants: vec![Constant::Number(5.0), Constant::Number(3.0)],
+            externs: vec![],
         };
 
-        let code = generate_code(module, Target::X64);
+        let code = generate_code(module, Target::X64, &CodegenOptions::default());
         assert!(code.is_some());
         assert!(code.unwrap().contains("add"));
     }
 
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_x64_uses_sse2_for_numbers() {
+        let function = function_with(
+            "calc",
+            vec!["x".to_string(), "y".to_string()],
+            2,
+            2,
+            vec![
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::Load("y".to_string()),
+                IRInstruction::Binary(BinaryOp::Div),
+                IRInstruction::Load("y".to_string()),
+                IRInstruction::Binary(BinaryOp::Lt),
+                IRInstruction::Return(true),
+            ],
+        );
+
+        let module = IRModule {
+            functions: vec![function],
+            constants: vec![],
+            externs: vec![],
+        };
+
+        let code = generate_code(module, Target::X64, &CodegenOptions::default()).unwrap();
+        assert!(code.contains("divsd %xmm1, %xmm0"));
+        assert!(code.contains("ucomisd %xmm0, %xmm1"));
+        assert!(!code.contains("idiv"));
+        // Operands that are not both numbers go through the runtime
+        assert!(code.contains("call jsrt_div"));
+        assert!(code.contains("call jsrt_lt"));
+    }
+
+    #[cfg(feature = "wasm")]
     #[test]
     fn test_wasm_generation() {
-        let function = IRFunction {
-            name: "add".to_string(),
-            params: vec!["x".to_string(), "y".to_string()],
-            max_stack: 2,
-            max_locals: 2,
-            instructions: vec![
+        let function = function_with(
+            "add",
+            vec!["x".to_string(), "y".to_string()],
+            2,
+            2,
+            vec![
                 IRInstruction::Load("x".to_string()),
                 IRInstruction::Load("y".to_string()),
                 IRInstruction::Binary(BinaryOp::Add),
                 IRInstruction::Return(true),
             ],
-            exception_table: vec![],
-        };
+        );
 
         let module = IRModule {
             functions: vec![function],
             constants: vec![],
+            externs: vec![],
         };
 
-        let code = generate_code(module, Target::Wasm);
+        let code = generate_code(module, Target::Wasm, &CodegenOptions::default());
         assert!(code.is_some());
         let wasm_code = code.unwrap();
         // Update assertions to match actual WebAssembly text format
@@ -94,27 +737,768 @@ mod tests {
         assert!(wasm_code.contains("(func"));
     }
 
+    #[cfg(feature = "wasm")]
     #[test]
-    fn test_arm64_generation() {
-        let function = IRFunction {
-            name: "main".to_string(),
-            params: vec![],
-            max_stack: 1,
-            max_locals: 0,
-            instructions: vec![
-                IRInstruction::PushConst(Constant::Number(42.0)),
-                IRInstruction::Return(true),
-            ],
-            exception_table: vec![],
+    fn test_wasm_binary_encoding() {
+        let source = "function main() { let x = 1; print(x, x); return x; }";
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let text = generate_code(module, Target::Wasm, &CodegenOptions::default()).unwrap();
+
+        let binary = wasm::encode_binary(&text).unwrap();
+        assert_eq!(&binary[..4], b"\0asm");
+        assert!(wasm::encode_binary("(module (func (result i64)))").is_err());
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_structured_control_flow() {
+        let source = "
+            function fib(n) { if (n <= 1) { return n; } return fib(n - 1) + fib(n - 2); }
+            function count(n) {
+                let i = 0;
+                while (i < n) { if (i > 2) { print(i); } else { print(0); } let i = i + 1; }
+            }
+            function main() { print(fib(10), !0); count(5); }";
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let text = generate_code(module, Target::Wasm, &CodegenOptions::default()).unwrap();
+
+        assert!(text.contains("loop"));
+        assert!(!text.contains("br L"));
+        wasm::encode_binary(&text).unwrap();
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_strings() {
+        let source = r#"function main() { let s = "hi"; print(s + 1, "hi"); }"#;
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let text = generate_code(module, Target::Wasm, &CodegenOptions::default()).unwrap();
+
+        // One copy of "hi", followed by its (ptr, len) pair
+        assert!(text.contains(r"\68\69\00\00\00\00\00\00\60\00\00\00\02\00\00\00"));
+        assert!(text.contains("call $jsrt_add"));
+        wasm::encode_binary(&text).unwrap();
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_string_equality() {
+        let source = r#"function main() { let s = "a"; print(s == "a", s !== 1, s < "b"); }"#;
+        for wasm_values in [WasmValues::NanBoxed, WasmValues::GcReferences] {
+            let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+            let options = CodegenOptions {
+                wasm_values,
+                ..Default::default()
+            };
+            let text = generate_code(module, Target::Wasm, &options).unwrap();
+
+            // Equality goes through the runtime, not a comparison of doubles
+            let main = &text[text.find("(func $main").unwrap()..];
+            assert_eq!(main.matches("call $jsrt_equals").count(), 1);
+            assert_eq!(main.matches("call $jsrt_strict_equals").count(), 1);
+            assert!(main.contains("call $jsrt_comparable\nf64.lt"));
+            assert!(!main.contains("f64.eq"));
+            wasm::encode_binary(&text).unwrap();
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_print_line() {
+        let source = "function main() { print(true, 1, 2); }";
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let text = generate_code(module, Target::Wasm, &CodegenOptions::default()).unwrap();
+
+        // Booleans are tagged, not the numbers 1 and 0
+        assert!(text.contains(&format!("i64.const {}\ni64.or", 0x7ffa_0000_0000_0000u64)));
+        // Two separators and one newline, written by main itself
+        let main = &text[text.find("(func $main").unwrap()..];
+        assert_eq!(main.matches("call $log_string").count(), 3);
+        wasm::encode_binary(&text).unwrap();
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_wasi_module() {
+        let source = r#"function main() { print("n", 1.5); }"#;
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            wasm_host: WasmHost::Wasi,
+            ..Default::default()
+        };
+        let text = generate_code(module, Target::Wasm, &options).unwrap();
+
+        assert!(text.contains("\"wasi_snapshot_preview1\" \"fd_write\""));
+        assert!(text.contains("(export \"_start\")"));
+        assert!(!text.contains("(import \"console\""));
+        wasm::encode_binary(&text).unwrap();
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_exports() {
+        let source = r#"
+            function add(a, b) { return a + b; }
+            function twice(x) { return add(x, x); }
+            function main() { return twice(2); }
+        "#;
+        let mut module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        module.export("add");
+        let text = generate_code(module, Target::Wasm, &CodegenOptions::default()).unwrap();
+
+        assert!(text.contains("(export \"add\" (func $add))"));
+        assert!(!text.contains("(export \"twice\""));
+        assert!(text.contains("(export \"main\" (func $main))"));
+        wasm::encode_binary(&text).unwrap();
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_host_imports() {
+        let source = r#"
+            extern function scale(double, int): long;
+            extern function greet(string): string;
+            extern function beep();
+            function main() { beep(); print(greet("n=" + scale(1.5, 2))); }
+        "#;
+        for wasm_values in [WasmValues::NanBoxed, WasmValues::GcReferences] {
+            let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+            let options = CodegenOptions {
+                wasm_values,
+                ..Default::default()
+            };
+            let text = generate_code(module, Target::Wasm, &options).unwrap();
+
+            assert!(text
+                .contains("(import \"env\" \"scale\" (func $scale (param f64 i32) (result i64)))"));
+            assert!(text.contains(
+                "(import \"env\" \"greet\" (func $greet (param i32 i32) (result i32 i32)))"
+            ));
+            assert!(text.contains("(import \"env\" \"beep\" (func $beep))"));
+            assert!(text.contains("i32.trunc_sat_f64_s\ncall $scale\nf64.convert_i64_s\n"));
+            wasm::encode_binary(&text).unwrap();
+        }
+    }
+
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_streaming_generation() {
+        let source = r#"
+            function greet() { print("hi"); }
+            function main() { greet(); return 1.5; }
+        "#;
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            ..Default::default()
+        };
+        let mut generator = generator(Target::X64, &options).unwrap();
+        let mut out = Vec::new();
+        generator.begin_module(&module, &mut out).unwrap();
+
+        // Each function is written as soon as it is generated
+        generator
+            .emit_function(&module.functions[0], &mut out)
+            .unwrap();
+        let greet = String::from_utf8(out.clone()).unwrap();
+        assert!(greet.contains("greet:"));
+        assert!(!greet.contains("js_main:"));
+
+        // The literals both functions use follow them
+        generator
+            .emit_function(&module.functions[1], &mut out)
+            .unwrap();
+        generator.finish(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with(&greet));
+        assert!(text.find(".asciz \"hi\"").unwrap() > text.find("js_main:").unwrap());
+        assert!(text.contains(".double 1.5"));
+        assert_eq!(text, generate_code(module, Target::X64, &options).unwrap());
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm_gc_values() {
+        let source = r#"
+            function half(x) { return x / 2; }
+            function main() { let s = "n=" + half(3); if (s) { print(s, true, null); } }"#;
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            wasm_values: WasmValues::GcReferences,
+            ..Default::default()
         };
+        let text = generate_code(module, Target::Wasm, &options).unwrap();
+
+        assert!(text.contains("(func $half (param anyref) (result anyref)"));
+        assert!(text.contains("struct.new $number"));
+        assert!(!text.contains("i64.reinterpret_f64"));
+        wasm::encode_binary(&text).unwrap();
+    }
+
+    #[cfg(feature = "llvm")]
+    #[test]
+    fn test_llvm_ir_generation() {
+        let source = "
+            function fib(n) { if (n <= 1) { return n; } return fib(n - 1) + fib(n - 2); }
+            function main() { print(fib(10), \"s\"); return sqrt(2); }";
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let code = generate_code(module, Target::LlvmIr, &CodegenOptions::default()).unwrap();
+
+        assert!(code.contains("define double @fib(double %p0) {"));
+        assert!(code.contains("fcmp ole double"));
+        assert!(code.contains("call double @fib("));
+        assert!(code.contains("call void @jsrt_print_value(i64"));
+        assert!(code.contains("declare i64 @jsrt_add(i64, i64)"));
+        assert!(code.contains("@.str.0 = private unnamed_addr constant [2 x i8] c\"\\73\\00\""));
+        // Undefined callees are declared, defined ones are not
+        assert!(code.contains("declare double @sqrt(double)"));
+        assert!(!code.contains("declare double @fib"));
+    }
+
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_closures_unsupported() {
+        let source = "function f(k) { function g(x) { return x * k; } return g(2); }";
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let payload = std::panic::catch_unwind(|| {
+            generate_code(module, Target::X64, &CodegenOptions::default())
+        })
+        .unwrap_err();
+        assert_eq!(
+            Diagnostic::from_panic(payload).message,
+            "The x64 backend does not support closures"
+        );
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64"))]
+    #[test]
+    fn test_globals_unsupported() {
+        let source = "function main() {\n  print(x);\n}";
+        for target in [Target::X64, Target::ARM64] {
+            let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+            let payload = std::panic::catch_unwind(|| {
+                generate_code(module, target.clone(), &CodegenOptions::default())
+            })
+            .unwrap_err();
+            let diagnostic = Diagnostic::from_panic(payload);
+            assert_eq!(
+                diagnostic.to_string(),
+                format!(
+                    "2: The {} backend does not support global variables",
+                    target.name()
+                )
+            );
+        }
+    }
+
+    #[cfg(feature = "cranelift")]
+    #[test]
+    fn test_cranelift_generation() {
+        let source = "
+            function fib(n) { if (n <= 1) { return n; } return fib(n - 1) + fib(n - 2); }
+            function main() { print(fib(10), \"s\"); return sqrt(2); }";
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let object = cranelift_object(&module);
+        let code = generate_code(module, Target::Cranelift, &CodegenOptions::default()).unwrap();
+
+        assert!(code.contains("fcmp.f64 le"));
+        assert!(code.contains("call fn0("));
+        assert!(!object.is_empty());
+        if cfg!(target_os = "linux") {
+            assert_eq!(&object[..4], b"\x7fELF");
+        }
+    }
+
+    #[cfg(feature = "arm64")]
+    #[test]
+    fn test_arm64_generation() {
+        let function = function_with(
+            "main",
+            vec![],
+            1,
+            0,
+            vec![IRInstruction::PushConst(0), IRInstruction::Return(true)],
+        );
 
         let module = IRModule {
             functions: vec![function],
             constants: vec![Constant::Number(42.0)],
+            externs: vec![],
         };
 
-        let code = generate_code(module, Target::ARM64);
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::Aarch64, TargetOs::MacOs),
+            ..Default::default()
+        };
+        let code = generate_code(module, Target::ARM64, &options);
         assert!(code.is_some());
         assert!(code.unwrap().contains(".global _main"));
     }
+
+    #[cfg(feature = "arm64")]
+    #[test]
+    fn test_arm64_uses_fp_registers_for_numbers() {
+        let function = function_with(
+            "calc",
+            vec!["x".to_string(), "y".to_string()],
+            2,
+            2,
+            vec![
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::Load("y".to_string()),
+                IRInstruction::Binary(BinaryOp::Add),
+                IRInstruction::Load("y".to_string()),
+                IRInstruction::Binary(BinaryOp::Le),
+                IRInstruction::Return(true),
+            ],
+        );
+
+        let module = IRModule {
+            functions: vec![function],
+            constants: vec![],
+            externs: vec![],
+        };
+
+        let code = generate_code(module, Target::ARM64, &CodegenOptions::default()).unwrap();
+        assert!(code.contains("fadd d0, d0, d1"));
+        assert!(code.contains("fcmp d0, d1"));
+        assert!(code.contains("cset x0, ls"));
+        assert!(!code.contains("add x0, x0, x1"));
+    }
+
+    /// A function with the code `instructions` in the linear form.
+    #[cfg(any(feature = "x64", feature = "arm64", feature = "wasm"))]
+    fn function_with(
+        name: &str,
+        params: Vec<String>,
+        max_stack: u16,
+        max_locals: u16,
+        instructions: Vec<IRInstruction>,
+    ) -> IRFunction {
+        let mut function = IRFunction::new(name.to_string(), params);
+        function.max_stack = max_stack;
+        function.max_locals = max_locals;
+        function.set_instructions(instructions).unwrap();
+        function
+    }
+
+    #[cfg(any(feature = "x64", feature = "arm64"))]
+    fn branching_function(name: &str) -> IRFunction {
+        function_with(
+            name,
+            vec!["x".to_string()],
+            1,
+            1,
+            vec![
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::JumpIf("L1".to_string()),
+                IRInstruction::Label("L1".to_string()),
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::Return(true),
+            ],
+        )
+    }
+
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_x64_linux_flavor() {
+        let module = IRModule {
+            functions: vec![branching_function("first"), branching_function("second")],
+            constants: vec![],
+            externs: vec![],
+        };
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            ..Default::default()
+        };
+
+        let code = generate_code(module, Target::X64, &options).unwrap();
+        assert!(code.contains(".globl first"));
+        assert!(code.contains(".type first, @function"));
+        assert!(code.contains("\t.text\n"));
+        assert!(code.contains(".section .note.GNU-stack"));
+        // Labels from different functions must not collide
+        assert!(code.contains(".Lfirst_L1:"));
+        assert!(code.contains(".Lsecond_L1:"));
+    }
+
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_x64_macos_flavor() {
+        let module = IRModule {
+            functions: vec![branching_function("first")],
+            constants: vec![],
+            externs: vec![],
+        };
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::MacOs),
+            ..Default::default()
+        };
+
+        let code = generate_code(module, Target::X64, &options).unwrap();
+        assert!(code.contains(".globl _first"));
+        assert!(code.contains(".section __TEXT,__text"));
+        assert!(!code.contains("@function"));
+    }
+
+    #[cfg(feature = "arm64")]
+    #[test]
+    fn test_arm64_stack_arguments() {
+        let params: Vec<String> = (0..10).map(|i| format!("p{}", i)).collect();
+        let mut callee = branching_function("callee");
+        callee.params = params.clone();
+        callee
+            .set_instructions(vec![
+                IRInstruction::Load("p9".to_string()),
+                IRInstruction::Return(true),
+            ])
+            .unwrap();
+        let mut caller = branching_function("caller");
+        caller.params = params.clone();
+        caller
+            .set_instructions(
+                params
+                    .iter()
+                    .map(|p| IRInstruction::Load(p.clone()))
+                    .chain([IRInstruction::Call(0, 10), IRInstruction::Return(true)])
+                    .collect(),
+            )
+            .unwrap();
+        let module = IRModule {
+            functions: vec![callee, caller],
+            constants: vec![],
+            externs: vec![],
+        };
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::Aarch64, TargetOs::Linux),
+            ..Default::default()
+        };
+
+        let code = generate_code(module, Target::ARM64, &options).unwrap();
+        // p8 and p9 arrive above the frame record
+        assert!(code.contains("ldr x0, [fp, #24]"));
+        assert!(code.contains(
+            "sub sp, sp, #16\n\tldr x9, [sp, #32]\n\tstr x9, [sp, #0]\n\tldr x9, [sp, #16]\n\tstr x9, [sp, #8]\n\tbl callee\n\tadd sp, sp, #176"
+        ));
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64"))]
+    #[test]
+    fn test_debug_info_directives() {
+        let source = "function main() {\n    let t = true;\n    return t;\n}";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            source_file: Some("main.js".to_string()),
+            ..Default::default()
+        };
+
+        let x64 = generate_code(module(), Target::X64, &options).unwrap();
+        assert!(x64.starts_with("\t.file 1 \"main.js\"\n"));
+        assert!(x64.contains("main:\n\t.cfi_startproc\n\t.loc 1 1\n\tpush %rbp\n"));
+        assert!(x64.contains("\t.cfi_def_cfa_register %rbp\n"));
+        assert!(x64.contains("\t.loc 1 2\n"));
+        assert!(x64.contains("\t.loc 1 3\n"));
+        assert!(x64.contains("\tpop %rbp\n\t.cfi_def_cfa %rsp, 8\n\tret\n"));
+        assert!(x64.contains("\t.cfi_endproc\n"));
+
+        let arm64 = generate_code(module(), Target::ARM64, &options).unwrap();
+        assert!(arm64.contains("\t.cfi_def_cfa w29, 16\n"));
+        assert!(arm64.contains("\t.loc 1 3\n"));
+
+        // Without a source file there is no debug info
+        let plain = generate_code(module(), Target::X64, &CodegenOptions::default()).unwrap();
+        assert!(!plain.contains(".loc") && !plain.contains(".cfi_"));
+    }
+
+    #[cfg(all(feature = "x64", feature = "wasm"))]
+    #[test]
+    fn test_source_maps() {
+        use source_map::SourceMap;
+
+        let source = "function main() {\n    let t = true;\n    return t;\n}";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            source_map: true,
+            ..Default::default()
+        };
+
+        // Every line of the function's code maps to the JS line it is for
+        let mut x64 = generator(Target::X64, &options).unwrap();
+        let text = x64.generate(module());
+        let map = SourceMap::for_text(x64.line_table().unwrap(), "a.s", "a.js", source);
+        let lines: Vec<&str> = text.lines().collect();
+        let line_of = |code: &str| {
+            let i = lines
+                .iter()
+                .position(|line| line.starts_with(code))
+                .unwrap();
+            map.lines[i].first().map(|&(_, line)| line)
+        };
+        assert_eq!(line_of("main:"), None);
+        assert_eq!(line_of("\tpush %rbp"), None);
+        assert_eq!(line_of("\tmovabs $"), Some(2));
+        assert_eq!(line_of("\tret"), Some(3));
+
+        // Wasm marks each line with a nop, found by its offset in the binary
+        let mut wasm = generator(Target::Wasm, &options).unwrap();
+        let binary = wasm_binary(&wasm.generate(module())).unwrap();
+        let map = SourceMap::for_wasm_binary(
+            wasm.line_table().unwrap(),
+            &binary,
+            "a.wasm",
+            "a.js",
+            source,
+        )
+        .unwrap();
+        let lines: Vec<usize> = map.lines[0].iter().map(|&(_, line)| line).collect();
+        assert_eq!(lines, [1, 2, 3]);
+        assert!(map.lines[0]
+            .iter()
+            .all(|&(offset, _)| binary[offset] == 0x01));
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64", feature = "wasm"))]
+    #[test]
+    fn test_annotated_output() {
+        let source = "function main() {\n    let t = true;\n    return t;\n}";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            annotated_source: Some(source.to_string()),
+            ..Default::default()
+        };
+
+        let x64 = generate_code(module(), Target::X64, &options).unwrap();
+        assert!(x64.contains("# 2: let t = true;\n\t# PushConst(Boolean(true))\n\tmovabs $"));
+        assert!(x64.contains("# 3: return t;\n\t# Load(\"t\")\n"));
+
+        let arm64 = generate_code(module(), Target::ARM64, &options).unwrap();
+        assert!(arm64.contains("// 3: return t;\n\t// Load(\"t\")\n"));
+
+        let wat = generate_code(module(), Target::Wasm, &options).unwrap();
+        assert!(wat.contains(";; 3: return t;\n;; Load(\"t\")\nlocal.get 0\n"));
+        wasm::encode_binary(&wat).unwrap();
+    }
+
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_x64_windows_abi() {
+        let mut caller = branching_function("caller");
+        caller.params = vec!["x".to_string(), "y".to_string()];
+        caller
+            .set_instructions(vec![
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::Load("y".to_string()),
+                IRInstruction::CallNative("callee".to_string(), 2),
+                IRInstruction::Return(true),
+            ])
+            .unwrap();
+        let module = IRModule {
+            functions: vec![caller],
+            constants: vec![],
+            externs: vec![],
+        };
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Windows),
+            ..Default::default()
+        };
+
+        let code = generate_code(module, Target::X64, &options).unwrap();
+        assert!(code.contains(".def caller;"));
+        assert!(code.contains("movq %xmm0, %rax\n\tmov %rax, -8(%rbp)"));
+        assert!(code.contains("movq %xmm1, %rax\n\tmov %rax, -16(%rbp)"));
+        assert!(code.contains("push %rdi"));
+        assert!(code.contains("sub $32, %rsp\n\tand $-16, %rsp\n\tcall callee\n\tmov %rbx, %rsp"));
+        assert!(!code.contains("@function"));
+    }
+
+    #[cfg(feature = "arm64")]
+    #[test]
+    fn test_arm64_linux_flavor() {
+        let mut function = branching_function("main");
+        let mut code = function.instructions();
+        code.splice(0..0, [IRInstruction::PushConst(0), IRInstruction::Pop]);
+        function.set_instructions(code).unwrap();
+        let module = IRModule {
+            functions: vec![function],
+            constants: vec![Constant::Number(1.5)],
+            externs: vec![],
+        };
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::Aarch64, TargetOs::Linux),
+            ..Default::default()
+        };
+
+        let code = generate_code(module, Target::ARM64, &options).unwrap();
+        assert!(code.contains(".global main"));
+        assert!(code.contains(".type main, %function"));
+        assert!(code.contains("\t.text"));
+        assert!(code.contains("adrp x0, .LCD0"));
+        assert!(code.contains("ldr d0, [x0, :lo12:.LCD0]"));
+        assert!(code.contains("cbnz x0, .Lmain_L1"));
+        assert!(!code.contains("__DATA"));
+        assert!(!code.contains("@PAGE"));
+    }
+
+    #[cfg(feature = "arm64")]
+    #[test]
+    fn test_arm64_rejects_windows() {
+        let options = CodegenOptions {
+            target: TargetSpec::from_triple("aarch64-pc-windows-msvc").unwrap(),
+            ..Default::default()
+        };
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(
+            "function main() { return 0; }",
+        )));
+        let payload = std::panic::catch_unwind(|| generate_code(module, Target::ARM64, &options))
+            .unwrap_err();
+        assert_eq!(
+            Diagnostic::from_panic(payload).message,
+            "The arm64 backend does not support Windows"
+        );
+        // Other backends can still target it
+        assert_eq!(Target::LlvmIr.unsupported(&options.target), None);
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64"))]
+    #[test]
+    fn test_print_calls_runtime() {
+        let module = || {
+            let mut function = branching_function("show");
+            function
+                .set_instructions(vec![
+                    IRInstruction::Load("x".to_string()),
+                    IRInstruction::Load("x".to_string()),
+                    IRInstruction::CallNative("print".to_string(), 2),
+                    IRInstruction::Return(true),
+                ])
+                .unwrap();
+            IRModule {
+                functions: vec![function],
+                constants: vec![],
+                externs: vec![],
+            }
+        };
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            ..Default::default()
+        };
+
+        let x64 = generate_code(module(), Target::X64, &options).unwrap();
+        assert!(x64.contains("mov 8(%rsp), %rdi\n\tmov $32, %rsi"));
+        assert!(x64.contains("mov 0(%rsp), %rdi\n\tmov $10, %rsi"));
+        assert!(x64.contains("call jsrt_print_value"));
+        assert!(!x64.contains("call print"));
+
+        let arm64 = generate_code(module(), Target::ARM64, &options).unwrap();
+        assert!(arm64.contains("ldr x0, [sp, #16]\n\tmov w1, #32\n\tbl jsrt_print_value"));
+        assert!(arm64.contains("add sp, sp, #32"));
+        assert!(runtime::RUNTIME_C_SOURCE.contains("void jsrt_print_value("));
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64", feature = "llvm"))]
+    #[test]
+    fn test_extern_calls_use_c_abi() {
+        let source = "extern function ldexp(double, int): double;
+            extern function printf(string, ...): int;
+            function main() { let t = true; printf(t, ldexp(t, t)); }";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let linux = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            ..Default::default()
+        };
+
+        let x64 = generate_code(module(), Target::X64, &linux).unwrap();
+        assert!(x64.contains("movq %rax, %xmm0\n\tmov 0(%rsp), %rdi\n"));
+        assert!(x64.contains("call ldexp@PLT"));
+        assert!(x64.contains("mov $0, %eax\n\tmov %rsp, %rbx\n\tand $-16, %rsp\n\tcall printf@PLT"));
+        assert!(x64.contains("call jsrt_to_c_vararg"));
+        assert!(x64.contains("call jsrt_from_c_double"));
+
+        let arm64 = generate_code(module(), Target::ARM64, &linux).unwrap();
+        assert!(arm64.contains("ldr d0, [sp, #16]\n\tldr x0, [sp, #0]\n\tbl ldexp"));
+        assert!(arm64.contains("ldr x1, [sp, #0]\n\tbl printf"));
+        let macos = CodegenOptions {
+            target: TargetSpec::new(Arch::Aarch64, TargetOs::MacOs),
+            ..Default::default()
+        };
+        let arm64 = generate_code(module(), Target::ARM64, &macos).unwrap();
+        assert!(arm64.contains("str x9, [sp, #0]\n\tbl _printf"));
+
+        let llvm = generate_code(module(), Target::LlvmIr, &linux).unwrap();
+        assert!(llvm.contains("declare i32 @printf(ptr, ...)"));
+        assert!(llvm.contains("declare double @ldexp(double, i32)"));
+        assert!(llvm.contains("call i32 (ptr, ...) @printf(ptr "));
+    }
+
+    #[cfg(feature = "x64")]
+    #[test]
+    fn test_x64_intel_syntax() {
+        let source = "function main() { let t = true; print(t + t); return t; }";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            x64_syntax: X64Syntax::Intel,
+            ..Default::default()
+        };
+
+        let code = generate_code(module(), Target::X64, &options).unwrap();
+        assert!(code.starts_with("\t.intel_syntax noprefix\n"));
+        assert!(code.contains("\tmov rbp, rsp\n"));
+        assert!(code.contains("\tmov [rbp - 8], rax\n"));
+        assert!(code.contains("\tmov rdi, [rsp]\n\tmov rsi, 10\n"));
+        assert!(code.contains("\tand rsp, -16\n\tcall jsrt_add\n"));
+        assert!(!code.contains('%') && !code.contains('$'));
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64"))]
+    #[test]
+    fn test_c_abi_wrappers() {
+        let source = "function add(a, b) { return a + b; } function main() { return add(1, 2); }";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = CodegenOptions {
+            target: TargetSpec::new(Arch::X86_64, TargetOs::Linux),
+            c_abi: true,
+            ..Default::default()
+        };
+
+        let x64 = generate_code(module(), Target::X64, &options).unwrap();
+        assert!(x64.contains("\njs_add:\n"));
+        assert!(x64.contains("\njs_main:\n"));
+        assert!(x64.contains("\tcall js_add\n"));
+        assert!(x64.contains("\nadd:\n"));
+        assert!(x64.contains("\tcall jsrt_to_number\n"));
+        assert!(!x64.contains("\nmain:\n"));
+
+        let arm64 = generate_code(module(), Target::ARM64, &options).unwrap();
+        assert!(arm64.contains("\nadd:\n"));
+        assert!(arm64.contains("\tfmov x1, d1\n\tcmp x1, x9\n\tcsel x1, x1, x10, ls\n"));
+        assert!(arm64.contains("\tbl js_add\n\tbl jsrt_to_number\n"));
+        assert!(!arm64.contains("\nmain:\n"));
+    }
+
+    #[cfg(all(feature = "x64", feature = "arm64"))]
+    #[test]
+    fn test_position_independent_code() {
+        let source = "function main() { print(1.5, \"hi\"); return 0; }";
+        let module = || crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        let options = |triple| CodegenOptions {
+            target: TargetSpec::from_triple(triple).unwrap(),
+            pic: true,
+            ..Default::default()
+        };
+
+        let x64 =
+            generate_code(module(), Target::X64, &options("x86_64-unknown-linux-gnu")).unwrap();
+        assert!(x64.contains("\tcall jsrt_print_value@PLT\n"));
+        assert!(x64.contains("(%rip)"));
+
+        let linux = generate_code(module(), Target::ARM64, &options("aarch64-linux-gnu")).unwrap();
+        assert!(linux.contains("\tadrp x0, :got:js.LCD0\n\tldr x0, [x0, :got_lo12:js.LCD0]\n"));
+        assert!(linux.contains("\tadrp x0, :got:js.LC0\n\tldr x0, [x0, :got_lo12:js.LC0]\n"));
+
+        let macos = generate_code(module(), Target::ARM64, &options("arm64-apple-darwin")).unwrap();
+        assert!(macos.contains("\tadrp x0, js.LCD0@GOTPAGE\n\tldr x0, [x0, js.LCD0@GOTPAGEOFF]\n"));
+    }
 }
@@ -0,0 +1,454 @@
+/*
+ * Runtime support library for natively compiled JavaScript.
+ *
+ * Every JS value is a 64-bit word (see runtime.rs for the constants).
+ * Numbers are the bits of a double, and NaNs are always the canonical quiet
+ * NaN 0x7FF8... or 0xFFF8... Words above 0xFFF8000000000000 box the other
+ * types, with the tag in the top 16 bits:
+ *
+ *   0xFFF9  string: the low 48 bits point to NUL-terminated UTF-8
+ *   0xFFFA  boolean: the low bit is the value
+ *   0xFFFB  null
+ *   0xFFFC  undefined
+ *
+ * Generated code does arithmetic and comparisons on numbers inline and calls
+ * the functions here for every other combination of operands.
+ */
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <math.h>
+
+typedef uint64_t js_value;
+
+#define JS_MAX_NUMBER 0xFFF8000000000000ull
+#define JS_TAG_SHIFT 48
+#define JS_PAYLOAD_MASK 0x0000FFFFFFFFFFFFull
+#define JS_TAG_STRING 0xFFF9ull
+#define JS_TAG_BOOLEAN 0xFFFAull
+#define JS_NULL 0xFFFB000000000000ull
+#define JS_UNDEFINED 0xFFFC000000000000ull
+
+static int jsrt_is_number(js_value value) {
+    return value <= JS_MAX_NUMBER;
+}
+
+static uint64_t jsrt_tag(js_value value) {
+    return value >> JS_TAG_SHIFT;
+}
+
+static double jsrt_to_double(js_value value) {
+    double d;
+    memcpy(&d, &value, sizeof d);
+    return d;
+}
+
+static js_value jsrt_from_double(double d) {
+    js_value value;
+    if (isnan(d)) {
+        /* Keep NaN payloads out of the boxed range */
+        return 0x7FF8000000000000ull;
+    }
+    memcpy(&value, &d, sizeof value);
+    return value;
+}
+
+static js_value jsrt_from_bool(int b) {
+    return (JS_TAG_BOOLEAN << JS_TAG_SHIFT) | (b != 0);
+}
+
+static const char *jsrt_string_chars(js_value value) {
+    return (const char *)(uintptr_t)(value & JS_PAYLOAD_MASK);
+}
+
+static js_value jsrt_from_string(const char *chars) {
+    return (JS_TAG_STRING << JS_TAG_SHIFT) | ((uintptr_t)chars & JS_PAYLOAD_MASK);
+}
+
+static int jsrt_is_string(js_value value) {
+    return !jsrt_is_number(value) && jsrt_tag(value) == JS_TAG_STRING;
+}
+
+/*
+ * Format a number the way JavaScript's Number::toString does: integers up to
+ * 2^53 in full, other numbers with the fewest digits that read back as the
+ * same double, in fixed notation when the decimal exponent is from -6 to 20
+ * and in exponential notation otherwise.
+ */
+char *jsrt_number_to_string(js_value value) {
+    double d = jsrt_to_double(value);
+    char buffer[64];
+
+    if (isnan(d)) {
+        strcpy(buffer, "NaN");
+    } else if (isinf(d)) {
+        strcpy(buffer, d > 0 ? "Infinity" : "-Infinity");
+    } else if (d == 0) {
+        strcpy(buffer, "0");
+    } else if (d == trunc(d) && fabs(d) <= 9007199254740992.0) {
+        /* Every digit of these is needed to read back as the same double */
+        snprintf(buffer, sizeof buffer, "%.0f", d);
+    } else {
+        /* Shortest digits: "d.ddde[+-]x", tried with more digits until they round-trip */
+        char exponential[32];
+        for (int precision = 0; precision < 17; precision++) {
+            snprintf(exponential, sizeof exponential, "%.*e", precision, d);
+            if (strtod(exponential, NULL) == d) {
+                break;
+            }
+        }
+
+        /* The digits without the point, and n, where the number is 0.digits * 10^n */
+        char digits[20];
+        int k = 0;
+        const char *c = exponential + (d < 0);
+        for (; *c != 'e'; c++) {
+            if (*c != '.') {
+                digits[k++] = *c;
+            }
+        }
+        while (k > 1 && digits[k - 1] == '0') {
+            k--;
+        }
+        digits[k] = '\0';
+        int n = atoi(c + 1) + 1;
+
+        char *out = buffer;
+        if (d < 0) {
+            *out++ = '-';
+        }
+        if (-6 < n && n <= 21) {
+            /* Fixed, with zeros between the point and the digits or after them */
+            if (n <= 0) {
+                *out++ = '0';
+                *out++ = '.';
+                for (int i = n; i < 0; i++) {
+                    *out++ = '0';
+                }
+            }
+            for (int i = 0; i < k || i < n; i++) {
+                if (i == n && n > 0) {
+                    *out++ = '.';
+                }
+                *out++ = i < k ? digits[i] : '0';
+            }
+            *out = '\0';
+        } else {
+            sprintf(out, "%c%s%se%+d", digits[0], k > 1 ? "." : "", digits + 1, n - 1);
+        }
+    }
+
+    char *result = malloc(strlen(buffer) + 1);
+    strcpy(result, buffer);
+    return result;
+}
+
+/* JS ToString, as a freshly allocated C string. */
+char *jsrt_to_string(js_value value) {
+    const char *text;
+    if (jsrt_is_number(value)) {
+        return jsrt_number_to_string(value);
+    }
+    switch (jsrt_tag(value)) {
+    case JS_TAG_STRING:
+        text = jsrt_string_chars(value);
+        break;
+    case JS_TAG_BOOLEAN:
+        text = value & 1 ? "true" : "false";
+        break;
+    case JS_NULL >> JS_TAG_SHIFT:
+        text = "null";
+        break;
+    default:
+        text = "undefined";
+        break;
+    }
+    char *result = malloc(strlen(text) + 1);
+    strcpy(result, text);
+    return result;
+}
+
+/* JS ToNumber. */
+static int jsrt_is_space(char c) {
+    return c == ' ' || (c >= '\t' && c <= '\r');
+}
+
+/* The number a string converts to: surrounding whitespace is ignored, an
+ * empty string is 0, and otherwise the rest must be a decimal literal, a
+ * signed or unsigned Infinity, or a 0x, 0o or 0b integer, or it is NaN. */
+static double jsrt_string_to_number(const char *chars) {
+    while (jsrt_is_space(*chars)) {
+        chars++;
+    }
+    size_t len = strlen(chars);
+    while (len > 0 && jsrt_is_space(chars[len - 1])) {
+        len--;
+    }
+    if (len == 0) {
+        return 0;
+    }
+    int radix = 0;
+    if (len > 2 && chars[0] == '0') {
+        switch (chars[1] | 32) {
+        case 'x':
+            radix = 16;
+            break;
+        case 'o':
+            radix = 8;
+            break;
+        case 'b':
+            radix = 2;
+            break;
+        }
+    }
+    if (radix) {
+        double value = 0;
+        for (size_t i = 2; i < len; i++) {
+            int c = chars[i] | 32;
+            int digit = chars[i] >= '0' && chars[i] <= '9' ? chars[i] - '0'
+                        : c >= 'a' && c <= 'f'             ? c - 'a' + 10
+                                                           : 16;
+            if (digit >= radix) {
+                return NAN;
+            }
+            value = value * radix + digit;
+        }
+        return value;
+    }
+    const char *start = chars + (chars[0] == '+' || chars[0] == '-');
+    if (chars + len - start == 8 && strncmp(start, "Infinity", 8) == 0) {
+        return chars[0] == '-' ? -INFINITY : INFINITY;
+    }
+    /* strtod also reads hex floats, "inf" and "nan" */
+    for (const char *c = start; c < chars + len; c++) {
+        if (!(*c >= '0' && *c <= '9') && *c != '.' && *c != 'e' && *c != 'E' && *c != '+' &&
+            *c != '-') {
+            return NAN;
+        }
+    }
+    char *end;
+    double d = strtod(chars, &end);
+    return end == chars + len ? d : NAN;
+}
+
+double jsrt_to_number(js_value value) {
+    if (jsrt_is_number(value)) {
+        return jsrt_to_double(value);
+    }
+    switch (jsrt_tag(value)) {
+    case JS_TAG_STRING:
+        return jsrt_string_to_number(jsrt_string_chars(value));
+    case JS_TAG_BOOLEAN:
+        return value & 1;
+    case JS_NULL >> JS_TAG_SHIFT:
+        return 0;
+    default:
+        return NAN;
+    }
+}
+
+char *jsrt_concat(const char *left, const char *right) {
+    size_t left_len = strlen(left);
+    size_t right_len = strlen(right);
+    char *result = malloc(left_len + right_len + 1);
+    memcpy(result, left, left_len);
+    memcpy(result + left_len, right, right_len + 1);
+    return result;
+}
+
+/* 1 unless the value is false, +-0.0, NaN, "", null or undefined. */
+uint64_t jsrt_truthy(js_value value) {
+    if (jsrt_is_number(value)) {
+        double d = jsrt_to_double(value);
+        return d != 0 && !isnan(d);
+    }
+    switch (jsrt_tag(value)) {
+    case JS_TAG_STRING:
+        return *jsrt_string_chars(value) != '\0';
+    case JS_TAG_BOOLEAN:
+        return value & 1;
+    default:
+        return 0;
+    }
+}
+
+/* `+` concatenates when either side is a string, and adds numbers otherwise. */
+js_value jsrt_add(js_value left, js_value right) {
+    if (jsrt_is_string(left) || jsrt_is_string(right)) {
+        char *left_text = jsrt_to_string(left);
+        char *right_text = jsrt_to_string(right);
+        char *result = jsrt_concat(left_text, right_text);
+        free(left_text);
+        free(right_text);
+        return jsrt_from_string(result);
+    }
+    return jsrt_from_double(jsrt_to_number(left) + jsrt_to_number(right));
+}
+
+js_value jsrt_sub(js_value left, js_value right) {
+    return jsrt_from_double(jsrt_to_number(left) - jsrt_to_number(right));
+}
+
+js_value jsrt_mul(js_value left, js_value right) {
+    return jsrt_from_double(jsrt_to_number(left) * jsrt_to_number(right));
+}
+
+js_value jsrt_div(js_value left, js_value right) {
+    return jsrt_from_double(jsrt_to_number(left) / jsrt_to_number(right));
+}
+
+/* fmod truncates the quotient and keeps the dividend's sign, like `%`. */
+js_value jsrt_mod(js_value left, js_value right) {
+    return jsrt_from_double(fmod(jsrt_to_number(left), jsrt_to_number(right)));
+}
+
+/* Loose equality: null and undefined only equal each other, two strings
+ * compare by content, and anything else compares as numbers. */
+js_value jsrt_eq(js_value left, js_value right) {
+    int left_nullish = left == JS_NULL || left == JS_UNDEFINED;
+    int right_nullish = right == JS_NULL || right == JS_UNDEFINED;
+    if (left_nullish || right_nullish) {
+        return jsrt_from_bool(left_nullish && right_nullish);
+    }
+    if (jsrt_is_string(left) && jsrt_is_string(right)) {
+        return jsrt_from_bool(strcmp(jsrt_string_chars(left), jsrt_string_chars(right)) == 0);
+    }
+    return jsrt_from_bool(jsrt_to_number(left) == jsrt_to_number(right));
+}
+
+js_value jsrt_ne(js_value left, js_value right) {
+    return jsrt_from_bool(!(jsrt_eq(left, right) & 1));
+}
+
+/* Strict equality: no conversions, so values of different types differ. */
+js_value jsrt_strict_eq(js_value left, js_value right) {
+    if (jsrt_is_number(left) && jsrt_is_number(right)) {
+        return jsrt_from_bool(jsrt_to_double(left) == jsrt_to_double(right));
+    }
+    if (jsrt_is_string(left) && jsrt_is_string(right)) {
+        return jsrt_from_bool(strcmp(jsrt_string_chars(left), jsrt_string_chars(right)) == 0);
+    }
+    return jsrt_from_bool(left == right);
+}
+
+/* Relational comparison: strings compare by content, anything else as
+ * numbers. Returns <0, 0 or >0, or 2 when the operands are unordered. */
+static int jsrt_compare(js_value left, js_value right) {
+    if (jsrt_is_string(left) && jsrt_is_string(right)) {
+        int order = strcmp(jsrt_string_chars(left), jsrt_string_chars(right));
+        return (order > 0) - (order < 0);
+    }
+    double a = jsrt_to_number(left);
+    double b = jsrt_to_number(right);
+    if (isnan(a) || isnan(b)) {
+        return 2;
+    }
+    return (a > b) - (a < b);
+}
+
+js_value jsrt_lt(js_value left, js_value right) {
+    return jsrt_from_bool(jsrt_compare(left, right) == -1);
+}
+
+js_value jsrt_gt(js_value left, js_value right) {
+    return jsrt_from_bool(jsrt_compare(left, right) == 1);
+}
+
+js_value jsrt_le(js_value left, js_value right) {
+    int order = jsrt_compare(left, right);
+    return jsrt_from_bool(order == -1 || order == 0);
+}
+
+js_value jsrt_ge(js_value left, js_value right) {
+    int order = jsrt_compare(left, right);
+    return jsrt_from_bool(order == 1 || order == 0);
+}
+
+js_value jsrt_and(js_value left, js_value right) {
+    return jsrt_from_bool(jsrt_truthy(left) && jsrt_truthy(right));
+}
+
+js_value jsrt_or(js_value left, js_value right) {
+    return jsrt_from_bool(jsrt_truthy(left) || jsrt_truthy(right));
+}
+
+js_value jsrt_neg(js_value value) {
+    return jsrt_from_double(-jsrt_to_number(value));
+}
+
+js_value jsrt_not(js_value value) {
+    return jsrt_from_bool(!jsrt_truthy(value));
+}
+
+/* Conversions at calls to C functions declared with `extern function`. */
+
+/* ToNumber truncated towards zero, with NaN and out of range values as 0. */
+int64_t jsrt_to_c_int(js_value value) {
+    double d = jsrt_to_number(value);
+    if (!(fabs(d) < 9223372036854775808.0)) {
+        return 0;
+    }
+    return (int64_t)d;
+}
+
+/* The characters of a string, or of ToString of any other value. */
+const char *jsrt_to_c_string(js_value value) {
+    if (jsrt_is_string(value)) {
+        return jsrt_string_chars(value);
+    }
+    return jsrt_to_string(value);
+}
+
+/* Variadic arguments are passed as pointers to strings and integers otherwise. */
+int64_t jsrt_to_c_vararg(js_value value) {
+    if (jsrt_is_string(value)) {
+        return (int64_t)(uintptr_t)jsrt_string_chars(value);
+    }
+    return jsrt_to_c_int(value);
+}
+
+js_value jsrt_from_c_int(int32_t i) {
+    return jsrt_from_double(i);
+}
+
+js_value jsrt_from_c_long(int64_t i) {
+    return jsrt_from_double((double)i);
+}
+
+js_value jsrt_from_c_double(double d) {
+    return jsrt_from_double(d);
+}
+
+/* A NULL `char *` becomes null. */
+js_value jsrt_from_c_string(const char *chars) {
+    return chars ? jsrt_from_string(chars) : JS_NULL;
+}
+
+/* Print one argument of `print(...)` followed by `separator`. */
+void jsrt_print_value(js_value value, int32_t separator) {
+    char *text = jsrt_to_string(value);
+    fputs(text, stdout);
+    free(text);
+    putchar(separator);
+}
+
+void jsrt_print_newline(void) {
+    putchar('\n');
+}
+
+/*
+ * Exit with the status the result of the JS `main` gives, called by the
+ * `main` entry point the native backends generate. A number is truncated to
+ * an integer (the system keeps its low 8 bits); any other value exits with 0.
+ */
+void jsrt_exit(js_value result) {
+    int status = 0;
+    if (jsrt_is_number(result)) {
+        double d = jsrt_to_double(result);
+        if (isfinite(d)) {
+            status = (int)fmod(trunc(d), 256.0);
+        }
+    }
+    exit(status);
+}
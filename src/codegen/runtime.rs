@@ -0,0 +1,105 @@
+use crate::ir::{BinaryOp, CType, UnaryOp};
+
+/// C source of the runtime support library that natively compiled programs
+/// link against. It provides `print` and the operations generated code
+/// cannot do inline.
+pub const RUNTIME_C_SOURCE: &str = include_str!("runtime.c");
+
+/// File name the runtime is written to next to generated assembly.
+pub const RUNTIME_FILE_NAME: &str = "jsrt.c";
+
+// Native value representation, shared by the native backends and the runtime
+// library. Every value is a 64-bit word: numbers are the bits of a double with
+// NaNs kept canonical, and the other types are boxed in the NaN space above
+// `MAX_NUMBER`, tagged by their top 16 bits.
+
+/// Largest word that is a number (the canonical negative NaN).
+pub const MAX_NUMBER: u64 = 0xFFF8_0000_0000_0000;
+/// The canonical positive NaN, which C-ABI wrappers pass on in place of NaNs
+/// from C that lie above `MAX_NUMBER`.
+pub const NAN: u64 = 0x7FF8_0000_0000_0000;
+/// Tag of a string; the low 48 bits point to NUL-terminated UTF-8.
+pub const STRING_TAG: u64 = 0xFFF9_0000_0000_0000;
+pub const FALSE: u64 = 0xFFFA_0000_0000_0000;
+pub const TRUE: u64 = 0xFFFA_0000_0000_0001;
+pub const NULL: u64 = 0xFFFB_0000_0000_0000;
+pub const UNDEFINED: u64 = 0xFFFC_0000_0000_0000;
+/// Low bits of a boxed value holding its payload.
+pub const PAYLOAD_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+/// Symbol of the JS function `main` in native code. The backends generate
+/// the C `main` entry point themselves: it calls the JS `main` and passes its
+/// result to the runtime's `jsrt_exit`, so the exit status reflects it.
+pub const JS_MAIN: &str = "js_main";
+
+/// Runtime function the entry point exits through.
+pub const EXIT_FUNCTION: &str = "jsrt_exit";
+
+/// Name of the native function compiled from the JS function `name`.
+pub fn function_name(name: &str) -> &str {
+    if name == "main" {
+        JS_MAIN
+    } else {
+        name
+    }
+}
+
+/// JS built-ins that the native backends lower to runtime calls instead of
+/// calls to compiled JS functions.
+pub fn is_builtin(name: &str) -> bool {
+    matches!(name, "print")
+}
+
+/// Runtime function implementing `op` for operands that are not both
+/// numbers. Each takes two values and returns one.
+pub fn binary_function(op: &BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "jsrt_add",
+        BinaryOp::Sub => "jsrt_sub",
+        BinaryOp::Mul => "jsrt_mul",
+        BinaryOp::Div => "jsrt_div",
+        BinaryOp::Mod => "jsrt_mod",
+        BinaryOp::Eq => "jsrt_eq",
+        BinaryOp::Neq => "jsrt_ne",
+        BinaryOp::StrictEq => "jsrt_strict_eq",
+        BinaryOp::Lt => "jsrt_lt",
+        BinaryOp::Gt => "jsrt_gt",
+        BinaryOp::Le => "jsrt_le",
+        BinaryOp::Ge => "jsrt_ge",
+        BinaryOp::And => "jsrt_and",
+        BinaryOp::Or => "jsrt_or",
+    }
+}
+
+/// Runtime function implementing `op` for an operand that is not a number.
+pub fn unary_function(op: &UnaryOp) -> &'static str {
+    match op {
+        UnaryOp::Neg => "jsrt_neg",
+        UnaryOp::Not => "jsrt_not",
+    }
+}
+
+/// Runtime function converting a value into an argument of C type `ty`, or
+/// into a variadic argument when `ty` is `None`. `jsrt_to_number` returns a
+/// `double`; the others return a 64-bit integer or pointer.
+pub fn to_c_function(ty: Option<CType>) -> &'static str {
+    match ty {
+        Some(CType::Int | CType::Long) => "jsrt_to_c_int",
+        Some(CType::Double) => "jsrt_to_number",
+        Some(CType::String) => "jsrt_to_c_string",
+        Some(CType::Void) => panic!("void is not a parameter type"),
+        None => "jsrt_to_c_vararg",
+    }
+}
+
+/// Runtime function converting a C result of type `ty` into a value, or
+/// `None` for `void`, which yields `UNDEFINED`.
+pub fn from_c_function(ty: CType) -> Option<&'static str> {
+    match ty {
+        CType::Int => Some("jsrt_from_c_int"),
+        CType::Long => Some("jsrt_from_c_long"),
+        CType::Double => Some("jsrt_from_c_double"),
+        CType::String => Some("jsrt_from_c_string"),
+        CType::Void => None,
+    }
+}
@@ -0,0 +1,239 @@
+//! Source maps (revision 3) from generated code back to the JS lines it
+//! comes from, so debuggers and browser devtools can show the original
+//! source.
+//!
+//! Backends asked for `CodegenOptions::source_map` keep a `LineTable` of
+//! where each line's code starts in their text output. For text, a line of
+//! output maps to the JS line whose code it is part of. For a binary Wasm
+//! module the backend also puts a `nop` at the start of each line's code;
+//! the `nop`s are found in the binary in the same order, and the "column"
+//! of a mapping is the offset into the module, as browsers expect.
+
+/// Where the code of each JS line starts in a backend's text output.
+#[derive(Debug, Clone, Default)]
+pub struct LineTable {
+    /// Lines of output already written out.
+    flushed_lines: usize,
+    /// Line of output, from 0, and the JS line the code from there on
+    /// comes from; `None` where code stops coming from any JS line.
+    pub entries: Vec<(usize, Option<usize>)>,
+}
+
+impl LineTable {
+    /// Note that code for JS `line` starts after `pending`, the output not
+    /// written out yet.
+    pub fn mark(&mut self, pending: &str, line: Option<usize>) {
+        let position = self.flushed_lines + pending.matches('\n').count();
+        self.entries.push((position, line));
+    }
+
+    /// Note that `text` was written out.
+    pub fn flushed(&mut self, text: &str) {
+        self.flushed_lines += text.matches('\n').count();
+    }
+}
+
+/// A source map for one generated file built from one JS file.
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    /// Name of the generated file.
+    pub file: String,
+    /// Name of the JS file, as the map refers to it.
+    pub source: String,
+    /// The JS source, embedded so the map works without it.
+    pub source_content: String,
+    /// For each line of generated code, the columns starting a mapping and
+    /// the JS line (from 1) each maps to.
+    pub lines: Vec<Vec<(usize, usize)>>,
+}
+
+impl SourceMap {
+    fn new(file: &str, source: &str, source_content: &str) -> Self {
+        SourceMap {
+            file: file.to_string(),
+            source: source.to_string(),
+            source_content: source_content.to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Map each line of the text output `table` was recorded for, once
+    /// all of it is written out.
+    pub fn for_text(table: &LineTable, file: &str, source: &str, source_content: &str) -> Self {
+        let mut map = SourceMap::new(file, source, source_content);
+        map.lines = vec![Vec::new(); table.flushed_lines];
+        for (i, &(start, line)) in table.entries.iter().enumerate() {
+            let Some(line) = line else { continue };
+            let end = table
+                .entries
+                .get(i + 1)
+                .map_or(map.lines.len(), |&(end, _)| end);
+            for segments in map.lines.iter_mut().take(end).skip(start) {
+                segments.push((0, line));
+            }
+        }
+        map
+    }
+
+    /// Map the code of `binary`, the Wasm module encoded from the text
+    /// `table` was recorded for: each `nop` in the function bodies starts
+    /// the code of the next JS line in the table.
+    #[cfg(feature = "wasm")]
+    pub fn for_wasm_binary(
+        table: &LineTable,
+        binary: &[u8],
+        file: &str,
+        source: &str,
+        source_content: &str,
+    ) -> Result<Self, String> {
+        use wasmparser::{Operator, Parser, Payload};
+
+        let mut lines = table.entries.iter().filter_map(|&(_, line)| line);
+        let mut segments = Vec::new();
+        for payload in Parser::new(0).parse_all(binary) {
+            let Payload::CodeSectionEntry(body) = payload.map_err(|e| e.to_string())? else {
+                continue;
+            };
+            let mut reader = body.get_operators_reader().map_err(|e| e.to_string())?;
+            while !reader.eof() {
+                let (operator, offset) = reader.read_with_offset().map_err(|e| e.to_string())?;
+                if let Operator::Nop = operator {
+                    let line = lines.next().ok_or("More nops than JS lines")?;
+                    segments.push((offset as usize, line));
+                }
+            }
+        }
+        if lines.next().is_some() {
+            return Err("Fewer nops than JS lines".to_string());
+        }
+
+        let mut map = SourceMap::new(file, source, source_content);
+        map.lines = vec![segments];
+        Ok(map)
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    pub fn for_wasm_binary(
+        _table: &LineTable,
+        _binary: &[u8],
+        _file: &str,
+        _source: &str,
+        _source_content: &str,
+    ) -> Result<Self, String> {
+        panic!("The wasm target requires the `wasm` feature")
+    }
+
+    /// The map as JSON, with `mappings` in the Base64 VLQ encoding.
+    pub fn to_json(&self) -> String {
+        let mut mappings = String::new();
+        let mut previous_line = 0;
+        for (i, segments) in self.lines.iter().enumerate() {
+            if i > 0 {
+                mappings.push(';');
+            }
+            let mut previous_column = 0;
+            for (j, &(column, line)) in segments.iter().enumerate() {
+                if j > 0 {
+                    mappings.push(',');
+                }
+                // Generated column, source index, source line and column,
+                // each relative to the previous segment
+                let line = line as i64 - 1;
+                encode_vlq(&mut mappings, column as i64 - previous_column);
+                encode_vlq(&mut mappings, 0);
+                encode_vlq(&mut mappings, line - previous_line);
+                encode_vlq(&mut mappings, 0);
+                previous_column = column as i64;
+                previous_line = line;
+            }
+        }
+        serde_json::json!({
+            "version": 3,
+            "file": self.file,
+            "sources": [self.source],
+            "sourcesContent": [self.source_content],
+            "names": [],
+            "mappings": mappings,
+        })
+        .to_string()
+    }
+}
+
+/// Append `value` as Base64 VLQ: five bits per digit, least significant
+/// first, with the sign in the lowest bit of the first.
+fn encode_vlq(out: &mut String, value: i64) {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut rest = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = rest & 0b11111;
+        rest >>= 5;
+        if rest > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if rest == 0 {
+            break;
+        }
+    }
+}
+
+/// Append a `sourceMappingURL` custom section pointing at `url` to a
+/// binary Wasm module. It comes last, so code offsets stay the same.
+pub fn add_source_mapping_url(binary: &mut Vec<u8>, url: &str) {
+    let mut section = Vec::new();
+    encode_leb128(&mut section, "sourceMappingURL".len());
+    section.extend_from_slice(b"sourceMappingURL");
+    encode_leb128(&mut section, url.len());
+    section.extend_from_slice(url.as_bytes());
+
+    binary.push(0); // Custom section
+    encode_leb128(binary, section.len());
+    binary.extend_from_slice(&section);
+}
+
+fn encode_leb128(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vlq() {
+        let mut out = String::new();
+        for value in [0, 1, -1, 15, 16, -17, 1000] {
+            encode_vlq(&mut out, value);
+            out.push(' ');
+        }
+        assert_eq!(out, "A C D e gB jB w+B ");
+    }
+
+    #[test]
+    fn test_text_lines_map_to_the_js_line_before_them() {
+        let mut table = LineTable::default();
+        table.flushed("header\n");
+        table.mark("", Some(1));
+        table.mark("a\nb\n", Some(3));
+        table.mark("a\nb\nc\n", None);
+        table.flushed("a\nb\nc\nfooter\n");
+        let map = SourceMap::for_text(&table, "a.s", "a.js", "");
+        assert_eq!(
+            map.lines,
+            [vec![], vec![(0, 1)], vec![(0, 1)], vec![(0, 3)], vec![]]
+        );
+        assert!(map.to_json().contains(r#""mappings":";AAAA;AAAA;AAEA;""#));
+    }
+}
@@ -0,0 +1,245 @@
+//! Turns the jumps and labels of a stack IR function into structured
+//! control flow: nested loops, blocks and two-way ifs, left through
+//! branches that count enclosing constructs like Wasm's `br`. Wasm needs it
+//! to be valid, and it suits any backend emitting high-level code.
+//!
+//! Blocks are placed in reverse postorder of the CFG, nested by dominance.
+//! A block that is the target of a backward edge opens a loop, and one
+//! reached by several forward edges is preceded by a block to branch out
+//! of; everything else is placed where it is branched to. This handles the
+//! reducible CFGs the lowering produces.
+
+use crate::ir::{IRFunction, Terminator};
+use crate::optimizer::cfg::ControlFlowGraph;
+
+/// A piece of structured control flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// The instructions of a basic block, by index, without its
+    /// terminator unless it returns. Where control goes next is given by
+    /// the nodes that follow.
+    Code(usize),
+    /// A loop; branching to it starts the next iteration.
+    Loop(Vec<Node>),
+    /// A block; branching to it continues after its end.
+    Block(Vec<Node>),
+    /// Follows a block ending in `JumpIf` whose condition is truthy in
+    /// `then`; it can be branched out of too.
+    If {
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    /// Leave the construct this many levels out, 0 being the innermost.
+    Branch(usize),
+}
+
+/// Enclosing structured construct, innermost last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    IfThenElse,
+    /// A loop whose start is the given block.
+    LoopHeadedBy(usize),
+    /// A block whose end is followed by the given block.
+    BlockFollowedBy(usize),
+}
+
+/// A function's CFG and the per-block facts structuring it needs.
+pub struct Structure<'a> {
+    pub function: &'a IRFunction,
+    pub cfg: ControlFlowGraph,
+    /// Operand stack depth on entry to each block.
+    pub entry_depths: Vec<usize>,
+    rpo_index: Vec<usize>,
+    dominator_children: Vec<Vec<usize>>,
+}
+
+impl Structure<'_> {
+    pub fn new(function: &IRFunction) -> Structure<'_> {
+        let cfg = ControlFlowGraph::build(function);
+        let mut rpo_index = vec![usize::MAX; cfg.blocks.len()];
+        for (i, b) in cfg.reverse_postorder().into_iter().enumerate() {
+            rpo_index[b] = i;
+        }
+
+        let mut dominator_children = vec![Vec::new(); cfg.blocks.len()];
+        for (b, idom) in cfg.immediate_dominators().into_iter().enumerate() {
+            if let Some(idom) = idom.filter(|&idom| idom != b) {
+                dominator_children[idom].push(b);
+            }
+        }
+
+        let mut entry_depths = vec![0; cfg.blocks.len()];
+        let mut work_list = vec![0];
+        let mut visited = vec![false; cfg.blocks.len()];
+        while let Some(b) = work_list.pop() {
+            if visited[b] {
+                continue;
+            }
+            visited[b] = true;
+            let depth = function.blocks[b].exit_depth(entry_depths[b]);
+            for &succ in &cfg.blocks[b].successors {
+                entry_depths[succ] = depth;
+                work_list.push(succ);
+            }
+        }
+
+        Structure {
+            function,
+            cfg,
+            entry_depths,
+            rpo_index,
+            dominator_children,
+        }
+    }
+
+    /// The function body as structured control flow, starting at the entry
+    /// block. Empty for a function without instructions.
+    pub fn nodes(&self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        if !self.cfg.blocks.is_empty() {
+            self.tree(0, &mut Vec::new(), &mut nodes);
+        }
+        nodes
+    }
+
+    fn is_backward(&self, from: usize, to: usize) -> bool {
+        self.rpo_index[to] <= self.rpo_index[from]
+    }
+
+    fn is_loop_header(&self, b: usize) -> bool {
+        self.cfg.blocks[b]
+            .predecessors
+            .iter()
+            .any(|&p| self.rpo_index[p] != usize::MAX && self.is_backward(p, b))
+    }
+
+    /// Blocks reached by more than one forward edge need a block to branch out of.
+    fn is_merge_node(&self, b: usize) -> bool {
+        self.cfg.blocks[b]
+            .predecessors
+            .iter()
+            .filter(|&&p| self.rpo_index[p] != usize::MAX && !self.is_backward(p, b))
+            .count()
+            > 1
+    }
+
+    /// Place block `b` and the blocks it dominates.
+    fn tree(&self, b: usize, context: &mut Vec<Frame>, nodes: &mut Vec<Node>) {
+        // Merge nodes follow this block, the last one in RPO outermost
+        let mut merges: Vec<usize> = self.dominator_children[b]
+            .iter()
+            .copied()
+            .filter(|&child| self.is_merge_node(child))
+            .collect();
+        merges.sort_by_key(|&child| std::cmp::Reverse(self.rpo_index[child]));
+
+        if self.is_loop_header(b) {
+            let mut body = Vec::new();
+            context.push(Frame::LoopHeadedBy(b));
+            self.within(b, &merges, context, &mut body);
+            context.pop();
+            nodes.push(Node::Loop(body));
+        } else {
+            self.within(b, &merges, context, nodes);
+        }
+    }
+
+    fn within(&self, b: usize, merges: &[usize], context: &mut Vec<Frame>, nodes: &mut Vec<Node>) {
+        if let Some((&merge, inner)) = merges.split_first() {
+            let mut body = Vec::new();
+            context.push(Frame::BlockFollowedBy(merge));
+            self.within(b, inner, context, &mut body);
+            context.pop();
+            nodes.push(Node::Block(body));
+            self.tree(merge, context, nodes);
+            return;
+        }
+
+        nodes.push(Node::Code(b));
+        let block = &self.cfg.blocks[b];
+        let fallthrough = b + 1;
+        match self.function.blocks[b].terminator {
+            Terminator::Return(_) => {}
+            Terminator::JumpIf(target) => {
+                if target == fallthrough {
+                    self.branch(b, target, context, nodes);
+                } else {
+                    let (mut then, mut otherwise) = (Vec::new(), Vec::new());
+                    context.push(Frame::IfThenElse);
+                    self.branch(b, target, context, &mut then);
+                    self.branch(b, fallthrough, context, &mut otherwise);
+                    context.pop();
+                    nodes.push(Node::If { then, otherwise });
+                }
+            }
+            // Falling off the end of the function
+            _ if block.successors.is_empty() => {}
+            _ => self.branch(b, block.successors[0], context, nodes),
+        }
+    }
+
+    fn branch(&self, from: usize, to: usize, context: &mut Vec<Frame>, nodes: &mut Vec<Node>) {
+        let frame = if self.is_backward(from, to) {
+            Frame::LoopHeadedBy(to)
+        } else if self.is_merge_node(to) {
+            Frame::BlockFollowedBy(to)
+        } else {
+            // Only reachable from here, so place it here
+            self.tree(to, context, nodes);
+            return;
+        };
+        let depth = context
+            .iter()
+            .rev()
+            .position(|&f| f == frame)
+            .unwrap_or_else(|| panic!("No enclosing construct for {:?}", frame));
+        nodes.push(Node::Branch(depth));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(source: &str) -> Vec<Node> {
+        let module = crate::ir::lower_ast(crate::parser::parse(crate::lexer::tokenize(source)));
+        Structure::new(&module.functions[0]).nodes()
+    }
+
+    #[test]
+    fn test_if_else_joins_in_a_block() {
+        let nodes = nodes("function f(x) { if (x) { print(1); } else { print(2); } return 0; }");
+        let [Node::Block(body), Node::Code(_)] = &nodes[..] else {
+            panic!("Expected a block followed by the join: {:?}", nodes);
+        };
+        let [Node::Code(_), Node::If { then, otherwise }] = &body[..] else {
+            panic!("Expected a two-way if: {:?}", body);
+        };
+        // Both arms leave the block, one level out of the if
+        assert!(matches!(then.last(), Some(Node::Branch(1))));
+        assert!(matches!(otherwise.last(), Some(Node::Branch(1))));
+    }
+
+    #[test]
+    fn test_backward_jump_continues_a_loop() {
+        let nodes = nodes("function f(n) { while (n) { print(n); } return n; }");
+        fn branches_to_loop(nodes: &[Node], depth: usize) -> bool {
+            nodes.iter().any(|node| match node {
+                Node::Branch(d) => *d == depth,
+                Node::Loop(body) | Node::Block(body) => branches_to_loop(body, depth + 1),
+                Node::If { then, otherwise } => {
+                    branches_to_loop(then, depth + 1) || branches_to_loop(otherwise, depth + 1)
+                }
+                Node::Code(_) => false,
+            })
+        }
+        let body = nodes
+            .iter()
+            .find_map(|node| match node {
+                Node::Loop(body) => Some(body),
+                _ => None,
+            })
+            .expect("Expected a loop");
+        assert!(branches_to_loop(body, 0));
+    }
+}
@@ -0,0 +1,203 @@
+use super::{runtime, TargetOs};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// C compiler driver used to assemble and link, overridable with `CC`.
+pub fn c_compiler() -> String {
+    std::env::var("CC").unwrap_or_else(|_| "cc".to_string())
+}
+
+/// Executable path for a program built from `source`.
+pub fn executable_path(source: &Path, os: TargetOs) -> PathBuf {
+    match os {
+        TargetOs::Windows => source.with_extension("exe"),
+        TargetOs::Linux | TargetOs::MacOs => source.with_extension(""),
+    }
+}
+
+/// Assemble and link `code` (a `.s` or object file) with the runtime library
+/// into the executable `output`. The runtime source is written next to it.
+pub fn link_executable(code: &Path, output: &Path) -> Result<(), String> {
+    let runtime_path = output.with_file_name(runtime::RUNTIME_FILE_NAME);
+    fs::write(&runtime_path, runtime::RUNTIME_C_SOURCE)
+        .map_err(|e| format!("Failed to write {}: {}", runtime_path.display(), e))?;
+
+    let compiler = c_compiler();
+    let status = Command::new(&compiler)
+        .arg(code)
+        .arg(&runtime_path)
+        .arg("-o")
+        .arg(output)
+        .arg("-lm")
+        .status()
+        .map_err(|e| format!("Failed to run {}: {}", compiler, e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", compiler, status))
+    }
+}
+
+#[cfg(all(test, feature = "x64"))]
+mod tests {
+    use super::*;
+    use crate::codegen::{generate_code, CodegenOptions, Target};
+    use crate::ir::lower_ast;
+    use crate::lexer::tokenize;
+    use crate::parser::parse;
+
+    /// Whether the host runs x64 Unix executables and has a C compiler.
+    fn can_run_x64() -> bool {
+        cfg!(target_arch = "x86_64")
+            && CodegenOptions::default().target.os != TargetOs::Windows
+            && Command::new(c_compiler()).arg("--version").output().is_ok()
+    }
+
+    /// Compile, link and run `source` natively, returning its output; `None`
+    /// when the host can't run x64 Unix executables or has no C compiler.
+    fn run_x64_program(source: &str, name: &str) -> Option<std::process::Output> {
+        let options = CodegenOptions::default();
+        if !can_run_x64() {
+            return None;
+        }
+
+        let module = lower_ast(parse(tokenize(source)));
+        let asm = generate_code(module, Target::X64, &options).unwrap();
+        let dir = std::env::temp_dir().join(format!("js-compiler-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let code = dir.join("program.s");
+        fs::write(&code, asm).unwrap();
+        let executable = executable_path(&code, options.target.os);
+
+        link_executable(&code, &executable).unwrap();
+        let output = Command::new(&executable).output().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        Some(output)
+    }
+
+    #[test]
+    fn test_link_and_run_x64_program() {
+        if let Some(output) = run_x64_program("function main() { print(); }", "link") {
+            assert_eq!(output.stdout, b"\n");
+        }
+    }
+
+    #[test]
+    fn test_exit_status_is_main_result() {
+        let source = "function main() { let t = true; return t + t + t; }";
+        if let Some(output) = run_x64_program(source, "exit-status") {
+            assert_eq!(output.status.code(), Some(3));
+        }
+        let source = "function main() { print(); }";
+        if let Some(output) = run_x64_program(source, "exit-undefined") {
+            assert_eq!(output.status.code(), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_x64_calls_keep_arguments_and_alignment() {
+        let source = "
+            function pick(a, b) { return b; }
+            function twice(x) { print(x, x); }
+            function main() { let t = true; print(pick(t, null), pick(null, t)); twice(t); }";
+        if let Some(output) = run_x64_program(source, "calls") {
+            assert_eq!(output.stdout, b"null true\ntrue true\n");
+        }
+    }
+
+    #[test]
+    fn test_x64_stack_arguments() {
+        let source = "
+            function last(a, b, c, d, e, f, g, h, i, j) { print(a, h, i, j); return i; }
+            function main() { let t = true; let n = null; print(last(t + t, n, n, n, n, n, n, t, n, t)); }";
+        if let Some(output) = run_x64_program(source, "stack-arguments") {
+            assert_eq!(output.stdout, b"2 true null true\nnull\n");
+        }
+    }
+
+    #[test]
+    fn test_x64_dynamic_operations() {
+        let source = "
+            function main() { let t = true; let n = null; print(t + n, -t, !n, n == n, t < t + t, t * t - t); }";
+        if let Some(output) = run_x64_program(source, "dynamic") {
+            assert_eq!(output.stdout, b"1 -1 true true true 0\n");
+        }
+    }
+
+    #[test]
+    fn test_x64_extern_calls() {
+        let source = "
+            extern function puts(string): int;
+            extern function abs(int): int;
+            extern function sqrt(double): double;
+            extern function getenv(string): string;
+            extern function printf(string, ...): int;
+            function main() {
+                let t = true; let n = null; let four = t + t + t + t;
+                puts(t);
+                print(abs(-four), sqrt(four), getenv(n));
+                printf(n, t);
+                print();
+            }";
+        if let Some(output) = run_x64_program(source, "extern") {
+            assert_eq!(output.stdout, b"true\n4 2 null\nnull\n");
+        }
+    }
+
+    #[test]
+    fn test_x64_c_abi_library() {
+        if !can_run_x64() {
+            return;
+        }
+        let source = "
+            function hypot2(a, b) { return a * a + b * b; }
+            function is_big(x) { return x > 100; }
+            function id(x) { return x; }";
+        // The NaN passed to `id` would read as a string at address 0
+        let c_main = r#"
+            #include <stdint.h>
+            #include <stdio.h>
+            #include <string.h>
+            double hypot2(double, double);
+            double is_big(double);
+            double id(double);
+            int main(void) {
+                uint64_t bits = 0xFFF9000000000000ull;
+                double nan;
+                memcpy(&nan, &bits, sizeof nan);
+                printf("%g %g %g %g\n", hypot2(3, 4), is_big(1000), is_big(1), id(nan));
+                return 0;
+            }"#;
+        let options = CodegenOptions {
+            c_abi: true,
+            ..Default::default()
+        };
+        let module = lower_ast(parse(tokenize(source)));
+        let asm = generate_code(module, Target::X64, &options).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("js-compiler-c-abi-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let code = dir.join("library.s");
+        fs::write(&code, asm).unwrap();
+        let main = dir.join("main.c");
+        fs::write(&main, c_main).unwrap();
+        let runtime_path = dir.join(runtime::RUNTIME_FILE_NAME);
+        fs::write(&runtime_path, runtime::RUNTIME_C_SOURCE).unwrap();
+        let executable = dir.join("program");
+        let status = Command::new(c_compiler())
+            .arg(&code)
+            .arg(&main)
+            .arg(&runtime_path)
+            .arg("-o")
+            .arg(&executable)
+            .arg("-lm")
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let output = Command::new(&executable).output().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(output.stdout, b"25 1 0 nan\n");
+    }
+}
@@ -1,26 +1,1140 @@
-use super::CodeGenerator;
-use crate::ir::{BinaryOp, Constant, IRFunction, IRInstruction, IRModule, UnaryOp};
+use super::source_map::LineTable;
+use super::structurizer::{Node, Structure};
+use super::{
+    closures_unsupported, objects_unsupported, CodeGenerator, CodegenOptions, Target, WasmHost,
+    WasmValues,
+};
+use crate::ir::{
+    BinaryOp, CType, Constant, ExternFunction, IRFunction, IRInstruction, IRModule, Terminator,
+    UnaryOp,
+};
 use std::collections::HashMap;
+use std::io;
+
+/// Bits of the NaN that stands for `null`.
+const NULL_BITS: u64 = 0x7ffb_0000_0000_0000;
+
+/// Bits of the NaN that stands for `undefined`. NaN itself is the
+/// canonical NaN below the tags, which arithmetic on numbers keeps to.
+const UNDEFINED_BITS: u64 = 0x7ffd_0000_0000_0000;
+
+/// NaN tag of string values; the low 32 bits address a `(ptr, len)` pair
+/// of i32s in linear memory.
+const STRING_TAG: u64 = 0x7ffc_0000_0000_0000;
+
+/// NaN tag of booleans; the low bit is the value.
+const BOOLEAN_TAG: u64 = 0x7ffa_0000_0000_0000;
+
+/// Bump allocator for strings built at run time, growing memory as needed.
+const MEMORY_FUNCTIONS: &str = r#"(func $jsrt_alloc (param $size i32) (result i32)
+(local $ptr i32)
+global.get $jsrt_heap
+local.set $ptr
+local.get $ptr
+local.get $size
+i32.add
+i32.const 7
+i32.add
+i32.const -8
+i32.and
+global.set $jsrt_heap
+block
+global.get $jsrt_heap
+memory.size
+i32.const 16
+i32.shl
+i32.le_u
+br_if 0
+global.get $jsrt_heap
+memory.size
+i32.const 16
+i32.shl
+i32.sub
+i32.const 65535
+i32.add
+i32.const 16
+i32.shr_u
+memory.grow
+i32.const -1
+i32.eq
+if
+unreachable
+end
+end
+local.get $ptr
+)
+"#;
+
+/// `%` on numbers, which Wasm has no instruction for: `x - trunc(x / y) * y`,
+/// corrected when `x / y` rounds to the wrong integer so that safe integers
+/// give exact results. The remainder keeps the sign of `x`, and a finite `x`
+/// is its own remainder by an infinite `y`.
+const NUMBER_FUNCTIONS: &str = r#"(func $jsrt_fmod (param $x f64) (param $y f64) (result f64)
+  (local $r f64)
+  (if (i32.and
+        (f64.eq (f64.abs (local.get $y)) (f64.const inf))
+        (f64.lt (f64.abs (local.get $x)) (f64.const inf)))
+    (then (return (local.get $x))))
+  (local.set $r
+    (f64.sub
+      (local.get $x)
+      (f64.mul (f64.trunc (f64.div (local.get $x) (local.get $y))) (local.get $y))))
+  (if (f64.ge (f64.abs (local.get $r)) (f64.abs (local.get $y)))
+    (then (local.set $r (f64.sub (local.get $r) (f64.copysign (local.get $y) (local.get $r))))))
+  (if (f64.lt (f64.mul (local.get $r) (local.get $x)) (f64.const 0))
+    (then (local.set $r (f64.add (local.get $r) (f64.copysign (local.get $y) (local.get $x))))))
+  (f64.copysign (local.get $r) (local.get $x)))
+"#;
+
+/// Order of two byte strings in memory: negative, zero or positive as the
+/// first sorts before, equal to or after the second.
+const COMPARE_BYTES: &str = r#"(func $jsrt_compare_bytes (param $a i32) (param $a_len i32) (param $b i32) (param $b_len i32) (result i32)
+  (local $i i32)
+  (local $end i32)
+  (local $diff i32)
+  (local.set $end
+    (select (local.get $a_len) (local.get $b_len) (i32.lt_u (local.get $a_len) (local.get $b_len))))
+  (block $done
+    (loop $next_byte
+      (br_if $done (i32.ge_u (local.get $i) (local.get $end)))
+      (local.set $diff
+        (i32.sub
+          (i32.load8_u (i32.add (local.get $a) (local.get $i)))
+          (i32.load8_u (i32.add (local.get $b) (local.get $i)))))
+      (if (local.get $diff)
+        (then (return (local.get $diff))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $next_byte)))
+  (i32.sub (local.get $a_len) (local.get $b_len)))
+"#;
+
+/// Decimal scaling shared by number formatting and parsing, and
+/// `$jsrt_parse_number`, which gives strings their number value.
+const DECIMAL_FUNCTIONS: &str = r#"(func $jsrt_pow10 (param $k i32) (result f64)
+  (local $result f64)
+  (local.set $result (f64.const 1))
+  (block $done
+    (loop $multiply
+      (br_if $done (i32.le_s (local.get $k) (i32.const 0)))
+      (local.set $result (f64.mul (local.get $result) (f64.const 10)))
+      (local.set $k (i32.sub (local.get $k) (i32.const 1)))
+      (br $multiply)))
+  (local.get $result))
+;; The exact product `a * b` minus the rounded one, by Dekker's splitting
+(func $jsrt_product_error (param $a f64) (param $b f64) (result f64)
+  (local $split f64)
+  (local $a_hi f64)
+  (local $a_lo f64)
+  (local $b_hi f64)
+  (local $b_lo f64)
+  (local.set $split (f64.mul (local.get $a) (f64.const 134217729)))
+  (local.set $a_hi (f64.sub (local.get $split) (f64.sub (local.get $split) (local.get $a))))
+  (local.set $a_lo (f64.sub (local.get $a) (local.get $a_hi)))
+  (local.set $split (f64.mul (local.get $b) (f64.const 134217729)))
+  (local.set $b_hi (f64.sub (local.get $split) (f64.sub (local.get $split) (local.get $b))))
+  (local.set $b_lo (f64.sub (local.get $b) (local.get $b_hi)))
+  (f64.add
+    (f64.add
+      (f64.add
+        (f64.sub (f64.mul (local.get $a_hi) (local.get $b_hi)) (f64.mul (local.get $a) (local.get $b)))
+        (f64.mul (local.get $a_hi) (local.get $b_lo)))
+      (f64.mul (local.get $a_lo) (local.get $b_hi)))
+    (f64.mul (local.get $a_lo) (local.get $b_lo))))
+;; `value * 10^k` as a double and the error of rounding it to one. Each
+;; step scales by an exact power of ten up to 10^22 and carries the error
+;; along, so the pair stays within about 2^-100 of the product.
+(func $jsrt_scale (param $value f64) (param $k i32) (result f64 f64)
+  (local $hi f64)
+  (local $lo f64)
+  (local $step i32)
+  (local $power f64)
+  (local $product f64)
+  (local $error f64)
+  (local $unscale f64)
+  ;; Values near the ends of the range first move by an exact 2^128 so
+  ;; that no step underflows or overflows
+  (local.set $unscale (f64.const 1))
+  (if (f64.lt (local.get $value) (f64.const 0x1p-900))
+    (then
+      (local.set $value (f64.mul (local.get $value) (f64.const 0x1p128)))
+      (local.set $unscale (f64.const 0x1p-128))))
+  (if (f64.gt (local.get $value) (f64.const 0x1p900))
+    (then
+      (local.set $value (f64.mul (local.get $value) (f64.const 0x1p-128)))
+      (local.set $unscale (f64.const 0x1p128))))
+  (local.set $hi (local.get $value))
+  (loop $next_step
+    (local.set $step
+      (select
+        (i32.const 22)
+        (select (i32.sub (i32.const 0) (local.get $k)) (local.get $k) (i32.lt_s (local.get $k) (i32.const 0)))
+        (i32.gt_u
+          (select (i32.sub (i32.const 0) (local.get $k)) (local.get $k) (i32.lt_s (local.get $k) (i32.const 0)))
+          (i32.const 22))))
+    (local.set $power (call $jsrt_pow10 (local.get $step)))
+    (if (i32.ge_s (local.get $k) (i32.const 0))
+      (then
+        (local.set $product (f64.mul (local.get $hi) (local.get $power)))
+        (local.set $error
+          (f64.add
+            (call $jsrt_product_error (local.get $hi) (local.get $power))
+            (f64.mul (local.get $lo) (local.get $power))))
+        (local.set $k (i32.sub (local.get $k) (local.get $step))))
+      (else
+        ;; `product * power` misses `hi` by `-error * power`
+        (local.set $product (f64.div (local.get $hi) (local.get $power)))
+        (local.set $error
+          (f64.div
+            (f64.sub
+              (local.get $lo)
+              (f64.add
+                (f64.sub (f64.mul (local.get $product) (local.get $power)) (local.get $hi))
+                (call $jsrt_product_error (local.get $product) (local.get $power))))
+            (local.get $power)))
+        (local.set $k (i32.add (local.get $k) (local.get $step)))))
+    (local.set $hi (f64.add (local.get $product) (local.get $error)))
+    (local.set $lo (f64.sub (local.get $error) (f64.sub (local.get $hi) (local.get $product))))
+    (br_if $next_step (local.get $k)))
+  (f64.mul (local.get $hi) (local.get $unscale))
+  (f64.mul (local.get $lo) (local.get $unscale)))
+(func $jsrt_is_space (param $c i32) (result i32)
+  (i32.or
+    (i32.eq (local.get $c) (i32.const 32))
+    (i32.lt_u (i32.sub (local.get $c) (i32.const 9)) (i32.const 5))))
+;; Value of a hex digit, or 16 for any other byte
+(func $jsrt_digit_value (param $c i32) (result i32)
+  (if (i32.lt_u (i32.sub (local.get $c) (i32.const 48)) (i32.const 10))
+    (then (return (i32.sub (local.get $c) (i32.const 48)))))
+  (local.set $c (i32.or (local.get $c) (i32.const 32)))
+  (if (i32.lt_u (i32.sub (local.get $c) (i32.const 97)) (i32.const 6))
+    (then (return (i32.sub (local.get $c) (i32.const 87)))))
+  (i32.const 16))
+;; The number a string converts to, as in JavaScript: surrounding ASCII
+;; whitespace is ignored, an empty string is 0, and otherwise the rest must
+;; be a decimal literal, a signed or unsigned Infinity, or a 0x, 0o or 0b
+;; integer, or the result is NaN. Decimals keep 19 significant digits and
+;; are scaled as a pair of doubles, so they round correctly unless within
+;; about 2^-100 of halfway between two doubles.
+(func $jsrt_parse_number (param $ptr i32) (param $len i32) (result f64)
+  (local $end i32)
+  (local $c i32)
+  (local $radix i32)
+  (local $digit i32)
+  (local $negative i32)
+  (local $point i32)
+  (local $seen i32)
+  (local $digits i32)
+  (local $exponent i32)
+  (local $e i32)
+  (local $e_negative i32)
+  (local $mantissa i64)
+  (local $value f64)
+  (local $hi f64)
+  (local $lo f64)
+  (local $error f64)
+  (local.set $end (i32.add (local.get $ptr) (local.get $len)))
+  (block $trimmed
+    (loop $trim_start
+      (br_if $trimmed (i32.ge_u (local.get $ptr) (local.get $end)))
+      (br_if $trimmed (i32.eqz (call $jsrt_is_space (i32.load8_u (local.get $ptr)))))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+      (br $trim_start)))
+  (block $trimmed
+    (loop $trim_end
+      (br_if $trimmed (i32.ge_u (local.get $ptr) (local.get $end)))
+      (br_if $trimmed
+        (i32.eqz (call $jsrt_is_space (i32.load8_u (i32.sub (local.get $end) (i32.const 1))))))
+      (local.set $end (i32.sub (local.get $end) (i32.const 1)))
+      (br $trim_end)))
+  (if (i32.eq (local.get $ptr) (local.get $end))
+    (then (return (f64.const 0))))
+  ;; 0x, 0o and 0b integers take no sign and at least one digit
+  (if (i32.and
+        (i32.ge_u (i32.sub (local.get $end) (local.get $ptr)) (i32.const 3))
+        (i32.eq (i32.load8_u (local.get $ptr)) (i32.const 48)))
+    (then
+      (local.set $c (i32.or (i32.load8_u offset=1 (local.get $ptr)) (i32.const 32)))
+      (local.set $radix
+        (select
+          (i32.const 16)
+          (select
+            (i32.const 8)
+            (select (i32.const 2) (i32.const 0) (i32.eq (local.get $c) (i32.const 98)))
+            (i32.eq (local.get $c) (i32.const 111)))
+          (i32.eq (local.get $c) (i32.const 120))))
+      (if (local.get $radix)
+        (then
+          (local.set $ptr (i32.add (local.get $ptr) (i32.const 2)))
+          (loop $next_digit
+            (local.set $digit (call $jsrt_digit_value (i32.load8_u (local.get $ptr))))
+            (if (i32.ge_u (local.get $digit) (local.get $radix))
+              (then (return (f64.const nan))))
+            (local.set $value
+              (f64.add
+                (f64.mul (local.get $value) (f64.convert_i32_u (local.get $radix)))
+                (f64.convert_i32_u (local.get $digit))))
+            (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+            (br_if $next_digit (i32.lt_u (local.get $ptr) (local.get $end))))
+          (return (local.get $value))))))
+  (local.set $c (i32.load8_u (local.get $ptr)))
+  (if (i32.or (i32.eq (local.get $c) (i32.const 43)) (i32.eq (local.get $c) (i32.const 45)))
+    (then
+      (local.set $negative (i32.eq (local.get $c) (i32.const 45)))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))))
+  ;; "Infinity"
+  (if (i32.and
+        (i32.eq (i32.sub (local.get $end) (local.get $ptr)) (i32.const 8))
+        (i64.eq (i64.load (local.get $ptr)) (i64.const 0x7974696e69666e49)))
+    (then
+      (return
+        (select (f64.const -inf) (f64.const inf) (local.get $negative)))))
+  ;; Digits and a decimal point, then an optional exponent
+  (block $digits_done
+    (loop $next_char
+      (br_if $digits_done (i32.ge_u (local.get $ptr) (local.get $end)))
+      (local.set $c (i32.load8_u (local.get $ptr)))
+      (if (i32.eq (local.get $c) (i32.const 46))
+        (then
+          (if (local.get $point)
+            (then (return (f64.const nan))))
+          (local.set $point (i32.const 1)))
+        (else
+          (local.set $digit (i32.sub (local.get $c) (i32.const 48)))
+          (br_if $digits_done (i32.ge_u (local.get $digit) (i32.const 10)))
+          (local.set $seen (i32.const 1))
+          (if (i32.lt_u (local.get $digits) (i32.const 19))
+            (then
+              ;; Leading zeros only move the point
+              (if (i32.or (i64.ne (local.get $mantissa) (i64.const 0)) (local.get $digit))
+                (then
+                  (local.set $mantissa
+                    (i64.add
+                      (i64.mul (local.get $mantissa) (i64.const 10))
+                      (i64.extend_i32_u (local.get $digit))))
+                  (local.set $digits (i32.add (local.get $digits) (i32.const 1)))))
+              (if (local.get $point)
+                (then (local.set $exponent (i32.sub (local.get $exponent) (i32.const 1))))))
+            (else
+              ;; Digits past the 19th are dropped, keeping their place
+              (if (i32.eqz (local.get $point))
+                (then (local.set $exponent (i32.add (local.get $exponent) (i32.const 1)))))))))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+      (br $next_char)))
+  (if (i32.eqz (local.get $seen))
+    (then (return (f64.const nan))))
+  (if (i32.lt_u (local.get $ptr) (local.get $end))
+    (then
+      (if (i32.ne (i32.or (i32.load8_u (local.get $ptr)) (i32.const 32)) (i32.const 101))
+        (then (return (f64.const nan))))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+      (if (i32.lt_u (local.get $ptr) (local.get $end))
+        (then
+          (local.set $c (i32.load8_u (local.get $ptr)))
+          (if (i32.or (i32.eq (local.get $c) (i32.const 43)) (i32.eq (local.get $c) (i32.const 45)))
+            (then
+              (local.set $e_negative (i32.eq (local.get $c) (i32.const 45)))
+              (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))))))
+      (if (i32.ge_u (local.get $ptr) (local.get $end))
+        (then (return (f64.const nan))))
+      (loop $next_exponent_digit
+        (local.set $digit (i32.sub (i32.load8_u (local.get $ptr)) (i32.const 48)))
+        (if (i32.ge_u (local.get $digit) (i32.const 10))
+          (then (return (f64.const nan))))
+        ;; Far past the range of doubles any larger exponent gives the same
+        (if (i32.lt_u (local.get $e) (i32.const 100000))
+          (then
+            (local.set $e
+              (i32.add (i32.mul (local.get $e) (i32.const 10)) (local.get $digit)))))
+        (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+        (br_if $next_exponent_digit (i32.lt_u (local.get $ptr) (local.get $end))))
+      (local.set $exponent
+        (select
+          (i32.sub (local.get $exponent) (local.get $e))
+          (i32.add (local.get $exponent) (local.get $e))
+          (local.get $e_negative)))))
+  (local.set $value (f64.convert_i64_u (local.get $mantissa)))
+  (if (i64.ne (local.get $mantissa) (i64.const 0))
+    (then
+      (if (i32.and
+            (i64.lt_u (local.get $mantissa) (i64.const 0x20000000000000))
+            (i32.le_u (i32.add (local.get $exponent) (i32.const 22)) (i32.const 44)))
+        (then
+          ;; Both operands are exact, so the one rounding is correct
+          (local.set $value
+            (select
+              (f64.mul (local.get $value) (call $jsrt_pow10 (local.get $exponent)))
+              (f64.div (local.get $value) (call $jsrt_pow10 (i32.sub (i32.const 0) (local.get $exponent))))
+              (i32.ge_s (local.get $exponent) (i32.const 0)))))
+        (else
+          (if (i32.gt_s (local.get $exponent) (i32.const 310))
+            (then (local.set $value (f64.const inf)))
+            (else
+              (if (i32.lt_s (local.get $exponent) (i32.const -400))
+                (then (local.set $value (f64.const 0)))
+                (else
+                  ;; The mantissa as a double and what it missed by, each scaled
+                  (local.set $lo
+                    (f64.convert_i64_s
+                      (i64.sub (local.get $mantissa) (i64.trunc_f64_u (local.get $value)))))
+                  (call $jsrt_scale (local.get $value) (local.get $exponent))
+                  (local.set $error)
+                  (local.set $hi)
+                  (call $jsrt_scale (local.get $lo) (local.get $exponent))
+                  (drop)
+                  (local.set $lo)
+                  (local.set $value
+                    (f64.add (local.get $hi) (f64.add (local.get $error) (local.get $lo))))
+                  ;; Past the largest double the error terms are inf - inf
+                  (if (f64.ne (local.get $value) (local.get $value))
+                    (then (local.set $value (f64.const inf))))))))))))
+  (select (f64.neg (local.get $value)) (local.get $value) (local.get $negative)))
+"#;
+
+/// Support functions for NaN-boxed values. Host imports print values and
+/// format numbers into memory; concatenation allocates from a bump heap.
+/// Booleans, null, strings and undefined are tagged 0x7ffa to 0x7ffd.
+const RUNTIME_FUNCTIONS: &str = r#"(func $jsrt_is_boxed (param $value i64) (result i32)
+  (i64.lt_u
+    (i64.sub (i64.shr_u (local.get $value) (i64.const 48)) (i64.const 0x7ffa))
+    (i64.const 4)))
+(func $jsrt_is_string (param $value i64) (result i32)
+local.get $value
+i64.const 48
+i64.shr_u
+i64.const 0x7ffc
+i64.eq
+)
+(func $jsrt_is_boolean (param $value i64) (result i32)
+local.get $value
+i64.const 48
+i64.shr_u
+i64.const 0x7ffa
+i64.eq
+)
+(func $jsrt_to_number (param $value i64) (result f64)
+  (local $pair i32)
+  (if (i32.eqz (call $jsrt_is_boxed (local.get $value)))
+    (then (return (f64.reinterpret_i64 (local.get $value)))))
+  (if (call $jsrt_is_string (local.get $value))
+    (then
+      (local.set $pair (i32.wrap_i64 (local.get $value)))
+      (return
+        (call $jsrt_parse_number (i32.load (local.get $pair)) (i32.load offset=4 (local.get $pair))))))
+  (if (call $jsrt_is_boolean (local.get $value))
+    (then (return (f64.convert_i32_u (i32.wrap_i64 (local.get $value))))))
+  (if (i64.eq (local.get $value) (global.get $jsrt_null))
+    (then (return (f64.const 0))))
+  (f64.const nan))
+(func $jsrt_make_string (param $ptr i32) (param $len i32) (result i64)
+(local $pair i32)
+i32.const 8
+call $jsrt_alloc
+local.tee $pair
+local.get $ptr
+i32.store
+local.get $pair
+local.get $len
+i32.store offset=4
+local.get $pair
+i64.extend_i32_u
+i64.const 0x7ffc000000000000
+i64.or
+)
+(func $jsrt_to_string (param $value i64) (result i64)
+(local $ptr i32)
+local.get $value
+call $jsrt_is_string
+if (result i64)
+local.get $value
+else
+local.get $value
+call $jsrt_is_boolean
+if (result i64)
+global.get $jsrt_true_string
+global.get $jsrt_false_string
+local.get $value
+i32.wrap_i64
+select
+else
+local.get $value
+global.get $jsrt_null
+i64.eq
+if (result i64)
+global.get $jsrt_null_string
+else
+local.get $value
+global.get $jsrt_undefined
+i64.eq
+if (result i64)
+global.get $jsrt_undefined_string
+else
+i32.const 32
+call $jsrt_alloc
+local.tee $ptr
+local.get $value
+f64.reinterpret_i64
+local.get $ptr
+call $format_number
+call $jsrt_make_string
+end
+end
+end
+end
+)
+(func $jsrt_concat (param $left i64) (param $right i64) (result i64)
+(local $left_ptr i32)
+(local $left_len i32)
+(local $right_ptr i32)
+(local $right_len i32)
+(local $ptr i32)
+local.get $left
+call $jsrt_to_string
+i32.wrap_i64
+local.tee $ptr
+i32.load
+local.set $left_ptr
+local.get $ptr
+i32.load offset=4
+local.set $left_len
+local.get $right
+call $jsrt_to_string
+i32.wrap_i64
+local.tee $ptr
+i32.load
+local.set $right_ptr
+local.get $ptr
+i32.load offset=4
+local.set $right_len
+local.get $left_len
+local.get $right_len
+i32.add
+call $jsrt_alloc
+local.tee $ptr
+local.get $left_ptr
+local.get $left_len
+memory.copy
+local.get $ptr
+local.get $left_len
+i32.add
+local.get $right_ptr
+local.get $right_len
+memory.copy
+local.get $ptr
+local.get $left_len
+local.get $right_len
+i32.add
+call $jsrt_make_string
+)
+(func $jsrt_add (param $left i64) (param $right i64) (result i64)
+local.get $left
+call $jsrt_is_string
+local.get $right
+call $jsrt_is_string
+i32.or
+if (result i64)
+local.get $left
+local.get $right
+call $jsrt_concat
+else
+local.get $left
+call $jsrt_to_number
+local.get $right
+call $jsrt_to_number
+f64.add
+i64.reinterpret_f64
+end
+)
+(func $jsrt_truthy (param $value i64) (result i32)
+(local $number f64)
+local.get $value
+call $jsrt_is_string
+if (result i32)
+local.get $value
+i32.wrap_i64
+i32.load offset=4
+i32.const 0
+i32.ne
+else
+local.get $value
+call $jsrt_to_number
+local.tee $number
+f64.const 0
+f64.ne
+local.get $number
+local.get $number
+f64.eq
+i32.and
+end
+)
+(func $jsrt_print_value (param $value i64)
+(local $pair i32)
+local.get $value
+call $jsrt_is_boxed
+if
+local.get $value
+call $jsrt_to_string
+i32.wrap_i64
+local.tee $pair
+i32.load
+local.get $pair
+i32.load offset=4
+call $log_string
+else
+local.get $value
+call $log
+end
+)
+"#;
+
+/// `==`, under which null and undefined only equal each other, two
+/// strings are equal by their bytes and anything else compares as numbers;
+/// `===`, which never converts: strings are equal by their bytes, other
+/// boxed values by their bits, and otherwise only numbers equal numbers.
+/// The operands of `<` and friends: two strings become their order and 0,
+/// anything else its number.
+const COMPARISON_FUNCTIONS: &str = r#"(func $jsrt_string_order (param $left i64) (param $right i64) (result i32)
+  (local $left_pair i32)
+  (local $right_pair i32)
+  (local.set $left_pair (i32.wrap_i64 (local.get $left)))
+  (local.set $right_pair (i32.wrap_i64 (local.get $right)))
+  (call $jsrt_compare_bytes
+    (i32.load (local.get $left_pair))
+    (i32.load offset=4 (local.get $left_pair))
+    (i32.load (local.get $right_pair))
+    (i32.load offset=4 (local.get $right_pair))))
+(func $jsrt_is_nullish (param $value i64) (result i32)
+  (i32.or
+    (i64.eq (local.get $value) (global.get $jsrt_null))
+    (i64.eq (local.get $value) (global.get $jsrt_undefined))))
+(func $jsrt_equals (param $left i64) (param $right i64) (result i32)
+  (if (i32.or (call $jsrt_is_nullish (local.get $left)) (call $jsrt_is_nullish (local.get $right)))
+    (then
+      (return
+        (i32.and (call $jsrt_is_nullish (local.get $left)) (call $jsrt_is_nullish (local.get $right))))))
+  (if (i32.and (call $jsrt_is_string (local.get $left)) (call $jsrt_is_string (local.get $right)))
+    (then (return (i32.eqz (call $jsrt_string_order (local.get $left) (local.get $right))))))
+  (f64.eq (call $jsrt_to_number (local.get $left)) (call $jsrt_to_number (local.get $right))))
+(func $jsrt_strict_equals (param $left i64) (param $right i64) (result i32)
+  (if (i32.or (call $jsrt_is_string (local.get $left)) (call $jsrt_is_string (local.get $right)))
+    (then
+      (return
+        (select
+          (i32.eqz (call $jsrt_string_order (local.get $left) (local.get $right)))
+          (i32.const 0)
+          (i32.and
+            (call $jsrt_is_string (local.get $left))
+            (call $jsrt_is_string (local.get $right)))))))
+  (if (i32.or (call $jsrt_is_boxed (local.get $left)) (call $jsrt_is_boxed (local.get $right)))
+    (then (return (i64.eq (local.get $left) (local.get $right)))))
+  (f64.eq (f64.reinterpret_i64 (local.get $left)) (f64.reinterpret_i64 (local.get $right))))
+(func $jsrt_comparable (param $left i64) (param $right i64) (result f64 f64)
+  (if (i32.and (call $jsrt_is_string (local.get $left)) (call $jsrt_is_string (local.get $right)))
+    (then
+      (return
+        (f64.convert_i32_s (call $jsrt_string_order (local.get $left) (local.get $right)))
+        (f64.const 0))))
+  (call $jsrt_to_number (local.get $left))
+  (call $jsrt_to_number (local.get $right)))
+"#;
+
+/// Support functions for GC values. Strings are `(ptr, len)` structs over
+/// linear memory, so they share the allocator and host imports.
+const GC_RUNTIME_FUNCTIONS: &str = r#"(func $jsrt_to_number (param $value anyref) (result f64)
+  (if (ref.test (ref $number) (local.get $value))
+    (then (return (struct.get $number 0 (ref.cast (ref $number) (local.get $value))))))
+  (if (ref.test (ref i31) (local.get $value))
+    (then (return (f64.convert_i32_u (i31.get_u (ref.cast (ref i31) (local.get $value)))))))
+  (if (ref.is_null (local.get $value))
+    (then (return (f64.const 0))))
+  (if (ref.test (ref $string) (local.get $value))
+    (then
+      (return
+        (call $jsrt_parse_number
+          (struct.get $string 0 (ref.cast (ref $string) (local.get $value)))
+          (struct.get $string 1 (ref.cast (ref $string) (local.get $value)))))))
+  (f64.const nan))
+(func $jsrt_truthy (param $value anyref) (result i32)
+  (local $number f64)
+  (if (ref.test (ref $string) (local.get $value))
+    (then
+      (return
+        (i32.ne (struct.get $string 1 (ref.cast (ref $string) (local.get $value))) (i32.const 0)))))
+  (local.set $number (call $jsrt_to_number (local.get $value)))
+  (i32.and
+    (f64.ne (local.get $number) (f64.const 0))
+    (f64.eq (local.get $number) (local.get $number))))
+(func $jsrt_to_string (param $value anyref) (result (ref $string))
+  (local $ptr i32)
+  (if (ref.test (ref $string) (local.get $value))
+    (then (return (ref.cast (ref $string) (local.get $value)))))
+  (if (ref.test (ref i31) (local.get $value))
+    (then
+      (return
+        (select (result (ref $string))
+          (global.get $jsrt_true_string)
+          (global.get $jsrt_false_string)
+          (i31.get_u (ref.cast (ref i31) (local.get $value)))))))
+  (if (ref.is_null (local.get $value))
+    (then (return (global.get $jsrt_null_string))))
+  (if (ref.test (ref $undefined) (local.get $value))
+    (then (return (global.get $jsrt_undefined_string))))
+  (local.set $ptr (call $jsrt_alloc (i32.const 32)))
+  (struct.new $string
+    (local.get $ptr)
+    (call $format_number
+      (struct.get $number 0 (ref.cast (ref $number) (local.get $value)))
+      (local.get $ptr))))
+(func $jsrt_concat (param $left (ref $string)) (param $right (ref $string)) (result (ref $string))
+  (local $ptr i32)
+  (local $left_len i32)
+  (local $right_len i32)
+  (local.set $left_len (struct.get $string 1 (local.get $left)))
+  (local.set $right_len (struct.get $string 1 (local.get $right)))
+  (local.set $ptr (call $jsrt_alloc (i32.add (local.get $left_len) (local.get $right_len))))
+  (memory.copy (local.get $ptr) (struct.get $string 0 (local.get $left)) (local.get $left_len))
+  (memory.copy
+    (i32.add (local.get $ptr) (local.get $left_len))
+    (struct.get $string 0 (local.get $right))
+    (local.get $right_len))
+  (struct.new $string (local.get $ptr) (i32.add (local.get $left_len) (local.get $right_len))))
+(func $jsrt_add (param $left anyref) (param $right anyref) (result anyref)
+  (if (i32.or
+        (ref.test (ref $string) (local.get $left))
+        (ref.test (ref $string) (local.get $right)))
+    (then
+      (return
+        (call $jsrt_concat
+          (call $jsrt_to_string (local.get $left))
+          (call $jsrt_to_string (local.get $right))))))
+  (struct.new $number
+    (f64.add (call $jsrt_to_number (local.get $left)) (call $jsrt_to_number (local.get $right)))))
+(func $jsrt_print_value (param $value anyref)
+  (local $heap i32)
+  (local $string (ref null $string))
+  (local.set $heap (global.get $jsrt_heap))
+  (local.set $string (call $jsrt_to_string (local.get $value)))
+  (call $log_string (struct.get $string 0 (local.get $string)) (struct.get $string 1 (local.get $string)))
+  (global.set $jsrt_heap (local.get $heap)))
+"#;
+
+/// `COMPARISON_FUNCTIONS` for GC values.
+const GC_COMPARISON_FUNCTIONS: &str = r#"(func $jsrt_string_order (param $left anyref) (param $right anyref) (result i32)
+  (local $left_string (ref null $string))
+  (local $right_string (ref null $string))
+  (local.set $left_string (ref.cast (ref $string) (local.get $left)))
+  (local.set $right_string (ref.cast (ref $string) (local.get $right)))
+  (call $jsrt_compare_bytes
+    (struct.get $string 0 (local.get $left_string))
+    (struct.get $string 1 (local.get $left_string))
+    (struct.get $string 0 (local.get $right_string))
+    (struct.get $string 1 (local.get $right_string))))
+(func $jsrt_is_nullish (param $value anyref) (result i32)
+  (i32.or (ref.is_null (local.get $value)) (ref.test (ref $undefined) (local.get $value))))
+(func $jsrt_equals (param $left anyref) (param $right anyref) (result i32)
+  (if (i32.or (call $jsrt_is_nullish (local.get $left)) (call $jsrt_is_nullish (local.get $right)))
+    (then
+      (return
+        (i32.and (call $jsrt_is_nullish (local.get $left)) (call $jsrt_is_nullish (local.get $right))))))
+  (if (i32.and
+        (ref.test (ref $string) (local.get $left))
+        (ref.test (ref $string) (local.get $right)))
+    (then (return (i32.eqz (call $jsrt_string_order (local.get $left) (local.get $right))))))
+  (f64.eq (call $jsrt_to_number (local.get $left)) (call $jsrt_to_number (local.get $right))))
+(func $jsrt_strict_equals (param $left anyref) (param $right anyref) (result i32)
+  (if (i32.and
+        (ref.test (ref $string) (local.get $left))
+        (ref.test (ref $string) (local.get $right)))
+    (then (return (i32.eqz (call $jsrt_string_order (local.get $left) (local.get $right))))))
+  (if (i32.and
+        (ref.test (ref $number) (local.get $left))
+        (ref.test (ref $number) (local.get $right)))
+    (then
+      (return
+        (f64.eq
+          (struct.get $number 0 (ref.cast (ref $number) (local.get $left)))
+          (struct.get $number 0 (ref.cast (ref $number) (local.get $right)))))))
+  (if (i32.and (ref.test (ref i31) (local.get $left)) (ref.test (ref i31) (local.get $right)))
+    (then
+      (return
+        (i32.eq
+          (i31.get_u (ref.cast (ref i31) (local.get $left)))
+          (i31.get_u (ref.cast (ref i31) (local.get $right)))))))
+  (if (i32.and (ref.is_null (local.get $left)) (ref.is_null (local.get $right)))
+    (then (return (i32.const 1))))
+  (i32.and
+    (ref.test (ref $undefined) (local.get $left))
+    (ref.test (ref $undefined) (local.get $right))))
+(func $jsrt_comparable (param $left anyref) (param $right anyref) (result f64 f64)
+  (if (i32.and
+        (ref.test (ref $string) (local.get $left))
+        (ref.test (ref $string) (local.get $right)))
+    (then
+      (return
+        (f64.convert_i32_s (call $jsrt_string_order (local.get $left) (local.get $right)))
+        (f64.const 0))))
+  (call $jsrt_to_number (local.get $left))
+  (call $jsrt_to_number (local.get $right)))
+"#;
+
+/// `(ptr, len)` of the string a value converts to, which is how strings
+/// are passed to imported host functions.
+const NAN_BOXED_STRING_DATA: &str = r#"(func $jsrt_string_data (param $value i64) (result i32 i32)
+  (local $pair i32)
+  (local.set $pair (i32.wrap_i64 (call $jsrt_to_string (local.get $value))))
+  (i32.load (local.get $pair))
+  (i32.load offset=4 (local.get $pair)))
+"#;
+
+/// `NAN_BOXED_STRING_DATA` for GC values.
+const GC_STRING_DATA: &str = r#"(func $jsrt_string_data (param $value anyref) (result i32 i32)
+  (local $string (ref null $string))
+  (local.set $string (call $jsrt_to_string (local.get $value)))
+  (struct.get $string 0 (local.get $string))
+  (struct.get $string 1 (local.get $string)))
+"#;
+
+/// Wasm types a C type is passed to and from host functions as; strings
+/// are a `(ptr, len)` pair in linear memory.
+fn import_types(ty: CType) -> &'static [&'static str] {
+    match ty {
+        CType::Int => &["i32"],
+        CType::Long => &["i64"],
+        CType::Double => &["f64"],
+        CType::String => &["i32", "i32"],
+        CType::Void => &[],
+    }
+}
+
+/// Under WASI the printing and formatting imports are implemented in the
+/// module itself on top of `fd_write`. Addresses 0..12 are scratch space
+/// for an iovec and the written count.
+const WASI_FUNCTIONS: &str = r#"(func $log_string (param $ptr i32) (param $len i32)
+  (i32.store (i32.const 0) (local.get $ptr))
+  (i32.store (i32.const 4) (local.get $len))
+  (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8))))
+(func $log (param $value i64)
+  (local $heap i32)
+  (local $ptr i32)
+  (local.set $heap (global.get $jsrt_heap))
+  (local.set $ptr (call $jsrt_alloc (i32.const 32)))
+  (call $log_string
+    (local.get $ptr)
+    (call $format_number (f64.reinterpret_i64 (local.get $value)) (local.get $ptr)))
+  (global.set $jsrt_heap (local.get $heap)))
+(func $jsrt_write_digits (param $n i64) (param $width i32) (param $ptr i32) (result i32)
+  (local $count i32)
+  (local $rest i64)
+  (local $end i32)
+  (local.set $rest (local.get $n))
+  (loop $count_digits
+    (local.set $count (i32.add (local.get $count) (i32.const 1)))
+    (local.set $rest (i64.div_u (local.get $rest) (i64.const 10)))
+    (br_if $count_digits (i64.ne (local.get $rest) (i64.const 0))))
+  (if (i32.lt_u (local.get $count) (local.get $width))
+    (then (local.set $count (local.get $width))))
+  (local.set $end (i32.add (local.get $ptr) (local.get $count)))
+  (local.set $rest (local.get $n))
+  (loop $write_digit
+    (local.set $count (i32.sub (local.get $count) (i32.const 1)))
+    (i32.store8
+      (i32.add (local.get $ptr) (local.get $count))
+      (i32.add (i32.const 48) (i32.wrap_i64 (i64.rem_u (local.get $rest) (i64.const 10)))))
+    (local.set $rest (i64.div_u (local.get $rest) (i64.const 10)))
+    (br_if $write_digit (local.get $count)))
+  (local.get $end))
+;; Whether digits that miss `value * 10^k` by `miss` read back as
+;; `value`: they are nearer than half the gap to the neighbouring double on
+;; their side, or halfway with `value` even
+(func $jsrt_reads_back (param $value f64) (param $k i32) (param $miss f64) (result i32)
+  (local $bits i64)
+  (local $gap f64)
+  (local.set $bits (i64.reinterpret_f64 (local.get $value)))
+  (local.set $gap
+    (f64.sub (f64.reinterpret_i64 (i64.add (local.get $bits) (i64.const 1))) (local.get $value)))
+  ;; Above the largest double the gap is the one below it
+  (if (i32.or
+        (f64.gt (local.get $miss) (f64.const 0))
+        (f64.eq (local.get $gap) (f64.const inf)))
+    (then
+      (local.set $gap
+        (f64.sub (local.get $value) (f64.reinterpret_i64 (i64.sub (local.get $bits) (i64.const 1)))))))
+  (call $jsrt_scale (local.get $gap) (local.get $k))
+  (drop)
+  (local.set $gap (f64.mul (f64.const 0.5)))
+  (local.set $miss (f64.abs (local.get $miss)))
+  ;; Both sides carry rounding errors far below 2^-32 of the gap
+  (if (f64.le
+        (f64.abs (f64.sub (local.get $miss) (local.get $gap)))
+        (f64.mul (local.get $gap) (f64.const 0x1p-32)))
+    (then (return (i64.eqz (i64.and (local.get $bits) (i64.const 1))))))
+  (f64.lt (local.get $miss) (local.get $gap)))
+;; Write `value` as JavaScript does: integers in full, other numbers with
+;; the fewest significant digits (up to 17) that read back as `value`, in
+;; fixed notation when the decimal exponent is between -7 and 21
+(func $format_number (param $value f64) (param $ptr i32) (result i32)
+  (local $out i32)
+  (local $start i32)
+  (local $exponent i32)
+  (local $precision i32)
+  (local $shift i32)
+  (local $point i32)
+  (local $scaled f64)
+  (local $error f64)
+  (local $bound f64)
+  (local $fraction f64)
+  (local $rounded f64)
+  (local $miss f64)
+  (local $bits i64)
+  (local $digits i64)
+  (local $other i64)
+  (local.set $out (local.get $ptr))
+  (if (f64.ne (local.get $value) (local.get $value))
+    (then
+      (i32.store (local.get $out) (i32.const 0x4e614e))
+      (return (i32.const 3))))
+  (if (f64.lt (local.get $value) (f64.const 0))
+    (then
+      (i32.store8 (local.get $out) (i32.const 45))
+      (local.set $out (i32.add (local.get $out) (i32.const 1)))
+      (local.set $value (f64.neg (local.get $value)))))
+  (if (f64.eq (local.get $value) (f64.const inf))
+    (then
+      (i64.store (local.get $out) (i64.const 0x7974696e69666e49))
+      (return (i32.sub (i32.add (local.get $out) (i32.const 8)) (local.get $ptr)))))
+  (if (i32.and
+        (f64.le (local.get $value) (f64.const 0x1p53))
+        (f64.eq (local.get $value) (f64.floor (local.get $value))))
+    (then
+      (return
+        (i32.sub
+          (call $jsrt_write_digits
+            (i64.trunc_f64_u (local.get $value))
+            (i32.const 1)
+            (local.get $out))
+          (local.get $ptr)))))
+  ;; Estimate the decimal exponent from the binary one; the digit count
+  ;; below corrects it
+  (local.set $bits (i64.reinterpret_f64 (local.get $value)))
+  (local.set $exponent
+    (i32.trunc_f64_s
+      (f64.floor
+        (f64.mul
+          (f64.convert_i64_s
+            (i64.sub (i64.shr_u (local.get $bits) (i64.const 52)) (i64.const 1023)))
+          (f64.const 0.3010299956639812)))))
+  (local.set $precision (i32.const 1))
+  (block $found
+    (loop $next_precision
+      ;; `precision` digits: value * 10^(precision - 1 - exponent), rounded
+      (local.set $shift
+        (i32.sub (i32.sub (local.get $precision) (i32.const 1)) (local.get $exponent)))
+      (call $jsrt_scale (local.get $value) (local.get $shift))
+      (local.set $error)
+      (local.set $scaled)
+      ;; `scaled + error` is exact enough to place powers of ten, which
+      ;; `scaled` alone may round onto
+      (local.set $bound (call $jsrt_pow10 (local.get $precision)))
+      (if (i32.or
+            (f64.gt (local.get $scaled) (local.get $bound))
+            (i32.and
+              (f64.eq (local.get $scaled) (local.get $bound))
+              (f64.ge (local.get $error) (f64.const 0))))
+        (then
+          (local.set $exponent (i32.add (local.get $exponent) (i32.const 1)))
+          (br $next_precision)))
+      (local.set $bound (call $jsrt_pow10 (i32.sub (local.get $precision) (i32.const 1))))
+      (if (i32.or
+            (f64.lt (local.get $scaled) (local.get $bound))
+            (i32.and
+              (f64.eq (local.get $scaled) (local.get $bound))
+              (f64.lt (local.get $error) (f64.const 0))))
+        (then
+          (local.set $exponent (i32.sub (local.get $exponent) (i32.const 1)))
+          (br $next_precision)))
+      (local.set $fraction (f64.sub (local.get $scaled) (f64.floor (local.get $scaled))))
+      (local.set $rounded (f64.nearest (f64.add (local.get $fraction) (local.get $error))))
+      (local.set $digits
+        (i64.add
+          (i64.trunc_f64_u (f64.floor (local.get $scaled)))
+          (i64.trunc_f64_s (local.get $rounded))))
+      ;; How far the digits fall short of the scaled value; the subtraction
+      ;; is exact, so the error is not lost against the fraction
+      (local.set $miss
+        (f64.add (f64.sub (local.get $fraction) (local.get $rounded)) (local.get $error)))
+      (br_if $found
+        (call $jsrt_reads_back (local.get $value) (local.get $shift) (local.get $miss)))
+      ;; Below a power of two the gap to the next double down is half the
+      ;; one up, so the digits on the other side may read back instead
+      (local.set $other
+        (i64.add
+          (local.get $digits)
+          (i64.trunc_f64_s (f64.copysign (f64.const 1) (local.get $miss)))))
+      (if (i32.and
+            (i64.ge_u (local.get $other) (i64.trunc_f64_u (local.get $bound)))
+            (call $jsrt_reads_back
+              (local.get $value)
+              (local.get $shift)
+              (f64.sub (local.get $miss) (f64.copysign (f64.const 1) (local.get $miss)))))
+        (then
+          (local.set $digits (local.get $other))
+          (br $found)))
+      (br_if $found (i32.ge_u (local.get $precision) (i32.const 17)))
+      (local.set $precision (i32.add (local.get $precision) (i32.const 1)))
+      (br $next_precision)))
+  ;; 9.96 rounds to 10 with one digit, which is 1e1
+  (if (i64.eq
+        (local.get $digits)
+        (i64.trunc_f64_u (call $jsrt_pow10 (local.get $precision))))
+    (then
+      (local.set $digits (i64.div_u (local.get $digits) (i64.const 10)))
+      (local.set $exponent (i32.add (local.get $exponent) (i32.const 1)))))
+  (block $trimmed
+    (loop $trim
+      (br_if $trimmed (i64.ne (i64.rem_u (local.get $digits) (i64.const 10)) (i64.const 0)))
+      (local.set $digits (i64.div_u (local.get $digits) (i64.const 10)))
+      (local.set $precision (i32.sub (local.get $precision) (i32.const 1)))
+      (br $trim)))
+  (local.set $start (local.get $out))
+  (local.set $point (i32.add (local.get $exponent) (i32.const 1)))
+  (if (i32.and
+        (i32.gt_s (local.get $point) (i32.const -6))
+        (i32.le_s (local.get $point) (i32.const 21)))
+    (then
+      ;; 0.000ddd
+      (if (i32.le_s (local.get $point) (i32.const 0))
+        (then
+          (i32.store16 (local.get $out) (i32.const 0x2e30))
+          (local.set $out (i32.add (local.get $out) (i32.const 2)))
+          (memory.fill (local.get $out) (i32.const 48) (i32.sub (i32.const 0) (local.get $point)))
+          (local.set $out (i32.sub (local.get $out) (local.get $point)))
+          (return
+            (i32.sub
+              (call $jsrt_write_digits
+                (local.get $digits)
+                (local.get $precision)
+                (local.get $out))
+              (local.get $ptr)))))
+      (local.set $out
+        (call $jsrt_write_digits (local.get $digits) (local.get $precision) (local.get $out)))
+      ;; ddd000
+      (if (i32.ge_s (local.get $point) (local.get $precision))
+        (then
+          (memory.fill
+            (local.get $out)
+            (i32.const 48)
+            (i32.sub (local.get $point) (local.get $precision)))
+          (return
+            (i32.sub
+              (i32.add (local.get $out) (i32.sub (local.get $point) (local.get $precision)))
+              (local.get $ptr)))))
+      ;; ddd.ddd
+      (memory.copy
+        (i32.add (i32.add (local.get $start) (local.get $point)) (i32.const 1))
+        (i32.add (local.get $start) (local.get $point))
+        (i32.sub (local.get $precision) (local.get $point)))
+      (i32.store8 (i32.add (local.get $start) (local.get $point)) (i32.const 46))
+      (return (i32.sub (i32.add (local.get $out) (i32.const 1)) (local.get $ptr)))))
+  ;; d.ddde+x: write the digits one place on, then move the first before the point
+  (local.set $out
+    (call $jsrt_write_digits
+      (local.get $digits)
+      (local.get $precision)
+      (i32.add (local.get $start) (i32.const 1))))
+  (i32.store8 (local.get $start) (i32.load8_u (i32.add (local.get $start) (i32.const 1))))
+  (if (i32.gt_u (local.get $precision) (i32.const 1))
+    (then (i32.store8 (i32.add (local.get $start) (i32.const 1)) (i32.const 46)))
+    (else (local.set $out (i32.sub (local.get $out) (i32.const 1)))))
+  (i32.store8 (local.get $out) (i32.const 101))
+  (i32.store8
+    (i32.add (local.get $out) (i32.const 1))
+    (select (i32.const 45) (i32.const 43) (i32.lt_s (local.get $exponent) (i32.const 0))))
+  (local.set $out
+    (call $jsrt_write_digits
+      (i64.extend_i32_u
+        (select
+          (i32.sub (i32.const 0) (local.get $exponent))
+          (local.get $exponent)
+          (i32.lt_s (local.get $exponent) (i32.const 0))))
+      (i32.const 1)
+      (i32.add (local.get $out) (i32.const 2))))
+  (i32.sub (local.get $out) (local.get $ptr)))
+"#;
 
 pub struct WasmGenerator {
+    options: CodegenOptions,
     output: String,
+    line_table: LineTable,
     locals: HashMap<String, u32>,
     local_count: u32,
-    string_data: Vec<String>,
-    float_data: Vec<f64>,
+    /// Initial contents of linear memory, starting at address 0.
+    data: Vec<u8>,
+    /// Address of the bytes and of the `(ptr, len)` pair of each string constant.
+    strings: HashMap<String, (u32, u32)>,
+    /// The module's constants, which `PushConst` indexes.
+    constants: Vec<Constant>,
+    /// Host functions declared with `extern function`, imported from `env`.
+    imports: HashMap<String, ExternFunction>,
+    /// Names of the module's functions, which `Call` indexes.
+    functions: Vec<String>,
+    /// Functions to export, by their JS names.
+    exports: Vec<String>,
+    has_main: bool,
+}
+
+impl Default for WasmGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WasmGenerator {
     pub fn new() -> Self {
+        Self::with_options(CodegenOptions::default())
+    }
+
+    pub fn with_options(options: CodegenOptions) -> Self {
         Self {
+            options,
             output: String::new(),
+            line_table: LineTable::default(),
             locals: HashMap::new(),
             local_count: 0,
-            string_data: Vec::new(),
-            float_data: Vec::new(),
+            // Scratch space for WASI output; no string lives at address 0
+            data: vec![0; 24],
+            strings: HashMap::new(),
+            constants: Vec::new(),
+            imports: HashMap::new(),
+            functions: Vec::new(),
+            exports: Vec::new(),
+            has_main: false,
+        }
+    }
+
+    /// Wasm type of a JavaScript value.
+    fn value_type(&self) -> &'static str {
+        match self.options.wasm_values {
+            WasmValues::NanBoxed => "i64",
+            WasmValues::GcReferences => "anyref",
         }
     }
 
+    /// Replace the value on top of the stack with its number as an f64.
+    fn generate_to_number(&mut self) {
+        self.output.push_str("call $jsrt_to_number\n");
+    }
+
+    /// Turn the f64 on top of the stack into a value.
+    fn generate_box_number(&mut self) {
+        self.output.push_str(match self.options.wasm_values {
+            WasmValues::NanBoxed => "i64.reinterpret_f64\n",
+            WasmValues::GcReferences => "struct.new $number\n",
+        });
+    }
+
+    fn generate_undefined(&mut self) {
+        self.output.push_str("global.get $jsrt_undefined\n");
+    }
+
     fn reset_state(&mut self) {
         self.locals.clear();
         self.local_count = 0;
@@ -39,63 +1153,202 @@ impl WasmGenerator {
         // Function header
         self.output.push_str(&format!("(func ${} ", function.name));
 
-        // Parameters
-        for _ in &function.params {
-            self.output.push_str("(param i64) ");
+        // Parameters take the first local indices
+        for param in &function.params {
+            self.allocate_local(param);
+            self.output
+                .push_str(&format!("(param {}) ", self.value_type()));
+        }
+        self.output
+            .push_str(&format!("(result {})\n", self.value_type()));
+
+        // Local variables, plus scratch locals for `Dup` and the arguments
+        // of `print` and host functions
+        let mut call_args = 0;
+        for instruction in function.block_instructions() {
+            match instruction {
+                IRInstruction::Load(name) | IRInstruction::Store(name)
+                    if !self.locals.contains_key(name) =>
+                {
+                    self.allocate_local(name);
+                    self.output
+                        .push_str(&format!("(local {})\n", self.value_type()));
+                }
+                IRInstruction::CallNative(name, argc)
+                    if name == "print" || self.imports.contains_key(name) =>
+                {
+                    call_args = call_args.max(*argc);
+                }
+                _ => {}
+            }
+        }
+        let value_type = self.value_type();
+        self.output
+            .push_str(&format!("(local $tmp {})\n", value_type));
+        self.output.push_str("(local $cond i32)\n");
+        for i in 0..call_args {
+            self.output
+                .push_str(&format!("(local $arg{} {})\n", i, value_type));
         }
-        self.output.push_str("(result i64)\n");
 
-        // Local variables
-        if function.max_locals > 0 {
+        // Values live across block boundaries are passed in `$sN` locals
+        let structure = Structure::new(function);
+        let max_depth = structure.entry_depths.iter().copied().max().unwrap_or(0);
+        for i in 0..max_depth {
             self.output
-                .push_str(&format!("(local ${} i64)\n", self.local_count));
+                .push_str(&format!("(local $s{} {})\n", i, value_type));
         }
 
-        // Generate instructions
-        for instruction in &function.instructions {
-            self.generate_instruction(instruction);
+        for node in structure.nodes() {
+            self.generate_node(&structure, &node);
         }
 
+        // Every path returns explicitly
+        self.output.push_str("unreachable\n");
         self.output.push_str(")\n");
+        if self.options.source_map {
+            self.line_table.mark(&self.output, None);
+        }
+    }
+
+    fn generate_node(&mut self, structure: &Structure, node: &Node) {
+        match node {
+            Node::Code(b) => self.generate_block(structure, *b),
+            Node::Loop(body) => {
+                self.output.push_str("loop\n");
+                for node in body {
+                    self.generate_node(structure, node);
+                }
+                self.output.push_str("end\n");
+            }
+            Node::Block(body) => {
+                self.output.push_str("block\n");
+                for node in body {
+                    self.generate_node(structure, node);
+                }
+                self.output.push_str("end\n");
+            }
+            Node::If { then, otherwise } => {
+                self.output.push_str("local.get $cond\n");
+                self.output.push_str("if\n");
+                for node in then {
+                    self.generate_node(structure, node);
+                }
+                self.output.push_str("else\n");
+                for node in otherwise {
+                    self.generate_node(structure, node);
+                }
+                self.output.push_str("end\n");
+            }
+            Node::Branch(depth) => self.output.push_str(&format!("br {}\n", depth)),
+        }
+    }
+
+    /// Emit the instructions of block `b`, leaving the operands it passes
+    /// on in the `$sN` locals and a `JumpIf` condition in `$cond`.
+    fn generate_block(&mut self, structure: &Structure, b: usize) {
+        let function = structure.function;
+        let block = &function.blocks[b];
+        for i in 0..structure.entry_depths[b] {
+            self.output.push_str(&format!("local.get $s{}\n", i));
+        }
+
+        if let Some(label) = &block.label {
+            self.generate_annotation(&IRInstruction::Label(label.clone()));
+        }
+        let mut depth = structure.entry_depths[b];
+        for instruction in &block.instructions {
+            self.generate_annotation(instruction);
+            self.generate_instruction(instruction);
+            let (pops, pushes) = instruction.stack_effect();
+            depth = depth - pops + pushes;
+        }
+        if let Some(terminator) = function.terminator_instruction(b) {
+            self.generate_annotation(&terminator);
+        }
+
+        match block.terminator {
+            Terminator::Return(has_value) => {
+                self.generate_instruction(&IRInstruction::Return(has_value))
+            }
+            Terminator::JumpIf(_) => {
+                self.generate_truthy();
+                self.output.push_str("local.set $cond\n");
+                self.generate_spill(depth - 1);
+            }
+            _ if structure.cfg.blocks[b].successors.is_empty() => {
+                // Falling off the end of the function returns undefined
+                self.generate_undefined();
+                self.output.push_str("return\n");
+            }
+            _ => self.generate_spill(depth),
+        }
+    }
+
+    fn generate_annotation(&mut self, instruction: &IRInstruction) {
+        if let Some(comment) =
+            self.options
+                .annotation(instruction, &self.constants, &self.functions, ";;", "")
+        {
+            self.output.push_str(&comment);
+            self.output.push('\n');
+        }
+    }
+
+    /// Move the operand stack into the `$sN` locals before leaving a block.
+    fn generate_spill(&mut self, depth: usize) {
+        for i in (0..depth).rev() {
+            self.output.push_str(&format!("local.set $s{}\n", i));
+        }
     }
 
     fn generate_instruction(&mut self, instruction: &IRInstruction) {
         match instruction {
-            IRInstruction::PushConst(constant) => self.generate_const(constant),
+            IRInstruction::PushConst(index) => {
+                let constant = self.constants[*index as usize].clone();
+                self.generate_const(&constant)
+            }
             IRInstruction::Load(name) => {
-                let local_idx = self.locals.get(name).cloned().unwrap_or_else(|| {
-                    let idx = self.allocate_local(name);
-                    idx
-                });
+                let local_idx = self.locals[name];
                 self.output.push_str(&format!("local.get {}\n", local_idx));
             }
             IRInstruction::Store(name) => {
-                let local_idx = self.locals.get(name).cloned().unwrap_or_else(|| {
-                    let idx = self.allocate_local(name);
-                    idx
-                });
+                let local_idx = self.locals[name];
                 self.output.push_str(&format!("local.set {}\n", local_idx));
             }
+            IRInstruction::LoadUpvalue(_)
+            | IRInstruction::StoreUpvalue(_)
+            | IRInstruction::MakeClosure(..) => closures_unsupported(Target::Wasm),
+            IRInstruction::GetProperty(_) => objects_unsupported(Target::Wasm),
             IRInstruction::Binary(op) => self.generate_binary_op(op),
             IRInstruction::Unary(op) => self.generate_unary_op(op),
-            IRInstruction::Call(name, argc) => {
+            IRInstruction::CallNative(name, argc) if name == "print" => self.generate_print(*argc),
+            IRInstruction::CallNative(name, argc) if self.imports.contains_key(name) => {
+                let function = self.imports[name].clone();
+                self.generate_import_call(&function, *argc);
+            }
+            IRInstruction::Call(id, argc) => {
+                let name = &self.functions[*id as usize];
+                self.output
+                    .push_str(&format!("call ${} ;; args: {}\n", name, argc));
+            }
+            IRInstruction::CallNative(name, argc) => {
                 self.output
                     .push_str(&format!("call ${} ;; args: {}\n", name, argc));
             }
             IRInstruction::Return(has_value) => {
-                if (!has_value) {
-                    self.output.push_str("i64.const 0\n");
+                if !has_value {
+                    self.generate_undefined();
                 }
                 self.output.push_str("return\n");
             }
-            IRInstruction::Jump(label) => {
-                self.output.push_str(&format!("br {}\n", label));
-            }
-            IRInstruction::JumpIf(label) => {
-                self.output.push_str(&format!("br_if {}\n", label));
-            }
-            IRInstruction::Label(label) => {
-                self.output.push_str(&format!("(block ${}\n", label));
+            // Control flow is emitted by the structurizer
+            IRInstruction::Jump(_) | IRInstruction::JumpIf(_) | IRInstruction::Label(_) => {}
+            IRInstruction::Line(line, _) => {
+                if self.options.source_map {
+                    self.line_table.mark(&self.output, Some(*line));
+                    self.output.push_str("nop\n");
+                }
             }
             IRInstruction::Pop => {
                 self.output.push_str("drop\n");
@@ -107,98 +1360,411 @@ impl WasmGenerator {
         }
     }
 
+    /// Log each `print` argument through the host, first argument first,
+    /// separated by spaces and followed by a newline.
+    fn generate_print(&mut self, argc: u16) {
+        for i in (0..argc).rev() {
+            self.output.push_str(&format!("local.set $arg{}\n", i));
+        }
+        for i in 0..argc {
+            if i > 0 {
+                self.generate_log_string(" ");
+            }
+            self.output.push_str(&format!("local.get $arg{}\n", i));
+            self.output.push_str("call $jsrt_print_value\n");
+        }
+        self.generate_log_string("\n");
+        // print returns undefined
+        self.generate_undefined();
+    }
+
+    /// Write the string constant `s` through the host.
+    fn generate_log_string(&mut self, s: &str) {
+        let (ptr, _) = self.intern_string(s);
+        self.output.push_str(&format!(
+            "i32.const {}\ni32.const {}\ncall $log_string\n",
+            ptr,
+            s.len()
+        ));
+    }
+
+    /// Call the host function `function`, converting the arguments to its
+    /// parameter types and its result back to a value.
+    fn generate_import_call(&mut self, function: &ExternFunction, argc: u16) {
+        if argc as usize != function.params.len() {
+            panic!(
+                "{} takes {} arguments, got {}",
+                function.name,
+                function.params.len(),
+                argc
+            );
+        }
+        for i in (0..argc).rev() {
+            self.output.push_str(&format!("local.set $arg{}\n", i));
+        }
+        for (i, &ty) in function.params.iter().enumerate() {
+            self.output.push_str(&format!("local.get $arg{}\n", i));
+            match ty {
+                CType::Int => {
+                    self.generate_to_number();
+                    self.output.push_str("i32.trunc_sat_f64_s\n");
+                }
+                CType::Long => {
+                    self.generate_to_number();
+                    self.output.push_str("i64.trunc_sat_f64_s\n");
+                }
+                CType::Double => self.generate_to_number(),
+                CType::String => self.output.push_str("call $jsrt_string_data\n"),
+                CType::Void => panic!("{} cannot take a void parameter", function.name),
+            }
+        }
+        self.output.push_str(&format!("call ${}\n", function.name));
+        match function.returns {
+            CType::Int => {
+                self.output.push_str("f64.convert_i32_s\n");
+                self.generate_box_number();
+            }
+            CType::Long => {
+                self.output.push_str("f64.convert_i64_s\n");
+                self.generate_box_number();
+            }
+            CType::Double => self.generate_box_number(),
+            CType::String => self.output.push_str(match self.options.wasm_values {
+                WasmValues::NanBoxed => "call $jsrt_make_string\n",
+                WasmValues::GcReferences => "struct.new $string\n",
+            }),
+            CType::Void => self.generate_undefined(),
+        }
+    }
+
     fn generate_const(&mut self, constant: &Constant) {
         match constant {
             Constant::Number(n) => {
                 self.output.push_str(&format!("f64.const {}\n", n));
-                self.output.push_str("i64.reinterpret_f64\n");
+                self.generate_box_number();
             }
             Constant::String(s) => {
-                let index = self.string_data.len();
-                self.string_data.push(s.clone());
-                self.output.push_str(&format!("i64.const {}\n", index));
+                let (ptr, pair) = self.intern_string(s);
+                let code = match self.options.wasm_values {
+                    WasmValues::NanBoxed => format!("i64.const {}\n", STRING_TAG | pair as u64),
+                    WasmValues::GcReferences => format!(
+                        "i32.const {}\ni32.const {}\nstruct.new $string\n",
+                        ptr,
+                        s.len()
+                    ),
+                };
+                self.output.push_str(&code);
             }
             Constant::Boolean(b) => {
-                self.output
-                    .push_str(&format!("i64.const {}\n", if *b { 1 } else { 0 }));
+                self.output.push_str(&format!("i32.const {}\n", *b as i32));
+                self.generate_bool_from_i32();
             }
-            Constant::Null => {
-                self.output.push_str("i64.const 0\n");
+            Constant::Null => match self.options.wasm_values {
+                WasmValues::NanBoxed => self.output.push_str("global.get $jsrt_null\n"),
+                WasmValues::GcReferences => self.output.push_str("ref.null none\n"),
+            },
+        }
+    }
+
+    /// Replace the value on top of the stack with an i32 that is 1 when it
+    /// is truthy: a non-empty string, or a number other than +-0.0 and NaN.
+    fn generate_truthy(&mut self) {
+        self.output.push_str("call $jsrt_truthy\n");
+    }
+
+    /// Lay out a string constant in the data segment, returning the address
+    /// of its bytes and of its `(ptr, len)` pair. Equal constants share one copy.
+    fn intern_string(&mut self, s: &str) -> (u32, u32) {
+        if let Some(&addresses) = self.strings.get(s) {
+            return addresses;
+        }
+        let ptr = self.data.len() as u32;
+        self.data.extend_from_slice(s.as_bytes());
+        while !self.data.len().is_multiple_of(8) {
+            self.data.push(0);
+        }
+        let pair = self.data.len() as u32;
+        self.data.extend_from_slice(&ptr.to_le_bytes());
+        self.data.extend_from_slice(&(s.len() as u32).to_le_bytes());
+        self.strings.insert(s.to_string(), (ptr, pair));
+        (ptr, pair)
+    }
+
+    /// Convert an i32 flag on top of the stack to a boolean: the flag
+    /// under `BOOLEAN_TAG` when NaN-boxed, an `i31ref` under GC.
+    fn generate_bool_from_i32(&mut self) {
+        match self.options.wasm_values {
+            WasmValues::NanBoxed => {
+                self.output.push_str("i64.extend_i32_u\n");
+                self.output
+                    .push_str(&format!("i64.const {}\ni64.or\n", BOOLEAN_TAG));
             }
+            WasmValues::GcReferences => self.output.push_str("ref.i31\n"),
         }
     }
 
     fn generate_binary_op(&mut self, op: &BinaryOp) {
+        if let BinaryOp::And | BinaryOp::Or = op {
+            self.generate_truthy();
+            self.output.push_str("local.set $cond\n");
+            self.generate_truthy();
+            self.output.push_str("local.get $cond\n");
+            let cmd = if matches!(op, BinaryOp::And) {
+                "i32.and"
+            } else {
+                "i32.or"
+            };
+            self.output.push_str(&format!("{}\n", cmd));
+            self.generate_bool_from_i32();
+            return;
+        }
+        // `+` concatenates when either side is a string
+        if let BinaryOp::Add = op {
+            self.output.push_str("call $jsrt_add\n");
+            return;
+        }
+
+        // Strings compare by content; only `==` and `!=` convert
+        if let BinaryOp::Eq | BinaryOp::StrictEq | BinaryOp::Neq = op {
+            self.output.push_str(if let BinaryOp::StrictEq = op {
+                "call $jsrt_strict_equals\n"
+            } else {
+                "call $jsrt_equals\n"
+            });
+            if let BinaryOp::Neq = op {
+                self.output.push_str("i32.eqz\n");
+            }
+            self.generate_bool_from_i32();
+            return;
+        }
+        // Two strings compare by their order, anything else as numbers
+        if let BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge = op {
+            self.output.push_str("call $jsrt_comparable\n");
+        } else {
+            self.output.push_str("local.set $tmp\n");
+            self.generate_to_number();
+            self.output.push_str("local.get $tmp\n");
+            self.generate_to_number();
+        }
+        let cmd = match op {
+            BinaryOp::Sub => "f64.sub",
+            BinaryOp::Mul => "f64.mul",
+            BinaryOp::Div => "f64.div",
+            BinaryOp::Mod => "call $jsrt_fmod",
+            BinaryOp::Lt => "f64.lt",
+            BinaryOp::Gt => "f64.gt",
+            BinaryOp::Le => "f64.le",
+            BinaryOp::Ge => "f64.ge",
+            BinaryOp::Add
+            | BinaryOp::And
+            | BinaryOp::Or
+            | BinaryOp::Eq
+            | BinaryOp::StrictEq
+            | BinaryOp::Neq => unreachable!(),
+        };
+        self.output.push_str(&format!("{}\n", cmd));
         match op {
-            BinaryOp::Add => self.output.push_str("i64.add\n"),
-            BinaryOp::Sub => self.output.push_str("i64.sub\n"),
-            BinaryOp::Mul => self.output.push_str("i64.mul\n"),
-            BinaryOp::Div => {
-                self.output.push_str("f64.reinterpret_i64\n");
-                self.output.push_str("f64.div\n");
-                self.output.push_str("i64.reinterpret_f64\n");
+            BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
+                self.generate_box_number()
             }
-            BinaryOp::Eq => self.output.push_str("i64.eq\n"),
-            BinaryOp::Lt => self.output.push_str("i64.lt_s\n"),
-            BinaryOp::Gt => self.output.push_str("i64.gt_s\n"),
-            BinaryOp::Le => self.output.push_str("i64.le_s\n"),
-            BinaryOp::Ge => self.output.push_str("i64.ge_s\n"),
-            BinaryOp::And => self.output.push_str("i64.and\n"),
-            BinaryOp::Or => self.output.push_str("i64.or\n"),
+            // Comparisons produce an i32 flag
+            _ => self.generate_bool_from_i32(),
         }
     }
 
     fn generate_unary_op(&mut self, op: &UnaryOp) {
         match op {
             UnaryOp::Neg => {
-                self.output.push_str("i64.const -1\n");
-                self.output.push_str("i64.mul\n");
+                self.generate_to_number();
+                self.output.push_str("f64.neg\n");
+                self.generate_box_number();
             }
             UnaryOp::Not => {
-                self.output.push_str("i64.eqz\n");
-                self.output.push_str("i64.extend_i32_u\n");
+                self.generate_truthy();
+                self.output.push_str("i32.eqz\n");
+                self.generate_bool_from_i32();
             }
         }
     }
 }
 
+/// Encode WebAssembly text into a validated binary `.wasm` module.
+pub fn encode_binary(wat: &str) -> Result<Vec<u8>, String> {
+    let binary = wat::parse_str(wat).map_err(|e| e.to_string())?;
+    wasmparser::validate(&binary).map_err(|e| e.to_string())?;
+    Ok(binary)
+}
+
 impl CodeGenerator for WasmGenerator {
-    fn generate(&mut self, module: IRModule) -> String {
+    fn begin_module(&mut self, module: &IRModule, out: &mut dyn io::Write) -> io::Result<()> {
+        self.constants = module.constants.clone();
+        self.functions = module.function_names();
+
         // Module header
         self.output.push_str("(module\n");
 
-        // Memory section for string data
-        self.output.push_str("(memory 1)\n");
-
-        // Import JavaScript console.log
-        self.output
-            .push_str("(import \"console\" \"log\" (func $log (param i64)))\n");
+        if self.options.wasm_values == WasmValues::GcReferences {
+            self.output
+                .push_str("(type $number (struct (field f64)))\n");
+            self.output
+                .push_str("(type $string (struct (field i32) (field i32)))\n");
+            self.output.push_str("(type $undefined (struct))\n");
+        }
 
-        // Generate data sections for strings
-        for (i, string) in self.string_data.iter().enumerate() {
+        // Host imports; imports must precede definitions
+        match self.options.wasm_host {
+            WasmHost::JavaScript => {
+                self.output
+                    .push_str("(import \"console\" \"log\" (func $log (param i64)))\n");
+                self.output.push_str(
+                    "(import \"console\" \"log_string\" (func $log_string (param i32 i32)))\n",
+                );
+                self.output.push_str(
+                    "(import \"console\" \"format_number\" (func $format_number (param f64 i32) (result i32)))\n",
+                );
+            }
+            WasmHost::Wasi => {
+                self.output.push_str(
+                    "(import \"wasi_snapshot_preview1\" \"fd_write\" (func $fd_write (param i32 i32 i32 i32) (result i32)))\n",
+                );
+            }
+        }
+        // Functions declared with `extern function` come from the embedder
+        for function in &module.externs {
+            if function.variadic {
+                panic!("Wasm cannot import the variadic function {}", function.name);
+            }
+            let params: Vec<&str> = function
+                .params
+                .iter()
+                .flat_map(|&ty| import_types(ty))
+                .copied()
+                .collect();
+            let mut signature = String::new();
+            if !params.is_empty() {
+                signature.push_str(&format!(" (param {})", params.join(" ")));
+            }
+            if function.returns != CType::Void {
+                signature.push_str(&format!(
+                    " (result {})",
+                    import_types(function.returns).join(" ")
+                ));
+            }
             self.output.push_str(&format!(
-                "(data (i32.const {}) \"{}\")\n",
-                i * 8,
-                string.escape_default()
+                "(import \"env\" \"{}\" (func ${}{}))\n",
+                function.name, function.name, signature
             ));
+            self.imports.insert(function.name.clone(), function.clone());
         }
 
-        // Check for main function
-        let has_main = module.functions.iter().any(|f| f.name == "main");
+        // Linear memory holds string data; the host reads it through the export
+        self.output.push_str("(memory 1)\n");
+        self.output.push_str("(export \"memory\" (memory 0))\n");
+        self.output.push_str(MEMORY_FUNCTIONS);
+        self.output.push_str(NUMBER_FUNCTIONS);
+        self.output.push_str(COMPARE_BYTES);
+        self.output.push_str(DECIMAL_FUNCTIONS);
+        match self.options.wasm_values {
+            WasmValues::NanBoxed => {
+                self.output.push_str(&format!(
+                    "(global $jsrt_null i64 (i64.const {}))\n(global $jsrt_undefined i64 (i64.const {}))\n",
+                    NULL_BITS, UNDEFINED_BITS
+                ));
+                // Strings that booleans, null and undefined convert to
+                for name in ["true", "false", "null", "undefined"] {
+                    let (_, pair) = self.intern_string(name);
+                    self.output.push_str(&format!(
+                        "(global $jsrt_{}_string i64 (i64.const {}))\n",
+                        name,
+                        STRING_TAG | pair as u64
+                    ));
+                }
+                self.output.push_str(RUNTIME_FUNCTIONS);
+                self.output.push_str(COMPARISON_FUNCTIONS);
+            }
+            WasmValues::GcReferences => {
+                self.output.push_str(
+                    "(global $jsrt_undefined (ref $undefined) (struct.new $undefined))\n",
+                );
+                // Strings that booleans, null and undefined convert to
+                for name in ["true", "false", "null", "undefined"] {
+                    let (ptr, _) = self.intern_string(name);
+                    self.output.push_str(&format!(
+                        "(global $jsrt_{}_string (ref $string) (struct.new $string (i32.const {}) (i32.const {})))\n",
+                        name,
+                        ptr,
+                        name.len()
+                    ));
+                }
+                self.output.push_str(GC_RUNTIME_FUNCTIONS);
+                self.output.push_str(GC_COMPARISON_FUNCTIONS);
+            }
+        }
+        if self.options.wasm_host == WasmHost::Wasi {
+            self.output.push_str(WASI_FUNCTIONS);
+        }
+        let string_params = module
+            .externs
+            .iter()
+            .any(|function| function.params.contains(&CType::String));
+        if string_params {
+            self.output.push_str(match self.options.wasm_values {
+                WasmValues::NanBoxed => NAN_BOXED_STRING_DATA,
+                WasmValues::GcReferences => GC_STRING_DATA,
+            });
+        }
+
+        self.flush(out)
+    }
 
-        // Generate functions
-        for function in module.functions {
-            self.generate_function(&function);
+    fn emit_function(&mut self, function: &IRFunction, out: &mut dyn io::Write) -> io::Result<()> {
+        self.generate_function(function);
+        if function.export || function.name == "main" {
+            self.exports.push(function.name.clone());
         }
+        self.has_main |= function.name == "main";
+        self.flush(out)
+    }
 
-        // Export main function if it exists
-        if has_main {
-            self.output.push_str("(export \"main\" (func $main))\n");
+    /// Emit the string data and exports, which Wasm allows after the
+    /// functions.
+    fn finish(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        // String constants, followed by the heap
+        let data: String = self.data.iter().map(|b| format!("\\{:02x}", b)).collect();
+        self.output
+            .push_str(&format!("(data (i32.const 0) \"{}\")\n", data));
+        let heap_start = self.data.len().next_multiple_of(8);
+        self.output.push_str(&format!(
+            "(global $jsrt_heap (mut i32) (i32.const {}))\n",
+            heap_start
+        ));
+
+        // Export main and the functions marked for export by their JS names
+        for name in &self.exports {
+            self.output
+                .push_str(&format!("(export \"{}\" (func ${}))\n", name, name));
+        }
+        // WASI runtimes start the program through `_start`
+        if self.has_main && self.options.wasm_host == WasmHost::Wasi {
+            self.output
+                .push_str("(func $_start (export \"_start\") (drop (call $main)))\n");
         }
 
         // Close module
         self.output.push_str(")\n");
+        self.flush(out)
+    }
 
-        self.output.clone()
+    fn line_table(&self) -> Option<&LineTable> {
+        self.options.source_map.then_some(&self.line_table)
+    }
+}
+
+impl WasmGenerator {
+    /// Write the module text generated so far to `out`.
+    fn flush(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        let text = std::mem::take(&mut self.output);
+        self.line_table.flushed(&text);
+        out.write_all(text.as_bytes())
     }
 }
@@ -1,116 +1,371 @@
-use super::CodeGenerator;
-use crate::ir::{BinaryOp, Constant, IRFunction, IRInstruction, IRModule, UnaryOp};
+use super::source_map::LineTable;
+use super::{
+    assembler, closures_unsupported, globals_unsupported, objects_unsupported, runtime, Arch,
+    CallingConvention, CodeGenerator, CodegenOptions, Target, TargetOs, X64Syntax,
+};
+use crate::ir::{
+    BinaryOp, CType, Constant, ExternFunction, FunctionTypes, IRFunction, IRInstruction, IRModule,
+    UnaryOp,
+};
+use crate::parallel;
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::io;
 
 pub struct X64Generator {
+    options: CodegenOptions,
     output: String,
+    line_table: LineTable,
+    current_function: String,
     string_literals: Vec<String>,
     float_literals: Vec<f64>,
     local_offsets: HashMap<String, i32>,
     current_stack_size: i32,
-    label_counter: usize,
+    next_local_label: usize,
+    externs: Vec<ExternFunction>,
+    /// Names of the module's functions, which `Call` indexes.
+    functions: Vec<String>,
+    /// The module's constants, which `PushConst` indexes.
+    constants: Vec<Constant>,
+    /// Types inferred for each function of the module not generated yet.
+    types: HashMap<String, FunctionTypes>,
+    /// Whether type inference showed the current instruction's operands
+    /// are all numbers, so it needs no dispatch on their types.
+    operands_are_numbers: bool,
+    /// Source line of the instruction being generated, for errors.
+    line: Option<usize>,
+}
+
+impl Default for X64Generator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl X64Generator {
     pub fn new() -> Self {
+        Self::with_options(CodegenOptions::default())
+    }
+
+    pub fn with_options(mut options: CodegenOptions) -> Self {
+        options.target = options.target.with_arch(Arch::X86_64);
         Self {
+            options,
             output: String::new(),
+            line_table: LineTable::default(),
+            current_function: String::new(),
             string_literals: Vec::new(),
             float_literals: Vec::new(),
             local_offsets: HashMap::new(),
             current_stack_size: 0,
-            label_counter: 0,
+            next_local_label: 0,
+            externs: Vec::new(),
+            functions: Vec::new(),
+            constants: Vec::new(),
+            types: HashMap::new(),
+            operands_are_numbers: false,
+            line: None,
         }
     }
 
     fn reset_state(&mut self) {
         self.local_offsets.clear();
         self.current_stack_size = 0;
+        self.line = None;
     }
 
-    fn next_label(&mut self) -> String {
-        self.label_counter += 1;
-        format!(".L{}", self.label_counter)
+    /// Global symbol for a JS function.
+    fn symbol(&self, name: &str) -> String {
+        format!("{}{}", self.options.target.symbol_prefix(), name)
+    }
+
+    /// Symbol to call a C function from a shared library through: the PLT
+    /// on ELF, which also works when it ends up in the executable.
+    fn extern_symbol(&self, name: &str) -> String {
+        match self.options.target.os {
+            TargetOs::Linux => format!("{}@PLT", name),
+            TargetOs::Windows | TargetOs::MacOs => self.symbol(name),
+        }
+    }
+
+    /// Assembler-local label name.
+    fn local(&self, name: &str) -> String {
+        format!("{}{}", self.options.target.local_prefix(), name)
+    }
+
+    /// Whether C calls follow the Microsoft x64 convention rather than
+    /// System V.
+    fn win64(&self) -> bool {
+        self.options.target.calling_convention() == CallingConvention::Win64
+    }
+
+    /// Integer argument registers.
+    fn argument_registers(&self) -> &'static [&'static str] {
+        if self.win64() {
+            &["%rcx", "%rdx", "%r8", "%r9"]
+        } else {
+            &["%rdi", "%rsi", "%rdx", "%rcx", "%r8", "%r9"]
+        }
+    }
+
+    /// Floating-point argument registers. JS values are doubles, so they are
+    /// passed like C `double`s; Win64 assigns the first four by position.
+    fn float_argument_registers(&self) -> &'static [&'static str] {
+        if self.win64() {
+            &["%xmm0", "%xmm1", "%xmm2", "%xmm3"]
+        } else {
+            &[
+                "%xmm0", "%xmm1", "%xmm2", "%xmm3", "%xmm4", "%xmm5", "%xmm6", "%xmm7",
+            ]
+        }
+    }
+
+    /// Stack space the Microsoft ABI reserves for the callee above the
+    /// return address, where it may spill its register arguments.
+    fn shadow_space(&self) -> i32 {
+        if self.win64() {
+            32
+        } else {
+            0
+        }
+    }
+
+    /// Callee-saved registers we clobber; Win64 also preserves %rsi and %rdi.
+    fn callee_saved_registers(&self) -> &'static [&'static str] {
+        if self.win64() {
+            &["%rbx", "%rsi", "%rdi", "%r12", "%r13", "%r14", "%r15"]
+        } else {
+            &["%rbx", "%r12", "%r13", "%r14", "%r15"]
+        }
+    }
+
+    /// IR labels restart in every function, so qualify them with its name.
+    fn label(&self, label: &str) -> String {
+        self.local(&format!("L{}_{}", self.current_function, label))
+    }
+
+    /// Declare the global function `name` and start it.
+    fn generate_function_header(&mut self, name: &str) {
+        let symbol = self.symbol(name);
+        writeln!(self.output, "\t.globl {}", symbol).unwrap();
+        match self.options.target.os {
+            TargetOs::Linux => writeln!(self.output, "\t.type {}, @function", symbol).unwrap(),
+            // External storage class, function type
+            TargetOs::Windows => writeln!(
+                self.output,
+                "\t.def {};\t.scl 2;\t.type 32;\t.endef",
+                symbol
+            )
+            .unwrap(),
+            TargetOs::MacOs => {}
+        }
+        writeln!(self.output, "{}:", symbol).unwrap();
     }
 
     fn generate_function(&mut self, function: &IRFunction) {
         self.reset_state();
-
-        // Function header
-        writeln!(self.output, "\t.globl {}", function.name).unwrap();
-        writeln!(self.output, "\t.type {}, @function", function.name).unwrap();
-        writeln!(self.output, "{}:", function.name).unwrap();
+        self.current_function = function.name.clone();
+
+        let symbol = self.options.function_name(&function.name);
+        self.generate_function_header(&symbol);
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_startproc").unwrap();
+            if let Some(IRInstruction::Line(line, _)) = function.instructions().first() {
+                writeln!(self.output, "\t.loc 1 {}", line).unwrap();
+            }
+        }
 
         // Function prologue
         writeln!(self.output, "\tpush %rbp").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_def_cfa_offset 16").unwrap();
+            writeln!(self.output, "\t.cfi_offset %rbp, -16").unwrap();
+        }
         writeln!(self.output, "\tmov %rsp, %rbp").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_def_cfa_register %rbp").unwrap();
+        }
 
         // Allocate stack frame
-        let frame_size = ((function.max_locals * 8 + 15) / 16) * 16;
+        let frame_size = (function.max_locals * 8).div_ceil(16) * 16;
         if frame_size > 0 {
             writeln!(self.output, "\tsub ${}, %rsp", frame_size).unwrap();
         }
 
         // Save callee-saved registers
-        writeln!(self.output, "\tpush %rbx").unwrap();
-        writeln!(self.output, "\tpush %r12").unwrap();
-        writeln!(self.output, "\tpush %r13").unwrap();
-        writeln!(self.output, "\tpush %r14").unwrap();
-        writeln!(self.output, "\tpush %r15").unwrap();
+        for reg in self.callee_saved_registers() {
+            writeln!(self.output, "\tpush {}", reg).unwrap();
+        }
 
         // Move parameters to their slots
+        let registers = self.float_argument_registers();
         for (i, param) in function.params.iter().enumerate() {
-            let param_reg = match i {
-                0 => "%rdi",
-                1 => "%rsi",
-                2 => "%rdx",
-                3 => "%rcx",
-                4 => "%r8",
-                5 => "%r9",
-                _ => panic!("Too many parameters"),
-            };
-            let offset = self.allocate_local(&param);
-            writeln!(self.output, "\tmov {}, {}(%rbp)", param_reg, offset).unwrap();
+            match registers.get(i) {
+                Some(param_reg) => {
+                    let offset = self.allocate_local(param);
+                    writeln!(self.output, "\tmovq {}, %rax", param_reg).unwrap();
+                    writeln!(self.output, "\tmov %rax, {}(%rbp)", offset).unwrap();
+                }
+                // The rest were passed on the stack above the return address
+                // (and shadow space), where they stay
+                None => {
+                    let offset = 16 + self.shadow_space() + (i - registers.len()) as i32 * 8;
+                    self.local_offsets.insert(param.clone(), offset);
+                }
+            }
         }
 
         // Generate code for each instruction
-        for instruction in &function.instructions {
+        let types = match self.types.remove(&function.name) {
+            Some(types) => types,
+            None => function.infer_types(&self.constants),
+        };
+        for (i, instruction) in function.instructions().iter().enumerate() {
+            self.operands_are_numbers = types.operands_are_numbers(i, instruction);
             self.generate_instruction(instruction);
         }
+        self.operands_are_numbers = false;
 
         // Function epilogue is generated by Return instruction
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_endproc").unwrap();
+        }
+    }
+
+    /// The C `main`: call the JS `main`, then exit with the status its
+    /// result gives. `jsrt_exit` does not return.
+    fn generate_entry_point(&mut self) {
+        self.generate_function_header("main");
+        writeln!(self.output, "\tpush %rbp").unwrap();
+        writeln!(self.output, "\tmov %rsp, %rbp").unwrap();
+        self.generate_aligned_call(runtime::JS_MAIN, &[]);
+        let registers = self.argument_registers();
+        writeln!(self.output, "\tmov %rax, {}", registers[0]).unwrap();
+        self.generate_aligned_call(runtime::EXIT_FUNCTION, &[]);
+    }
+
+    /// C-ABI wrapper for `function`, taking and returning `double`s. JS
+    /// values are passed like doubles already, so it only replaces NaNs that
+    /// would read as boxed values, then converts the result to a number.
+    fn generate_c_wrapper(&mut self, function: &IRFunction) {
+        self.current_function = function.name.clone();
+        self.generate_function_header(&function.name);
+        writeln!(self.output, "\tpush %rbp").unwrap();
+        writeln!(self.output, "\tmov %rsp, %rbp").unwrap();
+
+        let registers = self.float_argument_registers();
+        let argc = function.params.len();
+        let stack_argc = argc.saturating_sub(registers.len());
+        let shadow_space = self.shadow_space();
+        let reserved = (stack_argc * 8).next_multiple_of(16) as i32 + shadow_space;
+        if reserved > 0 {
+            writeln!(self.output, "\tsub ${}, %rsp", reserved).unwrap();
+        }
+        for register in registers.iter().take(argc) {
+            writeln!(self.output, "\tmovq {}, %rax", register).unwrap();
+            self.generate_canonical_nan();
+            writeln!(self.output, "\tmovq %rax, {}", register).unwrap();
+        }
+        // Copy the stack arguments down to where the JS function finds them
+        for j in 0..stack_argc as i32 {
+            let offset = 16 + shadow_space + j * 8;
+            writeln!(self.output, "\tmov {}(%rbp), %rax", offset).unwrap();
+            self.generate_canonical_nan();
+            writeln!(self.output, "\tmov %rax, {}(%rsp)", shadow_space + j * 8).unwrap();
+        }
+
+        let symbol = self.options.function_name(&function.name);
+        writeln!(self.output, "\tcall {}", self.call_symbol(&symbol)).unwrap();
+        let registers = self.argument_registers();
+        writeln!(self.output, "\tmov %rax, {}", registers[0]).unwrap();
+        writeln!(self.output, "\tcall {}", self.call_symbol("jsrt_to_number")).unwrap();
+        writeln!(self.output, "\tmov %rbp, %rsp").unwrap();
+        writeln!(self.output, "\tpop %rbp").unwrap();
+        writeln!(self.output, "\tret").unwrap();
+    }
+
+    /// Replace the double in %rax with the canonical NaN if it is a NaN
+    /// above `MAX_NUMBER`. Clobbers %rdx.
+    fn generate_canonical_nan(&mut self) {
+        let done = self.local_label();
+        writeln!(self.output, "\tmovabs ${}, %rdx", runtime::MAX_NUMBER).unwrap();
+        writeln!(self.output, "\tcmp %rdx, %rax").unwrap();
+        writeln!(self.output, "\tjbe {}", done).unwrap();
+        writeln!(self.output, "\tmovabs ${}, %rax", runtime::NAN).unwrap();
+        writeln!(self.output, "{}:", done).unwrap();
     }
 
     fn generate_epilogue(&mut self) {
+        // The frame is only torn down on this path; code after `ret` still has it
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_remember_state").unwrap();
+        }
         // Restore callee-saved registers
-        writeln!(self.output, "\tpop %r15").unwrap();
-        writeln!(self.output, "\tpop %r14").unwrap();
-        writeln!(self.output, "\tpop %r13").unwrap();
-        writeln!(self.output, "\tpop %r12").unwrap();
-        writeln!(self.output, "\tpop %rbx").unwrap();
+        for reg in self.callee_saved_registers().iter().rev() {
+            writeln!(self.output, "\tpop {}", reg).unwrap();
+        }
         writeln!(self.output, "\tmov %rbp, %rsp").unwrap();
         writeln!(self.output, "\tpop %rbp").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_def_cfa %rsp, 8").unwrap();
+        }
         writeln!(self.output, "\tret").unwrap();
+        if self.debug_info() {
+            writeln!(self.output, "\t.cfi_restore_state").unwrap();
+        }
+    }
+
+    /// Whether to emit DWARF line info and frame descriptions.
+    fn debug_info(&self) -> bool {
+        self.options.source_file.is_some()
     }
 
     fn generate_instruction(&mut self, instruction: &IRInstruction) {
+        if let Some(comment) =
+            self.options
+                .annotation(instruction, &self.constants, &self.functions, "#", "\t")
+        {
+            writeln!(self.output, "{}", comment).unwrap();
+        }
         match instruction {
-            IRInstruction::PushConst(constant) => self.generate_push_const(constant),
+            IRInstruction::PushConst(index) => {
+                let constant = self.constants[*index as usize].clone();
+                self.generate_push_const(&constant)
+            }
             IRInstruction::Load(name) => self.generate_load(name),
             IRInstruction::Store(name) => self.generate_store(name),
             IRInstruction::Binary(op) => self.generate_binary_op(op),
             IRInstruction::Unary(op) => self.generate_unary_op(op),
-            IRInstruction::Call(name, argc) => self.generate_call(name, *argc),
+            IRInstruction::Call(id, argc) => {
+                let name = self.functions[*id as usize].clone();
+                self.generate_call(&name, *argc)
+            }
+            IRInstruction::CallNative(name, argc) => self.generate_call(name, *argc),
+            IRInstruction::LoadUpvalue(_)
+            | IRInstruction::StoreUpvalue(_)
+            | IRInstruction::MakeClosure(..) => closures_unsupported(Target::X64),
+            IRInstruction::GetProperty(_) => objects_unsupported(Target::X64),
             IRInstruction::Return(has_value) => self.generate_return(*has_value),
             IRInstruction::Jump(label) => self.generate_jump(label),
             IRInstruction::JumpIf(label) => self.generate_jump_if(label),
-            IRInstruction::Label(label) => writeln!(self.output, "{}:", label).unwrap(),
+            IRInstruction::Label(label) => {
+                let label = self.label(label);
+                writeln!(self.output, "{}:", label).unwrap()
+            }
             IRInstruction::Pop => writeln!(self.output, "\tpop %rax").unwrap(),
             IRInstruction::Dup => {
                 writeln!(self.output, "\tmov (%rsp), %rax").unwrap();
                 writeln!(self.output, "\tpush %rax").unwrap();
             }
+            IRInstruction::Line(line, _) => {
+                self.line = Some(*line);
+                if self.debug_info() {
+                    writeln!(self.output, "\t.loc 1 {}", line).unwrap();
+                }
+                if self.options.source_map {
+                    self.line_table.mark(&self.output, Some(*line));
+                }
+            }
         }
     }
 
@@ -126,20 +381,27 @@ impl X64Generator {
             Constant::Number(n) => {
                 let idx = self.float_literals.len();
                 self.float_literals.push(*n);
-                writeln!(self.output, "\tmovsd .LCD{}(%rip), %xmm0", idx).unwrap();
-                writeln!(self.output, "\tpush %xmm0").unwrap();
+                let literal = self.local(&format!("LCD{}", idx));
+                writeln!(self.output, "\tmov {}(%rip), %rax", literal).unwrap();
+                writeln!(self.output, "\tpush %rax").unwrap();
             }
             Constant::String(s) => {
                 let idx = self.string_literals.len();
                 self.string_literals.push(s.clone());
-                writeln!(self.output, "\tleaq .LC{}(%rip), %rax", idx).unwrap();
+                let literal = self.local(&format!("LC{}", idx));
+                writeln!(self.output, "\tleaq {}(%rip), %rax", literal).unwrap();
+                writeln!(self.output, "\tmovabs ${}, %rcx", runtime::STRING_TAG).unwrap();
+                writeln!(self.output, "\tor %rcx, %rax").unwrap();
                 writeln!(self.output, "\tpush %rax").unwrap();
             }
             Constant::Boolean(b) => {
-                writeln!(self.output, "\tpush ${}", if *b { 1 } else { 0 }).unwrap();
+                let bits = if *b { runtime::TRUE } else { runtime::FALSE };
+                writeln!(self.output, "\tmovabs ${}, %rax", bits).unwrap();
+                writeln!(self.output, "\tpush %rax").unwrap();
             }
             Constant::Null => {
-                writeln!(self.output, "\tpush $0").unwrap();
+                writeln!(self.output, "\tmovabs ${}, %rax", runtime::NULL).unwrap();
+                writeln!(self.output, "\tpush %rax").unwrap();
             }
         }
     }
@@ -149,16 +411,16 @@ impl X64Generator {
             writeln!(self.output, "\tmov {}(%rbp), %rax", offset).unwrap();
             writeln!(self.output, "\tpush %rax").unwrap();
         } else {
-            // Global variable handling could be added here
-            panic!("Undefined variable: {}", name);
+            globals_unsupported(Target::X64, name, self.line);
         }
     }
 
     fn generate_store(&mut self, name: &str) {
-        let offset = self.local_offsets.get(name).cloned().unwrap_or_else(|| {
-            let offset = self.allocate_local(name);
-            offset
-        });
+        let offset = self
+            .local_offsets
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| self.allocate_local(name));
         writeln!(self.output, "\tpop %rax").unwrap();
         writeln!(self.output, "\tmov %rax, {}(%rbp)", offset).unwrap();
     }
@@ -167,134 +429,481 @@ impl X64Generator {
         writeln!(self.output, "\tpop %rcx").unwrap(); // right operand
         writeln!(self.output, "\tpop %rax").unwrap(); // left operand
 
-        match op {
-            BinaryOp::Add => {
-                writeln!(self.output, "\tadd %rcx, %rax").unwrap();
-            }
-            BinaryOp::Sub => {
-                writeln!(self.output, "\tsub %rcx, %rax").unwrap();
-            }
-            BinaryOp::Mul => {
-                writeln!(self.output, "\timul %rcx, %rax").unwrap();
-            }
-            BinaryOp::Div => {
-                writeln!(self.output, "\tcqo").unwrap();
-                writeln!(self.output, "\tidiv %rcx").unwrap();
-            }
-            BinaryOp::Eq | BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge => {
-                writeln!(self.output, "\tcmp %rcx, %rax").unwrap();
-                let cmd = match op {
-                    BinaryOp::Eq => "sete",
-                    BinaryOp::Lt => "setl",
-                    BinaryOp::Gt => "setg",
-                    BinaryOp::Le => "setle",
-                    BinaryOp::Ge => "setge",
-                    _ => unreachable!(),
-                };
-                writeln!(self.output, "\t{} %al", cmd).unwrap();
-                writeln!(self.output, "\tmovzx %al, %rax").unwrap();
-            }
-            BinaryOp::And => {
-                writeln!(self.output, "\tand %rcx, %rax").unwrap();
-            }
-            BinaryOp::Or => {
-                writeln!(self.output, "\tor %rcx, %rax").unwrap();
-            }
+        // SSE has no remainder instruction, so `%` always calls fmod
+        if matches!(op, BinaryOp::And | BinaryOp::Or | BinaryOp::Mod) {
+            self.generate_runtime_call(runtime::binary_function(op), 2);
+            writeln!(self.output, "\tpush %rax").unwrap();
+            return;
         }
+
+        self.generate_dispatch(&["%rax", "%rcx"], runtime::binary_function(op), |g| {
+            // Both operands are numbers; move their bits into SSE registers
+            writeln!(g.output, "\tmovq %rcx, %xmm1").unwrap();
+            writeln!(g.output, "\tmovq %rax, %xmm0").unwrap();
+            match op {
+                BinaryOp::Add => writeln!(g.output, "\taddsd %xmm1, %xmm0").unwrap(),
+                BinaryOp::Sub => writeln!(g.output, "\tsubsd %xmm1, %xmm0").unwrap(),
+                BinaryOp::Mul => writeln!(g.output, "\tmulsd %xmm1, %xmm0").unwrap(),
+                BinaryOp::Div => writeln!(g.output, "\tdivsd %xmm1, %xmm0").unwrap(),
+                BinaryOp::Eq | BinaryOp::StrictEq => {
+                    // Equal and ordered: ZF=1 and PF=0
+                    writeln!(g.output, "\tucomisd %xmm1, %xmm0").unwrap();
+                    writeln!(g.output, "\tsete %al").unwrap();
+                    writeln!(g.output, "\tsetnp %cl").unwrap();
+                    writeln!(g.output, "\tand %cl, %al").unwrap();
+                    g.generate_bool_from_al();
+                    return;
+                }
+                BinaryOp::Neq => {
+                    // Unequal or unordered: ZF=0 or PF=1
+                    writeln!(g.output, "\tucomisd %xmm1, %xmm0").unwrap();
+                    writeln!(g.output, "\tsetne %al").unwrap();
+                    writeln!(g.output, "\tsetp %cl").unwrap();
+                    writeln!(g.output, "\tor %cl, %al").unwrap();
+                    g.generate_bool_from_al();
+                    return;
+                }
+                BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge => {
+                    // `seta`/`setae` are false for unordered operands, so NaN
+                    // compares false; `<` and `<=` swap the operands to use them
+                    let (lhs, rhs, cmd) = match op {
+                        BinaryOp::Gt => ("%xmm0", "%xmm1", "seta"),
+                        BinaryOp::Ge => ("%xmm0", "%xmm1", "setae"),
+                        BinaryOp::Lt => ("%xmm1", "%xmm0", "seta"),
+                        BinaryOp::Le => ("%xmm1", "%xmm0", "setae"),
+                        _ => unreachable!(),
+                    };
+                    writeln!(g.output, "\tucomisd {}, {}", rhs, lhs).unwrap();
+                    writeln!(g.output, "\t{} %al", cmd).unwrap();
+                    g.generate_bool_from_al();
+                    return;
+                }
+                BinaryOp::And | BinaryOp::Or | BinaryOp::Mod => unreachable!(),
+            }
+            // Operations on canonical NaNs only produce canonical NaNs
+            writeln!(g.output, "\tmovq %xmm0, %rax").unwrap();
+        });
         writeln!(self.output, "\tpush %rax").unwrap();
     }
 
+    /// Convert the flag byte in %al into a boolean value in %rax.
+    fn generate_bool_from_al(&mut self) {
+        writeln!(self.output, "\tmovzx %al, %eax").unwrap();
+        writeln!(self.output, "\tmovabs ${}, %rcx", runtime::FALSE).unwrap();
+        writeln!(self.output, "\tor %rcx, %rax").unwrap();
+    }
+
     fn generate_unary_op(&mut self, op: &UnaryOp) {
         writeln!(self.output, "\tpop %rax").unwrap();
-        match op {
+        self.generate_dispatch(&["%rax"], runtime::unary_function(op), |g| match op {
             UnaryOp::Neg => {
-                writeln!(self.output, "\tneg %rax").unwrap();
+                // Flip the sign bit of the double; canonical NaNs stay canonical
+                writeln!(g.output, "\tbtc $63, %rax").unwrap();
             }
             UnaryOp::Not => {
-                writeln!(self.output, "\tcmp $0, %rax").unwrap();
-                writeln!(self.output, "\tsete %al").unwrap();
-                writeln!(self.output, "\tmovzx %al, %rax").unwrap();
+                // ucomisd sets ZF for both +-0.0 and NaN, the falsy numbers
+                writeln!(g.output, "\tmovq %rax, %xmm0").unwrap();
+                writeln!(g.output, "\txorpd %xmm1, %xmm1").unwrap();
+                writeln!(g.output, "\tucomisd %xmm1, %xmm0").unwrap();
+                writeln!(g.output, "\tsete %al").unwrap();
+                g.generate_bool_from_al();
             }
-        }
+        });
         writeln!(self.output, "\tpush %rax").unwrap();
     }
 
+    /// Fresh assembler-local label within the current function.
+    fn local_label(&mut self) -> String {
+        self.next_local_label += 1;
+        let name = format!("L{}.{}", self.current_function, self.next_local_label);
+        self.local(&name)
+    }
+
+    /// Leave `fast(operands)` in %rax when all `operands` are numbers, and
+    /// the result of the runtime function `slow` otherwise. Operands known
+    /// to be numbers skip the checks.
+    fn generate_dispatch(&mut self, operands: &[&str], slow: &str, fast: impl FnOnce(&mut Self)) {
+        if self.operands_are_numbers {
+            fast(self);
+            return;
+        }
+        let slow_path = self.local_label();
+        let done = self.local_label();
+        writeln!(self.output, "\tmovabs ${}, %rdx", runtime::MAX_NUMBER).unwrap();
+        for operand in operands {
+            writeln!(self.output, "\tcmp %rdx, {}", operand).unwrap();
+            writeln!(self.output, "\tja {}", slow_path).unwrap();
+        }
+        fast(self);
+        writeln!(self.output, "\tjmp {}", done).unwrap();
+        writeln!(self.output, "{}:", slow_path).unwrap();
+        self.generate_runtime_call(slow, operands.len());
+        writeln!(self.output, "{}:", done).unwrap();
+    }
+
+    /// Call a runtime function on the values in %rax (and %rcx), leaving
+    /// its result in %rax.
+    fn generate_runtime_call(&mut self, name: &str, argc: usize) {
+        let registers = self.argument_registers();
+        // On Windows the first argument register is %rcx, so move the second
+        // operand out of it first
+        if argc > 1 {
+            writeln!(self.output, "\tmov %rcx, {}", registers[1]).unwrap();
+        }
+        writeln!(self.output, "\tmov %rax, {}", registers[0]).unwrap();
+        self.generate_aligned_call(name, &[]);
+    }
+
     fn generate_call(&mut self, name: &str, argc: u16) {
-        // Align stack to 16 bytes (System V ABI requirement)
-        let stack_adjustment = (argc as i32 * 8) & 15;
-        if stack_adjustment > 0 {
-            writeln!(self.output, "\tsub ${}, %rsp", 16 - stack_adjustment).unwrap();
-        }
-
-        // Set up arguments
-        for i in (0..argc).rev() {
-            let reg = match i {
-                0 => "%rdi",
-                1 => "%rsi",
-                2 => "%rdx",
-                3 => "%rcx",
-                4 => "%r8",
-                5 => "%r9",
-                _ => panic!("Too many arguments in call to {}", name),
+        if runtime::is_builtin(name) {
+            self.generate_print(argc);
+            return;
+        }
+        if let Some(function) = self.externs.iter().find(|f| f.name == name) {
+            let function = function.clone();
+            self.generate_extern_call(&function, argc);
+            return;
+        }
+
+        // Arguments sit on the operand stack, the last one on top. The first
+        // ones go in the floating-point argument registers, the rest on the
+        // stack. Operand stack values live in memory, so no caller-saved
+        // register holds anything across the call.
+        let registers = self.float_argument_registers();
+        let argument_offset = |i: usize| (argc as usize - 1 - i) as i32 * 8;
+        for (i, reg) in registers.iter().enumerate().take(argc as usize) {
+            writeln!(self.output, "\tmov {}(%rsp), %rax", argument_offset(i)).unwrap();
+            writeln!(self.output, "\tmovq %rax, {}", reg).unwrap();
+        }
+        let stack_arguments: Vec<i32> = (registers.len()..argc as usize)
+            .map(argument_offset)
+            .collect();
+
+        let symbol = self.options.function_name(name);
+        self.generate_aligned_call(&symbol, &stack_arguments);
+        if argc > 0 {
+            writeln!(self.output, "\tadd ${}, %rsp", argc as i32 * 8).unwrap();
+        }
+
+        // The result comes back in %xmm0
+        writeln!(self.output, "\tmovq %xmm0, %rax").unwrap();
+        writeln!(self.output, "\tpush %rax").unwrap();
+    }
+
+    /// Call a C function. Each argument is converted in place on the
+    /// operand stack, then passed by the C calling convention: integers and
+    /// pointers in the integer argument registers and doubles in the SSE
+    /// ones, which System V fills independently and Windows by position.
+    fn generate_extern_call(&mut self, function: &ExternFunction, argc: u16) {
+        let argc = argc as usize;
+        let argument_offset = |i: usize| (argc - 1 - i) as i32 * 8;
+        let types: Vec<Option<CType>> =
+            (0..argc).map(|i| function.params.get(i).copied()).collect();
+        for (i, &ty) in types.iter().enumerate() {
+            writeln!(self.output, "	mov {}(%rsp), %rax", argument_offset(i)).unwrap();
+            self.generate_runtime_call(runtime::to_c_function(ty), 1);
+            if ty == Some(CType::Double) {
+                writeln!(self.output, "	movq %xmm0, %rax").unwrap();
+            }
+            writeln!(self.output, "	mov %rax, {}(%rsp)", argument_offset(i)).unwrap();
+        }
+
+        let registers = self.argument_registers();
+        let float_registers = self.float_argument_registers();
+        let (mut next_register, mut next_float_register) = (0, 0);
+        let mut stack_arguments = Vec::new();
+        for (i, &ty) in types.iter().enumerate() {
+            let is_double = ty == Some(CType::Double);
+            if self.win64() {
+                (next_register, next_float_register) = (i, i);
+            }
+            let register = if is_double {
+                next_float_register += 1;
+                float_registers.get(next_float_register - 1)
+            } else {
+                next_register += 1;
+                registers.get(next_register - 1)
             };
-            writeln!(self.output, "\tpop {}", reg).unwrap();
+            match register {
+                Some(register) if is_double => {
+                    writeln!(self.output, "	mov {}(%rsp), %rax", argument_offset(i)).unwrap();
+                    writeln!(self.output, "	movq %rax, {}", register).unwrap();
+                }
+                Some(register) => {
+                    writeln!(
+                        self.output,
+                        "	mov {}(%rsp), {}",
+                        argument_offset(i),
+                        register
+                    )
+                    .unwrap();
+                }
+                None => stack_arguments.push(argument_offset(i)),
+            }
+        }
+        // System V variadic functions take the number of SSE registers used in %al
+        if function.variadic && !self.win64() {
+            let used = next_float_register.min(float_registers.len());
+            writeln!(self.output, "	mov ${}, %eax", used).unwrap();
+        }
+
+        let symbol = self.extern_symbol(&function.name);
+        self.generate_aligned_call_to(&symbol, &stack_arguments);
+        if argc > 0 {
+            writeln!(self.output, "	add ${}, %rsp", argc as i32 * 8).unwrap();
         }
 
-        writeln!(self.output, "\tcall {}", name).unwrap();
+        match runtime::from_c_function(function.returns) {
+            // A double result is already in %xmm0, the argument register
+            Some(convert) if function.returns == CType::Double => {
+                self.generate_aligned_call(convert, &[])
+            }
+            Some(convert) => self.generate_runtime_call(convert, 1),
+            None => writeln!(self.output, "	movabs ${}, %rax", runtime::UNDEFINED).unwrap(),
+        }
+        writeln!(self.output, "	push %rax").unwrap();
+    }
 
-        // Clean up stack if needed
-        if stack_adjustment > 0 {
-            writeln!(self.output, "\tadd ${}, %rsp", 16 - stack_adjustment).unwrap();
+    /// `print(a, b, ...)` prints each argument through the runtime, separated
+    /// by spaces and terminated by a newline.
+    fn generate_print(&mut self, argc: u16) {
+        let registers = self.argument_registers();
+        if argc == 0 {
+            self.generate_aligned_call("jsrt_print_newline", &[]);
+        }
+        for i in 0..argc {
+            // The first argument was pushed first, so it sits deepest
+            let offset = (argc - 1 - i) as i32 * 8;
+            let separator = if i + 1 == argc { b'\n' } else { b' ' };
+            writeln!(self.output, "\tmov {}(%rsp), {}", offset, registers[0]).unwrap();
+            writeln!(self.output, "\tmov ${}, {}", separator, registers[1]).unwrap();
+            self.generate_aligned_call("jsrt_print_value", &[]);
+        }
+        if argc > 0 {
+            writeln!(self.output, "\tadd ${}, %rsp", argc as i32 * 8).unwrap();
         }
 
-        // Push return value
+        writeln!(self.output, "\tmovabs ${}, %rax", runtime::UNDEFINED).unwrap();
         writeln!(self.output, "\tpush %rax").unwrap();
     }
 
+    /// Call a function on a 16-byte aligned stack, as both ABIs require.
+    /// Register arguments are already in place; `stack_arguments` are the
+    /// `%rsp` offsets of the values to pass on the stack, in order. The
+    /// operand stack depth varies, so align dynamically; %rbx is
+    /// callee-saved and holds the unaligned %rsp across the call.
+    fn generate_aligned_call(&mut self, name: &str, stack_arguments: &[i32]) {
+        let symbol = self.call_symbol(name);
+        self.generate_aligned_call_to(&symbol, stack_arguments);
+    }
+
+    /// Symbol to call the function `name` by. A shared library can't reach
+    /// functions with plain PC32 calls.
+    fn call_symbol(&self, name: &str) -> String {
+        if self.options.pic {
+            self.extern_symbol(name)
+        } else {
+            self.symbol(name)
+        }
+    }
+
+    /// [`Self::generate_aligned_call`] to an exact symbol. It only clobbers
+    /// %rbx and %r11 before the call, so %rax can carry an argument.
+    fn generate_aligned_call_to(&mut self, symbol: &str, stack_arguments: &[i32]) {
+        writeln!(self.output, "\tmov %rsp, %rbx").unwrap();
+        // The Microsoft ABI requires 32 bytes of shadow space for the callee
+        let shadow_space = self.shadow_space();
+        let reserved = (stack_arguments.len() * 8).next_multiple_of(16) as i32 + shadow_space;
+        if reserved > 0 {
+            writeln!(self.output, "\tsub ${}, %rsp", reserved).unwrap();
+        }
+        writeln!(self.output, "\tand $-16, %rsp").unwrap();
+        for (j, offset) in stack_arguments.iter().enumerate() {
+            writeln!(self.output, "\tmov {}(%rbx), %r11", offset).unwrap();
+            writeln!(
+                self.output,
+                "\tmov %r11, {}(%rsp)",
+                shadow_space + j as i32 * 8
+            )
+            .unwrap();
+        }
+        writeln!(self.output, "\tcall {}", symbol).unwrap();
+        writeln!(self.output, "\tmov %rbx, %rsp").unwrap();
+    }
+
     fn generate_return(&mut self, has_value: bool) {
+        // Return the double in %xmm0, and its bits in %rax
         if has_value {
             writeln!(self.output, "\tpop %rax").unwrap();
+        } else {
+            writeln!(self.output, "\tmovabs ${}, %rax", runtime::UNDEFINED).unwrap();
         }
+        writeln!(self.output, "\tmovq %rax, %xmm0").unwrap();
         self.generate_epilogue();
     }
 
     fn generate_jump(&mut self, label: &str) {
+        let label = self.label(label);
         writeln!(self.output, "\tjmp {}", label).unwrap();
     }
 
     fn generate_jump_if(&mut self, label: &str) {
+        let label = self.label(label);
+        let boxed = self.local_label();
+        let done = self.local_label();
         writeln!(self.output, "\tpop %rax").unwrap();
+        writeln!(self.output, "\tmovabs ${}, %rdx", runtime::MAX_NUMBER).unwrap();
+        writeln!(self.output, "\tcmp %rdx, %rax").unwrap();
+        writeln!(self.output, "\tja {}", boxed).unwrap();
+        // A number is truthy unless it is +-0.0 or NaN, which set ZF
+        writeln!(self.output, "\tmovq %rax, %xmm0").unwrap();
+        writeln!(self.output, "\txorpd %xmm1, %xmm1").unwrap();
+        writeln!(self.output, "\tucomisd %xmm1, %xmm0").unwrap();
+        writeln!(self.output, "\tjne {}", label).unwrap();
+        writeln!(self.output, "\tjmp {}", done).unwrap();
+        writeln!(self.output, "{}:", boxed).unwrap();
+        self.generate_runtime_call("jsrt_truthy", 1);
         writeln!(self.output, "\tcmp $0, %rax").unwrap();
         writeln!(self.output, "\tjne {}", label).unwrap();
+        writeln!(self.output, "{}:", done).unwrap();
     }
 }
 
 impl CodeGenerator for X64Generator {
-    fn generate(&mut self, module: IRModule) -> String {
-        // Data section for constants
-        writeln!(self.output, "\t.section .data").unwrap();
+    fn begin_module(&mut self, module: &IRModule, out: &mut dyn io::Write) -> io::Result<()> {
+        if self.options.x64_syntax == X64Syntax::Intel {
+            writeln!(out, "\t.intel_syntax noprefix")?;
+        }
+        if let Some(file) = self.options.file_directive() {
+            writeln!(self.output, "{}", file).unwrap();
+        }
+        writeln!(self.output, "\t{}", self.options.target.text_section()).unwrap();
+        self.externs = module.externs.clone();
+        self.functions = module.function_names();
+        self.constants = module.constants.clone();
+        // Inferring types is the work on each function that needs no state
+        // of the generator, so it runs for all of them at once
+        let types = parallel::map(&module.functions, |function| {
+            function.infer_types(&module.constants)
+        });
+        let names = module
+            .functions
+            .iter()
+            .map(|function| function.name.clone());
+        self.types = names.zip(types).collect();
+        self.flush(out)
+    }
+
+    fn emit_function(&mut self, function: &IRFunction, out: &mut dyn io::Write) -> io::Result<()> {
+        self.generate_function(function);
+        if self.options.source_map {
+            self.line_table.mark(&self.output, None);
+        }
+        if self.options.c_abi {
+            if function.name != "main" {
+                self.generate_c_wrapper(function);
+            }
+        } else if function.name == "main" {
+            self.generate_entry_point();
+        }
+        self.flush(out)
+    }
+
+    /// Emit the literals the functions collected, after their code.
+    fn finish(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        writeln!(self.output, "\t{}", self.options.target.data_section()).unwrap();
 
         // Add string literals
         for (i, s) in self.string_literals.iter().enumerate() {
-            writeln!(self.output, ".LC{}:", i).unwrap();
-            writeln!(self.output, "\t.string \"{}\"", s).unwrap();
+            writeln!(self.output, "{}:", self.local(&format!("LC{}", i))).unwrap();
+            writeln!(self.output, "\t.asciz {}", assembler::quote(s)).unwrap();
         }
 
-        // Add float literals
+        // Add float literals, aligned for 8-byte loads after the strings
+        if !self.float_literals.is_empty() {
+            writeln!(self.output, "\t.p2align 3").unwrap();
+        }
         for (i, f) in self.float_literals.iter().enumerate() {
-            writeln!(self.output, ".LCD{}:", i).unwrap();
+            writeln!(self.output, "{}:", self.local(&format!("LCD{}", i))).unwrap();
             writeln!(self.output, "\t.double {}", f).unwrap();
         }
 
-        // Text section for code
-        writeln!(self.output, "\t.section .text").unwrap();
+        // Mark the stack non-executable for GNU ld
+        if self.options.target.os == TargetOs::Linux {
+            writeln!(self.output, "\t.section .note.GNU-stack,\"\",@progbits").unwrap();
+        }
+        self.flush(out)
+    }
+
+    fn line_table(&self) -> Option<&LineTable> {
+        self.options.source_map.then_some(&self.line_table)
+    }
+}
+
+impl X64Generator {
+    /// Write the assembly generated so far to `out`.
+    fn flush(&mut self, out: &mut dyn io::Write) -> io::Result<()> {
+        let assembly = std::mem::take(&mut self.output);
+        self.line_table.flushed(&assembly);
+        match self.options.x64_syntax {
+            X64Syntax::Att => out.write_all(assembly.as_bytes()),
+            X64Syntax::Intel => out.write_all(to_intel_syntax(&assembly).as_bytes()),
+        }
+    }
+}
 
-        // Generate code for each function
-        for function in module.functions {
-            self.generate_function(&function);
+/// Rewrite this backend's AT&T output in Intel syntax: destination operand
+/// first, no `%`/`$` sigils, and memory operands in brackets. Every memory
+/// access pairs with a register, which gives its size.
+fn to_intel_syntax(att: &str) -> String {
+    let mut output = String::new();
+    for line in att.lines() {
+        let instruction = line
+            .strip_prefix('\t')
+            .filter(|rest| !rest.starts_with(['.', '#']));
+        let Some(instruction) = instruction else {
+            // Labels, directives and comments are the same in both
+            writeln!(output, "{}", line).unwrap();
+            continue;
+        };
+        let (mnemonic, operands) = instruction.split_once(' ').unwrap_or((instruction, ""));
+        let mnemonic = match mnemonic {
+            "leaq" => "lea",
+            mnemonic => mnemonic,
+        };
+        let mut operands: Vec<String> = operands
+            .split(", ")
+            .filter(|operand| !operand.is_empty())
+            .map(intel_operand)
+            .collect();
+        operands.reverse();
+        if operands.is_empty() {
+            writeln!(output, "\t{}", mnemonic).unwrap();
+        } else {
+            writeln!(output, "\t{} {}", mnemonic, operands.join(", ")).unwrap();
         }
+    }
+    output
+}
 
-        // Return the generated assembly
-        self.output.clone()
+fn intel_operand(operand: &str) -> String {
+    if let Some(register) = operand.strip_prefix('%') {
+        return register.to_string();
+    }
+    if let Some(value) = operand.strip_prefix('$') {
+        return value.to_string();
+    }
+    let Some((displacement, base)) = operand.strip_suffix(')').and_then(|o| o.split_once('('))
+    else {
+        // Branch and call targets
+        return operand.to_string();
+    };
+    let base = base.trim_start_matches('%');
+    match displacement {
+        "" | "0" => format!("[{}]", base),
+        _ => match displacement.strip_prefix('-') {
+            Some(magnitude) => format!("[{} - {}]", base, magnitude),
+            None => format!("[{} + {}]", base, displacement),
+        },
     }
 }
@@ -0,0 +1,259 @@
+//! The project file, `jscompiler.toml`, giving defaults that flags on the
+//! command line override. It is read from the current directory, or the
+//! nearest directory above it that has one:
+//!
+//! ```toml
+//! [build]
+//! target = "x64"        # for build and emit without --target or --triple
+//! opt-level = 2
+//! out-dir = "build"     # where generated code goes without -o
+//!
+//! [vm]
+//! builtins = ["print", "assert"]
+//! max-call-depth = 1000
+//! max-instructions = 10_000_000
+//! ```
+//!
+//! Only the part of TOML these settings need is understood: sections,
+//! strings, integers and arrays on one line, and `#` comments.
+
+use js_compiler::codegen::Target;
+use js_compiler::optimizer::OptLevel;
+use js_compiler::vm;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = "jscompiler.toml";
+
+/// Settings from a project file; `None` for those it leaves out.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub target: Option<Target>,
+    pub opt_level: Option<OptLevel>,
+    /// Relative to the directory of the project file.
+    pub out_dir: Option<PathBuf>,
+    /// The natives the VM provides, out of `vm::BUILTINS`.
+    pub builtins: Option<Vec<String>>,
+    pub limits: vm::Limits,
+}
+
+impl Config {
+    /// The settings of the project file in the current directory or the
+    /// nearest above it, or none without one.
+    pub fn find() -> Result<Config, String> {
+        let mut directory = std::env::current_dir().ok();
+        while let Some(current) = directory {
+            let path = current.join(FILE_NAME);
+            if path.is_file() {
+                return Config::load(&path);
+            }
+            directory = current.parent().map(Path::to_path_buf);
+        }
+        Ok(Config::default())
+    }
+
+    fn load(path: &Path) -> Result<Config, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut config = Config::parse(&text).map_err(|e| format!("{}:{}", path.display(), e))?;
+        if let (Some(out_dir), Some(directory)) = (&mut config.out_dir, path.parent()) {
+            *out_dir = directory.join(&*out_dir);
+        }
+        Ok(config)
+    }
+
+    /// The settings in the text of a project file; an error starts with
+    /// the number of the line it is on.
+    fn parse(text: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut section = String::new();
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| format!("{}: {}", index + 1, message);
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("Unclosed section header".to_string()))?
+                    .trim();
+                if !matches!(name, "build" | "vm") {
+                    return Err(error(format!("Unknown section `[{}]`", name)));
+                }
+                section = name.to_string();
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("Expected `key = value`".to_string()))?;
+            let key = key.trim();
+            let value = Value::parse(value.trim())
+                .ok_or_else(|| error(format!("Invalid value for `{}`", key)))?;
+            config.set(&section, key, &value).map_err(error)?;
+        }
+        Ok(config)
+    }
+
+    fn set(&mut self, section: &str, key: &str, value: &Value) -> Result<(), String> {
+        match (section, key) {
+            ("build", "target") => {
+                let name = value.string(key)?;
+                self.target = Some(
+                    Target::from_name(name)
+                        .filter(|target| !matches!(target, Target::None))
+                        .ok_or_else(|| format!("Unknown target `{}`", name))?,
+                );
+            }
+            ("build", "opt-level") => {
+                let level = value.integer(key)?;
+                self.opt_level = Some(
+                    OptLevel::from_flag(&format!("-O{}", level))
+                        .ok_or_else(|| format!("Unknown optimization level `{}`", level))?,
+                );
+            }
+            ("build", "out-dir") => self.out_dir = Some(PathBuf::from(value.string(key)?)),
+            ("vm", "builtins") => {
+                let names = value.strings(key)?;
+                let known = |name: &String| vm::BUILTINS.iter().any(|(builtin, _)| builtin == name);
+                if let Some(name) = names.iter().find(|name| !known(name)) {
+                    return Err(format!("Unknown built-in `{}`", name));
+                }
+                self.builtins = Some(names);
+            }
+            ("vm", "max-call-depth") => {
+                self.limits.max_call_depth = Some(value.positive(key)? as usize)
+            }
+            ("vm", "max-instructions") => self.limits.max_instructions = Some(value.positive(key)?),
+            ("", _) => return Err(format!("`{}` is outside any section", key)),
+            _ => return Err(format!("Unknown setting `{}` in [{}]", key, section)),
+        }
+        Ok(())
+    }
+}
+
+/// `line` without the comment it ends with, if any.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// A setting's value.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn parse(text: &str) -> Option<Value> {
+        if let Some(items) = text.strip_prefix('[') {
+            // Strings in arrays are names, which have no commas
+            let items = items.strip_suffix(']')?;
+            return items
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(Value::parse)
+                .collect::<Option<_>>()
+                .map(Value::Array);
+        }
+        if let Some(string) = text.strip_prefix('"') {
+            let string = string.strip_suffix('"').filter(|s| !s.contains('"'))?;
+            return Some(Value::String(string.to_string()));
+        }
+        text.replace('_', "").parse().ok().map(Value::Integer)
+    }
+
+    fn string(&self, key: &str) -> Result<&str, String> {
+        match self {
+            Value::String(string) => Ok(string),
+            _ => Err(format!("`{}` needs a string", key)),
+        }
+    }
+
+    fn integer(&self, key: &str) -> Result<i64, String> {
+        match self {
+            Value::Integer(n) => Ok(*n),
+            _ => Err(format!("`{}` needs a number", key)),
+        }
+    }
+
+    fn positive(&self, key: &str) -> Result<u64, String> {
+        u64::try_from(self.integer(key)?)
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("`{}` needs a positive number", key))
+    }
+
+    fn strings(&self, key: &str) -> Result<Vec<String>, String> {
+        let error = || format!("`{}` needs an array of strings", key);
+        let Value::Array(items) = self else {
+            return Err(error());
+        };
+        items
+            .iter()
+            .map(|item| item.string(key).map(String::from).map_err(|_| error()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "# Project settings\n\
+             [build]\n\
+             target = \"arm64\"  # for the board\n\
+             opt-level = 2\n\
+             out-dir = \"build\"\n\
+             \n\
+             [vm]\n\
+             builtins = [\"print\", \"assert\",]\n\
+             max-instructions = 1_000_000\n",
+        )
+        .unwrap();
+        assert!(matches!(config.target, Some(Target::ARM64)));
+        assert_eq!(config.opt_level, Some(OptLevel::O2));
+        assert_eq!(config.out_dir, Some(PathBuf::from("build")));
+        assert_eq!(config.builtins.unwrap(), ["print", "assert"]);
+        assert_eq!(config.limits.max_instructions, Some(1_000_000));
+        assert_eq!(config.limits.max_call_depth, None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |text: &str| Config::parse(text).unwrap_err();
+        assert_eq!(
+            error("opt-level = 1"),
+            "1: `opt-level` is outside any section"
+        );
+        assert_eq!(
+            error("[build]\nopt-level = 3"),
+            "2: Unknown optimization level `3`"
+        );
+        assert_eq!(
+            error("[vm]\nbuiltins = [\"exit\"]"),
+            "2: Unknown built-in `exit`"
+        );
+        assert_eq!(
+            error("[vm]\nmax-call-depth = 0"),
+            "2: `max-call-depth` needs a positive number"
+        );
+        assert_eq!(error("[run]"), "1: Unknown section `[run]`");
+        assert_eq!(
+            error("[build]\ntarget = x64"),
+            "2: Invalid value for `target`"
+        );
+    }
+}
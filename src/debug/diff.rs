@@ -0,0 +1,178 @@
+//! Comparing two recorded traces, for `debug diff`: to check that an
+//! optimizer pass or a change to the VM leaves what a program does alone,
+//! trace it before and after and find the first step where they part.
+
+use super::DebugFrame;
+use serde::Deserialize;
+
+/// Where two traces first differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Index of the frame compared in each trace; `None` for a trace that
+    /// ended before the other.
+    pub a: Option<usize>,
+    pub b: Option<usize>,
+    /// What differs, with its value in each trace: the function, the
+    /// instruction, the stack, or a local by name.
+    pub differences: Vec<(String, String, String)>,
+}
+
+/// The frames of a trace written as JSON, as one object or a line each.
+pub fn read_frames(text: &str) -> Result<Vec<DebugFrame>, String> {
+    #[derive(Deserialize)]
+    struct Trace {
+        frames: Vec<DebugFrame>,
+    }
+
+    if text.trim_start().starts_with(r#"{"frames""#) {
+        let trace: Trace = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        return Ok(trace.frames);
+    }
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| format!("line {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+/// The first step at which `a` and `b` do something different, or `None`
+/// if they match throughout. Line markers and labels do nothing, so they
+/// are skipped, and traces of code differing only in those line up.
+pub fn first_divergence(a: &[DebugFrame], b: &[DebugFrame]) -> Option<Divergence> {
+    let steps = |frames: &[DebugFrame]| -> Vec<usize> {
+        (0..frames.len())
+            .filter(|&i| !is_marker(&frames[i].instruction))
+            .collect()
+    };
+    let (steps_a, steps_b) = (steps(a), steps(b));
+    for step in 0..steps_a.len().max(steps_b.len()) {
+        let (index_a, index_b) = (steps_a.get(step).copied(), steps_b.get(step).copied());
+        let (Some(i), Some(j)) = (index_a, index_b) else {
+            return Some(Divergence {
+                a: index_a,
+                b: index_b,
+                differences: Vec::new(),
+            });
+        };
+        let differences = differences(&a[i], &b[j]);
+        if !differences.is_empty() {
+            return Some(Divergence {
+                a: index_a,
+                b: index_b,
+                differences,
+            });
+        }
+    }
+    None
+}
+
+fn is_marker(instruction: &str) -> bool {
+    instruction.starts_with("Line(") || instruction.starts_with("Label(")
+}
+
+fn differences(a: &DebugFrame, b: &DebugFrame) -> Vec<(String, String, String)> {
+    let mut differences = Vec::new();
+    let mut compare = |what: String, a: String, b: String| {
+        if a != b {
+            differences.push((what, a, b));
+        }
+    };
+    compare(
+        "function".to_string(),
+        a.function_name.clone(),
+        b.function_name.clone(),
+    );
+    compare(
+        "instruction".to_string(),
+        a.instruction.clone(),
+        b.instruction.clone(),
+    );
+    compare(
+        "stack".to_string(),
+        format!("[{}]", a.stack.join(", ")),
+        format!("[{}]", b.stack.join(", ")),
+    );
+    let names = a
+        .locals
+        .keys()
+        .chain(b.locals.keys().filter(|name| !a.locals.contains_key(*name)));
+    for name in names {
+        let value = |frame: &DebugFrame| {
+            frame
+                .locals
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| "unset".to_string())
+        };
+        compare(format!("local {}", name), value(a), value(b));
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn frame(instruction: &str, stack: &[&str], n: &str) -> DebugFrame {
+        DebugFrame {
+            instruction: instruction.to_string(),
+            stack: stack.iter().map(|value| value.to_string()).collect(),
+            locals: BTreeMap::from([("n".to_string(), n.to_string())]),
+            ip: 0,
+            function_name: "main".to_string(),
+            depth: 1,
+            line: None,
+            column: None,
+        }
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let a = [
+            frame("Line(1, 1)", &[], "1"),
+            frame("Load(\"n\")", &[], "1"),
+            frame("Return(true)", &["Number(1.0)"], "1"),
+        ];
+        let b = [
+            frame("Load(\"n\")", &[], "1"),
+            frame("Return(true)", &["Number(2.0)"], "2"),
+        ];
+        assert_eq!(first_divergence(&a, &a), None);
+        assert_eq!(
+            first_divergence(&a, &b),
+            Some(Divergence {
+                a: Some(2),
+                b: Some(1),
+                differences: vec![
+                    (
+                        "stack".to_string(),
+                        "[Number(1.0)]".to_string(),
+                        "[Number(2.0)]".to_string()
+                    ),
+                    ("local n".to_string(), "1".to_string(), "2".to_string()),
+                ],
+            })
+        );
+        let divergence = first_divergence(&a, &b[..1]).unwrap();
+        assert_eq!((divergence.a, divergence.b), (Some(2), None));
+    }
+
+    #[test]
+    fn test_read_frames() {
+        let frames = [frame("Pop", &[], "1"), frame("Pop", &[], "2")];
+        let ndjson: String = frames
+            .iter()
+            .map(|frame| serde_json::to_string(frame).unwrap() + "\n")
+            .collect();
+        let json = format!(
+            r#"{{"frames":[{}],"functions":{{}}}}"#,
+            ndjson.trim_end().replace('\n', ",")
+        );
+        assert_eq!(read_frames(&ndjson).unwrap().len(), 2);
+        assert_eq!(read_frames(&json).unwrap()[1].locals["n"], "2");
+        assert!(read_frames("{\"ip\":").unwrap_err().starts_with("line 1: "));
+    }
+}
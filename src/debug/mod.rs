@@ -1,28 +1,244 @@
-use crate::ir::{IRFunction, IRInstruction};
+mod diff;
+
+use crate::ir::{Constant, IRFunction, IRInstruction};
 use crate::vm::Value;
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::{self, Write};
+
+pub use diff::{first_divergence, read_frames, Divergence};
 
-#[derive(Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DebugFrame {
     pub instruction: String,
     pub stack: Vec<String>,
-    pub locals: HashMap<String, String>,
+    /// Sorted by name, so traces of two runs can be compared line by line.
+    pub locals: BTreeMap<String, String>,
     pub ip: usize,
     pub function_name: String,
+    /// Number of calls in progress, this function's included.
+    pub depth: usize,
+    /// Source line and column of the statement being executed, if known.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Where in the program an instruction is executed.
+#[derive(Debug, Clone, Copy)]
+pub struct Location<'a> {
+    pub function_name: &'a str,
+    pub ip: usize,
+    /// Number of calls in progress, this function's included.
+    pub depth: usize,
+    /// Source line and column of the statement, if known.
+    pub position: Option<(usize, usize)>,
+    /// The constants `PushConst` instructions index.
+    pub constants: &'a [Constant],
+    /// Names of the functions `Call` instructions index.
+    pub functions: &'a [String],
+}
+
+/// Where the trace marks a frame for the page to stop at when playing:
+/// an instruction of a function, by default its first, and only when a
+/// local has a given value if there is a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakpoint {
+    pub function: String,
+    pub ip: Option<usize>,
+    /// A local and the value it must have.
+    pub condition: Option<(String, Value)>,
+}
+
+impl Breakpoint {
+    /// Read a breakpoint written `function[:ip][ if local == value]`, such
+    /// as `fib:3` or `fib if n == 3`. Values are written as in the source.
+    pub fn parse(text: &str) -> Result<Breakpoint, String> {
+        let (place, condition) = match text.split_once(" if ") {
+            Some((place, condition)) => (place.trim(), Some(condition)),
+            None => (text.trim(), None),
+        };
+        let (function, ip) = match place.split_once(':') {
+            Some((function, ip)) => {
+                let ip = ip
+                    .parse()
+                    .map_err(|_| format!("Invalid instruction index `{}`", ip))?;
+                (function, Some(ip))
+            }
+            None => (place, None),
+        };
+        if function.is_empty() {
+            return Err(format!("`{}` names no function to break in", text));
+        }
+        let condition = match condition {
+            Some(condition) => {
+                let (local, value) = condition
+                    .split_once("==")
+                    .map(|(local, value)| (local.trim(), value.trim()))
+                    .filter(|(local, _)| !local.is_empty())
+                    .ok_or_else(|| format!("Expected `local == value`, not `{}`", condition))?;
+                let value =
+                    parse_value(value).ok_or_else(|| format!("Invalid value `{}`", value))?;
+                Some((local.to_string(), value))
+            }
+            None => None,
+        };
+        Ok(Breakpoint {
+            function: function.to_string(),
+            ip,
+            condition,
+        })
+    }
+
+    fn hits(&self, location: &Location, locals: &HashMap<String, Value>) -> bool {
+        self.function == location.function_name
+            && self.ip.unwrap_or(0) == location.ip
+            && self
+                .condition
+                .as_ref()
+                .is_none_or(|(local, value)| locals.get(local) == Some(value))
+    }
+}
+
+/// A literal as it would be written in the source.
+fn parse_value(text: &str) -> Option<Value> {
+    match text {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        "null" => Some(Value::Null),
+        "undefined" => Some(Value::Undefined),
+        _ => match text
+            .strip_prefix('"')
+            .and_then(|text| text.strip_suffix('"'))
+        {
+            Some(string) => Some(Value::String(string.into())),
+            None => text.parse().ok().map(Value::Number),
+        },
+    }
+}
+
+/// Which executed instructions a trace records, to keep traces of loops
+/// and deep recursion small.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceOptions {
+    /// Only the instructions of these functions; all when `None`.
+    pub functions: Option<Vec<String>>,
+    /// Only calls and returns, leaving out the work between them.
+    pub calls_only: bool,
+    /// Record one instruction out of this many of those the other
+    /// options keep.
+    pub sample_every: u64,
+    /// Keep only this many of the latest frames, dropping older ones.
+    pub last: Option<usize>,
+    /// Frames to mark, which are recorded whatever the options above say.
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+impl Default for TraceOptions {
+    fn default() -> Self {
+        TraceOptions {
+            functions: None,
+            calls_only: false,
+            sample_every: 1,
+            last: None,
+            breakpoints: Vec::new(),
+        }
+    }
+}
+
+impl TraceOptions {
+    fn includes(&self, function_name: &str) -> bool {
+        self.functions
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|name| name == function_name))
+    }
 }
 
 #[derive(Serialize)]
 pub struct DebugTrace {
-    pub frames: Vec<DebugFrame>,
+    pub frames: VecDeque<DebugFrame>,
+    /// Frames recorded but no longer in `frames`: older than the last ones
+    /// kept, or streamed out.
+    pub dropped_frames: u64,
+    /// Indices of the frames at a breakpoint, in `frames` or, when
+    /// streaming, among the frames written.
     pub breakpoints: Vec<usize>,
+    /// The instructions of each function called, for the page to list
+    /// around the one being executed.
+    pub functions: BTreeMap<String, Vec<String>>,
+    /// Lines of the program's source, for the page to show the statement
+    /// being executed; empty if not known.
+    pub source: Vec<String>,
+    #[serde(skip)]
+    options: TraceOptions,
+    /// Instructions the options kept, sampled or not.
+    #[serde(skip)]
+    kept: u64,
+    /// Where frames are written as NDJSON instead of being kept.
+    #[serde(skip)]
+    stream: Option<Box<dyn Write>>,
+}
+
+impl Default for DebugTrace {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DebugTrace {
     pub fn new() -> Self {
+        Self::with_options(TraceOptions::default())
+    }
+
+    pub fn with_options(options: TraceOptions) -> Self {
         DebugTrace {
-            frames: Vec::new(),
+            frames: VecDeque::new(),
+            dropped_frames: 0,
             breakpoints: Vec::new(),
+            functions: BTreeMap::new(),
+            source: Vec::new(),
+            options,
+            kept: 0,
+            stream: None,
+        }
+    }
+
+    /// A trace writing each frame to `out` as it is recorded, a JSON object
+    /// per line, so a long run takes no memory for its frames.
+    pub fn streaming(options: TraceOptions, out: Box<dyn Write>) -> Self {
+        DebugTrace {
+            stream: Some(out),
+            ..Self::with_options(options)
+        }
+    }
+
+    /// Give the source the lines and columns of frames refer to.
+    pub fn set_source(&mut self, source: &str) {
+        self.source = source.lines().map(String::from).collect();
+    }
+
+    /// Flush what has been streamed; nothing to do for a trace kept in
+    /// memory.
+    pub fn finish(&mut self) -> io::Result<()> {
+        match &mut self.stream {
+            Some(out) => out.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Record the listing of `function` the first time it is called.
+    pub fn add_function(
+        &mut self,
+        function: &IRFunction,
+        constants: &[Constant],
+        functions: &[String],
+    ) {
+        if self.options.includes(&function.name) && !self.functions.contains_key(&function.name) {
+            let listing = function
+                .instructions()
+                .iter()
+                .map(|instruction| instruction.describe(constants, functions))
+                .collect();
+            self.functions.insert(function.name.clone(), listing);
         }
     }
 
@@ -31,24 +247,341 @@ impl DebugTrace {
         instruction: &IRInstruction,
         stack: &[Value],
         locals: &HashMap<String, Value>,
-        ip: usize,
-        function_name: &str,
+        location: Location,
     ) {
+        let function_name = location.function_name;
+        let options = &self.options;
+        let at_breakpoint = options
+            .breakpoints
+            .iter()
+            .any(|breakpoint| breakpoint.hits(&location, locals));
+        let is_call = matches!(
+            instruction,
+            IRInstruction::Call(..) | IRInstruction::CallNative(..) | IRInstruction::Return(_)
+        );
+        if !at_breakpoint {
+            if !options.includes(function_name) || (options.calls_only && !is_call) {
+                return;
+            }
+            let sampled = self.kept.is_multiple_of(options.sample_every);
+            self.kept += 1;
+            if !sampled {
+                return;
+            }
+        }
         let frame = DebugFrame {
-            instruction: format!("{:?}", instruction),
+            instruction: instruction.describe(location.constants, location.functions),
             stack: stack.iter().map(|v| format!("{:?}", v)).collect(),
             locals: locals
                 .iter()
                 .map(|(k, v)| (k.clone(), format!("{:?}", v)))
                 .collect(),
-            ip,
+            ip: location.ip,
             function_name: function_name.to_string(),
+            depth: location.depth,
+            line: location.position.map(|(line, _)| line),
+            column: location.position.map(|(_, column)| column),
         };
-        self.frames.push(frame);
+        if let Some(out) = &mut self.stream {
+            serde_json::to_writer(&mut *out, &frame)
+                .map_err(io::Error::from)
+                .and_then(|()| out.write_all(b"\n"))
+                .expect("Failed to write the debug trace");
+            if at_breakpoint {
+                self.breakpoints.push(self.dropped_frames as usize);
+            }
+            self.dropped_frames += 1;
+            return;
+        }
+        if self.options.last == Some(self.frames.len()) {
+            self.frames.pop_front();
+            self.dropped_frames += 1;
+            // Indices of the frames left move down one
+            self.breakpoints
+                .retain_mut(|index| match index.checked_sub(1) {
+                    Some(moved) => {
+                        *index = moved;
+                        true
+                    }
+                    None => false,
+                });
+        }
+        if at_breakpoint {
+            self.breakpoints.push(self.frames.len());
+        }
+        self.frames.push_back(frame);
     }
 
     pub fn generate_html(&self) -> String {
-        include_str!("debug.template")
-            .replace("{{TRACE_DATA}}", &serde_json::to_string(self).unwrap())
+        include_str!("debug.template").replace("{{TRACE_DATA}}", &self.to_json())
+    }
+
+    /// The whole trace as one JSON object: `frames`, `breakpoints` and the
+    /// instructions of each function in `functions`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// The trace as folded stacks, which flamegraph tools draw: a line per
+    /// call path, its functions from the outermost joined by `;`, and the
+    /// number of instructions executed in the innermost.
+    pub fn folded_stacks(&self) -> String {
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        let mut path: Vec<&str> = Vec::new();
+        for frame in &self.frames {
+            path.truncate(frame.depth.saturating_sub(1));
+            path.push(&frame.function_name);
+            *counts.entry(path.join(";")).or_default() += 1;
+        }
+        counts
+            .iter()
+            .map(|(path, count)| format!("{} {}\n", path, count))
+            .collect()
+    }
+
+    /// The call graph of the trace in Graphviz's DOT language: a node per
+    /// function with the instructions executed in it, and an edge from
+    /// each caller to each function it called, with the number of calls
+    /// and the instructions executed in them.
+    pub fn to_dot(&self) -> String {
+        let mut instructions: BTreeMap<&str, u64> = BTreeMap::new();
+        // Calls along each edge, and instructions executed in those calls
+        let mut edges: BTreeMap<(&str, &str), (u64, u64)> = BTreeMap::new();
+        let mut path: Vec<&str> = Vec::new();
+        for frame in &self.frames {
+            let called = frame.depth > path.len();
+            path.truncate(frame.depth.saturating_sub(1));
+            path.push(&frame.function_name);
+            *instructions.entry(&frame.function_name).or_default() += 1;
+            if let [.., caller, callee] = path[..] {
+                let edge = edges.entry((caller, callee)).or_default();
+                edge.0 += u64::from(called);
+                edge.1 += 1;
+            }
+        }
+
+        let count = |n: u64, what: &str| format!("{} {}{}", n, what, if n == 1 { "" } else { "s" });
+        let mut dot = String::from("digraph calls {\n    node [shape=box];\n");
+        for (function, executed) in instructions {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"];\n",
+                function,
+                function,
+                count(executed, "instruction")
+            ));
+        }
+        for ((caller, callee), (calls, executed)) in edges {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\\n{}\"];\n",
+                caller,
+                callee,
+                count(calls, "call"),
+                count(executed, "instruction")
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Write each frame as a JSON object on a line of its own, for tools
+    /// that read a trace as it streams in.
+    pub fn write_ndjson(&self, out: &mut impl Write) -> io::Result<()> {
+        for frame in &self.frames {
+            serde_json::to_writer(&mut *out, frame)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(function_name: &str, ip: usize, depth: usize) -> Location<'_> {
+        Location {
+            function_name,
+            ip,
+            depth,
+            position: None,
+            constants: &[],
+            functions: &[],
+        }
+    }
+
+    #[test]
+    fn test_ndjson_has_a_line_per_frame() {
+        let mut trace = DebugTrace::new();
+        let locals = HashMap::from([("x".to_string(), Value::Number(1.0))]);
+        let location = Location {
+            position: Some((3, 5)),
+            ..at("main", 0, 1)
+        };
+        trace.add_frame(&IRInstruction::Pop, &[Value::Null], &locals, location);
+        trace.add_frame(
+            &IRInstruction::Return(false),
+            &[],
+            &locals,
+            at("main", 1, 1),
+        );
+        let mut out = Vec::new();
+        trace.write_ndjson(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"instruction":"Pop","stack":["Null"],"locals":{"x":"Number(1.0)"},"ip":0,"function_name":"main","depth":1,"line":3,"column":5}"#
+        );
+        assert_eq!(lines.len(), 2);
+        assert!(trace
+            .to_json()
+            .starts_with(r#"{"frames":[{"instruction":"Pop""#));
+    }
+
+    #[test]
+    fn test_options() {
+        let locals = HashMap::new();
+        let record = |options: TraceOptions| {
+            let mut trace = DebugTrace::with_options(options);
+            for (ip, function) in ["main", "fib", "fib", "fib", "main"].iter().enumerate() {
+                let instruction = match ip {
+                    1 => IRInstruction::Call(0, 1),
+                    3 => IRInstruction::Return(true),
+                    _ => IRInstruction::Pop,
+                };
+                trace.add_frame(&instruction, &[], &locals, at(function, ip, 1));
+            }
+            trace
+                .frames
+                .iter()
+                .map(|frame| frame.ip)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(record(TraceOptions::default()), [0, 1, 2, 3, 4]);
+        let functions = Some(vec!["main".to_string()]);
+        assert_eq!(
+            record(TraceOptions {
+                functions,
+                ..Default::default()
+            }),
+            [0, 4]
+        );
+        let calls_only = true;
+        assert_eq!(
+            record(TraceOptions {
+                calls_only,
+                ..Default::default()
+            }),
+            [1, 3]
+        );
+        let sample_every = 2;
+        assert_eq!(
+            record(TraceOptions {
+                sample_every,
+                ..Default::default()
+            }),
+            [0, 2, 4]
+        );
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let breakpoint = Breakpoint::parse("fib if n == 3").unwrap();
+        let condition = Some(("n".to_string(), Value::Number(3.0)));
+        assert_eq!((breakpoint.ip, &breakpoint.condition), (None, &condition));
+        assert_eq!(Breakpoint::parse("fib:4").unwrap().ip, Some(4));
+        assert_eq!(
+            Breakpoint::parse("fib if n").unwrap_err(),
+            "Expected `local == value`, not `n`"
+        );
+        assert_eq!(
+            Breakpoint::parse("fib if n == three").unwrap_err(),
+            "Invalid value `three`"
+        );
+
+        // Breakpoints are kept even where sampling would skip them
+        let options = TraceOptions {
+            breakpoints: vec![breakpoint, Breakpoint::parse("main:2").unwrap()],
+            sample_every: 100,
+            ..Default::default()
+        };
+        let mut trace = DebugTrace::with_options(options);
+        for (function, ip, n) in [
+            ("main", 0, 0.0),
+            ("main", 2, 0.0),
+            ("fib", 0, 4.0),
+            ("fib", 0, 3.0),
+            ("fib", 1, 3.0),
+        ] {
+            let locals = HashMap::from([("n".to_string(), Value::Number(n))]);
+            trace.add_frame(&IRInstruction::Pop, &[], &locals, at(function, ip, 1));
+        }
+        let frames: Vec<(&str, usize)> = trace
+            .frames
+            .iter()
+            .map(|frame| (frame.function_name.as_str(), frame.ip))
+            .collect();
+        assert_eq!(frames, [("main", 0), ("main", 2), ("fib", 0)]);
+        assert_eq!(trace.breakpoints, [1, 2]);
+    }
+
+    #[test]
+    fn test_keeps_the_last_frames() {
+        let last = Some(2);
+        let mut trace = DebugTrace::with_options(TraceOptions {
+            last,
+            ..Default::default()
+        });
+        for ip in 0..5 {
+            trace.add_frame(&IRInstruction::Pop, &[], &HashMap::new(), at("main", ip, 1));
+        }
+        let ips: Vec<usize> = trace.frames.iter().map(|frame| frame.ip).collect();
+        assert_eq!(ips, [3, 4]);
+        assert_eq!(trace.dropped_frames, 3);
+    }
+
+    #[test]
+    fn test_call_graph() {
+        let mut trace = DebugTrace::new();
+        let locals = HashMap::new();
+        let frames = [
+            ("main", 1),
+            ("fib", 2),
+            ("fib", 3),
+            ("fib", 2),
+            ("fib", 3),
+            ("main", 1),
+        ];
+        for (function, depth) in frames {
+            trace.add_frame(&IRInstruction::Pop, &[], &locals, at(function, 0, depth));
+        }
+        assert_eq!(
+            trace.to_dot(),
+            "digraph calls {\n    node [shape=box];\n    \
+             \"fib\" [label=\"fib\\n4 instructions\"];\n    \
+             \"main\" [label=\"main\\n2 instructions\"];\n    \
+             \"fib\" -> \"fib\" [label=\"2 calls\\n2 instructions\"];\n    \
+             \"main\" -> \"fib\" [label=\"1 call\\n2 instructions\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_folded_stacks() {
+        let mut trace = DebugTrace::new();
+        let locals = HashMap::new();
+        for (function, depth) in [
+            ("main", 1),
+            ("fib", 2),
+            ("fib", 3),
+            ("fib", 3),
+            ("fib", 2),
+            ("main", 1),
+        ] {
+            trace.add_frame(&IRInstruction::Pop, &[], &locals, at(function, 0, depth));
+        }
+        assert_eq!(
+            trace.folded_stacks(),
+            "main 2\nmain;fib 2\nmain;fib;fib 2\n"
+        );
     }
 }
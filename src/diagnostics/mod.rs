@@ -0,0 +1,300 @@
+//! Errors and warnings about a JS source, rendered the way rustc does: the
+//! message, the file position, and the offending lines with carets under
+//! the spans they are about.
+//!
+//! The lexer, parser, lowering and VM report errors by raising a
+//! `Diagnostic` as a panic payload, in keeping with the rest of the crate
+//! panicking on bad input. Whoever has the source catches it and renders it
+//! with `render`; `from_panic` also turns other panics into diagnostics.
+
+use std::any::Any;
+use std::fmt;
+
+/// A run of characters on one line of the source. Lines and columns count
+/// from 1; a column of 0 stands for the whole line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    /// Number of characters covered, at least one caret's worth.
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, len: usize) -> Self {
+        Span { line, column, len }
+    }
+
+    /// The whole of `line`, when only the line is known.
+    pub fn line(line: usize) -> Self {
+        Span {
+            line,
+            column: 0,
+            len: 0,
+        }
+    }
+
+    /// Where `word` first stands as a word of its own on `line` of
+    /// `source`, or the whole line if it cannot be found.
+    pub fn word(source: &str, line: usize, word: &str) -> Self {
+        let Some(text) = line.checked_sub(1).and_then(|i| source.lines().nth(i)) else {
+            return Span::line(line);
+        };
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        for (start, _) in text.match_indices(word) {
+            let end = start + word.len();
+            let before = text[..start].chars().next_back();
+            let after = text[end..].chars().next();
+            if !before.is_some_and(is_word) && !after.is_some_and(is_word) {
+                let column = text[..start].chars().count() + 1;
+                return Span::new(line, column, word.chars().count());
+            }
+        }
+        Span::line(line)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn name(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+
+    /// ANSI escape for the severity's color: bold red or bold yellow.
+    fn color(self) -> &'static str {
+        match self {
+            Severity::Error => "\x1b[1;31m",
+            Severity::Warning => "\x1b[1;33m",
+        }
+    }
+}
+
+/// A span with a message shown next to its carets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// Spans to show; the first one gives the position in the header.
+    pub labels: Vec<Label>,
+    /// Lines of explanation after the source.
+    pub notes: Vec<String>,
+}
+
+const BOLD: &str = "\x1b[1m";
+const BLUE: &str = "\x1b[1;34m";
+const RESET: &str = "\x1b[0m";
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            message: message.into(),
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(message)
+        }
+    }
+
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Report the diagnostic by panicking with it as the payload.
+    pub fn raise(self) -> ! {
+        std::panic::panic_any(self)
+    }
+
+    /// The diagnostic a caught panic carries, or an error with the panic's
+    /// message if it was raised some other way.
+    pub fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let payload = match payload.downcast::<Diagnostic>() {
+            Ok(diagnostic) => return *diagnostic,
+            Err(payload) => payload,
+        };
+        let message = payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "unknown error".to_string());
+        Diagnostic::error(message)
+    }
+
+    /// Render the diagnostic for `source`, read from `file_name`, with ANSI
+    /// colors if `color` is set.
+    pub fn render(&self, file_name: &str, source: &str, color: bool) -> String {
+        let paint = |style: &str, text: &str| {
+            if color {
+                format!("{}{}{}", style, text, RESET)
+            } else {
+                text.to_string()
+            }
+        };
+        let severity = self.severity.name();
+        let mut out = format!(
+            "{}{}\n",
+            paint(self.severity.color(), severity),
+            paint(BOLD, &format!(": {}", self.message))
+        );
+
+        let lines: Vec<&str> = source.lines().collect();
+        let width = self
+            .labels
+            .iter()
+            .map(|label| label.span.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(width);
+        if let Some(first) = self.labels.first() {
+            let position = match first.span.column {
+                0 => format!("{}:{}", file_name, first.span.line),
+                column => format!("{}:{}:{}", file_name, first.span.line, column),
+            };
+            out.push_str(&format!("{}{} {}\n", gutter, paint(BLUE, "-->"), position));
+            out.push_str(&format!("{} {}\n", gutter, paint(BLUE, "|")));
+        }
+
+        // Shown top to bottom, whichever gives the position
+        let mut labels: Vec<&Label> = self.labels.iter().collect();
+        labels.sort_by_key(|label| (label.span.line, label.span.column));
+        let mut shown_line = None;
+        for label in labels {
+            let Some(text) = label.span.line.checked_sub(1).and_then(|i| lines.get(i)) else {
+                continue;
+            };
+            if shown_line != Some(label.span.line) {
+                let number = format!("{:>width$}", label.span.line, width = width);
+                out.push_str(&format!(
+                    "{} {} {}\n",
+                    paint(BLUE, &number),
+                    paint(BLUE, "|"),
+                    text
+                ));
+                shown_line = Some(label.span.line);
+            }
+
+            let (start, len) = match label.span.column {
+                // Underline the line without its indentation
+                0 => {
+                    let indent = text.chars().take_while(|c| c.is_whitespace()).count();
+                    (indent, text.chars().count().saturating_sub(indent))
+                }
+                column => (column - 1, label.span.len),
+            };
+            // Keep tabs so the carets line up under them
+            let padding: String = text
+                .chars()
+                .chain(std::iter::repeat(' '))
+                .take(start)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let carets = "^".repeat(len.max(1));
+            let marker = if label.message.is_empty() {
+                carets
+            } else {
+                format!("{} {}", carets, label.message)
+            };
+            out.push_str(&format!(
+                "{} {} {}{}\n",
+                gutter,
+                paint(BLUE, "|"),
+                padding,
+                paint(self.severity.color(), &marker)
+            ));
+        }
+
+        for note in &self.notes {
+            out.push_str(&format!("{} {} note: {}\n", gutter, paint(BLUE, "="), note));
+        }
+        out
+    }
+}
+
+/// The message, prefixed with the position of the first label.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.labels.first().map(|label| label.span) {
+            Some(Span {
+                line, column: 0, ..
+            }) => write!(f, "{}: {}", line, self.message),
+            Some(Span { line, column, .. }) => write!(f, "{}:{}: {}", line, column, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Whether to color diagnostics written to stderr: when it is a terminal
+/// and `NO_COLOR` is not set.
+pub fn stderr_color() -> bool {
+    use std::io::IsTerminal;
+    std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_points_at_the_span() {
+        let source = "function main() {\n\tprint(1) + ;\n}\n";
+        let diagnostic = Diagnostic::error("Unexpected token in expression")
+            .with_label(Span::new(2, 13, 1), "expected an expression")
+            .with_note("operators need an operand on each side");
+        assert_eq!(
+            diagnostic.render("test.js", source, false),
+            "error: Unexpected token in expression\n \
+             --> test.js:2:13\n  \
+             |\n\
+             2 | \tprint(1) + ;\n  \
+             | \t           ^ expected an expression\n  \
+             = note: operators need an operand on each side\n"
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "2:13: Unexpected token in expression"
+        );
+    }
+
+    #[test]
+    fn test_line_spans_underline_the_statement() {
+        let diagnostic = Diagnostic::warning("Unreachable code").with_label(Span::line(1), "");
+        let rendered = diagnostic.render("a.js", "  return 1;", false);
+        assert!(rendered.starts_with("warning: Unreachable code\n --> a.js:1\n"));
+        assert!(rendered.ends_with("1 |   return 1;\n  |   ^^^^^^^^^\n"));
+    }
+
+    #[test]
+    fn test_other_panics_become_errors() {
+        let payload = std::panic::catch_unwind(|| panic!("Function {} not found", "f"));
+        let diagnostic = Diagnostic::from_panic(payload.unwrap_err());
+        assert_eq!(diagnostic, Diagnostic::error("Function f not found"));
+    }
+}
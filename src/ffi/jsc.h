@@ -0,0 +1,64 @@
+/* Embedding the JS compiler and VM from C; see src/ffi/mod.rs.
+ *
+ * Link with the cdylib, libjs_compiler. Modules, VMs and values are
+ * opaque, each freed by its own jsc_*_free. A function that fails returns
+ * NULL, and jsc_last_error then gives the error for the thread it failed
+ * on, which stays valid until the next failing call there. */
+
+#ifndef JSC_H
+#define JSC_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct JscModule JscModule;
+typedef struct JscVm JscVm;
+typedef struct JscValue JscValue;
+
+typedef enum {
+    JSC_UNDEFINED = 0,
+    JSC_NULL = 1,
+    JSC_NUMBER = 2,
+    JSC_STRING = 3,
+    JSC_BOOLEAN = 4,
+    /* Objects and closures */
+    JSC_OTHER = 5,
+} JscType;
+
+const char *jsc_last_error(void);
+
+/* Compile JS source, optimizing at opt_level 0 to 2 */
+JscModule *jsc_compile(const char *source, int opt_level);
+void jsc_module_free(JscModule *module);
+
+/* Takes ownership of the module; the result is NULL if it is NULL */
+JscVm *jsc_vm_new(JscModule *module);
+void jsc_vm_free(JscVm *vm);
+
+/* The arguments stay the caller's; the result is NULL on an error */
+JscValue *jsc_call(JscVm *vm, const char *name, const JscValue *const *args, size_t argc);
+
+JscValue *jsc_value_undefined(void);
+JscValue *jsc_value_null(void);
+JscValue *jsc_value_number(double n);
+JscValue *jsc_value_boolean(bool b);
+JscValue *jsc_value_string(const char *s);
+JscType jsc_value_type(const JscValue *value);
+/* NaN if not a number */
+double jsc_value_as_number(const JscValue *value);
+/* false if not a boolean */
+bool jsc_value_as_boolean(const JscValue *value);
+/* A copy to free with jsc_string_free, or NULL if not a string */
+char *jsc_value_as_string(const JscValue *value);
+void jsc_value_free(JscValue *value);
+void jsc_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
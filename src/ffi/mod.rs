@@ -0,0 +1,393 @@
+//! A C interface for embedding the compiler and the VM, exported from the
+//! `cdylib`; `jsc.h` declares it. Modules, VMs and values are opaque
+//! pointers, each freed by its own `jsc_*_free`. A function that fails
+//! returns NULL, and `jsc_last_error` then gives the diagnostic, rendered
+//! against the source, for the thread it failed on.
+
+use crate::diagnostics::Diagnostic;
+use crate::ir::{self, IRModule};
+use crate::optimizer::{self, OptLevel};
+use crate::vm::{Value, VM};
+use crate::{lexer, parser};
+use std::cell::{Cell, RefCell};
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::Once;
+
+/// A compiled module, from `jsc_compile`.
+pub struct JscModule {
+    module: IRModule,
+    /// The JS it was compiled from, which errors are rendered against.
+    source: String,
+}
+
+/// A VM running a module, from `jsc_vm_new`.
+pub struct JscVm {
+    vm: VM,
+    source: String,
+}
+
+/// A value passed to or returned from a JS function.
+pub struct JscValue(Value);
+
+/// Type of a value, from `jsc_value_type`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JscType {
+    Undefined = 0,
+    Null = 1,
+    Number = 2,
+    String = 3,
+    Boolean = 4,
+    /// Objects and closures, which C only sees through their type.
+    Other = 5,
+}
+
+thread_local! {
+    /// The rendered diagnostic of the last call that failed on the thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+    /// Whether the thread is inside `catch`, whose diagnostics the panic
+    /// hook leaves unprinted.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f`, catching the error it raises: the error is kept for
+/// `jsc_last_error`, rendered against `source`, and `None` returned.
+fn catch<T>(source: &str, f: impl FnOnce() -> T) -> Option<T> {
+    // Diagnostics raised here are the embedder's to report, through
+    // `jsc_last_error`; other panics, and any outside `catch`, go to the
+    // hook the embedder had
+    static QUIET: Once = Once::new();
+    QUIET.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !(CATCHING.get() && info.payload().is::<Diagnostic>()) {
+                previous_hook(info);
+            }
+        }));
+    });
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    match result {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            let message = Diagnostic::from_panic(payload).render("<source>", source, false);
+            set_error(message);
+            None
+        }
+    }
+}
+
+/// Whether the thread is inside `catch`. `parallel` sets it on its
+/// workers, so diagnostics raised there stay unprinted as well.
+pub(crate) fn catching() -> bool {
+    CATCHING.get()
+}
+
+pub(crate) fn set_catching(catching: bool) {
+    CATCHING.set(catching);
+}
+
+fn set_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap();
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+}
+
+/// The string `s` points to, or an error kept for `jsc_last_error` if it
+/// is NULL or not UTF-8.
+///
+/// # Safety
+///
+/// `s` must be NULL or point to a NUL-terminated string.
+unsafe fn string_arg(s: *const c_char, what: &str) -> Option<String> {
+    if s.is_null() {
+        set_error(format!("error: {} is NULL", what));
+        return None;
+    }
+    match CStr::from_ptr(s).to_str() {
+        Ok(s) => Some(s.to_string()),
+        Err(_) => {
+            set_error(format!("error: {} is not UTF-8", what));
+            None
+        }
+    }
+}
+
+fn into_ptr<T>(value: Option<T>) -> *mut T {
+    value.map_or(ptr::null_mut(), |value| Box::into_raw(Box::new(value)))
+}
+
+/// The error of the last call that failed on this thread, or NULL. The
+/// string stays valid until the next failing call on the thread.
+#[no_mangle]
+pub extern "C" fn jsc_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// Compile the JS in `source` and optimize it at `opt_level`, 0 to 2.
+///
+/// # Safety
+///
+/// `source` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_compile(source: *const c_char, opt_level: c_int) -> *mut JscModule {
+    let Some(source) = string_arg(source, "source") else {
+        return ptr::null_mut();
+    };
+    let level = match opt_level {
+        0 => OptLevel::O0,
+        1 => OptLevel::O1,
+        2 => OptLevel::O2,
+        _ => {
+            set_error(format!("error: no optimization level {}", opt_level));
+            return ptr::null_mut();
+        }
+    };
+    let module = catch(&source, || {
+        let module = ir::lower_ast(parser::parse(lexer::tokenize(&source)));
+        optimizer::optimize_with(module, level)
+    });
+    into_ptr(module.map(|module| JscModule { module, source }))
+}
+
+/// # Safety
+///
+/// `module` must be NULL or come from `jsc_compile`, and not be used after.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_module_free(module: *mut JscModule) {
+    if !module.is_null() {
+        drop(Box::from_raw(module));
+    }
+}
+
+/// A VM running `module`, which it takes ownership of.
+///
+/// # Safety
+///
+/// `module` must be NULL or come from `jsc_compile`, and not be used
+/// after.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_vm_new(module: *mut JscModule) -> *mut JscVm {
+    if module.is_null() {
+        set_error("error: module is NULL".to_string());
+        return ptr::null_mut();
+    }
+    let JscModule { module, source } = *Box::from_raw(module);
+    let vm = catch(&source, || VM::new(module));
+    into_ptr(vm.map(|vm| JscVm { vm, source }))
+}
+
+/// # Safety
+///
+/// `vm` must be NULL or come from `jsc_vm_new`, and not be used after.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_vm_free(vm: *mut JscVm) {
+    if !vm.is_null() {
+        drop(Box::from_raw(vm));
+    }
+}
+
+/// Call the function `name` with the `argc` values in `args`, which stay
+/// the caller's, returning its result, or NULL if it raised an error.
+///
+/// # Safety
+///
+/// `vm` must come from `jsc_vm_new`, `name` must be NULL or point to a
+/// NUL-terminated string, and `args` must point to `argc` values.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_call(
+    vm: *mut JscVm,
+    name: *const c_char,
+    args: *const *const JscValue,
+    argc: usize,
+) -> *mut JscValue {
+    let vm = &mut *vm;
+    let Some(name) = string_arg(name, "function name") else {
+        return ptr::null_mut();
+    };
+    let args: Vec<Value> = match argc {
+        0 => Vec::new(),
+        _ => std::slice::from_raw_parts(args, argc)
+            .iter()
+            .map(|&arg| (*arg).0.clone())
+            .collect(),
+    };
+    let result = catch(&vm.source, || vm.vm.execute_function(&name, args));
+    if result.is_none() {
+        vm.vm.reset_stack();
+    }
+    into_ptr(result.map(JscValue))
+}
+
+#[no_mangle]
+pub extern "C" fn jsc_value_undefined() -> *mut JscValue {
+    into_ptr(Some(JscValue(Value::Undefined)))
+}
+
+#[no_mangle]
+pub extern "C" fn jsc_value_null() -> *mut JscValue {
+    into_ptr(Some(JscValue(Value::Null)))
+}
+
+#[no_mangle]
+pub extern "C" fn jsc_value_number(n: f64) -> *mut JscValue {
+    into_ptr(Some(JscValue(Value::Number(n))))
+}
+
+#[no_mangle]
+pub extern "C" fn jsc_value_boolean(b: bool) -> *mut JscValue {
+    into_ptr(Some(JscValue(Value::Boolean(b))))
+}
+
+/// A string value with a copy of `s`.
+///
+/// # Safety
+///
+/// `s` must be NULL or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_value_string(s: *const c_char) -> *mut JscValue {
+    into_ptr(string_arg(s, "string").map(|s| JscValue(Value::String(s.into()))))
+}
+
+/// # Safety
+///
+/// `value` must come from a `jsc_value_*` constructor or `jsc_call`.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_value_type(value: *const JscValue) -> JscType {
+    match (*value).0 {
+        Value::Undefined => JscType::Undefined,
+        Value::Null => JscType::Null,
+        Value::Number(_) => JscType::Number,
+        Value::String(_) => JscType::String,
+        Value::Boolean(_) => JscType::Boolean,
+        Value::Object(_) | Value::Closure { .. } => JscType::Other,
+    }
+}
+
+/// The number, or NaN if the value is not one.
+///
+/// # Safety
+///
+/// `value` must come from a `jsc_value_*` constructor or `jsc_call`.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_value_as_number(value: *const JscValue) -> f64 {
+    match (*value).0 {
+        Value::Number(n) => n,
+        _ => f64::NAN,
+    }
+}
+
+/// The boolean, or false if the value is not one.
+///
+/// # Safety
+///
+/// `value` must come from a `jsc_value_*` constructor or `jsc_call`.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_value_as_boolean(value: *const JscValue) -> bool {
+    matches!((*value).0, Value::Boolean(true))
+}
+
+/// A copy of the string, to free with `jsc_string_free`, or NULL if the
+/// value is not one. NUL characters in it end the copy early.
+///
+/// # Safety
+///
+/// `value` must come from a `jsc_value_*` constructor or `jsc_call`.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_value_as_string(value: *const JscValue) -> *mut c_char {
+    match &(*value).0 {
+        Value::String(s) => {
+            let s = s.split('\0').next().unwrap_or_default();
+            CString::new(s).unwrap().into_raw()
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+/// # Safety
+///
+/// `value` must be NULL or come from a `jsc_value_*` constructor or
+/// `jsc_call`, and not be used after.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_value_free(value: *mut JscValue) {
+    if !value.is_null() {
+        drop(Box::from_raw(value));
+    }
+}
+
+/// # Safety
+///
+/// `s` must be NULL or come from `jsc_value_as_string`, and not be used
+/// after.
+#[no_mangle]
+pub unsafe extern "C" fn jsc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(jsc_last_error()) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_compile_and_call() {
+        let source = c"function greet(name) { return \"hi \" + name; }\n\
+                       function twice(x) { return x * 2; }";
+        unsafe {
+            let module = jsc_compile(source.as_ptr(), 2);
+            assert!(!module.is_null());
+            let vm = jsc_vm_new(module);
+
+            let x = jsc_value_number(21.0);
+            let result = jsc_call(vm, c"twice".as_ptr(), &(x as *const JscValue), 1);
+            assert_eq!(jsc_value_type(result), JscType::Number);
+            assert_eq!(jsc_value_as_number(result), 42.0);
+            jsc_value_free(result);
+            jsc_value_free(x);
+
+            let name = jsc_value_string(c"C".as_ptr());
+            let result = jsc_call(vm, c"greet".as_ptr(), &(name as *const JscValue), 1);
+            let s = jsc_value_as_string(result);
+            assert_eq!(CStr::from_ptr(s).to_str().unwrap(), "hi C");
+            jsc_string_free(s);
+            jsc_value_free(result);
+            jsc_value_free(name);
+
+            // The VM can go on after an error
+            let result = jsc_call(vm, c"missing".as_ptr(), ptr::null(), 0);
+            assert!(result.is_null());
+            assert!(last_error().contains("Function missing not found"));
+            let result = jsc_call(vm, c"greet".as_ptr(), ptr::null(), 0);
+            assert_eq!(jsc_value_type(result), JscType::String);
+            jsc_value_free(result);
+            jsc_vm_free(vm);
+        }
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        unsafe {
+            let module = jsc_compile(c"function f( {".as_ptr(), 0);
+            assert!(module.is_null());
+            assert!(last_error().starts_with("error: "));
+            assert!(last_error().contains("<source>:1:"));
+
+            assert!(jsc_compile(c"".as_ptr(), 3).is_null());
+            assert_eq!(last_error(), "error: no optimization level 3");
+            assert!(jsc_compile(ptr::null(), 0).is_null());
+            assert_eq!(last_error(), "error: source is NULL");
+            assert!(jsc_vm_new(ptr::null_mut()).is_null());
+            assert_eq!(last_error(), "error: module is NULL");
+        }
+    }
+}
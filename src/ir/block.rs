@@ -0,0 +1,298 @@
+//! Functions hold their code as basic blocks: straight-line instructions
+//! ending in an explicit terminator, which names the blocks control goes to
+//! by index. The linear form, with `Label`, `Jump`, `JumpIf` and `Return`
+//! instructions, is what the text and binary formats, the VM and the
+//! native backends read; `instructions` and `set_instructions` convert.
+
+use super::{IRFunction, IRInstruction, VerifyError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Index of a block in its function's `blocks`.
+pub type BlockId = usize;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicBlock {
+    /// Name of the block in the linear form; blocks that were not jumped
+    /// to there have none.
+    pub label: Option<String>,
+    /// Instructions other than labels, jumps and returns.
+    pub instructions: Vec<IRInstruction>,
+    pub terminator: Terminator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Terminator {
+    /// Continue with the next block; after the last one, control falls off
+    /// the end of the function.
+    Fallthrough,
+    Jump(BlockId),
+    /// Pop a value and continue with the block if it is truthy, and with the
+    /// next block otherwise.
+    JumpIf(BlockId),
+    /// Return, with the value popped if `true`.
+    Return(bool),
+}
+
+impl Terminator {
+    /// Number of values it pops.
+    pub fn pops(&self) -> usize {
+        match self {
+            Terminator::JumpIf(_) | Terminator::Return(true) => 1,
+            Terminator::Fallthrough | Terminator::Jump(_) | Terminator::Return(false) => 0,
+        }
+    }
+}
+
+impl BasicBlock {
+    /// Operand stack depth on leaving the block, entered at `depth`.
+    pub fn exit_depth(&self, depth: usize) -> usize {
+        let depth = self.instructions.iter().fold(depth, |depth, inst| {
+            let (pops, pushes) = inst.stack_effect();
+            depth - pops + pushes
+        });
+        depth - self.terminator.pops()
+    }
+}
+
+impl IRFunction {
+    /// An empty function.
+    pub fn new(name: String, params: Vec<String>) -> Self {
+        IRFunction {
+            name,
+            params,
+            max_stack: 0,
+            max_locals: 0,
+            blocks: Vec::new(),
+            exception_table: Vec::new(),
+            captures: Vec::new(),
+            export: false,
+        }
+    }
+
+    /// Blocks control can go to from block `id`, the fallthrough first.
+    pub fn successors(&self, id: BlockId) -> Vec<BlockId> {
+        let next = (id + 1 < self.blocks.len()).then_some(id + 1);
+        match self.blocks[id].terminator {
+            Terminator::Fallthrough => next.into_iter().collect(),
+            Terminator::Jump(target) => vec![target],
+            Terminator::JumpIf(target) => {
+                let mut targets: Vec<BlockId> = next.into_iter().collect();
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+                targets
+            }
+            Terminator::Return(_) => Vec::new(),
+        }
+    }
+
+    /// The instructions of all the blocks, without their terminators, in
+    /// order.
+    pub fn block_instructions(&self) -> impl Iterator<Item = &IRInstruction> {
+        self.blocks.iter().flat_map(|block| &block.instructions)
+    }
+
+    /// The block with the label `label`.
+    pub fn block_named(&self, label: &str) -> Option<BlockId> {
+        self.blocks
+            .iter()
+            .position(|block| block.label.as_deref() == Some(label))
+    }
+
+    /// Name of block `id` in the linear form.
+    pub fn block_label(&self, id: BlockId) -> String {
+        match &self.blocks[id].label {
+            Some(label) => label.clone(),
+            None => format!("B{}", id),
+        }
+    }
+
+    /// The instruction that ends block `id` in the linear form, if any.
+    pub fn terminator_instruction(&self, id: BlockId) -> Option<IRInstruction> {
+        match self.blocks[id].terminator {
+            Terminator::Fallthrough => None,
+            Terminator::Jump(target) => Some(IRInstruction::Jump(self.block_label(target))),
+            Terminator::JumpIf(target) => Some(IRInstruction::JumpIf(self.block_label(target))),
+            Terminator::Return(value) => Some(IRInstruction::Return(value)),
+        }
+    }
+
+    /// Index in the linear form of the first instruction of each block, and
+    /// then the length of the linear form.
+    pub fn block_starts(&self) -> Vec<usize> {
+        let targets = self.jump_targets();
+        let mut starts = Vec::with_capacity(self.blocks.len() + 1);
+        let mut start = 0;
+        for (id, block) in self.blocks.iter().enumerate() {
+            starts.push(start);
+            let labelled = block.label.is_some() || targets.contains(&id);
+            start += labelled as usize
+                + block.instructions.len()
+                + (block.terminator != Terminator::Fallthrough) as usize;
+        }
+        starts.push(start);
+        starts
+    }
+
+    /// Number of instructions in the linear form, labels included.
+    pub fn instruction_count(&self) -> usize {
+        self.block_starts().last().copied().unwrap_or(0)
+    }
+
+    fn jump_targets(&self) -> HashSet<BlockId> {
+        self.blocks
+            .iter()
+            .filter_map(|block| match block.terminator {
+                Terminator::Jump(target) | Terminator::JumpIf(target) => Some(target),
+                Terminator::Fallthrough | Terminator::Return(_) => None,
+            })
+            .collect()
+    }
+
+    /// The function's code in the linear form, with a `Label` starting
+    /// each block that has one or is jumped to.
+    pub fn instructions(&self) -> Vec<IRInstruction> {
+        let targets = self.jump_targets();
+        let mut instructions = Vec::new();
+        for (id, block) in self.blocks.iter().enumerate() {
+            if block.label.is_some() || targets.contains(&id) {
+                instructions.push(IRInstruction::Label(self.block_label(id)));
+            }
+            instructions.extend(block.instructions.iter().cloned());
+            instructions.extend(self.terminator_instruction(id));
+        }
+        instructions
+    }
+
+    /// Replace the function's code with `instructions` in the linear form,
+    /// split into blocks at each label and after each jump and return.
+    /// `instructions()` gives them back unchanged.
+    pub fn set_instructions(
+        &mut self,
+        instructions: Vec<IRInstruction>,
+    ) -> Result<(), VerifyError> {
+        // A label starts a block, and so does whatever follows a terminator
+        let mut labels: HashMap<String, BlockId> = HashMap::new();
+        let mut count = 0;
+        let mut open = false;
+        for instruction in &instructions {
+            match instruction {
+                IRInstruction::Label(label) => {
+                    if labels.insert(label.clone(), count).is_some() {
+                        return Err(VerifyError::DuplicateLabel {
+                            function: self.name.clone(),
+                            label: label.clone(),
+                        });
+                    }
+                    count += 1;
+                    open = true;
+                }
+                IRInstruction::Jump(_) | IRInstruction::JumpIf(_) | IRInstruction::Return(_) => {
+                    count += !open as usize;
+                    open = false;
+                }
+                _ => {
+                    count += !open as usize;
+                    open = true;
+                }
+            }
+        }
+
+        let target = |label: &String| {
+            labels
+                .get(label)
+                .copied()
+                .ok_or_else(|| VerifyError::UndefinedLabel {
+                    function: self.name.clone(),
+                    label: label.clone(),
+                })
+        };
+        let mut blocks: Vec<BasicBlock> = Vec::with_capacity(count);
+        let mut open = false;
+        for instruction in instructions {
+            if !open || matches!(instruction, IRInstruction::Label(_)) {
+                blocks.push(BasicBlock {
+                    label: None,
+                    instructions: Vec::new(),
+                    terminator: Terminator::Fallthrough,
+                });
+            }
+            let block = blocks.last_mut().unwrap();
+            open = true;
+            match instruction {
+                IRInstruction::Label(label) => block.label = Some(label),
+                IRInstruction::Jump(label) => {
+                    block.terminator = Terminator::Jump(target(&label)?);
+                    open = false;
+                }
+                IRInstruction::JumpIf(label) => {
+                    block.terminator = Terminator::JumpIf(target(&label)?);
+                    open = false;
+                }
+                IRInstruction::Return(value) => {
+                    block.terminator = Terminator::Return(value);
+                    open = false;
+                }
+                instruction => block.instructions.push(instruction),
+            }
+        }
+        self.blocks = blocks;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::lower_ast;
+    use crate::{lexer, parser};
+
+    #[test]
+    fn test_blocks_round_trip_to_linear_form() {
+        let source = "function f(x) {\n\
+                      while (x) { if (x > 1) { return 1; } let x = x - 1; }\n\
+                      return 0;\n\
+                      }";
+        let module = lower_ast(parser::parse(lexer::tokenize(source)));
+        let function = &module.functions[0];
+        let instructions = function.instructions();
+
+        let mut copy = IRFunction::new("g".to_string(), Vec::new());
+        copy.set_instructions(instructions.clone()).unwrap();
+        assert_eq!(
+            format!("{:?}", copy.instructions()),
+            format!("{:?}", instructions)
+        );
+        let starts = function.block_starts();
+        assert_eq!(starts.len(), function.blocks.len() + 1);
+        assert_eq!(*starts.last().unwrap(), instructions.len());
+
+        // The loop's condition block is jumped back to from the end of its body
+        let block_named = |label: &str| function.block_named(label).unwrap();
+        let head = block_named("L1");
+        assert!((0..function.blocks.len())
+            .any(|id| id > head && function.blocks[id].terminator == Terminator::Jump(head)));
+        assert_eq!(function.successors(head), [head + 1, block_named("L2")]);
+    }
+
+    #[test]
+    fn test_set_instructions_rejects_bad_labels() {
+        let mut function = IRFunction::new("f".to_string(), Vec::new());
+        let error = function
+            .set_instructions(vec![IRInstruction::Jump("missing".to_string())])
+            .unwrap_err();
+        assert_eq!(
+            error,
+            VerifyError::UndefinedLabel {
+                function: "f".to_string(),
+                label: "missing".to_string(),
+            }
+        );
+        let label = IRInstruction::Label("L1".to_string());
+        let error = function
+            .set_instructions(vec![label.clone(), label])
+            .unwrap_err();
+        assert!(matches!(error, VerifyError::DuplicateLabel { .. }));
+    }
+}
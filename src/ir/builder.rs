@@ -0,0 +1,335 @@
+//! Building modules from Rust, for tests, DSLs and frontends other than
+//! the JS parser. `IRModule::builder()` starts a module, `function` starts
+//! each of its functions, and `build` checks the result with `verify`:
+//!
+//! ```
+//! use js_compiler::ir::{BinaryOp, IRModule};
+//!
+//! let mut module = IRModule::builder();
+//! let mut function = module.function("max", &["a", "b"]);
+//! let second = function.new_label();
+//! function.load("b").load("a").binary(BinaryOp::Gt).jump_if(second);
+//! function.load("a").return_value();
+//! function.bind(second).load("b").return_value();
+//! function.finish();
+//! let module = module.build().unwrap();
+//! assert_eq!(module.functions[0].name, "max");
+//! ```
+//!
+//! Calls name their callee, which can be defined after the caller; `build`
+//! turns those to the module's functions into calls by index, as lowering
+//! does.
+
+use super::{
+    add_constant, text::count_locals, verify, BinaryOp, Constant, ExceptionHandler, ExternFunction,
+    FuncId, IRFunction, IRInstruction, IRModule, UnaryOp, VerifyError,
+};
+
+/// A module being built; see the module documentation.
+pub struct ModuleBuilder {
+    module: IRModule,
+    /// Errors found when finishing functions, which `build` reports.
+    errors: Vec<VerifyError>,
+}
+
+/// A jump target in a function being built, made by `new_label` and
+/// placed by `bind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Label(usize);
+
+/// A function being built, added to its module by `finish`. The emit
+/// methods return the builder, so that they chain.
+pub struct FunctionBuilder<'a> {
+    module: &'a mut ModuleBuilder,
+    function: IRFunction,
+    /// The function's code in the linear form.
+    instructions: Vec<IRInstruction>,
+    /// Whether each label has been bound.
+    bound: Vec<bool>,
+}
+
+impl IRModule {
+    /// Start building a module from Rust.
+    pub fn builder() -> ModuleBuilder {
+        ModuleBuilder {
+            module: IRModule::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl ModuleBuilder {
+    /// Declare a C function, which calls to its name call natively.
+    pub fn extern_function(&mut self, function: ExternFunction) -> &mut Self {
+        self.module.externs.push(function);
+        self
+    }
+
+    /// Start a function taking `params`.
+    pub fn function(&mut self, name: &str, params: &[&str]) -> FunctionBuilder<'_> {
+        let params = params.iter().map(|param| param.to_string()).collect();
+        FunctionBuilder {
+            module: self,
+            function: IRFunction::new(name.to_string(), params),
+            instructions: Vec::new(),
+            bound: Vec::new(),
+        }
+    }
+
+    /// The module, with its calls resolved, if it passes `verify`.
+    pub fn build(mut self) -> Result<IRModule, Vec<VerifyError>> {
+        if !self.errors.is_empty() {
+            return Err(self.errors);
+        }
+        self.module.resolve_calls();
+        verify(&self.module)?;
+        Ok(self.module)
+    }
+}
+
+impl FunctionBuilder<'_> {
+    /// Append any instruction; the typed methods below are the usual way.
+    pub fn emit(&mut self, instruction: IRInstruction) -> &mut Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// A label for `jump` and `jump_if` to go to, before or after `bind`
+    /// places it.
+    pub fn new_label(&mut self) -> Label {
+        self.bound.push(false);
+        Label(self.bound.len() - 1)
+    }
+
+    /// Place `label` before the next instruction. A label is bound once.
+    pub fn bind(&mut self, label: Label) -> &mut Self {
+        assert!(
+            !self.bound[label.0],
+            "label {} bound twice",
+            Self::name(label)
+        );
+        self.bound[label.0] = true;
+        self.emit(IRInstruction::Label(Self::name(label)))
+    }
+
+    fn name(label: Label) -> String {
+        format!("L{}", label.0 + 1)
+    }
+
+    pub fn push(&mut self, constant: Constant) -> &mut Self {
+        let index = add_constant(&mut self.module.module.constants, constant);
+        self.emit(IRInstruction::PushConst(index))
+    }
+
+    pub fn push_number(&mut self, value: f64) -> &mut Self {
+        self.push(Constant::Number(value))
+    }
+
+    pub fn push_string(&mut self, value: &str) -> &mut Self {
+        self.push(Constant::String(value.to_string()))
+    }
+
+    pub fn push_bool(&mut self, value: bool) -> &mut Self {
+        self.push(Constant::Boolean(value))
+    }
+
+    pub fn push_null(&mut self) -> &mut Self {
+        self.push(Constant::Null)
+    }
+
+    pub fn pop(&mut self) -> &mut Self {
+        self.emit(IRInstruction::Pop)
+    }
+
+    pub fn dup(&mut self) -> &mut Self {
+        self.emit(IRInstruction::Dup)
+    }
+
+    pub fn load(&mut self, name: &str) -> &mut Self {
+        self.emit(IRInstruction::Load(name.to_string()))
+    }
+
+    pub fn store(&mut self, name: &str) -> &mut Self {
+        self.emit(IRInstruction::Store(name.to_string()))
+    }
+
+    /// Push the value of the capture named `name`, adding it to the
+    /// function's captures if it is not there yet.
+    pub fn load_capture(&mut self, name: &str) -> &mut Self {
+        let index = self.capture(name);
+        self.emit(IRInstruction::LoadUpvalue(index))
+    }
+
+    /// Pop a value into the capture named `name`, adding it to the
+    /// function's captures if it is not there yet.
+    pub fn store_capture(&mut self, name: &str) -> &mut Self {
+        let index = self.capture(name);
+        self.emit(IRInstruction::StoreUpvalue(index))
+    }
+
+    fn capture(&mut self, name: &str) -> u16 {
+        let captures = &mut self.function.captures;
+        let index = match captures.iter().position(|capture| capture == name) {
+            Some(index) => index,
+            None => {
+                captures.push(name.to_string());
+                captures.len() - 1
+            }
+        };
+        index as u16
+    }
+
+    /// Pop the values of the `count` captures of `function` and push a
+    /// closure made of it.
+    pub fn make_closure(&mut self, function: &str, count: u16) -> &mut Self {
+        self.emit(IRInstruction::MakeClosure(function.to_string(), count))
+    }
+
+    pub fn binary(&mut self, op: BinaryOp) -> &mut Self {
+        self.emit(IRInstruction::Binary(op))
+    }
+
+    pub fn unary(&mut self, op: UnaryOp) -> &mut Self {
+        self.emit(IRInstruction::Unary(op))
+    }
+
+    pub fn jump(&mut self, label: Label) -> &mut Self {
+        self.emit(IRInstruction::Jump(Self::name(label)))
+    }
+
+    /// Pop a value and go to `label` if it is truthy.
+    pub fn jump_if(&mut self, label: Label) -> &mut Self {
+        self.emit(IRInstruction::JumpIf(Self::name(label)))
+    }
+
+    /// Pop `argc` arguments and call `name` with them, pushing the result.
+    pub fn call(&mut self, name: &str, argc: u16) -> &mut Self {
+        self.emit(IRInstruction::CallNative(name.to_string(), argc))
+    }
+
+    /// Call the module's function `id`, as `finish` returned it.
+    pub fn call_id(&mut self, id: FuncId, argc: u16) -> &mut Self {
+        self.emit(IRInstruction::Call(id, argc))
+    }
+
+    /// Pop a value and return it.
+    pub fn return_value(&mut self) -> &mut Self {
+        self.emit(IRInstruction::Return(true))
+    }
+
+    /// Return undefined.
+    pub fn return_undefined(&mut self) -> &mut Self {
+        self.emit(IRInstruction::Return(false))
+    }
+
+    /// Mark the instructions that follow as coming from a source line and
+    /// column, for errors and debug info.
+    pub fn line(&mut self, line: usize, column: usize) -> &mut Self {
+        self.emit(IRInstruction::Line(line, column))
+    }
+
+    /// Have errors of `exception_type` raised from `start` up to `end` go
+    /// to `handler`, with their message as the only operand.
+    pub fn handler(
+        &mut self,
+        start: Label,
+        end: Label,
+        handler: Label,
+        exception_type: &str,
+    ) -> &mut Self {
+        self.function.exception_table.push(ExceptionHandler {
+            start_label: Self::name(start),
+            end_label: Self::name(end),
+            handler_label: Self::name(handler),
+            exception_type: exception_type.to_string(),
+        });
+        self
+    }
+
+    /// Let hosts call the function by its name.
+    pub fn export(&mut self) -> &mut Self {
+        self.function.export = true;
+        self
+    }
+
+    /// Add the function to the module, returning its index. Code that
+    /// does not end in a return returns undefined.
+    pub fn finish(mut self) -> FuncId {
+        if !matches!(self.instructions.last(), Some(IRInstruction::Return(_))) {
+            self.return_undefined();
+        }
+        if let Err(error) = self.function.set_instructions(self.instructions) {
+            self.module.errors.push(error);
+        }
+        self.function.max_locals = count_locals(&self.function);
+        let functions = &mut self.module.module.functions;
+        functions.push(self.function);
+        (functions.len() - 1) as FuncId
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{Value, VM};
+
+    #[test]
+    fn test_built_module_runs() {
+        let mut module = IRModule::builder();
+        let mut main = module.function("main", &[]);
+        main.push_number(5.0).call("count", 1).return_value();
+        main.finish();
+
+        // Counts n down to zero in a loop, and returns how many times it went round
+        let mut count = module.function("count", &["n"]);
+        let (head, done) = (count.new_label(), count.new_label());
+        count.push_number(0.0).store("total");
+        count
+            .bind(head)
+            .load("n")
+            .push_number(0.0)
+            .binary(BinaryOp::Gt);
+        count.unary(UnaryOp::Not).jump_if(done);
+        count
+            .load("n")
+            .push_number(1.0)
+            .binary(BinaryOp::Sub)
+            .store("n");
+        count
+            .load("total")
+            .push_number(1.0)
+            .binary(BinaryOp::Add)
+            .store("total");
+        count.jump(head);
+        count.bind(done).load("total").return_value();
+        let id = count.finish();
+        assert_eq!(id, 1);
+
+        let module = module.build().unwrap();
+        assert!(matches!(
+            module.functions[0].blocks[0].instructions[1],
+            IRInstruction::Call(1, 1)
+        ));
+        assert_eq!(module.functions[1].max_locals, 2);
+        let mut vm = VM::new(module);
+        assert_eq!(vm.execute_function("main", vec![]), Value::Number(5.0));
+    }
+
+    #[test]
+    fn test_build_verifies() {
+        let mut module = IRModule::builder();
+        let mut function = module.function("f", &[]);
+        let nowhere = function.new_label();
+        function.jump(nowhere);
+        function.finish();
+        let errors = module.build().unwrap_err();
+        assert!(matches!(errors[..], [VerifyError::UndefinedLabel { .. }]));
+
+        let mut module = IRModule::builder();
+        let mut function = module.function("g", &[]);
+        function.binary(BinaryOp::Add);
+        function.finish();
+        let errors = module.build().unwrap_err();
+        assert!(matches!(errors[..], [VerifyError::StackUnderflow { .. }]));
+    }
+}
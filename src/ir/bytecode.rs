@@ -0,0 +1,87 @@
+//! `.jsbc` files: an IR module saved after optimization, to be loaded
+//! again without the source. The file is a `JSBC <version>` line followed
+//! by the module as JSON.
+
+use super::IRModule;
+
+/// Version written in the header; bumped whenever the IR changes shape.
+pub const BYTECODE_VERSION: u32 = 7;
+
+/// File extension of saved modules.
+pub const BYTECODE_EXTENSION: &str = "jsbc";
+
+impl IRModule {
+    pub fn to_bytecode(&self) -> String {
+        format!(
+            "JSBC {}\n{}\n",
+            BYTECODE_VERSION,
+            serde_json::to_string(self).expect("IR modules serialize")
+        )
+    }
+
+    /// Load a module written by `to_bytecode`.
+    pub fn from_bytecode(text: &str) -> Result<IRModule, String> {
+        let (header, body) = text.split_once('\n').unwrap_or((text, ""));
+        let version = header
+            .strip_prefix("JSBC ")
+            .ok_or("Not a .jsbc file")?
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid .jsbc header `{}`", header))?;
+        if version != BYTECODE_VERSION {
+            return Err(format!(
+                "Unsupported .jsbc version {} (expected {})",
+                version, BYTECODE_VERSION
+            ));
+        }
+        serde_json::from_str(body).map_err(|e| format!("Corrupt .jsbc file: {}", e))
+    }
+}
+
+/// Numbers as their shortest text, which unlike JSON numbers also covers
+/// the infinities and NaN that constant folding can produce.
+pub(super) mod number_text {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(n: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&n.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse()
+            .map_err(|_| de::Error::custom(format!("invalid number `{}`", text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ir::{lower_ast, Constant, IRInstruction, IRModule};
+    use crate::{lexer, parser};
+
+    #[test]
+    fn test_bytecode_round_trip() {
+        let source = "extern function puts(string): int;\n\
+                      function main() { puts(\"hi\"); return 1 / 0; }";
+        let mut module = lower_ast(parser::parse(lexer::tokenize(source)));
+        let nan = module.add_constant(Constant::Number(f64::NAN));
+        let block = module.functions[0].blocks.last_mut().unwrap();
+        block.instructions.push(IRInstruction::PushConst(nan));
+
+        let text = module.to_bytecode();
+        assert!(text.starts_with("JSBC 7\n"));
+        let loaded = IRModule::from_bytecode(&text).unwrap();
+        assert_eq!(loaded.to_string(), module.to_string());
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert_eq!(
+            IRModule::from_bytecode("function main() {}").unwrap_err(),
+            "Not a .jsbc file"
+        );
+        assert_eq!(
+            IRModule::from_bytecode("JSBC 99\n{}").unwrap_err(),
+            "Unsupported .jsbc version 99 (expected 7)"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! A listing of the IR for people reading it: instructions numbered, jumps
+//! with the index they go to, constants as JS literals, and each
+//! instruction's effect on the operand stack.
+
+use super::{Constant, IRFunction, IRInstruction, IRModule};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+impl IRModule {
+    /// Function-by-function disassembly, extern declarations first.
+    pub fn disassemble(&self) -> String {
+        let mut out = String::new();
+        for function in &self.externs {
+            let mut params: Vec<&str> = function.params.iter().map(|ty| ty.name()).collect();
+            if function.variadic {
+                params.push("...");
+            }
+            writeln!(
+                out,
+                "extern function {}({}): {}",
+                function.name,
+                params.join(", "),
+                function.returns.name()
+            )
+            .unwrap();
+        }
+        for function in &self.functions {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            disassemble_function(function, self, &mut out);
+        }
+        out
+    }
+}
+
+fn disassemble_function(function: &IRFunction, module: &IRModule, out: &mut String) {
+    let constants = &module.constants;
+    let export = if function.export { "export " } else { "" };
+    let instructions = function.instructions();
+    writeln!(
+        out,
+        "{}function {}({}); {} instructions, max stack {}",
+        export,
+        function.name,
+        function.params.join(", "),
+        instructions.len(),
+        function.max_stack_depth()
+    )
+    .unwrap();
+
+    let targets: HashMap<&str, usize> = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, inst)| match inst {
+            IRInstruction::Label(label) => Some((label.as_str(), i)),
+            _ => None,
+        })
+        .collect();
+    let target = |label: &str| match targets.get(label) {
+        Some(index) => format!("{} (-> {})", label, index),
+        None => format!("{} (-> ?)", label),
+    };
+
+    for (i, (instruction, depth)) in instructions.iter().zip(function.stack_depths()).enumerate() {
+        let text = match instruction {
+            IRInstruction::Label(label) => {
+                writeln!(out, "{:>5}  {}:", i, label).unwrap();
+                continue;
+            }
+            IRInstruction::Pop => "pop".to_string(),
+            IRInstruction::Dup => "dup".to_string(),
+            IRInstruction::PushConst(index) => match constants.get(*index as usize) {
+                Some(constant) => format!("push {}", literal(constant)),
+                None => format!("push #{}", index),
+            },
+            IRInstruction::Load(name) => format!("load {}", name),
+            IRInstruction::Store(name) => format!("store {}", name),
+            IRInstruction::LoadUpvalue(index) => format!("load_upvalue {}", index),
+            IRInstruction::StoreUpvalue(index) => format!("store_upvalue {}", index),
+            IRInstruction::MakeClosure(name, count) => format!("closure {}/{}", name, count),
+            IRInstruction::GetProperty(name) => format!("get_property {}", name),
+            IRInstruction::Binary(op) => format!("{:?}", op).to_lowercase(),
+            IRInstruction::Unary(op) => format!("{:?}", op).to_lowercase(),
+            IRInstruction::Jump(label) => format!("jump {}", target(label)),
+            IRInstruction::JumpIf(label) => format!("jump_if {}", target(label)),
+            IRInstruction::Call(id, argc) => match module.functions.get(*id as usize) {
+                Some(callee) => format!("call {}/{}", callee.name, argc),
+                None => format!("call #{}/{}", id, argc),
+            },
+            IRInstruction::CallNative(name, argc) => format!("call_native {}/{}", name, argc),
+            IRInstruction::Return(true) => "return value".to_string(),
+            IRInstruction::Return(false) => "return".to_string(),
+            IRInstruction::Line(line, column) => format!("line {}:{}", line, column),
+        };
+        let (pops, pushes) = instruction.stack_effect();
+        let stack = match depth {
+            Some(depth) => format!("{} -> {}", depth, depth - pops + pushes),
+            None => "unreachable".to_string(),
+        };
+        writeln!(
+            out,
+            "{:>5}    {:<28} {:<6} ; {}",
+            i,
+            text,
+            format!("-{} +{}", pops, pushes),
+            stack
+        )
+        .unwrap();
+    }
+}
+
+/// The constant as JS source.
+pub(super) fn literal(constant: &Constant) -> String {
+    match constant {
+        Constant::Null => "null".to_string(),
+        Constant::Number(n) if n.is_infinite() => {
+            if *n > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+        }
+        Constant::Number(n) => n.to_string(),
+        Constant::String(s) => format!("{:?}", s),
+        Constant::Boolean(b) => b.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ir::lower_ast;
+    use crate::{lexer, parser};
+
+    #[test]
+    fn test_listing_resolves_jumps() {
+        let source = "function f(x) { if (x) { return \"yes\"; } return 0; }";
+        let module = lower_ast(parser::parse(lexer::tokenize(source)));
+        let listing = module.disassemble();
+        assert!(listing.starts_with("function f(x); 16 instructions, max stack 1\n"));
+        assert!(listing.contains("    6    jump_if L1 (-> 11)           -1 +0  ; 1 -> 0\n"));
+        assert!(listing.contains("    8    push \"yes\"                   -0 +1  ; 0 -> 1\n"));
+        assert!(listing.contains("   10    jump L2 (-> 12)              -0 +0  ; unreachable\n"));
+        assert!(listing.contains("   11  L1:\n"));
+    }
+}
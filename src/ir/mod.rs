@@ -1,18 +1,51 @@
-use crate::parser::{Expression, Statement, AST};
-use std::collections::HashMap;
+mod binary;
+mod block;
+mod builder;
+mod bytecode;
+mod disasm;
+mod text;
+mod types;
+mod verify;
 
-#[derive(Debug, Clone)]
+use crate::diagnostics::{Diagnostic, Span};
+use crate::parallel;
+use crate::parser::{ExprId, Expression, Statement, StmtId, AST};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+pub use binary::BINARY_VERSION;
+pub use block::{BasicBlock, BlockId, Terminator};
+pub use builder::{FunctionBuilder, Label, ModuleBuilder};
+pub use bytecode::{BYTECODE_EXTENSION, BYTECODE_VERSION};
+pub use text::{parse_text, print_text, TEXT_EXTENSION};
+pub use types::{FunctionTypes, TypeState, ValueType};
+pub use verify::{verify, VerifyError};
+
+/// Index of a function in its module's `functions`.
+pub type FuncId = u32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IRInstruction {
     // Stack Operations
     Pop,
     Dup,
 
     // Constants
-    PushConst(Constant), // Unified push constant instruction
+    PushConst(u16), // Index into the module's constants
 
     // Variables
-    Load(String),  // Load from any scope (local/global)
-    Store(String), // Store to any scope (local/global)
+    Load(String),      // Load from any scope (local/global)
+    Store(String),     // Store to any scope (local/global)
+    LoadUpvalue(u16),  // Load the function's capture at the index
+    StoreUpvalue(u16), // Store to the function's capture at the index
+
+    // Closures
+    MakeClosure(String, u16), // Function name, capture count: pops its captures' values
+
+    // Objects
+    GetProperty(String), // Pops an object, pushes its property of the name
 
     // Arithmetic/Logic
     Binary(BinaryOp), // All binary operations
@@ -24,50 +57,341 @@ pub enum IRInstruction {
     JumpIf(String), // Conditional jump
 
     // Function Operations
-    Call(String, u16), // Function name, argument count
-    Return(bool),      // bool indicates if returning value
+    Call(FuncId, u16), // Function of the module, argument count
+    // Function the module does not define, by name: a built-in, an extern,
+    // a host function or a closure in a variable; argument count
+    CallNative(String, u16),
+    Return(bool), // bool indicates if returning value
+
+    // Debug Info
+    Line(usize, usize), // Source line and column of the instructions that follow
+}
+
+impl IRInstruction {
+    /// Number of values the instruction pops and pushes.
+    pub fn stack_effect(&self) -> (usize, usize) {
+        match self {
+            IRInstruction::Pop => (1, 0),
+            IRInstruction::Dup => (1, 2),
+            IRInstruction::PushConst(_) => (0, 1),
+            IRInstruction::Load(_) => (0, 1),
+            IRInstruction::Store(_) => (1, 0),
+            IRInstruction::LoadUpvalue(_) => (0, 1),
+            IRInstruction::StoreUpvalue(_) => (1, 0),
+            IRInstruction::MakeClosure(_, count) => (*count as usize, 1),
+            IRInstruction::GetProperty(_) => (1, 1),
+            IRInstruction::Binary(_) => (2, 1),
+            IRInstruction::Unary(_) => (1, 1),
+            IRInstruction::Label(_) | IRInstruction::Jump(_) | IRInstruction::Line(..) => (0, 0),
+            IRInstruction::JumpIf(_) => (1, 0),
+            IRInstruction::Call(_, argc) | IRInstruction::CallNative(_, argc) => {
+                (*argc as usize, 1)
+            }
+            IRInstruction::Return(has_value) => (usize::from(*has_value), 0),
+        }
+    }
+
+    /// The instruction as `{:?}` writes it, but with the constant it
+    /// pushes from `constants`, or the name of the function it calls from
+    /// `functions`, in place of its index.
+    pub fn describe(&self, constants: &[Constant], functions: &[String]) -> String {
+        match self {
+            IRInstruction::PushConst(index) => match constants.get(*index as usize) {
+                Some(constant) => format!("PushConst({:?})", constant),
+                None => format!("{:?}", self),
+            },
+            IRInstruction::Call(id, argc) => match functions.get(*id as usize) {
+                Some(name) => format!("Call({:?}, {})", name, argc),
+                None => format!("{:?}", self),
+            },
+            _ => format!("{:?}", self),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BinaryOp {
-    Add, // +
-    Sub, // -
-    Mul, // *
-    Div, // /
-    Eq,  // ==
-    Lt,  // <
-    Gt,  // >
-    Ge,  // >=
-    Le,  // <=
-    And, // &&
-    Or,  // ||
+    Add,      // +
+    Sub,      // -
+    Mul,      // *
+    Div,      // /
+    Mod,      // %
+    Eq,       // ==
+    Neq,      // !=
+    StrictEq, // ===
+    Lt,       // <
+    Gt,       // >
+    Ge,       // >=
+    Le,       // <=
+    And,      // &&
+    Or,       // ||
 }
 
-#[derive(Debug, Clone)]
+impl BinaryOp {
+    pub const ALL: [BinaryOp; 14] = [
+        BinaryOp::Add,
+        BinaryOp::Sub,
+        BinaryOp::Mul,
+        BinaryOp::Div,
+        BinaryOp::Mod,
+        BinaryOp::Eq,
+        BinaryOp::Neq,
+        BinaryOp::StrictEq,
+        BinaryOp::Lt,
+        BinaryOp::Gt,
+        BinaryOp::Ge,
+        BinaryOp::Le,
+        BinaryOp::And,
+        BinaryOp::Or,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UnaryOp {
     Neg,
     Not,
 }
 
-#[derive(Debug, Clone)]
+impl UnaryOp {
+    pub const ALL: [UnaryOp; 2] = [UnaryOp::Neg, UnaryOp::Not];
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Constant {
     Null,
-    Number(f64),
+    Number(#[serde(with = "bytecode::number_text")] f64),
     String(String),
     Boolean(bool),
 }
 
-#[derive(Debug, Clone)]
+impl Constant {
+    /// Whether the constants are interchangeable: numbers compare by their
+    /// bits, so NaN is the same as itself and 0 differs from -0.
+    pub fn same(&self, other: &Constant) -> bool {
+        match (self, other) {
+            (Constant::Number(a), Constant::Number(b)) => a.to_bits() == b.to_bits(),
+            (Constant::String(a), Constant::String(b)) => a == b,
+            (Constant::Boolean(a), Constant::Boolean(b)) => a == b,
+            (Constant::Null, Constant::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+/// `IRFunction::position_at` for code already in the linear form.
+pub fn position_at(instructions: &[IRInstruction], index: usize) -> Option<(usize, usize)> {
+    instructions
+        .iter()
+        .take(index + 1)
+        .rev()
+        .find_map(|instruction| match instruction {
+            IRInstruction::Line(line, column) => Some((*line, *column)),
+            _ => None,
+        })
+}
+
+/// Index of `constant` in `constants`, adding it if it is not there yet.
+pub fn add_constant(constants: &mut Vec<Constant>, constant: Constant) -> u16 {
+    let index = match constants.iter().position(|c| c.same(&constant)) {
+        Some(index) => index,
+        None => {
+            constants.push(constant);
+            constants.len() - 1
+        }
+    };
+    constant_index(index)
+}
+
+/// `index` as the index of a constant, an error if there are too many.
+pub fn constant_index(index: usize) -> u16 {
+    u16::try_from(index).unwrap_or_else(|_| {
+        Diagnostic::error(format!(
+            "More than {} distinct constants",
+            u16::MAX as usize + 1
+        ))
+        .raise()
+    })
+}
+
+/// C type of a parameter or result of an extern function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CType {
+    Int,    // int
+    Long,   // long, also used for pointers
+    Double, // double
+    String, // const char *
+    Void,   // void, results only
+}
+
+impl CType {
+    pub const ALL: [CType; 5] = [
+        CType::Int,
+        CType::Long,
+        CType::Double,
+        CType::String,
+        CType::Void,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            CType::Int => "int",
+            CType::Long => "long",
+            CType::Double => "double",
+            CType::String => "string",
+            CType::Void => "void",
+        }
+    }
+
+    fn from_name(name: &str, line: Option<usize>) -> Self {
+        match name {
+            "int" => CType::Int,
+            "long" => CType::Long,
+            "double" => CType::Double,
+            "string" => CType::String,
+            "void" => CType::Void,
+            _ => error_at_line(line, format!("Unknown C type: {}", name))
+                .with_note("the C types are int, long, double, string and void")
+                .raise(),
+        }
+    }
+}
+
+/// A C function declared with `extern function`. Calls to it use the
+/// platform's C calling convention, converting arguments and the result
+/// between JS values and C types.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternFunction {
+    pub name: String,
+    pub params: Vec<CType>,
+    /// Whether it takes more arguments after `params`, like `printf`.
+    pub variadic: bool,
+    pub returns: CType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IRFunction {
     pub name: String,
     pub params: Vec<String>,
     pub max_stack: u16,
     pub max_locals: u16,
-    pub instructions: Vec<IRInstruction>,
+    pub blocks: Vec<BasicBlock>,
     pub exception_table: Vec<ExceptionHandler>,
+    /// Variables of the enclosing function it uses, which `LoadUpvalue` and
+    /// `StoreUpvalue` index. A closure made of it holds their values from
+    /// when `MakeClosure` ran, and its calls share them.
+    pub captures: Vec<String>,
+    /// Whether hosts can call it by its JS name. Only the Wasm backend
+    /// exports functions; `main` is always exported.
+    pub export: bool,
+}
+
+impl IRFunction {
+    /// Operand stack depth before each instruction; `None` where unreachable.
+    /// Assumes a function that passes `verify`.
+    pub fn stack_depths(&self) -> Vec<Option<usize>> {
+        let instructions = self.instructions();
+        let labels: HashMap<&str, usize> = instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, inst)| match inst {
+                IRInstruction::Label(label) => Some((label.as_str(), i)),
+                _ => None,
+            })
+            .collect();
+
+        let mut depths = vec![None; instructions.len()];
+        let mut work_list = vec![(0, 0)];
+        while let Some((pc, depth)) = work_list.pop() {
+            if pc >= depths.len() || depths[pc].is_some() {
+                continue;
+            }
+            depths[pc] = Some(depth);
+            let instruction = &instructions[pc];
+            let (pops, pushes) = instruction.stack_effect();
+            let next = depth - pops + pushes;
+            match instruction {
+                IRInstruction::Jump(label) => work_list.push((labels[label.as_str()], next)),
+                IRInstruction::JumpIf(label) => {
+                    work_list.push((labels[label.as_str()], next));
+                    work_list.push((pc + 1, next));
+                }
+                IRInstruction::Return(_) => {}
+                _ => work_list.push((pc + 1, next)),
+            }
+        }
+        depths
+    }
+
+    /// Point the function's constants, indices into `from`, at the same
+    /// constants in `into`, adding those it is missing.
+    pub fn move_constants(&mut self, from: &[Constant], into: &mut Vec<Constant>) {
+        let instructions = self
+            .blocks
+            .iter_mut()
+            .flat_map(|block| &mut block.instructions);
+        for instruction in instructions {
+            if let IRInstruction::PushConst(index) = instruction {
+                *index = add_constant(into, from[*index as usize].clone());
+            }
+        }
+    }
+
+    /// Point the function's calls, indices into its module's functions, at
+    /// the functions `ids` gives for those indices.
+    pub fn move_functions(&mut self, ids: &[FuncId]) {
+        let instructions = self
+            .blocks
+            .iter_mut()
+            .flat_map(|block| &mut block.instructions);
+        for instruction in instructions {
+            if let IRInstruction::Call(id, _) = instruction {
+                *id = ids[*id as usize];
+            }
+        }
+    }
+
+    /// Names the function uses as variables: its parameters, captures and
+    /// the names it stores to. A call to one of them calls the closure it
+    /// holds.
+    fn variables(&self) -> HashSet<&str> {
+        let stored = self.block_instructions().filter_map(|inst| match inst {
+            IRInstruction::Store(name) => Some(name.as_str()),
+            _ => None,
+        });
+        self.params
+            .iter()
+            .chain(&self.captures)
+            .map(String::as_str)
+            .chain(stored)
+            .collect()
+    }
+
+    /// Source line and column of the instruction at `index`: those of the
+    /// `Line` marker last before it in the function, which holds however
+    /// control reached it, since lowering starts each statement with one.
+    pub fn position_at(&self, index: usize) -> Option<(usize, usize)> {
+        position_at(&self.instructions(), index)
+    }
+
+    /// Deepest the operand stack gets.
+    pub fn max_stack_depth(&self) -> usize {
+        self.instructions()
+            .iter()
+            .zip(self.stack_depths())
+            .filter_map(|(inst, depth)| {
+                let (pops, pushes) = inst.stack_effect();
+                depth.map(|depth| depth - pops + pushes)
+            })
+            .max()
+            .unwrap_or(0)
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Where errors raised by the instructions from `start_label` up to
+/// `end_label` go: the VM continues at `handler_label` with the error's
+/// message as the only operand, if the error is of `exception_type`.
+/// Native code raises no errors, so it never reaches a handler.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExceptionHandler {
     pub start_label: String,
     pub end_label: String,
@@ -75,10 +399,11 @@ pub struct ExceptionHandler {
     pub exception_type: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IRModule {
     pub functions: Vec<IRFunction>,
     pub constants: Vec<Constant>,
+    pub externs: Vec<ExternFunction>,
 }
 
 impl IRModule {
@@ -86,40 +411,203 @@ impl IRModule {
         IRModule {
             functions: Vec::new(),
             constants: Vec::new(),
+            externs: Vec::new(),
         }
     }
 
+    /// Index of the function `name`; the last of that name if several are.
+    pub fn function_id(&self, name: &str) -> Option<FuncId> {
+        let id = self.functions.iter().rposition(|f| f.name == name)?;
+        Some(id as FuncId)
+    }
+
+    /// Names of the functions, by index.
+    pub fn function_names(&self) -> Vec<String> {
+        self.functions.iter().map(|f| f.name.clone()).collect()
+    }
+
+    /// Turn calls by name into calls by index where the name is a
+    /// function of the module, unless it is a variable of the caller.
+    pub fn resolve_calls(&mut self) {
+        let mut ids = HashMap::new();
+        for (id, function) in self.functions.iter().enumerate() {
+            ids.insert(function.name.clone(), id as FuncId);
+        }
+        for function in &mut self.functions {
+            let variables: HashSet<String> =
+                function.variables().into_iter().map(String::from).collect();
+            let instructions = function
+                .blocks
+                .iter_mut()
+                .flat_map(|block| &mut block.instructions);
+            for instruction in instructions {
+                if let IRInstruction::CallNative(name, argc) = instruction {
+                    if let (Some(&id), false) = (ids.get(name), variables.contains(name)) {
+                        *instruction = IRInstruction::Call(id, *argc);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The extern declaration of `name`, if it is a C function.
+    pub fn extern_function(&self, name: &str) -> Option<&ExternFunction> {
+        self.externs.iter().find(|function| function.name == name)
+    }
+
+    /// Number of instructions in all the functions, labels included.
+    pub fn instruction_count(&self) -> usize {
+        self.functions
+            .iter()
+            .map(|function| function.instruction_count())
+            .sum()
+    }
+
+    /// Index of `constant` in the module's constants, adding it if needed.
+    pub fn add_constant(&mut self, constant: Constant) -> u16 {
+        add_constant(&mut self.constants, constant)
+    }
+
+    /// Mark the function `name` as exported.
+    pub fn export(&mut self, name: &str) {
+        self.functions
+            .iter_mut()
+            .find(|function| function.name == name)
+            .unwrap_or_else(|| panic!("Undefined function: {}", name))
+            .export = true;
+    }
+
+    /// Add the functions and externs of a module lowered from another
+    /// file, and resolve the calls between the two. A name defined in both,
+    /// or an extern declared differently in each, is an error at the line
+    /// of the other module's definition.
+    pub fn link(&mut self, other: IRModule) {
+        for function in other.externs {
+            match self.extern_function(&function.name) {
+                Some(existing)
+                    if existing.params == function.params
+                        && existing.variadic == function.variadic
+                        && existing.returns == function.returns => {}
+                Some(_) => Diagnostic::error(format!(
+                    "extern function {} is declared differently in another file",
+                    function.name
+                ))
+                .raise(),
+                None => self.externs.push(function),
+            }
+        }
+        let first = self.functions.len() as FuncId;
+        let ids: Vec<FuncId> = (first..).take(other.functions.len()).collect();
+        for mut function in other.functions {
+            function.move_constants(&other.constants, &mut self.constants);
+            function.move_functions(&ids);
+            let defined = self.functions.iter().any(|f| f.name == function.name)
+                || self.extern_function(&function.name).is_some();
+            if defined {
+                let line = function.block_instructions().find_map(|inst| match inst {
+                    IRInstruction::Line(line, _) => Some(*line),
+                    _ => None,
+                });
+                error_at_line(
+                    line,
+                    format!("{} is also defined in another file", function.name),
+                )
+                .with_note("functions of all the input files share one namespace")
+                .raise();
+            }
+            self.add_function(function);
+        }
+        self.resolve_calls();
+    }
+
     fn add_function(&mut self, function: IRFunction) {
         self.functions.push(function);
     }
+}
+
+/// The module in the text syntax of `.ir` files, which `parse_text`
+/// reads back.
+impl fmt::Display for IRModule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&print_text(self))
+    }
+}
 
-    fn add_constant(&mut self, constant: Constant) -> usize {
-        self.constants.push(constant);
-        self.constants.len() - 1
+/// An error about the statement on `line`, if it is known.
+/// The error to report for a module that fails `verify`, at the source
+/// line of the first error. A local loaded before it is stored is a mistake
+/// in the program, such as using a `let` outside the block declaring it;
+/// anything else is a bug in lowering.
+fn verification_error(module: &IRModule, errors: &[VerifyError]) -> Diagnostic {
+    let first = &errors[0];
+    let line = module
+        .functions
+        .iter()
+        .find(|function| function.name == first.function())
+        .zip(first.index())
+        .and_then(|(function, index)| {
+            function.instructions()[..index].iter().rev().find_map(
+                |instruction| match instruction {
+                    IRInstruction::Line(line, _) => Some(*line),
+                    _ => None,
+                },
+            )
+        });
+    let diagnostic = match first {
+        VerifyError::UninitializedLocal { function, name, .. } => error_at_line(
+            line,
+            format!("{}: `{}` may be used before it is declared", function, name),
+        )
+        .with_note(
+            "a `let` declares a variable to the end of its block; declare it before the block instead",
+        ),
+        _ => error_at_line(line, format!("internal compiler error: {}", first))
+            .with_note("the program lowered to invalid IR; this is a bug in the compiler"),
+    };
+    errors[1..].iter().fold(diagnostic, |diagnostic, error| {
+        diagnostic.with_note(error.to_string())
+    })
+}
+
+fn error_at_line(line: Option<usize>, message: String) -> Diagnostic {
+    let diagnostic = Diagnostic::error(message);
+    match line {
+        Some(line) => diagnostic.with_label(Span::line(line), ""),
+        None => diagnostic,
     }
 }
 
 struct IRBuilder {
     current_function: IRFunction,
+    /// The function's code in the linear form, split into blocks once
+    /// it is complete.
+    instructions: Vec<IRInstruction>,
     label_counter: usize,
     local_vars: HashMap<String, u16>,
     next_local: u16,
+    /// Line of the statement being lowered, for errors.
+    line: Option<usize>,
+    /// The module's constants, lent to the builder while it lowers.
+    constants: Vec<Constant>,
+    /// Variables of the enclosing functions, which the function captures
+    /// when it uses them.
+    outer: HashSet<String>,
+    /// Functions of the closures declared in the function.
+    closures: Vec<IRFunction>,
 }
 
 impl IRBuilder {
-    fn new(name: String) -> Self {
+    fn new(name: String, constants: Vec<Constant>, outer: HashSet<String>) -> Self {
         IRBuilder {
-            current_function: IRFunction {
-                name,
-                params: Vec::new(),
-                max_stack: 0,
-                max_locals: 0,
-                instructions: Vec::new(),
-                exception_table: Vec::new(),
-            },
+            current_function: IRFunction::new(name, Vec::new()),
+            instructions: Vec::new(),
             label_counter: 0,
             local_vars: HashMap::new(),
             next_local: 0,
+            line: None,
+            constants,
+            outer,
+            closures: Vec::new(),
         }
     }
 
@@ -137,7 +625,12 @@ impl IRBuilder {
     }
 
     fn emit(&mut self, instruction: IRInstruction) {
-        self.current_function.instructions.push(instruction);
+        self.instructions.push(instruction);
+    }
+
+    fn push_constant(&mut self, constant: Constant) {
+        let index = add_constant(&mut self.constants, constant);
+        self.emit(IRInstruction::PushConst(index));
     }
 
     fn get_or_create_local(&mut self, name: &str) -> u16 {
@@ -147,43 +640,202 @@ impl IRBuilder {
             self.allocate_local(name)
         }
     }
+
+    /// Index of `name` in the function's captures, capturing it if it is
+    /// a variable of an enclosing function and not one of its own.
+    fn capture(&mut self, name: &str) -> Option<u16> {
+        if self.local_vars.contains_key(name) || !self.outer.contains(name) {
+            return None;
+        }
+        let captures = &mut self.current_function.captures;
+        let index = match captures.iter().position(|capture| capture == name) {
+            Some(index) => index,
+            None => {
+                captures.push(name.to_string());
+                captures.len() - 1
+            }
+        };
+        Some(index as u16)
+    }
+
+    /// Whether `name` is a variable of the function or of one it is
+    /// declared in.
+    fn is_variable(&self, name: &str) -> bool {
+        self.local_vars.contains_key(name) || self.outer.contains(name)
+    }
+
+    /// The name `expr` spells if it is a member of a name that is not a
+    /// variable, like `Math.random` or a host object's `counter.count`:
+    /// the VM provides those by their qualified names.
+    fn qualified_name(&self, ast: &AST, expr: ExprId) -> Option<String> {
+        match &ast[expr] {
+            Expression::Identifier(name) if !self.is_variable(name) => Some(name.clone()),
+            Expression::Member { object, property } => self
+                .qualified_name(ast, *object)
+                .map(|object| format!("{}.{}", object, property)),
+            _ => None,
+        }
+    }
+
+    /// Push the value of the variable `name`, wherever it lives.
+    fn load(&mut self, name: String) {
+        match self.capture(&name) {
+            Some(index) => self.emit(IRInstruction::LoadUpvalue(index)),
+            None => self.emit(IRInstruction::Load(name)),
+        }
+    }
+}
+
+/// Lower a function declaration, followed by the functions of the closures
+/// declared in it. `outer` holds the variables of the functions it is
+/// declared in.
+fn lower_function(
+    ast: &AST,
+    name: String,
+    params: Vec<String>,
+    body: &[StmtId],
+    position: Option<(usize, usize)>,
+    constants: &mut Vec<Constant>,
+    outer: HashSet<String>,
+) -> Vec<IRFunction> {
+    let mut builder = IRBuilder::new(name, std::mem::take(constants), outer);
+    if let Some((line, column)) = position {
+        builder.emit(IRInstruction::Line(line, column));
+    }
+
+    // Store params in the IRFunction
+    builder.current_function.params = params.clone();
+
+    // Allocate parameters as local variables
+    for param in params {
+        builder.allocate_local(&param);
+        // Load parameter from the local variable
+        builder.emit(IRInstruction::Load(param.clone()));
+        builder.emit(IRInstruction::Store(param));
+    }
+
+    // Lower function body
+    for &stmt in body {
+        lower_statement(&mut builder, ast, stmt);
+    }
+
+    // Add implicit return if needed
+    if !matches!(builder.instructions.last(), Some(IRInstruction::Return(_))) {
+        builder.emit(IRInstruction::Return(false));
+    }
+
+    *constants = builder.constants;
+    let mut function = builder.current_function;
+    function
+        .set_instructions(builder.instructions)
+        .expect("lowering defines each label once");
+    let mut functions = vec![function];
+    functions.extend(builder.closures);
+    functions
 }
 
 pub fn lower_ast(ast: AST) -> IRModule {
     let mut module = IRModule::new();
+    let mut function_lines = HashMap::new();
 
-    for statement in ast.statements {
-        match statement {
-            Statement::FunctionDeclaration { name, params, body } => {
-                let mut builder = IRBuilder::new(name.clone());
+    let statements: Vec<_> = ast
+        .statements
+        .iter()
+        .map(|&statement| match &ast[statement] {
+            Statement::Located {
+                line,
+                column,
+                statement,
+                ..
+            } => (Some((*line, *column)), &ast[*statement]),
+            statement => (None, statement),
+        })
+        .collect();
 
-                // Store params in the IRFunction
-                builder.current_function.params = params.clone();
+    // Functions lower in parallel, each with constants of its own, which
+    // are added to the module's in the order lowering one at a time would
+    // add them. An error is raised where that would have raised it.
+    let declarations: Vec<_> = statements
+        .iter()
+        .filter(|(_, statement)| matches!(statement, Statement::FunctionDeclaration { .. }))
+        .collect();
+    let lowered = parallel::map(&declarations, |&&(position, statement)| {
+        let Statement::FunctionDeclaration { name, params, body } = statement else {
+            unreachable!("only function declarations are lowered")
+        };
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut constants = Vec::new();
+            let functions = lower_function(
+                &ast,
+                name.clone(),
+                params.clone(),
+                body,
+                position,
+                &mut constants,
+                HashSet::new(),
+            );
+            (functions, constants)
+        }))
+    });
+    let mut lowered = lowered.into_iter();
 
-                // Allocate parameters as local variables
-                for param in params {
-                    let idx = builder.allocate_local(&param);
-                    // Load parameter from the local variable
-                    builder.emit(IRInstruction::Load(param.clone()));
-                    builder.emit(IRInstruction::Store(param));
-                }
+    for &(position, statement) in &statements {
+        let line = position.map(|(line, _)| line);
+        if let Statement::ExternDeclaration {
+            name,
+            param_types,
+            variadic,
+            return_type,
+        } = statement
+        {
+            let params: Vec<CType> = param_types
+                .iter()
+                .map(|t| CType::from_name(t, line))
+                .collect();
+            if params.contains(&CType::Void) {
+                error_at_line(line, format!("{}: void is not a parameter type", name)).raise();
+            }
+            module.externs.push(ExternFunction {
+                name: name.clone(),
+                params,
+                variadic: *variadic,
+                returns: return_type
+                    .as_ref()
+                    .map_or(CType::Void, |t| CType::from_name(t, line)),
+            });
+        } else if let Statement::FunctionDeclaration { name, .. } = statement {
+            function_lines.insert(name.clone(), line);
+            let (functions, constants) = match lowered.next().expect("lowered each function") {
+                Ok(lowered) => lowered,
+                Err(payload) => panic::resume_unwind(payload),
+            };
+            for constant in &constants {
+                add_constant(&mut module.constants, constant.clone());
+            }
+            for mut function in functions {
+                function.move_constants(&constants, &mut module.constants);
+                module.add_function(function);
+            }
+        }
+    }
 
-                // Lower function body
-                for stmt in body {
-                    lower_statement(&mut builder, stmt);
-                }
+    for function in &module.externs {
+        if module.functions.iter().any(|f| f.name == function.name) {
+            error_at_line(
+                function_lines[&function.name],
+                format!("{} is declared both extern and in JS", function.name),
+            )
+            .with_note("rename one of them; calls cannot tell them apart")
+            .raise();
+        }
+    }
 
-                // Add implicit return if needed
-                if !matches!(
-                    builder.current_function.instructions.last(),
-                    Some(IRInstruction::Return(_))
-                ) {
-                    builder.emit(IRInstruction::Return(false));
-                }
+    module.resolve_calls();
 
-                module.add_function(builder.current_function);
-            }
-            _ => {}
+    // Catch lowering bugs early in debug builds
+    if cfg!(debug_assertions) {
+        if let Err(errors) = verify(&module) {
+            verification_error(&module, &errors).raise();
         }
     }
 
@@ -191,22 +843,22 @@ pub fn lower_ast(ast: AST) -> IRModule {
 }
 
 // Also fix the Statement::Let handling to ensure proper variable initialization
-fn lower_statement(builder: &mut IRBuilder, stmt: Statement) {
-    match stmt {
+fn lower_statement(builder: &mut IRBuilder, ast: &AST, stmt: StmtId) {
+    match &ast[stmt] {
         Statement::Return(Some(expr)) => {
-            lower_expression(builder, expr);
+            lower_expression(builder, ast, *expr);
             builder.emit(IRInstruction::Return(true));
         }
         Statement::Return(None) => {
             builder.emit(IRInstruction::Return(false));
         }
         Statement::Let { name, initializer } => {
-            lower_expression(builder, initializer);
-            builder.get_or_create_local(&name); // Ensure local exists
-            builder.emit(IRInstruction::Store(name));
+            lower_expression(builder, ast, *initializer);
+            builder.get_or_create_local(name); // Ensure local exists
+            builder.emit(IRInstruction::Store(name.clone()));
         }
         Statement::ExpressionStatement(expr) => {
-            lower_expression(builder, expr);
+            lower_expression(builder, ast, *expr);
             builder.emit(IRInstruction::Pop);
         }
         Statement::If {
@@ -218,21 +870,21 @@ fn lower_statement(builder: &mut IRBuilder, stmt: Statement) {
             let end_label = builder.generate_label();
 
             // Compile condition
-            lower_expression(builder, condition);
+            lower_expression(builder, ast, *condition);
             builder.emit(IRInstruction::Unary(UnaryOp::Not)); // Add this line to negate the condition
             builder.emit(IRInstruction::JumpIf(else_label.clone()));
 
             // Compile then branch
-            for stmt in then_branch {
-                lower_statement(builder, stmt);
+            for &stmt in then_branch {
+                lower_statement(builder, ast, stmt);
             }
             builder.emit(IRInstruction::Jump(end_label.clone()));
 
             // Compile else branch if it exists
             builder.emit(IRInstruction::Label(else_label));
             if let Some(else_stmts) = else_branch {
-                for stmt in else_stmts {
-                    lower_statement(builder, stmt);
+                for &stmt in else_stmts {
+                    lower_statement(builder, ast, stmt);
                 }
             }
             builder.emit(IRInstruction::Label(end_label));
@@ -242,102 +894,192 @@ fn lower_statement(builder: &mut IRBuilder, stmt: Statement) {
             let end_label = builder.generate_label();
 
             builder.emit(IRInstruction::Label(start_label.clone()));
-            lower_expression(builder, condition);
+            lower_expression(builder, ast, *condition);
+            builder.emit(IRInstruction::Unary(UnaryOp::Not));
             builder.emit(IRInstruction::JumpIf(end_label.clone()));
 
-            for stmt in body {
-                lower_statement(builder, stmt);
+            for &stmt in body {
+                lower_statement(builder, ast, stmt);
+            }
+            builder.emit(IRInstruction::Jump(start_label));
+            builder.emit(IRInstruction::Label(end_label));
+        }
+        Statement::For {
+            initializer,
+            condition,
+            update,
+            body,
+        } => {
+            let start_label = builder.generate_label();
+            let end_label = builder.generate_label();
+
+            if let Some(initializer) = initializer {
+                lower_statement(builder, ast, *initializer);
+            }
+            builder.emit(IRInstruction::Label(start_label.clone()));
+            // Without a condition the loop only ends by returning
+            if let Some(condition) = condition {
+                lower_expression(builder, ast, *condition);
+                builder.emit(IRInstruction::Unary(UnaryOp::Not));
+                builder.emit(IRInstruction::JumpIf(end_label.clone()));
+            }
+
+            for &stmt in body {
+                lower_statement(builder, ast, stmt);
+            }
+            if let Some(update) = update {
+                lower_statement(builder, ast, *update);
             }
             builder.emit(IRInstruction::Jump(start_label));
             builder.emit(IRInstruction::Label(end_label));
         }
         Statement::Block(statements) => {
-            for stmt in statements {
-                lower_statement(builder, stmt);
+            for &stmt in statements {
+                lower_statement(builder, ast, stmt);
             }
         }
-        Statement::FunctionDeclaration { name, .. } => {
-            // Function declarations are handled at the module level
-            builder.emit(IRInstruction::PushConst(Constant::String(name.clone())));
-            builder.emit(IRInstruction::Store(name));
+        Statement::Located {
+            line,
+            column,
+            statement,
+            ..
+        } => {
+            builder.line = Some(*line);
+            builder.emit(IRInstruction::Line(*line, *column));
+            lower_statement(builder, ast, *statement);
+        }
+        Statement::ExternDeclaration { name, .. } => error_at_line(
+            builder.line,
+            format!("extern function {} must be declared at the top level", name),
+        )
+        .raise(),
+        Statement::FunctionDeclaration { name, params, body } => {
+            // A closure over the variables of this function and those it
+            // is declared in, named after both functions
+            let mut outer = builder.outer.clone();
+            outer.extend(builder.local_vars.keys().cloned());
+            let mut functions = lower_function(
+                ast,
+                format!("{}.{}", builder.current_function.name, name),
+                params.clone(),
+                body,
+                None,
+                &mut builder.constants,
+                outer,
+            );
+            let captures = functions[0].captures.clone();
+            for capture in captures.iter().cloned() {
+                builder.load(capture);
+            }
+            let closure = functions[0].name.clone();
+            builder.emit(IRInstruction::MakeClosure(closure, captures.len() as u16));
+            builder.closures.append(&mut functions);
+            builder.get_or_create_local(name);
+            builder.emit(IRInstruction::Store(name.clone()));
         }
     }
 }
 
-fn lower_expression(builder: &mut IRBuilder, expr: Expression) {
-    match expr {
+fn lower_expression(builder: &mut IRBuilder, ast: &AST, expr: ExprId) {
+    match &ast[expr] {
         Expression::Number(n) => {
-            builder.emit(IRInstruction::PushConst(Constant::Number(n)));
+            builder.push_constant(Constant::Number(*n));
         }
         Expression::String(s) => {
-            builder.emit(IRInstruction::PushConst(Constant::String(s)));
+            builder.push_constant(Constant::String(s.clone()));
         }
         Expression::Boolean(b) => {
-            builder.emit(IRInstruction::PushConst(Constant::Boolean(b)));
+            builder.push_constant(Constant::Boolean(*b));
         }
         Expression::Null => {
-            builder.emit(IRInstruction::PushConst(Constant::Null));
+            builder.push_constant(Constant::Null);
         }
         Expression::Identifier(name) => {
-            builder.emit(IRInstruction::Load(name));
+            builder.load(name.clone());
         }
         Expression::FunctionCall { name, arguments } => {
             // First evaluate all arguments
             let arg_size = arguments.len();
-            for arg in arguments {
-                match arg {
-                    Expression::Identifier(ref var_name) => {
-                        builder.emit(IRInstruction::Load(var_name.clone()));
-                    }
-                    _ => lower_expression(builder, arg),
+            for &arg in arguments {
+                match &ast[arg] {
+                    Expression::Identifier(var_name) => builder.load(var_name.clone()),
+                    _ => lower_expression(builder, ast, arg),
                 }
             }
-            builder.emit(IRInstruction::Call(name, arg_size as u16));
+            // Calls to a closure in an enclosing function's variable find
+            // it among the captures; `resolve_calls` turns calls to the
+            // module's functions into calls by index
+            builder.capture(name);
+            builder.emit(IRInstruction::CallNative(name.clone(), arg_size as u16));
+        }
+        Expression::Member { object, property } => match builder.qualified_name(ast, expr) {
+            Some(name) => builder.emit(IRInstruction::Load(name)),
+            None => {
+                lower_expression(builder, ast, *object);
+                builder.emit(IRInstruction::GetProperty(property.clone()));
+            }
+        },
+        Expression::MethodCall {
+            object,
+            method,
+            arguments,
+        } => {
+            let Some(object) = builder.qualified_name(ast, *object) else {
+                error_at_line(
+                    builder.line,
+                    format!("Cannot call the method {} of a value", method),
+                )
+                .with_note("only built-ins such as `Math.random` and host objects have methods")
+                .raise()
+            };
+            for &arg in arguments {
+                lower_expression(builder, ast, arg);
+            }
+            let name = format!("{}.{}", object, method);
+            builder.emit(IRInstruction::CallNative(name, arguments.len() as u16));
         }
         Expression::BinaryOp { op, left, right } => {
-            lower_expression(builder, *left);
-            lower_expression(builder, *right);
+            lower_expression(builder, ast, *left);
+            if op == "&&" || op == "||" {
+                // Short-circuit: the left operand is the value unless it is
+                // truthy for `&&` or falsy for `||`, when the right one is
+                let end_label = builder.generate_label();
+                builder.emit(IRInstruction::Dup);
+                if op == "&&" {
+                    builder.emit(IRInstruction::Unary(UnaryOp::Not));
+                }
+                builder.emit(IRInstruction::JumpIf(end_label.clone()));
+                builder.emit(IRInstruction::Pop);
+                lower_expression(builder, ast, *right);
+                builder.emit(IRInstruction::Label(end_label));
+                return;
+            }
+            lower_expression(builder, ast, *right);
 
             let op = match op.as_str() {
                 "+" => BinaryOp::Add,
                 "-" => BinaryOp::Sub,
                 "*" => BinaryOp::Mul,
                 "/" => BinaryOp::Div,
+                "%" => BinaryOp::Mod,
                 "==" => BinaryOp::Eq,
+                "!=" => BinaryOp::Neq,
+                "===" => BinaryOp::StrictEq,
+                "!==" => {
+                    builder.emit(IRInstruction::Binary(BinaryOp::StrictEq));
+                    builder.emit(IRInstruction::Unary(UnaryOp::Not));
+                    return;
+                }
                 "<" => BinaryOp::Lt,
                 ">" => BinaryOp::Gt,
                 "<=" => BinaryOp::Le,
                 ">=" => BinaryOp::Ge,
-                "&&" => {
-                    // Short-circuit evaluation for &&
-                    let end_label = builder.generate_label();
-                    let false_label = builder.generate_label();
-                    builder.emit(IRInstruction::Dup);
-                    builder.emit(IRInstruction::JumpIf(false_label.clone()));
-                    builder.emit(IRInstruction::Pop);
-                    builder.emit(IRInstruction::Jump(end_label.clone()));
-                    builder.emit(IRInstruction::Label(false_label));
-                    builder.emit(IRInstruction::Label(end_label));
-                    return;
-                }
-                "||" => {
-                    // Short-circuit evaluation for ||
-                    let end_label = builder.generate_label();
-                    let true_label = builder.generate_label();
-                    builder.emit(IRInstruction::Dup);
-                    builder.emit(IRInstruction::JumpIf(true_label.clone()));
-                    builder.emit(IRInstruction::Pop);
-                    builder.emit(IRInstruction::Jump(end_label.clone()));
-                    builder.emit(IRInstruction::Label(true_label));
-                    builder.emit(IRInstruction::Label(end_label));
-                    return;
-                }
                 _ => panic!("Unsupported binary operator: {}", op),
             };
             builder.emit(IRInstruction::Binary(op));
         }
         Expression::UnaryOp { op, expr } => {
-            lower_expression(builder, *expr);
+            lower_expression(builder, ast, *expr);
             let op = match op.as_str() {
                 "-" => UnaryOp::Neg,
                 "!" => UnaryOp::Not,
@@ -353,14 +1095,14 @@ fn lower_expression(builder: &mut IRBuilder, expr: Expression) {
             let else_label = builder.generate_label();
             let end_label: String = builder.generate_label();
 
-            lower_expression(builder, *condition);
+            lower_expression(builder, ast, *condition);
             builder.emit(IRInstruction::JumpIf(else_label.clone()));
 
-            lower_expression(builder, *then_expr);
+            lower_expression(builder, ast, *then_expr);
             builder.emit(IRInstruction::Jump(end_label.clone()));
 
             builder.emit(IRInstruction::Label(else_label));
-            lower_expression(builder, *else_expr);
+            lower_expression(builder, ast, *else_expr);
             builder.emit(IRInstruction::Label(end_label));
         }
     }
@@ -378,7 +1120,7 @@ mod tests {
         let tokens = tokenize(input);
         let ast = parse(tokens);
         let ir_module = lower_ast(ast);
-        
+
         assert_eq!(ir_module.functions.len(), 1);
         let function = &ir_module.functions[0];
         assert_eq!(function.name, "add");
@@ -387,20 +1129,103 @@ mod tests {
         assert!(function.params.contains(&"y".to_string()));
     }
 
+    #[test]
+    fn test_functions_lower_as_if_one_at_a_time() {
+        let module = lower_ast(parse(tokenize(
+            "function f() { return \"a\" + 1; }\n\
+             function g() { function h() { return 3; } return 1 + \"b\"; }\n\
+             function i() { return \"a\" + 2; }",
+        )));
+        let constants: Vec<String> = module
+            .constants
+            .iter()
+            .map(|c| format!("{:?}", c))
+            .collect();
+        assert_eq!(
+            constants,
+            [
+                "String(\"a\")",
+                "Number(1.0)",
+                "Number(3.0)",
+                "String(\"b\")",
+                "Number(2.0)"
+            ]
+        );
+
+        // The error of the first statement with one
+        let error = |source: &str| {
+            let payload = std::panic::catch_unwind(|| lower_ast(parse(tokenize(source))));
+            Diagnostic::from_panic(payload.unwrap_err()).message
+        };
+        let nested = "function f() { extern function puts(string): int; }";
+        let void = "extern function g(void): int;";
+        assert_eq!(
+            error(&format!("{}\n{}", void, nested)),
+            "g: void is not a parameter type"
+        );
+        assert!(error(&format!("{}\n{}", nested, void)).contains("puts"));
+    }
+
+    #[test]
+    fn test_link() {
+        let lower = |source: &str| lower_ast(parse(tokenize(source)));
+        let mut module =
+            lower("extern function puts(string): int;\nfunction main() { puts(\"a\"); helper(); }");
+        module.link(lower(
+            "extern function puts(string): int;\nfunction helper() { last(); }\nfunction last() {}",
+        ));
+        let names: Vec<&str> = module.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["main", "helper", "last"]);
+        assert_eq!(module.externs.len(), 1);
+
+        // Calls into the other file resolve, and its own calls keep their callees
+        let calls = |id: usize| {
+            let calls = module.functions[id]
+                .block_instructions()
+                .filter_map(|inst| match inst {
+                    IRInstruction::Call(callee, _) => Some(format!("#{}", callee)),
+                    IRInstruction::CallNative(name, _) => Some(name.clone()),
+                    _ => None,
+                });
+            calls.collect::<Vec<_>>()
+        };
+        assert_eq!(calls(0), ["puts", "#1"]);
+        assert_eq!(calls(1), ["#2"]);
+
+        let payload = std::panic::catch_unwind(move || {
+            module.link(lower("\nfunction helper() {}"));
+        })
+        .unwrap_err();
+        assert_eq!(
+            Diagnostic::from_panic(payload).to_string(),
+            "2: helper is also defined in another file"
+        );
+    }
+
     #[test]
     fn test_binary_operation() {
         let input = "function calc() { return 5 + 3; }";
         let tokens = tokenize(input);
         let ast = parse(tokens);
         let ir_module = lower_ast(ast);
-        
-        let function = &ir_module.functions[0];
-        let instructions = &function.instructions;
-        
+
+        let code = ir_module.functions[0].instructions();
+        let instructions: Vec<&IRInstruction> = code
+            .iter()
+            .filter(|inst| !matches!(inst, IRInstruction::Line(..)))
+            .collect();
+
         // Check for constant pushing and binary operation
-        assert!(matches!(instructions[0], IRInstruction::PushConst(Constant::Number(5.0))));
-        assert!(matches!(instructions[1], IRInstruction::PushConst(Constant::Number(3.0))));
-        assert!(matches!(instructions[2], IRInstruction::Binary(BinaryOp::Add)));
+        assert!(matches!(instructions[0], IRInstruction::PushConst(0)));
+        assert!(matches!(instructions[1], IRInstruction::PushConst(1)));
+        assert!(matches!(
+            ir_module.constants[..],
+            [Constant::Number(5.0), Constant::Number(3.0)]
+        ));
+        assert!(matches!(
+            instructions[2],
+            IRInstruction::Binary(BinaryOp::Add)
+        ));
         assert!(matches!(instructions[3], IRInstruction::Return(true)));
     }
 
@@ -410,14 +1235,143 @@ mod tests {
         let tokens = tokenize(input);
         let ast = parse(tokens);
         let ir_module = lower_ast(ast);
-        
+
         let function = &ir_module.functions[0];
-        
-        // Verify that we have conditional jump instructions
-        let has_jumps = function.instructions.iter().any(|inst| {
-            matches!(inst, IRInstruction::JumpIf(_))
-        });
-        
+
+        // Verify that we have conditional jumps
+        let has_jumps = function
+            .blocks
+            .iter()
+            .any(|block| matches!(block.terminator, Terminator::JumpIf(_)));
+
         assert!(has_jumps, "If statement should generate jump instructions");
     }
+
+    #[test]
+    fn test_closures() {
+        let input = "function f(a) {\n\
+                     let b = 1;\n\
+                     function g(x) {\n\
+                     function h() { return a + x; }\n\
+                     return h() + b;\n\
+                     }\n\
+                     return g(2);\n\
+                     }";
+        let module = lower_ast(parse(tokenize(input)));
+        let names: Vec<&str> = module.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["f", "f.g", "f.g.h"]);
+        // `g` captures `a` for `h`, as well as the `b` it reads itself
+        assert_eq!(module.functions[1].captures, ["a", "b"]);
+        assert_eq!(module.functions[2].captures, ["a", "x"]);
+
+        let code = module.functions[0].instructions();
+        let closure = code
+            .iter()
+            .position(|inst| matches!(inst, IRInstruction::MakeClosure(name, 2) if name == "f.g"))
+            .unwrap();
+        assert!(matches!(&code[closure + 1], IRInstruction::Store(name) if name == "g"));
+        let code = module.functions[1].instructions();
+        assert!(matches!(
+            code[..],
+            [
+                IRInstruction::Load(_),
+                IRInstruction::Store(_),
+                IRInstruction::Line(..),
+                IRInstruction::LoadUpvalue(0),
+                IRInstruction::Load(_),
+                IRInstruction::MakeClosure(..),
+                ..
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_members() {
+        let input = "function f(size) {\n\
+                     let area = size.width * size.height;\n\
+                     return counter.add(area) + config.limits.max;\n\
+                     }";
+        let module = lower_ast(parse(tokenize(input)));
+        let code: Vec<String> = module.functions[0]
+            .instructions()
+            .iter()
+            .filter(|inst| !matches!(inst, IRInstruction::Line(..)))
+            .map(|inst| format!("{:?}", inst))
+            .collect();
+        // Members of variables are read from their values; those of other
+        // names are what the VM provides by the qualified name
+        assert_eq!(
+            code[2..],
+            [
+                "Load(\"size\")",
+                "GetProperty(\"width\")",
+                "Load(\"size\")",
+                "GetProperty(\"height\")",
+                "Binary(Mul)",
+                "Store(\"area\")",
+                "Load(\"area\")",
+                "CallNative(\"counter.add\", 1)",
+                "Load(\"config.limits.max\")",
+                "Binary(Add)",
+                "Return(true)",
+            ]
+        );
+
+        let payload =
+            panic::catch_unwind(|| lower_ast(parse(tokenize("function f(s) {\n  s.trim();\n}"))))
+                .unwrap_err();
+        let diagnostic = Diagnostic::from_panic(payload);
+        assert_eq!(
+            diagnostic.to_string(),
+            "2: Cannot call the method trim of a value"
+        );
+    }
+
+    // Only debug builds verify what they lower
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_verification_failure_is_a_diagnostic() {
+        let source = "function f(c) {\n  if (c) { let y = 1; }\n  return y;\n}";
+        let payload = panic::catch_unwind(|| lower_ast(parse(tokenize(source)))).unwrap_err();
+        let diagnostic = Diagnostic::from_panic(payload);
+        assert_eq!(
+            diagnostic.to_string(),
+            "3: f: `y` may be used before it is declared"
+        );
+    }
+
+    #[test]
+    fn test_position_at() {
+        let input = "function f(x) {\n    while (x) {\n        print(x);\n    }\n    return x;\n}";
+        let function = &lower_ast(parse(tokenize(input))).functions[0];
+        let instructions = function.instructions();
+        let index_of = |wanted: &IRInstruction| {
+            instructions
+                .iter()
+                .position(|inst| format!("{:?}", inst) == format!("{:?}", wanted))
+                .unwrap()
+        };
+        // The loop's condition, jumped back to from its body
+        let condition = index_of(&IRInstruction::Label("L1".to_string())) + 1;
+        assert_eq!(function.position_at(condition), Some((2, 5)));
+        let call = index_of(&IRInstruction::CallNative("print".to_string(), 1));
+        assert_eq!(function.position_at(call), Some((3, 9)));
+        assert_eq!(function.position_at(instructions.len() - 1), Some((5, 5)));
+    }
+
+    #[test]
+    fn test_module_listing() {
+        let input =
+            "extern function puts(string): int;\nfunction f(x) { if (x) { return 1; } return 2; }";
+        let listing = lower_ast(parse(tokenize(input))).to_string();
+
+        assert!(listing.starts_with(
+            "extern function puts(string): int\nconstant 1\nconstant 2\n\nfunction f(x) locals=1 {\n"
+        ));
+        assert!(listing.contains("    push 1\n"));
+        // Labels stand out from the instructions
+        assert!(listing
+            .lines()
+            .any(|line| line.starts_with("  L") && line.ends_with(':')));
+    }
 }
@@ -0,0 +1,597 @@
+//! `.ir` files: a module written out as text, to read, to edit by hand
+//! for a test, or to compile later where lowering would have left off.
+//! `print_text` writes the syntax below and `parse_text` reads it back.
+//!
+//! ```text
+//! ; comments run to the end of the line
+//! extern function puts(string, ...): int
+//! constant "hello"
+//! export function main(x) locals=2 {
+//!     line 1:1
+//!     push "hello"
+//!     call puts/1
+//!     jump_if L1
+//!   L1:
+//!     return value
+//!     try L1 L2 catch L3 Error
+//! }
+//! ```
+//!
+//! Instructions are written as in the disassembly, and constants as JS
+//! literals, strings escaped as in Rust. The module's constants are listed
+//! first, in order, so each `push` of a literal reads back to the same
+//! index; `push #N` pushes constant N as it is, even one not listed.
+//! Calls name their function, `call f/N`, or give its index, `call #I/N`;
+//! one to a function the module does not define, or to a variable of the
+//! caller holding a closure, is written `call_native f/N`. Either way, a
+//! call by name to one of the module's functions reads back as a call by
+//! index, as lowering leaves it.
+//! A function a closure is made of lists its captures after its
+//! parameters, as `captures=x,y`.
+
+use super::{
+    add_constant, disasm::literal, BinaryOp, CType, Constant, ExceptionHandler, ExternFunction,
+    IRFunction, IRInstruction, IRModule, UnaryOp,
+};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// File extension of modules written as text.
+pub const TEXT_EXTENSION: &str = "ir";
+
+/// The module as text that `parse_text` reads back to the same module.
+pub fn print_text(module: &IRModule) -> String {
+    let mut out = String::new();
+    for function in &module.externs {
+        let mut params: Vec<&str> = function.params.iter().map(|ty| ty.name()).collect();
+        if function.variadic {
+            params.push("...");
+        }
+        writeln!(
+            out,
+            "extern function {}({}): {}",
+            function.name,
+            params.join(", "),
+            function.returns.name()
+        )
+        .unwrap();
+    }
+    for constant in &module.constants {
+        writeln!(out, "constant {}", literal(constant)).unwrap();
+    }
+    for function in &module.functions {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let export = if function.export { "export " } else { "" };
+        write!(
+            out,
+            "{}function {}({})",
+            export,
+            function.name,
+            function.params.join(", ")
+        )
+        .unwrap();
+        if !function.captures.is_empty() {
+            write!(out, " captures={}", function.captures.join(",")).unwrap();
+        }
+        if function.max_stack > 0 {
+            write!(out, " stack={}", function.max_stack).unwrap();
+        }
+        writeln!(out, " locals={} {{", function.max_locals).unwrap();
+        for instruction in &function.instructions() {
+            match instruction {
+                IRInstruction::Label(label) => writeln!(out, "  {}:", label).unwrap(),
+                _ => writeln!(out, "    {}", mnemonic(instruction, module)).unwrap(),
+            }
+        }
+        for handler in &function.exception_table {
+            writeln!(
+                out,
+                "    try {} {} catch {} {}",
+                handler.start_label,
+                handler.end_label,
+                handler.handler_label,
+                handler.exception_type
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+fn mnemonic(instruction: &IRInstruction, module: &IRModule) -> String {
+    let constants = &module.constants;
+    match instruction {
+        IRInstruction::Pop => "pop".to_string(),
+        IRInstruction::Dup => "dup".to_string(),
+        IRInstruction::PushConst(index) => match constants.get(*index as usize) {
+            Some(constant) => format!("push {}", literal(constant)),
+            None => format!("push #{}", index),
+        },
+        IRInstruction::Load(name) => format!("load {}", name),
+        IRInstruction::Store(name) => format!("store {}", name),
+        IRInstruction::LoadUpvalue(index) => format!("load_upvalue {}", index),
+        IRInstruction::StoreUpvalue(index) => format!("store_upvalue {}", index),
+        IRInstruction::MakeClosure(name, count) => format!("closure {}/{}", name, count),
+        IRInstruction::GetProperty(name) => format!("get_property {}", name),
+        IRInstruction::Binary(op) => format!("{:?}", op).to_lowercase(),
+        IRInstruction::Unary(op) => format!("{:?}", op).to_lowercase(),
+        IRInstruction::Label(label) => format!("{}:", label),
+        IRInstruction::Jump(label) => format!("jump {}", label),
+        IRInstruction::JumpIf(label) => format!("jump_if {}", label),
+        IRInstruction::Call(id, argc) => match module.functions.get(*id as usize) {
+            Some(callee) => format!("call {}/{}", callee.name, argc),
+            None => format!("call #{}/{}", id, argc),
+        },
+        IRInstruction::CallNative(name, argc) => format!("call_native {}/{}", name, argc),
+        IRInstruction::Return(true) => "return value".to_string(),
+        IRInstruction::Return(false) => "return".to_string(),
+        IRInstruction::Line(line, column) => format!("line {}:{}", line, column),
+    }
+}
+
+/// Read a module written by `print_text`, or by hand in the same syntax.
+/// Errors name the line they are on; jumps to missing labels are errors
+/// at the end of their function. The module is not verified otherwise, so
+/// stack underflows and the like are left for `verify` to find.
+pub fn parse_text(text: &str) -> Result<IRModule, String> {
+    let mut module = IRModule::new();
+    let mut function: Option<IRFunction> = None;
+    // The code of the function being read, in the linear form
+    let mut instructions = Vec::new();
+    // Whether the function being read gave `locals=`
+    let mut has_locals = false;
+    for (index, line) in text.lines().enumerate() {
+        let at = |message: String| format!("line {}: {}", index + 1, message);
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        match function.as_mut() {
+            Some(current) if line == "}" => {
+                current
+                    .set_instructions(std::mem::take(&mut instructions))
+                    .map_err(|error| at(error.to_string()))?;
+                if !has_locals {
+                    current.max_locals = count_locals(current);
+                }
+                module.add_function(function.take().unwrap());
+            }
+            Some(current) => {
+                if let Some(rest) = line.strip_prefix("try ") {
+                    let handler = parse_handler(rest).map_err(at)?;
+                    current.exception_table.push(handler);
+                } else {
+                    let instruction = parse_instruction(line, &mut module.constants).map_err(at)?;
+                    instructions.push(instruction);
+                }
+            }
+            None => {
+                if let Some(rest) = line.strip_prefix("extern function ") {
+                    let declaration = parse_extern(rest).map_err(at)?;
+                    module.externs.push(declaration);
+                } else if let Some(rest) = line.strip_prefix("constant ") {
+                    let constant = parse_literal(rest).map_err(at)?;
+                    module.constants.push(constant);
+                } else {
+                    let (header, locals_given) = parse_header(line).map_err(at)?;
+                    has_locals = locals_given;
+                    function = Some(header);
+                }
+            }
+        }
+    }
+    match function {
+        Some(function) => Err(format!(
+            "line {}: function {} is missing its closing `}}`",
+            text.lines().count(),
+            function.name
+        )),
+        None => {
+            module.resolve_calls();
+            Ok(module)
+        }
+    }
+}
+
+/// `line` up to a `;` that is not in a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ';' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Locals a hand-written function needs when it does not give `locals=`,
+/// or a built one: one for each parameter and each name stored to.
+pub(super) fn count_locals(function: &IRFunction) -> u16 {
+    let mut names: HashSet<&str> = function.params.iter().map(String::as_str).collect();
+    for instruction in function.block_instructions() {
+        if let IRInstruction::Store(name) = instruction {
+            names.insert(name);
+        }
+    }
+    names.len() as u16
+}
+
+/// `name(params)[ captures=a,b][ stack=N][ locals=N] {`, after
+/// `[export ]function`, and
+/// whether it gave `locals=`.
+fn parse_header(line: &str) -> Result<(IRFunction, bool), String> {
+    let (export, rest) = match line.strip_prefix("export ") {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let rest = rest
+        .strip_prefix("function ")
+        .ok_or_else(|| format!("Expected a function, extern or constant, not `{}`", line))?;
+    let rest = rest
+        .strip_suffix('{')
+        .ok_or_else(|| format!("Expected `{{` after function {}", rest))?;
+    let (name, params, attributes) = split_signature(rest)?;
+    let params = params.iter().map(|param| param.to_string()).collect();
+    let mut function = IRFunction::new(name.to_string(), params);
+    function.export = export;
+    let mut has_locals = false;
+    for attribute in attributes.split_whitespace() {
+        let (key, value) = attribute
+            .split_once('=')
+            .ok_or_else(|| format!("Expected `key=value`, not `{}`", attribute))?;
+        let number = || -> Result<u16, String> {
+            value
+                .parse()
+                .map_err(|_| format!("Invalid {} `{}`", key, value))
+        };
+        match key {
+            "captures" => {
+                for name in value.split(',') {
+                    if !is_name(name) {
+                        return Err(format!("Invalid capture `{}`", name));
+                    }
+                    function.captures.push(name.to_string());
+                }
+            }
+            "stack" => function.max_stack = number()?,
+            "locals" => {
+                function.max_locals = number()?;
+                has_locals = true;
+            }
+            _ => return Err(format!("Unknown function attribute `{}`", key)),
+        }
+    }
+    Ok((function, has_locals))
+}
+
+/// `name(a, b)rest` as its name, parameters and what follows them.
+fn split_signature(text: &str) -> Result<(&str, Vec<&str>, &str), String> {
+    let (name, rest) = text
+        .split_once('(')
+        .ok_or_else(|| format!("Expected `(` after `{}`", text.trim()))?;
+    let (params, rest) = rest
+        .split_once(')')
+        .ok_or_else(|| format!("Expected `)` after `{}`", rest.trim()))?;
+    let name = name.trim();
+    if !is_name(name) {
+        return Err(format!("Invalid function name `{}`", name));
+    }
+    let params = params
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .collect();
+    Ok((name, params, rest))
+}
+
+/// `name(types): type`, after `extern function`.
+fn parse_extern(text: &str) -> Result<ExternFunction, String> {
+    let (name, mut params, rest) = split_signature(text)?;
+    let returns = rest
+        .trim()
+        .strip_prefix(':')
+        .ok_or_else(|| format!("Expected `: type` after extern function {}", name))?;
+    let variadic = params.last() == Some(&"...");
+    if variadic {
+        params.pop();
+    }
+    Ok(ExternFunction {
+        name: name.to_string(),
+        params: params
+            .into_iter()
+            .map(parse_c_type)
+            .collect::<Result<_, _>>()?,
+        variadic,
+        returns: parse_c_type(returns.trim())?,
+    })
+}
+
+fn parse_c_type(name: &str) -> Result<CType, String> {
+    CType::ALL
+        .into_iter()
+        .find(|ty| ty.name() == name)
+        .ok_or_else(|| format!("Unknown C type `{}`", name))
+}
+
+/// `start end catch handler type`, after `try`.
+fn parse_handler(text: &str) -> Result<ExceptionHandler, String> {
+    match text.split_whitespace().collect::<Vec<_>>()[..] {
+        [start, end, "catch", handler, exception_type] => Ok(ExceptionHandler {
+            start_label: start.to_string(),
+            end_label: end.to_string(),
+            handler_label: handler.to_string(),
+            exception_type: exception_type.to_string(),
+        }),
+        _ => Err(format!(
+            "Expected `try start end catch handler type`, not `try {}`",
+            text
+        )),
+    }
+}
+
+/// An instruction, adding the constant it pushes to `constants`.
+fn parse_instruction(line: &str, constants: &mut Vec<Constant>) -> Result<IRInstruction, String> {
+    if let Some(label) = line.strip_suffix(':') {
+        if !is_name(label) {
+            return Err(format!("Invalid label `{}`", label));
+        }
+        return Ok(IRInstruction::Label(label.to_string()));
+    }
+    let (op, operand) = match line.split_once(char::is_whitespace) {
+        Some((op, operand)) => (op, Some(operand.trim())),
+        None => (line, None),
+    };
+    let index = |what: &str| match operand {
+        Some(index) => index
+            .parse()
+            .map_err(|_| format!("Invalid {} `{}`", what, index)),
+        None => Err(format!("`{}` needs a {}", op, what)),
+    };
+    let name = |what: &str| match operand {
+        Some(name) if is_name(name) => Ok(name.to_string()),
+        Some(name) => Err(format!("Invalid {} `{}`", what, name)),
+        None => Err(format!("`{}` needs a {}", op, what)),
+    };
+    let instruction = match (op, operand) {
+        ("pop", None) => IRInstruction::Pop,
+        ("dup", None) => IRInstruction::Dup,
+        ("push", Some(operand)) => match operand.strip_prefix('#') {
+            Some(index) => IRInstruction::PushConst(
+                index
+                    .parse()
+                    .map_err(|_| format!("Invalid constant index `{}`", index))?,
+            ),
+            None => IRInstruction::PushConst(add_constant(constants, parse_literal(operand)?)),
+        },
+        ("load", _) => IRInstruction::Load(name("variable")?),
+        ("store", _) => IRInstruction::Store(name("variable")?),
+        ("jump", _) => IRInstruction::Jump(name("label")?),
+        ("jump_if", _) => IRInstruction::JumpIf(name("label")?),
+        ("load_upvalue", _) => IRInstruction::LoadUpvalue(index("capture index")?),
+        ("store_upvalue", _) => IRInstruction::StoreUpvalue(index("capture index")?),
+        ("get_property", _) => IRInstruction::GetProperty(name("property")?),
+        ("call" | "call_native", Some(operand)) => {
+            let (name, argc) = operand
+                .rsplit_once('/')
+                .ok_or_else(|| format!("Expected `{} function/argc`, not `{}`", op, line))?;
+            let argc = argc
+                .parse()
+                .map_err(|_| format!("Invalid argument count `{}`", argc))?;
+            match name.strip_prefix('#') {
+                Some(id) if op == "call" => IRInstruction::Call(
+                    id.parse()
+                        .map_err(|_| format!("Invalid function index `{}`", id))?,
+                    argc,
+                ),
+                _ => IRInstruction::CallNative(name.to_string(), argc),
+            }
+        }
+        ("closure", Some(operand)) => {
+            let (name, count) = operand
+                .rsplit_once('/')
+                .ok_or_else(|| format!("Expected `closure function/captures`, not `{}`", line))?;
+            let count = count
+                .parse()
+                .map_err(|_| format!("Invalid capture count `{}`", count))?;
+            IRInstruction::MakeClosure(name.to_string(), count)
+        }
+        ("return", None) => IRInstruction::Return(false),
+        ("return", Some("value")) => IRInstruction::Return(true),
+        ("line", Some(operand)) => {
+            let position = operand
+                .split_once(':')
+                .and_then(|(line, column)| Some((line.parse().ok()?, column.parse().ok()?)));
+            let (line, column) =
+                position.ok_or_else(|| format!("Expected `line L:C`, not `{}`", line))?;
+            IRInstruction::Line(line, column)
+        }
+        (op, None) => {
+            let binary = BinaryOp::ALL
+                .into_iter()
+                .find(|binary| format!("{:?}", binary).to_lowercase() == op);
+            let unary = UnaryOp::ALL
+                .into_iter()
+                .find(|unary| format!("{:?}", unary).to_lowercase() == op);
+            match (binary, unary) {
+                (Some(binary), _) => IRInstruction::Binary(binary),
+                (_, Some(unary)) => IRInstruction::Unary(unary),
+                _ => return Err(format!("Unknown instruction `{}`", line)),
+            }
+        }
+        _ => return Err(format!("Unknown instruction `{}`", line)),
+    };
+    Ok(instruction)
+}
+
+/// Labels and the names of functions and variables; the functions of
+/// closures are named `outer.inner`.
+fn is_name(text: &str) -> bool {
+    !text.is_empty()
+        && !text.starts_with(|c: char| c.is_ascii_digit())
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+}
+
+/// A constant written by `literal`.
+fn parse_literal(text: &str) -> Result<Constant, String> {
+    let text = text.trim();
+    match text {
+        "null" => return Ok(Constant::Null),
+        "true" => return Ok(Constant::Boolean(true)),
+        "false" => return Ok(Constant::Boolean(false)),
+        "Infinity" => return Ok(Constant::Number(f64::INFINITY)),
+        "-Infinity" => return Ok(Constant::Number(f64::NEG_INFINITY)),
+        _ => {}
+    }
+    if let Some(quoted) = text.strip_prefix('"') {
+        return parse_string(quoted).map(Constant::String);
+    }
+    text.parse()
+        .map(Constant::Number)
+        .map_err(|_| format!("Invalid constant `{}`", text))
+}
+
+/// The string after its opening quote, with the escapes Rust writes.
+fn parse_string(quoted: &str) -> Result<String, String> {
+    let unterminated = || format!("Unterminated string `\"{}`", quoted);
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    loop {
+        match chars.next().ok_or_else(unterminated)? {
+            '"' if chars.as_str().is_empty() => return Ok(value),
+            '"' => return Err(format!("Unexpected `{}` after string", chars.as_str())),
+            '\\' => {
+                let escaped = match chars.next().ok_or_else(unterminated)? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    c @ ('\\' | '"' | '\'') => c,
+                    'u' => {
+                        let rest = chars.as_str();
+                        let code = rest
+                            .strip_prefix('{')
+                            .and_then(|rest| rest.split_once('}'))
+                            .and_then(|(hex, _)| u32::from_str_radix(hex, 16).ok())
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("Invalid escape in `\"{}`", quoted))?;
+                        let end = rest.find('}').unwrap();
+                        chars = rest[end + 1..].chars();
+                        code
+                    }
+                    c => return Err(format!("Unknown escape `\\{}`", c)),
+                };
+                value.push(escaped);
+            }
+            c => value.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::lower_ast;
+    use crate::{lexer, parser};
+
+    #[test]
+    fn test_text_round_trip() {
+        let source = "extern function printf(string, ...): int;\n\
+                      function fib(n) { if (n < 2) { return n; } return fib(n - 1) + fib(n - 2); }\n\
+                      function main() { printf(\"a \\\"b\\\"; c\\n\"); return -fib(10) / 0; }\n\
+                      function scale(k) { function by(x) { return x * k; } return by(2); }";
+        let mut module = lower_ast(parser::parse(lexer::tokenize(source)));
+        module.constants.push(Constant::Number(f64::NAN));
+        module
+            .constants
+            .push(Constant::String("é\u{1}".to_string()));
+        module.functions[0].exception_table.push(ExceptionHandler {
+            start_label: "L1".to_string(),
+            end_label: "L2".to_string(),
+            handler_label: "L3".to_string(),
+            exception_type: "Error".to_string(),
+        });
+        module.export("fib");
+
+        let text = print_text(&module);
+        assert!(text.contains("export function fib(n) locals=1 {\n"));
+        assert!(text.contains("    push \"a \\\"b\\\"; c\\n\"\n"));
+        assert!(text.contains("function scale.by(x) captures=k locals=1 {\n"));
+        assert!(text.contains("    closure scale.by/1\n"));
+        let parsed = parse_text(&text).unwrap();
+        assert_eq!(print_text(&parsed), text);
+        assert_eq!(parsed.to_bytecode(), module.to_bytecode());
+    }
+
+    #[test]
+    fn test_parse_hand_written() {
+        let text = "; adds one\n\
+                    function inc(x) {\n\
+                    \x20   load x   ; the argument\n\
+                    \x20   push 1\n\
+                    \x20   add\n\
+                    \x20   store y\n\
+                    \x20   load y\n\
+                    \x20   return value\n\
+                    }\n\
+                    function main() {\n\
+                    \x20   push 1\n\
+                    \x20   call inc/1\n\
+                    \x20   call #0/1\n\
+                    \x20   call_native print/1\n\
+                    \x20   return value\n\
+                    }\n";
+        let module = parse_text(text).unwrap();
+        assert!(matches!(
+            module.functions[1].blocks[0].instructions[..],
+            [
+                _,
+                IRInstruction::Call(0, 1),
+                IRInstruction::Call(0, 1),
+                IRInstruction::CallNative(..)
+            ]
+        ));
+        let function = &module.functions[0];
+        assert_eq!(function.params, ["x"]);
+        assert_eq!(function.max_locals, 2);
+        assert!(matches!(
+            function.blocks[0].instructions[2],
+            IRInstruction::Binary(BinaryOp::Add)
+        ));
+        assert!(crate::ir::verify(&module).is_ok());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |text: &str| parse_text(text).unwrap_err();
+        assert_eq!(
+            error("function f() {\n  push \"a\n}"),
+            "line 2: Unterminated string `\"a`"
+        );
+        assert_eq!(
+            error("function f() {\n\n  frobnicate\n}"),
+            "line 3: Unknown instruction `frobnicate`"
+        );
+        assert_eq!(
+            error("extern function f(char): int"),
+            "line 1: Unknown C type `char`"
+        );
+        assert_eq!(
+            error("function f() {\n  jump L9\n}"),
+            "line 3: f: jump to undefined label 'L9'"
+        );
+        assert_eq!(
+            error("function f() {\n  return"),
+            "line 2: function f is missing its closing `}`"
+        );
+    }
+}
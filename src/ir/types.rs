@@ -0,0 +1,299 @@
+//! What type of value each operand stack slot and local holds before each
+//! instruction, where that is the same on every path there. Backends
+//! leave out the checks for non-numbers around arithmetic on operands
+//! known to be numbers.
+
+use super::{BinaryOp, Constant, IRFunction, IRInstruction, UnaryOp};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Number,
+    String,
+    Boolean,
+    /// Null, undefined, an object, or different types on different paths.
+    Unknown,
+}
+
+impl ValueType {
+    fn of(constant: &Constant) -> Self {
+        match constant {
+            Constant::Number(_) => ValueType::Number,
+            Constant::String(_) => ValueType::String,
+            Constant::Boolean(_) => ValueType::Boolean,
+            Constant::Null => ValueType::Unknown,
+        }
+    }
+
+    /// The type of a value that is `self` on one path and `other` on
+    /// another.
+    fn join(self, other: ValueType) -> ValueType {
+        if self == other {
+            self
+        } else {
+            ValueType::Unknown
+        }
+    }
+
+    /// Type of the result of `op`, as the VM and runtime compute it.
+    fn binary(op: &BinaryOp, left: ValueType, right: ValueType) -> ValueType {
+        use ValueType::*;
+        match (op, left, right) {
+            (BinaryOp::Add, String, _) | (BinaryOp::Add, _, String) => String,
+            (
+                BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod,
+                Number,
+                Number,
+            ) => Number,
+            (
+                BinaryOp::Eq | BinaryOp::Neq | BinaryOp::StrictEq | BinaryOp::And | BinaryOp::Or,
+                _,
+                _,
+            ) => Boolean,
+            (BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Ge | BinaryOp::Le, Number, Number)
+            | (BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Ge | BinaryOp::Le, String, String) => {
+                Boolean
+            }
+            _ => Unknown,
+        }
+    }
+
+    fn unary(op: &UnaryOp, operand: ValueType) -> ValueType {
+        match (op, operand) {
+            (UnaryOp::Neg, ValueType::Number) => ValueType::Number,
+            (UnaryOp::Not, _) => ValueType::Boolean,
+            _ => ValueType::Unknown,
+        }
+    }
+}
+
+/// Types before an instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeState {
+    /// The operand stack's, bottom first.
+    pub stack: Vec<ValueType>,
+    /// Those of the locals with one type on every path; the rest are
+    /// `Unknown`.
+    pub locals: BTreeMap<String, ValueType>,
+}
+
+impl TypeState {
+    fn join(&self, other: &TypeState) -> TypeState {
+        TypeState {
+            stack: self
+                .stack
+                .iter()
+                .zip(&other.stack)
+                .map(|(a, b)| a.join(*b))
+                .collect(),
+            locals: self
+                .locals
+                .iter()
+                .filter_map(|(name, ty)| {
+                    let joined = ty.join(*other.locals.get(name)?);
+                    (joined != ValueType::Unknown).then(|| (name.clone(), joined))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn local(&self, name: &str) -> ValueType {
+        self.locals.get(name).copied().unwrap_or(ValueType::Unknown)
+    }
+}
+
+/// The types inferred for a function.
+#[derive(Debug, Clone)]
+pub struct FunctionTypes {
+    /// Before each instruction; `None` where unreachable.
+    states: Vec<Option<TypeState>>,
+}
+
+impl FunctionTypes {
+    pub fn before(&self, index: usize) -> Option<&TypeState> {
+        self.states.get(index)?.as_ref()
+    }
+
+    /// Whether every operand the instruction at `index` pops is a number.
+    pub fn operands_are_numbers(&self, index: usize, instruction: &IRInstruction) -> bool {
+        let (pops, _) = instruction.stack_effect();
+        match self.before(index) {
+            Some(state) if pops > 0 && pops <= state.stack.len() => state.stack
+                [state.stack.len() - pops..]
+                .iter()
+                .all(|ty| *ty == ValueType::Number),
+            _ => false,
+        }
+    }
+}
+
+impl IRFunction {
+    /// Infer the types before each instruction, with `constants` those of
+    /// the module. Parameters and globals could be anything, and a call
+    /// could store anything to a name that is not a parameter, which in the
+    /// VM may be a global. Assumes a function that passes `verify`.
+    pub fn infer_types(&self, constants: &[Constant]) -> FunctionTypes {
+        let starts = self.block_starts();
+        let mut states: Vec<Option<TypeState>> = vec![None; self.instruction_count()];
+        let mut entries: Vec<Option<TypeState>> = vec![None; self.blocks.len()];
+        let entry = TypeState {
+            stack: Vec::new(),
+            locals: BTreeMap::new(),
+        };
+        let mut work_list = vec![(0, entry)];
+        while let Some((b, state)) = work_list.pop() {
+            if b >= self.blocks.len() {
+                continue;
+            }
+            let mut state = match &entries[b] {
+                Some(old) => {
+                    let joined = old.join(&state);
+                    if joined == *old {
+                        continue;
+                    }
+                    joined
+                }
+                None => state,
+            };
+            entries[b] = Some(state.clone());
+
+            // The block's label, if it has one, comes first
+            let block = &self.blocks[b];
+            states[starts[b]] = Some(state.clone());
+            let terminator = self.terminator_instruction(b);
+            let first = starts[b + 1] - block.instructions.len() - terminator.is_some() as usize;
+            for (pc, instruction) in (first..).zip(block.instructions.iter().chain(&terminator)) {
+                states[pc] = Some(state.clone());
+                state = self.transfer(instruction, state, constants);
+            }
+            for successor in self.successors(b) {
+                work_list.push((successor, state.clone()));
+            }
+        }
+        FunctionTypes { states }
+    }
+
+    /// The types after `instruction`, from those before it.
+    fn transfer(
+        &self,
+        instruction: &IRInstruction,
+        mut state: TypeState,
+        constants: &[Constant],
+    ) -> TypeState {
+        let stack = &mut state.stack;
+        let pop = |stack: &mut Vec<ValueType>| stack.pop().unwrap_or(ValueType::Unknown);
+        match instruction {
+            IRInstruction::Pop | IRInstruction::JumpIf(_) | IRInstruction::StoreUpvalue(_) => {
+                pop(stack);
+            }
+            IRInstruction::LoadUpvalue(_) => stack.push(ValueType::Unknown),
+            IRInstruction::GetProperty(_) => {
+                pop(stack);
+                stack.push(ValueType::Unknown);
+            }
+            IRInstruction::MakeClosure(_, count) => {
+                stack.truncate(stack.len().saturating_sub(*count as usize));
+                stack.push(ValueType::Unknown);
+            }
+            IRInstruction::Dup => {
+                let top = stack.last().copied().unwrap_or(ValueType::Unknown);
+                stack.push(top);
+            }
+            IRInstruction::PushConst(index) => {
+                let ty = constants
+                    .get(*index as usize)
+                    .map_or(ValueType::Unknown, ValueType::of);
+                stack.push(ty);
+            }
+            IRInstruction::Load(name) => {
+                let ty = state.locals.get(name).copied();
+                stack.push(ty.unwrap_or(ValueType::Unknown));
+            }
+            IRInstruction::Store(name) => {
+                let ty = pop(stack);
+                if ty == ValueType::Unknown {
+                    state.locals.remove(name);
+                } else {
+                    state.locals.insert(name.clone(), ty);
+                }
+            }
+            IRInstruction::Binary(op) => {
+                let right = pop(stack);
+                let left = pop(stack);
+                stack.push(ValueType::binary(op, left, right));
+            }
+            IRInstruction::Unary(op) => {
+                let operand = pop(stack);
+                stack.push(ValueType::unary(op, operand));
+            }
+            IRInstruction::Call(_, argc) | IRInstruction::CallNative(_, argc) => {
+                stack.truncate(stack.len().saturating_sub(*argc as usize));
+                stack.push(ValueType::Unknown);
+                let params = &self.params;
+                state.locals.retain(|name, _| params.contains(name));
+            }
+            IRInstruction::Return(_)
+            | IRInstruction::Label(_)
+            | IRInstruction::Jump(_)
+            | IRInstruction::Line(..) => {}
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::lower_ast;
+    use crate::{lexer, parser};
+
+    #[test]
+    fn test_infer_types() {
+        let source = "function f(x) {\n\
+                      let n = 1;\n\
+                      let s = \"a\" + x;\n\
+                      if (x) { let n = 2; } else { let n = \"two\"; }\n\
+                      let y = n * 2;\n\
+                      let m = 3;\n\
+                      print(m);\n\
+                      return m + s + y;\n\
+                      }";
+        let module = lower_ast(parser::parse(lexer::tokenize(source)));
+        let function = &module.functions[0];
+        let types = function.infer_types(&module.constants);
+        let instructions = function.instructions();
+
+        let last = |wanted: &str| {
+            let index = instructions
+                .iter()
+                .rposition(|inst| format!("{:?}", inst) == wanted)
+                .unwrap();
+            (index, types.before(index).unwrap())
+        };
+        // `n` is a number on one branch and a string on the other
+        let (_, state) = last("Load(\"n\")");
+        assert_eq!(state.local("n"), ValueType::Unknown);
+        assert_eq!(state.local("s"), ValueType::String);
+        // The call could have changed `m` and `s`, which are not parameters
+        let (index, state) = last("Binary(Add)");
+        assert_eq!(state.stack, [ValueType::Unknown, ValueType::Unknown]);
+        assert!(!types.operands_are_numbers(index, &IRInstruction::Binary(BinaryOp::Add)));
+        assert_eq!(last("Load(\"m\")").1.local("m"), ValueType::Unknown);
+        let (index, state) = last("Store(\"n\")");
+        assert_eq!(state.stack, [ValueType::String]);
+        assert!(!types.operands_are_numbers(index, &IRInstruction::Store("n".to_string())));
+
+        let source = "function g() { let a = 2; return -(a * 3.5) / 2; }";
+        let module = lower_ast(parser::parse(lexer::tokenize(source)));
+        let function = &module.functions[0];
+        let types = function.infer_types(&module.constants);
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            if matches!(
+                instruction,
+                IRInstruction::Binary(_) | IRInstruction::Unary(_)
+            ) {
+                assert!(types.operands_are_numbers(index, instruction));
+            }
+        }
+    }
+}
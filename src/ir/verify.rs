@@ -0,0 +1,730 @@
+use super::{FuncId, IRFunction, IRInstruction, IRModule, Terminator};
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerifyError {
+    UndefinedLabel {
+        function: String,
+        label: String,
+    },
+    DuplicateLabel {
+        function: String,
+        label: String,
+    },
+    StackUnderflow {
+        function: String,
+        index: usize,
+    },
+    StackMismatch {
+        function: String,
+        index: usize,
+        depths: (usize, usize),
+    },
+    MissingReturn {
+        function: String,
+    },
+    UninitializedLocal {
+        function: String,
+        name: String,
+        index: usize,
+    },
+    ExternArity {
+        function: String,
+        name: String,
+        argc: usize,
+    },
+    UndefinedConstant {
+        function: String,
+        constant: u16,
+        index: usize,
+    },
+    UndefinedUpvalue {
+        function: String,
+        upvalue: u16,
+        index: usize,
+    },
+    UndefinedFunction {
+        function: String,
+        callee: FuncId,
+        index: usize,
+    },
+    ClosureMismatch {
+        function: String,
+        name: String,
+        count: u16,
+        index: usize,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::UndefinedLabel { function, label } => {
+                write!(f, "{}: jump to undefined label '{}'", function, label)
+            }
+            VerifyError::DuplicateLabel { function, label } => {
+                write!(f, "{}: label '{}' defined more than once", function, label)
+            }
+            VerifyError::StackUnderflow { function, index } => {
+                write!(f, "{}: stack underflow at instruction {}", function, index)
+            }
+            VerifyError::StackMismatch {
+                function,
+                index,
+                depths: (first, second),
+            } => write!(
+                f,
+                "{}: paths join at instruction {} with {} and {} values on the stack",
+                function, index, first, second
+            ),
+            VerifyError::MissingReturn { function } => {
+                write!(
+                    f,
+                    "{}: control can reach the end without a return",
+                    function
+                )
+            }
+            VerifyError::UninitializedLocal {
+                function,
+                name,
+                index,
+            } => write!(
+                f,
+                "{}: local '{}' may be loaded before it is stored (instruction {})",
+                function, name, index
+            ),
+            VerifyError::ExternArity {
+                function,
+                name,
+                argc,
+            } => write!(
+                f,
+                "{}: extern function '{}' called with {} arguments",
+                function, name, argc
+            ),
+            VerifyError::UndefinedConstant {
+                function,
+                constant,
+                index,
+            } => write!(
+                f,
+                "{}: push of undefined constant {} at instruction {}",
+                function, constant, index
+            ),
+            VerifyError::UndefinedUpvalue {
+                function,
+                upvalue,
+                index,
+            } => write!(
+                f,
+                "{}: use of undefined capture {} at instruction {}",
+                function, upvalue, index
+            ),
+            VerifyError::UndefinedFunction {
+                function,
+                callee,
+                index,
+            } => write!(
+                f,
+                "{}: call of undefined function {} at instruction {}",
+                function, callee, index
+            ),
+            VerifyError::ClosureMismatch {
+                function,
+                name,
+                count,
+                index,
+            } => write!(
+                f,
+                "{}: closure of '{}' with {} captures, which it does not have, at instruction {}",
+                function, name, count, index
+            ),
+        }
+    }
+}
+
+impl VerifyError {
+    /// Name of the function the error is in.
+    pub fn function(&self) -> &str {
+        match self {
+            VerifyError::UndefinedLabel { function, .. }
+            | VerifyError::DuplicateLabel { function, .. }
+            | VerifyError::StackUnderflow { function, .. }
+            | VerifyError::StackMismatch { function, .. }
+            | VerifyError::MissingReturn { function }
+            | VerifyError::UninitializedLocal { function, .. }
+            | VerifyError::ExternArity { function, .. }
+            | VerifyError::UndefinedConstant { function, .. }
+            | VerifyError::UndefinedUpvalue { function, .. }
+            | VerifyError::UndefinedFunction { function, .. }
+            | VerifyError::ClosureMismatch { function, .. } => function,
+        }
+    }
+
+    /// Index of the instruction the error is at, if it is at one.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            VerifyError::StackUnderflow { index, .. }
+            | VerifyError::StackMismatch { index, .. }
+            | VerifyError::UninitializedLocal { index, .. }
+            | VerifyError::UndefinedConstant { index, .. }
+            | VerifyError::UndefinedUpvalue { index, .. }
+            | VerifyError::UndefinedFunction { index, .. }
+            | VerifyError::ClosureMismatch { index, .. } => Some(*index),
+            VerifyError::UndefinedLabel { .. }
+            | VerifyError::DuplicateLabel { .. }
+            | VerifyError::MissingReturn { .. }
+            | VerifyError::ExternArity { .. } => None,
+        }
+    }
+}
+
+/// Abstract state flowing into an instruction: the stack depth, which every
+/// path must agree on, and the locals definitely stored on every path.
+#[derive(Clone, PartialEq)]
+struct FlowState {
+    depth: usize,
+    assigned: HashSet<String>,
+}
+
+impl FlowState {
+    /// The state where paths with `self` and `other` join, or their depths
+    /// if they differ.
+    fn merge(&self, other: &FlowState) -> Result<FlowState, (usize, usize)> {
+        if self.depth != other.depth {
+            return Err((self.depth, other.depth));
+        }
+        Ok(FlowState {
+            depth: self.depth,
+            assigned: self
+                .assigned
+                .intersection(&other.assigned)
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
+/// Checks structural invariants every backend relies on: jump targets exist,
+/// the operand stack never underflows, every path ends in a `Return`,
+/// locals are stored before they are loaded, constants pushed are in the
+/// module's constants, functions called by index are in the module,
+/// captures used are the function's, closures are made of functions with
+/// the captures given, and extern functions get the arguments they declare.
+pub fn verify(module: &IRModule) -> Result<(), Vec<VerifyError>> {
+    let mut errors = Vec::new();
+    for function in &module.functions {
+        verify_function(function, &mut errors);
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            if let IRInstruction::PushConst(constant) = instruction {
+                if *constant as usize >= module.constants.len() {
+                    push_error(
+                        &mut errors,
+                        VerifyError::UndefinedConstant {
+                            function: function.name.clone(),
+                            constant: *constant,
+                            index,
+                        },
+                    );
+                }
+            }
+            match instruction {
+                IRInstruction::LoadUpvalue(upvalue) | IRInstruction::StoreUpvalue(upvalue)
+                    if *upvalue as usize >= function.captures.len() =>
+                {
+                    push_error(
+                        &mut errors,
+                        VerifyError::UndefinedUpvalue {
+                            function: function.name.clone(),
+                            upvalue: *upvalue,
+                            index,
+                        },
+                    );
+                }
+                IRInstruction::Call(callee, _) if *callee as usize >= module.functions.len() => {
+                    push_error(
+                        &mut errors,
+                        VerifyError::UndefinedFunction {
+                            function: function.name.clone(),
+                            callee: *callee,
+                            index,
+                        },
+                    );
+                }
+                IRInstruction::MakeClosure(name, count) => {
+                    let captures = module
+                        .functions
+                        .iter()
+                        .find(|f| f.name == *name)
+                        .map(|f| f.captures.len());
+                    if captures != Some(*count as usize) {
+                        push_error(
+                            &mut errors,
+                            VerifyError::ClosureMismatch {
+                                function: function.name.clone(),
+                                name: name.clone(),
+                                count: *count,
+                                index,
+                            },
+                        );
+                    }
+                }
+                _ => {}
+            }
+            let IRInstruction::CallNative(name, argc) = instruction else {
+                continue;
+            };
+            let Some(callee) = module.extern_function(name) else {
+                continue;
+            };
+            let argc = *argc as usize;
+            if argc < callee.params.len() || (argc > callee.params.len() && !callee.variadic) {
+                push_error(
+                    &mut errors,
+                    VerifyError::ExternArity {
+                        function: function.name.clone(),
+                        name: name.clone(),
+                        argc,
+                    },
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn verify_function(function: &IRFunction, errors: &mut Vec<VerifyError>) {
+    let block_count = function.blocks.len();
+    for block in &function.blocks {
+        if let Terminator::Jump(target) | Terminator::JumpIf(target) = block.terminator {
+            if target >= block_count {
+                push_error(
+                    errors,
+                    VerifyError::UndefinedLabel {
+                        function: function.name.clone(),
+                        label: format!("B{}", target),
+                    },
+                );
+                return;
+            }
+        }
+    }
+    if block_count == 0 {
+        push_error(
+            errors,
+            VerifyError::MissingReturn {
+                function: function.name.clone(),
+            },
+        );
+        return;
+    }
+
+    // Blocks starting the range of each handler, and the handler's block
+    let mut handlers = Vec::new();
+    for handler in &function.exception_table {
+        let labels = [
+            &handler.start_label,
+            &handler.end_label,
+            &handler.handler_label,
+        ];
+        let blocks = labels.map(|label| function.block_named(label));
+        for (label, block) in labels.iter().zip(blocks) {
+            if block.is_none() {
+                push_error(
+                    errors,
+                    VerifyError::UndefinedLabel {
+                        function: function.name.clone(),
+                        label: label.to_string(),
+                    },
+                );
+            }
+        }
+        if let [Some(start), _, Some(target)] = blocks {
+            handlers.push((start, target));
+        }
+    }
+
+    // Names stored somewhere in the function are locals; anything else is a global
+    let locals: HashSet<&String> = function
+        .block_instructions()
+        .filter_map(|inst| match inst {
+            IRInstruction::Store(name) => Some(name),
+            _ => None,
+        })
+        .chain(function.params.iter())
+        .collect();
+
+    // Errors give instruction indices in the linear form
+    let starts = function.block_starts();
+    let mut entries: Vec<Option<FlowState>> = vec![None; block_count];
+    let mut work_list = vec![0];
+    entries[0] = Some(FlowState {
+        depth: 0,
+        assigned: function.params.iter().cloned().collect(),
+    });
+
+    'blocks: while let Some(b) = work_list.pop() {
+        let block = &function.blocks[b];
+        let mut state = entries[b].clone().unwrap();
+        // A handler starts with the error's message as its operand, and the
+        // locals stored before the range it covers
+        for &(_, target) in handlers.iter().filter(|(start, _)| *start == b) {
+            let entry = FlowState {
+                depth: 1,
+                assigned: state.assigned.clone(),
+            };
+            let flow = flow_into(&mut entries, &mut work_list, target, &entry);
+            report_mismatch(function, starts[target], flow, errors);
+        }
+        let terminator = function.terminator_instruction(b);
+        let first = starts[b + 1] - block.instructions.len() - terminator.is_some() as usize;
+        for (pos, instruction) in (first..).zip(block.instructions.iter().chain(&terminator)) {
+            let (pops, pushes) = instruction.stack_effect();
+            if state.depth < pops {
+                push_error(
+                    errors,
+                    VerifyError::StackUnderflow {
+                        function: function.name.clone(),
+                        index: pos,
+                    },
+                );
+                continue 'blocks;
+            }
+            state.depth = state.depth - pops + pushes;
+            match instruction {
+                IRInstruction::Load(name)
+                    if locals.contains(name) && !state.assigned.contains(name) =>
+                {
+                    push_error(
+                        errors,
+                        VerifyError::UninitializedLocal {
+                            function: function.name.clone(),
+                            name: name.clone(),
+                            index: pos,
+                        },
+                    );
+                }
+                IRInstruction::Store(name) => {
+                    state.assigned.insert(name.clone());
+                }
+                _ => {}
+            }
+        }
+
+        let falls_through = matches!(
+            block.terminator,
+            Terminator::Fallthrough | Terminator::JumpIf(_)
+        );
+        if falls_through && b + 1 == block_count {
+            push_error(
+                errors,
+                VerifyError::MissingReturn {
+                    function: function.name.clone(),
+                },
+            );
+        }
+
+        for next in function.successors(b) {
+            let flow = flow_into(&mut entries, &mut work_list, next, &state);
+            report_mismatch(function, starts[next], flow, errors);
+        }
+    }
+}
+
+/// Merge `state` into the state on entry to block `next`, revisiting it if
+/// that changed. If the block is reached with another stack depth, it is
+/// left as it was and the two depths returned.
+fn flow_into(
+    entries: &mut [Option<FlowState>],
+    work_list: &mut Vec<usize>,
+    next: usize,
+    state: &FlowState,
+) -> Result<(), (usize, usize)> {
+    let merged = match &entries[next] {
+        Some(existing) => existing.merge(state)?,
+        None => state.clone(),
+    };
+    if entries[next].as_ref() != Some(&merged) {
+        entries[next] = Some(merged);
+        work_list.push(next);
+    }
+    Ok(())
+}
+
+fn report_mismatch(
+    function: &IRFunction,
+    index: usize,
+    flow: Result<(), (usize, usize)>,
+    errors: &mut Vec<VerifyError>,
+) {
+    if let Err(depths) = flow {
+        push_error(
+            errors,
+            VerifyError::StackMismatch {
+                function: function.name.clone(),
+                index,
+                depths,
+            },
+        );
+    }
+}
+
+fn push_error(errors: &mut Vec<VerifyError>, error: VerifyError) {
+    if !errors.contains(&error) {
+        errors.push(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{lower_ast, CType, Constant, ExceptionHandler, ExternFunction, IRFunction};
+    use crate::lexer::tokenize;
+    use crate::parser::parse;
+
+    fn module_with(params: Vec<&str>, instructions: Vec<IRInstruction>) -> IRModule {
+        let params = params.into_iter().map(String::from).collect();
+        let mut function = IRFunction::new("test".to_string(), params);
+        function.set_instructions(instructions).unwrap();
+        IRModule {
+            functions: vec![function],
+            constants: vec![Constant::Number(1.0), Constant::String("%d".to_string())],
+            externs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_lowered_program_verifies() {
+        let source = "function fib(n) { if (n <= 1) { return n; } return fib(n - 1) + fib(n - 2); }
+                      function main() { let i = 0; while (i < 3) { print(i); }
+                      print(i > 1 || i < 0, i && fib(2)); return fib(10); }";
+        let module = lower_ast(parse(tokenize(source)));
+        assert_eq!(verify(&module), Ok(()));
+    }
+
+    #[test]
+    fn test_missing_return() {
+        let module = module_with(
+            vec![],
+            vec![
+                IRInstruction::PushConst(0),
+                IRInstruction::JumpIf("end".to_string()),
+                IRInstruction::PushConst(0),
+                IRInstruction::Return(true),
+                IRInstruction::Label("end".to_string()),
+            ],
+        );
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::MissingReturn {
+                function: "test".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_stack_mismatch() {
+        // One path to the label leaves the condition's copy on the stack
+        let module = module_with(
+            vec!["x"],
+            vec![
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::PushConst(0),
+                IRInstruction::Dup,
+                IRInstruction::JumpIf("end".to_string()),
+                IRInstruction::Pop,
+                IRInstruction::Label("end".to_string()),
+                IRInstruction::Return(true),
+            ],
+        );
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::StackMismatch {
+                function: "test".to_string(),
+                index: 5,
+                depths: (2, 1),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_stack_underflow() {
+        let module = module_with(
+            vec!["x"],
+            vec![
+                IRInstruction::Load("x".to_string()),
+                IRInstruction::Binary(crate::ir::BinaryOp::Add),
+                IRInstruction::Return(true),
+            ],
+        );
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::StackUnderflow {
+                function: "test".to_string(),
+                index: 1,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_load_before_store_on_some_path() {
+        let module = module_with(
+            vec!["flag"],
+            vec![
+                IRInstruction::Load("flag".to_string()),
+                IRInstruction::JumpIf("skip".to_string()),
+                IRInstruction::PushConst(0),
+                IRInstruction::Store("y".to_string()),
+                IRInstruction::Label("skip".to_string()),
+                IRInstruction::Load("y".to_string()),
+                IRInstruction::Return(true),
+            ],
+        );
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::UninitializedLocal {
+                function: "test".to_string(),
+                name: "y".to_string(),
+                index: 5,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_extern_arity() {
+        let mut module = module_with(
+            vec![],
+            vec![
+                IRInstruction::PushConst(1),
+                IRInstruction::PushConst(0),
+                IRInstruction::CallNative("printf".to_string(), 2),
+                IRInstruction::CallNative("puts".to_string(), 0),
+                IRInstruction::Return(true),
+            ],
+        );
+        for (name, variadic) in [("printf", true), ("puts", false)] {
+            module.externs.push(ExternFunction {
+                name: name.to_string(),
+                params: vec![CType::String],
+                variadic,
+                returns: CType::Int,
+            });
+        }
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::ExternArity {
+                function: "test".to_string(),
+                name: "puts".to_string(),
+                argc: 0,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_undefined_constant() {
+        let module = module_with(
+            vec![],
+            vec![IRInstruction::PushConst(2), IRInstruction::Return(true)],
+        );
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::UndefinedConstant {
+                function: "test".to_string(),
+                constant: 2,
+                index: 0,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_undefined_function() {
+        let module = module_with(
+            vec![],
+            vec![IRInstruction::Call(1, 0), IRInstruction::Return(true)],
+        );
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::UndefinedFunction {
+                function: "test".to_string(),
+                callee: 1,
+                index: 0,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_closures() {
+        let mut module = module_with(
+            vec![],
+            vec![
+                IRInstruction::PushConst(0),
+                IRInstruction::MakeClosure("test".to_string(), 1),
+                IRInstruction::StoreUpvalue(0),
+                IRInstruction::LoadUpvalue(1),
+                IRInstruction::Return(true),
+            ],
+        );
+        module.functions[0].captures.push("x".to_string());
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::UndefinedUpvalue {
+                function: "test".to_string(),
+                upvalue: 1,
+                index: 3,
+            }])
+        );
+
+        module.functions[0].captures.clear();
+        let errors = verify(&module).unwrap_err();
+        assert_eq!(
+            errors[0],
+            VerifyError::ClosureMismatch {
+                function: "test".to_string(),
+                name: "test".to_string(),
+                count: 1,
+                index: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_exception_handlers() {
+        let mut module = module_with(
+            vec![],
+            vec![
+                IRInstruction::Label("try".to_string()),
+                IRInstruction::CallNative("f".to_string(), 0),
+                IRInstruction::Return(true),
+                IRInstruction::Label("catch".to_string()),
+                IRInstruction::Return(true),
+            ],
+        );
+        // The handler is entered with the error on the stack
+        let handler = ExceptionHandler {
+            start_label: "try".to_string(),
+            end_label: "catch".to_string(),
+            handler_label: "catch".to_string(),
+            exception_type: "Error".to_string(),
+        };
+        module.functions[0].exception_table.push(handler.clone());
+        assert_eq!(verify(&module), Ok(()));
+
+        module.functions[0].exception_table[0] = ExceptionHandler {
+            handler_label: "finally".to_string(),
+            ..handler
+        };
+        assert_eq!(
+            verify(&module),
+            Err(vec![VerifyError::UndefinedLabel {
+                function: "test".to_string(),
+                label: "finally".to_string(),
+            }])
+        );
+    }
+}
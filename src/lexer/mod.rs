@@ -1,9 +1,16 @@
+//! The lexer. Tokens borrow their text from the source: an identifier is
+//! a slice of it, and so is a string literal unless it has escapes.
+
+use crate::diagnostics::{Diagnostic, Span};
+use std::borrow::Cow;
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq)]
-pub enum TokenType {
+pub enum TokenType<'src> {
     // Literals
     Number(f64),
-    StringLiteral(String),
-    Identifier(String),
+    StringLiteral(Cow<'src, str>),
+    Identifier(&'src str),
     True,
     False,
     Null,
@@ -15,6 +22,8 @@ pub enum TokenType {
     If,
     Else,
     While,
+    For,
+    Extern,
 
     // Operators
     Plus,
@@ -25,6 +34,8 @@ pub enum TokenType {
     Equal,
     EqualEqual,
     NotEqual,
+    StrictEqual,
+    StrictNotEqual,
     LessThan,    // Changed from Less
     GreaterThan, // Changed from Greater
     LessEqual,
@@ -42,320 +53,431 @@ pub enum TokenType {
     Comma,
     QuestionMark,
     Colon,
+    Dot,      // .
+    Ellipsis, // ...
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Token {
-    pub token_type: TokenType,
+pub struct Token<'src> {
+    pub token_type: TokenType<'src>,
     pub line: usize,
     pub column: usize,
 }
 
-impl Token {
-    fn new(token_type: TokenType, line: usize, column: usize) -> Self {
+impl<'src> Token<'src> {
+    fn new(token_type: TokenType<'src>, line: usize, column: usize) -> Self {
         Token {
             token_type,
             line,
             column,
         }
     }
+
+    /// Where the token is in the source, as far as its text can be told
+    /// from the token: escapes in strings make it longer.
+    pub fn span(&self) -> Span {
+        match &self.token_type {
+            // The column of a string is that of its first character
+            TokenType::StringLiteral(_) => Span::new(
+                self.line,
+                self.column - 1,
+                self.token_type.to_string().chars().count(),
+            ),
+            token_type => Span::new(
+                self.line,
+                self.column,
+                token_type.to_string().chars().count(),
+            ),
+        }
+    }
 }
 
-pub fn tokenize(source: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut chars = source.chars().peekable();
-    let mut line = 1;
-    let mut column = 1;
+/// The token as it is written in the source.
+impl fmt::Display for TokenType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            TokenType::Number(n) => return write!(f, "{}", n),
+            TokenType::StringLiteral(s) => return write!(f, "{:?}", s),
+            TokenType::Identifier(name) => name,
+            TokenType::True => "true",
+            TokenType::False => "false",
+            TokenType::Null => "null",
+            TokenType::Function => "function",
+            TokenType::Let => "let",
+            TokenType::Return => "return",
+            TokenType::If => "if",
+            TokenType::Else => "else",
+            TokenType::While => "while",
+            TokenType::For => "for",
+            TokenType::Extern => "extern",
+            TokenType::Plus => "+",
+            TokenType::Minus => "-",
+            TokenType::Multiply => "*",
+            TokenType::Divide => "/",
+            TokenType::Modulo => "%",
+            TokenType::Equal => "=",
+            TokenType::EqualEqual => "==",
+            TokenType::NotEqual => "!=",
+            TokenType::StrictEqual => "===",
+            TokenType::StrictNotEqual => "!==",
+            TokenType::LessThan => "<",
+            TokenType::GreaterThan => ">",
+            TokenType::LessEqual => "<=",
+            TokenType::GreaterEqual => ">=",
+            TokenType::Not => "!",
+            TokenType::And => "&&",
+            TokenType::Or => "||",
+            TokenType::LParen => "(",
+            TokenType::RParen => ")",
+            TokenType::LBrace => "{",
+            TokenType::RBrace => "}",
+            TokenType::Semicolon => ";",
+            TokenType::Comma => ",",
+            TokenType::QuestionMark => "?",
+            TokenType::Colon => ":",
+            TokenType::Dot => ".",
+            TokenType::Ellipsis => "...",
+        };
+        f.write_str(text)
+    }
+}
 
-    while let Some(&c) = chars.peek() {
-        match c {
-            // Skip whitespace
-            ' ' | '\t' | '\r' => {
-                column += 1;
-                chars.next();
+/// Raise a lexical error at one position.
+fn error_at(line: usize, column: usize, len: usize, message: String, label: &str) -> ! {
+    Diagnostic::error(message)
+        .with_label(Span::new(line, column, len), label)
+        .raise()
+}
+
+/// A comment, which the tokens leave out but the formatter keeps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// Line the comment starts on.
+    pub line: usize,
+    /// The comment as written, `//` or `/* */` included.
+    pub text: String,
+}
+
+/// A place in the source, which looks at it byte by byte, counting lines
+/// and columns as it goes. Columns count characters, not bytes.
+struct Cursor<'src> {
+    source: &'src str,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'src> Cursor<'src> {
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    /// The character `offset` bytes on, for bytes past ASCII.
+    fn char_at(&self, offset: usize) -> Option<char> {
+        self.source.get(self.pos + offset..)?.chars().next()
+    }
+
+    /// Move past the next character, returning its first byte.
+    fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        // The rest of a character past ASCII
+        while self.peek().is_some_and(|next| next & 0xC0 == 0x80) {
+            self.pos += 1;
+        }
+        Some(byte)
+    }
+
+    /// Move past `byte` if it is next.
+    fn eat(&mut self, byte: u8) -> bool {
+        let next = self.peek() == Some(byte);
+        if next {
+            self.bump();
+        }
+        next
+    }
+
+    fn slice(&self, start: usize) -> &'src str {
+        &self.source[start..self.pos]
+    }
+
+    /// Whether the identifier being read goes on past the next byte.
+    fn identifier_continues(&self) -> bool {
+        match self.peek() {
+            Some(byte) if byte.is_ascii() => byte.is_ascii_alphanumeric() || byte == b'_',
+            Some(_) => self.char_at(0).is_some_and(char::is_alphanumeric),
+            None => false,
+        }
+    }
+}
+
+fn keyword_or_identifier(text: &str) -> TokenType<'_> {
+    match text {
+        "function" => TokenType::Function,
+        "let" => TokenType::Let,
+        "return" => TokenType::Return,
+        "if" => TokenType::If,
+        "else" => TokenType::Else,
+        "while" => TokenType::While,
+        "for" => TokenType::For,
+        "extern" => TokenType::Extern,
+        "true" => TokenType::True,
+        "false" => TokenType::False,
+        "null" => TokenType::Null,
+        _ => TokenType::Identifier(text),
+    }
+}
+
+/// The single-character token `byte` is, if it is one.
+fn punctuation(byte: u8) -> Option<TokenType<'static>> {
+    Some(match byte {
+        b'+' => TokenType::Plus,
+        b'-' => TokenType::Minus,
+        b'*' => TokenType::Multiply,
+        b'%' => TokenType::Modulo,
+        b'(' => TokenType::LParen,
+        b')' => TokenType::RParen,
+        b'{' => TokenType::LBrace,
+        b'}' => TokenType::RBrace,
+        b';' => TokenType::Semicolon,
+        b',' => TokenType::Comma,
+        b'?' => TokenType::QuestionMark,
+        b':' => TokenType::Colon,
+        _ => return None,
+    })
+}
+
+/// The rest of a string literal, up to the closing `quote`. It borrows
+/// from the source unless escapes make it differ.
+fn string_literal<'src>(cursor: &mut Cursor<'src>, quote: u8) -> Cow<'src, str> {
+    // Text since the last escape, still to be copied if there was one
+    let mut run = cursor.pos;
+    let mut unescaped: Option<String> = None;
+    let end = loop {
+        let end = cursor.pos;
+        match cursor.bump() {
+            None => break end,
+            Some(byte) if byte == quote => break end,
+            Some(b'\\') => {
+                let text = unescaped.get_or_insert_with(String::new);
+                text.push_str(&cursor.source[run..end]);
+                let escaped = cursor.char_at(0);
+                cursor.bump();
+                match escaped {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some(c @ ('\\' | '"' | '\'')) => text.push(c),
+                    Some(c) => error_at(
+                        cursor.line,
+                        cursor.column - 2,
+                        2,
+                        format!("Invalid escape sequence: \\{}", c),
+                        "unknown escape",
+                    ),
+                    None => {}
+                }
+                run = cursor.pos;
             }
+            Some(_) => {}
+        }
+    };
+    match unescaped {
+        None => Cow::Borrowed(&cursor.source[run..end]),
+        Some(mut text) => {
+            text.push_str(&cursor.source[run..end]);
+            Cow::Owned(text)
+        }
+    }
+}
+
+pub fn tokenize(source: &str) -> Vec<Token<'_>> {
+    tokenize_with_comments(source).0
+}
 
-            '\n' => {
-                line += 1;
-                column = 1;
-                chars.next();
+/// Tokenize `source`, also returning its comments in order.
+pub fn tokenize_with_comments(source: &str) -> (Vec<Token<'_>>, Vec<Comment>) {
+    let mut tokens = Vec::new();
+    let mut comments = Vec::new();
+    let mut cursor = Cursor {
+        source,
+        pos: 0,
+        line: 1,
+        column: 1,
+    };
+
+    while let Some(byte) = cursor.peek() {
+        let (start, line, column) = (cursor.pos, cursor.line, cursor.column);
+        let token_type = match byte {
+            // Skip whitespace
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                cursor.bump();
+                continue;
             }
 
             // Numbers
-            '0'..='9' => {
-                let mut number = String::new();
-                let start_column = column;
-
-                while let Some(&c) = chars.peek() {
-                    if c.is_digit(10) || c == '.' {
-                        number.push(chars.next().unwrap());
-                        column += 1;
-                    } else {
-                        break;
-                    }
+            b'0'..=b'9' => {
+                while matches!(cursor.peek(), Some(b'0'..=b'9' | b'.')) {
+                    cursor.bump();
+                }
+                let text = cursor.slice(start);
+                match text.parse() {
+                    Ok(n) => TokenType::Number(n),
+                    Err(_) => error_at(
+                        line,
+                        column,
+                        text.len(),
+                        format!("Invalid number: {}", text),
+                        "not a number",
+                    ),
                 }
-
-                tokens.push(Token::new(
-                    TokenType::Number(number.parse().unwrap()),
-                    line,
-                    start_column,
-                ));
             }
 
             // Identifiers and Keywords
-            'a'..='z' | 'A'..='Z' | '_' => {
-                let mut ident = String::new();
-                let start_column = column;
-
-                while let Some(&c) = chars.peek() {
-                    if c.is_alphanumeric() || c == '_' {
-                        ident.push(chars.next().unwrap());
-                        column += 1;
-                    } else {
-                        break;
-                    }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while cursor.identifier_continues() {
+                    cursor.bump();
                 }
-
-                let token_type = match ident.as_str() {
-                    "function" => TokenType::Function,
-                    "let" => TokenType::Let,
-                    "return" => TokenType::Return,
-                    "if" => TokenType::If,
-                    "else" => TokenType::Else,
-                    "while" => TokenType::While,
-                    "true" => TokenType::True,
-                    "false" => TokenType::False,
-                    "null" => TokenType::Null,
-                    _ => TokenType::Identifier(ident),
-                };
-
-                tokens.push(Token::new(token_type, line, start_column));
+                keyword_or_identifier(cursor.slice(start))
             }
 
-            // String Literals
-            '"' | '\'' => {
-                chars.next(); // consume quote
-                column += 1;
-                let quote = c;
-                let mut string = String::new();
-                let start_column = column;
-
-                while let Some(&c) = chars.peek() {
-                    chars.next();
-                    column += 1;
-
-                    if c == quote {
-                        break;
-                    } else if c == '\\' {
-                        if let Some(&escaped) = chars.peek() {
-                            chars.next();
-                            column += 1;
-                            match escaped {
-                                'n' => string.push('\n'),
-                                't' => string.push('\t'),
-                                'r' => string.push('\r'),
-                                '\\' => string.push('\\'),
-                                '"' => string.push('"'),
-                                '\'' => string.push('\''),
-                                _ => panic!("Invalid escape sequence: \\{}", escaped),
-                            }
-                        }
-                    } else {
-                        string.push(c);
-                    }
-                }
-
+            // String Literals. The column of a string is that of its first
+            // character.
+            b'"' | b'\'' => {
+                cursor.bump();
+                let string = string_literal(&mut cursor, byte);
                 tokens.push(Token::new(
                     TokenType::StringLiteral(string),
                     line,
-                    start_column,
+                    column + 1,
                 ));
+                continue;
             }
 
             // Comments
-            '/' => {
-                chars.next();
-                column += 1;
-                match chars.peek() {
-                    Some(&'/') => {
-                        // Single-line comment
-                        while let Some(&c) = chars.peek() {
-                            if c == '\n' {
-                                break;
-                            }
-                            chars.next();
-                            column += 1;
-                        }
+            b'/' => {
+                cursor.bump();
+                if cursor.eat(b'/') {
+                    // Single-line comment
+                    while !matches!(cursor.peek(), None | Some(b'\n')) {
+                        cursor.bump();
                     }
-                    Some(&'*') => {
-                        // Multi-line comment
-                        chars.next();
-                        column += 1;
-                        let mut nesting = 1;
-                        while nesting > 0 {
-                            match chars.next() {
-                                Some('*') => {
-                                    if let Some(&'/') = chars.peek() {
-                                        chars.next();
-                                        nesting -= 1;
-                                    }
-                                    column += 1;
-                                }
-                                Some('/') => {
-                                    if let Some(&'*') = chars.peek() {
-                                        chars.next();
-                                        nesting += 1;
-                                    }
-                                    column += 1;
-                                }
-                                Some('\n') => {
-                                    line += 1;
-                                    column = 1;
-                                }
-                                Some(_) => column += 1,
-                                None => panic!("Unterminated multi-line comment"),
-                            }
+                    comments.push(Comment {
+                        line,
+                        text: cursor.slice(start).trim_end().to_string(),
+                    });
+                    continue;
+                } else if cursor.eat(b'*') {
+                    // Multi-line comment
+                    let mut nesting = 1;
+                    while nesting > 0 {
+                        match cursor.bump() {
+                            Some(b'*') if cursor.eat(b'/') => nesting -= 1,
+                            Some(b'/') if cursor.eat(b'*') => nesting += 1,
+                            Some(_) => {}
+                            None => error_at(
+                                line,
+                                column,
+                                2,
+                                "Unterminated multi-line comment".to_string(),
+                                "comment starts here",
+                            ),
                         }
                     }
-                    _ => tokens.push(Token::new(TokenType::Divide, line, column - 1)),
+                    comments.push(Comment {
+                        line,
+                        text: cursor.slice(start).to_string(),
+                    });
+                    continue;
                 }
+                TokenType::Divide
             }
 
-            // Operators and punctuation
-            '+' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Plus, line, column));
-                column += 1;
-            }
-            '-' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Minus, line, column));
-                column += 1;
-            }
-            '*' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Multiply, line, column));
-                column += 1;
-            }
-            '%' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Modulo, line, column));
-                column += 1;
-            }
-            '(' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::LParen, line, column));
-                column += 1;
-            }
-            ')' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::RParen, line, column));
-                column += 1;
-            }
-            '{' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::LBrace, line, column));
-                column += 1;
-            }
-            '}' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::RBrace, line, column));
-                column += 1;
-            }
-            ';' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Semicolon, line, column));
-                column += 1;
-            }
-            ',' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Comma, line, column));
-                column += 1;
-            }
-            '?' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::QuestionMark, line, column));
-                column += 1;
-            }
-            ':' => {
-                chars.next();
-                tokens.push(Token::new(TokenType::Colon, line, column));
-                column += 1;
-            }
-
-            // Two-character operators
-            '=' => {
-                chars.next();
-                column += 1;
-                if let Some(&'=') = chars.peek() {
-                    chars.next();
-                    column += 1;
-                    tokens.push(Token::new(TokenType::EqualEqual, line, column - 2));
-                } else {
-                    tokens.push(Token::new(TokenType::Equal, line, column - 1));
-                }
-            }
-            '!' => {
-                chars.next();
-                column += 1;
-                if let Some(&'=') = chars.peek() {
-                    chars.next();
-                    column += 1;
-                    tokens.push(Token::new(TokenType::NotEqual, line, column - 2));
+            // `.` reads a member, as in `counter.add`; more dots only
+            // make `...`
+            b'.' => {
+                cursor.bump();
+                if cursor.eat(b'.') {
+                    if !cursor.eat(b'.') {
+                        error_at(
+                            line,
+                            column,
+                            2,
+                            "Expected '...'".to_string(),
+                            "only `...` uses more than one dot",
+                        );
+                    }
+                    TokenType::Ellipsis
                 } else {
-                    tokens.push(Token::new(TokenType::Not, line, column - 1));
+                    TokenType::Dot
                 }
             }
-            '<' => {
-                chars.next();
-                column += 1;
-                if let Some(&'=') = chars.peek() {
-                    chars.next();
-                    column += 1;
-                    tokens.push(Token::new(TokenType::LessEqual, line, column - 2));
-                } else {
-                    tokens.push(Token::new(TokenType::LessThan, line, column - 1));
+
+            // Two- and three-character operators
+            b'=' | b'!' => {
+                cursor.bump();
+                let not = byte == b'!';
+                match (cursor.eat(b'='), cursor.eat(b'=')) {
+                    (true, true) if not => TokenType::StrictNotEqual,
+                    (true, true) => TokenType::StrictEqual,
+                    (true, false) if not => TokenType::NotEqual,
+                    (true, false) => TokenType::EqualEqual,
+                    _ if not => TokenType::Not,
+                    _ => TokenType::Equal,
                 }
             }
-            '>' => {
-                chars.next();
-                column += 1;
-                if let Some(&'=') = chars.peek() {
-                    chars.next();
-                    column += 1;
-                    tokens.push(Token::new(TokenType::GreaterEqual, line, column - 2));
-                } else {
-                    tokens.push(Token::new(TokenType::GreaterThan, line, column - 1));
+            b'<' | b'>' => {
+                cursor.bump();
+                match (byte, cursor.eat(b'=')) {
+                    (b'<', true) => TokenType::LessEqual,
+                    (b'<', false) => TokenType::LessThan,
+                    (_, true) => TokenType::GreaterEqual,
+                    (_, false) => TokenType::GreaterThan,
                 }
             }
-            '&' => {
-                chars.next();
-                column += 1;
-                if let Some(&'&') = chars.peek() {
-                    chars.next();
-                    column += 1;
-                    tokens.push(Token::new(TokenType::And, line, column - 2));
-                } else {
-                    panic!("Expected '&&', got single '&'");
+            b'&' | b'|' => {
+                cursor.bump();
+                if !cursor.eat(byte) {
+                    let op = byte as char;
+                    error_at(
+                        line,
+                        column,
+                        1,
+                        format!("Expected '{}{}', got single '{}'", op, op, op),
+                        "bitwise operators are not supported",
+                    );
                 }
-            }
-            '|' => {
-                chars.next();
-                column += 1;
-                if let Some(&'|') = chars.peek() {
-                    chars.next();
-                    column += 1;
-                    tokens.push(Token::new(TokenType::Or, line, column - 2));
+                if byte == b'&' {
+                    TokenType::And
                 } else {
-                    panic!("Expected '||', got single '|'");
+                    TokenType::Or
                 }
             }
 
-            _ => panic!("Unexpected character: {}", c),
-        }
+            _ => match punctuation(byte) {
+                Some(token_type) => {
+                    cursor.bump();
+                    token_type
+                }
+                None => error_at(
+                    line,
+                    column,
+                    1,
+                    format!("Unexpected character: {}", cursor.char_at(0).unwrap()),
+                    "not part of any token",
+                ),
+            },
+        };
+        tokens.push(Token::new(token_type, line, column));
     }
 
-    tokens
+    (tokens, comments)
 }
 
 #[cfg(test)]
@@ -368,15 +490,57 @@ mod tests {
         let tokens = tokenize(input);
 
         assert_eq!(tokens[0].token_type, TokenType::Let);
-        assert_eq!(tokens[1].token_type, TokenType::Identifier("x".to_string()));
+        assert_eq!(tokens[1].token_type, TokenType::Identifier("x"));
         assert_eq!(tokens[2].token_type, TokenType::Equal);
         assert_eq!(tokens[3].token_type, TokenType::Number(5.0));
         assert_eq!(tokens[4].token_type, TokenType::Semicolon);
     }
 
+    #[test]
+    fn test_invalid_number() {
+        let payload = std::panic::catch_unwind(|| tokenize("let x =\n 1.2.3;")).unwrap_err();
+        let diagnostic = Diagnostic::from_panic(payload);
+        assert_eq!(diagnostic.message, "Invalid number: 1.2.3");
+        assert_eq!(diagnostic.labels[0].span, Span::new(2, 2, 5));
+    }
+
+    #[test]
+    fn test_member_access() {
+        let tokens = tokenize("counter . add(f(...rest))");
+        assert_eq!(tokens[0].token_type, TokenType::Identifier("counter"));
+        assert_eq!(tokens[1].token_type, TokenType::Dot);
+        assert_eq!(tokens[2].token_type, TokenType::Identifier("add"));
+        assert_eq!(tokens[4].token_type, TokenType::Identifier("f"));
+        assert_eq!(tokens[6].token_type, TokenType::Ellipsis);
+
+        let payload = std::panic::catch_unwind(|| tokenize("f(a..b)")).unwrap_err();
+        let diagnostic = Diagnostic::from_panic(payload);
+        assert_eq!(diagnostic.message, "Expected '...'");
+        assert_eq!(diagnostic.labels[0].span, Span::new(1, 4, 2));
+    }
+
+    #[test]
+    fn test_tokens_borrow_the_source() {
+        let tokens = tokenize("\"héllo\" 'a\\tb' naïve x");
+        assert!(matches!(
+            &tokens[0].token_type,
+            TokenType::StringLiteral(Cow::Borrowed("héllo"))
+        ));
+        assert!(matches!(
+            &tokens[1].token_type,
+            TokenType::StringLiteral(Cow::Owned(s)) if s == "a\tb"
+        ));
+        assert_eq!(tokens[2].token_type, TokenType::Identifier("naïve"));
+        // Columns count characters
+        assert_eq!(
+            (tokens[1].column, tokens[2].column, tokens[3].column),
+            (10, 16, 22)
+        );
+    }
+
     #[test]
     fn test_operators() {
-        let input = "+ - * / = == != < > <= >=";
+        let input = "+ - * / % = == != === !== < > <= >=";
         let tokens = tokenize(input);
 
         let expected = vec![
@@ -384,9 +548,12 @@ mod tests {
             TokenType::Minus,
             TokenType::Multiply,
             TokenType::Divide,
+            TokenType::Modulo,
             TokenType::Equal,
             TokenType::EqualEqual,
             TokenType::NotEqual,
+            TokenType::StrictEqual,
+            TokenType::StrictNotEqual,
             TokenType::LessThan,
             TokenType::GreaterThan,
             TokenType::LessEqual,
@@ -400,7 +567,7 @@ mod tests {
 
     #[test]
     fn test_keywords() {
-        let input = "function let return if else while true false null";
+        let input = "function let return if else while for true false null extern";
         let tokens = tokenize(input);
 
         let expected = vec![
@@ -410,13 +577,32 @@ mod tests {
             TokenType::If,
             TokenType::Else,
             TokenType::While,
+            TokenType::For,
             TokenType::True,
             TokenType::False,
             TokenType::Null,
+            TokenType::Extern,
         ];
 
         for (i, expected_type) in expected.into_iter().enumerate() {
             assert_eq!(tokens[i].token_type, expected_type);
         }
     }
+
+    #[test]
+    fn test_comments() {
+        let (tokens, comments) =
+            tokenize_with_comments("// one\nx; // two  \n/* three\n /* nested */ */ y;");
+        assert_eq!(tokens.len(), 4);
+        let texts: Vec<(usize, &str)> =
+            comments.iter().map(|c| (c.line, c.text.as_str())).collect();
+        assert_eq!(
+            texts,
+            [
+                (1, "// one"),
+                (2, "// two"),
+                (3, "/* three\n /* nested */ */")
+            ]
+        );
+    }
 }
@@ -0,0 +1,19 @@
+pub mod check;
+pub mod codegen;
+pub mod debug;
+pub mod diagnostics;
+pub mod ffi;
+pub mod ir;
+pub mod lexer;
+pub mod lint;
+pub mod log;
+#[cfg(feature = "node")]
+pub mod node;
+pub mod optimizer;
+pub mod parallel;
+pub mod parser;
+#[cfg(feature = "playground")]
+pub mod playground;
+pub mod profile;
+pub mod timings;
+pub mod vm;
@@ -0,0 +1,490 @@
+//! Warnings about code that compiles but is likely wrong: variables and
+//! parameters never read, statements after a `return`, names read or
+//! called without being declared, and variables read before their `let`.
+//!
+//! The language has no assignment besides `let`, so a name is declared by
+//! a `let`, as a parameter, or, for calls, by a function or extern
+//! declaration. Variables are scoped to their function, as the VM does;
+//! top-level `let`s declare globals any function may read, and a function
+//! declared inside another may read the variables declared before it.
+
+use crate::diagnostics::{Diagnostic, Span};
+use crate::parser::{ExprId, Expression, Statement, StmtId, AST};
+use std::collections::{HashMap, HashSet};
+
+/// Functions the VM provides without a declaration.
+const BUILTINS: &[&str] = &["print"];
+
+/// The warnings for `ast`, parsed from `source`, in source order.
+pub fn lint(ast: &AST, source: &str) -> Vec<Diagnostic> {
+    lint_with(ast, source, &[])
+}
+
+/// The warnings for one of several files compiled together, which may
+/// call the `functions` the others declare.
+pub fn lint_with<'a>(ast: &'a AST, source: &'a str, functions: &[&'a str]) -> Vec<Diagnostic> {
+    let mut functions: HashSet<&str> = BUILTINS.iter().chain(functions).copied().collect();
+    let mut globals = HashMap::new();
+    for &statement in &ast.statements {
+        let (line, statement) = located(ast, statement, 0);
+        match statement {
+            Statement::FunctionDeclaration { name, .. }
+            | Statement::ExternDeclaration { name, .. } => {
+                functions.insert(name);
+            }
+            Statement::Let { name, .. } => {
+                globals.entry(name.as_str()).or_insert(line);
+            }
+            _ => {}
+        }
+    }
+
+    let mut linter = Linter {
+        ast,
+        source,
+        functions,
+        globals,
+        global_reads: HashSet::new(),
+        warnings: Vec::new(),
+    };
+    let mut top_level = Scope::default();
+    for &statement in &ast.statements {
+        let (line, statement) = located(ast, statement, 0);
+        match statement {
+            Statement::FunctionDeclaration { params, body, .. } => {
+                linter.function(line, params, body)
+            }
+            // Globals are set by the time anything reads them
+            Statement::Let { initializer, .. } => {
+                linter.expression(&mut top_level, line, *initializer)
+            }
+            statement => linter.statement(&mut top_level, line, statement),
+        }
+    }
+
+    let globals = linter.globals.clone();
+    for (name, line) in globals {
+        if !linter.global_reads.contains(name) {
+            linter.unused("variable", name, line);
+        }
+    }
+
+    // Maps leave the unused names in no particular order
+    let mut warnings = linter.warnings;
+    warnings.sort_by_key(|warning| {
+        let span = warning.labels[0].span;
+        (span.line, span.column)
+    });
+    warnings
+}
+
+/// The line a statement starts on and the statement inside its location,
+/// or `line` for one without.
+fn located(ast: &AST, statement: StmtId, line: usize) -> (usize, &Statement) {
+    match &ast[statement] {
+        Statement::Located {
+            line, statement, ..
+        } => (*line, &ast[*statement]),
+        statement => (line, statement),
+    }
+}
+
+/// Whether control never goes past `statement`.
+fn always_returns(ast: &AST, statement: &Statement) -> bool {
+    match statement {
+        Statement::Return(_) => true,
+        Statement::If {
+            then_branch,
+            else_branch: Some(else_branch),
+            ..
+        } => block_returns(ast, then_branch) && block_returns(ast, else_branch),
+        Statement::Block(statements) => block_returns(ast, statements),
+        Statement::Located { statement, .. } => always_returns(ast, &ast[*statement]),
+        _ => false,
+    }
+}
+
+fn block_returns(ast: &AST, statements: &[StmtId]) -> bool {
+    statements
+        .iter()
+        .any(|&statement| always_returns(ast, &ast[statement]))
+}
+
+/// The variables of the function being linted.
+#[derive(Default)]
+struct Scope<'a> {
+    /// Line of the first `let` of each variable anywhere in the function.
+    locals: HashMap<&'a str, usize>,
+    /// Parameters and variables whose `let` has been passed.
+    declared: HashSet<&'a str>,
+    reads: HashSet<&'a str>,
+    /// Functions declared in the function and those it is declared in.
+    functions: HashSet<&'a str>,
+}
+
+struct Linter<'a> {
+    ast: &'a AST,
+    source: &'a str,
+    functions: HashSet<&'a str>,
+    /// Line of the first top-level `let` of each global.
+    globals: HashMap<&'a str, usize>,
+    global_reads: HashSet<&'a str>,
+    warnings: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    fn name_span(&self, line: usize, name: &str) -> Span {
+        Span::word(self.source, line, name)
+    }
+
+    fn function(&mut self, line: usize, params: &'a [String], body: &'a [StmtId]) {
+        self.function_in(Scope::default(), line, params, body);
+    }
+
+    /// Lint a function whose scope starts as `scope`, and return the scope
+    /// it ends with.
+    fn function_in(
+        &mut self,
+        mut scope: Scope<'a>,
+        line: usize,
+        params: &'a [String],
+        body: &'a [StmtId],
+    ) -> Scope<'a> {
+        collect_lets(self.ast, body, line, &mut scope.locals);
+        scope.declared.extend(params.iter().map(String::as_str));
+        self.statements(&mut scope, line, body);
+
+        for (i, param) in params.iter().enumerate() {
+            // `check` reports a parameter named twice
+            if !scope.reads.contains(param.as_str()) && !params[..i].contains(param) {
+                self.unused("parameter", param, line);
+            }
+        }
+        for (&name, &line) in &scope.locals {
+            if !scope.reads.contains(name) {
+                self.unused("variable", name, line);
+            }
+        }
+        scope
+    }
+
+    /// Lint a function declared inside the one `outer` is the scope of,
+    /// counting what it reads of the variables it captures as read there.
+    fn closure(
+        &mut self,
+        outer: &mut Scope<'a>,
+        line: usize,
+        params: &'a [String],
+        body: &'a [StmtId],
+    ) {
+        let mut scope = Scope {
+            functions: outer.functions.clone(),
+            ..Scope::default()
+        };
+        scope.declared.extend(&outer.declared);
+        let scope = self.function_in(scope, line, params, body);
+        let captured = scope.reads.iter().filter(|name| {
+            outer.declared.contains(*name)
+                && !scope.locals.contains_key(*name)
+                && !params.iter().any(|param| param == *name)
+        });
+        outer.reads.extend(captured);
+    }
+
+    /// Warn about the `kind` `name` declared on `line` and never read,
+    /// unless its name says that is intended.
+    fn unused(&mut self, kind: &str, name: &str, line: usize) {
+        if name.starts_with('_') {
+            return;
+        }
+        let warning = Diagnostic::warning(format!("Unused {} `{}`", kind, name))
+            .with_label(self.name_span(line, name), "never read")
+            .with_note("prefix the name with `_` if it is meant to be unused");
+        self.warnings.push(warning);
+    }
+
+    /// Lint a block, warning once about the statements after one that
+    /// always returns.
+    fn statements(&mut self, scope: &mut Scope<'a>, line: usize, statements: &'a [StmtId]) {
+        let mut return_line = None;
+        let mut warned = false;
+        for &statement in statements {
+            let (line, inner) = located(self.ast, statement, line);
+            if let (Some(return_line), false) = (return_line, warned) {
+                let warning = Diagnostic::warning("Unreachable code")
+                    .with_label(Span::line(line), "unreachable statement")
+                    .with_label(
+                        Span::line(return_line),
+                        "any code after this is unreachable",
+                    );
+                self.warnings.push(warning);
+                warned = true;
+            }
+            self.statement(scope, line, inner);
+            if return_line.is_none() && always_returns(self.ast, inner) {
+                return_line = Some(line);
+            }
+        }
+    }
+
+    fn statement(&mut self, scope: &mut Scope<'a>, line: usize, statement: &'a Statement) {
+        match statement {
+            Statement::Let { name, initializer } => {
+                self.expression(scope, line, *initializer);
+                scope.declared.insert(name);
+            }
+            Statement::Return(value) => {
+                if let Some(value) = value {
+                    self.expression(scope, line, *value);
+                }
+            }
+            Statement::ExpressionStatement(expression) => self.expression(scope, line, *expression),
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.expression(scope, line, *condition);
+                self.statements(scope, line, then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.statements(scope, line, else_branch);
+                }
+            }
+            Statement::While { condition, body } => {
+                self.expression(scope, line, *condition);
+                self.statements(scope, line, body);
+            }
+            Statement::For {
+                initializer,
+                condition,
+                update,
+                body,
+            } => {
+                if let Some(initializer) = initializer {
+                    self.statement(scope, line, &self.ast[*initializer]);
+                }
+                if let Some(condition) = condition {
+                    self.expression(scope, line, *condition);
+                }
+                self.statements(scope, line, body);
+                if let Some(update) = update {
+                    self.statement(scope, line, &self.ast[*update]);
+                }
+            }
+            Statement::Block(statements) => self.statements(scope, line, statements),
+            Statement::Located {
+                line, statement, ..
+            } => self.statement(scope, *line, &self.ast[*statement]),
+            Statement::FunctionDeclaration { name, params, body } => {
+                scope.functions.insert(name);
+                self.closure(scope, line, params, body);
+            }
+            // Nested externs are errors `check` reports
+            Statement::ExternDeclaration { .. } => {}
+        }
+    }
+
+    fn expression(&mut self, scope: &mut Scope<'a>, line: usize, expression: ExprId) {
+        let ast = self.ast;
+        match &ast[expression] {
+            Expression::Identifier(name) => self.read(scope, line, name),
+            Expression::FunctionCall { name, arguments } => {
+                let declared = self.functions.contains(name.as_str())
+                    || scope.functions.contains(name.as_str());
+                if !declared {
+                    self.undeclared(line, name, "called here", "calling it fails at run time");
+                }
+                for &argument in arguments {
+                    self.expression(scope, line, argument);
+                }
+            }
+            Expression::Member { object, .. } => match self.qualified_name(scope, expression) {
+                Some(name) => self.undeclared(line, &name, "read here", "it reads as undefined"),
+                None => self.expression(scope, line, *object),
+            },
+            Expression::MethodCall {
+                object,
+                method,
+                arguments,
+            } => {
+                match self.qualified_name(scope, *object) {
+                    Some(object) => self.undeclared(
+                        line,
+                        &format!("{}.{}", object, method),
+                        "called here",
+                        "calling it fails at run time",
+                    ),
+                    None => self.expression(scope, line, *object),
+                }
+                for &argument in arguments {
+                    self.expression(scope, line, argument);
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                self.expression(scope, line, *left);
+                self.expression(scope, line, *right);
+            }
+            Expression::UnaryOp { expr, .. } => self.expression(scope, line, *expr),
+            Expression::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                self.expression(scope, line, *condition);
+                self.expression(scope, line, *then_expr);
+                self.expression(scope, line, *else_expr);
+            }
+            Expression::Number(_)
+            | Expression::String(_)
+            | Expression::Boolean(_)
+            | Expression::Null => {}
+        }
+    }
+
+    fn read(&mut self, scope: &mut Scope<'a>, line: usize, name: &'a str) {
+        if scope.declared.contains(name) {
+            scope.reads.insert(name);
+        } else if let Some(&let_line) = scope.locals.get(name) {
+            scope.reads.insert(name);
+            let warning = Diagnostic::warning(format!("`{}` is read before its `let`", name))
+                .with_label(self.name_span(line, name), "read here")
+                .with_label(self.name_span(let_line, name), "declared here")
+                .with_note("until then it reads as undefined");
+            self.warnings.push(warning);
+        } else if self.globals.contains_key(name) {
+            self.global_reads.insert(name);
+        } else {
+            self.undeclared(line, name, "read here", "it reads as undefined");
+        }
+    }
+
+    /// The name `expr` spells if it is a member of a name that is not a
+    /// variable, such as `Math.random`, which the VM provides by that name
+    /// unless the host leaves it out.
+    fn qualified_name(&self, scope: &Scope<'a>, expr: ExprId) -> Option<String> {
+        match &self.ast[expr] {
+            Expression::Identifier(name) => {
+                let name = name.as_str();
+                let variable = scope.declared.contains(name)
+                    || scope.locals.contains_key(name)
+                    || self.globals.contains_key(name);
+                (!variable).then(|| name.to_string())
+            }
+            Expression::Member { object, property } => self
+                .qualified_name(scope, *object)
+                .map(|object| format!("{}.{}", object, property)),
+            _ => None,
+        }
+    }
+
+    /// Warn about `name`, used on `line` without being declared.
+    fn undeclared(&mut self, line: usize, name: &str, label: &str, note: &str) {
+        let warning = Diagnostic::warning(format!("`{}` is not declared", name))
+            .with_label(self.name_span(line, name), label)
+            .with_note(note);
+        self.warnings.push(warning);
+    }
+}
+
+/// Add the line of the first `let` of each variable in `statements` to
+/// `locals`, nested blocks included.
+fn collect_lets<'a>(
+    ast: &'a AST,
+    statements: &[StmtId],
+    line: usize,
+    locals: &mut HashMap<&'a str, usize>,
+) {
+    for &statement in statements {
+        let (line, statement) = located(ast, statement, line);
+        match statement {
+            Statement::Let { name, .. } => {
+                locals.entry(name.as_str()).or_insert(line);
+            }
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                collect_lets(ast, then_branch, line, locals);
+                if let Some(else_branch) = else_branch {
+                    collect_lets(ast, else_branch, line, locals);
+                }
+            }
+            Statement::While { body, .. } | Statement::Block(body) => {
+                collect_lets(ast, body, line, locals)
+            }
+            Statement::For {
+                initializer,
+                update,
+                body,
+                ..
+            } => {
+                let clauses: Vec<StmtId> = initializer
+                    .iter()
+                    .chain(body)
+                    .chain(update)
+                    .copied()
+                    .collect();
+                collect_lets(ast, &clauses, line, locals)
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexer, parser};
+
+    fn warnings(source: &str) -> Vec<String> {
+        let ast = parser::parse(lexer::tokenize(source));
+        lint(&ast, source).iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_warnings() {
+        let source = "let limit = 3;\n\
+                      function f(a, b, _c) {\n\
+                      let unused = a;\n\
+                      print(late, y);\n\
+                      let late = 1;\n\
+                      if (a) { return 1; } else { return 2; }\n\
+                      missing();\n\
+                      }";
+        assert_eq!(
+            warnings(source),
+            [
+                "1:5: Unused variable `limit`",
+                "2:15: Unused parameter `b`",
+                "3:5: Unused variable `unused`",
+                "4:7: `late` is read before its `let`",
+                "4:13: `y` is not declared",
+                "7: Unreachable code",
+                "7:1: `missing` is not declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_members() {
+        let source = "function f(size) {\n\
+                      return size.width + Math.random() + config.depth.max;\n\
+                      }";
+        assert_eq!(
+            warnings(source),
+            [
+                "2:21: `Math.random` is not declared",
+                "2:37: `config.depth.max` is not declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clean_program() {
+        let source = "extern function puts(string): int;\n\
+                      let greeting = \"hi\";\n\
+                      function main() { let n = 2; while (n) { puts(greeting); } return n; }";
+        assert!(warnings(source).is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+//! What the compiler is doing, for `-v` and `-vv`: each phase logs what it
+//! produced to stderr as `[phase] message` lines, when the verbosity set
+//! by the command line asks for its level.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// How much detail a message gives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// A summary of a phase, shown with `-v`.
+    Info = 1,
+    /// A line per function, shown with `-vv`.
+    Debug = 2,
+}
+
+/// Show messages up to `verbosity`: 0 for none, 1 for `Info`, 2 and up
+/// for `Debug` too.
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+/// Whether messages of `level` are shown, to skip working out ones that
+/// are not.
+pub fn enabled(level: Level) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+pub fn info(phase: &str, message: fmt::Arguments) {
+    log(Level::Info, phase, message);
+}
+
+pub fn debug(phase: &str, message: fmt::Arguments) {
+    log(Level::Debug, phase, message);
+}
+
+fn log(level: Level, phase: &str, message: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("[{}] {}", phase, message);
+    }
+}
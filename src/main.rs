@@ -1,13 +1,20 @@
-mod codegen;
-mod debug;
-mod ir;
-mod lexer;
-mod optimizer;
-mod parser;
-mod vm;
+mod cli;
+mod config;
+mod repl;
+mod test_runner;
 
+use cli::{Cli, Command, Stage, TraceFormat};
+use js_compiler::codegen::source_map::SourceMap;
+use js_compiler::diagnostics::{self, Diagnostic};
+use js_compiler::{codegen, debug, ir, lexer, log, optimizer, parser, timings, vm};
+use repl::Repl;
+use std::any::Any;
 use std::fs;
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
 
 const EXAMPLE_JS: &str = r#"
 // Simple function to calculate fibonacci number
@@ -30,82 +37,975 @@ function main() {
 }
 "#;
 
+/// How often `--watch` looks at the input file.
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A diagnostic about an input file after the first, raised in place of
+/// the plain diagnostic so `report` renders it against that file.
+struct FileDiagnostic {
+    name: String,
+    source: String,
+    diagnostic: Diagnostic,
+}
+
 fn main() {
-    // If no arguments provided, use the example
-    let source = if std::env::args().len() > 1 {
-        let args: Vec<String> = std::env::args().collect();
-        fs::read_to_string(&args[1]).expect("Failed to read source file")
-    } else {
-        String::from(EXAMPLE_JS)
-    };
-
-    println!("Compiling JavaScript:");
-    println!("{}", source);
-    println!("\nTokenizing...");
-    let tokens = lexer::tokenize(&source);
-    println!("Generated {} tokens", tokens.len());
-
-    println!("\nParsing...");
-    let ast = parser::parse(tokens);
-    // println!("Generated AST {:?}", ast.statements);
-
-    println!("\nGenerating IR...");
-    let ir = ir::lower_ast(ast);
-    // println!("Generated IR {:?}", ir);
-    // println!("Generated {} IR functions", ir.functions.len());
-
-    // println!("\nOptimizing...");
-    // let optimized_ir = optimizer::optimize(ir);
-
-    // Choose between targets based on features
-    let target = if cfg!(feature = "x64") {
-        codegen::Target::X64
-    } else if cfg!(feature = "arm64") {
-        codegen::Target::ARM64
-    } else if cfg!(feature = "wasm") {
-        codegen::Target::Wasm
-    } else {
-        codegen::Target::None
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let config = config::Config::find().unwrap_or_else(|message| {
+        eprintln!("error: {}", message);
+        std::process::exit(2);
+    });
+    let cli = cli::parse(&args, &config).unwrap_or_else(|message| {
+        eprintln!("error: {}\nRun `js-compiler help` for usage.", message);
+        std::process::exit(2);
+    });
+    log::set_verbosity(cli.verbosity);
+    if cli.timings {
+        timings::enable();
+    }
+
+    match cli.command {
+        Command::Help => print!("{}", cli::usage()),
+        Command::Repl => repl(),
+        _ => {
+            // Diagnostics are rendered against the source by `report`;
+            // other panics are reported as usual
+            let default_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if !info.payload().is::<Diagnostic>() {
+                    default_hook(info);
+                }
+            }));
+            if cli.watch {
+                watch(&cli);
+            }
+            let status = match cli.command {
+                Command::Fmt => fmt(&cli),
+                Command::Test => test_runner::run(&cli),
+                Command::Debug => debug_diff(&cli),
+                _ => execute(&cli, &read_source(&cli)),
+            };
+            if status != 0 {
+                std::process::exit(status);
+            }
+        }
+    }
+}
+
+/// Run the command on `source` once, returning the status to exit with,
+/// and report how long its phases took if asked to.
+fn execute(cli: &Cli, source: &str) -> i32 {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match cli.command {
+        Command::Run => run(cli, source),
+        Command::Check => check(cli, source),
+        Command::Lint => lint(cli, source),
+        Command::Bench => bench(cli, source),
+        Command::Disasm => {
+            disasm(cli, source);
+            0
+        }
+        _ => {
+            compile(cli, source);
+            0
+        }
+    }));
+    let status = result.unwrap_or_else(|payload| report(cli, file_name(cli), source, payload));
+    if cli.timings {
+        eprint!("{}", timing_report(&timings::take()));
+    }
+    status
+}
+
+/// A line for each phase with the time it took, and their total.
+fn timing_report(times: &[(String, Duration)]) -> String {
+    let line = |phase: &str, time: Duration| {
+        format!("{:<24}{:>10.3} ms\n", phase, time.as_secs_f64() * 1000.0)
     };
+    let mut report = String::from("\ntimings:\n");
+    for (phase, time) in times {
+        report.push_str(&format!("  {}", line(phase, *time)));
+    }
+    let total = times.iter().map(|(_, time)| *time).sum();
+    report.push_str(&format!("  {}", line("total", total)));
+    report
+}
 
-    match target {
-        codegen::Target::None => {
-            println!("Running in VM mode (no native code generation)");
-            let mut vm = vm::VM::new(ir);
-            vm.enable_debugging();
-            let result = vm.execute_function("main", vec![]);
+/// Repeat the command each time an input file is modified, until
+/// interrupted. Compile errors and failing runs are reported and then
+/// wait for the next change.
+fn watch(cli: &Cli) -> ! {
+    let path = cli.input.as_deref().expect("--watch needs an input file");
+    let mut last_modified = None;
+    loop {
+        let modified = cli
+            .files
+            .iter()
+            .map(|file| {
+                fs::metadata(file)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .collect::<Option<Vec<_>>>();
+        // An editor saving the file may briefly remove it
+        if modified.is_some() && modified != last_modified {
+            last_modified = modified;
+            if let Ok(source) = fs::read_to_string(path) {
+                let status = execute(cli, &source);
+                eprintln!(
+                    "[exit status {}; watching {} for changes]",
+                    status,
+                    cli.files.join(", ")
+                );
+            }
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Name of the input in messages.
+fn file_name(cli: &Cli) -> &str {
+    cli.input.as_deref().unwrap_or("<example>")
+}
+
+/// Print the diagnostic a command raised about the file `name`, or about
+/// another input file, and return the status of 1, or carry on unwinding
+/// for any other panic unless watching.
+fn report(cli: &Cli, name: &str, source: &str, payload: Box<dyn Any + Send>) -> i32 {
+    let payload = match payload.downcast::<FileDiagnostic>() {
+        Ok(file) => return report(cli, &file.name, &file.source, Box::new(file.diagnostic)),
+        Err(payload) => payload,
+    };
+    match payload.downcast::<Diagnostic>() {
+        Ok(diagnostic) => {
+            eprint!(
+                "{}",
+                diagnostic.render(name, source, diagnostics::stderr_color())
+            );
+            1
+        }
+        // Already reported by the panic hook
+        Err(_) if cli.watch => 1,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// The input file's contents, or the built-in example without one.
+/// Exits with 1 if the file cannot be read.
+fn read_source(cli: &Cli) -> String {
+    match &cli.input {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprint!("{}", render_io_error(&io_error("read", path, e)));
+            std::process::exit(1);
+        }),
+        None => String::from(EXAMPLE_JS),
+    }
+}
 
-            if let Some(debug_trace) = vm.get_debug_trace() {
-                let html = debug_trace.generate_html();
-                fs::write("debug_output.html", html).expect("Failed to write debug output");
-                println!("Debug visualization written to debug_output.html");
+/// The error for a file that could not be read or written.
+fn io_error(action: &str, path: impl AsRef<Path>, error: io::Error) -> Diagnostic {
+    Diagnostic::error(format!(
+        "Cannot {} {}: {}",
+        action,
+        path.as_ref().display(),
+        error
+    ))
+}
+
+/// An error from `io_error` as printed outside `execute`, with no source
+/// to show.
+fn render_io_error(diagnostic: &Diagnostic) -> String {
+    diagnostic.render("", "", diagnostics::stderr_color())
+}
+
+/// The contents of the file at `path`, raising an error if it cannot be
+/// read.
+fn read_file(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| io_error("read", path, e).raise())
+}
+
+/// Write `contents` to the file at `path`, raising an error if it cannot
+/// be written.
+fn write_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) {
+    let path = path.as_ref();
+    fs::write(path, contents).unwrap_or_else(|e| io_error("write", path, e).raise());
+}
+
+/// Create the file at `path` for writing, raising an error if it cannot
+/// be.
+fn create_file(path: impl AsRef<Path>) -> io::BufWriter<fs::File> {
+    let path = path.as_ref();
+    io::BufWriter::new(
+        fs::File::create(path).unwrap_or_else(|e| io_error("write", path, e).raise()),
+    )
+}
+
+/// Lex, parse and lower `source`, or read it if the input is an `.ir`
+/// file, then optimize at the requested level, printing the stages
+/// `--emit` asks for along the way.
+fn lower(cli: &Cli, source: &str) -> ir::IRModule {
+    if input_has_extension(cli, ir::TEXT_EXTENSION) {
+        let ir = ir::parse_text(source)
+            .unwrap_or_else(|e| Diagnostic::error(format!("{}: {}", file_name(cli), e)).raise());
+        return link_and_optimize(cli, source, ir);
+    }
+    lower_parsed(cli, source, parse(cli, source))
+}
+
+fn input_has_extension(cli: &Cli, extension: &str) -> bool {
+    cli.input
+        .as_ref()
+        .is_some_and(|path| Path::new(path).extension() == Some(extension.as_ref()))
+}
+
+/// Lex and parse `source`, printing the tokens and AST if asked to.
+fn parse(cli: &Cli, source: &str) -> parser::AST {
+    let tokens = timings::time("lex", || lexer::tokenize(source));
+    log::info("lex", format_args!("{} tokens", tokens.len()));
+    emit_stage(cli, Stage::Tokens, || {
+        tokens
+            .iter()
+            .map(|token| format!("{}:{}\t{:?}\n", token.line, token.column, token.token_type))
+            .collect()
+    });
+    let ast = timings::time("parse", || parser::parse(tokens));
+    log::info(
+        "parse",
+        format_args!("{} top-level statements", ast.statements.len()),
+    );
+    emit_stage(cli, Stage::Ast, || format!("{:#?}\n", ast));
+    emit_stage(cli, Stage::MinJs, || parser::minify(&ast));
+    ast
+}
+
+/// The second half of `lower`, from the AST of `source`, adding the
+/// functions of the other input files.
+fn lower_parsed(cli: &Cli, source: &str, ast: parser::AST) -> ir::IRModule {
+    let ir = timings::time("lower", || ir::lower_ast(ast));
+    link_and_optimize(cli, source, ir)
+}
+
+/// Add the functions of the other input files to `ir`, then export and
+/// optimize it and print the later stages.
+fn link_and_optimize(cli: &Cli, source: &str, mut ir: ir::IRModule) -> ir::IRModule {
+    for path in cli.files.iter().skip(1) {
+        let other = read_file(path);
+        in_file(path, &other, || {
+            let ast = parse(cli, &other);
+            ir.link(timings::time("lower", || ir::lower_ast(ast)))
+        });
+    }
+    for name in &cli.exports {
+        ir.export(name);
+    }
+    log_sizes("lower", &ir);
+    let ir = optimizer::optimize_with(ir, cli.opt_level);
+    if cli.opt_level > optimizer::OptLevel::O0 {
+        log_sizes("opt", &ir);
+    }
+    emit_stage(cli, Stage::Ir, || ir.to_string());
+    emit_stage(cli, Stage::Jsbc, || ir.to_bytecode());
+
+    let asm_target = match cli.target {
+        codegen::Target::X64 | codegen::Target::ARM64 => cli.target.clone(),
+        _ => codegen::Arch::host().target(),
+    };
+    for (stage, target) in [
+        (Stage::Asm, asm_target),
+        (Stage::Wat, codegen::Target::Wasm),
+    ] {
+        emit_stage(cli, stage, || {
+            let options = codegen_options(cli, source);
+            // Neither target is the VM, which has no code to print
+            codegen::generate_code(ir.clone(), target, &options).unwrap_or_default()
+        });
+    }
+    ir
+}
+
+/// Run `f` on the input file `name` after the first, so `report` renders
+/// the diagnostics it raises against that file.
+fn in_file<T>(name: &str, source: &str, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        match payload.downcast::<Diagnostic>() {
+            Ok(diagnostic) => panic::resume_unwind(Box::new(FileDiagnostic {
+                name: name.to_string(),
+                source: source.to_string(),
+                diagnostic: *diagnostic,
+            })),
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    })
+}
+
+/// Log the size of the module `phase` produced, and with `-vv` that of
+/// each function.
+fn log_sizes(phase: &str, ir: &ir::IRModule) {
+    log::info(
+        phase,
+        format_args!(
+            "{} functions, {} externs, {} instructions",
+            ir.functions.len(),
+            ir.externs.len(),
+            ir.instruction_count()
+        ),
+    );
+    for function in &ir.functions {
+        log::debug(
+            phase,
+            format_args!(
+                "{}: {} instructions, stack depth {}",
+                function.name,
+                function.instruction_count(),
+                function.max_stack_depth()
+            ),
+        );
+    }
+}
+
+/// Print `stage` or write it to the files `--emit` names, generating the
+/// text only when it was asked for.
+fn emit_stage(cli: &Cli, stage: Stage, text: impl FnOnce() -> String) {
+    let mut destinations = cli.emit.iter().filter(|(s, _)| *s == stage).peekable();
+    if destinations.peek().is_none() {
+        return;
+    }
+    let text = text();
+    for (_, file) in destinations {
+        match file {
+            Some(path) => write_file(path, &text),
+            None => print!("{}", text),
+        }
+    }
+}
+
+/// Print the lint warnings for `source`, returning 1 if there are any.
+fn lint(cli: &Cli, source: &str) -> i32 {
+    let ast = parser::parse(lexer::tokenize(source));
+    let warnings = js_compiler::lint::lint(&ast, source);
+    for warning in &warnings {
+        eprintln!(
+            "{}",
+            warning.render(file_name(cli), source, diagnostics::stderr_color())
+        );
+    }
+    match warnings.len() {
+        0 => 0,
+        n => {
+            eprintln!("{} warning{}", n, if n == 1 { "" } else { "s" });
+            1
+        }
+    }
+}
+
+/// Reformat each input file in place, or stdin to stdout without one.
+/// With `--check`, list the files that would change instead, returning 1
+/// if there are any.
+fn fmt(cli: &Cli) -> i32 {
+    let format = |name: &str, source: &str| {
+        panic::catch_unwind(|| parser::format_source(source))
+            .map_err(|payload| report(cli, name, source, payload))
+    };
+
+    if cli.files.is_empty() {
+        let mut source = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut source) {
+            eprint!("{}", render_io_error(&io_error("read", "<stdin>", e)));
+            return 1;
+        }
+        return match format("<stdin>", &source) {
+            Ok(formatted) if cli.check && formatted != source => {
+                eprintln!("<stdin> would be reformatted");
+                1
             }
+            Ok(_) if cli.check => 0,
+            Ok(formatted) => {
+                print!("{}", formatted);
+                0
+            }
+            Err(status) => status,
+        };
+    }
 
-            match result {
-                vm::Value::Number(n) => println!("Result: {}", n),
-                vm::Value::String(s) => println!("Result: \"{}\"", s),
-                vm::Value::Undefined => println!("Result: undefined"),
-                _ => println!("Result: {:?}", result),
+    let mut status = 0;
+    for path in &cli.files {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprint!("{}", render_io_error(&io_error("read", path, e)));
+                status = 1;
+                continue;
+            }
+        };
+        match format(path, &source) {
+            Ok(formatted) if formatted == source => {}
+            Ok(_) if cli.check => {
+                eprintln!("{} would be reformatted", path);
+                status = 1;
             }
+            Ok(formatted) => {
+                if let Err(e) = fs::write(path, formatted) {
+                    eprint!("{}", render_io_error(&io_error("write", path, e)));
+                    status = 1;
+                }
+            }
+            Err(error) => status = error,
         }
-        _ => {
-            println!("\nGenerating code for target {:?}...", target);
-            if let Some(output) = codegen::generate_code(ir, target.clone()) {
-                let extension = match target {
-                    codegen::Target::X64 | codegen::Target::ARM64 => "s",
-                    codegen::Target::Wasm => "wat",
-                    _ => unreachable!(),
-                };
-
-                let output_path = if std::env::args().len() > 1 {
-                    Path::new(&std::env::args().nth(1).unwrap()).with_extension(extension)
-                } else {
-                    Path::new(&format!("output.{}", extension)).to_path_buf()
-                };
-
-                fs::write(&output_path, output).expect("Failed to write output");
-                println!("Output written to: {}", output_path.display());
+    }
+    status
+}
+
+/// Compare the two traces of `debug diff`, written by `run` as JSON or
+/// NDJSON, printing where they first differ. As with `diff`, returns 1 if
+/// they do and 2 if one cannot be read.
+fn debug_diff(cli: &Cli) -> i32 {
+    let [a, b] = &cli.files[..] else {
+        unreachable!("debug diff takes two traces")
+    };
+    let read = |path: &str| {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| debug::read_frames(&text))
+            .map_err(|e| eprintln!("{}: {}", path, e))
+    };
+    let (Ok(frames_a), Ok(frames_b)) = (read(a), read(b)) else {
+        return 2;
+    };
+    let Some(divergence) = debug::first_divergence(&frames_a, &frames_b) else {
+        println!(
+            "The traces match ({} and {} frames)",
+            frames_a.len(),
+            frames_b.len()
+        );
+        return 0;
+    };
+
+    let describe = |path: &str, frames: &[debug::DebugFrame], index: Option<usize>| {
+        let Some(index) = index else {
+            return format!("{} ends after {} frames", path, frames.len());
+        };
+        let frame = &frames[index];
+        let line = match (frame.line, frame.column) {
+            (Some(line), Some(column)) => format!(", line {}:{}", line, column),
+            _ => String::new(),
+        };
+        format!(
+            "{} frame {}: {} at ip {}{}",
+            path,
+            index + 1,
+            frame.function_name,
+            frame.ip,
+            line
+        )
+    };
+    println!("The traces diverge:");
+    println!("  {}", describe(a, &frames_a, divergence.a));
+    println!("  {}", describe(b, &frames_b, divergence.b));
+    for (what, in_a, in_b) in &divergence.differences {
+        println!("  {}:\n    {}\n    {}", what, in_a, in_b);
+    }
+    1
+}
+
+/// Options for the backends from the command line.
+fn codegen_options(cli: &Cli, source: &str) -> codegen::CodegenOptions {
+    codegen::CodegenOptions {
+        target: cli.target_spec,
+        // The built-in assembler reads AT&T syntax
+        x64_syntax: if cli.emit_object {
+            codegen::X64Syntax::Att
+        } else {
+            cli.x64_syntax
+        },
+        wasm_host: cli.wasm_host,
+        wasm_values: cli.wasm_values,
+        // Line info refers to the input, so the built-in example has none
+        source_file: cli.input.clone().filter(|_| cli.debug_info),
+        annotated_source: cli.annotate.then(|| source.to_string()),
+        pic: cli.pic,
+        source_map: cli.source_map,
+        c_abi: cli.c_abi,
+    }
+}
+
+/// How the VM would show a value `main` returned.
+fn describe(value: &vm::Value) -> String {
+    match value {
+        vm::Value::Number(n) => vm::number_to_string(*n),
+        vm::Value::String(s) => format!("\"{}\"", s),
+        vm::Value::Boolean(b) => b.to_string(),
+        vm::Value::Null => "null".to_string(),
+        vm::Value::Undefined => "undefined".to_string(),
+        _ => format!("{:?}", value),
+    }
+}
+
+/// A VM for `ir` with the built-ins and limits the project file sets.
+fn new_vm(cli: &Cli, ir: ir::IRModule) -> vm::VM {
+    let mut vm = vm::VM::new(ir);
+    if let Some(builtins) = &cli.builtins {
+        vm.set_builtins(builtins);
+    }
+    vm.set_limits(cli.vm_limits);
+    vm
+}
+
+/// Run `main` in the VM, returning the exit status its result gives, as a
+/// native build of the program would.
+fn run(cli: &Cli, source: &str) -> i32 {
+    let ir = lower(cli, source);
+    if cli.jit {
+        return run_jit(cli, ir);
+    }
+
+    let mut vm = new_vm(cli, ir);
+    let default_path = format!("debug_output.{}", cli.trace_format.extension());
+    let path = cli.output.as_deref().unwrap_or(&default_path);
+    let open = || -> Box<dyn Write> {
+        match path {
+            "-" => Box::new(io::stdout().lock()),
+            path => Box::new(create_file(path)),
+        }
+    };
+    // NDJSON goes out as the program runs, unless only the last frames
+    // are wanted
+    let streaming = cli.trace_format == TraceFormat::Ndjson && cli.trace_options.last.is_none();
+    if cli.debug && streaming {
+        vm.stream_debugging_to(cli.trace_options.clone(), open());
+    } else if cli.debug {
+        vm.enable_debugging_with(cli.trace_options.clone());
+    }
+    if let Some(interval) = cli.profile {
+        vm.enable_profiling(interval);
+    }
+    // An uncaught error exits with 1, once the trace is written
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        timings::time("run", || vm.execute_function("main", vec![]))
+    }));
+    if let Some(profile) = vm.take_profile() {
+        eprint!("{}", profile.report(start.elapsed()));
+    }
+
+    if let Some(mut debug_trace) = vm.take_debug_trace() {
+        // Lines of the other input files would be taken for this one's
+        if cli.files.len() <= 1 {
+            debug_trace.set_source(source);
+        }
+        let written = if streaming {
+            debug_trace.finish()
+        } else {
+            let mut out = open();
+            match cli.trace_format {
+                TraceFormat::Html => out.write_all(debug_trace.generate_html().as_bytes()),
+                TraceFormat::Json => writeln!(out, "{}", debug_trace.to_json()),
+                TraceFormat::Ndjson => debug_trace.write_ndjson(&mut out),
+                TraceFormat::Folded => out.write_all(debug_trace.folded_stacks().as_bytes()),
+                TraceFormat::Dot => out.write_all(debug_trace.to_dot().as_bytes()),
             }
+            .and_then(|()| out.flush())
+        };
+        written.unwrap_or_else(|e| io_error("write", path, e).raise());
+        if path != "-" {
+            log::info("debug", format_args!("wrote the trace to {}", path));
+        }
+    }
+    match result {
+        Ok(result) => {
+            println!("Result: {}", describe(&result));
+            result.exit_status()
+        }
+        Err(payload) if payload.is::<Diagnostic>() => panic::resume_unwind(payload),
+        // Reported by the panic hook
+        Err(_) => 1,
+    }
+}
+
+/// Report the errors and lint warnings in `source` and the other input
+/// files, and verify the IR they compile to, without running anything or
+/// writing output. Returns 1 if there are errors; warnings alone pass.
+fn check(cli: &Cli, source: &str) -> i32 {
+    let mut files = vec![(file_name(cli).to_string(), source.to_string())];
+    for path in cli.files.iter().skip(1) {
+        let other = read_file(path);
+        files.push((path.clone(), other));
+    }
+    // Only the first file's AST is lowered from; linking parses the others
+    // again, printing what --emit asks for
+    let asts: Vec<parser::AST> = files
+        .iter()
+        .enumerate()
+        .map(|(i, (name, source))| match i {
+            0 => parse(cli, source),
+            _ => in_file(name, source, || parser::parse(lexer::tokenize(source))),
+        })
+        .collect();
+
+    let color = diagnostics::stderr_color();
+    let (mut error_count, mut warning_count) = (0, 0);
+    for (i, ((name, source), ast)) in files.iter().zip(&asts).enumerate() {
+        let elsewhere: Vec<&str> = asts
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .flat_map(|(_, ast)| ast.function_names())
+            .collect();
+        let errors = js_compiler::check::check(ast, source);
+        let warnings = js_compiler::lint::lint_with(ast, source, &elsewhere);
+        let mut diagnostics: Vec<&Diagnostic> = errors.iter().chain(&warnings).collect();
+        diagnostics
+            .sort_by_key(|diagnostic| diagnostic.labels.first().map(|label| label.span.line));
+        for diagnostic in diagnostics {
+            eprintln!("{}", diagnostic.render(name, source, color));
+        }
+        error_count += errors.len();
+        warning_count += warnings.len();
+    }
+    let name = file_name(cli);
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    let warned = match warning_count {
+        0 => String::new(),
+        n => format!(", {} warning{}", n, plural(n)),
+    };
+    if error_count > 0 {
+        eprintln!(
+            "{}: {} error{}{}",
+            name,
+            error_count,
+            plural(error_count),
+            warned
+        );
+        return 1;
+    }
+
+    let ast = asts.into_iter().next().unwrap();
+    let ir = lower_parsed(cli, source, ast);
+    if let Err(errors) = ir::verify(&ir) {
+        for error in errors {
+            eprintln!("{}: {}", name, error);
+        }
+        return 1;
+    }
+    println!("{}: ok ({} functions{})", name, ir.functions.len(), warned);
+    0
+}
+
+/// Print the disassembly of the IR compiled from the input, or loaded from
+/// it if it is a `.jsbc` file.
+fn disasm(cli: &Cli, source: &str) {
+    let ir = if input_has_extension(cli, ir::BYTECODE_EXTENSION) {
+        ir::IRModule::from_bytecode(source)
+            .unwrap_or_else(|e| Diagnostic::error(format!("{}: {}", file_name(cli), e)).raise())
+    } else {
+        lower(cli, source)
+    };
+    print!("{}", ir.disassemble());
+}
+
+/// Read snippets at a prompt, continuing a line while it has unclosed
+/// brackets, and print the value of each.
+fn repl() {
+    // Errors are reported at the prompt instead
+    panic::set_hook(Box::new(|_| {}));
+    let mut session = Repl::new();
+    let stdin = io::stdin();
+    let mut source = String::new();
+    loop {
+        print!("{}", if source.is_empty() { "> " } else { "... " });
+        io::stdout().flush().expect("Failed to write prompt");
+        if stdin.read_line(&mut source).expect("Failed to read input") == 0 {
+            println!();
+            return;
+        }
+        if source.trim().is_empty() {
+            source.clear();
+            continue;
+        }
+        if !repl::is_complete(&source) {
+            continue;
+        }
+        match session.eval(&source) {
+            Ok(vm::Value::Undefined) => {}
+            Ok(value) => println!("{}", describe(&value)),
+            Err(diagnostic) => eprint!(
+                "{}",
+                diagnostic.render("<repl>", &source, diagnostics::stderr_color())
+            ),
+        }
+        source.clear();
+    }
+}
+
+/// Generate code for `build` and `emit`, linking an executable for `build`.
+fn compile(cli: &Cli, source: &str) {
+    let ir = lower(cli, source);
+    let build = cli.command == Command::Build;
+    let target = cli.target.clone();
+    let target_spec = cli.target_spec;
+    let input = cli.input.as_ref();
+
+    let options = codegen_options(cli, source);
+    // Cranelift compiles straight to machine code rather than assembly
+    // text, so building an executable goes through an object file
+    let emit_object = cli.emit_object || (build && matches!(target, codegen::Target::Cranelift));
+    // Code generation is timed up to the output written
+    let codegen_start = Instant::now();
+    let cranelift_object = if emit_object && matches!(target, codegen::Target::Cranelift) {
+        Some(codegen::cranelift_object(&ir))
+    } else {
+        None
+    };
+    let Some(mut generator) = codegen::generator(target.clone(), &options) else {
+        return;
+    };
+    let native = matches!(
+        target,
+        codegen::Target::X64 | codegen::Target::ARM64 | codegen::Target::Cranelift
+    );
+    let extension = match target {
+        codegen::Target::X64 | codegen::Target::ARM64 | codegen::Target::Cranelift
+            if emit_object =>
+        {
+            if target_spec.os == codegen::TargetOs::Windows {
+                "obj"
+            } else {
+                "o"
+            }
+        }
+        codegen::Target::X64 | codegen::Target::ARM64 => "s",
+        codegen::Target::Wasm if emit_object => "wasm",
+        codegen::Target::Wasm => "wat",
+        codegen::Target::LlvmIr => "ll",
+        codegen::Target::Cranelift => "clif",
+        _ => unreachable!(),
+    };
+
+    // With -o, build names the executable and puts the code beside it;
+    // without, the code goes beside the input or in the output directory
+    let output_path = match (&cli.output, input) {
+        (Some(path), _) if build => Path::new(path).with_extension(extension),
+        (Some(path), _) => Path::new(path).to_path_buf(),
+        (None, input) => {
+            let path = Path::new(input.map_or("output", String::as_str)).with_extension(extension);
+            match &cli.out_dir {
+                Some(directory) => {
+                    fs::create_dir_all(directory)
+                        .unwrap_or_else(|e| io_error("create", directory, e).raise());
+                    directory.join(path.file_name().unwrap())
+                }
+                None => path,
+            }
+        }
+    };
+    let to_stdout = !build && cli.output.as_deref() == Some("-");
+    let mut out: Box<dyn Write> = if to_stdout {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(create_file(&output_path))
+    };
+    let written_path = if to_stdout {
+        Path::new("<stdout>")
+    } else {
+        &output_path
+    };
+    let written_error = |e| io_error("write", written_path, e).raise();
+
+    if let Some(object) = cranelift_object {
+        out.write_all(&object).unwrap_or_else(written_error);
+    } else if emit_object && matches!(target, codegen::Target::Wasm) {
+        let mut binary = codegen::wasm_binary(&generator.generate(ir))
+            .unwrap_or_else(|e| panic!("Invalid Wasm module: {}", e));
+        if let Some(table) = generator.line_table() {
+            let (file, js_file) = source_map_names(cli, &output_path);
+            let map = SourceMap::for_wasm_binary(table, &binary, &file, &js_file, source)
+                .unwrap_or_else(|e| panic!("Failed to map the Wasm module: {}", e));
+            let url = write_source_map(&output_path, &map);
+            codegen::source_map::add_source_mapping_url(&mut binary, &url);
+        }
+        out.write_all(&binary).unwrap_or_else(written_error);
+    } else if emit_object {
+        let assembly = generator.generate(ir);
+        let object = codegen::assembler::assemble(&assembly, &target, &options);
+        out.write_all(&object).unwrap_or_else(written_error);
+    } else {
+        // Text goes out a function at a time
+        generator
+            .generate_to(&ir, &mut out)
+            .unwrap_or_else(written_error);
+        if let Some(table) = generator.line_table() {
+            let (file, js_file) = source_map_names(cli, &output_path);
+            let map = SourceMap::for_text(table, &file, &js_file, source);
+            write_source_map(&output_path, &map);
+        }
+    }
+    out.flush().unwrap_or_else(written_error);
+    drop(out);
+    timings::record("codegen", codegen_start.elapsed());
+    // Nothing else goes to stdout, and the runtime has nowhere to go
+    if to_stdout {
+        return;
+    }
+    let size = fs::metadata(&output_path).map_or(0, |metadata| metadata.len());
+    log::info(
+        "codegen",
+        format_args!(
+            "wrote {} bytes of {} code to {}",
+            size,
+            target.name(),
+            output_path.display()
+        ),
+    );
+
+    // Native code links against the runtime support library
+    if build && native {
+        let executable = match &cli.output {
+            Some(path) => Path::new(path).to_path_buf(),
+            None => codegen::toolchain::executable_path(&output_path, target_spec.os),
+        };
+        timings::time("link", || {
+            codegen::toolchain::link_executable(&output_path, &executable)
+        })
+        .unwrap_or_else(|e| panic!("Build failed: {}", e));
+        log::info(
+            "link",
+            format_args!("wrote the executable to {}", executable.display()),
+        );
+    } else if matches!(target, codegen::Target::LlvmIr) {
+        let runtime_path = output_path.with_file_name(codegen::runtime::RUNTIME_FILE_NAME);
+        write_file(&runtime_path, codegen::runtime::RUNTIME_C_SOURCE);
+        log::info(
+            "codegen",
+            format_args!(
+                "wrote the runtime to {} (link with: clang {} {} -lm)",
+                runtime_path.display(),
+                output_path.display(),
+                runtime_path.display()
+            ),
+        );
+    } else if native {
+        let runtime_path = output_path.with_file_name(codegen::runtime::RUNTIME_FILE_NAME);
+        write_file(&runtime_path, codegen::runtime::RUNTIME_C_SOURCE);
+        log::info(
+            "codegen",
+            format_args!(
+                "wrote the runtime to {} (link with: cc {} {})",
+                runtime_path.display(),
+                output_path.display(),
+                runtime_path.display()
+            ),
+        );
+    }
+}
+
+/// Names of the code at `output_path` and of the JS file, as a source map
+/// beside the code refers to them.
+fn source_map_names(cli: &Cli, output_path: &Path) -> (String, String) {
+    let name = |path: &Path| {
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    };
+    let js_file = match &cli.input {
+        Some(input) => name(Path::new(input)),
+        None => "example.js".to_string(),
+    };
+    (name(output_path), js_file)
+}
+
+/// Write `map` beside the code at `output_path` as `<output>.map`,
+/// returning the name the code can refer to it by.
+fn write_source_map(output_path: &Path, map: &SourceMap) -> String {
+    let mut path = output_path.as_os_str().to_owned();
+    path.push(".map");
+    let path = Path::new(&path);
+    write_file(path, map.to_json());
+    log::info(
+        "codegen",
+        format_args!("wrote the source map to {}", path.display()),
+    );
+    path.file_name().unwrap().to_string_lossy().into_owned()
+}
+
+/// Time `--iterations` runs of `main`, each in a new VM, and as many with
+/// the JIT if asked, printing the mean, fastest and slowest.
+fn bench(cli: &Cli, source: &str) -> i32 {
+    let ir = lower(cli, source);
+    println!(
+        "{}: {} iterations at -{:?}",
+        file_name(cli),
+        cli.iterations,
+        cli.opt_level
+    );
+
+    let mut times = Vec::new();
+    let mut instructions = 0;
+    for _ in 0..cli.iterations {
+        let mut vm = new_vm(cli, ir.clone());
+        vm.capture_output();
+        let start = Instant::now();
+        vm.execute_function("main", vec![]);
+        times.push(start.elapsed());
+        instructions = vm.instruction_count();
+    }
+    println!(
+        "{}, {} instructions per run",
+        timing_summary("vm", &times),
+        instructions
+    );
+
+    if cli.jit {
+        let module = codegen::jit::JitModule::compile(ir);
+        let times: Vec<Duration> = (0..cli.iterations)
+            .map(|_| {
+                let start = Instant::now();
+                module.call("main", &[]);
+                start.elapsed()
+            })
+            .collect();
+        println!("{}", timing_summary("jit", &times));
+    }
+    0
+}
+
+/// Mean, minimum and maximum of `times`, in milliseconds.
+fn timing_summary(label: &str, times: &[Duration]) -> String {
+    let ms = |duration: Duration| format!("{:.3} ms", duration.as_secs_f64() * 1000.0);
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+    format!(
+        "{:<4} mean {}, min {}, max {}",
+        label,
+        ms(mean),
+        ms(*times.iter().min().unwrap()),
+        ms(*times.iter().max().unwrap())
+    )
+}
+
+/// Run `main` compiled to machine code in process, then in the VM, and check
+/// that both return the same value, returning 1 if they do not.
+fn run_jit(cli: &Cli, ir: ir::IRModule) -> i32 {
+    println!("\nRunning main with the JIT...");
+    let module = timings::time("jit compile", || {
+        codegen::jit::JitModule::compile(ir.clone())
+    });
+    let result = timings::time("jit run", || module.call("main", &[]));
+    let result = codegen::jit::to_string(result);
+    println!("JIT result: {}", result);
+
+    println!("\nRunning main in the VM...");
+    let mut vm = new_vm(cli, ir);
+    let value = timings::time("run", || vm.execute_function("main", vec![]));
+    // Compare the values as the VM would print them
+    let expected = match &value {
+        vm::Value::Object(_) | vm::Value::Closure { .. } => None,
+        value => Some(value.to_string()),
+    };
+    match expected {
+        Some(text) if text == result => println!("VM result: {} (match)", text),
+        Some(text) => {
+            println!("VM result: {} (MISMATCH)", text);
+            return 1;
         }
+        None => println!("VM result is an object; nothing to compare"),
     }
+    // The results match, so exit as the run without the JIT would
+    value.exit_status()
 }
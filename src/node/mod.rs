@@ -0,0 +1,263 @@
+//! Bindings for Node.js with the `node` feature: the cdylib, renamed to
+//! end in `.node`, is a native addon exporting `compile` and a `Vm` class.
+//!
+//! ```js
+//! const { compile, Vm } = require("./js_compiler.node");
+//! const vm = new Vm(compile("function main(x) { return log(x) + 1; }"));
+//! vm.register("log", (x) => { console.log(x); return x; });
+//! vm.call("main", [41]); // 42
+//! ```
+//!
+//! Numbers, strings, booleans, null, undefined and plain objects convert
+//! both ways; closures cannot leave the VM. Errors the compiler or VM
+//! raise are thrown as `Error`s with the diagnostic, rendered against the
+//! source, as their message, and an exception a callback throws is
+//! rethrown from the `call` it ran under.
+
+use crate::diagnostics::Diagnostic;
+use crate::ir::{self, IRModule};
+use crate::optimizer::{self, OptLevel};
+use crate::vm::{VMContext, Value, VM};
+use crate::{lexer, parser};
+use napi::{Env, Error, JsFunction, JsObject, JsString, JsUnknown, Ref, Result, Status, ValueType};
+use napi_derive::napi;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::Once;
+
+/// JS functions registered on a VM, by the name the program calls.
+type Callbacks = Rc<RefCell<HashMap<String, Ref<()>>>>;
+
+thread_local! {
+    /// The environment and callbacks of the `Vm.call` running, which
+    /// `call_callback` calls into.
+    static ACTIVE: RefCell<Option<(Env, Callbacks)>> = const { RefCell::new(None) };
+    /// The exception a callback threw, which its `Vm.call` rethrows.
+    static THROWN: RefCell<Option<Error>> = const { RefCell::new(None) };
+}
+
+/// A compiled module, from `compile`.
+#[napi]
+pub struct Module {
+    module: IRModule,
+    /// The JS it was compiled from, which errors are rendered against.
+    source: String,
+}
+
+/// A VM running a module.
+#[napi]
+pub struct Vm {
+    vm: VM,
+    source: String,
+    env: Env,
+    callbacks: Callbacks,
+}
+
+/// Run `f`, turning the error it raises into a JS error with the
+/// diagnostic rendered against `source`.
+fn catch<T>(source: &str, f: impl FnOnce() -> T) -> Result<T> {
+    // Diagnostics are thrown to JS; other panics are reported as usual
+    static QUIET: Once = Once::new();
+    QUIET.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !info.payload().is::<Diagnostic>() {
+                default_hook(info);
+            }
+        }));
+    });
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = Diagnostic::from_panic(payload).render("<source>", source, false);
+        Error::from_reason(message)
+    })
+}
+
+/// Compile the JS in `source` and optimize it at `optLevel`, 0 to 2, or
+/// 2 if left out.
+#[napi]
+pub fn compile(source: String, opt_level: Option<u32>) -> Result<Module> {
+    let level = match opt_level.unwrap_or(2) {
+        0 => OptLevel::O0,
+        1 => OptLevel::O1,
+        2 => OptLevel::O2,
+        level => {
+            let message = format!("no optimization level {}", level);
+            return Err(Error::new(Status::InvalidArg, message));
+        }
+    };
+    let module = catch(&source, || {
+        let module = ir::lower_ast(parser::parse(lexer::tokenize(&source)));
+        optimizer::optimize_with(module, level)
+    })?;
+    Ok(Module { module, source })
+}
+
+#[napi]
+impl Module {
+    /// The module in the `.ir` text syntax.
+    #[napi(getter)]
+    pub fn ir(&self) -> String {
+        ir::print_text(&self.module)
+    }
+
+    /// Names of the module's functions, in order.
+    #[napi(getter)]
+    pub fn functions(&self) -> Vec<String> {
+        self.module.function_names()
+    }
+}
+
+#[napi]
+impl Vm {
+    /// A VM running a copy of `module`.
+    #[napi(constructor)]
+    pub fn new(env: Env, module: &Module) -> Result<Self> {
+        let vm = catch(&module.source, || VM::new(module.module.clone()))?;
+        Ok(Vm {
+            vm,
+            source: module.source.clone(),
+            env,
+            callbacks: Callbacks::default(),
+        })
+    }
+
+    /// Call the function `name` with `args`, returning its result.
+    #[napi]
+    pub fn call(
+        &mut self,
+        env: Env,
+        name: String,
+        args: Option<Vec<JsUnknown>>,
+    ) -> Result<JsUnknown> {
+        let args = args
+            .unwrap_or_default()
+            .into_iter()
+            .map(to_value)
+            .collect::<Result<Vec<_>>>()?;
+        let outer = ACTIVE.with(|active| active.replace(Some((env, self.callbacks.clone()))));
+        THROWN.with(|thrown| thrown.borrow_mut().take());
+        let result = catch(&self.source, || self.vm.execute_function(&name, args));
+        ACTIVE.with(|active| *active.borrow_mut() = outer);
+        let thrown = THROWN.with(|thrown| thrown.borrow_mut().take());
+        match result {
+            Ok(value) => to_js(&env, &value),
+            Err(error) => {
+                self.vm.reset_stack();
+                Err(thrown.unwrap_or(error))
+            }
+        }
+    }
+
+    /// Implement the function `name` with `callback`, such as one declared
+    /// with `extern function`. It gets the arguments converted to JS, and
+    /// its result is converted back.
+    #[napi]
+    pub fn register(&mut self, env: Env, name: String, callback: JsFunction) -> Result<()> {
+        let callback = env.create_reference(callback)?;
+        let replaced = self.callbacks.borrow_mut().insert(name.clone(), callback);
+        if let Some(mut replaced) = replaced {
+            replaced.unref(env)?;
+        }
+        self.vm.register_native(&name, call_callback);
+        Ok(())
+    }
+
+    /// Collect what `print` writes instead of printing it to stdout.
+    #[napi]
+    pub fn capture_output(&mut self) {
+        self.vm.capture_output();
+    }
+
+    /// Output captured since the last call.
+    #[napi]
+    pub fn take_output(&mut self) -> String {
+        self.vm.take_output()
+    }
+}
+
+impl Drop for Vm {
+    fn drop(&mut self) {
+        for (_, mut callback) in self.callbacks.borrow_mut().drain() {
+            let _ = callback.unref(self.env);
+        }
+    }
+}
+
+/// The native behind every registered callback: calls the JS function
+/// registered under the name it was called by.
+fn call_callback(context: &mut VMContext, args: Vec<Value>) -> Value {
+    let name = context.native_name().to_string();
+    let (env, callbacks) = ACTIVE
+        .with(|active| active.borrow().clone())
+        .expect("callbacks run within Vm.call");
+    let result = (|| {
+        let callback: JsFunction = env.get_reference_value(&callbacks.borrow()[&name])?;
+        let args = args
+            .iter()
+            .map(|arg| to_js(&env, arg))
+            .collect::<Result<Vec<_>>>()?;
+        to_value(callback.call(None, &args)?)
+    })();
+    match result {
+        Ok(value) => value,
+        Err(error) => {
+            let message = format!("{} threw: {}", name, error.reason);
+            THROWN.with(|thrown| *thrown.borrow_mut() = Some(error));
+            context.error(message).raise()
+        }
+    }
+}
+
+/// The VM value of a JS value. Objects are copied property by property.
+fn to_value(value: JsUnknown) -> Result<Value> {
+    Ok(match value.get_type()? {
+        ValueType::Undefined => Value::Undefined,
+        ValueType::Null => Value::Null,
+        ValueType::Boolean => Value::Boolean(value.coerce_to_bool()?.get_value()?),
+        ValueType::Number => Value::Number(value.coerce_to_number()?.get_double()?),
+        ValueType::String => Value::String(string(value.coerce_to_string()?)?.into()),
+        ValueType::Object => {
+            let object: JsObject = unsafe { value.cast() };
+            let names = object.get_property_names()?;
+            let mut properties = HashMap::new();
+            for index in 0..names.get_array_length()? {
+                let name = string(names.get_element::<JsString>(index)?)?;
+                let property = object.get_named_property::<JsUnknown>(&name)?;
+                properties.insert(name, to_value(property)?);
+            }
+            Value::Object(properties)
+        }
+        other => {
+            let message = format!("a {} cannot be passed to the VM", other);
+            return Err(Error::new(Status::InvalidArg, message));
+        }
+    })
+}
+
+fn string(value: JsString) -> Result<String> {
+    value.into_utf8()?.into_owned()
+}
+
+/// The JS value of a VM value.
+fn to_js(env: &Env, value: &Value) -> Result<JsUnknown> {
+    Ok(match value {
+        Value::Number(n) => env.create_double(*n)?.into_unknown(),
+        Value::String(s) => env.create_string(s)?.into_unknown(),
+        Value::Boolean(b) => env.get_boolean(*b)?.into_unknown(),
+        Value::Null => env.get_null()?.into_unknown(),
+        Value::Undefined => env.get_undefined()?.into_unknown(),
+        Value::Object(properties) => {
+            let mut object = env.create_object()?;
+            for (name, property) in properties {
+                object.set_named_property(name, to_js(env, property)?)?;
+            }
+            object.into_unknown()
+        }
+        Value::Closure { function, .. } => {
+            let message = format!("the closure of {} cannot be passed to JS", function);
+            return Err(Error::new(Status::InvalidArg, message));
+        }
+    })
+}
@@ -0,0 +1,118 @@
+use crate::ir::IRFunction;
+
+/// A block of the function, spanning `[start, end)` of its linear form.
+#[derive(Debug, Clone)]
+pub struct BasicBlock {
+    pub start: usize,
+    pub end: usize,
+    pub successors: Vec<usize>,
+    pub predecessors: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ControlFlowGraph {
+    pub blocks: Vec<BasicBlock>,
+}
+
+impl ControlFlowGraph {
+    pub fn build(function: &IRFunction) -> Self {
+        let starts = function.block_starts();
+        let mut blocks: Vec<BasicBlock> = (0..function.blocks.len())
+            .map(|b| BasicBlock {
+                start: starts[b],
+                end: starts[b + 1],
+                successors: function.successors(b),
+                predecessors: Vec::new(),
+            })
+            .collect();
+        for b in 0..blocks.len() {
+            for succ in blocks[b].successors.clone() {
+                blocks[succ].predecessors.push(b);
+            }
+        }
+        ControlFlowGraph { blocks }
+    }
+
+    /// Blocks reachable from the entry in reverse postorder.
+    pub fn reverse_postorder(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.blocks.len()];
+        let mut order = Vec::new();
+        if self.blocks.is_empty() {
+            return order;
+        }
+
+        // Iterative DFS; the flag marks whether children were already pushed
+        let mut stack = vec![(0, false)];
+        while let Some((b, expanded)) = stack.pop() {
+            if expanded {
+                order.push(b);
+                continue;
+            }
+            if visited[b] {
+                continue;
+            }
+            visited[b] = true;
+            stack.push((b, true));
+            for &succ in self.blocks[b].successors.iter().rev() {
+                if !visited[succ] {
+                    stack.push((succ, false));
+                }
+            }
+        }
+
+        order.reverse();
+        order
+    }
+
+    /// Immediate dominator of each block (`None` for the entry and for
+    /// unreachable blocks), computed with the Cooper-Harvey-Kennedy algorithm.
+    pub fn immediate_dominators(&self) -> Vec<Option<usize>> {
+        let rpo = self.reverse_postorder();
+        let mut rpo_index = vec![usize::MAX; self.blocks.len()];
+        for (i, &b) in rpo.iter().enumerate() {
+            rpo_index[b] = i;
+        }
+
+        let mut idom: Vec<Option<usize>> = vec![None; self.blocks.len()];
+        if rpo.is_empty() {
+            return idom;
+        }
+        idom[0] = Some(0);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &b in rpo.iter().skip(1) {
+                let mut new_idom: Option<usize> = None;
+                for &pred in &self.blocks[b].predecessors {
+                    if idom[pred].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(current) => intersect(&idom, &rpo_index, pred, current),
+                    });
+                }
+                if new_idom.is_some() && idom[b] != new_idom {
+                    idom[b] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        idom[0] = None;
+        idom
+    }
+}
+
+fn intersect(idom: &[Option<usize>], rpo_index: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while rpo_index[a] > rpo_index[b] {
+            a = idom[a].unwrap();
+        }
+        while rpo_index[b] > rpo_index[a] {
+            b = idom[b].unwrap();
+        }
+    }
+    a
+}
@@ -0,0 +1,435 @@
+use super::cfg::ControlFlowGraph;
+use crate::ir::{BinaryOp, IRFunction, IRInstruction, UnaryOp};
+use std::collections::{HashMap, HashSet};
+
+type ValueNumber = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ValueKey {
+    /// A constant by its index, which names it alone in a module whose
+    /// constants were added with `add_constant`.
+    Const(u16),
+    Binary(BinaryOp, ValueNumber, ValueNumber),
+    Unary(UnaryOp, ValueNumber),
+}
+
+/// A value on the symbolic operand stack. `range` is the contiguous,
+/// side-effect free instruction range that computed it, if there is one.
+#[derive(Clone, Copy)]
+struct StackEntry {
+    vn: ValueNumber,
+    range: Option<(usize, usize)>,
+}
+
+/// Facts that hold on entry to a block, inherited from its immediate dominator.
+#[derive(Clone, Default)]
+struct ScopeState {
+    vars: HashMap<String, ValueNumber>,
+    // Value number -> index of the dominating instruction that computed it
+    computed: HashMap<ValueNumber, usize>,
+}
+
+struct GlobalValueNumbering<'a> {
+    function: &'a IRFunction,
+    /// The function's linear form, which instruction indices refer to.
+    instructions: Vec<IRInstruction>,
+    table: HashMap<ValueKey, ValueNumber>,
+    next_vn: ValueNumber,
+    // Instruction index -> temp that should capture its result
+    temps: HashMap<usize, String>,
+    next_temp: usize,
+    replacements: Vec<(usize, usize, String)>,
+}
+
+impl<'a> GlobalValueNumbering<'a> {
+    fn new(function: &'a IRFunction) -> Self {
+        Self {
+            function,
+            instructions: function.instructions(),
+            table: HashMap::new(),
+            next_vn: 0,
+            temps: HashMap::new(),
+            next_temp: 0,
+            replacements: Vec::new(),
+        }
+    }
+
+    fn fresh(&mut self) -> ValueNumber {
+        self.next_vn += 1;
+        self.next_vn
+    }
+
+    fn number(&mut self, key: ValueKey) -> ValueNumber {
+        if let Some(&vn) = self.table.get(&key) {
+            return vn;
+        }
+        let vn = self.fresh();
+        self.table.insert(key, vn);
+        vn
+    }
+
+    fn temp_for(&mut self, index: usize) -> String {
+        if let Some(temp) = self.temps.get(&index) {
+            return temp.clone();
+        }
+        let temp = format!("$gvn{}", self.next_temp);
+        self.next_temp += 1;
+        self.temps.insert(index, temp.clone());
+        temp
+    }
+
+    fn pop(&mut self, stack: &mut Vec<StackEntry>) -> StackEntry {
+        stack.pop().unwrap_or_else(|| StackEntry {
+            vn: self.fresh(),
+            range: None,
+        })
+    }
+
+    /// Forget everything a call could invalidate. Only parameters are
+    /// guaranteed to live in the caller's frame.
+    fn clobber(&self, state: &mut ScopeState) {
+        let params = &self.function.params;
+        state.vars.retain(|name, _| params.contains(name));
+        state.computed.clear();
+    }
+
+    fn visit_block(&mut self, cfg: &ControlFlowGraph, block: usize, state: &mut ScopeState) {
+        let mut stack: Vec<StackEntry> = Vec::new();
+
+        let block = &cfg.blocks[block];
+        for i in block.start..block.end {
+            let instruction = self.instructions[i].clone();
+            match &instruction {
+                IRInstruction::PushConst(constant) => {
+                    let vn = self.number(ValueKey::Const(*constant));
+                    stack.push(StackEntry {
+                        vn,
+                        range: Some((i, i)),
+                    });
+                }
+                IRInstruction::Load(name) => {
+                    let vn = match state.vars.get(name) {
+                        Some(&vn) => vn,
+                        None => {
+                            let vn = self.fresh();
+                            state.vars.insert(name.clone(), vn);
+                            vn
+                        }
+                    };
+                    stack.push(StackEntry {
+                        vn,
+                        range: Some((i, i)),
+                    });
+                }
+                IRInstruction::Store(name) => {
+                    let value = self.pop(&mut stack);
+                    state.vars.insert(name.clone(), value.vn);
+                }
+                IRInstruction::Binary(op) => {
+                    let right = self.pop(&mut stack);
+                    let left = self.pop(&mut stack);
+                    let vn = self.number(ValueKey::Binary(op.clone(), left.vn, right.vn));
+                    let range = match (left.range, right.range) {
+                        (Some((start, left_end)), Some((right_start, right_end)))
+                            if left_end + 1 == right_start && right_end + 1 == i =>
+                        {
+                            Some((start, i))
+                        }
+                        _ => None,
+                    };
+                    stack.push(self.reuse(state, vn, range, i));
+                }
+                IRInstruction::Unary(op) => {
+                    let operand = self.pop(&mut stack);
+                    let vn = self.number(ValueKey::Unary(op.clone(), operand.vn));
+                    let range = match operand.range {
+                        Some((start, end)) if end + 1 == i => Some((start, i)),
+                        _ => None,
+                    };
+                    stack.push(self.reuse(state, vn, range, i));
+                }
+                IRInstruction::Dup => {
+                    let value = self.pop(&mut stack);
+                    stack.push(value);
+                    stack.push(StackEntry {
+                        vn: value.vn,
+                        range: None,
+                    });
+                }
+                IRInstruction::Pop
+                | IRInstruction::JumpIf(_)
+                | IRInstruction::Return(true)
+                | IRInstruction::StoreUpvalue(_) => {
+                    self.pop(&mut stack);
+                }
+                // Captures and properties are not numbered, so each load is
+                // a new value
+                IRInstruction::LoadUpvalue(_) => {
+                    let vn = self.fresh();
+                    stack.push(StackEntry { vn, range: None });
+                }
+                IRInstruction::GetProperty(_) => {
+                    self.pop(&mut stack);
+                    let vn = self.fresh();
+                    stack.push(StackEntry { vn, range: None });
+                }
+                IRInstruction::MakeClosure(_, count) => {
+                    for _ in 0..*count {
+                        self.pop(&mut stack);
+                    }
+                    let vn = self.fresh();
+                    stack.push(StackEntry { vn, range: None });
+                }
+                IRInstruction::Call(_, argc) | IRInstruction::CallNative(_, argc) => {
+                    for _ in 0..*argc {
+                        self.pop(&mut stack);
+                    }
+                    self.clobber(state);
+                    let vn = self.fresh();
+                    stack.push(StackEntry { vn, range: None });
+                }
+                IRInstruction::Label(_)
+                | IRInstruction::Jump(_)
+                | IRInstruction::Return(false)
+                | IRInstruction::Line(..) => {}
+            }
+        }
+    }
+
+    /// Decide whether the pure computation ending at `index` is redundant, and
+    /// if so record a replacement that loads the value from where it lives.
+    fn reuse(
+        &mut self,
+        state: &mut ScopeState,
+        vn: ValueNumber,
+        range: Option<(usize, usize)>,
+        index: usize,
+    ) -> StackEntry {
+        let entry = StackEntry { vn, range };
+        let Some((start, end)) = range else {
+            state.computed.entry(vn).or_insert(index);
+            return entry;
+        };
+
+        let holder = state
+            .vars
+            .iter()
+            .filter(|(_, &held)| held == vn)
+            .map(|(name, _)| name.clone())
+            .min();
+        let holder = match holder {
+            Some(name) => name,
+            None => match state.computed.get(&vn) {
+                Some(&first) => self.temp_for(first),
+                None => {
+                    state.computed.insert(vn, index);
+                    return entry;
+                }
+            },
+        };
+
+        // Anything first computed inside the replaced range no longer exists
+        state.computed.retain(|_, &mut at| at < start || at > end);
+        self.temps.retain(|&at, _| at < start || at > end);
+        self.replacements.push((start, end, holder));
+        entry
+    }
+
+    fn run(mut self) -> Option<(Vec<IRInstruction>, usize)> {
+        let cfg = ControlFlowGraph::build(self.function);
+        let idom = cfg.immediate_dominators();
+
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); cfg.blocks.len()];
+        for (b, parent) in idom.iter().enumerate() {
+            if let Some(parent) = parent {
+                children[*parent].push(b);
+            }
+        }
+
+        // Walk the dominator tree, handing each block its dominator's exit state
+        let mut work_list = vec![(0, ScopeState::default())];
+        while let Some((block, mut state)) = work_list.pop() {
+            if block >= cfg.blocks.len() {
+                break;
+            }
+            if let Some(parent) = idom[block] {
+                self.invalidate_between(&cfg, parent, block, &mut state);
+            }
+            self.visit_block(&cfg, block, &mut state);
+            for &child in &children[block] {
+                work_list.push((child, state.clone()));
+            }
+        }
+
+        if self.replacements.is_empty() {
+            return None;
+        }
+        let temp_count = self.temps.len();
+        Some((self.rewrite(), temp_count))
+    }
+
+    /// Invalidate facts killed on any path from the end of `dominator` to the
+    /// start of `block` (including around loops back into `block`).
+    fn invalidate_between(
+        &self,
+        cfg: &ControlFlowGraph,
+        dominator: usize,
+        block: usize,
+        state: &mut ScopeState,
+    ) {
+        let mut seen = HashSet::new();
+        let mut work_list: Vec<usize> = cfg.blocks[block].predecessors.clone();
+        while let Some(b) = work_list.pop() {
+            if b == dominator || !seen.insert(b) {
+                continue;
+            }
+            work_list.extend(cfg.blocks[b].predecessors.iter().copied());
+        }
+
+        for b in seen {
+            let block = &cfg.blocks[b];
+            for instruction in &self.instructions[block.start..block.end] {
+                match instruction {
+                    IRInstruction::Store(name) => {
+                        state.vars.remove(name);
+                    }
+                    IRInstruction::Call(..) | IRInstruction::CallNative(..) => self.clobber(state),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn rewrite(mut self) -> Vec<IRInstruction> {
+        // Only the outermost replacement of nested ranges survives
+        self.replacements
+            .sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        let mut kept: Vec<(usize, usize, String)> = Vec::new();
+        for replacement in self.replacements.drain(..) {
+            match kept.last() {
+                Some(last) if replacement.0 <= last.1 => {}
+                _ => kept.push(replacement),
+            }
+        }
+
+        let instructions = &self.instructions;
+        let mut output = Vec::with_capacity(instructions.len());
+        let mut kept = kept.into_iter().peekable();
+        let mut i = 0;
+        while i < instructions.len() {
+            if let Some((_, end, holder)) = kept.next_if(|(start, _, _)| *start == i) {
+                output.push(IRInstruction::Load(holder));
+                i = end + 1;
+                continue;
+            }
+            output.push(instructions[i].clone());
+            if let Some(temp) = self.temps.get(&i) {
+                output.push(IRInstruction::Dup);
+                output.push(IRInstruction::Store(temp.clone()));
+            }
+            i += 1;
+        }
+        output
+    }
+}
+
+/// Global value numbering over the dominator tree of the function's CFG.
+/// Redundant pure expressions are replaced by a load of the variable that
+/// already holds the value, or of a temp captured at the dominating
+/// computation.
+pub fn global_value_numbering(function: &mut IRFunction) {
+    if let Some((instructions, temp_count)) = GlobalValueNumbering::new(function).run() {
+        function
+            .set_instructions(instructions)
+            .expect("value numbering keeps the labels");
+        function.max_locals += temp_count as u16;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{lower_ast, verify, IRModule};
+    use crate::lexer::tokenize;
+    use crate::parser::parse;
+    use crate::vm::{Value, VM};
+
+    fn lower(source: &str) -> IRModule {
+        lower_ast(parse(tokenize(source)))
+    }
+
+    fn binary_count(function: &IRFunction, op: BinaryOp) -> usize {
+        function
+            .block_instructions()
+            .filter(|inst| matches!(inst, IRInstruction::Binary(o) if *o == op))
+            .count()
+    }
+
+    #[test]
+    fn test_reuses_value_held_by_variable() {
+        let mut module = lower("function f(x, y) { let a = x * y; let b = x * y; return a + b; }");
+        let function = &mut module.functions[0];
+        global_value_numbering(function);
+
+        assert_eq!(binary_count(function, BinaryOp::Mul), 1);
+        assert_eq!(verify(&module), Ok(()));
+    }
+
+    #[test]
+    fn test_redundancy_across_blocks_uses_temp() {
+        let mut module = lower(
+            "function f(x, y) {
+                print(1);
+                if (x > y) { return (x - y) * 2; }
+                return (x - y) * 3;
+             }
+             function g(x, y) { return (x - y) + (x - y); }",
+        );
+        for function in &mut module.functions {
+            global_value_numbering(function);
+        }
+
+        // `x - y` in g is computed once and captured in a temp
+        assert_eq!(binary_count(&module.functions[1], BinaryOp::Sub), 1);
+        assert!(module.functions[1]
+            .block_instructions()
+            .any(|inst| matches!(inst, IRInstruction::Store(name) if name == "$gvn0")));
+        assert_eq!(verify(&module), Ok(()));
+
+        let mut vm = VM::new(module);
+        let result = vm.execute_function("g", vec![Value::Number(7.0), Value::Number(2.0)]);
+        assert_eq!(result, Value::Number(10.0));
+    }
+
+    #[test]
+    fn test_dominating_computation_reused_in_branch() {
+        let mut module = lower(
+            "function f(x, y) {
+                let a = x * y + 1;
+                if (x > 0) { return x * y + 1; }
+                return 0;
+             }",
+        );
+        let function = &mut module.functions[0];
+        global_value_numbering(function);
+
+        assert_eq!(binary_count(function, BinaryOp::Mul), 1);
+        assert_eq!(binary_count(function, BinaryOp::Add), 1);
+    }
+
+    #[test]
+    fn test_store_in_loop_blocks_reuse() {
+        let mut module = lower(
+            "function f(n) {
+                let a = n + 1;
+                let i = 0;
+                while (i < 3) { let n = i; let i = n; }
+                return n + 1;
+             }",
+        );
+        let function = &mut module.functions[0];
+        global_value_numbering(function);
+
+        assert_eq!(binary_count(function, BinaryOp::Add), 2);
+    }
+}
@@ -1,5 +1,33 @@
-use crate::ir::{BinaryOp, Constant, IRFunction, IRInstruction, IRModule, UnaryOp};
-use std::collections::{HashMap, HashSet};
+pub mod cfg;
+mod gvn;
+
+use crate::ir::{
+    add_constant, constant_index, BinaryOp, BlockId, Constant, IRFunction, IRInstruction, IRModule,
+    Terminator, UnaryOp,
+};
+use crate::{log, parallel, timings};
+use std::collections::HashSet;
+
+/// How aggressively `optimize_with` transforms the module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum OptLevel {
+    #[default]
+    O0,
+    O1,
+    O2,
+}
+
+impl OptLevel {
+    /// Parse a `-O0`/`-O1`/`-O2` command-line flag.
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "-O0" => Some(OptLevel::O0),
+            "-O1" => Some(OptLevel::O1),
+            "-O2" => Some(OptLevel::O2),
+            _ => None,
+        }
+    }
+}
 
 struct Optimizer {
     module: IRModule,
@@ -11,152 +39,244 @@ impl Optimizer {
     }
 
     fn constant_folding(&mut self) -> &mut Self {
-        for function in &mut self.module.functions {
-            let mut i = 0;
-            while i < function.instructions.len() {
-                let instructions = function.instructions[i..].to_vec();
-                let folded = Self::try_fold_constants(&instructions);
-                if let Some(folded) = folded {
-                    // Replace the instruction(s) with the folded constant
-                    function
-                        .instructions
-                        .splice(i..i + folded.len, folded.result);
-                    i += 1;
-                } else {
-                    i += 1;
+        let IRModule {
+            functions,
+            constants,
+            ..
+        } = &mut self.module;
+        // Functions fold in parallel, each adding the constants it folds to
+        // after the module's; those are added to the module's afterwards,
+        // in the order folding one function at a time would add them
+        let module_constants: &[Constant] = constants;
+        let added = parallel::map_mut(functions, |function| {
+            let mut constants = FoldConstants {
+                module: module_constants,
+                added: Vec::new(),
+            };
+            Self::fold_function(function, &mut constants);
+            constants.added
+        });
+        let count = constants.len();
+        for (function, added) in self.module.functions.iter_mut().zip(added) {
+            let indices: Vec<u16> = added
+                .into_iter()
+                .map(|constant| add_constant(&mut self.module.constants, constant))
+                .collect();
+            let instructions = function
+                .blocks
+                .iter_mut()
+                .flat_map(|block| &mut block.instructions);
+            for instruction in instructions {
+                if let IRInstruction::PushConst(index) = instruction {
+                    if let Some(added) = (*index as usize).checked_sub(count) {
+                        *index = indices[added];
+                    }
                 }
             }
         }
         self
     }
 
-    fn try_fold_constants(instructions: &[IRInstruction]) -> Option<FoldResult> {
-        match &instructions[0] {
-            IRInstruction::Binary(op) => {
-                // Look for pattern: PushConst, PushConst, Binary
-                if instructions.len() < 3 {
-                    return None;
-                }
+    fn fold_function(function: &mut IRFunction, constants: &mut FoldConstants) {
+        let mut code = function.instructions();
+        let mut i = 0;
+        while i < code.len() {
+            let instructions = code[i..].to_vec();
+            let folded = Self::try_fold_constants(&instructions, constants);
+            if let Some(folded) = folded {
+                // Replace the instruction(s) with the folded constant
+                // and look again from the constant before it, which may
+                // fold with the result
+                code.splice(i..i + folded.len, folded.result);
+                i = i.saturating_sub(1);
+            } else {
+                i += 1;
+            }
+        }
+        function
+            .set_instructions(code)
+            .expect("folding keeps the labels");
+    }
 
-                if let (
-                    IRInstruction::PushConst(left),
-                    IRInstruction::PushConst(right),
-                    IRInstruction::Binary(bin_op),
-                ) = (&instructions[0], &instructions[1], &instructions[2])
-                {
-                    let result = match (left, right, bin_op) {
-                        (Constant::Number(a), Constant::Number(b), BinaryOp::Add) => {
-                            Some(Constant::Number(a + b))
-                        }
-                        (Constant::Number(a), Constant::Number(b), BinaryOp::Sub) => {
-                            Some(Constant::Number(a - b))
-                        }
-                        (Constant::Number(a), Constant::Number(b), BinaryOp::Mul) => {
-                            Some(Constant::Number(a * b))
-                        }
-                        (Constant::Number(a), Constant::Number(b), BinaryOp::Div) if *b != 0.0 => {
-                            Some(Constant::Number(a / b))
-                        }
-                        (Constant::String(a), Constant::String(b), BinaryOp::Add) => {
-                            Some(Constant::String(a.clone() + b))
-                        }
-                        _ => None,
-                    };
+    /// Fold the instructions at the start of `instructions`, adding the
+    /// constant they fold to to `constants`.
+    fn try_fold_constants(
+        instructions: &[IRInstruction],
+        constants: &mut FoldConstants,
+    ) -> Option<FoldResult> {
+        match instructions {
+            [IRInstruction::PushConst(left), IRInstruction::PushConst(right), IRInstruction::Binary(bin_op), ..] =>
+            {
+                let (left, right) = (constants.get(*left), constants.get(*right));
+                let result = match (left, right, bin_op) {
+                    (Constant::Number(a), Constant::Number(b), BinaryOp::Add) => {
+                        Some(Constant::Number(a + b))
+                    }
+                    (Constant::Number(a), Constant::Number(b), BinaryOp::Sub) => {
+                        Some(Constant::Number(a - b))
+                    }
+                    (Constant::Number(a), Constant::Number(b), BinaryOp::Mul) => {
+                        Some(Constant::Number(a * b))
+                    }
+                    (Constant::Number(a), Constant::Number(b), BinaryOp::Div) if *b != 0.0 => {
+                        Some(Constant::Number(a / b))
+                    }
+                    (Constant::String(a), Constant::String(b), BinaryOp::Add) => {
+                        Some(Constant::String(a.clone() + b))
+                    }
+                    _ => None,
+                };
 
-                    result.map(|const_result| FoldResult {
-                        result: vec![IRInstruction::PushConst(const_result)],
-                        len: 3,
-                    })
-                } else {
-                    None
-                }
+                result.map(|const_result| FoldResult {
+                    result: vec![IRInstruction::PushConst(constants.add(const_result))],
+                    len: 3,
+                })
             }
-            IRInstruction::Unary(op) => {
-                // Look for pattern: PushConst, Unary
-                if instructions.len() < 2 {
-                    return None;
-                }
-
-                if let IRInstruction::PushConst(constant) = &instructions[1] {
-                    let result = match (op, constant) {
-                        (UnaryOp::Neg, Constant::Number(n)) => Some(Constant::Number(-n)),
-                        (UnaryOp::Not, Constant::Boolean(b)) => Some(Constant::Boolean(!b)),
-                        _ => None,
-                    };
+            [IRInstruction::PushConst(constant), IRInstruction::Unary(op), ..] => {
+                let result = match (op, constants.get(*constant)) {
+                    (UnaryOp::Neg, Constant::Number(n)) => Some(Constant::Number(-n)),
+                    (UnaryOp::Not, Constant::Boolean(b)) => Some(Constant::Boolean(!b)),
+                    _ => None,
+                };
 
-                    result.map(|const_result| FoldResult {
-                        result: vec![IRInstruction::PushConst(const_result)],
-                        len: 2,
-                    })
-                } else {
-                    None
-                }
+                result.map(|const_result| FoldResult {
+                    result: vec![IRInstruction::PushConst(constants.add(const_result))],
+                    len: 2,
+                })
             }
             _ => None,
         }
     }
 
     fn dead_code_elimination(&mut self) -> &mut Self {
-        for function in &mut self.module.functions {
-            // Find all reachable instructions
-            let reachable = Self::find_reachable_instructions(function);
+        parallel::map_mut(&mut self.module.functions, |function| {
+            let reachable = Self::find_reachable_blocks(function);
 
-            // Remove unreachable instructions
-            function.instructions = function
-                .instructions
-                .iter()
+            // Blocks fall through only into reachable blocks, so removing
+            // the rest leaves control flow as it was
+            let mut new_ids = vec![0; function.blocks.len()];
+            let mut next = 0;
+            for (id, new_id) in new_ids.iter_mut().enumerate() {
+                *new_id = next;
+                next += reachable.contains(&id) as usize;
+            }
+            let blocks = std::mem::take(&mut function.blocks);
+            function.blocks = blocks
+                .into_iter()
                 .enumerate()
-                .filter(|(i, _)| reachable.contains(i))
-                .map(|(_, instr)| instr.clone())
+                .filter(|(id, _)| reachable.contains(id))
+                .map(|(_, mut block)| {
+                    block.terminator = match block.terminator {
+                        Terminator::Jump(target) => Terminator::Jump(new_ids[target]),
+                        Terminator::JumpIf(target) => Terminator::JumpIf(new_ids[target]),
+                        terminator => terminator,
+                    };
+                    block
+                })
                 .collect();
-        }
+        });
         self
     }
 
-    fn find_reachable_instructions(function: &IRFunction) -> HashSet<usize> {
+    fn find_reachable_blocks(function: &IRFunction) -> HashSet<BlockId> {
         let mut reachable = HashSet::new();
-        let mut work_list = vec![0]; // Start from first instruction
-        let mut label_positions = HashMap::new();
-
-        // First pass: collect all label positions
-        for (i, instr) in function.instructions.iter().enumerate() {
-            if let IRInstruction::Label(label) = instr {
-                label_positions.insert(label.clone(), i);
+        // Start from the entry block and the exception handlers
+        let mut work_list = vec![0];
+        work_list.extend(
+            function
+                .exception_table
+                .iter()
+                .filter_map(|handler| function.block_named(&handler.handler_label)),
+        );
+        while let Some(id) = work_list.pop() {
+            if id >= function.blocks.len() || !reachable.insert(id) {
+                continue;
             }
+            work_list.extend(function.successors(id));
         }
+        reachable
+    }
 
-        // Second pass: find all reachable instructions
-        while let Some(pos) = work_list.pop() {
-            if pos >= function.instructions.len() || !reachable.insert(pos) {
-                continue;
-            }
+    fn global_value_numbering(&mut self) -> &mut Self {
+        parallel::map_mut(&mut self.module.functions, gvn::global_value_numbering);
+        self
+    }
 
-            match &function.instructions[pos] {
-                IRInstruction::Jump(label) => {
-                    if let Some(&target) = label_positions.get(label) {
-                        work_list.push(target);
-                    }
-                }
-                IRInstruction::JumpIf(label) => {
-                    if let Some(&target) = label_positions.get(label) {
-                        work_list.push(target);
-                    }
-                    work_list.push(pos + 1); // Fall-through path
-                }
-                IRInstruction::Return(_) => {
-                    // No more instructions after return
-                }
-                _ => {
-                    work_list.push(pos + 1); // Sequential execution
-                }
+    /// Run `pass`, logging how many instructions it removed or added and
+    /// timing it.
+    fn logged(&mut self, name: &str, pass: fn(&mut Self) -> &mut Self) -> &mut Self {
+        let sizes: Vec<usize> = self
+            .module
+            .functions
+            .iter()
+            .map(|function| function.instruction_count())
+            .collect();
+        timings::time(name, || pass(self));
+        for (function, before) in self.module.functions.iter().zip(&sizes) {
+            let after = function.instruction_count();
+            if after != *before {
+                log::debug(
+                    "opt",
+                    format_args!(
+                        "{}: {}: {} -> {} instructions",
+                        name, function.name, before, after
+                    ),
+                );
             }
         }
-
-        reachable
+        log::info(
+            "opt",
+            format_args!(
+                "{}: {} -> {} instructions",
+                name,
+                sizes.iter().sum::<usize>(),
+                self.module.instruction_count()
+            ),
+        );
+        self
     }
 
     fn run_all_passes(&mut self) -> &mut Self {
-        self.constant_folding().dead_code_elimination()
+        self.logged("constant folding", Self::constant_folding)
+            .logged("dead code elimination", Self::dead_code_elimination)
+    }
+
+    fn run_passes_for(&mut self, level: OptLevel) -> &mut Self {
+        if level >= OptLevel::O1 {
+            self.run_all_passes();
+        }
+        if level >= OptLevel::O2 {
+            self.logged("global value numbering", Self::global_value_numbering);
+        }
+        self
+    }
+}
+
+/// The constants a function folding in parallel with others sees: the
+/// module's, then those it folded to that the module does not have.
+struct FoldConstants<'a> {
+    module: &'a [Constant],
+    added: Vec<Constant>,
+}
+
+impl FoldConstants<'_> {
+    fn get(&self, index: u16) -> &Constant {
+        let index = index as usize;
+        match index.checked_sub(self.module.len()) {
+            Some(added) => &self.added[added],
+            None => &self.module[index],
+        }
+    }
+
+    /// Index of `constant`, adding it if it is not there yet.
+    fn add(&mut self, constant: Constant) -> u16 {
+        match self.module.iter().position(|c| c.same(&constant)) {
+            Some(index) => index as u16,
+            None => {
+                let added = add_constant(&mut self.added, constant) as usize;
+                constant_index(self.module.len() + added)
+            }
+        }
     }
 }
 
@@ -170,3 +290,9 @@ pub fn optimize(module: IRModule) -> IRModule {
     optimizer.run_all_passes();
     optimizer.module
 }
+
+pub fn optimize_with(module: IRModule, level: OptLevel) -> IRModule {
+    let mut optimizer = Optimizer::new(module);
+    optimizer.run_passes_for(level);
+    optimizer.module
+}
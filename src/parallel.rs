@@ -0,0 +1,129 @@
+//! Running work on each function of a module across threads: lowering,
+//! the optimizer's passes and the backends' analyses. Items are split
+//! into one run of consecutive items per core, and results come back in
+//! the items' order, so what is compiled does not depend on the number of
+//! threads. Where there is one core, or no threads as on wasm32, the work
+//! runs on the calling thread.
+//!
+//! An error raised for an item is raised again on the calling thread, and
+//! if several items raise one, it is the first item's, as if they had run
+//! one at a time. Workers run inside `ffi`'s `catch` when the calling
+//! thread does, so its errors are reported the same way.
+
+use crate::ffi;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::thread;
+
+/// Threads to split work between.
+fn threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// `f` of each of `items`, in order.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = threads().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let f = &f;
+    let catching = ffi::catching();
+    let chunk_size = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    ffi::set_catching(catching);
+                    chunk.iter().map(f).collect::<Vec<R>>()
+                })
+            })
+            .collect();
+        let mut results = Vec::with_capacity(items.len());
+        for worker in workers {
+            match worker.join() {
+                Ok(chunk) => results.extend(chunk),
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+        results
+    })
+}
+
+/// `f` of each of `items`, which it can change, in order.
+pub fn map_mut<T: Send, R: Send>(items: &mut [T], f: impl Fn(&mut T) -> R + Sync) -> Vec<R> {
+    let threads = threads().min(items.len());
+    if threads <= 1 {
+        return items.iter_mut().map(f).collect();
+    }
+    let f = &f;
+    let catching = ffi::catching();
+    let chunk_size = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks_mut(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    ffi::set_catching(catching);
+                    chunk.iter_mut().map(f).collect::<Vec<R>>()
+                })
+            })
+            .collect();
+        let mut results = Vec::new();
+        for worker in workers {
+            match worker.join() {
+                Ok(chunk) => results.extend(chunk),
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+        results
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::Diagnostic;
+
+    #[test]
+    fn test_results_keep_the_items_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = map(&items, |n| n * n);
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+
+        let mut items = items;
+        let before = map_mut(&mut items, |n| {
+            *n += 1;
+            *n - 1
+        });
+        assert_eq!(before, (0..1000).collect::<Vec<_>>());
+        assert_eq!(items, (1..1001).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_raises_the_first_items_error() {
+        let items: Vec<usize> = (0..1000).collect();
+        let payload = panic::catch_unwind(|| {
+            map(&items, |&n| {
+                if n % 100 == 50 {
+                    Diagnostic::error(format!("item {}", n)).raise();
+                }
+                n
+            })
+        })
+        .unwrap_err();
+        assert_eq!(Diagnostic::from_panic(payload).message, "item 50");
+    }
+
+    #[test]
+    fn test_workers_catch_as_the_calling_thread_does() {
+        let items: Vec<usize> = (0..1000).collect();
+        ffi::set_catching(true);
+        let catching = map(&items, |_| ffi::catching());
+        ffi::set_catching(false);
+        assert!(catching.iter().all(|&catching| catching));
+        assert!(map(&items, |_| ffi::catching())
+            .iter()
+            .all(|&catching| !catching));
+    }
+}
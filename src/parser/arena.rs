@@ -0,0 +1,221 @@
+//! The arena the nodes of an AST live in. Expressions and statements are
+//! pushed onto one vector each as they are parsed, and refer to their
+//! children by index, rather than each being a box of its own: a big file
+//! takes a few allocations instead of one per node, and a pass over it
+//! walks memory in the order it was written.
+
+use super::{Expression, Statement};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// An expression in an `Arena`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExprId(u32);
+
+/// A statement in an `Arena`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StmtId(u32);
+
+#[derive(Debug, Clone, Default)]
+pub struct Arena {
+    expressions: Vec<Expression>,
+    statements: Vec<Statement>,
+}
+
+impl Arena {
+    pub fn new() -> Self {
+        Arena::default()
+    }
+
+    pub fn expression(&mut self, expression: Expression) -> ExprId {
+        self.expressions.push(expression);
+        ExprId(self.expressions.len() as u32 - 1)
+    }
+
+    pub fn statement(&mut self, statement: Statement) -> StmtId {
+        self.statements.push(statement);
+        StmtId(self.statements.len() as u32 - 1)
+    }
+
+    /// How many nodes there are, expressions and statements.
+    pub fn len(&self) -> usize {
+        self.expressions.len() + self.statements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Index<ExprId> for Arena {
+    type Output = Expression;
+
+    fn index(&self, id: ExprId) -> &Expression {
+        &self.expressions[id.0 as usize]
+    }
+}
+
+impl IndexMut<ExprId> for Arena {
+    fn index_mut(&mut self, id: ExprId) -> &mut Expression {
+        &mut self.expressions[id.0 as usize]
+    }
+}
+
+impl Index<StmtId> for Arena {
+    type Output = Statement;
+
+    fn index(&self, id: StmtId) -> &Statement {
+        &self.statements[id.0 as usize]
+    }
+}
+
+impl IndexMut<StmtId> for Arena {
+    fn index_mut(&mut self, id: StmtId) -> &mut Statement {
+        &mut self.statements[id.0 as usize]
+    }
+}
+
+/// A node and the arena it is in. Its `Debug` shows the nodes it refers to
+/// where the derived one would show their ids.
+pub(super) struct Node<'a, T> {
+    pub arena: &'a Arena,
+    pub id: T,
+}
+
+impl Arena {
+    pub(super) fn nodes<T: Copy>(&self, ids: &[T]) -> Vec<Node<'_, T>> {
+        ids.iter().map(|&id| Node { arena: self, id }).collect()
+    }
+
+    fn node<T>(&self, id: T) -> Node<'_, T> {
+        Node { arena: self, id }
+    }
+}
+
+impl fmt::Debug for Node<'_, ExprId> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arena = self.arena;
+        match &arena[self.id] {
+            Expression::Number(n) => f.debug_tuple("Number").field(n).finish(),
+            Expression::String(s) => f.debug_tuple("String").field(s).finish(),
+            Expression::Boolean(b) => f.debug_tuple("Boolean").field(b).finish(),
+            Expression::Null => f.write_str("Null"),
+            Expression::Identifier(name) => f.debug_tuple("Identifier").field(name).finish(),
+            Expression::FunctionCall { name, arguments } => f
+                .debug_struct("FunctionCall")
+                .field("name", name)
+                .field("arguments", &arena.nodes(arguments))
+                .finish(),
+            Expression::Member { object, property } => f
+                .debug_struct("Member")
+                .field("object", &arena.node(*object))
+                .field("property", property)
+                .finish(),
+            Expression::MethodCall {
+                object,
+                method,
+                arguments,
+            } => f
+                .debug_struct("MethodCall")
+                .field("object", &arena.node(*object))
+                .field("method", method)
+                .field("arguments", &arena.nodes(arguments))
+                .finish(),
+            Expression::BinaryOp { op, left, right } => f
+                .debug_struct("BinaryOp")
+                .field("op", op)
+                .field("left", &arena.node(*left))
+                .field("right", &arena.node(*right))
+                .finish(),
+            Expression::UnaryOp { op, expr } => f
+                .debug_struct("UnaryOp")
+                .field("op", op)
+                .field("expr", &arena.node(*expr))
+                .finish(),
+            Expression::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => f
+                .debug_struct("Conditional")
+                .field("condition", &arena.node(*condition))
+                .field("then_expr", &arena.node(*then_expr))
+                .field("else_expr", &arena.node(*else_expr))
+                .finish(),
+        }
+    }
+}
+
+impl fmt::Debug for Node<'_, StmtId> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arena = self.arena;
+        match &arena[self.id] {
+            Statement::Let { name, initializer } => f
+                .debug_struct("Let")
+                .field("name", name)
+                .field("initializer", &arena.node(*initializer))
+                .finish(),
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => f
+                .debug_struct("If")
+                .field("condition", &arena.node(*condition))
+                .field("then_branch", &arena.nodes(then_branch))
+                .field(
+                    "else_branch",
+                    &else_branch.as_deref().map(|body| arena.nodes(body)),
+                )
+                .finish(),
+            Statement::While { condition, body } => f
+                .debug_struct("While")
+                .field("condition", &arena.node(*condition))
+                .field("body", &arena.nodes(body))
+                .finish(),
+            Statement::For {
+                initializer,
+                condition,
+                update,
+                body,
+            } => f
+                .debug_struct("For")
+                .field("initializer", &initializer.map(|clause| arena.node(clause)))
+                .field(
+                    "condition",
+                    &condition.map(|condition| arena.node(condition)),
+                )
+                .field("update", &update.map(|clause| arena.node(clause)))
+                .field("body", &arena.nodes(body))
+                .finish(),
+            Statement::FunctionDeclaration { name, params, body } => f
+                .debug_struct("FunctionDeclaration")
+                .field("name", name)
+                .field("params", params)
+                .field("body", &arena.nodes(body))
+                .finish(),
+            Statement::Return(value) => f
+                .debug_tuple("Return")
+                .field(&value.map(|value| arena.node(value)))
+                .finish(),
+            statement @ Statement::ExternDeclaration { .. } => statement.fmt(f),
+            Statement::Block(body) => f.debug_tuple("Block").field(&arena.nodes(body)).finish(),
+            Statement::ExpressionStatement(expression) => f
+                .debug_tuple("ExpressionStatement")
+                .field(&arena.node(*expression))
+                .finish(),
+            Statement::Located {
+                line,
+                column,
+                end_line,
+                statement,
+            } => f
+                .debug_struct("Located")
+                .field("line", line)
+                .field("column", column)
+                .field("end_line", end_line)
+                .field("statement", &arena.node(*statement))
+                .finish(),
+        }
+    }
+}
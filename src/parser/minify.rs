@@ -0,0 +1,530 @@
+//! Minified source: the program on one line without spaces, with the
+//! parameters and variables of each function renamed to the shortest
+//! names free there, and the functions and externs no call can reach left
+//! out.
+//!
+//! Functions are reached from `main` and the top-level statements; a file
+//! without `main` keeps all its functions, as something else calls them.
+//! Function and global names stay as they are, since they are what the
+//! host and other code see.
+
+use super::{Arena, ExprId, Expression, Statement, StmtId, AST};
+use std::collections::{HashMap, HashSet};
+
+/// Words a renamed variable must not become: JS keywords and literals,
+/// and this language's `extern`.
+const RESERVED: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "extern",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+    "NaN",
+    "Infinity",
+    "undefined",
+];
+
+/// `ast` minified, ending with a newline.
+pub fn minify(ast: &AST) -> String {
+    let statements: Vec<StmtId> = ast
+        .statements
+        .iter()
+        .map(|&statement| unlocated(&ast.arena, statement))
+        .collect();
+    let live = live_functions(ast, &statements);
+
+    // Names a variable may not be renamed to anywhere: what the top level
+    // declares and everything called
+    let mut global_names: HashSet<&str> = RESERVED.iter().copied().collect();
+    for &statement in &statements {
+        match &ast[statement] {
+            Statement::FunctionDeclaration { name, .. }
+            | Statement::ExternDeclaration { name, .. }
+            | Statement::Let { name, .. } => {
+                global_names.insert(name);
+            }
+            _ => {}
+        }
+        visit_expressions(ast, statement, &mut |expression| {
+            if let Expression::FunctionCall { name, .. } = expression {
+                global_names.insert(name);
+            }
+        });
+    }
+
+    // Functions are renamed in a copy
+    let mut arena = ast.arena.clone();
+    let mut out = String::new();
+    for statement in statements {
+        match &ast[statement] {
+            Statement::FunctionDeclaration { name, .. }
+            | Statement::ExternDeclaration { name, .. }
+                if !live.contains(name.as_str()) => {}
+            Statement::FunctionDeclaration { .. } => {
+                let names = short_names(ast, statement, &global_names);
+                rename(&mut arena, statement, &names);
+                write_statement(&mut out, &arena, statement);
+            }
+            _ => write_statement(&mut out, &arena, statement),
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// The statement inside any `Located` around `id`.
+fn unlocated(arena: &Arena, id: StmtId) -> StmtId {
+    match arena[id] {
+        Statement::Located { statement, .. } => unlocated(arena, statement),
+        _ => id,
+    }
+}
+
+/// The functions and externs reachable by calls from `main` and the
+/// top-level statements, or all of them without a `main`.
+fn live_functions<'a>(ast: &'a AST, statements: &[StmtId]) -> HashSet<&'a str> {
+    let mut bodies = HashMap::new();
+    let mut roots = Vec::new();
+    for &statement in statements {
+        match &ast[statement] {
+            Statement::FunctionDeclaration { name, .. } => {
+                bodies.insert(name.as_str(), statement);
+            }
+            Statement::ExternDeclaration { .. } => {}
+            _ => visit_expressions(ast, statement, &mut |expression| {
+                if let Expression::FunctionCall { name, .. } = expression {
+                    roots.push(name.as_str());
+                }
+            }),
+        }
+    }
+    if bodies.contains_key("main") {
+        roots.push("main");
+    } else {
+        roots.extend(bodies.keys());
+    }
+
+    let mut live = HashSet::new();
+    while let Some(name) = roots.pop() {
+        if !live.insert(name) {
+            continue;
+        }
+        if let Some(&function) = bodies.get(name) {
+            visit_expressions(ast, function, &mut |expression| {
+                if let Expression::FunctionCall { name, .. } = expression {
+                    roots.push(name.as_str());
+                }
+            });
+        }
+    }
+    live
+}
+
+/// Call `f` on every expression in `statement`, outermost first.
+fn visit_expressions<'a>(ast: &'a AST, statement: StmtId, f: &mut impl FnMut(&'a Expression)) {
+    fn expression<'a>(ast: &'a AST, id: ExprId, f: &mut impl FnMut(&'a Expression)) {
+        let expr = &ast[id];
+        f(expr);
+        match expr {
+            Expression::FunctionCall { arguments, .. } => {
+                for &argument in arguments {
+                    expression(ast, argument, f);
+                }
+            }
+            Expression::Member { object, .. } => expression(ast, *object, f),
+            Expression::MethodCall {
+                object, arguments, ..
+            } => {
+                expression(ast, *object, f);
+                for &argument in arguments {
+                    expression(ast, argument, f);
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                expression(ast, *left, f);
+                expression(ast, *right, f);
+            }
+            Expression::UnaryOp { expr, .. } => expression(ast, *expr, f),
+            Expression::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                expression(ast, *condition, f);
+                expression(ast, *then_expr, f);
+                expression(ast, *else_expr, f);
+            }
+            _ => {}
+        }
+    }
+
+    match &ast[statement] {
+        Statement::Let { initializer, .. } => expression(ast, *initializer, f),
+        Statement::Return(Some(value)) | Statement::ExpressionStatement(value) => {
+            expression(ast, *value, f)
+        }
+        Statement::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            expression(ast, *condition, f);
+            for &statement in then_branch.iter().chain(else_branch.iter().flatten()) {
+                visit_expressions(ast, statement, f);
+            }
+        }
+        Statement::While { condition, body } => {
+            expression(ast, *condition, f);
+            for &statement in body {
+                visit_expressions(ast, statement, f);
+            }
+        }
+        Statement::For {
+            initializer,
+            condition,
+            update,
+            body,
+        } => {
+            if let Some(condition) = condition {
+                expression(ast, *condition, f);
+            }
+            for &statement in initializer.iter().chain(body).chain(update) {
+                visit_expressions(ast, statement, f);
+            }
+        }
+        Statement::FunctionDeclaration { body, .. } | Statement::Block(body) => {
+            for &statement in body {
+                visit_expressions(ast, statement, f);
+            }
+        }
+        Statement::Located { statement, .. } => visit_expressions(ast, *statement, f),
+        Statement::Return(None) | Statement::ExternDeclaration { .. } => {}
+    }
+}
+
+/// New names for the parameters and variables of `function`, shortest
+/// first in the order they are declared, avoiding `taken` and the globals
+/// the function reads.
+fn short_names(ast: &AST, function: StmtId, taken: &HashSet<&str>) -> HashMap<String, String> {
+    let Statement::FunctionDeclaration { params, body, .. } = &ast[function] else {
+        return HashMap::new();
+    };
+    let mut locals: Vec<&str> = params.iter().map(String::as_str).collect();
+    fn collect_lets<'a>(ast: &'a AST, statements: &[StmtId], locals: &mut Vec<&'a str>) {
+        for &statement in statements {
+            match ast.unlocated(statement) {
+                Statement::Let { name, .. } if !locals.contains(&name.as_str()) => {
+                    locals.push(name)
+                }
+                Statement::If {
+                    then_branch,
+                    else_branch,
+                    ..
+                } => {
+                    collect_lets(ast, then_branch, locals);
+                    collect_lets(ast, else_branch.as_deref().unwrap_or_default(), locals);
+                }
+                Statement::While { body, .. } | Statement::Block(body) => {
+                    collect_lets(ast, body, locals)
+                }
+                Statement::For {
+                    initializer,
+                    update,
+                    body,
+                    ..
+                } => {
+                    let clauses: Vec<StmtId> = initializer
+                        .iter()
+                        .chain(body)
+                        .chain(update)
+                        .copied()
+                        .collect();
+                    collect_lets(ast, &clauses, locals)
+                }
+                _ => {}
+            }
+        }
+    }
+    collect_lets(ast, body, &mut locals);
+
+    let mut taken = taken.clone();
+    visit_expressions(ast, function, &mut |expression| {
+        if let Expression::Identifier(name) = expression {
+            if !locals.contains(&name.as_str()) {
+                taken.insert(name);
+            }
+        }
+    });
+
+    let mut candidates = (0..)
+        .map(short_name)
+        .filter(|name| !taken.contains(name.as_str()));
+    locals
+        .into_iter()
+        .map(|local| (local.to_string(), candidates.next().unwrap()))
+        .collect()
+}
+
+/// The `n`th identifier by length: `a` to `Z`, then `aa`, `ab` and on.
+fn short_name(mut n: usize) -> String {
+    const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_";
+    const REST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_0123456789";
+    let mut name = String::new();
+    name.push(FIRST[n % FIRST.len()] as char);
+    n /= FIRST.len();
+    while n > 0 {
+        n -= 1;
+        name.push(REST[n % REST.len()] as char);
+        n /= REST.len();
+    }
+    name
+}
+
+/// Rename the variables in `statement` as `names` says.
+fn rename(arena: &mut Arena, statement: StmtId, names: &HashMap<String, String>) {
+    fn expression(arena: &mut Arena, id: ExprId, names: &HashMap<String, String>) {
+        let children = match &mut arena[id] {
+            Expression::Identifier(name) => {
+                if let Some(new) = names.get(name) {
+                    *name = new.clone();
+                }
+                return;
+            }
+            Expression::FunctionCall { arguments, .. } => arguments.clone(),
+            Expression::Member { object, .. } => vec![*object],
+            Expression::MethodCall {
+                object, arguments, ..
+            } => std::iter::once(*object)
+                .chain(arguments.iter().copied())
+                .collect(),
+            Expression::BinaryOp { left, right, .. } => vec![*left, *right],
+            Expression::UnaryOp { expr, .. } => vec![*expr],
+            Expression::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => vec![*condition, *then_expr, *else_expr],
+            _ => return,
+        };
+        for child in children {
+            expression(arena, child, names);
+        }
+    }
+    let rename_name = |name: &mut String| {
+        if let Some(new) = names.get(name) {
+            *name = new.clone();
+        }
+    };
+
+    // What the statement holds, renamed after it
+    let (condition, body) = match &mut arena[statement] {
+        Statement::Let { name, initializer } => {
+            rename_name(name);
+            (Some(*initializer), Vec::new())
+        }
+        Statement::Return(Some(value)) | Statement::ExpressionStatement(value) => {
+            (Some(*value), Vec::new())
+        }
+        Statement::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            let body = then_branch.iter().chain(else_branch.iter().flatten());
+            (Some(*condition), body.copied().collect())
+        }
+        Statement::While { condition, body } => (Some(*condition), body.clone()),
+        Statement::For {
+            initializer,
+            condition,
+            update,
+            body,
+        } => {
+            let body = initializer.iter().chain(body.iter()).chain(update.iter());
+            (*condition, body.copied().collect())
+        }
+        Statement::FunctionDeclaration { params, body, .. } => {
+            params.iter_mut().for_each(rename_name);
+            (None, body.clone())
+        }
+        Statement::Block(body) => (None, body.clone()),
+        Statement::Located { statement, .. } => (None, vec![*statement]),
+        Statement::Return(None) | Statement::ExternDeclaration { .. } => (None, Vec::new()),
+    };
+    if let Some(condition) = condition {
+        expression(arena, condition, names);
+    }
+    for statement in body {
+        rename(arena, statement, names);
+    }
+}
+
+fn write_block(out: &mut String, arena: &Arena, statements: &[StmtId]) {
+    out.push('{');
+    for &statement in statements {
+        write_statement(out, arena, statement);
+    }
+    out.push('}');
+}
+
+/// The initializer or update of a `for`, without the `;` ending it as a
+/// statement.
+fn write_clause(out: &mut String, arena: &Arena, clause: Option<StmtId>) {
+    if let Some(clause) = clause {
+        write_statement(out, arena, clause);
+        out.pop();
+    }
+}
+
+fn write_statement(out: &mut String, arena: &Arena, statement: StmtId) {
+    match &arena[statement] {
+        Statement::Let { name, initializer } => {
+            out.push_str(&format!("let {}={:#};", name, arena.source(*initializer)))
+        }
+        Statement::Return(None) => out.push_str("return;"),
+        Statement::Return(Some(value)) => {
+            out.push_str(&format!("return {:#};", arena.source(*value)))
+        }
+        Statement::ExpressionStatement(expression) => {
+            out.push_str(&format!("{:#};", arena.source(*expression)))
+        }
+        Statement::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            out.push_str(&format!("if({:#})", arena.source(*condition)));
+            write_block(out, arena, then_branch);
+            if let Some(else_branch) = else_branch {
+                out.push_str("else");
+                write_block(out, arena, else_branch);
+            }
+        }
+        Statement::While { condition, body } => {
+            out.push_str(&format!("while({:#})", arena.source(*condition)));
+            write_block(out, arena, body);
+        }
+        Statement::For {
+            initializer,
+            condition,
+            update,
+            body,
+        } => {
+            out.push_str("for(");
+            write_clause(out, arena, *initializer);
+            out.push(';');
+            if let Some(condition) = condition {
+                out.push_str(&format!("{:#}", arena.source(*condition)));
+            }
+            out.push(';');
+            write_clause(out, arena, *update);
+            out.push(')');
+            write_block(out, arena, body);
+        }
+        Statement::FunctionDeclaration { name, params, body } => {
+            out.push_str(&format!("function {}({})", name, params.join(",")));
+            write_block(out, arena, body);
+        }
+        Statement::ExternDeclaration {
+            name,
+            param_types,
+            variadic,
+            return_type,
+        } => {
+            let mut params = param_types.clone();
+            if *variadic {
+                params.push("...".to_string());
+            }
+            out.push_str(&format!("extern function {}({})", name, params.join(",")));
+            if let Some(return_type) = return_type {
+                out.push_str(&format!(":{}", return_type));
+            }
+            out.push(';');
+        }
+        Statement::Block(statements) => write_block(out, arena, statements),
+        Statement::Located { statement, .. } => write_statement(out, arena, *statement),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_minify() {
+        let source = "extern function puts(string): int;\n\
+                      extern function abs(int): int;\n\
+                      let b = 2;\n\
+                      function unused(x) { return x; }\n\
+                      function add(first, second) {\n\
+                          let total = first + second * b;\n\
+                          return total - -1;\n\
+                      }\n\
+                      function main() { puts(\"hi\"); return add(1, 2) > 3 ? 1 : 0; }";
+        let minified = minify(&parse(tokenize(source)));
+        assert_eq!(
+            minified,
+            "extern function puts(string):int;let b=2;\
+             function add(a,c){let d=a+c*b;return d- -1;}\
+             function main(){puts(\"hi\");return add(1,2)>3?1:0;}\n"
+        );
+        assert_eq!(minify(&parse(tokenize(&minified))), minified);
+    }
+
+    #[test]
+    fn test_for_clauses_are_renamed() {
+        let source = "function count(limit) {\n\
+                          for (let index = 0; index < limit; let index = index + 1) { print(index); }\n\
+                          for (;;) { return limit; }\n\
+                      }";
+        assert_eq!(
+            minify(&parse(tokenize(source))),
+            "function count(a){for(let b=0;b<a;let b=b+1){print(b);}for(;;){return a;}}\n"
+        );
+    }
+
+    #[test]
+    fn test_short_names() {
+        assert_eq!(short_name(0), "a");
+        assert_eq!(short_name(52), "_");
+        assert_eq!(short_name(53), "aa");
+        assert_eq!(short_name(54), "ba");
+    }
+}
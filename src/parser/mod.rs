@@ -1,5 +1,14 @@
+use crate::diagnostics::{Diagnostic, Span};
 use crate::lexer::{Token, TokenType};
 
+mod arena;
+mod minify;
+mod printer;
+pub use arena::{Arena, ExprId, StmtId};
+pub use minify::minify;
+pub use printer::format_source;
+
+/// An expression. Its subexpressions are in the arena of its `AST`.
 #[derive(Debug, Clone)]
 pub enum Expression {
     // Literals
@@ -12,80 +21,173 @@ pub enum Expression {
     Identifier(String),
     FunctionCall {
         name: String,
-        arguments: Vec<Expression>,
+        arguments: Vec<ExprId>,
+    },
+    // `object.property`
+    Member {
+        object: ExprId,
+        property: String,
+    },
+    // `object.method(arguments)`
+    MethodCall {
+        object: ExprId,
+        method: String,
+        arguments: Vec<ExprId>,
     },
 
     // Operators
     BinaryOp {
         op: String,
-        left: Box<Expression>,
-        right: Box<Expression>,
+        left: ExprId,
+        right: ExprId,
     },
     UnaryOp {
         op: String,
-        expr: Box<Expression>,
+        expr: ExprId,
     },
 
     // Control Flow
     Conditional {
-        condition: Box<Expression>,
-        then_expr: Box<Expression>,
-        else_expr: Box<Expression>,
+        condition: ExprId,
+        then_expr: ExprId,
+        else_expr: ExprId,
     },
 }
 
+/// A statement. What it contains is in the arena of its `AST`.
 #[derive(Debug, Clone)]
 pub enum Statement {
     // Variable Declaration
     Let {
         name: String,
-        initializer: Expression,
+        initializer: ExprId,
     },
 
     // Control Flow
     If {
-        condition: Expression,
-        then_branch: Vec<Statement>,
-        else_branch: Option<Vec<Statement>>,
+        condition: ExprId,
+        then_branch: Vec<StmtId>,
+        else_branch: Option<Vec<StmtId>>,
     },
     While {
-        condition: Expression,
-        body: Vec<Statement>,
+        condition: ExprId,
+        body: Vec<StmtId>,
+    },
+    // `for (initializer; condition; update) { body }`, where the
+    // initializer and update are a `let` or an expression, without a `;`
+    For {
+        initializer: Option<StmtId>,
+        condition: Option<ExprId>,
+        update: Option<StmtId>,
+        body: Vec<StmtId>,
     },
 
     // Functions
     FunctionDeclaration {
         name: String,
         params: Vec<String>,
-        body: Vec<Statement>,
+        body: Vec<StmtId>,
+    },
+    Return(Option<ExprId>),
+    // `extern function name(type, ..., ...): type;`, a C function
+    ExternDeclaration {
+        name: String,
+        param_types: Vec<String>,
+        variadic: bool,
+        return_type: Option<String>,
     },
-    Return(Option<Expression>),
 
     // Other
-    Block(Vec<Statement>),
-    ExpressionStatement(Expression),
+    Block(Vec<StmtId>),
+    ExpressionStatement(ExprId),
+
+    // Source position of the statement inside: the lines of its first and
+    // last tokens, and the column of its first
+    Located {
+        line: usize,
+        column: usize,
+        end_line: usize,
+        statement: StmtId,
+    },
 }
 
-#[derive(Debug)]
+/// A program: its top-level statements, and the arena they and all they
+/// contain are in.
+#[derive(Clone, Default)]
 pub struct AST {
-    pub statements: Vec<Statement>,
+    pub statements: Vec<StmtId>,
+    pub arena: Arena,
 }
 
-pub struct Parser {
-    tokens: Vec<Token>,
+/// The top-level statements, with what they contain in place of its ids.
+impl std::fmt::Debug for AST {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.arena.nodes(&self.statements).fmt(f)
+    }
+}
+
+impl std::ops::Index<ExprId> for AST {
+    type Output = Expression;
+
+    fn index(&self, id: ExprId) -> &Expression {
+        &self.arena[id]
+    }
+}
+
+impl std::ops::Index<StmtId> for AST {
+    type Output = Statement;
+
+    fn index(&self, id: StmtId) -> &Statement {
+        &self.arena[id]
+    }
+}
+
+impl AST {
+    /// Statement `id`, out of the `Located` around it.
+    pub fn unlocated(&self, id: StmtId) -> &Statement {
+        match &self[id] {
+            Statement::Located { statement, .. } => self.unlocated(*statement),
+            statement => statement,
+        }
+    }
+
+    /// Names of the functions and externs declared at the top level.
+    pub fn function_names(&self) -> Vec<&str> {
+        self.statements
+            .iter()
+            .filter_map(|&statement| match self.unlocated(statement) {
+                Statement::FunctionDeclaration { name, .. }
+                | Statement::ExternDeclaration { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+pub struct Parser<'src> {
+    tokens: Vec<Token<'src>>,
     current: usize,
+    arena: Arena,
 }
 
-impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, current: 0 }
+impl<'src> Parser<'src> {
+    pub fn new(tokens: Vec<Token<'src>>) -> Self {
+        Parser {
+            tokens,
+            current: 0,
+            arena: Arena::new(),
+        }
+    }
+
+    fn expression(&mut self, expression: Expression) -> ExprId {
+        self.arena.expression(expression)
     }
 
-    fn peek(&self) -> Option<&Token> {
+    fn peek(&self) -> Option<&Token<'src>> {
         self.tokens.get(self.current)
     }
 
-    fn advance(&mut self) -> Option<Token> {
+    fn advance(&mut self) -> Option<Token<'src>> {
         if self.current < self.tokens.len() {
             self.current += 1;
             Some(self.tokens[self.current - 1].clone())
@@ -94,11 +196,46 @@ impl Parser {
         }
     }
 
+    /// The next token, which the grammar requires to be there.
+    fn next_token(&mut self) -> Token<'src> {
+        match self.advance() {
+            Some(token) => token,
+            None => self.end_of_input(),
+        }
+    }
+
+    fn peek_token(&self) -> &Token<'src> {
+        match self.peek() {
+            Some(token) => token,
+            None => self.end_of_input(),
+        }
+    }
+
+    fn end_of_input(&self) -> ! {
+        let mut diagnostic = Diagnostic::error("Unexpected end of input");
+        if let Some(last) = self.tokens.last() {
+            let span = last.span();
+            diagnostic = diagnostic.with_label(
+                Span::new(span.line, span.column + span.len, 1),
+                "input ends here",
+            );
+        }
+        diagnostic.raise()
+    }
+
+    /// Raise a syntax error about `token`.
+    fn error(&self, token: &Token<'_>, message: impl Into<String>) -> ! {
+        Diagnostic::error(message)
+            .with_label(token.span(), format!("found `{}`", token.token_type))
+            .raise()
+    }
+
     fn parse_function(&mut self) -> Statement {
         self.advance(); // consume 'function'
-        let name = match self.advance().unwrap().token_type {
-            TokenType::Identifier(name) => name,
-            _ => panic!("Expected function name"),
+        let token = self.next_token();
+        let name = match token.token_type {
+            TokenType::Identifier(name) => name.to_string(),
+            _ => self.error(&token, "Expected function name"),
         };
 
         let mut params = Vec::new();
@@ -111,7 +248,7 @@ impl Parser {
                     break;
                 }
                 TokenType::Identifier(param) => {
-                    params.push(param.clone());
+                    params.push(param.to_string());
                     self.advance();
                     if let Some(Token {
                         token_type: TokenType::Comma,
@@ -121,7 +258,7 @@ impl Parser {
                         self.advance();
                     }
                 }
-                _ => panic!("Invalid parameter"),
+                _ => self.error(token, "Invalid parameter"),
             }
         }
 
@@ -134,44 +271,120 @@ impl Parser {
                     self.advance();
                     break;
                 }
-                _ => body.push(self.parse_statement()),
+                _ => body.push(self.parse_located_statement()),
             }
         }
 
         Statement::FunctionDeclaration { name, params, body }
     }
 
+    fn parse_extern_declaration(&mut self) -> Statement {
+        self.advance(); // consume 'extern'
+        self.expect_token(TokenType::Function);
+        let token = self.next_token();
+        let name = match token.token_type {
+            TokenType::Identifier(name) => name.to_string(),
+            _ => self.error(&token, "Expected extern function name"),
+        };
+
+        let type_name = |parser: &Self, token: Token<'src>| match token.token_type {
+            TokenType::Identifier(name) => name.to_string(),
+            _ => parser.error(&token, "Expected a C type"),
+        };
+        let mut param_types = Vec::new();
+        let mut variadic = false;
+        self.expect_token(TokenType::LParen);
+        loop {
+            let token = self.next_token();
+            match token.token_type {
+                TokenType::RParen => break,
+                TokenType::Ellipsis => variadic = true,
+                _ if variadic => self.error(&token, "'...' must be the last parameter"),
+                _ => param_types.push(type_name(self, token)),
+            }
+            let token = self.peek_token();
+            match token.token_type {
+                TokenType::Comma => {
+                    self.advance();
+                }
+                TokenType::RParen => {}
+                _ => self.error(token, "Expected ',' or ')' in extern declaration"),
+            }
+        }
+
+        let return_type = match self.peek_token().token_type {
+            TokenType::Colon => {
+                self.advance();
+                let token = self.next_token();
+                Some(type_name(self, token))
+            }
+            _ => None,
+        };
+        self.expect_token(TokenType::Semicolon);
+
+        Statement::ExternDeclaration {
+            name,
+            param_types,
+            variadic,
+            return_type,
+        }
+    }
+
     fn parse_statement(&mut self) -> Statement {
-        match self.peek().unwrap().token_type {
+        match self.peek_token().token_type {
             TokenType::Function => self.parse_function(),
+            TokenType::Extern => self.parse_extern_declaration(),
             TokenType::Let => self.parse_let_statement(),
             TokenType::Return => self.parse_return_statement(),
             TokenType::If => self.parse_if_statement(),
             TokenType::While => self.parse_while_statement(),
+            TokenType::For => self.parse_for_statement(),
             _ => self.parse_expression_statement(),
         }
     }
 
+    /// Parse a statement, keeping the lines it spans for debug info and
+    /// the formatter.
+    fn parse_located_statement(&mut self) -> StmtId {
+        let (line, column) = (self.peek_token().line, self.peek_token().column);
+        let statement = self.parse_statement();
+        let statement = self.arena.statement(statement);
+        self.arena.statement(Statement::Located {
+            line,
+            column,
+            end_line: self.tokens[self.current - 1].line,
+            statement,
+        })
+    }
+
     fn parse_let_statement(&mut self) -> Statement {
+        let statement = self.parse_let();
+
+        let token = self.next_token();
+        if token.token_type != TokenType::Semicolon {
+            self.error(&token, "Expected ';' after let statement");
+        }
+
+        statement
+    }
+
+    /// `let name = initializer`, without the `;` ending a let statement.
+    fn parse_let(&mut self) -> Statement {
         self.advance(); // consume 'let'
 
-        let name = match self.advance().unwrap().token_type {
-            TokenType::Identifier(name) => name,
-            _ => panic!("Expected identifier after 'let'"),
+        let token = self.next_token();
+        let name = match token.token_type {
+            TokenType::Identifier(name) => name.to_string(),
+            _ => self.error(&token, "Expected identifier after 'let'"),
         };
 
-        match self.advance().unwrap().token_type {
-            TokenType::Equal => {}
-            _ => panic!("Expected '=' after identifier in let statement"),
+        let token = self.next_token();
+        if token.token_type != TokenType::Equal {
+            self.error(&token, "Expected '=' after identifier in let statement");
         }
 
         let initializer = self.parse_expression();
 
-        match self.advance().unwrap().token_type {
-            TokenType::Semicolon => {}
-            _ => panic!("Expected ';' after let statement"),
-        }
-
         Statement::Let { name, initializer }
     }
 
@@ -188,9 +401,9 @@ impl Parser {
             None
         };
 
-        match self.advance().unwrap().token_type {
-            TokenType::Semicolon => {}
-            _ => panic!("Expected ';' after return statement"),
+        let token = self.next_token();
+        if token.token_type != TokenType::Semicolon {
+            self.error(&token, "Expected ';' after return statement");
         }
 
         Statement::Return(expr)
@@ -199,19 +412,19 @@ impl Parser {
     fn parse_expression_statement(&mut self) -> Statement {
         let expr = self.parse_expression();
 
-        match self.advance().unwrap().token_type {
-            TokenType::Semicolon => {}
-            _ => panic!("Expected ';' after expression statement"),
+        let token = self.next_token();
+        if token.token_type != TokenType::Semicolon {
+            self.error(&token, "Expected ';' after expression statement");
         }
 
         Statement::ExpressionStatement(expr)
     }
 
-    fn parse_expression(&mut self) -> Expression {
+    fn parse_expression(&mut self) -> ExprId {
         self.parse_conditional()
     }
 
-    fn parse_conditional(&mut self) -> Expression {
+    fn parse_conditional(&mut self) -> ExprId {
         let mut expr = self.parse_logical_or();
 
         if let Some(token) = self.peek() {
@@ -220,28 +433,28 @@ impl Parser {
                 let then_expr = self.parse_expression();
                 self.expect_token(TokenType::Colon);
                 let else_expr = self.parse_conditional();
-                expr = Expression::Conditional {
-                    condition: Box::new(expr),
-                    then_expr: Box::new(then_expr),
-                    else_expr: Box::new(else_expr),
-                };
+                expr = self.expression(Expression::Conditional {
+                    condition: expr,
+                    then_expr,
+                    else_expr,
+                });
             }
         }
         expr
     }
 
-    fn parse_logical_or(&mut self) -> Expression {
+    fn parse_logical_or(&mut self) -> ExprId {
         let mut expr = self.parse_logical_and();
 
         while let Some(token) = self.peek() {
             if matches!(token.token_type, TokenType::Or) {
                 self.advance();
                 let right = self.parse_logical_and();
-                expr = Expression::BinaryOp {
+                expr = self.expression(Expression::BinaryOp {
                     op: "||".to_string(),
-                    left: Box::new(expr),
-                    right: Box::new(right),
-                };
+                    left: expr,
+                    right,
+                });
             } else {
                 break;
             }
@@ -249,18 +462,18 @@ impl Parser {
         expr
     }
 
-    fn parse_logical_and(&mut self) -> Expression {
+    fn parse_logical_and(&mut self) -> ExprId {
         let mut expr = self.parse_equality();
 
         while let Some(token) = self.peek() {
             if matches!(token.token_type, TokenType::And) {
                 self.advance();
                 let right = self.parse_equality();
-                expr = Expression::BinaryOp {
+                expr = self.expression(Expression::BinaryOp {
                     op: "&&".to_string(),
-                    left: Box::new(expr),
-                    right: Box::new(right),
-                };
+                    left: expr,
+                    right,
+                });
             } else {
                 break;
             }
@@ -268,27 +481,29 @@ impl Parser {
         expr
     }
 
-    fn parse_equality(&mut self) -> Expression {
+    fn parse_equality(&mut self) -> ExprId {
         let mut expr = self.parse_comparison();
 
         while let Some(token) = self.peek() {
             let op = match &token.token_type {
                 TokenType::EqualEqual => "==",
                 TokenType::NotEqual => "!=",
+                TokenType::StrictEqual => "===",
+                TokenType::StrictNotEqual => "!==",
                 _ => break,
             };
             self.advance();
             let right = self.parse_comparison();
-            expr = Expression::BinaryOp {
+            expr = self.expression(Expression::BinaryOp {
                 op: op.to_string(),
-                left: Box::new(expr),
-                right: Box::new(right),
-            };
+                left: expr,
+                right,
+            });
         }
         expr
     }
 
-    fn parse_comparison(&mut self) -> Expression {
+    fn parse_comparison(&mut self) -> ExprId {
         let mut expr = self.parse_term();
 
         while let Some(token) = self.peek() {
@@ -301,16 +516,16 @@ impl Parser {
             };
             self.advance();
             let right = self.parse_term();
-            expr = Expression::BinaryOp {
+            expr = self.expression(Expression::BinaryOp {
                 op: op.to_string(),
-                left: Box::new(expr),
-                right: Box::new(right),
-            };
+                left: expr,
+                right,
+            });
         }
         expr
     }
 
-    fn parse_term(&mut self) -> Expression {
+    fn parse_term(&mut self) -> ExprId {
         let mut expr = self.parse_factor();
 
         while let Some(token) = self.peek() {
@@ -321,16 +536,16 @@ impl Parser {
             };
             self.advance();
             let right = self.parse_factor();
-            expr = Expression::BinaryOp {
+            expr = self.expression(Expression::BinaryOp {
                 op: op.to_string(),
-                left: Box::new(expr),
-                right: Box::new(right),
-            };
+                left: expr,
+                right,
+            });
         }
         expr
     }
 
-    fn parse_factor(&mut self) -> Expression {
+    fn parse_factor(&mut self) -> ExprId {
         let mut expr = self.parse_unary();
 
         while let Some(token) = self.peek() {
@@ -342,16 +557,16 @@ impl Parser {
             };
             self.advance();
             let right = self.parse_unary();
-            expr = Expression::BinaryOp {
+            expr = self.expression(Expression::BinaryOp {
                 op: op.to_string(),
-                left: Box::new(expr),
-                right: Box::new(right),
-            };
+                left: expr,
+                right,
+            });
         }
         expr
     }
 
-    fn parse_unary(&mut self) -> Expression {
+    fn parse_unary(&mut self) -> ExprId {
         if let Some(token) = self.peek() {
             match &token.token_type {
                 TokenType::Not | TokenType::Minus => {
@@ -363,73 +578,114 @@ impl Parser {
                         _ => unreachable!(),
                     };
                     let expr = self.parse_unary();
-                    return Expression::UnaryOp {
+                    return self.expression(Expression::UnaryOp {
                         op: op.to_string(),
-                        expr: Box::new(expr),
-                    };
+                        expr,
+                    });
                 }
                 _ => {}
             }
         }
-        self.parse_primary()
+        self.parse_postfix()
     }
 
-    fn parse_primary(&mut self) -> Expression {
-        let token = self.advance().expect("Expected expression");
-        match token.token_type {
+    /// A primary expression followed by the members read from it, as in
+    /// `a.b.c` or `counter.add(2)`.
+    fn parse_postfix(&mut self) -> ExprId {
+        let mut expr = self.parse_primary();
+        while self
+            .peek()
+            .is_some_and(|token| token.token_type == TokenType::Dot)
+        {
+            self.advance();
+            let token = self.next_token();
+            let TokenType::Identifier(name) = token.token_type else {
+                self.error(&token, "Expected a property name after `.`")
+            };
+            let name = name.to_string();
+            let call = self
+                .peek()
+                .is_some_and(|token| token.token_type == TokenType::LParen);
+            expr = if call {
+                let arguments = self.parse_arguments();
+                self.expression(Expression::MethodCall {
+                    object: expr,
+                    method: name,
+                    arguments,
+                })
+            } else {
+                self.expression(Expression::Member {
+                    object: expr,
+                    property: name,
+                })
+            };
+        }
+        expr
+    }
+
+    fn parse_primary(&mut self) -> ExprId {
+        let token = self.next_token();
+        let expression = match token.token_type {
             TokenType::Number(n) => Expression::Number(n),
-            TokenType::StringLiteral(s) => Expression::String(s),
+            TokenType::StringLiteral(s) => Expression::String(s.into_owned()),
             TokenType::True => Expression::Boolean(true),
             TokenType::False => Expression::Boolean(false),
             TokenType::Null => Expression::Null,
             TokenType::Identifier(name) => {
                 if let Some(token) = self.peek() {
                     if matches!(token.token_type, TokenType::LParen) {
-                        return self.parse_function_call(name);
+                        return self.parse_function_call(name.to_string());
                     }
                 }
-                Expression::Identifier(name)
+                Expression::Identifier(name.to_string())
             }
             TokenType::LParen => {
                 let expr = self.parse_expression();
                 self.expect_token(TokenType::RParen);
-                expr
+                return expr;
             }
-            _ => panic!("Unexpected token in expression: {:?}", token),
-        }
+            _ => self.error(&token, "Unexpected token in expression"),
+        };
+        self.expression(expression)
+    }
+
+    fn parse_function_call(&mut self, name: String) -> ExprId {
+        let arguments = self.parse_arguments();
+        self.expression(Expression::FunctionCall { name, arguments })
     }
 
-    fn parse_function_call(&mut self, name: String) -> Expression {
+    /// The arguments of a call, from its `(` to its `)`.
+    fn parse_arguments(&mut self) -> Vec<ExprId> {
         self.advance(); // consume '('
 
         let mut arguments = Vec::new();
 
         loop {
-            match self.peek().unwrap().token_type {
+            match self.peek_token().token_type {
                 TokenType::RParen => {
                     self.advance();
                     break;
                 }
                 _ => {
                     arguments.push(self.parse_expression());
-                    match self.peek().unwrap().token_type {
+                    let token = self.peek_token();
+                    match token.token_type {
                         TokenType::Comma => {
                             self.advance();
                         }
                         TokenType::RParen => {}
-                        _ => panic!("Expected ',' or ')' in function call"),
+                        _ => self.error(token, "Expected ',' or ')' in function call"),
                     }
                 }
             }
         }
-
-        Expression::FunctionCall { name, arguments }
+        arguments
     }
 
-    fn expect_token(&mut self, expected: TokenType) -> Token {
-        let token = self.advance().unwrap();
+    fn expect_token(&mut self, expected: TokenType<'_>) -> Token<'src> {
+        let token = self.next_token();
         if token.token_type != expected {
-            panic!("Expected {:?}, got {:?}", expected, token.token_type);
+            self.error(&token, format!("Expected `{}`", expected));
         }
         token
     }
@@ -471,7 +727,44 @@ impl Parser {
         Statement::While { condition, body }
     }
 
-    fn parse_block(&mut self) -> Vec<Statement> {
+    fn parse_for_statement(&mut self) -> Statement {
+        self.advance(); // consume 'for'
+        self.expect_token(TokenType::LParen);
+        let initializer = self.parse_for_clause(TokenType::Semicolon);
+        self.expect_token(TokenType::Semicolon);
+        let condition = if self.peek_token().token_type == TokenType::Semicolon {
+            None
+        } else {
+            Some(self.parse_expression())
+        };
+        self.expect_token(TokenType::Semicolon);
+        let update = self.parse_for_clause(TokenType::RParen);
+        self.expect_token(TokenType::RParen);
+
+        let body = self.parse_block();
+
+        Statement::For {
+            initializer,
+            condition,
+            update,
+            body,
+        }
+    }
+
+    /// The initializer or update of a `for`, unless `end` comes first.
+    fn parse_for_clause(&mut self, end: TokenType<'_>) -> Option<StmtId> {
+        let token_type = &self.peek_token().token_type;
+        if *token_type == end {
+            return None;
+        }
+        let statement = match token_type {
+            TokenType::Let => self.parse_let(),
+            _ => Statement::ExpressionStatement(self.parse_expression()),
+        };
+        Some(self.arena.statement(statement))
+    }
+
+    fn parse_block(&mut self) -> Vec<StmtId> {
         self.expect_token(TokenType::LBrace);
 
         let mut statements = Vec::new();
@@ -479,7 +772,7 @@ impl Parser {
             if matches!(token.token_type, TokenType::RBrace) {
                 break;
             }
-            statements.push(self.parse_statement());
+            statements.push(self.parse_located_statement());
         }
 
         self.expect_token(TokenType::RBrace);
@@ -487,15 +780,18 @@ impl Parser {
     }
 }
 
-pub fn parse(tokens: Vec<Token>) -> AST {
+pub fn parse(tokens: Vec<Token<'_>>) -> AST {
     let mut parser = Parser::new(tokens);
     let mut statements = Vec::new();
 
     while parser.peek().is_some() {
-        statements.push(parser.parse_statement());
+        statements.push(parser.parse_located_statement());
     }
 
-    AST { statements }
+    AST {
+        statements,
+        arena: parser.arena,
+    }
 }
 
 #[cfg(test)]
@@ -509,12 +805,12 @@ mod tests {
         let tokens = tokenize(input);
         let mut parser = Parser::new(tokens);
 
-        let statements = vec![parser.parse_statement()];
+        let statements = [parser.parse_statement()];
 
         match &statements[0] {
             Statement::Let { name, initializer } => {
                 assert_eq!(name, "x");
-                match initializer {
+                match &parser.arena[*initializer] {
                     Expression::Number(val) => assert_eq!(*val, 5.0),
                     _ => panic!("Expected number expression"),
                 }
@@ -529,10 +825,10 @@ mod tests {
         let tokens = tokenize(input);
         let mut parser = Parser::new(tokens);
 
-        let statements = vec![parser.parse_statement()];
+        let statements = [parser.parse_statement()];
 
         match &statements[0] {
-            Statement::Return(Some(expr)) => match expr {
+            Statement::Return(Some(expr)) => match &parser.arena[*expr] {
                 Expression::Number(val) => assert_eq!(*val, 10.0),
                 _ => panic!("Expected number expression"),
             },
@@ -546,23 +842,23 @@ mod tests {
         let tokens = tokenize(input);
         let mut parser = Parser::new(tokens);
 
-        let statements = vec![parser.parse_statement()];
+        let statements = [parser.parse_statement()];
 
         match &statements[0] {
             Statement::If {
                 condition,
-                then_branch,
                 else_branch,
+                ..
             } => {
                 assert!(else_branch.is_none());
-                match condition {
+                match &parser.arena[*condition] {
                     Expression::BinaryOp { op, left, right } => {
                         assert_eq!(op, ">");
-                        match &**left {
+                        match &parser.arena[*left] {
                             Expression::Identifier(name) => assert_eq!(name, "x"),
                             _ => panic!("Expected identifier"),
                         }
-                        match &**right {
+                        match &parser.arena[*right] {
                             Expression::Number(val) => assert_eq!(*val, 5.0),
                             _ => panic!("Expected number"),
                         }
@@ -573,4 +869,113 @@ mod tests {
             _ => panic!("Expected if statement"),
         }
     }
+
+    #[test]
+    fn test_for_statement() {
+        let ast = parse(tokenize(
+            "for (let i = 0; i < 3; let i = i + 1) { f(i); }\nfor (g(); ; ) {}",
+        ));
+
+        match ast.unlocated(ast.statements[0]) {
+            Statement::For {
+                initializer: Some(initializer),
+                condition: Some(condition),
+                update: Some(update),
+                body,
+            } => {
+                assert!(matches!(&ast[*initializer], Statement::Let { name, .. } if name == "i"));
+                assert!(matches!(&ast[*condition], Expression::BinaryOp { op, .. } if op == "<"));
+                assert!(matches!(&ast[*update], Statement::Let { name, .. } if name == "i"));
+                assert_eq!(body.len(), 1);
+            }
+            _ => panic!("Expected for statement with all its clauses"),
+        }
+        match ast.unlocated(ast.statements[1]) {
+            Statement::For {
+                initializer: Some(initializer),
+                condition: None,
+                update: None,
+                body,
+            } => {
+                assert!(matches!(
+                    &ast[*initializer],
+                    Statement::ExpressionStatement(_)
+                ));
+                assert!(body.is_empty());
+            }
+            _ => panic!("Expected for statement without a condition or update"),
+        }
+    }
+
+    #[test]
+    fn test_member_access() {
+        let ast = parse(tokenize("counter.add(f().x, -a.b);"));
+        let Statement::ExpressionStatement(call) = ast.unlocated(ast.statements[0]) else {
+            panic!("Expected expression statement");
+        };
+        let Expression::MethodCall {
+            object,
+            method,
+            arguments,
+        } = &ast[*call]
+        else {
+            panic!("Expected method call");
+        };
+        assert!(matches!(&ast[*object], Expression::Identifier(name) if name == "counter"));
+        assert_eq!(method, "add");
+        let Expression::Member { object, property } = &ast[arguments[0]] else {
+            panic!("Expected member");
+        };
+        assert!(matches!(&ast[*object], Expression::FunctionCall { name, .. } if name == "f"));
+        assert_eq!(property, "x");
+        // Members bind tighter than unary operators
+        let Expression::UnaryOp { expr, .. } = &ast[arguments[1]] else {
+            panic!("Expected unary operation");
+        };
+        assert!(matches!(&ast[*expr], Expression::Member { property, .. } if property == "b"));
+    }
+
+    #[test]
+    fn test_nodes_share_an_arena() {
+        let ast = parse(tokenize("let x = 1 + 2;\nf(x);"));
+        // Five expressions, and two statements with a `Located` each
+        assert_eq!(ast.arena.len(), 9);
+        let Statement::Let { initializer, .. } = ast.unlocated(ast.statements[0]) else {
+            panic!("Expected let statement");
+        };
+        let Expression::BinaryOp { left, right, .. } = &ast[*initializer] else {
+            panic!("Expected binary operation");
+        };
+        assert!(matches!(ast[*left], Expression::Number(n) if n == 1.0));
+        assert!(matches!(ast[*right], Expression::Number(n) if n == 2.0));
+    }
+
+    #[test]
+    fn test_extern_declaration() {
+        let mut parser = Parser::new(tokenize("extern function printf(string, ...): int;"));
+
+        match parser.parse_statement() {
+            Statement::ExternDeclaration {
+                name,
+                param_types,
+                variadic,
+                return_type,
+            } => {
+                assert_eq!(name, "printf");
+                assert_eq!(param_types, ["string"]);
+                assert!(variadic);
+                assert_eq!(return_type.as_deref(), Some("int"));
+            }
+            _ => panic!("Expected extern declaration"),
+        }
+    }
+
+    #[test]
+    fn test_syntax_error_points_at_token() {
+        let payload = std::panic::catch_unwind(|| parse(tokenize("let x = 1 +\n  ;"))).unwrap_err();
+        let diagnostic = Diagnostic::from_panic(payload);
+        assert_eq!(diagnostic.message, "Unexpected token in expression");
+        assert_eq!(diagnostic.labels[0].span, Span::new(2, 3, 1));
+        assert_eq!(diagnostic.labels[0].message, "found `;`");
+    }
 }
@@ -0,0 +1,496 @@
+//! Printing the AST back as source, for `fmt`: four spaces of indentation,
+//! one statement per line, spaces around binary operators, and parentheses
+//! only where precedence needs them. Comments and single blank lines are
+//! kept where they were, next to the statements around them.
+
+use super::{parse, Arena, ExprId, Expression, Statement, StmtId};
+use crate::lexer::{tokenize_with_comments, Comment, Token, TokenType};
+use std::fmt;
+
+const INDENT: &str = "    ";
+
+/// How tightly an expression binds, from the conditional operator up to
+/// literals and calls.
+fn precedence(expr: &Expression) -> u8 {
+    match expr {
+        Expression::Conditional { .. } => 1,
+        Expression::BinaryOp { op, .. } => match op.as_str() {
+            "||" => 2,
+            "&&" => 3,
+            "==" | "!=" | "===" | "!==" => 4,
+            "<" | ">" | "<=" | ">=" => 5,
+            "+" | "-" => 6,
+            _ => 7,
+        },
+        Expression::UnaryOp { .. } => 8,
+        _ => 9,
+    }
+}
+
+/// The object of a member as source, in parentheses unless it binds
+/// tighter than any operator: a number too, whose `.` would read as its
+/// decimal point.
+fn member_object(arena: &Arena, id: ExprId, compact: bool) -> String {
+    let source = arena.source(id);
+    let object = if compact {
+        format!("{:#}", source)
+    } else {
+        source.to_string()
+    };
+    match arena[id] {
+        Expression::Number(_) => format!("({})", object),
+        ref expr if precedence(expr) < 9 => format!("({})", object),
+        _ => object,
+    }
+}
+
+/// An expression as source, from `Arena::source`; `{:#}` leaves out the
+/// spaces.
+pub struct Source<'a> {
+    arena: &'a Arena,
+    id: ExprId,
+}
+
+impl Arena {
+    pub fn source(&self, id: ExprId) -> Source<'_> {
+        Source { arena: self, id }
+    }
+}
+
+impl Source<'_> {
+    /// Subexpression `id`, in parentheses when it binds looser than `min`;
+    /// without spaces if `compact`.
+    fn operand(&self, id: ExprId, min: u8, compact: bool) -> String {
+        let expr = self.arena.source(id);
+        match (precedence(&self.arena[id]) < min, compact) {
+            (true, false) => format!("({})", expr),
+            (true, true) => format!("({:#})", expr),
+            (false, false) => expr.to_string(),
+            (false, true) => format!("{:#}", expr),
+        }
+    }
+}
+
+impl fmt::Display for Source<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let compact = f.alternate();
+        let space = if compact { "" } else { " " };
+        let operand = |id, min| self.operand(id, min, compact);
+        let expression = &self.arena[self.id];
+        match expression {
+            Expression::Number(n) => write!(f, "{}", n),
+            Expression::String(s) => {
+                f.write_str("\"")?;
+                for c in s.chars() {
+                    match c {
+                        '\n' => f.write_str("\\n")?,
+                        '\t' => f.write_str("\\t")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '"' => f.write_str("\\\"")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                f.write_str("\"")
+            }
+            Expression::Boolean(b) => write!(f, "{}", b),
+            Expression::Null => f.write_str("null"),
+            Expression::Identifier(name) => f.write_str(name),
+            Expression::FunctionCall { name, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|&a| operand(a, 0)).collect();
+                write!(f, "{}({})", name, arguments.join(&format!(",{}", space)))
+            }
+            Expression::Member { object, property } => {
+                write!(
+                    f,
+                    "{}.{}",
+                    member_object(self.arena, *object, compact),
+                    property
+                )
+            }
+            Expression::MethodCall {
+                object,
+                method,
+                arguments,
+            } => {
+                let arguments: Vec<String> = arguments.iter().map(|&a| operand(a, 0)).collect();
+                write!(
+                    f,
+                    "{}.{}({})",
+                    member_object(self.arena, *object, compact),
+                    method,
+                    arguments.join(&format!(",{}", space))
+                )
+            }
+            Expression::BinaryOp { op, left, right } => {
+                // Operators group to the left, so a right operand of the
+                // same precedence needs parentheses
+                let p = precedence(expression);
+                let right = operand(*right, p + 1);
+                // `a- -b`, not the decrement `a--b`
+                let gap = if op == "-" && right.starts_with('-') {
+                    " "
+                } else {
+                    space
+                };
+                let left = operand(*left, p);
+                write!(f, "{}{}{}{}{}", left, space, op, gap, right)
+            }
+            Expression::UnaryOp { op, expr } => {
+                let expr = operand(*expr, 8);
+                // `- -x`, not the decrement `--x`
+                let space = if op == "-" && expr.starts_with('-') {
+                    " "
+                } else {
+                    ""
+                };
+                write!(f, "{}{}{}", op, space, expr)
+            }
+            Expression::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+            } => write!(
+                f,
+                "{}{space}?{space}{}{space}:{space}{}",
+                operand(*condition, 2),
+                operand(*then_expr, 0),
+                operand(*else_expr, 0),
+                space = space
+            ),
+        }
+    }
+}
+
+/// Reprint `source` in the standard layout. Raises the same diagnostics as
+/// parsing it would.
+pub fn format_source(source: &str) -> String {
+    let (tokens, comments) = tokenize_with_comments(source);
+    let ast = parse(tokens.clone());
+
+    let mut printer = Printer {
+        arena: &ast.arena,
+        out: String::new(),
+        depth: 0,
+        first: true,
+        lines: source.lines().collect(),
+        comments: comments.into_iter().peekable(),
+        else_lines: else_lines(&tokens).into_iter(),
+    };
+    printer.statements(&ast.statements, usize::MAX);
+    printer.leading_comments(usize::MAX);
+    printer.out
+}
+
+struct Printer<'a> {
+    arena: &'a Arena,
+    out: String,
+    depth: usize,
+    /// Whether nothing is printed yet in the current block.
+    first: bool,
+    lines: Vec<&'a str>,
+    comments: std::iter::Peekable<std::vec::IntoIter<Comment>>,
+    /// Line of the `else` of each `if` not printed yet.
+    else_lines: std::vec::IntoIter<Option<usize>>,
+}
+
+/// The line of the `else` of each `if` statement, in source order. An
+/// `else` is that of the last `if` outside any braces not closed yet.
+fn else_lines(tokens: &[Token<'_>]) -> Vec<Option<usize>> {
+    let mut lines = Vec::new();
+    // Brace depth of each `if` that could still have an `else`, and its
+    // index in `lines`
+    let mut open_ifs: Vec<(usize, usize)> = Vec::new();
+    let mut depth = 0;
+    for token in tokens {
+        match token.token_type {
+            TokenType::LBrace => depth += 1,
+            TokenType::RBrace => depth -= 1,
+            TokenType::If => {
+                open_ifs.push((depth, lines.len()));
+                lines.push(None);
+            }
+            TokenType::Else => {
+                while open_ifs.last().is_some_and(|&(d, _)| d > depth) {
+                    open_ifs.pop();
+                }
+                if let Some((_, index)) = open_ifs.pop() {
+                    lines[index] = Some(token.line);
+                }
+            }
+            _ => {}
+        }
+    }
+    lines
+}
+
+impl Printer<'_> {
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+        self.first = false;
+    }
+
+    /// Add `text` to the end of the last line.
+    fn append(&mut self, text: &str) {
+        self.out.pop();
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    /// Separate what starts on source line `line` from what is above it
+    /// if the source does, or if `always`.
+    fn blank_line(&mut self, line: usize, always: bool) {
+        let blank_above = line >= 2
+            && self
+                .lines
+                .get(line - 2)
+                .is_some_and(|l| l.trim().is_empty());
+        if !self.first && !self.out.ends_with("\n\n") && (always || blank_above) {
+            self.out.push('\n');
+        }
+    }
+
+    /// Print the comments before source line `line` on lines of their own.
+    fn leading_comments(&mut self, line: usize) {
+        while let Some(comment) = self.comments.next_if(|c| c.line < line) {
+            self.blank_line(comment.line, false);
+            self.line(&comment.text);
+        }
+    }
+
+    /// Print the comments starting up to source line `line` after what was
+    /// printed last.
+    fn trailing_comments(&mut self, line: usize) {
+        if let Some(comment) = self.comments.next_if(|c| c.line <= line) {
+            self.append(&format!(" {}", comment.text));
+        }
+        while let Some(comment) = self.comments.next_if(|c| c.line <= line) {
+            self.line(&comment.text);
+        }
+    }
+
+    /// Print the block of `body` after its header, printed up to the `{` on
+    /// source line `open_line`. The `}` is on source line `close_line`.
+    fn block(&mut self, open_line: usize, body: &[StmtId], close_line: usize) {
+        // Comments on the line of `{` go after it, unless the body starts
+        // there too and they are its
+        let body_line = match body.first().map(|&first| &self.arena[first]) {
+            Some(Statement::Located { line, .. }) => *line,
+            _ => usize::MAX,
+        };
+        self.trailing_comments(open_line.min(body_line - 1));
+        self.depth += 1;
+        self.first = true;
+        self.statements(body, close_line);
+
+        self.leading_comments(close_line);
+        self.depth -= 1;
+        if self.out.ends_with("{\n") {
+            self.append("}");
+        } else {
+            self.line("}");
+        }
+    }
+
+    /// Print the statements of a block whose `}` is on source line
+    /// `close_line`; comments after it are not theirs.
+    fn statements(&mut self, statements: &[StmtId], close_line: usize) {
+        let mut previous_function = false;
+        for &id in statements {
+            let &Statement::Located {
+                line,
+                end_line,
+                statement,
+                ..
+            } = &self.arena[id]
+            else {
+                self.statement(id, 0, 0);
+                continue;
+            };
+            // Functions at the top level always stand apart
+            let function = matches!(self.arena[statement], Statement::FunctionDeclaration { .. });
+            let apart = self.depth == 0 && (function || previous_function);
+            previous_function = function;
+
+            let first_line = match self.comments.peek() {
+                Some(comment) if comment.line < line => comment.line,
+                _ => line,
+            };
+            self.blank_line(first_line, apart);
+            self.leading_comments(line);
+            self.blank_line(line, false);
+            self.statement(statement, line, end_line);
+            self.trailing_comments(end_line.min(close_line - 1));
+        }
+    }
+
+    /// The initializer or update of a `for`, which has no `;` of its own.
+    fn clause(&self, id: StmtId) -> String {
+        match &self.arena[id] {
+            Statement::Let { name, initializer } => {
+                format!("let {} = {}", name, self.arena.source(*initializer))
+            }
+            Statement::ExpressionStatement(expression) => {
+                self.arena.source(*expression).to_string()
+            }
+            statement => unreachable!("{:?} in a `for` clause", statement),
+        }
+    }
+
+    /// Print a statement spanning source lines `line` to `end_line`.
+    fn statement(&mut self, id: StmtId, line: usize, end_line: usize) {
+        let arena = self.arena;
+        match &arena[id] {
+            Statement::Let { name, initializer } => {
+                let initializer = arena.source(*initializer);
+                self.line(&format!("let {} = {};", name, initializer));
+            }
+            Statement::Return(None) => self.line("return;"),
+            Statement::Return(Some(value)) => {
+                self.line(&format!("return {};", arena.source(*value)))
+            }
+            Statement::ExpressionStatement(expression) => {
+                self.line(&format!("{};", arena.source(*expression)))
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let else_line = self.else_lines.next().flatten().unwrap_or(end_line);
+                self.line(&format!("if ({}) {{", arena.source(*condition)));
+                match else_branch {
+                    Some(else_branch) => {
+                        self.block(line, then_branch, else_line);
+                        self.append(" else {");
+                        self.block(else_line, else_branch, end_line);
+                    }
+                    None => self.block(line, then_branch, end_line),
+                }
+            }
+            Statement::While { condition, body } => {
+                self.line(&format!("while ({}) {{", arena.source(*condition)));
+                self.block(line, body, end_line);
+            }
+            Statement::For {
+                initializer,
+                condition,
+                update,
+                body,
+            } => {
+                let condition = match condition {
+                    Some(condition) => format!(" {}", arena.source(*condition)),
+                    None => String::new(),
+                };
+                let update = match update {
+                    Some(update) => format!(" {}", self.clause(*update)),
+                    None => String::new(),
+                };
+                let initializer = initializer.map_or(String::new(), |clause| self.clause(clause));
+                self.line(&format!(
+                    "for ({};{};{}) {{",
+                    initializer, condition, update
+                ));
+                self.block(line, body, end_line);
+            }
+            Statement::FunctionDeclaration { name, params, body } => {
+                self.line(&format!("function {}({}) {{", name, params.join(", ")));
+                self.block(line, body, end_line);
+            }
+            Statement::ExternDeclaration {
+                name,
+                param_types,
+                variadic,
+                return_type,
+            } => {
+                let mut params = param_types.clone();
+                if *variadic {
+                    params.push("...".to_string());
+                }
+                let returns = match return_type {
+                    Some(return_type) => format!(": {}", return_type),
+                    None => String::new(),
+                };
+                self.line(&format!(
+                    "extern function {}({}){};",
+                    name,
+                    params.join(", "),
+                    returns
+                ));
+            }
+            Statement::Block(statements) => {
+                self.line("{");
+                self.block(line, statements, end_line);
+            }
+            Statement::Located {
+                line,
+                end_line,
+                statement,
+                ..
+            } => self.statement(*statement, *line, *end_line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn expression(source: &str) -> String {
+        let ast = parse(tokenize(&format!("{};", source)));
+        match ast.unlocated(ast.statements[0]) {
+            Statement::ExpressionStatement(expression) => ast.arena.source(*expression).to_string(),
+            _ => panic!("Expected an expression statement"),
+        }
+    }
+
+    #[test]
+    fn test_parentheses_only_where_needed() {
+        assert_eq!(expression("(1 + (2 * 3))"), "1 + 2 * 3");
+        assert_eq!(expression("(1 + 2) * 3"), "(1 + 2) * 3");
+        assert_eq!(expression("1 - (2 - 3)"), "1 - (2 - 3)");
+        assert_eq!(expression("-(-x)"), "- -x");
+        assert_eq!(
+            expression("(a ? b : c) ? d : e ? f : g"),
+            "(a ? b : c) ? d : e ? f : g"
+        );
+        assert_eq!(
+            expression("f( \"a\\\"b\\n\" ,!(x&&y))"),
+            "f(\"a\\\"b\\n\", !(x && y))"
+        );
+        assert_eq!(expression("-( a . b ).c(1 ,x)"), "-a.b.c(1, x)");
+        assert_eq!(expression("(a + b).c"), "(a + b).c");
+        assert_eq!(expression("(1).x"), "(1).x");
+    }
+
+    #[test]
+    fn test_layout_and_comments() {
+        let source = "// Adds\nfunction add(a,b){return a+b; // sum\n}\n\n\n\
+                      extern function printf(string, ...): int;\n\
+                      function main() {\n  let x=1;\n\n  if (x) { printf(\"%d\", x); }\n  \
+                      else { /* none */ }\n  while(x<1){}\n}";
+        let expected = "// Adds\n\
+                        function add(a, b) {\n    return a + b; // sum\n}\n\n\
+                        extern function printf(string, ...): int;\n\n\
+                        function main() {\n    let x = 1;\n\n    \
+                        if (x) {\n        printf(\"%d\", x);\n    } else { /* none */\n    }\n    \
+                        while (x < 1) {}\n}\n";
+        let formatted = format_source(source);
+        assert_eq!(formatted, expected);
+        assert_eq!(format_source(&formatted), formatted);
+    }
+
+    #[test]
+    fn test_for_clauses() {
+        let source = "for(let i=0;i<3;let i=i+1){f(i);}\nfor(;;){}\nfor(f();;g()){}";
+        let expected = "for (let i = 0; i < 3; let i = i + 1) {\n    f(i);\n}\n\
+                        for (;;) {}\n\
+                        for (f();; g()) {}\n";
+        assert_eq!(format_source(source), expected);
+    }
+}
@@ -0,0 +1,263 @@
+//! The compiler as a WebAssembly module for a browser playground, with the
+//! `playground` feature: wasm-bindgen exports that take JS source and give
+//! back each stage of compiling it, what running it printed, and its
+//! errors and warnings, as plain JS objects and strings.
+//!
+//! wasm32 aborts on a panic, and the compiler reports errors by raising
+//! them, so an error there throws a `RuntimeError` from the export instead
+//! of returning; `last_error` then gives the diagnostic. Elsewhere the
+//! exports return it as their `Err`.
+
+use crate::diagnostics::{self, Severity};
+use crate::ir::{self, IRModule};
+use crate::optimizer::{self, OptLevel};
+use crate::vm::{self, Limits, Value, VM};
+use crate::{check, lexer, lint, parser};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use wasm_bindgen::prelude::*;
+
+/// Instructions a program may run before the playground stops it, so a
+/// loop that never ends does not hang the page.
+pub const MAX_INSTRUCTIONS: u64 = 10_000_000;
+
+/// Calls a program may have in progress, so unbounded recursion is an
+/// error before it overflows the stack.
+pub const MAX_CALL_DEPTH: usize = 100;
+
+/// An error or warning about the source.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct Message {
+    /// `error` or `warning`.
+    pub severity: String,
+    pub message: String,
+    /// Where in the source it is, when known.
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// The message as the CLI prints it, with the source line underlined.
+    pub rendered: String,
+}
+
+/// Each stage of compiling the source, as text.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct Compilation {
+    /// A token per line, after its line and column.
+    pub tokens: String,
+    pub ast: String,
+    /// The optimized IR in the `.ir` syntax; empty if there are errors.
+    pub ir: String,
+    /// Semantic errors and lint warnings, by line.
+    pub diagnostics: Vec<Message>,
+}
+
+/// The result of running `main`.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct Execution {
+    /// What `print` wrote.
+    pub output: String,
+    /// The value `main` returned, as the REPL shows it.
+    pub value: String,
+}
+
+thread_local! {
+    /// The error the last export raised, for when it could not return it.
+    static LAST_ERROR: RefCell<Option<Message>> = const { RefCell::new(None) };
+    /// Source of the export running, which errors are rendered against.
+    static SOURCE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+impl Message {
+    fn new(diagnostic: &diagnostics::Diagnostic, source: &str) -> Self {
+        let span = diagnostic.labels.first().map(|label| label.span);
+        let severity = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        Message {
+            severity: severity.to_string(),
+            message: diagnostic.message.clone(),
+            line: span.map(|span| span.line as u32),
+            column: span.map(|span| span.column as u32),
+            rendered: diagnostic.render("<playground>", source, false),
+        }
+    }
+}
+
+/// Run `f` on `source`, returning the error it raises. The panic hook
+/// keeps the error for `last_error` first, since on wasm32 nothing catches
+/// it.
+fn catch<T>(source: &str, f: impl FnOnce() -> T) -> Result<T, Message> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            match info.payload().downcast_ref::<diagnostics::Diagnostic>() {
+                Some(diagnostic) => {
+                    let message = SOURCE.with(|source| Message::new(diagnostic, &source.borrow()));
+                    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+                }
+                None => default_hook(info),
+            }
+        }));
+    });
+    SOURCE.with(|current| *current.borrow_mut() = source.to_string());
+    LAST_ERROR.with(|error| error.borrow_mut().take());
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = Message::new(&diagnostics::Diagnostic::from_panic(payload), source);
+        LAST_ERROR.with(|error| *error.borrow_mut() = Some(message.clone()));
+        message
+    })
+}
+
+fn optimization(level: u8) -> OptLevel {
+    match level {
+        0 => OptLevel::O0,
+        1 => OptLevel::O1,
+        _ => OptLevel::O2,
+    }
+}
+
+fn lower(source: &str, level: u8) -> IRModule {
+    let module = ir::lower_ast(parser::parse(lexer::tokenize(source)));
+    optimizer::optimize_with(module, optimization(level))
+}
+
+/// The error the last call raised, if it threw instead of returning it.
+#[wasm_bindgen]
+pub fn last_error() -> Option<Message> {
+    LAST_ERROR.with(|error| error.borrow().clone())
+}
+
+/// The stages of compiling `source` at optimization level `opt_level`,
+/// 0 to 2. A syntax error is raised; other errors are among the
+/// diagnostics.
+#[wasm_bindgen]
+pub fn compile(source: &str, opt_level: u8) -> Result<Compilation, Message> {
+    catch(source, || {
+        let tokens = lexer::tokenize(source);
+        let listing = tokens
+            .iter()
+            .map(|token| format!("{}:{}\t{:?}\n", token.line, token.column, token.token_type))
+            .collect();
+        let ast = parser::parse(tokens);
+        let errors = check::check(&ast, source);
+        let mut found: Vec<diagnostics::Diagnostic> = errors
+            .iter()
+            .cloned()
+            .chain(lint::lint(&ast, source))
+            .collect();
+        found.sort_by_key(|diagnostic| diagnostic.labels.first().map(|label| label.span.line));
+        let ast_text = format!("{:#?}\n", ast);
+        let ir = if errors.is_empty() {
+            let module = ir::lower_ast(ast);
+            ir::print_text(&optimizer::optimize_with(module, optimization(opt_level)))
+        } else {
+            String::new()
+        };
+        Compilation {
+            tokens: listing,
+            ast: ast_text,
+            ir,
+            diagnostics: found.iter().map(|d| Message::new(d, source)).collect(),
+        }
+    })
+}
+
+/// The optimized IR of `source`, in the `.ir` syntax.
+#[wasm_bindgen]
+pub fn emit_ir(source: &str, opt_level: u8) -> Result<String, Message> {
+    catch(source, || ir::print_text(&lower(source, opt_level)))
+}
+
+/// Compile `source` and run its `main` in the VM, within
+/// `MAX_INSTRUCTIONS` and `MAX_CALL_DEPTH`.
+#[wasm_bindgen]
+pub fn run(source: &str, opt_level: u8) -> Result<Execution, Message> {
+    catch(source, || {
+        let mut vm = VM::new(lower(source, opt_level));
+        vm.capture_output();
+        vm.set_limits(Limits {
+            max_call_depth: Some(MAX_CALL_DEPTH),
+            max_instructions: Some(MAX_INSTRUCTIONS),
+        });
+        let value = vm.execute_function("main", vec![]);
+        Execution {
+            output: vm.take_output(),
+            value: describe(&value),
+        }
+    })
+}
+
+/// The syntax and semantic errors and lint warnings in `source`, by line,
+/// without compiling it. A syntax error is the only message, where it does
+/// not throw.
+#[wasm_bindgen]
+pub fn diagnostics(source: &str) -> Vec<Message> {
+    let found = catch(source, || {
+        let ast = parser::parse(lexer::tokenize(source));
+        let mut found = check::check(&ast, source);
+        found.extend(lint::lint(&ast, source));
+        found.sort_by_key(|diagnostic| diagnostic.labels.first().map(|label| label.span.line));
+        found
+    });
+    match found {
+        Ok(found) => found.iter().map(|d| Message::new(d, source)).collect(),
+        Err(error) => vec![error],
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Number(n) => vm::number_to_string(*n),
+        Value::String(s) => format!("\"{}\"", s),
+        Value::Boolean(b) => b.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Undefined => "undefined".to_string(),
+        _ => format!("{:?}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_and_run() {
+        let source = "function main() {\n  let x = 40;\n  print(\"x is\", x);\n  return x + 2;\n}";
+        let compilation = compile(source, 2).unwrap();
+        assert!(compilation.tokens.starts_with("1:1\tFunction\n"));
+        assert!(compilation.ast.contains("FunctionDeclaration"));
+        assert!(compilation.ir.contains("function main() "));
+        assert!(compilation.diagnostics.is_empty());
+
+        let execution = run(source, 0).unwrap();
+        assert_eq!(execution.output, "x is 40\n");
+        assert_eq!(execution.value, "42");
+        assert!(emit_ir(source, 0).unwrap().contains("call_native print/2"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = run("function main() {\n  return missing();\n}", 0).unwrap_err();
+        assert_eq!(error.severity, "error");
+        assert_eq!(error.message, "Function missing not found");
+        assert_eq!(error.line, Some(2));
+        assert_eq!(last_error().unwrap().message, error.message);
+
+        let error = run("function main() { return main(); }", 0).unwrap_err();
+        assert_eq!(error.message, "Call depth exceeded the limit of 100");
+
+        let found = diagnostics("function f( {");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].rendered.starts_with("error: "));
+
+        // Semantic errors leave out the IR instead of raising
+        let compilation = compile("function f() {}\nfunction f() {}", 0).unwrap();
+        assert!(compilation.ir.is_empty());
+        assert_eq!(compilation.diagnostics[0].severity, "error");
+    }
+}
@@ -0,0 +1,139 @@
+//! A sampling profiler for the VM, for `run --profile`. Every so many
+//! instructions it notes which functions are on the call stack, so it costs
+//! little more than counting them. The share of samples a function is seen
+//! in estimates the share of the run's time it took: running itself (self)
+//! or with the functions it called (total).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Samples of each function seen on the call stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionProfile {
+    pub name: String,
+    /// Samples taken while it was the function executing.
+    pub self_samples: u64,
+    /// Samples taken while it was on the call stack, counted once however
+    /// many calls of it there were.
+    pub total_samples: u64,
+}
+
+pub struct Profile {
+    /// Instructions between samples.
+    interval: u64,
+    samples: u64,
+    /// Self and total samples of each function.
+    functions: HashMap<String, (u64, u64)>,
+}
+
+impl Profile {
+    pub fn new(interval: u64) -> Self {
+        Profile {
+            interval,
+            samples: 0,
+            functions: HashMap::new(),
+        }
+    }
+
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Take a sample of the functions on the call stack, outermost first.
+    pub fn sample<'a>(&mut self, stack: impl IntoIterator<Item = &'a str>) {
+        let stack: Vec<&str> = stack.into_iter().collect();
+        let Some(&executing) = stack.last() else {
+            return;
+        };
+        self.samples += 1;
+        for (depth, &name) in stack.iter().enumerate() {
+            if stack[..depth].contains(&name) {
+                continue;
+            }
+            self.functions.entry(name.to_string()).or_default().1 += 1;
+        }
+        self.functions.get_mut(executing).unwrap().0 += 1;
+    }
+
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// The functions sampled, those on the stack most often first.
+    pub fn functions(&self) -> Vec<FunctionProfile> {
+        let mut functions: Vec<FunctionProfile> = self
+            .functions
+            .iter()
+            .map(|(name, &(self_samples, total_samples))| FunctionProfile {
+                name: name.clone(),
+                self_samples,
+                total_samples,
+            })
+            .collect();
+        functions.sort_by(|a, b| {
+            (b.total_samples, b.self_samples, &a.name).cmp(&(
+                a.total_samples,
+                a.self_samples,
+                &b.name,
+            ))
+        });
+        functions
+    }
+
+    /// A line per function with its self and total time, estimated from
+    /// its samples out of a run that took `elapsed`.
+    pub fn report(&self, elapsed: Duration) -> String {
+        let mut report = format!(
+            "\nprofile: {} samples, one every {} instructions\n",
+            self.samples, self.interval
+        );
+        if self.samples == 0 {
+            report.push_str("  the run was too short to sample\n");
+            return report;
+        }
+        report.push_str(&format!(
+            "  {:<24}{:>20}{:>20}\n",
+            "function", "self", "total"
+        ));
+        let time = |samples: u64| {
+            let share = samples as f64 / self.samples as f64;
+            format!(
+                "{:>10.3} ms{:>6.1}%",
+                elapsed.as_secs_f64() * 1000.0 * share,
+                share * 100.0
+            )
+        };
+        for function in self.functions() {
+            report.push_str(&format!(
+                "  {:<24}{}{}\n",
+                function.name,
+                time(function.self_samples),
+                time(function.total_samples)
+            ));
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_and_total_samples() {
+        let mut profile = Profile::new(100);
+        profile.sample(["main", "fib", "fib"]);
+        profile.sample(["main", "fib"]);
+        profile.sample(["main"]);
+        profile.sample([]);
+        assert_eq!(profile.samples(), 3);
+        let functions = profile.functions();
+        let summary: Vec<(&str, u64, u64)> = functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.self_samples, f.total_samples))
+            .collect();
+        assert_eq!(summary, [("main", 1, 3), ("fib", 2, 2)]);
+        let report = profile.report(Duration::from_millis(3));
+        assert!(report.contains("fib                          2.000 ms  66.7%     2.000 ms  66.7%"));
+    }
+}
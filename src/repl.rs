@@ -0,0 +1,127 @@
+//! The `repl` subcommand's session: each snippet is compiled into a module
+//! loaded into one VM, so the functions it declares and the variables it
+//! sets stay around for the snippets after it.
+
+use js_compiler::diagnostics::Diagnostic;
+use js_compiler::parser::{self, Arena, Statement, StmtId, AST};
+use js_compiler::{ir, lexer, vm};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Function the statements of a snippet run in, replaced by each snippet.
+const SNIPPET_FUNCTION: &str = "__repl";
+
+pub struct Repl {
+    vm: vm::VM,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Repl {
+            vm: vm::VM::new(ir::lower_ast(AST::default())),
+        }
+    }
+
+    /// Compile and run `source`, returning the value of its last statement
+    /// if that is an expression, or the error it raised.
+    /// Function and extern declarations are added to the session; `let`
+    /// outside them sets a global.
+    pub fn eval(&mut self, source: &str) -> Result<vm::Value, Diagnostic> {
+        let vm = &mut self.vm;
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut ast = parser::parse(lexer::tokenize(source));
+            let top_level = std::mem::take(&mut ast.statements);
+            let (mut statements, body): (Vec<_>, Vec<_>) =
+                top_level.into_iter().partition(|&statement| {
+                    matches!(
+                        ast.unlocated(statement),
+                        Statement::FunctionDeclaration { .. } | Statement::ExternDeclaration { .. }
+                    )
+                });
+            if let Some(&last) = body.last() {
+                return_expression(&mut ast.arena, last);
+            }
+            statements.push(ast.arena.statement(Statement::FunctionDeclaration {
+                name: SNIPPET_FUNCTION.to_string(),
+                params: Vec::new(),
+                body,
+            }));
+            ast.statements = statements;
+
+            vm.load_module(ir::lower_ast(ast));
+            vm.execute_function(SNIPPET_FUNCTION, vec![])
+        }))
+        .map_err(|payload| {
+            self.vm.reset_stack();
+            Diagnostic::from_panic(payload)
+        })
+    }
+}
+
+/// Turn an expression statement into returning its value.
+fn return_expression(arena: &mut Arena, statement: StmtId) {
+    match arena[statement] {
+        Statement::Located { statement, .. } => return_expression(arena, statement),
+        Statement::ExpressionStatement(expression) => {
+            arena[statement] = Statement::Return(Some(expression));
+        }
+        _ => {}
+    }
+}
+
+/// Whether `source` closes every bracket it opens, or should be continued
+/// on the next line.
+pub fn is_complete(source: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote = None;
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '{') => depth += 1,
+            (None, ')' | '}') => depth -= 1,
+            (None, _) => {}
+        }
+    }
+    depth <= 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_keeps_functions_and_globals() {
+        let mut repl = Repl::new();
+        assert_eq!(
+            repl.eval("function square(x) { return x * x; }"),
+            Ok(vm::Value::Undefined)
+        );
+        assert_eq!(repl.eval("let n = square(6);"), Ok(vm::Value::Undefined));
+        assert_eq!(repl.eval("n + 6;"), Ok(vm::Value::Number(42.0)));
+        assert_eq!(
+            repl.eval("\"n is \" + n;"),
+            Ok(vm::Value::String("n is 36".into()))
+        );
+    }
+
+    #[test]
+    fn test_errors_leave_the_session_usable() {
+        let mut repl = Repl::new();
+        repl.eval("let n = 1;").unwrap();
+        let error = repl.eval("missing(n);").unwrap_err();
+        assert_eq!(error.to_string(), "1: Function missing not found");
+        assert_eq!(repl.eval("n;"), Ok(vm::Value::Number(1.0)));
+    }
+
+    #[test]
+    fn test_unclosed_brackets_continue() {
+        assert!(is_complete("print(1);"));
+        assert!(!is_complete("function f() {"));
+        assert!(is_complete("function f() {\n  return \"}\";\n}"));
+    }
+}
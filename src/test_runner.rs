@@ -0,0 +1,118 @@
+//! The `test` subcommand: every `*.test.js` file under a directory is a
+//! test, passing if its `main` runs to the end. Each file runs in a VM of
+//! its own, with `assert(condition, message)` available besides the
+//! project's built-ins; an assertion that fails, or any other error, fails
+//! the test.
+
+use crate::cli::Cli;
+use js_compiler::diagnostics::Diagnostic;
+use js_compiler::{ir, lexer, optimizer, parser, vm};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Ending of the names of test files.
+const TEST_SUFFIX: &str = ".test.js";
+
+/// Directories not searched for tests: dependencies and build output.
+const SKIPPED_DIRECTORIES: &[&str] = &["node_modules", "target"];
+
+/// Run the tests under the input path (a test file, or a directory to
+/// search), or under the current directory without one. Returns the exit
+/// status: 1 if any test failed.
+pub fn run(cli: &Cli) -> i32 {
+    let root = Path::new(cli.input.as_deref().unwrap_or("."));
+    let mut files = Vec::new();
+    if root.is_dir() {
+        find_tests(root, &mut files);
+    } else {
+        files.push(root.to_path_buf());
+    }
+    files.sort();
+
+    // Failures are reported with the results, not as they happen
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    println!("running {} test file{}", files.len(), plural(files.len()));
+    let mut failures = Vec::new();
+    for path in &files {
+        let name = path.display().to_string();
+        let started = Instant::now();
+        let source = fs::read_to_string(path);
+        let result = match &source {
+            Ok(source) => run_test(cli, source),
+            Err(error) => Err((Diagnostic::error(error.to_string()), String::new())),
+        };
+        let elapsed = started.elapsed();
+        match result {
+            Ok(()) => println!("test {} ... ok ({})", name, milliseconds(elapsed)),
+            Err((diagnostic, output)) => {
+                println!("test {} ... FAILED ({})", name, milliseconds(elapsed));
+                let source = source.unwrap_or_default();
+                failures.push((name, source, diagnostic, output));
+            }
+        }
+    }
+    panic::set_hook(hook);
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, source, diagnostic, output) in &failures {
+            println!("\n---- {} ----", name);
+            print!("{}", output);
+            print!("{}", diagnostic.render(name, source, false));
+        }
+    }
+
+    let passed = files.len() - failures.len();
+    println!(
+        "\ntest result: {}. {} passed; {} failed; finished in {}",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        failures.len(),
+        milliseconds(start.elapsed())
+    );
+    i32::from(!failures.is_empty())
+}
+
+/// Add the test files in `directory` and those below it to `files`.
+fn find_tests(directory: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if path.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRECTORIES.contains(&name.as_str()) {
+                find_tests(&path, files);
+            }
+        } else if name.ends_with(TEST_SUFFIX) {
+            files.push(path);
+        }
+    }
+}
+
+/// Compile `source` and run its `main` in a new VM, returning what failed
+/// and what the test printed if it did not pass.
+fn run_test(cli: &Cli, source: &str) -> Result<(), (Diagnostic, String)> {
+    let mut vm = None;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let ast = parser::parse(lexer::tokenize(source));
+        let module = optimizer::optimize_with(ir::lower_ast(ast), cli.opt_level);
+        let vm = vm.insert(crate::new_vm(cli, module));
+        vm.capture_output();
+        vm.register_native("assert", vm::native_assert);
+        vm.execute_function("main", vec![]);
+    }));
+    let output = vm.as_mut().map(vm::VM::take_output).unwrap_or_default();
+    result.map_err(|payload| (Diagnostic::from_panic(payload), output))
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,76 @@
+//! Time spent in each phase of compiling and running a program, for
+//! `--timings`. Phases record how long they took while timing is enabled,
+//! and whoever enabled it takes the totals once the command is done.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TIMES: Mutex<Vec<(String, Duration)>> = Mutex::new(Vec::new());
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Add `elapsed` to the time of `phase`, if timing is enabled.
+pub fn record(phase: &str, elapsed: Duration) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut times = TIMES.lock().unwrap();
+    match times.iter_mut().find(|(name, _)| name == phase) {
+        Some((_, total)) => *total += elapsed,
+        None => times.push((phase.to_string(), elapsed)),
+    }
+}
+
+/// Run `f`, recording how long it took as part of `phase`. The time is
+/// recorded even if `f` panics, so a failing run still shows where it
+/// spent its time. The clock is not read unless timing is enabled, since
+/// wasm32 has none.
+pub fn time<T>(phase: &str, f: impl FnOnce() -> T) -> T {
+    struct Timer<'a> {
+        phase: &'a str,
+        start: Option<Instant>,
+    }
+    impl Drop for Timer<'_> {
+        fn drop(&mut self) {
+            if let Some(start) = self.start {
+                record(self.phase, start.elapsed());
+            }
+        }
+    }
+    let _timer = Timer {
+        phase,
+        start: ENABLED.load(Ordering::Relaxed).then(Instant::now),
+    };
+    f()
+}
+
+/// The time of each phase recorded since the last call, in the order the
+/// phases first ran.
+pub fn take() -> Vec<(String, Duration)> {
+    std::mem::take(&mut *TIMES.lock().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_add_up() {
+        // Other tests running passes may record too, under other names
+        enable();
+        record("test phase", Duration::from_millis(2));
+        let value = time("test phase", || 42);
+        assert_eq!(value, 42);
+        let times = take();
+        let phases: Vec<&(String, Duration)> = times
+            .iter()
+            .filter(|(name, _)| name == "test phase")
+            .collect();
+        assert_eq!(phases.len(), 1);
+        assert!(phases[0].1 >= Duration::from_millis(2));
+    }
+}
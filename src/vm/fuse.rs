@@ -0,0 +1,212 @@
+//! Superinstructions: runs of IR instructions common in loops, fused into
+//! one instruction as the VM loads a function, so that each takes one trip
+//! through the dispatch loop instead of four.
+//!
+//! - `Load x; PushConst c; Binary op; Store x`, as in `let i = i + 1;`,
+//!   becomes `Update`.
+//! - `Load a; Load b; Binary op; Unary not; JumpIf label`, as in the test
+//!   of `for (...; i < n; ...)`, which leaves the loop unless `i < n`,
+//!   becomes `CompareJump`, as does the same run without the `not`.
+//!
+//! A label always starts a run of its own, so no jump lands inside a
+//! fused instruction, and neither is there a `Line` inside one to move
+//! errors to another statement.
+//!
+//! Variables that are not the function's parameters are globals, which
+//! are numbered as the functions using them load; their loads and stores,
+//! fused or not, find them by that number instead of by name. Those of
+//! parameters find them by their index among the frame's.
+
+use crate::ir::{BinaryOp, IRInstruction, UnaryOp};
+
+/// An instruction as the VM runs it.
+#[derive(Debug, Clone)]
+pub(super) enum Op {
+    IR(IRInstruction),
+    /// `Load` of a parameter.
+    LoadLocal(u16),
+    /// `Store` to a parameter.
+    StoreLocal(u16),
+    /// `Load` of a global.
+    LoadGlobal(u32),
+    /// `Store` to a global.
+    StoreGlobal(u32),
+    /// `Load var; PushConst constant; Binary op; Store var`.
+    Update {
+        var: Var,
+        constant: u16,
+        op: BinaryOp,
+    },
+    /// `Load left; Load right; Binary op; JumpIf label`, with `Unary not`
+    /// before the jump if `negated`.
+    CompareJump {
+        left: Var,
+        right: Var,
+        op: BinaryOp,
+        negated: bool,
+        label: String,
+    },
+}
+
+/// A variable a fused instruction uses.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Var {
+    /// A parameter of the function, by its index.
+    Local(u16),
+    /// A global, by its number.
+    Global(u32),
+}
+
+impl Op {
+    /// How many IR instructions it stands for.
+    pub fn size(&self) -> u64 {
+        match self {
+            Op::IR(_)
+            | Op::LoadLocal(_)
+            | Op::StoreLocal(_)
+            | Op::LoadGlobal(_)
+            | Op::StoreGlobal(_) => 1,
+            Op::Update { .. } | Op::CompareJump { negated: false, .. } => 4,
+            Op::CompareJump { negated: true, .. } => 5,
+        }
+    }
+}
+
+/// `instructions` with the runs above fused, and each variable found as
+/// `var` says.
+pub(super) fn fuse(instructions: Vec<IRInstruction>, mut var: impl FnMut(&str) -> Var) -> Vec<Op> {
+    use IRInstruction::{Binary, JumpIf, Load, PushConst, Store, Unary};
+
+    let mut ops = Vec::with_capacity(instructions.len());
+    let mut rest = instructions.as_slice();
+    while let Some(first) = rest.first() {
+        let (op, size) = match rest {
+            [Load(name), PushConst(constant), Binary(op), Store(stored), ..]
+                if name == stored && is_arithmetic(op) =>
+            {
+                let op = Op::Update {
+                    var: var(name),
+                    constant: *constant,
+                    op: op.clone(),
+                };
+                (op, 4)
+            }
+            [Load(left), Load(right), Binary(op), Unary(UnaryOp::Not), JumpIf(label), ..]
+                if is_comparison(op) =>
+            {
+                let op = Op::CompareJump {
+                    left: var(left),
+                    right: var(right),
+                    op: op.clone(),
+                    negated: true,
+                    label: label.clone(),
+                };
+                (op, 5)
+            }
+            [Load(left), Load(right), Binary(op), JumpIf(label), ..] if is_comparison(op) => {
+                let op = Op::CompareJump {
+                    left: var(left),
+                    right: var(right),
+                    op: op.clone(),
+                    negated: false,
+                    label: label.clone(),
+                };
+                (op, 4)
+            }
+            [Load(name), ..] => match var(name) {
+                Var::Global(slot) => (Op::LoadGlobal(slot), 1),
+                Var::Local(index) => (Op::LoadLocal(index), 1),
+            },
+            [Store(name), ..] => match var(name) {
+                Var::Global(slot) => (Op::StoreGlobal(slot), 1),
+                Var::Local(index) => (Op::StoreLocal(index), 1),
+            },
+            _ => (Op::IR(first.clone()), 1),
+        };
+        ops.push(op);
+        rest = &rest[size..];
+    }
+    ops
+}
+
+/// `instructions` one for one, as the VM runs them when it traces each
+/// instruction, finding variables by name.
+pub(super) fn unfused(instructions: Vec<IRInstruction>) -> Vec<Op> {
+    instructions.into_iter().map(Op::IR).collect()
+}
+
+/// Source line and column of the instruction at `index`, as
+/// `ir::position_at` finds it.
+pub(super) fn position_at(ops: &[Op], index: usize) -> Option<(usize, usize)> {
+    ops.iter().take(index + 1).rev().find_map(|op| match op {
+        Op::IR(IRInstruction::Line(line, column)) => Some((*line, *column)),
+        _ => None,
+    })
+}
+
+fn is_arithmetic(op: &BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod
+    )
+}
+
+fn is_comparison(op: &BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::Lt
+            | BinaryOp::Gt
+            | BinaryOp::Le
+            | BinaryOp::Ge
+            | BinaryOp::Eq
+            | BinaryOp::Neq
+            | BinaryOp::StrictEq
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir, lexer, parser};
+
+    /// The first function's code, with `n` a global.
+    fn ops(source: &str) -> Vec<Op> {
+        let module = ir::lower_ast(parser::parse(lexer::tokenize(source)));
+        let params = &module.functions[0].params;
+        fuse(module.functions[0].instructions(), |name| {
+            match params.iter().position(|param| param == name) {
+                Some(index) => Var::Local(index as u16),
+                None => Var::Global(0),
+            }
+        })
+    }
+
+    #[test]
+    fn test_fuses_loop_runs() {
+        let ops = ops("function main(i) { let n = 3;\n\
+                       for (; i < n; let i = i + 1) { print(i); }\n\
+                       return i; }");
+        let fused: Vec<String> = ops
+            .iter()
+            .filter(|op| matches!(op, Op::Update { .. } | Op::CompareJump { .. }))
+            .map(|op| format!("{:?}", op))
+            .collect();
+        assert_eq!(
+            fused,
+            [
+                "CompareJump { left: Local(0), right: Global(0), op: Lt, negated: true, label: \"L2\" }",
+                "Update { var: Local(0), constant: 1, op: Add }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_leaves_other_runs() {
+        // A store to another name, and a load of a constant before a jump
+        let ops = ops("function main(i, j) { let j = i + 1;\n\
+                       for (; i < 3;) { print(j); } }");
+        assert!(ops
+            .iter()
+            .all(|op| !matches!(op, Op::Update { .. } | Op::CompareJump { .. })));
+    }
+}
@@ -0,0 +1,54 @@
+//! Natives that return futures, for I/O such as HTTP requests or file
+//! reads. A script calling one is suspended until the future resolves:
+//! `VM::execute_function_async` returns to the host's executor meanwhile,
+//! and `VM::execute_function` parks the thread.
+
+use super::{HostError, Value};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// What an async native resolves to: its result, or an error raised at
+/// the call.
+pub type NativeFuture = Pin<Box<dyn Future<Output = Result<Value, HostError>>>>;
+
+/// Wakes a thread parked in `block_on`.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Wait for `future` on this thread, parking it while the future is
+/// pending.
+pub(super) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// An async native's future, with any error it panics with while polled
+/// caught, for the VM to raise at the call.
+pub(super) struct CatchUnwind(pub(super) NativeFuture);
+
+impl Future for CatchUnwind {
+    type Output = thread::Result<Result<Value, HostError>>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        match panic::catch_unwind(AssertUnwindSafe(|| self.0.as_mut().poll(context))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
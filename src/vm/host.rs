@@ -0,0 +1,368 @@
+//! Rust objects that scripts use as JS objects. A `HostObject` registered
+//! with `VM::register_host_object` under a name, say `counter`, has its
+//! methods called as `counter.add(2)` and its properties read as
+//! `counter.count`:
+//!
+//! ```
+//! use js_compiler::vm::{Arguments, HostError, HostObject, Value, VM};
+//!
+//! struct Counter { count: f64 }
+//!
+//! impl HostObject for Counter {
+//!     fn methods(&self) -> &[&'static str] {
+//!         &["add"]
+//!     }
+//!
+//!     fn call(&mut self, _method: &str, args: Arguments) -> Result<Value, HostError> {
+//!         self.count += args.get::<f64>(0)?;
+//!         Ok(self.count.into())
+//!     }
+//!
+//!     fn get(&self, property: &str) -> Option<Value> {
+//!         (property == "count").then(|| self.count.into())
+//!     }
+//! }
+//!
+//! let source = "function main() { counter.add(2); counter.add(3); return counter.count; }";
+//! let module = js_compiler::ir::lower_ast(js_compiler::parser::parse(js_compiler::lexer::tokenize(source)));
+//! let mut vm = VM::new(module);
+//! let counter = vm.register_host_object("counter", Counter { count: 0.0 });
+//! assert_eq!(vm.execute_function("main", vec![]), Value::Number(5.0));
+//! assert_eq!(counter.borrow().count, 5.0);
+//! ```
+//!
+//! Arguments convert to Rust types through `FromValue`, and results from
+//! them through `Into<Value>`. A method's `HostError`, such as one for an
+//! argument of the wrong type, is raised as a VM error at the call.
+
+use super::{VMContext, Value};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// A Rust object scripts can use; see the module documentation.
+pub trait HostObject {
+    /// Names of the methods scripts can call.
+    fn methods(&self) -> &[&'static str];
+
+    /// Call `method`, one of `methods`, with the script's arguments.
+    fn call(&mut self, method: &str, args: Arguments) -> Result<Value, HostError>;
+
+    /// Value of the property `name`; scripts read one that is `None` as
+    /// undefined.
+    fn get(&self, _name: &str) -> Option<Value> {
+        None
+    }
+}
+
+/// Host objects registered with a VM, by name.
+pub(super) type HostObjects = std::collections::HashMap<String, Rc<RefCell<dyn HostObject>>>;
+
+/// Why a method failed, raised as a VM error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostError(pub String);
+
+impl From<String> for HostError {
+    fn from(message: String) -> Self {
+        HostError(message)
+    }
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for HostError {
+    fn from(message: &str) -> Self {
+        HostError(message.to_string())
+    }
+}
+
+/// A Rust type an argument can convert to.
+pub trait FromValue: Sized {
+    /// What the argument should be, for errors: "a number".
+    const EXPECTED: &'static str;
+
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl FromValue for f64 {
+    const EXPECTED: &'static str = "a number";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for i64 {
+    const EXPECTED: &'static str = "an integer";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 2f64.powi(53) => Some(*n as i64),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for bool {
+    const EXPECTED: &'static str = "a boolean";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for String {
+    const EXPECTED: &'static str = "a string";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => Some(s.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for Value {
+    const EXPECTED: &'static str = "a value";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Boolean(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s.into())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.into())
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::Undefined
+    }
+}
+
+/// The arguments of a method call, converted to Rust types on request.
+pub struct Arguments {
+    values: Vec<Value>,
+}
+
+impl Arguments {
+    pub fn new(values: Vec<Value>) -> Self {
+        Arguments { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Argument `index` as a `T`; an error if it is missing or of another
+    /// type.
+    pub fn get<T: FromValue>(&self, index: usize) -> Result<T, HostError> {
+        match self.values.get(index) {
+            Some(value) => T::from_value(value).ok_or_else(|| {
+                let found = type_of(value);
+                HostError(format!(
+                    "argument {} should be {}, not {}",
+                    index + 1,
+                    T::EXPECTED,
+                    found
+                ))
+            }),
+            None => Err(HostError(format!(
+                "missing argument {}, which should be {}",
+                index + 1,
+                T::EXPECTED
+            ))),
+        }
+    }
+
+    /// Argument `index` as a `T`, or `None` if it is missing or undefined.
+    pub fn optional<T: FromValue>(&self, index: usize) -> Result<Option<T>, HostError> {
+        match self.values.get(index) {
+            None | Some(Value::Undefined) => Ok(None),
+            Some(_) => self.get(index).map(Some),
+        }
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Boolean(_) => "a boolean",
+        Value::Null => "null",
+        Value::Undefined => "undefined",
+        Value::Object(_) => "an object",
+        Value::Closure { .. } => "a function",
+    }
+}
+
+/// Call `method` of `object`, where `name` is `object.method`, raising its
+/// error as the VM's.
+pub(super) fn call_method(
+    context: &mut VMContext,
+    object: &RefCell<dyn HostObject>,
+    name: &str,
+    args: Vec<Value>,
+) -> Value {
+    let (_, method) = name.split_once('.').unwrap();
+    let result = object.borrow_mut().call(method, Arguments::new(args));
+    match result {
+        Ok(value) => value,
+        Err(error) => context.raise_host_error(name, error),
+    }
+}
+
+impl VMContext {
+    /// Raise `error` from the native `name` as a VM error at the call.
+    pub fn raise_host_error(&self, name: &str, error: impl fmt::Display) -> ! {
+        self.error(format!("{}: {}", name, error)).raise()
+    }
+
+    /// The property a dotted name such as `counter.count` reads, if it
+    /// names a host object's.
+    pub(super) fn host_property(&self, name: &str) -> Option<Value> {
+        let (object, property) = name.split_once('.')?;
+        self.host_objects.get(object)?.borrow().get(property)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::Diagnostic;
+    use crate::vm::VM;
+    use crate::{ir, lexer, parser};
+    use std::panic::{self, AssertUnwindSafe};
+
+    struct Greeter {
+        greeting: String,
+        greeted: Vec<String>,
+    }
+
+    impl HostObject for Greeter {
+        fn methods(&self) -> &[&'static str] {
+            &["greet", "repeat"]
+        }
+
+        fn call(&mut self, method: &str, args: Arguments) -> Result<Value, HostError> {
+            match method {
+                "greet" => {
+                    let name: String = args.get(0)?;
+                    self.greeted.push(name.clone());
+                    Ok(format!("{}, {}", self.greeting, name).into())
+                }
+                _ => {
+                    let times = args.optional::<i64>(1)?.unwrap_or(2);
+                    Ok(args.get::<String>(0)?.repeat(times as usize).into())
+                }
+            }
+        }
+
+        fn get(&self, property: &str) -> Option<Value> {
+            match property {
+                "greeting" => Some(self.greeting.as_str().into()),
+                "count" => Some((self.greeted.len() as f64).into()),
+                _ => None,
+            }
+        }
+    }
+
+    fn vm(source: &str) -> VM {
+        VM::new(ir::lower_ast(parser::parse(lexer::tokenize(source))))
+    }
+
+    #[test]
+    fn test_host_object() {
+        let mut vm = vm(
+            "function main(name) { greeter.greet(\"Ada\"); return greeter.greet(name); }\n\
+                         function count() { return greeter.count; }\n\
+                         function missing() { return greeter.nothing; }\n\
+                         function repeat(n) { return greeter.repeat(\"ab\", n); }",
+        );
+        let greeter = vm.register_host_object(
+            "greeter",
+            Greeter {
+                greeting: "Hello".to_string(),
+                greeted: Vec::new(),
+            },
+        );
+        assert_eq!(
+            vm.execute_function("main", vec!["Grace".into()]),
+            Value::String("Hello, Grace".into())
+        );
+        assert_eq!(greeter.borrow().greeted, ["Ada", "Grace"]);
+        assert_eq!(vm.execute_function("count", vec![]), Value::Number(2.0));
+        assert_eq!(vm.execute_function("missing", vec![]), Value::Undefined);
+        assert_eq!(
+            vm.execute_function("repeat", vec![]),
+            Value::String("abab".into())
+        );
+    }
+
+    #[test]
+    fn test_argument_errors() {
+        let mut vm = vm("function main(x) {\n  return greeter.greet(x);\n}\n\
+                         function repeat(n) { return greeter.repeat(\"ab\", n); }");
+        vm.register_host_object(
+            "greeter",
+            Greeter {
+                greeting: "Hi".to_string(),
+                greeted: Vec::new(),
+            },
+        );
+        let mut error = |function: &str, args: Vec<Value>| {
+            let result =
+                panic::catch_unwind(AssertUnwindSafe(|| vm.execute_function(function, args)));
+            vm.reset_stack();
+            Diagnostic::from_panic(result.unwrap_err())
+        };
+        let diagnostic = error("main", vec![Value::Number(1.0)]);
+        assert_eq!(
+            diagnostic.message,
+            "greeter.greet: argument 1 should be a string, not a number"
+        );
+        assert_eq!(diagnostic.labels[0].span.line, 2);
+        assert_eq!(
+            error("main", vec![]).message,
+            "greeter.greet: argument 1 should be a string, not undefined"
+        );
+        assert_eq!(
+            error("repeat", vec![Value::Number(1.5)]).message,
+            "greeter.repeat: argument 2 should be an integer, not a number"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Messages between a VM and the application, for a VM running on a
+//! thread of its own. `VM::message_channel` gives the host a sender and a
+//! receiver, and scripts the natives at the other end:
+//!
+//! - `postMessage(value)` sends a copy of the value to the host.
+//! - `receiveMessage()` waits for the host's next message. It returns
+//!   undefined once the host's sender is dropped.
+//! - `pollMessage()` returns the next message if there is one, and
+//!   undefined if not.
+//!
+//! Messages are copies, as `Message`s, so the two sides share no memory.
+//! Closures cannot be sent.
+
+use super::{Value, VM};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+/// A copy of a value, which can go to another thread.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Null,
+    Undefined,
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Object(HashMap<String, Message>),
+}
+
+impl TryFrom<&Value> for Message {
+    /// What could not be copied: "a function".
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Self, String> {
+        Ok(match value {
+            Value::Null => Message::Null,
+            Value::Undefined => Message::Undefined,
+            Value::Number(n) => Message::Number(*n),
+            Value::String(s) => Message::String(s.to_string()),
+            Value::Boolean(b) => Message::Boolean(*b),
+            Value::Object(properties) => Message::Object(
+                properties
+                    .iter()
+                    .map(|(name, value)| Ok((name.clone(), Message::try_from(value)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            Value::Closure { .. } => return Err("a function".to_string()),
+        })
+    }
+}
+
+impl From<Message> for Value {
+    fn from(message: Message) -> Self {
+        match message {
+            Message::Null => Value::Null,
+            Message::Undefined => Value::Undefined,
+            Message::Number(n) => Value::Number(n),
+            Message::String(s) => Value::String(s.into()),
+            Message::Boolean(b) => Value::Boolean(b),
+            Message::Object(properties) => Value::Object(
+                properties
+                    .into_iter()
+                    .map(|(name, message)| (name, message.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl VM {
+    /// Connect the VM to the host: scripts' `postMessage` sends to the
+    /// returned receiver, and `receiveMessage` and `pollMessage` receive
+    /// from the returned sender. A new channel replaces the last one.
+    pub fn message_channel(&mut self) -> (Sender<Message>, Receiver<Message>) {
+        let (to_host, from_vm) = mpsc::channel();
+        let (to_vm, from_host) = mpsc::channel::<Message>();
+
+        self.register_native("postMessage", move |context, args| {
+            let value = args.into_iter().next().unwrap_or(Value::Undefined);
+            let message = Message::try_from(&value).unwrap_or_else(|found| {
+                context
+                    .error(format!("postMessage cannot send {}", found))
+                    .raise()
+            });
+            if to_host.send(message).is_err() {
+                let message = "postMessage found the host's receiver dropped";
+                context.error(message.to_string()).raise();
+            }
+            Value::Undefined
+        });
+
+        let from_host = Rc::new(from_host);
+        let receiver = from_host.clone();
+        self.register_native("receiveMessage", move |_, _| {
+            receiver.recv().map_or(Value::Undefined, Value::from)
+        });
+        self.register_native("pollMessage", move |_, _| match from_host.try_recv() {
+            Ok(message) => message.into(),
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => Value::Undefined,
+        });
+        (to_vm, from_vm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir, lexer, parser};
+    use std::thread;
+
+    #[test]
+    fn test_messages_between_threads() {
+        // Doubles each number it receives, until a null, then returns the total
+        let source = "function main() { postMessage(\"ready\"); return serve(0); }\n\
+                      function serve(total) {\n\
+                        let n = receiveMessage();\n\
+                        if (n === null) { return total; }\n\
+                        postMessage(n * 2);\n\
+                        return serve(total + n);\n\
+                      }";
+        let module = ir::lower_ast(parser::parse(lexer::tokenize(source)));
+        let (ports, ready) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            let mut vm = VM::new(module);
+            ports.send(vm.message_channel()).unwrap();
+            Message::try_from(&vm.execute_function("main", vec![])).unwrap()
+        });
+
+        let (sender, receiver) = ready.recv().unwrap();
+        assert_eq!(receiver.recv(), Ok(Message::String("ready".to_string())));
+        for n in [1.0, 2.0, 3.0] {
+            sender.send(Message::Number(n)).unwrap();
+            assert_eq!(receiver.recv(), Ok(Message::Number(n * 2.0)));
+        }
+        sender.send(Message::Null).unwrap();
+        assert_eq!(worker.join().unwrap(), Message::Number(6.0));
+    }
+
+    #[test]
+    fn test_poll_and_copies() {
+        let source = "function poll() { return pollMessage(); }\n\
+                      function wait() { return receiveMessage(); }";
+        let mut vm = VM::new(ir::lower_ast(parser::parse(lexer::tokenize(source))));
+        let (sender, _receiver) = vm.message_channel();
+        assert_eq!(vm.execute_function("poll", vec![]), Value::Undefined);
+
+        let object = Message::Object(HashMap::from([
+            ("n".to_string(), Message::Number(1.0)),
+            ("nested".to_string(), Message::Object(HashMap::new())),
+        ]));
+        sender.send(object.clone()).unwrap();
+        let value = vm.execute_function("poll", vec![]);
+        assert_eq!(Message::try_from(&value), Ok(object));
+
+        // Waiting stops once the host hangs up
+        drop(sender);
+        assert_eq!(vm.execute_function("wait", vec![]), Value::Undefined);
+
+        let closure = Value::Closure {
+            function: "f".to_string(),
+            captures: Default::default(),
+        };
+        assert_eq!(Message::try_from(&closure), Err("a function".to_string()));
+    }
+}
@@ -1,81 +1,329 @@
-use crate::debug::DebugTrace;
-use crate::ir::{BinaryOp, Constant, IRFunction, IRInstruction, IRModule, UnaryOp};
+use crate::debug::{DebugTrace, Location, TraceOptions};
+use crate::diagnostics::{Diagnostic, Span};
+use crate::ir::{BinaryOp, Constant, FuncId, IRFunction, IRInstruction, IRModule, UnaryOp};
+use crate::profile::Profile;
+use fuse::{Op, Var};
+use std::any::Any;
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::thread;
+
+mod fuse;
+mod future;
+mod host;
+mod messages;
+mod sandbox;
+mod string;
+
+pub use future::NativeFuture;
+pub use host::{Arguments, FromValue, HostError, HostObject};
+#[cfg(feature = "macros")]
+pub use js_compiler_macros::js_native;
+pub use messages::Message;
+pub use sandbox::{Capability, VmOptions};
+pub use string::JsString;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Null,
     Number(f64),
-    String(String),
+    String(JsString),
     Boolean(bool),
     Object(HashMap<String, Value>),
+    /// A function made by `MakeClosure`, with the values of its captures.
+    Closure {
+        function: String,
+        captures: Captures,
+    },
     Undefined,
 }
 
+/// Values of a closure's captures, which its calls share.
+pub type Captures = Rc<RefCell<Vec<Value>>>;
+
 impl Value {
+    /// Process exit status for a value `main` returned, as native programs
+    /// exit: a number truncated to an integer, which the system keeps the
+    /// low 8 bits of, and 0 for anything else.
+    pub fn exit_status(&self) -> i32 {
+        match self {
+            Value::Number(n) if n.is_finite() => (n.trunc() % 256.0) as i32,
+            _ => 0,
+        }
+    }
+
     fn from_constant(constant: &Constant) -> Self {
         match constant {
             Constant::Null => Value::Null,
             Constant::Number(n) => Value::Number(*n),
-            Constant::String(s) => Value::String(s.clone()),
+            Constant::String(s) => Value::String(s.as_str().into()),
             Constant::Boolean(b) => Value::Boolean(*b),
         }
     }
 }
 
-type NativeFunction = fn(Vec<Value>) -> Value;
+/// A value as `print` writes it.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&VM::to_string(self))
+    }
+}
+
+/// Type of the errors the VM raises, which exception handlers name to
+/// catch them.
+pub const ERROR_TYPE: &str = "Error";
+
+/// A function implemented by the host, like `print`. It can capture state
+/// of its own, such as a counter in a `Cell` or the sender of a channel.
+pub type NativeFunction = Rc<dyn Fn(&mut VMContext, Vec<Value>) -> Value>;
+
+/// A native that returns a future, which the calling script waits for
+/// without blocking the thread when run with `execute_function_async`.
+pub type AsyncNativeFunction = Rc<dyn Fn(&mut VMContext, Vec<Value>) -> NativeFuture>;
+
+/// A native that captures nothing, as the built-ins do.
+pub type Builtin = fn(&mut VMContext, Vec<Value>) -> Value;
+
+/// Natives a VM can provide without a declaration, by name; only `print`
+/// is there unless `set_builtins` or `VmOptions` say otherwise.
+pub const BUILTINS: &[(&str, Builtin)] = &[
+    ("print", native_print),
+    ("assert", native_assert),
+    ("Date.now", sandbox::native_now),
+    ("Math.random", sandbox::native_random),
+    ("fs.readFile", sandbox::native_read_file),
+    ("fs.writeFile", sandbox::native_write_file),
+    ("process.env", sandbox::native_env),
+    ("process.exit", sandbox::native_exit),
+];
+
+/// Bounds on a run, past which the VM raises an error instead of going on;
+/// `None` leaves a bound off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Most calls in progress at once.
+    pub max_call_depth: Option<usize>,
+    /// Most instructions executed over the VM's life.
+    pub max_instructions: Option<u64>,
+}
 
 pub struct VMContext {
     stack: Vec<Value>,
-    locals: HashMap<String, Value>, // Change from Vec to HashMap for better scoping
-    globals: HashMap<String, Value>,
-    functions: HashMap<String, Function>,
+    /// Value of each global, by its number; `None` until one is stored.
+    globals: Vec<Option<Value>>,
+    /// Number of each global, given as the first function using it loads,
+    /// or the first store to it by name.
+    global_slots: HashMap<String, u32>,
+    /// Name of each global, by its number.
+    global_names: Vec<String>,
+    /// Functions defined and registered, which `Call` instructions index.
+    functions: Vec<Function>,
+    /// Name of each of `functions`, for debug traces to show.
+    function_names: Vec<String>,
+    /// Index in `functions` of the function each name calls.
+    ids: HashMap<String, FuncId>,
+    /// The native function running, or last run.
+    native: FuncId,
+    /// Constants of all the modules loaded, which the functions' `PushConst`
+    /// instructions index.
+    constants: Vec<Constant>,
+    /// `constants` as values, made once so that pushing a string shares
+    /// its text rather than copying it.
+    constant_values: Vec<Value>,
     frames: Vec<CallFrame>,
+    /// Text written by `print`, when captured instead of going to stdout.
+    output: Option<String>,
+    host_objects: host::HostObjects,
 }
 
 #[derive(Clone)]
 enum Function {
-    IR(IRFunction),
+    /// A function of a module, and its code with superinstructions fused,
+    /// both shared with the frames calling it.
+    IR(Rc<IRFunction>, Rc<Code>),
     Native(NativeFunction),
+    AsyncNative(AsyncNativeFunction),
+    /// Declared with `extern function` but not registered as native; only
+    /// native code can call C.
+    Extern,
 }
 
 struct CallFrame {
-    function: IRFunction,
+    function: Rc<IRFunction>,
+    code: Rc<Code>,
     ip: usize,
-    locals: HashMap<String, Value>, // Local variables for this frame
-    stack_base: usize,              // Stack pointer at frame start
+    captures: Captures,               // Empty unless a closure was called
+    locals_base: usize,               // Stack index of the first parameter
+    stack_base: usize,                // Stack index of the first operand
+    position: Option<(usize, usize)>, // Source line and column being executed
+    handled: bool,                    // Whether it or a caller has handlers
+}
+
+/// A function's code as the VM runs it.
+struct Code {
+    /// In the linear form, which `ip` indexes.
+    ops: Vec<Op>,
+    /// Index in `ops` of each label, so that a jump need not look for it.
+    labels: HashMap<String, usize>,
+}
+
+impl Code {
+    fn new(ops: Vec<Op>) -> Self {
+        let labels = ops
+            .iter()
+            .enumerate()
+            .filter_map(|(index, op)| match op {
+                Op::IR(IRInstruction::Label(label)) => Some((label.clone(), index)),
+                _ => None,
+            })
+            .collect();
+        Code { ops, labels }
+    }
+
+    fn label(&self, label: &str) -> Option<usize> {
+        self.labels.get(label).copied()
+    }
 }
 
 impl CallFrame {
-    fn new(function: IRFunction, stack_base: usize) -> Self {
+    /// A frame for `function`, whose parameters are on the stack from
+    /// `locals_base`, with its operands above them.
+    fn new(
+        function: Rc<IRFunction>,
+        code: Rc<Code>,
+        captures: Captures,
+        locals_base: usize,
+    ) -> Self {
         Self {
+            stack_base: locals_base + function.params.len(),
+            code,
             function,
             ip: 0,
-            locals: HashMap::new(),
-            stack_base,
+            captures,
+            locals_base,
+            position: None,
+            handled: false,
+        }
+    }
+
+    /// Stack index of the parameter `name`, if the function has one.
+    fn local_index(&self, name: &str) -> Option<usize> {
+        let index = self
+            .function
+            .params
+            .iter()
+            .position(|param| param == name)?;
+        Some(self.locals_base + index)
+    }
+
+    /// The parameters with their values, for traces.
+    fn locals(&self, stack: &[Value]) -> HashMap<String, Value> {
+        let values = &stack[self.locals_base..self.stack_base];
+        self.function
+            .params
+            .iter()
+            .cloned()
+            .zip(values.iter().cloned())
+            .collect()
+    }
+
+    /// Continue at instruction `ip`, in the statement it belongs to rather
+    /// than the one last executed.
+    fn jump_to(&mut self, ip: usize) {
+        self.ip = ip;
+        if let Some(position) = fuse::position_at(&self.code.ops, ip) {
+            self.position = Some(position);
         }
     }
 }
 
 impl VMContext {
     fn new(module: &IRModule) -> Self {
-        let mut functions = HashMap::new();
+        let mut context = VMContext {
+            stack: Vec::with_capacity(1024),
+            globals: Vec::new(),
+            global_slots: HashMap::new(),
+            global_names: Vec::new(),
+            functions: Vec::new(),
+            function_names: Vec::new(),
+            ids: HashMap::new(),
+            native: 0,
+            constants: Vec::new(),
+            constant_values: Vec::new(),
+            frames: Vec::new(),
+            output: None,
+            host_objects: host::HostObjects::new(),
+        };
 
         // Add built-in functions
-        functions.insert("print".to_string(), Function::Native(native_print));
+        context.insert("print", Function::Native(Rc::new(native_print)));
+
+        context.define(module);
+        context
+    }
+
+    /// The function `name` calls, if any.
+    fn function(&self, name: &str) -> Option<&Function> {
+        self.ids.get(name).map(|&id| &self.functions[id as usize])
+    }
+
+    /// Make `name` call `function`, replacing the function of that name
+    /// in place so that calls by index reach the new one. Returns its index.
+    fn insert(&mut self, name: &str, function: Function) -> FuncId {
+        match self.ids.get(name) {
+            Some(&id) => {
+                self.functions[id as usize] = function;
+                id
+            }
+            None => {
+                let id = self.functions.len() as FuncId;
+                self.functions.push(function);
+                self.function_names.push(name.to_string());
+                self.ids.insert(name.to_string(), id);
+                id
+            }
+        }
+    }
 
-        // Add user-defined functions
-        for func in &module.functions {
-            functions.insert(func.name.clone(), Function::IR(func.clone()));
+    /// Add the module's extern declarations and user-defined functions,
+    /// replacing functions of the same names.
+    fn define(&mut self, module: &IRModule) {
+        for func in &module.externs {
+            // Keep a native implementation registered for it
+            let native = self.function(&func.name);
+            if !matches!(native, Some(Function::Native(_) | Function::AsyncNative(_))) {
+                self.insert(&func.name, Function::Extern);
+            }
         }
 
-        VMContext {
-            stack: Vec::with_capacity(1024),
-            locals: HashMap::new(),
-            globals: HashMap::new(),
-            functions,
-            frames: Vec::new(),
+        // Reserve the functions' indices first, for their calls to use
+        let ids: Vec<FuncId> = module
+            .functions
+            .iter()
+            .map(|func| self.insert(&func.name, Function::Extern))
+            .collect();
+        for (func, &id) in module.functions.iter().zip(&ids) {
+            let mut func = func.clone();
+            func.move_constants(&module.constants, &mut self.constants);
+            func.move_functions(&ids);
+            let params = &func.params;
+            let ops = fuse::fuse(func.instructions(), |name| {
+                match params.iter().position(|param| param == name) {
+                    Some(index) => Var::Local(index as u16),
+                    None => Var::Global(self.global_slot(name)),
+                }
+            });
+            let code = Rc::new(Code::new(ops));
+            self.functions[id as usize] = Function::IR(Rc::new(func), code);
         }
+        let new = &self.constants[self.constant_values.len()..];
+        self.constant_values
+            .extend(new.iter().map(Value::from_constant));
     }
 
     fn push(&mut self, value: Value) {
@@ -87,28 +335,110 @@ impl VMContext {
     }
 
     fn get_local(&self, name: &str) -> Value {
-        // First check current frame's locals
-        if let Some(frame) = self.frames.last() {
-            if let Some(value) = frame.locals.get(name) {
-                return value.clone();
+        // First check current frame's parameters
+        if let Some(index) = self.frames.last().and_then(|frame| frame.local_index(name)) {
+            return self.stack[index].clone();
+        }
+        // Then check globals, and host objects' properties
+        match self.global_slots.get(name) {
+            Some(&slot) => self.get_global(slot),
+            None => self.host_property(name).unwrap_or(Value::Undefined),
+        }
+    }
+
+    /// Number of the global `name`, numbering it if it has none yet.
+    fn global_slot(&mut self, name: &str) -> u32 {
+        if let Some(&slot) = self.global_slots.get(name) {
+            return slot;
+        }
+        let slot = self.globals.len() as u32;
+        self.globals.push(None);
+        self.global_names.push(name.to_string());
+        self.global_slots.insert(name.to_string(), slot);
+        slot
+    }
+
+    fn get_global(&self, slot: u32) -> Value {
+        match &self.globals[slot as usize] {
+            Some(value) => value.clone(),
+            None => {
+                let name = &self.global_names[slot as usize];
+                self.host_property(name).unwrap_or(Value::Undefined)
             }
         }
-        // Then check globals
-        self.globals.get(name).cloned().unwrap_or(Value::Undefined)
+    }
+
+    fn set_global(&mut self, slot: u32, value: Value) {
+        self.globals[slot as usize] = Some(value);
+    }
+
+    /// The closure that a call to `name` in the current frame calls: the
+    /// value of its variable or capture of that name, if it is one.
+    fn closure_named(&self, name: &str) -> Option<(String, Captures)> {
+        let frame = self.frames.last()?;
+        let captured = frame.function.captures.iter().position(|c| c == name);
+        let value = match captured {
+            Some(index) => frame.captures.borrow()[index].clone(),
+            None => self.get_local(name),
+        };
+        match value {
+            Value::Closure { function, captures } => Some((function, captures)),
+            _ => None,
+        }
+    }
+
+    /// Name the native function running was called by, so that one
+    /// function can implement several natives.
+    pub fn native_name(&self) -> &str {
+        &self.function_names[self.native as usize]
+    }
+
+    /// An error at the line being executed, for natives to raise.
+    pub fn error(&self, message: String) -> Diagnostic {
+        let diagnostic = Diagnostic::error(message);
+        match self.frames.last().and_then(|frame| frame.position) {
+            Some((line, _)) => diagnostic.with_label(Span::line(line), ""),
+            None => diagnostic,
+        }
     }
 
     fn set_local(&mut self, name: String, value: Value) {
-        if let Some(frame) = self.frames.last_mut() {
-            // First try to update existing local
-            if frame.locals.contains_key(&name) {
-                frame.locals.insert(name, value);
-            } else {
-                // If not found in current frame, set as global
-                self.globals.insert(name, value);
-            }
-        } else {
-            // No active frame, set as global
-            self.globals.insert(name, value);
+        // First try to update a parameter
+        if let Some(index) = self
+            .frames
+            .last()
+            .and_then(|frame| frame.local_index(&name))
+        {
+            self.stack[index] = value;
+            return;
+        }
+        // If not found in current frame, set as global
+        let slot = self.global_slot(&name);
+        self.set_global(slot, value);
+    }
+
+    /// The current frame's parameter `index`.
+    fn get_param(&self, index: u16) -> Value {
+        let frame = self.frames.last().unwrap();
+        self.stack[frame.locals_base + index as usize].clone()
+    }
+
+    fn set_param(&mut self, index: u16, value: Value) {
+        let frame = self.frames.last().unwrap();
+        self.stack[frame.locals_base + index as usize] = value;
+    }
+
+    fn get_var(&self, var: &Var) -> Value {
+        match var {
+            Var::Local(index) => self.get_param(*index),
+            Var::Global(slot) => self.get_global(*slot),
+        }
+    }
+
+    fn set_var(&mut self, var: Var, value: Value) {
+        match var {
+            Var::Local(index) => self.set_param(index, value),
+            Var::Global(slot) => self.set_global(slot, value),
         }
     }
 }
@@ -116,6 +446,24 @@ impl VMContext {
 pub struct VM {
     context: VMContext,
     debug_trace: Option<DebugTrace>,
+    profile: Option<Profile>,
+    /// Instructions executed so far, returns included.
+    instruction_count: u64,
+    limits: Limits,
+    /// Set when raising an error for going past a limit, which exception
+    /// handlers do not catch.
+    fatal: bool,
+    /// The future of the async native just called, which `run` suspends
+    /// the frames to wait for.
+    pending: Option<NativeFuture>,
+}
+
+/// What running frames came to.
+enum Run {
+    /// The outermost frame returned this value.
+    Returned(Value),
+    /// A frame called an async native, and waits for its future.
+    Suspended(NativeFuture),
 }
 
 impl VM {
@@ -123,79 +471,442 @@ impl VM {
         VM {
             context: VMContext::new(&module),
             debug_trace: None,
+            profile: None,
+            instruction_count: 0,
+            limits: Limits::default(),
+            fatal: false,
+            pending: None,
+        }
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Provide exactly the `BUILTINS` named, taking away the others, but
+    /// leaving functions the program defines of the same names. Names that
+    /// are not built-ins are ignored.
+    pub fn set_builtins(&mut self, names: &[String]) {
+        for (name, function) in BUILTINS {
+            let enabled = names.iter().any(|enabled| enabled == name);
+            match self.context.function(name) {
+                Some(Function::IR(..)) => {}
+                _ if enabled => self.register_native(name, *function),
+                Some(Function::Native(_)) => {
+                    self.context.ids.remove(*name);
+                }
+                _ => {}
+            }
         }
     }
 
     pub fn enable_debugging(&mut self) {
-        self.debug_trace = Some(DebugTrace::new());
+        self.enable_debugging_with(TraceOptions::default());
+    }
+
+    /// Record the instructions `options` selects as the program runs.
+    pub fn enable_debugging_with(&mut self, options: TraceOptions) {
+        self.debug_trace = Some(DebugTrace::with_options(options));
+    }
+
+    /// Write the instructions `options` selects to `out` as they run, as
+    /// `DebugTrace::streaming` does.
+    pub fn stream_debugging_to(&mut self, options: TraceOptions, out: Box<dyn Write>) {
+        self.debug_trace = Some(DebugTrace::streaming(options, out));
+    }
+
+    /// Number of IR instructions executed since the VM was created.
+    pub fn instruction_count(&self) -> u64 {
+        self.instruction_count
+    }
+
+    /// Collect what `print` writes instead of printing it to stdout.
+    pub fn capture_output(&mut self) {
+        self.context.output.get_or_insert_with(String::new);
+    }
+
+    /// Output captured since the last call; empty unless `capture_output`
+    /// was called.
+    pub fn take_output(&mut self) -> String {
+        self.context
+            .output
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Implement `name` with a host function, such as one declared with
+    /// `extern function`: the VM's counterpart of the host functions a Wasm
+    /// module imports.
+    pub fn register_native(
+        &mut self,
+        name: &str,
+        function: impl Fn(&mut VMContext, Vec<Value>) -> Value + 'static,
+    ) {
+        self.context
+            .insert(name, Function::Native(Rc::new(function)));
     }
 
+    /// Implement `name` with a host function returning a future, such as
+    /// one reading a file. The script calling it is suspended until the
+    /// future resolves, and continues with its value; its `HostError` is
+    /// raised at the call.
+    pub fn register_async_native<F>(
+        &mut self,
+        name: &str,
+        function: impl Fn(&mut VMContext, Vec<Value>) -> F + 'static,
+    ) where
+        F: std::future::Future<Output = Result<Value, HostError>> + 'static,
+    {
+        let function: AsyncNativeFunction =
+            Rc::new(move |context, args| Box::pin(function(context, args)));
+        self.context.insert(name, Function::AsyncNative(function));
+    }
+
+    /// Let scripts use `object` as `name`, calling its methods as
+    /// `name.method(...)` and reading its properties as `name.property`.
+    /// Returns the object, for the host to keep using.
+    pub fn register_host_object<T: HostObject + 'static>(
+        &mut self,
+        name: &str,
+        object: T,
+    ) -> Rc<RefCell<T>> {
+        let object = Rc::new(RefCell::new(object));
+        for method in object.borrow().methods() {
+            let (object, method) = (object.clone(), format!("{}.{}", name, method));
+            self.register_native(&method.clone(), move |context, args| {
+                host::call_method(context, &*object, &method, args)
+            });
+        }
+        self.context
+            .host_objects
+            .insert(name.to_string(), object.clone());
+        object
+    }
+
+    /// Add the functions of another module, replacing those of the same
+    /// names, while globals keep their values.
+    pub fn load_module(&mut self, module: IRModule) {
+        self.context.define(&module);
+    }
+
+    /// Drop the call frames and operands a call that panicked left behind,
+    /// so the VM can run again.
+    pub fn reset_stack(&mut self) {
+        self.context.frames.clear();
+        self.context.stack.clear();
+        self.fatal = false;
+        self.pending = None;
+    }
+
+    /// Call `name`, blocking the thread while an async native it calls is
+    /// pending.
     pub fn execute_function(&mut self, name: &str, args: Vec<Value>) -> Value {
-        match self.context.functions.get(name).cloned() {
-            Some(Function::IR(function)) => {
-                let stack_base = self.context.stack.len();
-                let mut frame = CallFrame::new(function, stack_base);
-                let mut return_value = Value::Undefined;
-
-                // Set up parameters as locals
-                for (param, arg) in frame.function.params.iter().zip(args) {
-                    frame.locals.insert(param.clone(), arg);
+        let base = self.start(name, args);
+        loop {
+            match self.run(base) {
+                Run::Returned(value) => return value,
+                Run::Suspended(future) => {
+                    let result = future::block_on(future::CatchUnwind(future));
+                    self.resume(base, result);
                 }
+            }
+        }
+    }
 
-                self.context.frames.push(frame);
+    /// Call `name`, suspending it while an async native it calls is
+    /// pending, so that the executor polling the call can run other tasks.
+    /// A call dropped before it finishes leaves its frames, which
+    /// `reset_stack` drops.
+    pub async fn execute_function_async(&mut self, name: &str, args: Vec<Value>) -> Value {
+        let base = self.start(name, args);
+        loop {
+            match self.run(base) {
+                Run::Returned(value) => return value,
+                Run::Suspended(future) => {
+                    let result = future::CatchUnwind(future).await;
+                    self.resume(base, result);
+                }
+            }
+        }
+    }
 
-                // Execute until frame returns
-                loop {
-                    let current_frame = self.context.frames.last_mut().unwrap();
-                    if current_frame.ip >= current_frame.function.instructions.len() {
-                        let stack_base = current_frame.stack_base;
-                        // Get any value left on the stack as implicit return
-                        if self.context.stack.len() > stack_base {
-                            return_value = self.context.pop();
-                        }
-                        self.context.frames.pop();
-                        self.context.stack.truncate(stack_base);
-                        break;
-                    }
+    /// Start calling `name` with `args`, returning the number of frames
+    /// below its own for `run` to stop at.
+    fn start(&mut self, name: &str, args: Vec<Value>) -> usize {
+        let base = self.context.frames.len();
+        let argc = args.len();
+        self.context.stack.extend(args);
+        self.call_named(name, Captures::default(), argc);
+        base
+    }
 
-                    let instruction = current_frame.function.instructions[current_frame.ip].clone();
-                    current_frame.ip += 1;
-
-                    // Handle explicit returns
-                    if let IRInstruction::Return(has_value) = &instruction {
-                        let stack_base = current_frame.stack_base;
-                        if *has_value {
-                            return_value = self.context.pop();
-                        }
-                        self.context.frames.pop();
-                        self.context.stack.truncate(stack_base);
-                        break;
-                    }
+    /// Call the function `name` with the `argc` values on top of the stack,
+    /// giving a closure's function the values of its captures.
+    fn call_named(&mut self, name: &str, captures: Captures, argc: usize) {
+        match self.context.ids.get(name) {
+            Some(&id) => self.call_function(id, captures, argc),
+            None => {
+                let base = self.context.stack.len() - argc;
+                self.context.stack.truncate(base);
+                self.error(format!("Function {} not found", name)).raise()
+            }
+        }
+    }
 
-                    self.execute_instruction(instruction);
+    /// Call function `id` with the `argc` values on top of the stack as its
+    /// arguments, giving a closure's function the values of its captures.
+    /// They stay where they are as the frame's parameters, with the call's
+    /// operands above them, and are taken off the stack when it returns.
+    ///
+    /// A function of a module gets a frame, which `run` executes; a
+    /// native's result is pushed, and an async native's future kept for
+    /// `run` to wait for.
+    fn call_function(&mut self, id: FuncId, captures: Captures, argc: usize) {
+        let locals_base = self.context.stack.len() - argc;
+        match self.context.functions[id as usize].clone() {
+            Function::IR(function, code) => {
+                if let Some(max) = self.limits.max_call_depth {
+                    if self.context.frames.len() >= max {
+                        self.fatal = true;
+                        self.error(format!("Call depth exceeded the limit of {}", max))
+                            .with_note("this is likely unbounded recursion")
+                            .raise();
+                    }
                 }
-
-                return_value
+                if let Some(debug_trace) = &mut self.debug_trace {
+                    let context = &self.context;
+                    debug_trace.add_function(
+                        &function,
+                        &context.constants,
+                        &context.function_names,
+                    );
+                }
+                // Traces show each instruction as the IR has it
+                let code = match self.debug_trace {
+                    Some(_) => Rc::new(Code::new(fuse::unfused(function.instructions()))),
+                    None => code,
+                };
+                // The arguments are the parameters, dropping extra ones
+                // A parameter without an argument is undefined
+                let params = function.params.len();
+                self.context
+                    .stack
+                    .resize(locals_base + params, Value::Undefined);
+                let handled = !function.exception_table.is_empty()
+                    || self
+                        .context
+                        .frames
+                        .last()
+                        .is_some_and(|frame| frame.handled);
+                let mut frame = CallFrame::new(function, code, captures, locals_base);
+                frame.handled = handled;
+                self.context.frames.push(frame);
+            }
+            Function::Native(func) => {
+                self.context.native = id;
+                let args = self.context.stack.drain(locals_base..).collect();
+                let result = func(&mut self.context, args);
+                self.context.push(result);
+            }
+            Function::AsyncNative(func) => {
+                self.context.native = id;
+                let args = self.context.stack.drain(locals_base..).collect();
+                self.pending = Some(func(&mut self.context, args));
+            }
+            Function::Extern => {
+                let name = &self.context.function_names[id as usize];
+                self.error(format!("The VM cannot call the C function {}", name))
+                    .with_note("only code compiled by a native backend can call C")
+                    .raise()
             }
-            Some(Function::Native(func)) => func(args),
-            None => panic!("Function {} not found", name),
         }
     }
 
-    fn execute_instruction(&mut self, instruction: IRInstruction) {
-        // Record debug info before execution
-        if let Some(debug_trace) = &mut self.debug_trace {
-            if let Some(frame) = self.context.frames.last() {
+    /// Execute the frames above the first `base` until the outermost of
+    /// them returns, or one calls an async native and waits for it.
+    fn run(&mut self, base: usize) -> Run {
+        loop {
+            if let Some(future) = self.pending.take() {
+                return Run::Suspended(future);
+            }
+            if self.context.frames.len() == base {
+                return Run::Returned(self.context.pop());
+            }
+
+            let current_frame = self.context.frames.last_mut().unwrap();
+            if current_frame.ip >= current_frame.code.ops.len() {
+                // Get any value left on the stack as implicit return
+                let value = match self.context.stack.len() > current_frame.stack_base {
+                    true => self.context.pop(),
+                    false => Value::Undefined,
+                };
+                self.return_value(value);
+                continue;
+            }
+
+            let op = current_frame.code.ops[current_frame.ip].clone();
+            let handled = current_frame.handled;
+            current_frame.ip += 1;
+            let before = self.instruction_count;
+            self.instruction_count += op.size();
+            if let Some(max) = self.limits.max_instructions {
+                if self.instruction_count > max {
+                    self.fatal = true;
+                    self.error(format!("Stopped after the limit of {} instructions", max))
+                        .raise();
+                }
+            }
+            if let Some(profile) = &mut self.profile {
+                let interval = profile.interval();
+                if self.instruction_count / interval != before / interval {
+                    let frames = self.context.frames.iter();
+                    profile.sample(frames.map(|frame| frame.function.name.as_str()));
+                }
+            }
+
+            // Record debug info before execution
+            if let (Some(debug_trace), Op::IR(instruction)) = (&mut self.debug_trace, &op) {
+                let frame = self.context.frames.last().unwrap();
                 debug_trace.add_frame(
-                    &instruction,
+                    instruction,
                     &self.context.stack,
-                    &frame.locals,
-                    frame.ip - 1,
-                    &frame.function.name,
+                    &frame.locals(&self.context.stack),
+                    Location {
+                        function_name: &frame.function.name,
+                        ip: frame.ip - 1,
+                        depth: self.context.frames.len(),
+                        position: frame.position,
+                        constants: &self.context.constants,
+                        functions: &self.context.function_names,
+                    },
                 );
             }
+
+            // Handle explicit returns
+            if let Op::IR(IRInstruction::Return(has_value)) = &op {
+                let value = match has_value {
+                    true => self.context.pop(),
+                    false => Value::Undefined,
+                };
+                self.return_value(value);
+                continue;
+            }
+
+            if handled {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| self.execute(op)));
+                if let Err(payload) = result {
+                    self.unwind_to(base, payload);
+                }
+            } else {
+                self.execute(op);
+            }
         }
+    }
+
+    /// Return `value` from the innermost frame, taking its parameters and
+    /// operands off the stack and pushing the value for its caller.
+    fn return_value(&mut self, value: Value) {
+        let frame = self.context.frames.pop().unwrap();
+        self.context.stack.truncate(frame.locals_base);
+        self.context.push(value);
+    }
+
+    /// Continue the frames above the first `base` after the async native
+    /// they wait for finished: with its value as the call's result, or
+    /// raising its error at the call.
+    fn resume(&mut self, base: usize, result: thread::Result<Result<Value, HostError>>) {
+        let payload = match result {
+            Ok(Ok(value)) => return self.context.push(value),
+            Ok(Err(error)) => Box::new(self.error(error.to_string())),
+            Err(payload) => payload,
+        };
+        self.unwind_to(base, payload);
+    }
+
+    /// An error at the line the innermost frame is executing, if known.
+    fn error(&self, message: String) -> Diagnostic {
+        self.context.error(message)
+    }
 
+    /// Handle the error raised with `payload` in a frame above the first
+    /// `base`: continue at the handler of the innermost frame covering the
+    /// instruction it was executing, with the error's message as the only
+    /// operand, or go on unwinding if there is none.
+    fn unwind_to(&mut self, base: usize, payload: Box<dyn Any + Send>) {
+        let frames = &self.context.frames;
+        let handler = (base..frames.len()).rev().find_map(|depth| {
+            let frame = &frames[depth];
+            let ip = frame.ip.checked_sub(1)?;
+            let target = frame.function.exception_table.iter().find_map(|handler| {
+                let start = frame.code.label(&handler.start_label)?;
+                let end = frame.code.label(&handler.end_label)?;
+                let target = frame.code.label(&handler.handler_label)?;
+                let catches = handler.exception_type == ERROR_TYPE && (start..end).contains(&ip);
+                catches.then_some(target)
+            })?;
+            Some((depth, target))
+        });
+        let (depth, target) = match handler {
+            Some(handler) if !self.fatal => handler,
+            _ => panic::resume_unwind(payload),
+        };
+
+        let message = Diagnostic::from_panic(payload).message;
+        self.context.frames.truncate(depth + 1);
+        let frame = self.context.frames.last_mut().unwrap();
+        self.context.stack.truncate(frame.stack_base);
+        self.context.stack.push(Value::String(message.into()));
+        frame.jump_to(target);
+    }
+
+    fn execute(&mut self, op: Op) {
+        match op {
+            Op::IR(instruction) => self.execute_instruction(instruction),
+            Op::LoadLocal(index) => {
+                let value = self.context.get_param(index);
+                self.context.push(value);
+            }
+            Op::StoreLocal(index) => {
+                let value = self.context.pop();
+                self.context.set_param(index, value);
+            }
+            Op::LoadGlobal(slot) => {
+                let value = self.context.get_global(slot);
+                self.context.push(value);
+            }
+            Op::StoreGlobal(slot) => {
+                let value = self.context.pop();
+                self.context.set_global(slot, value);
+            }
+            Op::Update { var, constant, op } => {
+                let left = self.context.get_var(&var);
+                let right = self.context.constant_values[constant as usize].clone();
+                let result = Self::binary(op, left, right);
+                self.context.set_var(var, result);
+            }
+            Op::CompareJump {
+                left,
+                right,
+                op,
+                negated,
+                label,
+            } => {
+                let left = self.context.get_var(&left);
+                let right = self.context.get_var(&right);
+                let result = Self::binary(op, left, right);
+                let condition = match negated {
+                    true => !Self::to_boolean(&result),
+                    false => matches!(result, Value::Boolean(true)),
+                };
+                if condition {
+                    self.jump(&label);
+                }
+            }
+        }
+    }
+
+    fn execute_instruction(&mut self, instruction: IRInstruction) {
         match instruction {
             IRInstruction::Pop => {
                 self.context.pop();
@@ -209,8 +920,9 @@ impl VM {
                     .unwrap_or(Value::Undefined);
                 self.context.push(value);
             }
-            IRInstruction::PushConst(constant) => {
-                self.context.push(Value::from_constant(&constant));
+            IRInstruction::PushConst(index) => {
+                let value = self.context.constant_values[index as usize].clone();
+                self.context.push(value);
             }
             IRInstruction::Load(name) => {
                 let value = self.context.get_local(&name);
@@ -220,37 +932,62 @@ impl VM {
                 let value = self.context.pop();
                 self.context.set_local(name, value);
             }
+            IRInstruction::LoadUpvalue(index) => {
+                let frame = self.context.frames.last().unwrap();
+                let value = frame.captures.borrow().get(index as usize).cloned();
+                self.context.push(value.unwrap_or(Value::Undefined));
+            }
+            IRInstruction::StoreUpvalue(index) => {
+                let value = self.context.pop();
+                let frame = self.context.frames.last().unwrap();
+                if let Some(slot) = frame.captures.borrow_mut().get_mut(index as usize) {
+                    *slot = value;
+                }
+            }
+            IRInstruction::MakeClosure(function, count) => {
+                let base = self.context.stack.len() - count as usize;
+                let captures = self.context.stack.drain(base..).collect();
+                self.context.push(Value::Closure {
+                    function,
+                    captures: Rc::new(RefCell::new(captures)),
+                });
+            }
+            IRInstruction::GetProperty(name) => {
+                let value = match self.context.pop() {
+                    Value::Object(mut properties) => {
+                        properties.remove(&name).unwrap_or(Value::Undefined)
+                    }
+                    object @ (Value::Null | Value::Undefined) => self
+                        .error(format!(
+                            "Cannot read {} of {}",
+                            name,
+                            VM::to_string(&object)
+                        ))
+                        .raise(),
+                    _ => Value::Undefined,
+                };
+                self.context.push(value);
+            }
             IRInstruction::Binary(op) => {
                 let right = self.context.pop();
                 let left = self.context.pop();
-                let result = match op {
-                    BinaryOp::Add => self.binary_add(left, right),
-                    BinaryOp::Sub => self.binary_sub(left, right),
-                    BinaryOp::Mul => self.binary_mul(left, right),
-                    BinaryOp::Div => self.binary_div(left, right),
-                    BinaryOp::Eq => self.binary_eq(left, right),
-                    BinaryOp::Lt => self.binary_lt(left, right),
-                    BinaryOp::Gt => self.binary_gt(left, right),
-                    BinaryOp::And => self.binary_and(left, right),
-                    BinaryOp::Or => self.binary_or(left, right),
-                    BinaryOp::Ge => self.binary_ge(right, left),
-                    BinaryOp::Le => self.binary_le(right, left),
-                };
+                let result = Self::binary(op, left, right);
                 self.context.push(result);
             }
             IRInstruction::Unary(op) => {
                 let operand = self.context.pop();
-                let result = match op {
-                    UnaryOp::Neg => self.unary_neg(operand),
-                    UnaryOp::Not => self.unary_not(operand),
-                };
+                let result = Self::unary(op, operand);
                 self.context.push(result);
             }
-            IRInstruction::Call(name, argc) => {
-                let stack_base = self.context.stack.len() - argc as usize;
-                let args: Vec<Value> = self.context.stack.drain(stack_base..).collect();
-                let result = self.execute_function(&name, args);
-                self.context.push(result);
+            IRInstruction::Call(id, argc) => {
+                self.call_function(id, Captures::default(), argc as usize);
+            }
+            IRInstruction::CallNative(name, argc) => {
+                let (function, captures) = match self.context.closure_named(&name) {
+                    Some(closure) => closure,
+                    None => (name, Captures::default()),
+                };
+                self.call_named(&function, captures, argc as usize);
             }
             IRInstruction::Return(has_value) => {
                 let return_value = if has_value {
@@ -260,78 +997,127 @@ impl VM {
                 };
 
                 if let Some(frame) = self.context.frames.pop() {
-                    self.context.stack.truncate(frame.stack_base);
+                    self.context.stack.truncate(frame.locals_base);
                     if let Some(value) = return_value {
                         self.context.push(value);
                     }
                 }
             }
             IRInstruction::Label(_) => {} // Labels are no-ops in VM
-            IRInstruction::Jump(label) => {
-                if let Some(frame) = self.context.frames.last_mut() {
-                    if let Some(pos) = Self::find_label(&frame.function, &label) {
-                        frame.ip = pos;
-                    }
-                }
+            IRInstruction::Line(line, column) => {
+                self.context.frames.last_mut().unwrap().position = Some((line, column));
             }
+            IRInstruction::Jump(label) => self.jump(&label),
             IRInstruction::JumpIf(label) => {
                 let condition = matches!(self.context.pop(), Value::Boolean(true));
                 if condition {
-                    if let Some(frame) = self.context.frames.last_mut() {
-                        if let Some(pos) = Self::find_label(&frame.function, &label) {
-                            frame.ip = pos;
-                        }
-                    }
+                    self.jump(&label);
                 }
             }
         }
     }
 
+    /// Continue the current frame at `label`.
+    fn jump(&mut self, label: &str) {
+        if let Some(frame) = self.context.frames.last_mut() {
+            if let Some(pos) = frame.code.label(label) {
+                frame.jump_to(pos);
+            }
+        }
+    }
+
+    /// `left op right`, converting operands as JavaScript does; the JIT's
+    /// runtime library calls this too.
+    pub(crate) fn binary(op: BinaryOp, left: Value, right: Value) -> Value {
+        match op {
+            BinaryOp::Add => Self::binary_add(left, right),
+            BinaryOp::Sub => Self::binary_sub(left, right),
+            BinaryOp::Mul => Self::binary_mul(left, right),
+            BinaryOp::Div => Self::binary_div(left, right),
+            BinaryOp::Mod => Self::binary_mod(left, right),
+            BinaryOp::Eq => Self::binary_eq(left, right),
+            BinaryOp::Neq => Self::unary_not(Self::binary_eq(left, right)),
+            BinaryOp::StrictEq => Self::binary_strict_eq(left, right),
+            BinaryOp::Lt => Self::binary_lt(left, right),
+            BinaryOp::Gt => Self::binary_gt(left, right),
+            BinaryOp::And => Self::binary_and(left, right),
+            BinaryOp::Or => Self::binary_or(left, right),
+            BinaryOp::Ge => Self::binary_ge(right, left),
+            BinaryOp::Le => Self::binary_le(right, left),
+        }
+    }
+
     pub fn get_debug_trace(&self) -> Option<&DebugTrace> {
         self.debug_trace.as_ref()
     }
 
+    /// The trace recorded so far, leaving the VM without one.
+    pub fn take_debug_trace(&mut self) -> Option<DebugTrace> {
+        self.debug_trace.take()
+    }
+
+    /// Sample the call stack every `interval` instructions.
+    pub fn enable_profiling(&mut self, interval: u64) {
+        self.profile = Some(Profile::new(interval));
+    }
+
+    /// The samples taken so far, leaving the VM without a profile.
+    pub fn take_profile(&mut self) -> Option<Profile> {
+        self.profile.take()
+    }
+
     // Helper methods for binary operations
-    fn binary_add(&self, left: Value, right: Value) -> Value {
+    fn binary_add(left: Value, right: Value) -> Value {
         match (left, right) {
             (Value::Number(a), Value::Number(b)) => Value::Number(a + b),
-            (Value::String(a), Value::String(b)) => Value::String(a + &b),
-            (Value::String(a), b) => Value::String(format!("{}{}", a, Self::to_string(&b))),
-            (a, Value::String(b)) => Value::String(format!("{}{}", Self::to_string(&a), b)),
-            _ => Value::Undefined,
+            (Value::String(a), Value::String(b)) => Value::String(a.concat(&b)),
+            (Value::String(a), b) => Value::String(a.concat(&Self::to_string(&b).into())),
+            (a, Value::String(b)) => Value::String(JsString::from(Self::to_string(&a)).concat(&b)),
+            // Objects and functions concatenate as their text
+            (a @ (Value::Object(_) | Value::Closure { .. }), b)
+            | (a, b @ (Value::Object(_) | Value::Closure { .. })) => {
+                Value::String(JsString::from(Self::to_string(&a) + &Self::to_string(&b)))
+            }
+            (a, b) => Value::Number(Self::to_number(&a) + Self::to_number(&b)),
         }
     }
 
-    fn binary_sub(&self, left: Value, right: Value) -> Value {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => Value::Number(a - b),
-            _ => Value::Undefined,
-        }
+    fn binary_sub(left: Value, right: Value) -> Value {
+        Value::Number(Self::to_number(&left) - Self::to_number(&right))
     }
 
-    fn binary_mul(&self, left: Value, right: Value) -> Value {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => Value::Number(a * b),
-            _ => Value::Undefined,
-        }
+    fn binary_mul(left: Value, right: Value) -> Value {
+        Value::Number(Self::to_number(&left) * Self::to_number(&right))
     }
 
-    fn binary_div(&self, left: Value, right: Value) -> Value {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => {
-                if b == 0.0 {
-                    Value::Number(f64::NAN)
-                } else {
-                    Value::Number(a / b)
-                }
-            }
-            _ => Value::Undefined,
-        }
+    fn binary_div(left: Value, right: Value) -> Value {
+        // A zero divisor gives an infinity, or NaN for 0 / 0
+        Value::Number(Self::to_number(&left) / Self::to_number(&right))
     }
 
-    fn binary_eq(&self, left: Value, right: Value) -> Value {
+    fn binary_mod(left: Value, right: Value) -> Value {
+        // Rust's `%` on floats truncates like JS, and is NaN for a zero divisor
+        Value::Number(Self::to_number(&left) % Self::to_number(&right))
+    }
+
+    /// Loose equality: null and undefined only equal each other, two
+    /// strings compare by content, and anything else compares as numbers.
+    fn binary_eq(left: Value, right: Value) -> Value {
         Value::Boolean(match (left, right) {
-            (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
+            (Value::Null | Value::Undefined, Value::Null | Value::Undefined) => true,
+            (Value::Null | Value::Undefined, _) | (_, Value::Null | Value::Undefined) => false,
+            // Copies of one string are equal without comparing the text
+            (Value::String(a), Value::String(b)) => a == b,
+            // Objects and functions have no identity to compare
+            (Value::Object(_) | Value::Closure { .. }, _)
+            | (_, Value::Object(_) | Value::Closure { .. }) => false,
+            (a, b) => Self::to_number(&a) == Self::to_number(&b),
+        })
+    }
+
+    fn binary_strict_eq(left: Value, right: Value) -> Value {
+        Value::Boolean(match (left, right) {
+            (Value::Number(a), Value::Number(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Boolean(a), Value::Boolean(b)) => a == b,
             (Value::Null, Value::Null) => true,
@@ -340,39 +1126,38 @@ impl VM {
         })
     }
 
-    fn binary_lt(&self, left: Value, right: Value) -> Value {
+    /// Relational comparison: two strings compare by content, anything
+    /// else as numbers. `None` when a number is NaN.
+    fn compare(left: &Value, right: &Value) -> Option<Ordering> {
         match (left, right) {
-            (Value::Number(a), Value::Number(b)) => Value::Boolean(a < b),
-            (Value::String(a), Value::String(b)) => Value::Boolean(a < b),
-            _ => Value::Undefined,
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (a, b) => Self::to_number(a).partial_cmp(&Self::to_number(b)),
         }
     }
 
-    fn binary_gt(&self, left: Value, right: Value) -> Value {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => Value::Boolean(a > b),
-            (Value::String(a), Value::String(b)) => Value::Boolean(a > b),
-            _ => Value::Undefined,
-        }
+    fn binary_lt(left: Value, right: Value) -> Value {
+        Value::Boolean(Self::compare(&left, &right) == Some(Ordering::Less))
     }
 
-    fn binary_ge(&self, right: Value, left: Value) -> Value {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => Value::Boolean(a >= b),
-            (Value::String(a), Value::String(b)) => Value::Boolean(a >= b),
-            _ => Value::Undefined,
-        }
+    fn binary_gt(left: Value, right: Value) -> Value {
+        Value::Boolean(Self::compare(&left, &right) == Some(Ordering::Greater))
     }
 
-    fn binary_le(&self, right: Value, left: Value) -> Value {
-        match (left, right) {
-            (Value::Number(a), Value::Number(b)) => Value::Boolean(a <= b),
-            (Value::String(a), Value::String(b)) => Value::Boolean(a <= b),
-            _ => Value::Undefined,
-        }
+    fn binary_ge(right: Value, left: Value) -> Value {
+        Value::Boolean(matches!(
+            Self::compare(&left, &right),
+            Some(Ordering::Greater | Ordering::Equal)
+        ))
     }
 
-    fn binary_and(&self, left: Value, right: Value) -> Value {
+    fn binary_le(right: Value, left: Value) -> Value {
+        Value::Boolean(matches!(
+            Self::compare(&left, &right),
+            Some(Ordering::Less | Ordering::Equal)
+        ))
+    }
+
+    fn binary_and(left: Value, right: Value) -> Value {
         // First evaluate left operand
         let left_bool = Self::to_boolean(&left);
         if !left_bool {
@@ -384,7 +1169,7 @@ impl VM {
         }
     }
 
-    fn binary_or(&self, left: Value, right: Value) -> Value {
+    fn binary_or(left: Value, right: Value) -> Value {
         // First evaluate left operand
         let left_bool = Self::to_boolean(&left);
         if left_bool {
@@ -396,84 +1181,172 @@ impl VM {
         }
     }
 
-    fn unary_neg(&self, operand: Value) -> Value {
-        match operand {
-            Value::Number(n) => Value::Number(-n),
-            _ => Value::Undefined,
+    /// `op operand`, as for `binary`.
+    pub(crate) fn unary(op: UnaryOp, operand: Value) -> Value {
+        match op {
+            UnaryOp::Neg => Self::unary_neg(operand),
+            UnaryOp::Not => Self::unary_not(operand),
         }
     }
 
-    fn unary_not(&self, operand: Value) -> Value {
+    fn unary_neg(operand: Value) -> Value {
+        Value::Number(-Self::to_number(&operand))
+    }
+
+    fn unary_not(operand: Value) -> Value {
         Value::Boolean(!Self::to_boolean(&operand))
     }
 
     // Helper methods for type conversion (JavaScript-like behavior)
-    fn to_boolean(value: &Value) -> bool {
+    pub(crate) fn to_boolean(value: &Value) -> bool {
         match value {
             Value::Boolean(b) => *b,
             Value::Number(n) => *n != 0.0 && !n.is_nan(),
             Value::String(s) => !s.is_empty(),
             Value::Null => false,
             Value::Undefined => false,
-            Value::Object(_) => true,
+            Value::Object(_) | Value::Closure { .. } => true,
         }
     }
 
-    fn to_number(value: &Value) -> f64 {
+    pub(crate) fn to_number(value: &Value) -> f64 {
         match value {
             Value::Number(n) => *n,
-            Value::Boolean(true) => 1.0,
-            Value::Boolean(false) => 0.0,
-            Value::String(s) => s.parse().unwrap_or(f64::NAN),
+            Value::String(s) => string_to_number(s),
+            Value::Boolean(b) => *b as u8 as f64,
             Value::Null => 0.0,
-            Value::Undefined => f64::NAN,
-            Value::Object(_) => f64::NAN,
+            Value::Undefined | Value::Object(_) | Value::Closure { .. } => f64::NAN,
         }
     }
 
-    fn to_string(value: &Value) -> String {
+    pub(crate) fn to_string(value: &Value) -> String {
         match value {
-            Value::String(s) => s.clone(),
-            Value::Number(n) => n.to_string(),
+            Value::String(s) => s.to_string(),
+            Value::Number(n) => number_to_string(*n),
             Value::Boolean(b) => b.to_string(),
             Value::Null => "null".to_string(),
             Value::Undefined => "undefined".to_string(),
             Value::Object(_) => "[object Object]".to_string(),
+            Value::Closure { .. } => "[Function]".to_string(),
         }
     }
+}
 
-    fn find_label(function: &IRFunction, label: &str) -> Option<usize> {
-        function
-            .instructions
-            .iter()
-            .position(|inst| matches!(inst, IRInstruction::Label(l) if l == label))
+/// `n` as JavaScript's `Number::toString` writes it, as the runtime library
+/// of compiled code does too: the fewest digits that read back as `n`, in
+/// fixed notation when the decimal exponent is from -7 to 20 and in
+/// exponential notation otherwise.
+pub fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if n == 0.0 {
+        return "0".to_string();
+    }
+    let sign = if n < 0.0 { "-" } else { "" };
+    // Shortest digits as "d.ddde-x"; `n` is 0.digits * 10^point
+    let exponential = format!("{:e}", n.abs());
+    let (mantissa, exponent) = exponential.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let count = digits.len() as i32;
+    let exponent: i32 = exponent.parse().unwrap();
+    let point = exponent + 1;
+    if -6 < point && point <= 0 {
+        format!("{}0.{}{}", sign, "0".repeat(-point as usize), digits)
+    } else if 0 < point && point <= 21 {
+        if point >= count {
+            format!("{}{}{}", sign, digits, "0".repeat((point - count) as usize))
+        } else {
+            let (whole, fraction) = digits.split_at(point as usize);
+            format!("{}{}.{}", sign, whole, fraction)
+        }
+    } else {
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        format!("{}{}{}{}e{:+}", sign, first, point, rest, exponent)
     }
 }
 
+/// The number a string converts to, as in JavaScript and the runtime
+/// library of compiled code: surrounding ASCII whitespace is ignored, an
+/// empty string is 0, and otherwise the rest must be a decimal literal, a
+/// signed or unsigned `Infinity`, or a `0x`, `0o` or `0b` integer, or the
+/// result is NaN.
+pub fn string_to_number(s: &str) -> f64 {
+    let s = s.trim_matches(|c: char| c == ' ' || ('\t'..='\r').contains(&c));
+    if s.is_empty() {
+        return 0.0;
+    }
+    let radix = match s.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 && s.len() > 2 {
+        // Digit by digit, as compiled code does, so that all agree past 2^53
+        return s[2..]
+            .chars()
+            .try_fold(0.0, |value, c| {
+                c.to_digit(radix).map(|d| value * radix as f64 + d as f64)
+            })
+            .unwrap_or(f64::NAN);
+    }
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    if unsigned == "Infinity" {
+        return if s.starts_with('-') {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+    }
+    // Rust also reads "inf" and "NaN", which JavaScript doesn't
+    if !unsigned
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'))
+    {
+        return f64::NAN;
+    }
+    s.parse().unwrap_or(f64::NAN)
+}
+
 // Native function implementations
-fn native_print(args: Vec<Value>) -> Value {
-    for (i, arg) in args.iter().enumerate() {
-        if i > 0 {
-            print!(" ");
-        }
-        match arg {
-            Value::Number(n) => print!("{}", n),
-            Value::String(s) => print!("{}", s),
-            Value::Boolean(b) => print!("{}", b),
-            Value::Null => print!("null"),
-            Value::Undefined => print!("undefined"),
-            Value::Object(_) => print!("[object Object]"),
-        }
+fn native_print(context: &mut VMContext, args: Vec<Value>) -> Value {
+    let mut line = args.iter().map(VM::to_string).collect::<Vec<_>>().join(" ");
+    line.push('\n');
+    match &mut context.output {
+        Some(output) => output.push_str(&line),
+        None => print!("{}", line),
     }
-    println!();
     Value::Undefined
 }
 
+/// `assert(condition, message)`: raise an error unless the condition is
+/// truthy. Only built in when enabled; `test` registers it.
+pub fn native_assert(context: &mut VMContext, args: Vec<Value>) -> Value {
+    if args.first().is_some_and(VM::to_boolean) {
+        return Value::Undefined;
+    }
+    match args.get(1) {
+        Some(message) => context.error(format!("Assertion failed: {}", VM::to_string(message))),
+        None => context.error("Assertion failed".to_string()),
+    }
+    .raise()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lexer::tokenize;
+    use crate::optimizer::OptLevel;
     use crate::parser::parse;
+    use future::block_on;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
 
     fn setup_vm(source: &str) -> VM {
         let tokens = tokenize(source);
@@ -492,6 +1365,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_optimizing_keeps_results() {
+        let source = "function rem() { return -7 % 3; }
+                      function sum() { return 1 + 2 * 3 - -4; }
+                      function not() { return !true; }
+                      function join() { return \"a\" + \"b\" + 1; }";
+        for level in [OptLevel::O1, OptLevel::O2] {
+            let module = crate::ir::lower_ast(parse(tokenize(source)));
+            let mut optimized = VM::new(crate::optimizer::optimize_with(module, level));
+            let mut vm = setup_vm(source);
+            for name in ["rem", "sum", "not", "join"] {
+                assert_eq!(
+                    optimized.execute_function(name, vec![]),
+                    vm.execute_function(name, vec![]),
+                    "{} at {:?}",
+                    name,
+                    level
+                );
+            }
+        }
+        let mut vm = setup_vm(source);
+        assert_eq!(vm.execute_function("rem", vec![]), Value::Number(-1.0));
+    }
+
     #[test]
     fn test_comparison_operations() {
         let mut vm = setup_vm("function test(x, y) { return x > y; }");
@@ -499,6 +1396,44 @@ mod tests {
         assert_eq!(result, Value::Boolean(true));
     }
 
+    #[test]
+    fn test_remainder_and_inequality() {
+        let mut vm = setup_vm(
+            "function rem(x, y) { return x % y; }
+             function ne(x, y) { return x != y; }
+             function strict(x, y) { return x === y; }",
+        );
+        let args = || vec![Value::Number(-7.0), Value::Number(5.0)];
+        assert_eq!(vm.execute_function("rem", args()), Value::Number(-2.0));
+        assert_eq!(vm.execute_function("ne", args()), Value::Boolean(true));
+        assert_eq!(vm.execute_function("strict", args()), Value::Boolean(false));
+    }
+
+    #[test]
+    fn test_strings_share_their_text() {
+        let mut vm = setup_vm(
+            "function hello() { return \"hello\"; }
+             function same(x, y) { return x === y; }
+             function join(x, y) { return x + y; }",
+        );
+        let (Value::String(a), Value::String(b)) = (
+            vm.execute_function("hello", vec![]),
+            vm.execute_function("hello", vec![]),
+        ) else {
+            panic!("hello() is not a string");
+        };
+        assert!(JsString::ptr_eq(&a, &b));
+        let args = vec![Value::String(a), Value::String("hello".into())];
+        assert_eq!(
+            vm.execute_function("same", args.clone()),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            vm.execute_function("join", args),
+            Value::String("hellohello".into())
+        );
+    }
+
     #[test]
     fn test_function_calls() {
         let mut vm = setup_vm(
@@ -531,6 +1466,65 @@ mod tests {
         assert_eq!(result_negative, Value::Boolean(false));
     }
 
+    #[test]
+    fn test_logical_operators_short_circuit() {
+        // `missing` is never called: `&&` stops at a falsy left operand
+        let mut vm = setup_vm(
+            "function main(x) { print(x >= 3 || x <= 1); \
+             return (x || 5) + (1 && 2) + (x && missing()); }",
+        );
+        vm.capture_output();
+        assert_eq!(
+            vm.execute_function("main", vec![Value::Number(0.0)]),
+            Value::Number(7.0)
+        );
+        assert_eq!(vm.take_output(), "true\n");
+    }
+
+    #[test]
+    fn test_while_loops() {
+        // The body runs while the condition holds, and not at all if it
+        // never does
+        let mut vm = setup_vm(
+            "function count(n) { let i = 0;\n\
+             while (i < n) { let i = i + 1; }\n\
+             return i; }",
+        );
+
+        assert_eq!(
+            vm.execute_function("count", vec![Value::Number(3.0)]),
+            Value::Number(3.0)
+        );
+        assert_eq!(
+            vm.execute_function("count", vec![Value::Number(-1.0)]),
+            Value::Number(0.0)
+        );
+    }
+
+    #[test]
+    fn test_for_loops() {
+        let mut vm = setup_vm(
+            "function sum(n) { let total = 0;\n\
+             for (let i = 1; i <= n; let i = i + 1) { let total = total + i; }\n\
+             return total; }\n\
+             function root_over(n) { let i = 0;\n\
+             for (;;) { if (i * i > n) { return i; } let i = i + 1; } }",
+        );
+
+        assert_eq!(
+            vm.execute_function("sum", vec![Value::Number(4.0)]),
+            Value::Number(10.0)
+        );
+        assert_eq!(
+            vm.execute_function("sum", vec![Value::Number(0.0)]),
+            Value::Number(0.0)
+        );
+        assert_eq!(
+            vm.execute_function("root_over", vec![Value::Number(10.0)]),
+            Value::Number(4.0)
+        );
+    }
+
     #[test]
     fn test_variable_scoping() {
         let mut vm = setup_vm(
@@ -544,8 +1538,7 @@ mod tests {
 
         // First set the global variable
         vm.context
-            .globals
-            .insert("global".to_string(), Value::Number(10.0));
+            .set_local("global".to_string(), Value::Number(10.0));
 
         let result = vm.execute_function("test", vec![]);
         match result {
@@ -553,4 +1546,515 @@ mod tests {
             _ => panic!("Expected number result"),
         }
     }
+
+    #[test]
+    fn test_captured_output() {
+        let mut vm = setup_vm("function test() { print(1.5, \"a\", null); print(); }");
+        vm.capture_output();
+        vm.execute_function("test", vec![]);
+        assert_eq!(vm.take_output(), "1.5 a null\n\n");
+        assert_eq!(vm.take_output(), "");
+        // Each of the 11 instructions of `test` once
+        assert_eq!(vm.instruction_count(), 11);
+    }
+
+    #[test]
+    fn test_number_to_string() {
+        let cases = [
+            (1e21, "1e+21"),
+            (1e20, "100000000000000000000"),
+            (2f64.powi(60), "1152921504606847000"),
+            (1e-7, "1e-7"),
+            (1.5e-7, "1.5e-7"),
+            (0.000001, "0.000001"),
+            (-0.0, "0"),
+            (-123.456, "-123.456"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (1.7976931348623157e308, "1.7976931348623157e+308"),
+            (5e-324, "5e-324"),
+            (f64::NAN, "NaN"),
+            (f64::NEG_INFINITY, "-Infinity"),
+        ];
+        for (n, text) in cases {
+            assert_eq!(number_to_string(n), text);
+        }
+    }
+
+    #[test]
+    fn test_string_to_number() {
+        let cases = [
+            ("", 0.0),
+            (" \t\n", 0.0),
+            (" 12 ", 12.0),
+            ("-1.5e3", -1500.0),
+            (".5", 0.5),
+            ("5.", 5.0),
+            ("0x1F", 31.0),
+            ("0b101", 5.0),
+            ("0o17", 15.0),
+            ("+Infinity", f64::INFINITY),
+            ("-Infinity", f64::NEG_INFINITY),
+            ("1e400", f64::INFINITY),
+        ];
+        for (text, n) in cases {
+            assert_eq!(string_to_number(text), n, "{:?}", text);
+        }
+        for text in [
+            "1e", ".", "-0x10", "0x", "inf", "NaN", "1_000", "12px", "--1",
+        ] {
+            assert!(string_to_number(text).is_nan(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_registered_native_function() {
+        let mut vm = setup_vm(
+            "extern function hypot(double, double): double;
+            function test() { return hypot(3, 4) + 1; }",
+        );
+        vm.register_native("hypot", |_, args| match args[..] {
+            [Value::Number(x), Value::Number(y)] => Value::Number(x.hypot(y)),
+            _ => Value::Undefined,
+        });
+        assert_eq!(vm.execute_function("test", vec![]), Value::Number(6.0));
+
+        // One function can serve several names
+        let name = |context: &mut VMContext, _| Value::String(context.native_name().into());
+        vm.register_native("first", name);
+        vm.register_native("second", name);
+        assert_eq!(
+            vm.execute_function("second", vec![]),
+            Value::String("second".into())
+        );
+    }
+
+    #[test]
+    fn test_native_function_captures_state() {
+        let mut vm = setup_vm("function test() { tick(); tick(); return tick(); }");
+        let count = Rc::new(std::cell::Cell::new(0.0));
+        let ticks = count.clone();
+        vm.register_native("tick", move |_, _| {
+            ticks.set(ticks.get() + 1.0);
+            Value::Number(ticks.get())
+        });
+        assert_eq!(vm.execute_function("test", vec![]), Value::Number(3.0));
+        assert_eq!(count.get(), 3.0);
+    }
+
+    #[test]
+    fn test_async_native_suspends_the_script() {
+        let mut vm = setup_vm("function test(x) { return read(x) + read(x + 1); }");
+        let slot: Rc<RefCell<Option<Value>>> = Rc::default();
+        let reads = slot.clone();
+        vm.register_async_native("read", move |_, _| {
+            let reads = reads.clone();
+            std::future::poll_fn(move |_| match reads.borrow_mut().take() {
+                Some(value) => Poll::Ready(Ok(value)),
+                None => Poll::Pending,
+            })
+        });
+
+        let mut context = Context::from_waker(Waker::noop());
+        let mut call = pin!(vm.execute_function_async("test", vec![Value::Number(1.0)]));
+        assert!(call.as_mut().poll(&mut context).is_pending());
+        *slot.borrow_mut() = Some(Value::Number(2.0));
+        assert!(call.as_mut().poll(&mut context).is_pending());
+        *slot.borrow_mut() = Some(Value::Number(3.0));
+        assert_eq!(
+            call.as_mut().poll(&mut context),
+            Poll::Ready(Value::Number(5.0))
+        );
+    }
+
+    #[test]
+    fn test_async_native_blocks_a_call_that_waits() {
+        let mut vm = setup_vm("function test(x) { return double(x) + 1; }");
+        vm.register_async_native("double", |_, args| {
+            let n = match args[0] {
+                Value::Number(n) => n,
+                _ => 0.0,
+            };
+            // Another thread computes the result, waking the call when done
+            let result = std::sync::Arc::new(std::sync::Mutex::new(None));
+            let mut started = false;
+            std::future::poll_fn(move |context| {
+                if let Some(n) = result.lock().unwrap().take() {
+                    return Poll::Ready(Ok(Value::Number(n)));
+                }
+                if !std::mem::replace(&mut started, true) {
+                    let (result, waker) = (result.clone(), context.waker().clone());
+                    std::thread::spawn(move || {
+                        std::thread::sleep(std::time::Duration::from_millis(10));
+                        *result.lock().unwrap() = Some(n * 2.0);
+                        waker.wake();
+                    });
+                }
+                Poll::Pending
+            })
+        });
+        assert_eq!(
+            vm.execute_function("test", vec![Value::Number(4.0)]),
+            Value::Number(9.0)
+        );
+        assert!(vm.context.frames.is_empty() && vm.context.stack.is_empty());
+    }
+
+    #[test]
+    fn test_async_native_errors_are_raised_at_the_call() {
+        let module = crate::ir::parse_text(
+            "function guarded() {\n\
+               L1:\n\
+               \x20   call fail/0\n\
+               \x20   return value\n\
+               L2:\n\
+               \x20   return value\n\
+               \x20   try L1 L2 catch L2 Error\n\
+             }\n\
+             function unguarded() {\n\
+               \x20   line 3:5\n\
+               \x20   call fail/0\n\
+               \x20   return value\n\
+             }\n",
+        )
+        .unwrap();
+        let mut vm = VM::new(module);
+        vm.register_async_native("fail", |_, _| async {
+            Err(HostError::from("no such file"))
+        });
+        assert_eq!(
+            block_on(vm.execute_function_async("guarded", vec![])),
+            Value::String("no such file".into())
+        );
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vm.execute_function("unguarded", vec![])
+        }))
+        .unwrap_err();
+        assert_eq!(
+            Diagnostic::from_panic(payload).to_string(),
+            "3: no such file"
+        );
+    }
+
+    #[test]
+    fn test_properties() {
+        let mut vm = setup_vm("function width(size) {\n    return size.width;\n}");
+        let size = HashMap::from([("width".to_string(), Value::Number(3.0))]);
+        assert_eq!(
+            vm.execute_function("width", vec![Value::Object(size)]),
+            Value::Number(3.0)
+        );
+        assert_eq!(
+            vm.execute_function("width", vec![Value::Number(3.0)]),
+            Value::Undefined
+        );
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vm.execute_function("width", vec![])
+        }))
+        .unwrap_err();
+        assert_eq!(
+            Diagnostic::from_panic(payload).to_string(),
+            "2: Cannot read width of undefined"
+        );
+    }
+
+    #[test]
+    fn test_assert() {
+        let mut vm = setup_vm("function test(x) {\n    assert(x > 1, \"x is \" + x);\n}");
+        vm.register_native("assert", native_assert);
+        vm.execute_function("test", vec![Value::Number(2.0)]);
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vm.execute_function("test", vec![Value::Number(1.0)])
+        }))
+        .unwrap_err();
+        assert_eq!(
+            Diagnostic::from_panic(payload).to_string(),
+            "2: Assertion failed: x is 1"
+        );
+    }
+
+    #[test]
+    fn test_error_after_jump_is_at_its_statement() {
+        let mut vm = setup_vm(
+            "function test() {\n    let i = 0;\n    while (!assert(i < 1, \"i is \" + i)) {\n        let i = i + 1;\n    }\n}",
+        );
+        vm.register_native("assert", native_assert);
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vm.execute_function("test", vec![])
+        }))
+        .unwrap_err();
+        // The condition fails on the way round, after the loop's body ran
+        assert_eq!(
+            Diagnostic::from_panic(payload).to_string(),
+            "3: Assertion failed: i is 1"
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let error = |vm: &mut VM, name: &str| {
+            let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                vm.execute_function(name, vec![Value::Number(0.0)])
+            }))
+            .unwrap_err();
+            vm.reset_stack();
+            Diagnostic::from_panic(payload).message
+        };
+        let mut vm = setup_vm("function down(n) { return down(n + 1); }");
+        vm.set_limits(Limits {
+            max_call_depth: Some(50),
+            max_instructions: None,
+        });
+        assert_eq!(
+            error(&mut vm, "down"),
+            "Call depth exceeded the limit of 50"
+        );
+
+        let mut vm = setup_vm("function sum(n) { return n + 1 + 2; }");
+        vm.set_limits(Limits {
+            max_call_depth: None,
+            max_instructions: Some(3),
+        });
+        assert_eq!(
+            error(&mut vm, "sum"),
+            "Stopped after the limit of 3 instructions"
+        );
+    }
+
+    #[test]
+    fn test_fused_instructions_run_as_their_parts() {
+        let source = "function count(n) { let total = 0;\n\
+                      for (let i = 0; i < n; let i = i + 1) { let total = total + i; }\n\
+                      return total * 10; }";
+        let mut fused = setup_vm(source);
+        let mut traced = setup_vm(source);
+        traced.enable_debugging();
+        for vm in [&mut fused, &mut traced] {
+            assert_eq!(
+                vm.execute_function("count", vec![Value::Number(4.0)]),
+                Value::Number(60.0)
+            );
+        }
+        assert_eq!(fused.instruction_count(), traced.instruction_count());
+        let steps = traced.get_debug_trace().unwrap().frames.len() as u64;
+        assert_eq!(steps, traced.instruction_count());
+    }
+
+    #[test]
+    fn test_labels_are_found_at_load() {
+        // Fused instructions come before some of the labels
+        let mut vm = setup_vm("function f(i, n) { for (; i < n; let i = i + 1) {} return i; }");
+        let Some(Function::IR(function, code)) = vm.context.function("f") else {
+            panic!("f is not defined");
+        };
+        assert!(code.ops.len() < function.instructions().len());
+        assert_eq!(code.labels.len(), 2);
+        for (label, &index) in &code.labels {
+            assert!(matches!(&code.ops[index], Op::IR(IRInstruction::Label(l)) if l == label));
+        }
+        // Jumps to them leave the loop once its condition is false
+        assert_eq!(
+            vm.execute_function("f", vec![Value::Number(1.0), Value::Number(4.0)]),
+            Value::Number(4.0)
+        );
+        assert_eq!(
+            vm.execute_function("f", vec![Value::Number(5.0), Value::Number(4.0)]),
+            Value::Number(5.0)
+        );
+    }
+
+    #[test]
+    fn test_calls_take_their_arguments_off_the_stack() {
+        let mut vm = setup_vm(
+            "function first(a) { return a; }\n\
+             function main() { print(first(1, 2, 3)); return first(4) + first(5, 6); }",
+        );
+        vm.capture_output();
+        assert_eq!(vm.execute_function("main", vec![]), Value::Number(9.0));
+        assert_eq!(vm.take_output(), "1\n");
+        assert!(vm.context.stack.is_empty());
+        assert_eq!(
+            vm.execute_function("first", vec![Value::Number(7.0), Value::Null]),
+            Value::Number(7.0)
+        );
+        assert!(vm.context.stack.is_empty());
+    }
+
+    #[test]
+    fn test_parameters_live_on_the_stack() {
+        // Stores to a parameter, fused or not, change only its frame's
+        let mut vm = setup_vm(
+            "function bump(n, unused) { let n = n + 1; let n = n * 2; return n; }\n\
+             function main(n) { return bump(n) + bump(n, 1, 2) + n; }",
+        );
+        let Some(Function::IR(_, code)) = vm.context.function("bump") else {
+            panic!("bump is not defined");
+        };
+        assert!(code.ops.iter().any(|op| matches!(op, Op::StoreLocal(0))));
+        assert_eq!(
+            vm.execute_function("main", vec![Value::Number(1.0)]),
+            Value::Number(9.0)
+        );
+        assert!(vm.context.stack.is_empty());
+    }
+
+    #[test]
+    fn test_globals_are_numbered_at_load() {
+        let mut vm = setup_vm(
+            "function set() { let g = 1; let x = 2; }\n\
+             function get() { return g; }\n\
+             function param(x) { return x; }",
+        );
+        assert_eq!(vm.context.global_names, ["g", "x"]);
+        assert_eq!(vm.execute_function("get", vec![]), Value::Undefined);
+        vm.execute_function("set", vec![]);
+        assert_eq!(vm.execute_function("get", vec![]), Value::Number(1.0));
+        // A parameter is not the global of its name, even without an argument
+        assert_eq!(vm.execute_function("param", vec![]), Value::Undefined);
+    }
+
+    #[test]
+    fn test_closures() {
+        let mut vm = setup_vm(
+            "function scale(k) {\n\
+             function by(x) { return x * k; }\n\
+             return by(2) + by(3);\n\
+             }",
+        );
+        assert_eq!(
+            vm.execute_function("scale", vec![Value::Number(10.0)]),
+            Value::Number(50.0)
+        );
+
+        // The calls of a closure share its captures
+        let module = crate::ir::parse_text(
+            "function counter(start) {\n\
+             \x20   load start\n\
+             \x20   closure counter.next/1\n\
+             \x20   return value\n\
+             }\n\
+             function counter.next() captures=count {\n\
+             \x20   load_upvalue 0\n\
+             \x20   push 1\n\
+             \x20   add\n\
+             \x20   dup\n\
+             \x20   store_upvalue 0\n\
+             \x20   return value\n\
+             }\n\
+             function main() {\n\
+             \x20   push 5\n\
+             \x20   call counter/1\n\
+             \x20   store next\n\
+             \x20   call next/0\n\
+             \x20   pop\n\
+             \x20   call next/0\n\
+             \x20   return value\n\
+             }\n",
+        )
+        .unwrap();
+        assert_eq!(crate::ir::verify(&module), Ok(()));
+        let mut vm = VM::new(module);
+        assert_eq!(vm.execute_function("main", vec![]), Value::Number(7.0));
+    }
+
+    #[test]
+    fn test_exception_handlers() {
+        let module = crate::ir::parse_text(
+            "function fail() {\n\
+               \x20   call missing/0\n\
+               \x20   return value\n\
+             }\n\
+             function guarded() {\n\
+               L1:\n\
+               \x20   call fail/0\n\
+               \x20   return value\n\
+               L2:\n\
+               \x20   store error\n\
+               \x20   push \"caught: \"\n\
+               \x20   load error\n\
+               \x20   add\n\
+               \x20   return value\n\
+               \x20   try L1 L2 catch L2 Error\n\
+             }\n\
+             function other() {\n\
+               L1:\n\
+               \x20   call fail/0\n\
+               \x20   return value\n\
+               L2:\n\
+               \x20   return value\n\
+               \x20   try L1 L2 catch L2 TypeError\n\
+             }\n\
+             function down(n) {\n\
+               L1:\n\
+               \x20   load n\n\
+               \x20   call down/1\n\
+               \x20   return value\n\
+               L2:\n\
+               \x20   return value\n\
+               \x20   try L1 L2 catch L2 Error\n\
+             }\n",
+        )
+        .unwrap();
+        assert_eq!(crate::ir::verify(&module), Ok(()));
+        // Handlers are only reached by errors, which optimizing must allow for
+        let module = crate::optimizer::optimize_with(module, crate::optimizer::OptLevel::O2);
+        let mut vm = VM::new(module);
+        assert_eq!(
+            vm.execute_function("guarded", vec![]),
+            Value::String("caught: Function missing not found".into())
+        );
+        assert!(vm.context.frames.is_empty() && vm.context.stack.is_empty());
+
+        let error = |vm: &mut VM, name: &str| {
+            let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                vm.execute_function(name, vec![Value::Number(0.0)])
+            }))
+            .unwrap_err();
+            vm.reset_stack();
+            Diagnostic::from_panic(payload).message
+        };
+        // A handler for another type of error, or past a limit, lets it through
+        assert_eq!(error(&mut vm, "other"), "Function missing not found");
+        vm.set_limits(Limits {
+            max_call_depth: Some(20),
+            max_instructions: None,
+        });
+        assert_eq!(
+            error(&mut vm, "down"),
+            "Call depth exceeded the limit of 20"
+        );
+    }
+
+    #[test]
+    fn test_builtins() {
+        let mut vm = setup_vm("function test() { assert(true); }");
+        vm.set_builtins(&["assert".to_string()]);
+        vm.execute_function("test", vec![]);
+        assert!(!vm.context.ids.contains_key("print"));
+    }
+
+    #[test]
+    fn test_loaded_module_sees_globals() {
+        let mut vm = setup_vm(
+            "function first() { let total = 40; return total; }
+             function third() { return first(); }",
+        );
+        vm.execute_function("first", vec![]);
+        let module = crate::ir::lower_ast(parse(tokenize(
+            "function first() { return 0; } function second() { return total + 2; }",
+        )));
+        vm.load_module(module);
+        assert_eq!(vm.execute_function("second", vec![]), Value::Number(42.0));
+        assert_eq!(vm.execute_function("first", vec![]), Value::Number(0.0));
+        // Calls by index reach the function that replaced their callee
+        assert_eq!(vm.execute_function("third", vec![]), Value::Number(0.0));
+    }
+
+    #[test]
+    fn test_exit_status() {
+        assert_eq!(Value::Number(3.9).exit_status(), 3);
+        assert_eq!(Value::Number(300.0).exit_status(), 44);
+        assert_eq!(Value::Number(-1.0).exit_status(), -1);
+        assert_eq!(Value::Number(f64::NAN).exit_status(), 0);
+        assert_eq!(Value::String("1".into()).exit_status(), 0);
+    }
 }
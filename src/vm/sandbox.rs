@@ -0,0 +1,293 @@
+//! Which built-ins a VM installs, by group. A script can only reach the
+//! system through natives, so a VM made with `VM::with_options` from a
+//! `VmOptions` that leaves out a group cannot touch what the group covers,
+//! and `VmOptions::builtins` lists the whole surface it is given:
+//!
+//! ```
+//! use js_compiler::vm::{Capability, VmOptions};
+//!
+//! let options = VmOptions::none().allow(Capability::Time);
+//! assert_eq!(options.builtins(), ["Date.now"]);
+//! ```
+
+use super::{Limits, VMContext, Value, VM};
+use crate::ir;
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A group of built-ins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Capability {
+    /// `print`, to stdout or the captured output.
+    Printing,
+    /// `Date.now()`, milliseconds since the Unix epoch.
+    Time,
+    /// `Math.random()`, a number from 0 up to 1.
+    Randomness,
+    /// `fs.readFile(path)` and `fs.writeFile(path, text)`.
+    Filesystem,
+    /// `process.env(name)` and `process.exit(status)`.
+    Process,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 5] = [
+        Capability::Printing,
+        Capability::Time,
+        Capability::Randomness,
+        Capability::Filesystem,
+        Capability::Process,
+    ];
+
+    /// Names of the group's built-ins, out of `BUILTINS`.
+    pub fn builtins(self) -> &'static [&'static str] {
+        match self {
+            Capability::Printing => &["print"],
+            Capability::Time => &["Date.now"],
+            Capability::Randomness => &["Math.random"],
+            Capability::Filesystem => &["fs.readFile", "fs.writeFile"],
+            Capability::Process => &["process.env", "process.exit"],
+        }
+    }
+}
+
+/// How to set up a new VM: the built-in groups it gets and the limits it
+/// runs within. The default is what `VM::new` gives, printing only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmOptions {
+    /// Sorted, without repeats.
+    capabilities: Vec<Capability>,
+    pub limits: Limits,
+}
+
+impl Default for VmOptions {
+    fn default() -> Self {
+        VmOptions::none().allow(Capability::Printing)
+    }
+}
+
+impl VmOptions {
+    /// No built-ins at all, for untrusted scripts to be given only what
+    /// they need.
+    pub fn none() -> Self {
+        VmOptions {
+            capabilities: Vec::new(),
+            limits: Limits::default(),
+        }
+    }
+
+    /// Every built-in group.
+    pub fn all() -> Self {
+        Capability::ALL
+            .into_iter()
+            .fold(VmOptions::none(), VmOptions::allow)
+    }
+
+    pub fn allow(mut self, capability: Capability) -> Self {
+        if let Err(index) = self.capabilities.binary_search(&capability) {
+            self.capabilities.insert(index, capability);
+        }
+        self
+    }
+
+    pub fn deny(mut self, capability: Capability) -> Self {
+        self.capabilities.retain(|&allowed| allowed != capability);
+        self
+    }
+
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn capabilities(&self) -> &[Capability] {
+        &self.capabilities
+    }
+
+    /// Names of the built-ins a VM made with the options gets.
+    pub fn builtins(&self) -> Vec<&'static str> {
+        self.capabilities
+            .iter()
+            .flat_map(|capability| capability.builtins())
+            .copied()
+            .collect()
+    }
+}
+
+impl VM {
+    /// A VM for `module` with the built-ins and limits of `options`.
+    pub fn with_options(module: ir::IRModule, options: &VmOptions) -> Self {
+        let mut vm = VM::new(module);
+        let builtins: Vec<String> = options
+            .builtins()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        vm.set_builtins(&builtins);
+        vm.set_limits(options.limits);
+        vm
+    }
+}
+
+pub(super) fn native_now(_: &mut VMContext, _: Vec<Value>) -> Value {
+    let since = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Value::Number(since.as_millis() as f64)
+}
+
+thread_local! {
+    /// State of the xorshift generator behind `Math.random`, seeded from
+    /// the randomness of `RandomState`.
+    static SEED: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+}
+
+pub(super) fn native_random(_: &mut VMContext, _: Vec<Value>) -> Value {
+    let bits = SEED.with(|seed| {
+        let mut x = seed.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        seed.set(x);
+        x
+    });
+    // The top 53 bits, as a fraction
+    Value::Number((bits >> 11) as f64 / (1u64 << 53) as f64)
+}
+
+/// The string argument `index` of the native `name`, raising an error if
+/// it is not one.
+fn string_arg(context: &VMContext, name: &str, args: &[Value], index: usize) -> String {
+    match args.get(index) {
+        Some(Value::String(s)) => s.to_string(),
+        _ => context
+            .error(format!("{} takes a string as argument {}", name, index + 1))
+            .raise(),
+    }
+}
+
+pub(super) fn native_read_file(context: &mut VMContext, args: Vec<Value>) -> Value {
+    let path = string_arg(context, "fs.readFile", &args, 0);
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Value::String(text.into()),
+        Err(error) => context
+            .error(format!("Cannot read {}: {}", path, error))
+            .raise(),
+    }
+}
+
+pub(super) fn native_write_file(context: &mut VMContext, args: Vec<Value>) -> Value {
+    let path = string_arg(context, "fs.writeFile", &args, 0);
+    let text = args.get(1).map(VM::to_string).unwrap_or_default();
+    match std::fs::write(&path, text) {
+        Ok(()) => Value::Undefined,
+        Err(error) => context
+            .error(format!("Cannot write {}: {}", path, error))
+            .raise(),
+    }
+}
+
+/// `process.env(name)`: the environment variable, or undefined if unset.
+pub(super) fn native_env(context: &mut VMContext, args: Vec<Value>) -> Value {
+    let name = string_arg(context, "process.env", &args, 0);
+    std::env::var(name).map_or(Value::Undefined, |value| Value::String(value.into()))
+}
+
+/// `process.exit(status)`: end the process, as `main` returning the status
+/// would.
+pub(super) fn native_exit(_: &mut VMContext, args: Vec<Value>) -> Value {
+    let status = args.first().map_or(0, Value::exit_status);
+    std::process::exit(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::Diagnostic;
+    use crate::{lexer, parser};
+    use std::panic::{self, AssertUnwindSafe};
+
+    fn vm(source: &str, options: &VmOptions) -> VM {
+        VM::with_options(
+            ir::lower_ast(parser::parse(lexer::tokenize(source))),
+            options,
+        )
+    }
+
+    fn error(vm: &mut VM, function: &str) -> String {
+        let result =
+            panic::catch_unwind(AssertUnwindSafe(|| vm.execute_function(function, vec![])));
+        vm.reset_stack();
+        Diagnostic::from_panic(result.unwrap_err()).message
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let source = "function hello() { print(\"hi\"); }\n\
+                      function now() { return Date.now(); }\n\
+                      function random() { return Math.random(); }";
+        let mut sandboxed = vm(source, &VmOptions::none());
+        assert_eq!(error(&mut sandboxed, "hello"), "Function print not found");
+        assert_eq!(error(&mut sandboxed, "now"), "Function Date.now not found");
+
+        let options = VmOptions::default()
+            .allow(Capability::Randomness)
+            .allow(Capability::Time)
+            .allow(Capability::Time);
+        assert_eq!(
+            options.capabilities(),
+            [
+                Capability::Printing,
+                Capability::Time,
+                Capability::Randomness
+            ]
+        );
+        let mut vm = vm(source, &options);
+        vm.capture_output();
+        vm.execute_function("hello", vec![]);
+        assert_eq!(vm.take_output(), "hi\n");
+        assert!(matches!(vm.execute_function("now", vec![]), Value::Number(ms) if ms > 1.6e12));
+        for _ in 0..100 {
+            let Value::Number(n) = vm.execute_function("random", vec![]) else {
+                panic!("Math.random() is not a number");
+            };
+            assert!((0.0..1.0).contains(&n));
+        }
+
+        assert_eq!(
+            VmOptions::all().deny(Capability::Process).builtins(),
+            [
+                "print",
+                "Date.now",
+                "Math.random",
+                "fs.readFile",
+                "fs.writeFile"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filesystem() {
+        let path = std::env::temp_dir().join(format!("sandbox-{}.txt", std::process::id()));
+        let source = format!(
+            "function copy() {{ fs.writeFile({path:?}, \"saved \" + 42); return fs.readFile({path:?}); }}\n\
+             function missing() {{ return fs.readFile({path:?} + \".missing\"); }}\n\
+             function bad() {{ return fs.readFile(1); }}",
+            path = path.to_str().unwrap()
+        );
+        let options = VmOptions::none().allow(Capability::Filesystem);
+        let mut vm = vm(&source, &options);
+        assert_eq!(
+            vm.execute_function("copy", vec![]),
+            Value::String("saved 42".into())
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert!(error(&mut vm, "missing").starts_with("Cannot read "));
+        assert_eq!(
+            error(&mut vm, "bad"),
+            "fs.readFile takes a string as argument 1"
+        );
+    }
+}
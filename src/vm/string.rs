@@ -0,0 +1,185 @@
+//! The text of string values. `+` on two strings makes a rope: a node
+//! holding both, rather than a copy of their text. The text is put
+//! together the first time it is read, and kept, so a loop adding a piece
+//! to a string each time takes time linear in its length where copying
+//! the string so far each time would be quadratic.
+
+use std::cell::{OnceCell, RefCell};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// Joins shorter than this are copied: a rope costs more than a few bytes.
+const MIN_ROPE_LEN: usize = 64;
+
+/// Text shared between the values holding it, so copying a value does not
+/// copy it. It reads as a `str`.
+#[derive(Clone)]
+pub struct JsString(Rc<Node>);
+
+struct Node {
+    /// Length in bytes.
+    len: usize,
+    /// The text, from the start or once the parts are put together.
+    text: OnceCell<Rc<str>>,
+    /// The strings joined, until their text is put together.
+    parts: RefCell<Option<(JsString, JsString)>>,
+}
+
+impl JsString {
+    /// The string followed by `other`.
+    pub fn concat(&self, other: &JsString) -> JsString {
+        let len = self.len() + other.len();
+        if len < MIN_ROPE_LEN {
+            let mut text = String::with_capacity(len);
+            text.push_str(self);
+            text.push_str(other);
+            return text.into();
+        }
+        JsString(Rc::new(Node {
+            len,
+            text: OnceCell::new(),
+            parts: RefCell::new(Some((self.clone(), other.clone()))),
+        }))
+    }
+
+    /// Length in bytes, which does not need the text put together.
+    pub fn len(&self) -> usize {
+        self.0.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.text.get_or_init(|| self.flatten())
+    }
+
+    /// Whether the two are the same string, not only equal text.
+    pub fn ptr_eq(a: &JsString, b: &JsString) -> bool {
+        Rc::ptr_eq(&a.0, &b.0)
+    }
+
+    /// The text of the parts, put together, after which the node lets
+    /// them go. Parts are walked with a stack of their own, since a string
+    /// built a piece at a time is a rope as deep as it has pieces.
+    fn flatten(&self) -> Rc<str> {
+        let mut text = String::with_capacity(self.len());
+        let (left, right) = self.0.parts.borrow_mut().take().expect("a rope has parts");
+        let mut pending = vec![right, left];
+        while let Some(part) = pending.pop() {
+            if let Some(part_text) = part.0.text.get() {
+                text.push_str(part_text);
+                continue;
+            }
+            let parts = part.0.parts.borrow();
+            let (left, right) = parts.as_ref().expect("a rope has parts");
+            pending.push(right.clone());
+            pending.push(left.clone());
+        }
+        text.into()
+    }
+}
+
+impl Drop for Node {
+    /// Drop the parts one node at a time rather than recursing, for the
+    /// same reason `flatten` does not.
+    fn drop(&mut self) {
+        let mut pending: Vec<JsString> = self
+            .parts
+            .get_mut()
+            .take()
+            .into_iter()
+            .flat_map(|(left, right)| [left, right])
+            .collect();
+        while let Some(part) = pending.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(part.0) {
+                if let Some((left, right)) = node.parts.get_mut().take() {
+                    pending.push(left);
+                    pending.push(right);
+                }
+            }
+        }
+    }
+}
+
+impl From<Rc<str>> for JsString {
+    fn from(text: Rc<str>) -> Self {
+        JsString(Rc::new(Node {
+            len: text.len(),
+            text: OnceCell::from(text),
+            parts: RefCell::new(None),
+        }))
+    }
+}
+
+impl From<&str> for JsString {
+    fn from(text: &str) -> Self {
+        Rc::<str>::from(text).into()
+    }
+}
+
+impl From<String> for JsString {
+    fn from(text: String) -> Self {
+        Rc::<str>::from(text).into()
+    }
+}
+
+impl Deref for JsString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for JsString {
+    fn eq(&self, other: &JsString) -> bool {
+        JsString::ptr_eq(self, other)
+            || (self.len() == other.len() && self.as_str() == other.as_str())
+    }
+}
+
+impl PartialOrd for JsString {
+    fn partial_cmp(&self, other: &JsString) -> Option<Ordering> {
+        Some(self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl fmt::Debug for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_joins_make_ropes_read_as_text() {
+        let piece = JsString::from("x".repeat(40));
+        let short = JsString::from("ab").concat(&"cd".into());
+        assert!(short.0.text.get().is_some());
+        assert_eq!(short, JsString::from("abcd"));
+
+        let mut built = JsString::from("");
+        for _ in 0..100_000 {
+            built = built.concat(&piece);
+        }
+        assert_eq!(built.len(), 4_000_000);
+        assert!(built.0.text.get().is_none());
+        assert!(built.bytes().all(|b| b == b'x'));
+        assert!(built.0.parts.borrow().is_none());
+        assert!(JsString::from("a") < JsString::from("b"));
+        assert_eq!(format!("{:?}", short), "\"abcd\"");
+    }
+}
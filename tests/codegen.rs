@@ -0,0 +1,336 @@
+//! Golden-file tests for the code generators.
+//!
+//! Every program in `tests/corpus` is compiled for each backend and the
+//! output compared with its snapshot in `tests/golden`. Run with
+//! `UPDATE_GOLDEN=1` to rewrite the snapshots after an intended change.
+//!
+//! Where a toolchain or emulator is installed, the compiled program is also
+//! run and what it prints compared with what the VM prints:
+//!
+//! - x64: `cc` (or `$CC`) on an x86_64 Unix host
+//! - ARM64: `cc` on an ARM64 Linux host, otherwise `aarch64-linux-gnu-gcc`
+//!   and `qemu-aarch64`
+//! - Wasm: `wasmtime`, otherwise `node` and its WASI module
+//! - LLVM IR: `clang`
+//!
+//! Backends without a runner are only checked against their snapshots.
+//! On an x86_64 or ARM64 Unix host the JIT runs every program as well.
+#![cfg(all(feature = "x64", feature = "arm64", feature = "wasm", feature = "llvm"))]
+
+use js_compiler::codegen::{self, jit, toolchain, CodegenOptions, Target, TargetSpec, WasmHost};
+use js_compiler::{ir, lexer, parser, vm};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Loads a `.wasm` file given on the command line and starts it under WASI.
+const NODE_WASI_LOADER: &str = r#"
+import { WASI } from "node:wasi";
+import { readFileSync } from "node:fs";
+const wasi = new WASI({ version: "preview1" });
+const module = readFileSync(process.argv[2]);
+const { instance } = await WebAssembly.instantiate(module, wasi.getImportObject());
+wasi.start(instance);
+"#;
+
+/// Builds and runs a corpus program in a scratch directory, returning what
+/// it printed.
+type Runner<'a> = &'a dyn Fn(&Program, &Path) -> Result<String, String>;
+
+struct Program {
+    name: String,
+    source: String,
+}
+
+impl Program {
+    fn module(&self) -> ir::IRModule {
+        ir::lower_ast(parser::parse(lexer::tokenize(&self.source)))
+    }
+
+    /// What the VM prints running `main`.
+    fn vm_output(&self) -> String {
+        let mut vm = vm::VM::new(self.module());
+        vm.capture_output();
+        vm.execute_function("main", vec![]);
+        vm.take_output()
+    }
+}
+
+fn corpus() -> Vec<Program> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut programs: Vec<Program> = fs::read_dir(&dir)
+        .expect("Failed to read the corpus")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "js"))
+        .map(|path| Program {
+            name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+            source: fs::read_to_string(&path).unwrap(),
+        })
+        .collect();
+    programs.sort_by(|a, b| a.name.cmp(&b.name));
+    programs
+}
+
+/// Compare `actual` with the snapshot at `tests/golden/<file_name>`,
+/// returning a description of the difference.
+fn check_golden(file_name: &str, actual: &str) -> Result<(), String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(file_name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, actual).unwrap();
+        return Ok(());
+    }
+    let expected = fs::read_to_string(&path).map_err(|_| {
+        format!(
+            "{}: no snapshot; run with UPDATE_GOLDEN=1 to create it",
+            file_name
+        )
+    })?;
+    if expected == actual {
+        return Ok(());
+    }
+    let (line, (expected_line, actual_line)) = expected
+        .lines()
+        .chain(std::iter::repeat(""))
+        .zip(actual.lines().chain(std::iter::repeat("")))
+        .enumerate()
+        .find(|(_, (e, a))| e != a)
+        .unwrap();
+    Err(format!(
+        "{}:{}: expected `{}`, generated `{}`",
+        file_name,
+        line + 1,
+        expected_line,
+        actual_line
+    ))
+}
+
+/// Whether `program` can be started on this host.
+fn available(program: &str) -> bool {
+    Command::new(program).arg("--version").output().is_ok()
+}
+
+/// Scratch directory for building one program, removed by the caller.
+fn scratch_dir(backend: &str, program: &Program) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "js-compiler-golden-{}-{}-{}",
+        backend,
+        program.name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run `command`, returning its stdout, or why it failed.
+fn run(command: &mut Command) -> Result<String, String> {
+    let output = command
+        .output()
+        .map_err(|e| format!("failed to run {:?}: {}", command, e))?;
+    if !output.status.success() && !output.stderr.is_empty() {
+        return Err(format!(
+            "{:?} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Check every corpus program against its snapshot for `backend`, then run
+/// it with `runner` (if any) and compare its output with the VM's.
+fn check_backend(
+    backend: &str,
+    target: Target,
+    options: &CodegenOptions,
+    extension: &str,
+    runner: Option<Runner>,
+) {
+    let mut failures = Vec::new();
+    for program in corpus() {
+        let code = codegen::generate_code(program.module(), target.clone(), options).unwrap();
+        let file_name = format!("{}.{}", program.name, extension);
+        if let Err(failure) = check_golden(&file_name, &code) {
+            failures.push(failure);
+        }
+
+        let Some(runner) = runner else {
+            continue;
+        };
+        let dir = scratch_dir(backend, &program);
+        let result = runner(&program, &dir).and_then(|output| {
+            let expected = program.vm_output();
+            if output == expected {
+                Ok(())
+            } else {
+                Err(format!(
+                    "printed {:?}, the VM printed {:?}",
+                    output, expected
+                ))
+            }
+        });
+        fs::remove_dir_all(&dir).unwrap();
+        if let Err(failure) = result {
+            failures.push(format!("{}: {}", file_name, failure));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+fn linux() -> CodegenOptions {
+    CodegenOptions {
+        target: TargetSpec::from_triple("x86_64-unknown-linux-gnu").unwrap(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_x64_golden() {
+    let runnable = cfg!(all(target_arch = "x86_64", target_os = "linux"))
+        && available(&toolchain::c_compiler());
+    let runner = |program: &Program, dir: &Path| {
+        let code = dir.join("program.s");
+        fs::write(
+            &code,
+            codegen::generate_code(program.module(), Target::X64, &linux()).unwrap(),
+        )
+        .unwrap();
+        let executable = dir.join("program");
+        toolchain::link_executable(&code, &executable)?;
+        run(&mut Command::new(&executable))
+    };
+    check_backend(
+        "x64",
+        Target::X64,
+        &linux(),
+        "x64.s",
+        runnable.then_some(&runner as _),
+    );
+}
+
+#[test]
+fn test_arm64_golden() {
+    let native = cfg!(all(target_arch = "aarch64", target_os = "linux"))
+        && available(&toolchain::c_compiler());
+    let emulated = available("aarch64-linux-gnu-gcc") && available("qemu-aarch64");
+    let runner = |program: &Program, dir: &Path| {
+        let code = dir.join("program.s");
+        fs::write(
+            &code,
+            codegen::generate_code(program.module(), Target::ARM64, &linux()).unwrap(),
+        )
+        .unwrap();
+        let executable = dir.join("program");
+        if native {
+            toolchain::link_executable(&code, &executable)?;
+            return run(&mut Command::new(&executable));
+        }
+        let runtime = dir.join(codegen::runtime::RUNTIME_FILE_NAME);
+        fs::write(&runtime, codegen::runtime::RUNTIME_C_SOURCE).unwrap();
+        run(Command::new("aarch64-linux-gnu-gcc")
+            .arg("-static")
+            .arg(&code)
+            .arg(&runtime)
+            .arg("-o")
+            .arg(&executable)
+            .arg("-lm"))?;
+        run(Command::new("qemu-aarch64").arg(&executable))
+    };
+    check_backend(
+        "arm64",
+        Target::ARM64,
+        &linux(),
+        "arm64.s",
+        (native || emulated).then_some(&runner as _),
+    );
+}
+
+#[test]
+fn test_wasm_golden() {
+    let wasmtime = available("wasmtime");
+    let node = available("node");
+    let runner = |program: &Program, dir: &Path| {
+        let options = CodegenOptions {
+            wasm_host: WasmHost::Wasi,
+            ..Default::default()
+        };
+        let text = codegen::generate_code(program.module(), Target::Wasm, &options).unwrap();
+        let module = dir.join("program.wasm");
+        fs::write(&module, codegen::wasm::encode_binary(&text)?).unwrap();
+        if wasmtime {
+            return run(Command::new("wasmtime").arg(&module));
+        }
+        let loader = dir.join("loader.mjs");
+        fs::write(&loader, NODE_WASI_LOADER).unwrap();
+        run(Command::new("node").arg(&loader).arg(&module))
+    };
+    check_backend(
+        "wasm",
+        Target::Wasm,
+        &CodegenOptions::default(),
+        "wat",
+        (wasmtime || node).then_some(&runner as _),
+    );
+}
+
+#[test]
+fn test_llvm_golden() {
+    let runner = |program: &Program, dir: &Path| {
+        let code = dir.join("program.ll");
+        fs::write(
+            &code,
+            codegen::generate_code(program.module(), Target::LlvmIr, &linux()).unwrap(),
+        )
+        .unwrap();
+        let runtime = dir.join(codegen::runtime::RUNTIME_FILE_NAME);
+        fs::write(&runtime, codegen::runtime::RUNTIME_C_SOURCE).unwrap();
+        let executable = dir.join("program");
+        run(Command::new("clang")
+            .arg(&code)
+            .arg(&runtime)
+            .arg("-o")
+            .arg(&executable)
+            .arg("-lm"))?;
+        run(&mut Command::new(&executable))
+    };
+    check_backend(
+        "llvm",
+        Target::LlvmIr,
+        &linux(),
+        "ll",
+        available("clang").then_some(&runner as _),
+    );
+}
+
+/// The JIT runs the host backend's code in process, with the VM's operators
+/// as its runtime library, and must print and return what the VM does.
+#[cfg(all(unix, any(target_arch = "x86_64", target_arch = "aarch64")))]
+#[test]
+fn test_jit_matches_vm() {
+    let mut failures = Vec::new();
+    for program in corpus() {
+        let module = jit::JitModule::compile(program.module());
+        module.capture_output();
+        let result = jit::to_string(module.call("main", &[]));
+        let output = module.take_output();
+
+        let mut vm = vm::VM::new(program.module());
+        vm.capture_output();
+        let expected = vm.execute_function("main", vec![]).to_string();
+        let expected_output = vm.take_output();
+        if output != expected_output {
+            failures.push(format!(
+                "{}: printed {:?}, the VM printed {:?}",
+                program.name, output, expected_output
+            ));
+        }
+        if result != expected {
+            failures.push(format!(
+                "{}: returned {}, the VM returned {}",
+                program.name, result, expected
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
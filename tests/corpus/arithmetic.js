@@ -0,0 +1,12 @@
+function average(a, b) {
+    return (a + b) / 2;
+}
+
+function main() {
+    print(average(3, 4));
+    print(0.1 + 0.2);
+    print(-(2 * 3.25) - 1);
+    print(7 / 2 > 3);
+    print(2 >= 3 || 1 <= 1);
+    return average(10, 20);
+}
@@ -0,0 +1,25 @@
+function sign(x) {
+    if (x < 0) {
+        return "negative";
+    } else {
+        if (x == 0) {
+            return "zero";
+        }
+    }
+    return "positive";
+}
+
+function sum_to(n) {
+    if (n == 0) {
+        return 0;
+    }
+    return n + sum_to(n - 1);
+}
+
+function main() {
+    print(sign(-3));
+    print(sign(0));
+    print(sign(8));
+    print(sum_to(100));
+    return sum_to(5);
+}
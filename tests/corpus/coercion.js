@@ -0,0 +1,19 @@
+function nothing() {
+}
+
+function main() {
+    // Loose equality converts everything but null and undefined
+    print(1 == "1", null == nothing(), null == 0, nothing() == 0, "" == 0, true == 1);
+    print(1 === "1", "a" === "a", null === null, nothing() != null, 1 != "2");
+    // Two strings compare by content, anything else as numbers
+    print(1 < "2", "10" < "9", 10 < "9", "a" < 1, null < 1, nothing() < 1);
+    print(2 >= "2", "b" > "a", true > 0, "3" <= 2);
+    // Arithmetic converts to numbers; only + concatenates
+    print(1 / 0, -1 / 0, 0 / 0, "6" / "3", "x" / 2, true + 1, "1" + 1, null + 1);
+    print("8" - 3, "2" * "4", "7" % 4, -"5", nothing() + 1);
+    // Strings read as decimals, Infinity or 0x, 0o and 0b integers
+    print(" 12 " * 1, "0x1f" * 1, "0b101" * 1, "0o17" * 1, "-Infinity" * 1, "1e3" * 1);
+    print(".5" * 1, "5." * 1, "1e" * 1, "-0x10" * 1, "1.5e-3" * 1, "inf" * 1);
+    print("1e400" * 1, "1e-400" * 1, "\t\n" * 1, "+7" * 1, "0.1" * 3);
+    return 0;
+}
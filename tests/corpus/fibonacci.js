@@ -0,0 +1,12 @@
+function fibonacci(n) {
+    if (n <= 1) {
+        return n;
+    }
+    return fibonacci(n - 1) + fibonacci(n - 2);
+}
+
+function main() {
+    print(fibonacci(10));
+    print(fibonacci(15));
+    return fibonacci(10);
+}
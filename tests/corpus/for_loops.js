@@ -0,0 +1,26 @@
+function sum_to(n) {
+    let total = 0;
+    for (let i = 1; i <= n; let i = i + 1) {
+        let total = total + i;
+    }
+    return total;
+}
+
+function first_square_over(n) {
+    let i = 0;
+    for (;;) {
+        if (i * i > n) {
+            return i;
+        }
+        let i = i + 1;
+    }
+}
+
+function main() {
+    print(sum_to(10));
+    print(first_square_over(50));
+    for (let i = 0; i < 3; let i = i + 1) {
+        print(i);
+    }
+    return sum_to(4);
+}
@@ -0,0 +1,17 @@
+function main() {
+    print(100);
+    print(610 * 10);
+    print(-42);
+    print(0.1 + 0.2);
+    print(1 / 3);
+    print(-1.5);
+    print(0.000025);
+    print(2 / 0.0001);
+    print(1000000 * 1000000 * 1000000 * 1000);
+    print(1000000 * 1000000 * 1000000 * 100);
+    print(1 / 10000000);
+    print(-15 / 100000000);
+    print(0.000001);
+    print(1.5 * 1000000 * 1000000 * 1000000 * 1000000 * 1000000 * 1000000);
+    return 0;
+}
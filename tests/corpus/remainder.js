@@ -0,0 +1,15 @@
+function is_even(n) {
+    return n % 2 === 0;
+}
+
+function main() {
+    print(7 % 3);
+    print(-7 % 3);
+    print(5.5 % 2);
+    print(is_even(4));
+    print(is_even(7));
+    print(3 != 4);
+    print("a" !== "b");
+    print(1 === "1");
+    return 17 % 5;
+}
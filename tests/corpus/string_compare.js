@@ -0,0 +1,11 @@
+function join(a, b) {
+    return a + b;
+}
+
+function main() {
+    let word = join("ab", "c");
+    print(word == "abc", word != "abc", word === "abc");
+    print("apple" < "banana", "b" > "a", "abc" <= "abd", "b" >= "c");
+    print(join(1, "2") == "12", join("x", true));
+    return 0;
+}
@@ -0,0 +1,11 @@
+function greet(name) {
+    return "Hello, " + name + "!";
+}
+
+function main() {
+    print(greet("world"));
+    print("n=" + 42);
+    print(1.5 + " apples");
+    print("a" == "a");
+    return 0;
+}
@@ -0,0 +1,12 @@
+function nothing() {
+}
+
+function main() {
+    print(null);
+    print(nothing());
+    print(0 / 0);
+    print(null, true, false, "text");
+    print("is " + null, nothing() + "!");
+    print(null == null, nothing() == nothing(), 0 / 0 == 0 / 0);
+    return 0;
+}
@@ -0,0 +1,28 @@
+function count_down(n) {
+    while (n > 0) {
+        print(n);
+        let n = n - 1;
+    }
+    return n;
+}
+
+function digits(n) {
+    let count = 1;
+    while (n >= 10) {
+        let n = n / 10;
+        let count = count + 1;
+    }
+    return count;
+}
+
+function main() {
+    print(count_down(3));
+    print(count_down(0));
+    print(digits(7));
+    print(digits(12345));
+    let i = 0;
+    while (i < 3) {
+        let i = i + 1;
+    }
+    return i;
+}
@@ -0,0 +1,307 @@
+	.text
+	.global average
+	.type average, %function
+	.p2align 2
+average:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	str x1, [fp, #-16]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Laverage.1
+	cmp x1, x9
+	b.hi .Laverage.1
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Laverage.2
+.Laverage.1:
+	bl jsrt_add
+.Laverage.2:
+	str x0, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Laverage.3
+	cmp x1, x9
+	b.hi .Laverage.3
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	b .Laverage.4
+.Laverage.3:
+	bl jsrt_div
+.Laverage.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #16]
+	ldr x1, [sp, #0]
+	bl average
+	add sp, sp, #32
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fsub d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD10
+	ldr d0, [x0, :lo12:.LCD10]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, gt
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD11
+	ldr d0, [x0, :lo12:.LCD11]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD12
+	ldr d0, [x0, :lo12:.LCD12]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ge
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.5
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lmain_L1
+	b .Lmain.6
+.Lmain.5:
+	bl jsrt_truthy
+	cbnz x0, .Lmain_L1
+.Lmain.6:
+	add sp, sp, #16
+	adrp x0, .LCD13
+	ldr d0, [x0, :lo12:.LCD13]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD14
+	ldr d0, [x0, :lo12:.LCD14]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ls
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+.Lmain_L1:
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD15
+	ldr d0, [x0, :lo12:.LCD15]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD16
+	ldr d0, [x0, :lo12:.LCD16]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #16]
+	ldr x1, [sp, #0]
+	bl average
+	add sp, sp, #32
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+	.p2align 3
+.LCD0:
+	.double 2
+.LCD1:
+	.double 3
+.LCD2:
+	.double 4
+.LCD3:
+	.double 0.1
+.LCD4:
+	.double 0.2
+.LCD5:
+	.double 2
+.LCD6:
+	.double 3.25
+.LCD7:
+	.double 1
+.LCD8:
+	.double 7
+.LCD9:
+	.double 2
+.LCD10:
+	.double 3
+.LCD11:
+	.double 2
+.LCD12:
+	.double 3
+.LCD13:
+	.double 1
+.LCD14:
+	.double 1
+.LCD15:
+	.double 10
+.LCD16:
+	.double 20
+	.section .note.GNU-stack,"",%progbits
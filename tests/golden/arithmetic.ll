@@ -0,0 +1,334 @@
+; Generated by js-compiler
+
+define double @average(double %p0, double %p1) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.a = alloca double
+  %v.b = alloca double
+  store double %p0, ptr %v.a
+  store double %p1, ptr %v.b
+  %t1 = load double, ptr %v.a
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.a
+  %t3 = load double, ptr %v.b
+  store double %t3, ptr %s0
+  %t4 = load double, ptr %s0
+  store double %t4, ptr %v.b
+  %t5 = load double, ptr %v.a
+  store double %t5, ptr %s0
+  %t6 = load double, ptr %v.b
+  store double %t6, ptr %s1
+  %t7 = load double, ptr %s0
+  %t8 = load double, ptr %s1
+  %t9 = bitcast double %t7 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 true, %t10
+  %t12 = bitcast double %t8 to i64
+  %t13 = icmp ule i64 %t12, -2251799813685248
+  %t14 = and i1 %t11, %t13
+  br i1 %t14, label %b1, label %b2
+b1:
+  %t15 = fadd double %t7, %t8
+  br label %b3
+b2:
+  %t16 = bitcast double %t7 to i64
+  %t17 = bitcast double %t8 to i64
+  %t18 = call i64 @jsrt_add(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  store double 0x4000000000000000, ptr %s1
+  %t21 = load double, ptr %s0
+  %t22 = load double, ptr %s1
+  %t23 = bitcast double %t21 to i64
+  %t24 = icmp ule i64 %t23, -2251799813685248
+  %t25 = and i1 true, %t24
+  %t26 = bitcast double %t22 to i64
+  %t27 = icmp ule i64 %t26, -2251799813685248
+  %t28 = and i1 %t25, %t27
+  br i1 %t28, label %b4, label %b5
+b4:
+  %t29 = fdiv double %t21, %t22
+  br label %b6
+b5:
+  %t30 = bitcast double %t21 to i64
+  %t31 = bitcast double %t22 to i64
+  %t32 = call i64 @jsrt_div(i64 %t30, i64 %t31)
+  %t33 = bitcast i64 %t32 to double
+  br label %b6
+b6:
+  %t34 = phi double [ %t29, %b4 ], [ %t33, %b5 ]
+  store double %t34, ptr %s0
+  %t35 = load double, ptr %s0
+  ret double %t35
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  store double 0x4008000000000000, ptr %s0
+  store double 0x4010000000000000, ptr %s1
+  %t1 = load double, ptr %s0
+  %t2 = load double, ptr %s1
+  %t3 = call double @average(double %t1, double %t2)
+  store double %t3, ptr %s0
+  %t4 = load double, ptr %s0
+  %t5 = bitcast double %t4 to i64
+  call void @jsrt_print_value(i64 %t5, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FB999999999999A, ptr %s0
+  store double 0x3FC999999999999A, ptr %s1
+  %t6 = load double, ptr %s0
+  %t7 = load double, ptr %s1
+  %t8 = bitcast double %t6 to i64
+  %t9 = icmp ule i64 %t8, -2251799813685248
+  %t10 = and i1 true, %t9
+  %t11 = bitcast double %t7 to i64
+  %t12 = icmp ule i64 %t11, -2251799813685248
+  %t13 = and i1 %t10, %t12
+  br i1 %t13, label %b1, label %b2
+b1:
+  %t14 = fadd double %t6, %t7
+  br label %b3
+b2:
+  %t15 = bitcast double %t6 to i64
+  %t16 = bitcast double %t7 to i64
+  %t17 = call i64 @jsrt_add(i64 %t15, i64 %t16)
+  %t18 = bitcast i64 %t17 to double
+  br label %b3
+b3:
+  %t19 = phi double [ %t14, %b1 ], [ %t18, %b2 ]
+  store double %t19, ptr %s0
+  %t20 = load double, ptr %s0
+  %t21 = bitcast double %t20 to i64
+  call void @jsrt_print_value(i64 %t21, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4000000000000000, ptr %s0
+  store double 0x400A000000000000, ptr %s1
+  %t22 = load double, ptr %s0
+  %t23 = load double, ptr %s1
+  %t24 = bitcast double %t22 to i64
+  %t25 = icmp ule i64 %t24, -2251799813685248
+  %t26 = and i1 true, %t25
+  %t27 = bitcast double %t23 to i64
+  %t28 = icmp ule i64 %t27, -2251799813685248
+  %t29 = and i1 %t26, %t28
+  br i1 %t29, label %b4, label %b5
+b4:
+  %t30 = fmul double %t22, %t23
+  br label %b6
+b5:
+  %t31 = bitcast double %t22 to i64
+  %t32 = bitcast double %t23 to i64
+  %t33 = call i64 @jsrt_mul(i64 %t31, i64 %t32)
+  %t34 = bitcast i64 %t33 to double
+  br label %b6
+b6:
+  %t35 = phi double [ %t30, %b4 ], [ %t34, %b5 ]
+  store double %t35, ptr %s0
+  %t36 = load double, ptr %s0
+  %t37 = bitcast double %t36 to i64
+  %t38 = icmp ule i64 %t37, -2251799813685248
+  %t39 = and i1 true, %t38
+  br i1 %t39, label %b7, label %b8
+b7:
+  %t40 = fneg double %t36
+  br label %b9
+b8:
+  %t41 = bitcast double %t36 to i64
+  %t42 = call i64 @jsrt_neg(i64 %t41)
+  %t43 = bitcast i64 %t42 to double
+  br label %b9
+b9:
+  %t44 = phi double [ %t40, %b7 ], [ %t43, %b8 ]
+  store double %t44, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t45 = load double, ptr %s0
+  %t46 = load double, ptr %s1
+  %t47 = bitcast double %t45 to i64
+  %t48 = icmp ule i64 %t47, -2251799813685248
+  %t49 = and i1 true, %t48
+  %t50 = bitcast double %t46 to i64
+  %t51 = icmp ule i64 %t50, -2251799813685248
+  %t52 = and i1 %t49, %t51
+  br i1 %t52, label %b10, label %b11
+b10:
+  %t53 = fsub double %t45, %t46
+  br label %b12
+b11:
+  %t54 = bitcast double %t45 to i64
+  %t55 = bitcast double %t46 to i64
+  %t56 = call i64 @jsrt_sub(i64 %t54, i64 %t55)
+  %t57 = bitcast i64 %t56 to double
+  br label %b12
+b12:
+  %t58 = phi double [ %t53, %b10 ], [ %t57, %b11 ]
+  store double %t58, ptr %s0
+  %t59 = load double, ptr %s0
+  %t60 = bitcast double %t59 to i64
+  call void @jsrt_print_value(i64 %t60, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x401C000000000000, ptr %s0
+  store double 0x4000000000000000, ptr %s1
+  %t61 = load double, ptr %s0
+  %t62 = load double, ptr %s1
+  %t63 = bitcast double %t61 to i64
+  %t64 = icmp ule i64 %t63, -2251799813685248
+  %t65 = and i1 true, %t64
+  %t66 = bitcast double %t62 to i64
+  %t67 = icmp ule i64 %t66, -2251799813685248
+  %t68 = and i1 %t65, %t67
+  br i1 %t68, label %b13, label %b14
+b13:
+  %t69 = fdiv double %t61, %t62
+  br label %b15
+b14:
+  %t70 = bitcast double %t61 to i64
+  %t71 = bitcast double %t62 to i64
+  %t72 = call i64 @jsrt_div(i64 %t70, i64 %t71)
+  %t73 = bitcast i64 %t72 to double
+  br label %b15
+b15:
+  %t74 = phi double [ %t69, %b13 ], [ %t73, %b14 ]
+  store double %t74, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t75 = load double, ptr %s0
+  %t76 = load double, ptr %s1
+  %t77 = bitcast double %t75 to i64
+  %t78 = icmp ule i64 %t77, -2251799813685248
+  %t79 = and i1 true, %t78
+  %t80 = bitcast double %t76 to i64
+  %t81 = icmp ule i64 %t80, -2251799813685248
+  %t82 = and i1 %t79, %t81
+  br i1 %t82, label %b16, label %b17
+b16:
+  %t83 = fcmp ogt double %t75, %t76
+  %t84 = zext i1 %t83 to i64
+  %t85 = or i64 %t84, -1688849860263936
+  %t86 = bitcast i64 %t85 to double
+  br label %b18
+b17:
+  %t87 = bitcast double %t75 to i64
+  %t88 = bitcast double %t76 to i64
+  %t89 = call i64 @jsrt_gt(i64 %t87, i64 %t88)
+  %t90 = bitcast i64 %t89 to double
+  br label %b18
+b18:
+  %t91 = phi double [ %t86, %b16 ], [ %t90, %b17 ]
+  store double %t91, ptr %s0
+  %t92 = load double, ptr %s0
+  %t93 = bitcast double %t92 to i64
+  call void @jsrt_print_value(i64 %t93, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4000000000000000, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t94 = load double, ptr %s0
+  %t95 = load double, ptr %s1
+  %t96 = bitcast double %t94 to i64
+  %t97 = icmp ule i64 %t96, -2251799813685248
+  %t98 = and i1 true, %t97
+  %t99 = bitcast double %t95 to i64
+  %t100 = icmp ule i64 %t99, -2251799813685248
+  %t101 = and i1 %t98, %t100
+  br i1 %t101, label %b19, label %b20
+b19:
+  %t102 = fcmp oge double %t94, %t95
+  %t103 = zext i1 %t102 to i64
+  %t104 = or i64 %t103, -1688849860263936
+  %t105 = bitcast i64 %t104 to double
+  br label %b21
+b20:
+  %t106 = bitcast double %t94 to i64
+  %t107 = bitcast double %t95 to i64
+  %t108 = call i64 @jsrt_ge(i64 %t106, i64 %t107)
+  %t109 = bitcast i64 %t108 to double
+  br label %b21
+b21:
+  %t110 = phi double [ %t105, %b19 ], [ %t109, %b20 ]
+  store double %t110, ptr %s0
+  %t111 = load double, ptr %s0
+  store double %t111, ptr %s1
+  %t112 = load double, ptr %s1
+  %t113 = bitcast double %t112 to i64
+  %t114 = icmp ule i64 %t113, -2251799813685248
+  %t115 = and i1 true, %t114
+  br i1 %t115, label %b22, label %b23
+b22:
+  %t116 = fcmp one double %t112, 0.0
+  br label %b24
+b23:
+  %t117 = bitcast double %t112 to i64
+  %t118 = call i64 @jsrt_truthy(i64 %t117)
+  %t119 = icmp ne i64 %t118, 0
+  br label %b24
+b24:
+  %t120 = phi i1 [ %t116, %b22 ], [ %t119, %b23 ]
+  br i1 %t120, label %LL1, label %b25
+b25:
+  store double 0x3FF0000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t121 = load double, ptr %s0
+  %t122 = load double, ptr %s1
+  %t123 = bitcast double %t121 to i64
+  %t124 = icmp ule i64 %t123, -2251799813685248
+  %t125 = and i1 true, %t124
+  %t126 = bitcast double %t122 to i64
+  %t127 = icmp ule i64 %t126, -2251799813685248
+  %t128 = and i1 %t125, %t127
+  br i1 %t128, label %b26, label %b27
+b26:
+  %t129 = fcmp ole double %t121, %t122
+  %t130 = zext i1 %t129 to i64
+  %t131 = or i64 %t130, -1688849860263936
+  %t132 = bitcast i64 %t131 to double
+  br label %b28
+b27:
+  %t133 = bitcast double %t121 to i64
+  %t134 = bitcast double %t122 to i64
+  %t135 = call i64 @jsrt_le(i64 %t133, i64 %t134)
+  %t136 = bitcast i64 %t135 to double
+  br label %b28
+b28:
+  %t137 = phi double [ %t132, %b26 ], [ %t136, %b27 ]
+  store double %t137, ptr %s0
+  br label %LL1
+LL1:
+  %t138 = load double, ptr %s0
+  %t139 = bitcast double %t138 to i64
+  call void @jsrt_print_value(i64 %t139, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4024000000000000, ptr %s0
+  store double 0x4034000000000000, ptr %s1
+  %t140 = load double, ptr %s0
+  %t141 = load double, ptr %s1
+  %t142 = call double @average(double %t140, double %t141)
+  store double %t142, ptr %s0
+  %t143 = load double, ptr %s0
+  ret double %t143
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_div(i64, i64)
+declare i64 @jsrt_ge(i64, i64)
+declare i64 @jsrt_gt(i64, i64)
+declare i64 @jsrt_le(i64, i64)
+declare i64 @jsrt_mul(i64, i64)
+declare i64 @jsrt_neg(i64)
+declare i64 @jsrt_sub(i64, i64)
+declare i64 @jsrt_truthy(i64)
@@ -0,0 +1,755 @@
+(module
+(import "console" "log" (func $log (param i64)))
+(import "console" "log_string" (func $log_string (param i32 i32)))
+(import "console" "format_number" (func $format_number (param f64 i32) (result i32)))
+(memory 1)
+(export "memory" (memory 0))
+(func $jsrt_alloc (param $size i32) (result i32)
+(local $ptr i32)
+global.get $jsrt_heap
+local.set $ptr
+local.get $ptr
+local.get $size
+i32.add
+i32.const 7
+i32.add
+i32.const -8
+i32.and
+global.set $jsrt_heap
+block
+global.get $jsrt_heap
+memory.size
+i32.const 16
+i32.shl
+i32.le_u
+br_if 0
+global.get $jsrt_heap
+memory.size
+i32.const 16
+i32.shl
+i32.sub
+i32.const 65535
+i32.add
+i32.const 16
+i32.shr_u
+memory.grow
+i32.const -1
+i32.eq
+if
+unreachable
+end
+end
+local.get $ptr
+)
+(func $jsrt_fmod (param $x f64) (param $y f64) (result f64)
+  (local $r f64)
+  (if (i32.and
+        (f64.eq (f64.abs (local.get $y)) (f64.const inf))
+        (f64.lt (f64.abs (local.get $x)) (f64.const inf)))
+    (then (return (local.get $x))))
+  (local.set $r
+    (f64.sub
+      (local.get $x)
+      (f64.mul (f64.trunc (f64.div (local.get $x) (local.get $y))) (local.get $y))))
+  (if (f64.ge (f64.abs (local.get $r)) (f64.abs (local.get $y)))
+    (then (local.set $r (f64.sub (local.get $r) (f64.copysign (local.get $y) (local.get $r))))))
+  (if (f64.lt (f64.mul (local.get $r) (local.get $x)) (f64.const 0))
+    (then (local.set $r (f64.add (local.get $r) (f64.copysign (local.get $y) (local.get $x))))))
+  (f64.copysign (local.get $r) (local.get $x)))
+(func $jsrt_compare_bytes (param $a i32) (param $a_len i32) (param $b i32) (param $b_len i32) (result i32)
+  (local $i i32)
+  (local $end i32)
+  (local $diff i32)
+  (local.set $end
+    (select (local.get $a_len) (local.get $b_len) (i32.lt_u (local.get $a_len) (local.get $b_len))))
+  (block $done
+    (loop $next_byte
+      (br_if $done (i32.ge_u (local.get $i) (local.get $end)))
+      (local.set $diff
+        (i32.sub
+          (i32.load8_u (i32.add (local.get $a) (local.get $i)))
+          (i32.load8_u (i32.add (local.get $b) (local.get $i)))))
+      (if (local.get $diff)
+        (then (return (local.get $diff))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br $next_byte)))
+  (i32.sub (local.get $a_len) (local.get $b_len)))
+(func $jsrt_pow10 (param $k i32) (result f64)
+  (local $result f64)
+  (local.set $result (f64.const 1))
+  (block $done
+    (loop $multiply
+      (br_if $done (i32.le_s (local.get $k) (i32.const 0)))
+      (local.set $result (f64.mul (local.get $result) (f64.const 10)))
+      (local.set $k (i32.sub (local.get $k) (i32.const 1)))
+      (br $multiply)))
+  (local.get $result))
+;; The exact product `a * b` minus the rounded one, by Dekker's splitting
+(func $jsrt_product_error (param $a f64) (param $b f64) (result f64)
+  (local $split f64)
+  (local $a_hi f64)
+  (local $a_lo f64)
+  (local $b_hi f64)
+  (local $b_lo f64)
+  (local.set $split (f64.mul (local.get $a) (f64.const 134217729)))
+  (local.set $a_hi (f64.sub (local.get $split) (f64.sub (local.get $split) (local.get $a))))
+  (local.set $a_lo (f64.sub (local.get $a) (local.get $a_hi)))
+  (local.set $split (f64.mul (local.get $b) (f64.const 134217729)))
+  (local.set $b_hi (f64.sub (local.get $split) (f64.sub (local.get $split) (local.get $b))))
+  (local.set $b_lo (f64.sub (local.get $b) (local.get $b_hi)))
+  (f64.add
+    (f64.add
+      (f64.add
+        (f64.sub (f64.mul (local.get $a_hi) (local.get $b_hi)) (f64.mul (local.get $a) (local.get $b)))
+        (f64.mul (local.get $a_hi) (local.get $b_lo)))
+      (f64.mul (local.get $a_lo) (local.get $b_hi)))
+    (f64.mul (local.get $a_lo) (local.get $b_lo))))
+;; `value * 10^k` as a double and the error of rounding it to one. Each
+;; step scales by an exact power of ten up to 10^22 and carries the error
+;; along, so the pair stays within about 2^-100 of the product.
+(func $jsrt_scale (param $value f64) (param $k i32) (result f64 f64)
+  (local $hi f64)
+  (local $lo f64)
+  (local $step i32)
+  (local $power f64)
+  (local $product f64)
+  (local $error f64)
+  (local $unscale f64)
+  ;; Values near the ends of the range first move by an exact 2^128 so
+  ;; that no step underflows or overflows
+  (local.set $unscale (f64.const 1))
+  (if (f64.lt (local.get $value) (f64.const 0x1p-900))
+    (then
+      (local.set $value (f64.mul (local.get $value) (f64.const 0x1p128)))
+      (local.set $unscale (f64.const 0x1p-128))))
+  (if (f64.gt (local.get $value) (f64.const 0x1p900))
+    (then
+      (local.set $value (f64.mul (local.get $value) (f64.const 0x1p-128)))
+      (local.set $unscale (f64.const 0x1p128))))
+  (local.set $hi (local.get $value))
+  (loop $next_step
+    (local.set $step
+      (select
+        (i32.const 22)
+        (select (i32.sub (i32.const 0) (local.get $k)) (local.get $k) (i32.lt_s (local.get $k) (i32.const 0)))
+        (i32.gt_u
+          (select (i32.sub (i32.const 0) (local.get $k)) (local.get $k) (i32.lt_s (local.get $k) (i32.const 0)))
+          (i32.const 22))))
+    (local.set $power (call $jsrt_pow10 (local.get $step)))
+    (if (i32.ge_s (local.get $k) (i32.const 0))
+      (then
+        (local.set $product (f64.mul (local.get $hi) (local.get $power)))
+        (local.set $error
+          (f64.add
+            (call $jsrt_product_error (local.get $hi) (local.get $power))
+            (f64.mul (local.get $lo) (local.get $power))))
+        (local.set $k (i32.sub (local.get $k) (local.get $step))))
+      (else
+        ;; `product * power` misses `hi` by `-error * power`
+        (local.set $product (f64.div (local.get $hi) (local.get $power)))
+        (local.set $error
+          (f64.div
+            (f64.sub
+              (local.get $lo)
+              (f64.add
+                (f64.sub (f64.mul (local.get $product) (local.get $power)) (local.get $hi))
+                (call $jsrt_product_error (local.get $product) (local.get $power))))
+            (local.get $power)))
+        (local.set $k (i32.add (local.get $k) (local.get $step)))))
+    (local.set $hi (f64.add (local.get $product) (local.get $error)))
+    (local.set $lo (f64.sub (local.get $error) (f64.sub (local.get $hi) (local.get $product))))
+    (br_if $next_step (local.get $k)))
+  (f64.mul (local.get $hi) (local.get $unscale))
+  (f64.mul (local.get $lo) (local.get $unscale)))
+(func $jsrt_is_space (param $c i32) (result i32)
+  (i32.or
+    (i32.eq (local.get $c) (i32.const 32))
+    (i32.lt_u (i32.sub (local.get $c) (i32.const 9)) (i32.const 5))))
+;; Value of a hex digit, or 16 for any other byte
+(func $jsrt_digit_value (param $c i32) (result i32)
+  (if (i32.lt_u (i32.sub (local.get $c) (i32.const 48)) (i32.const 10))
+    (then (return (i32.sub (local.get $c) (i32.const 48)))))
+  (local.set $c (i32.or (local.get $c) (i32.const 32)))
+  (if (i32.lt_u (i32.sub (local.get $c) (i32.const 97)) (i32.const 6))
+    (then (return (i32.sub (local.get $c) (i32.const 87)))))
+  (i32.const 16))
+;; The number a string converts to, as in JavaScript: surrounding ASCII
+;; whitespace is ignored, an empty string is 0, and otherwise the rest must
+;; be a decimal literal, a signed or unsigned Infinity, or a 0x, 0o or 0b
+;; integer, or the result is NaN. Decimals keep 19 significant digits and
+;; are scaled as a pair of doubles, so they round correctly unless within
+;; about 2^-100 of halfway between two doubles.
+(func $jsrt_parse_number (param $ptr i32) (param $len i32) (result f64)
+  (local $end i32)
+  (local $c i32)
+  (local $radix i32)
+  (local $digit i32)
+  (local $negative i32)
+  (local $point i32)
+  (local $seen i32)
+  (local $digits i32)
+  (local $exponent i32)
+  (local $e i32)
+  (local $e_negative i32)
+  (local $mantissa i64)
+  (local $value f64)
+  (local $hi f64)
+  (local $lo f64)
+  (local $error f64)
+  (local.set $end (i32.add (local.get $ptr) (local.get $len)))
+  (block $trimmed
+    (loop $trim_start
+      (br_if $trimmed (i32.ge_u (local.get $ptr) (local.get $end)))
+      (br_if $trimmed (i32.eqz (call $jsrt_is_space (i32.load8_u (local.get $ptr)))))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+      (br $trim_start)))
+  (block $trimmed
+    (loop $trim_end
+      (br_if $trimmed (i32.ge_u (local.get $ptr) (local.get $end)))
+      (br_if $trimmed
+        (i32.eqz (call $jsrt_is_space (i32.load8_u (i32.sub (local.get $end) (i32.const 1))))))
+      (local.set $end (i32.sub (local.get $end) (i32.const 1)))
+      (br $trim_end)))
+  (if (i32.eq (local.get $ptr) (local.get $end))
+    (then (return (f64.const 0))))
+  ;; 0x, 0o and 0b integers take no sign and at least one digit
+  (if (i32.and
+        (i32.ge_u (i32.sub (local.get $end) (local.get $ptr)) (i32.const 3))
+        (i32.eq (i32.load8_u (local.get $ptr)) (i32.const 48)))
+    (then
+      (local.set $c (i32.or (i32.load8_u offset=1 (local.get $ptr)) (i32.const 32)))
+      (local.set $radix
+        (select
+          (i32.const 16)
+          (select
+            (i32.const 8)
+            (select (i32.const 2) (i32.const 0) (i32.eq (local.get $c) (i32.const 98)))
+            (i32.eq (local.get $c) (i32.const 111)))
+          (i32.eq (local.get $c) (i32.const 120))))
+      (if (local.get $radix)
+        (then
+          (local.set $ptr (i32.add (local.get $ptr) (i32.const 2)))
+          (loop $next_digit
+            (local.set $digit (call $jsrt_digit_value (i32.load8_u (local.get $ptr))))
+            (if (i32.ge_u (local.get $digit) (local.get $radix))
+              (then (return (f64.const nan))))
+            (local.set $value
+              (f64.add
+                (f64.mul (local.get $value) (f64.convert_i32_u (local.get $radix)))
+                (f64.convert_i32_u (local.get $digit))))
+            (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+            (br_if $next_digit (i32.lt_u (local.get $ptr) (local.get $end))))
+          (return (local.get $value))))))
+  (local.set $c (i32.load8_u (local.get $ptr)))
+  (if (i32.or (i32.eq (local.get $c) (i32.const 43)) (i32.eq (local.get $c) (i32.const 45)))
+    (then
+      (local.set $negative (i32.eq (local.get $c) (i32.const 45)))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))))
+  ;; "Infinity"
+  (if (i32.and
+        (i32.eq (i32.sub (local.get $end) (local.get $ptr)) (i32.const 8))
+        (i64.eq (i64.load (local.get $ptr)) (i64.const 0x7974696e69666e49)))
+    (then
+      (return
+        (select (f64.const -inf) (f64.const inf) (local.get $negative)))))
+  ;; Digits and a decimal point, then an optional exponent
+  (block $digits_done
+    (loop $next_char
+      (br_if $digits_done (i32.ge_u (local.get $ptr) (local.get $end)))
+      (local.set $c (i32.load8_u (local.get $ptr)))
+      (if (i32.eq (local.get $c) (i32.const 46))
+        (then
+          (if (local.get $point)
+            (then (return (f64.const nan))))
+          (local.set $point (i32.const 1)))
+        (else
+          (local.set $digit (i32.sub (local.get $c) (i32.const 48)))
+          (br_if $digits_done (i32.ge_u (local.get $digit) (i32.const 10)))
+          (local.set $seen (i32.const 1))
+          (if (i32.lt_u (local.get $digits) (i32.const 19))
+            (then
+              ;; Leading zeros only move the point
+              (if (i32.or (i64.ne (local.get $mantissa) (i64.const 0)) (local.get $digit))
+                (then
+                  (local.set $mantissa
+                    (i64.add
+                      (i64.mul (local.get $mantissa) (i64.const 10))
+                      (i64.extend_i32_u (local.get $digit))))
+                  (local.set $digits (i32.add (local.get $digits) (i32.const 1)))))
+              (if (local.get $point)
+                (then (local.set $exponent (i32.sub (local.get $exponent) (i32.const 1))))))
+            (else
+              ;; Digits past the 19th are dropped, keeping their place
+              (if (i32.eqz (local.get $point))
+                (then (local.set $exponent (i32.add (local.get $exponent) (i32.const 1)))))))))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+      (br $next_char)))
+  (if (i32.eqz (local.get $seen))
+    (then (return (f64.const nan))))
+  (if (i32.lt_u (local.get $ptr) (local.get $end))
+    (then
+      (if (i32.ne (i32.or (i32.load8_u (local.get $ptr)) (i32.const 32)) (i32.const 101))
+        (then (return (f64.const nan))))
+      (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+      (if (i32.lt_u (local.get $ptr) (local.get $end))
+        (then
+          (local.set $c (i32.load8_u (local.get $ptr)))
+          (if (i32.or (i32.eq (local.get $c) (i32.const 43)) (i32.eq (local.get $c) (i32.const 45)))
+            (then
+              (local.set $e_negative (i32.eq (local.get $c) (i32.const 45)))
+              (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))))))
+      (if (i32.ge_u (local.get $ptr) (local.get $end))
+        (then (return (f64.const nan))))
+      (loop $next_exponent_digit
+        (local.set $digit (i32.sub (i32.load8_u (local.get $ptr)) (i32.const 48)))
+        (if (i32.ge_u (local.get $digit) (i32.const 10))
+          (then (return (f64.const nan))))
+        ;; Far past the range of doubles any larger exponent gives the same
+        (if (i32.lt_u (local.get $e) (i32.const 100000))
+          (then
+            (local.set $e
+              (i32.add (i32.mul (local.get $e) (i32.const 10)) (local.get $digit)))))
+        (local.set $ptr (i32.add (local.get $ptr) (i32.const 1)))
+        (br_if $next_exponent_digit (i32.lt_u (local.get $ptr) (local.get $end))))
+      (local.set $exponent
+        (select
+          (i32.sub (local.get $exponent) (local.get $e))
+          (i32.add (local.get $exponent) (local.get $e))
+          (local.get $e_negative)))))
+  (local.set $value (f64.convert_i64_u (local.get $mantissa)))
+  (if (i64.ne (local.get $mantissa) (i64.const 0))
+    (then
+      (if (i32.and
+            (i64.lt_u (local.get $mantissa) (i64.const 0x20000000000000))
+            (i32.le_u (i32.add (local.get $exponent) (i32.const 22)) (i32.const 44)))
+        (then
+          ;; Both operands are exact, so the one rounding is correct
+          (local.set $value
+            (select
+              (f64.mul (local.get $value) (call $jsrt_pow10 (local.get $exponent)))
+              (f64.div (local.get $value) (call $jsrt_pow10 (i32.sub (i32.const 0) (local.get $exponent))))
+              (i32.ge_s (local.get $exponent) (i32.const 0)))))
+        (else
+          (if (i32.gt_s (local.get $exponent) (i32.const 310))
+            (then (local.set $value (f64.const inf)))
+            (else
+              (if (i32.lt_s (local.get $exponent) (i32.const -400))
+                (then (local.set $value (f64.const 0)))
+                (else
+                  ;; The mantissa as a double and what it missed by, each scaled
+                  (local.set $lo
+                    (f64.convert_i64_s
+                      (i64.sub (local.get $mantissa) (i64.trunc_f64_u (local.get $value)))))
+                  (call $jsrt_scale (local.get $value) (local.get $exponent))
+                  (local.set $error)
+                  (local.set $hi)
+                  (call $jsrt_scale (local.get $lo) (local.get $exponent))
+                  (drop)
+                  (local.set $lo)
+                  (local.set $value
+                    (f64.add (local.get $hi) (f64.add (local.get $error) (local.get $lo))))
+                  ;; Past the largest double the error terms are inf - inf
+                  (if (f64.ne (local.get $value) (local.get $value))
+                    (then (local.set $value (f64.const inf))))))))))))
+  (select (f64.neg (local.get $value)) (local.get $value) (local.get $negative)))
+(global $jsrt_null i64 (i64.const 9221964661971222528))
+(global $jsrt_undefined i64 (i64.const 9222527611924643840))
+(global $jsrt_true_string i64 (i64.const 9222246136947933216))
+(global $jsrt_false_string i64 (i64.const 9222246136947933232))
+(global $jsrt_null_string i64 (i64.const 9222246136947933248))
+(global $jsrt_undefined_string i64 (i64.const 9222246136947933272))
+(func $jsrt_is_boxed (param $value i64) (result i32)
+  (i64.lt_u
+    (i64.sub (i64.shr_u (local.get $value) (i64.const 48)) (i64.const 0x7ffa))
+    (i64.const 4)))
+(func $jsrt_is_string (param $value i64) (result i32)
+local.get $value
+i64.const 48
+i64.shr_u
+i64.const 0x7ffc
+i64.eq
+)
+(func $jsrt_is_boolean (param $value i64) (result i32)
+local.get $value
+i64.const 48
+i64.shr_u
+i64.const 0x7ffa
+i64.eq
+)
+(func $jsrt_to_number (param $value i64) (result f64)
+  (local $pair i32)
+  (if (i32.eqz (call $jsrt_is_boxed (local.get $value)))
+    (then (return (f64.reinterpret_i64 (local.get $value)))))
+  (if (call $jsrt_is_string (local.get $value))
+    (then
+      (local.set $pair (i32.wrap_i64 (local.get $value)))
+      (return
+        (call $jsrt_parse_number (i32.load (local.get $pair)) (i32.load offset=4 (local.get $pair))))))
+  (if (call $jsrt_is_boolean (local.get $value))
+    (then (return (f64.convert_i32_u (i32.wrap_i64 (local.get $value))))))
+  (if (i64.eq (local.get $value) (global.get $jsrt_null))
+    (then (return (f64.const 0))))
+  (f64.const nan))
+(func $jsrt_make_string (param $ptr i32) (param $len i32) (result i64)
+(local $pair i32)
+i32.const 8
+call $jsrt_alloc
+local.tee $pair
+local.get $ptr
+i32.store
+local.get $pair
+local.get $len
+i32.store offset=4
+local.get $pair
+i64.extend_i32_u
+i64.const 0x7ffc000000000000
+i64.or
+)
+(func $jsrt_to_string (param $value i64) (result i64)
+(local $ptr i32)
+local.get $value
+call $jsrt_is_string
+if (result i64)
+local.get $value
+else
+local.get $value
+call $jsrt_is_boolean
+if (result i64)
+global.get $jsrt_true_string
+global.get $jsrt_false_string
+local.get $value
+i32.wrap_i64
+select
+else
+local.get $value
+global.get $jsrt_null
+i64.eq
+if (result i64)
+global.get $jsrt_null_string
+else
+local.get $value
+global.get $jsrt_undefined
+i64.eq
+if (result i64)
+global.get $jsrt_undefined_string
+else
+i32.const 32
+call $jsrt_alloc
+local.tee $ptr
+local.get $value
+f64.reinterpret_i64
+local.get $ptr
+call $format_number
+call $jsrt_make_string
+end
+end
+end
+end
+)
+(func $jsrt_concat (param $left i64) (param $right i64) (result i64)
+(local $left_ptr i32)
+(local $left_len i32)
+(local $right_ptr i32)
+(local $right_len i32)
+(local $ptr i32)
+local.get $left
+call $jsrt_to_string
+i32.wrap_i64
+local.tee $ptr
+i32.load
+local.set $left_ptr
+local.get $ptr
+i32.load offset=4
+local.set $left_len
+local.get $right
+call $jsrt_to_string
+i32.wrap_i64
+local.tee $ptr
+i32.load
+local.set $right_ptr
+local.get $ptr
+i32.load offset=4
+local.set $right_len
+local.get $left_len
+local.get $right_len
+i32.add
+call $jsrt_alloc
+local.tee $ptr
+local.get $left_ptr
+local.get $left_len
+memory.copy
+local.get $ptr
+local.get $left_len
+i32.add
+local.get $right_ptr
+local.get $right_len
+memory.copy
+local.get $ptr
+local.get $left_len
+local.get $right_len
+i32.add
+call $jsrt_make_string
+)
+(func $jsrt_add (param $left i64) (param $right i64) (result i64)
+local.get $left
+call $jsrt_is_string
+local.get $right
+call $jsrt_is_string
+i32.or
+if (result i64)
+local.get $left
+local.get $right
+call $jsrt_concat
+else
+local.get $left
+call $jsrt_to_number
+local.get $right
+call $jsrt_to_number
+f64.add
+i64.reinterpret_f64
+end
+)
+(func $jsrt_truthy (param $value i64) (result i32)
+(local $number f64)
+local.get $value
+call $jsrt_is_string
+if (result i32)
+local.get $value
+i32.wrap_i64
+i32.load offset=4
+i32.const 0
+i32.ne
+else
+local.get $value
+call $jsrt_to_number
+local.tee $number
+f64.const 0
+f64.ne
+local.get $number
+local.get $number
+f64.eq
+i32.and
+end
+)
+(func $jsrt_print_value (param $value i64)
+(local $pair i32)
+local.get $value
+call $jsrt_is_boxed
+if
+local.get $value
+call $jsrt_to_string
+i32.wrap_i64
+local.tee $pair
+i32.load
+local.get $pair
+i32.load offset=4
+call $log_string
+else
+local.get $value
+call $log
+end
+)
+(func $jsrt_string_order (param $left i64) (param $right i64) (result i32)
+  (local $left_pair i32)
+  (local $right_pair i32)
+  (local.set $left_pair (i32.wrap_i64 (local.get $left)))
+  (local.set $right_pair (i32.wrap_i64 (local.get $right)))
+  (call $jsrt_compare_bytes
+    (i32.load (local.get $left_pair))
+    (i32.load offset=4 (local.get $left_pair))
+    (i32.load (local.get $right_pair))
+    (i32.load offset=4 (local.get $right_pair))))
+(func $jsrt_is_nullish (param $value i64) (result i32)
+  (i32.or
+    (i64.eq (local.get $value) (global.get $jsrt_null))
+    (i64.eq (local.get $value) (global.get $jsrt_undefined))))
+(func $jsrt_equals (param $left i64) (param $right i64) (result i32)
+  (if (i32.or (call $jsrt_is_nullish (local.get $left)) (call $jsrt_is_nullish (local.get $right)))
+    (then
+      (return
+        (i32.and (call $jsrt_is_nullish (local.get $left)) (call $jsrt_is_nullish (local.get $right))))))
+  (if (i32.and (call $jsrt_is_string (local.get $left)) (call $jsrt_is_string (local.get $right)))
+    (then (return (i32.eqz (call $jsrt_string_order (local.get $left) (local.get $right))))))
+  (f64.eq (call $jsrt_to_number (local.get $left)) (call $jsrt_to_number (local.get $right))))
+(func $jsrt_strict_equals (param $left i64) (param $right i64) (result i32)
+  (if (i32.or (call $jsrt_is_string (local.get $left)) (call $jsrt_is_string (local.get $right)))
+    (then
+      (return
+        (select
+          (i32.eqz (call $jsrt_string_order (local.get $left) (local.get $right)))
+          (i32.const 0)
+          (i32.and
+            (call $jsrt_is_string (local.get $left))
+            (call $jsrt_is_string (local.get $right)))))))
+  (if (i32.or (call $jsrt_is_boxed (local.get $left)) (call $jsrt_is_boxed (local.get $right)))
+    (then (return (i64.eq (local.get $left) (local.get $right)))))
+  (f64.eq (f64.reinterpret_i64 (local.get $left)) (f64.reinterpret_i64 (local.get $right))))
+(func $jsrt_comparable (param $left i64) (param $right i64) (result f64 f64)
+  (if (i32.and (call $jsrt_is_string (local.get $left)) (call $jsrt_is_string (local.get $right)))
+    (then
+      (return
+        (f64.convert_i32_s (call $jsrt_string_order (local.get $left) (local.get $right)))
+        (f64.const 0))))
+  (call $jsrt_to_number (local.get $left))
+  (call $jsrt_to_number (local.get $right)))
+(func $average (param i64) (param i64) (result i64)
+(local $tmp i64)
+(local $cond i32)
+local.get 0
+local.set 0
+local.get 1
+local.set 1
+local.get 0
+local.get 1
+call $jsrt_add
+f64.const 2
+i64.reinterpret_f64
+local.set $tmp
+call $jsrt_to_number
+local.get $tmp
+call $jsrt_to_number
+f64.div
+i64.reinterpret_f64
+return
+unreachable
+)
+(func $main (result i64)
+(local $tmp i64)
+(local $cond i32)
+(local $arg0 i64)
+(local $s0 i64)
+block
+f64.const 3
+i64.reinterpret_f64
+f64.const 4
+i64.reinterpret_f64
+call $average ;; args: 2
+local.set $arg0
+local.get $arg0
+call $jsrt_print_value
+i32.const 96
+i32.const 1
+call $log_string
+global.get $jsrt_undefined
+drop
+f64.const 0.1
+i64.reinterpret_f64
+f64.const 0.2
+i64.reinterpret_f64
+call $jsrt_add
+local.set $arg0
+local.get $arg0
+call $jsrt_print_value
+i32.const 96
+i32.const 1
+call $log_string
+global.get $jsrt_undefined
+drop
+f64.const 2
+i64.reinterpret_f64
+f64.const 3.25
+i64.reinterpret_f64
+local.set $tmp
+call $jsrt_to_number
+local.get $tmp
+call $jsrt_to_number
+f64.mul
+i64.reinterpret_f64
+call $jsrt_to_number
+f64.neg
+i64.reinterpret_f64
+f64.const 1
+i64.reinterpret_f64
+local.set $tmp
+call $jsrt_to_number
+local.get $tmp
+call $jsrt_to_number
+f64.sub
+i64.reinterpret_f64
+local.set $arg0
+local.get $arg0
+call $jsrt_print_value
+i32.const 96
+i32.const 1
+call $log_string
+global.get $jsrt_undefined
+drop
+f64.const 7
+i64.reinterpret_f64
+f64.const 2
+i64.reinterpret_f64
+local.set $tmp
+call $jsrt_to_number
+local.get $tmp
+call $jsrt_to_number
+f64.div
+i64.reinterpret_f64
+f64.const 3
+i64.reinterpret_f64
+call $jsrt_comparable
+f64.gt
+i64.extend_i32_u
+i64.const 9221683186994511872
+i64.or
+local.set $arg0
+local.get $arg0
+call $jsrt_print_value
+i32.const 96
+i32.const 1
+call $log_string
+global.get $jsrt_undefined
+drop
+f64.const 2
+i64.reinterpret_f64
+f64.const 3
+i64.reinterpret_f64
+call $jsrt_comparable
+f64.ge
+i64.extend_i32_u
+i64.const 9221683186994511872
+i64.or
+local.tee $tmp
+local.get $tmp
+call $jsrt_truthy
+local.set $cond
+local.set $s0
+local.get $cond
+if
+br 1
+else
+local.get $s0
+drop
+f64.const 1
+i64.reinterpret_f64
+f64.const 1
+i64.reinterpret_f64
+call $jsrt_comparable
+f64.le
+i64.extend_i32_u
+i64.const 9221683186994511872
+i64.or
+local.set $s0
+br 1
+end
+end
+local.get $s0
+local.set $arg0
+local.get $arg0
+call $jsrt_print_value
+i32.const 96
+i32.const 1
+call $log_string
+global.get $jsrt_undefined
+drop
+f64.const 10
+i64.reinterpret_f64
+f64.const 20
+i64.reinterpret_f64
+call $average ;; args: 2
+return
+unreachable
+)
+(data (i32.const 0) "\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\00\74\72\75\65\00\00\00\00\18\00\00\00\04\00\00\00\66\61\6c\73\65\00\00\00\28\00\00\00\05\00\00\00\6e\75\6c\6c\00\00\00\00\38\00\00\00\04\00\00\00\75\6e\64\65\66\69\6e\65\64\00\00\00\00\00\00\00\48\00\00\00\09\00\00\00\0a\00\00\00\00\00\00\00\60\00\00\00\01\00\00\00")
+(global $jsrt_heap (mut i32) (i32.const 112))
+(export "main" (func $main))
+)
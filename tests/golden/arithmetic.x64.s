@@ -0,0 +1,340 @@
+	.text
+	.globl average
+	.type average, @function
+average:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	movq %xmm1, %rax
+	mov %rax, -16(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -16(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Laverage.1
+	cmp %rdx, %rcx
+	ja .Laverage.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Laverage.2
+.Laverage.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Laverage.2:
+	push %rax
+	mov .LCD0(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Laverage.3
+	cmp %rdx, %rcx
+	ja .Laverage.3
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Laverage.4
+.Laverage.3:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_div
+	mov %rbx, %rsp
+.Laverage.4:
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD1(%rip), %rax
+	push %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	mov 8(%rsp), %rax
+	movq %rax, %xmm0
+	mov 0(%rsp), %rax
+	movq %rax, %xmm1
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call average
+	mov %rbx, %rsp
+	add $16, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD3(%rip), %rax
+	push %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD5(%rip), %rax
+	push %rax
+	mov .LCD6(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov .LCD7(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	subsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD8(%rip), %rax
+	push %rax
+	mov .LCD9(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD10(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD11(%rip), %rax
+	push %rax
+	mov .LCD12(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	push %rax
+	mov (%rsp), %rax
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.5
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lmain_L1
+	jmp .Lmain.6
+.Lmain.5:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lmain_L1
+.Lmain.6:
+	pop %rax
+	mov .LCD13(%rip), %rax
+	push %rax
+	mov .LCD14(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	push %rax
+.Lmain_L1:
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD15(%rip), %rax
+	push %rax
+	mov .LCD16(%rip), %rax
+	push %rax
+	mov 8(%rsp), %rax
+	movq %rax, %xmm0
+	mov 0(%rsp), %rax
+	movq %rax, %xmm1
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call average
+	mov %rbx, %rsp
+	add $16, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+	.p2align 3
+.LCD0:
+	.double 2
+.LCD1:
+	.double 3
+.LCD2:
+	.double 4
+.LCD3:
+	.double 0.1
+.LCD4:
+	.double 0.2
+.LCD5:
+	.double 2
+.LCD6:
+	.double 3.25
+.LCD7:
+	.double 1
+.LCD8:
+	.double 7
+.LCD9:
+	.double 2
+.LCD10:
+	.double 3
+.LCD11:
+	.double 2
+.LCD12:
+	.double 3
+.LCD13:
+	.double 1
+.LCD14:
+	.double 1
+.LCD15:
+	.double 10
+.LCD16:
+	.double 20
+	.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,434 @@
+	.text
+	.global sign
+	.type sign, %function
+	.p2align 2
+sign:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsign.1
+	cmp x1, x9
+	b.hi .Lsign.1
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsign.2
+.Lsign.1:
+	bl jsrt_lt
+.Lsign.2:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsign.3
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsign.4
+.Lsign.3:
+	bl jsrt_not
+.Lsign.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsign.5
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lsign_L1
+	b .Lsign.6
+.Lsign.5:
+	bl jsrt_truthy
+	cbnz x0, .Lsign_L1
+.Lsign.6:
+	adrp x0, .LC0
+	add x0, x0, :lo12:.LC0
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	b .Lsign_L2
+.Lsign_L1:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsign.7
+	cmp x1, x9
+	b.hi .Lsign.7
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsign.8
+.Lsign.7:
+	bl jsrt_eq
+.Lsign.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsign.9
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsign.10
+.Lsign.9:
+	bl jsrt_not
+.Lsign.10:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsign.11
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lsign_L3
+	b .Lsign.12
+.Lsign.11:
+	bl jsrt_truthy
+	cbnz x0, .Lsign_L3
+.Lsign.12:
+	adrp x0, .LC1
+	add x0, x0, :lo12:.LC1
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	b .Lsign_L4
+.Lsign_L3:
+.Lsign_L4:
+.Lsign_L2:
+	adrp x0, .LC2
+	add x0, x0, :lo12:.LC2
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global sum_to
+	.type sum_to, %function
+	.p2align 2
+sum_to:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.13
+	cmp x1, x9
+	b.hi .Lsum_to.13
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsum_to.14
+.Lsum_to.13:
+	bl jsrt_eq
+.Lsum_to.14:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.15
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsum_to.16
+.Lsum_to.15:
+	bl jsrt_not
+.Lsum_to.16:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.17
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lsum_to_L1
+	b .Lsum_to.18
+.Lsum_to.17:
+	bl jsrt_truthy
+	cbnz x0, .Lsum_to_L1
+.Lsum_to.18:
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	b .Lsum_to_L2
+.Lsum_to_L1:
+.Lsum_to_L2:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.19
+	cmp x1, x9
+	b.hi .Lsum_to.19
+	fmov d0, x0
+	fmov d1, x1
+	fsub d0, d0, d1
+	fmov x0, d0
+	b .Lsum_to.20
+.Lsum_to.19:
+	bl jsrt_sub
+.Lsum_to.20:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sum_to
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.21
+	cmp x1, x9
+	b.hi .Lsum_to.21
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lsum_to.22
+.Lsum_to.21:
+	bl jsrt_add
+.Lsum_to.22:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sign
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sign
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sign
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sum_to
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sum_to
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+.LC0:
+	.asciz "negative"
+.LC1:
+	.asciz "zero"
+.LC2:
+	.asciz "positive"
+	.p2align 3
+.LCD0:
+	.double 0
+.LCD1:
+	.double 0
+.LCD2:
+	.double 0
+.LCD3:
+	.double 0
+.LCD4:
+	.double 1
+.LCD5:
+	.double 3
+.LCD6:
+	.double 0
+.LCD7:
+	.double 8
+.LCD8:
+	.double 100
+.LCD9:
+	.double 5
+	.section .note.GNU-stack,"",%progbits
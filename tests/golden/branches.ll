@@ -0,0 +1,379 @@
+; Generated by js-compiler
+
+define double @sign(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.x = alloca double
+  store double %p0, ptr %v.x
+  %t1 = load double, ptr %v.x
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.x
+  %t3 = load double, ptr %v.x
+  store double %t3, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t4 = load double, ptr %s0
+  %t5 = load double, ptr %s1
+  %t6 = bitcast double %t4 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 true, %t7
+  %t9 = bitcast double %t5 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 %t8, %t10
+  br i1 %t11, label %b1, label %b2
+b1:
+  %t12 = fcmp olt double %t4, %t5
+  %t13 = zext i1 %t12 to i64
+  %t14 = or i64 %t13, -1688849860263936
+  %t15 = bitcast i64 %t14 to double
+  br label %b3
+b2:
+  %t16 = bitcast double %t4 to i64
+  %t17 = bitcast double %t5 to i64
+  %t18 = call i64 @jsrt_lt(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  %t21 = load double, ptr %s0
+  %t22 = bitcast double %t21 to i64
+  %t23 = icmp ule i64 %t22, -2251799813685248
+  %t24 = and i1 true, %t23
+  br i1 %t24, label %b4, label %b5
+b4:
+  %t25 = fcmp ueq double %t21, 0.0
+  %t26 = zext i1 %t25 to i64
+  %t27 = or i64 %t26, -1688849860263936
+  %t28 = bitcast i64 %t27 to double
+  br label %b6
+b5:
+  %t29 = bitcast double %t21 to i64
+  %t30 = call i64 @jsrt_not(i64 %t29)
+  %t31 = bitcast i64 %t30 to double
+  br label %b6
+b6:
+  %t32 = phi double [ %t28, %b4 ], [ %t31, %b5 ]
+  store double %t32, ptr %s0
+  %t33 = load double, ptr %s0
+  %t34 = bitcast double %t33 to i64
+  %t35 = icmp ule i64 %t34, -2251799813685248
+  %t36 = and i1 true, %t35
+  br i1 %t36, label %b7, label %b8
+b7:
+  %t37 = fcmp one double %t33, 0.0
+  br label %b9
+b8:
+  %t38 = bitcast double %t33 to i64
+  %t39 = call i64 @jsrt_truthy(i64 %t38)
+  %t40 = icmp ne i64 %t39, 0
+  br label %b9
+b9:
+  %t41 = phi i1 [ %t37, %b7 ], [ %t40, %b8 ]
+  br i1 %t41, label %LL1, label %b10
+b10:
+  %t42 = ptrtoint ptr @.str.0 to i64
+  %t43 = or i64 %t42, -1970324836974592
+  %t44 = bitcast i64 %t43 to double
+  store double %t44, ptr %s0
+  %t45 = load double, ptr %s0
+  ret double %t45
+LL1:
+  %t46 = load double, ptr %v.x
+  store double %t46, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t47 = load double, ptr %s0
+  %t48 = load double, ptr %s1
+  %t49 = bitcast double %t47 to i64
+  %t50 = icmp ule i64 %t49, -2251799813685248
+  %t51 = and i1 true, %t50
+  %t52 = bitcast double %t48 to i64
+  %t53 = icmp ule i64 %t52, -2251799813685248
+  %t54 = and i1 %t51, %t53
+  br i1 %t54, label %b11, label %b12
+b11:
+  %t55 = fcmp oeq double %t47, %t48
+  %t56 = zext i1 %t55 to i64
+  %t57 = or i64 %t56, -1688849860263936
+  %t58 = bitcast i64 %t57 to double
+  br label %b13
+b12:
+  %t59 = bitcast double %t47 to i64
+  %t60 = bitcast double %t48 to i64
+  %t61 = call i64 @jsrt_eq(i64 %t59, i64 %t60)
+  %t62 = bitcast i64 %t61 to double
+  br label %b13
+b13:
+  %t63 = phi double [ %t58, %b11 ], [ %t62, %b12 ]
+  store double %t63, ptr %s0
+  %t64 = load double, ptr %s0
+  %t65 = bitcast double %t64 to i64
+  %t66 = icmp ule i64 %t65, -2251799813685248
+  %t67 = and i1 true, %t66
+  br i1 %t67, label %b14, label %b15
+b14:
+  %t68 = fcmp ueq double %t64, 0.0
+  %t69 = zext i1 %t68 to i64
+  %t70 = or i64 %t69, -1688849860263936
+  %t71 = bitcast i64 %t70 to double
+  br label %b16
+b15:
+  %t72 = bitcast double %t64 to i64
+  %t73 = call i64 @jsrt_not(i64 %t72)
+  %t74 = bitcast i64 %t73 to double
+  br label %b16
+b16:
+  %t75 = phi double [ %t71, %b14 ], [ %t74, %b15 ]
+  store double %t75, ptr %s0
+  %t76 = load double, ptr %s0
+  %t77 = bitcast double %t76 to i64
+  %t78 = icmp ule i64 %t77, -2251799813685248
+  %t79 = and i1 true, %t78
+  br i1 %t79, label %b17, label %b18
+b17:
+  %t80 = fcmp one double %t76, 0.0
+  br label %b19
+b18:
+  %t81 = bitcast double %t76 to i64
+  %t82 = call i64 @jsrt_truthy(i64 %t81)
+  %t83 = icmp ne i64 %t82, 0
+  br label %b19
+b19:
+  %t84 = phi i1 [ %t80, %b17 ], [ %t83, %b18 ]
+  br i1 %t84, label %LL3, label %b20
+b20:
+  %t85 = ptrtoint ptr @.str.1 to i64
+  %t86 = or i64 %t85, -1970324836974592
+  %t87 = bitcast i64 %t86 to double
+  store double %t87, ptr %s0
+  %t88 = load double, ptr %s0
+  ret double %t88
+LL3:
+  br label %LL4
+LL4:
+  br label %LL2
+LL2:
+  %t89 = ptrtoint ptr @.str.2 to i64
+  %t90 = or i64 %t89, -1970324836974592
+  %t91 = bitcast i64 %t90 to double
+  store double %t91, ptr %s0
+  %t92 = load double, ptr %s0
+  ret double %t92
+}
+
+define double @sum_to(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %s2 = alloca double
+  %v.n = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  %t3 = load double, ptr %v.n
+  store double %t3, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t4 = load double, ptr %s0
+  %t5 = load double, ptr %s1
+  %t6 = bitcast double %t4 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 true, %t7
+  %t9 = bitcast double %t5 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 %t8, %t10
+  br i1 %t11, label %b1, label %b2
+b1:
+  %t12 = fcmp oeq double %t4, %t5
+  %t13 = zext i1 %t12 to i64
+  %t14 = or i64 %t13, -1688849860263936
+  %t15 = bitcast i64 %t14 to double
+  br label %b3
+b2:
+  %t16 = bitcast double %t4 to i64
+  %t17 = bitcast double %t5 to i64
+  %t18 = call i64 @jsrt_eq(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  %t21 = load double, ptr %s0
+  %t22 = bitcast double %t21 to i64
+  %t23 = icmp ule i64 %t22, -2251799813685248
+  %t24 = and i1 true, %t23
+  br i1 %t24, label %b4, label %b5
+b4:
+  %t25 = fcmp ueq double %t21, 0.0
+  %t26 = zext i1 %t25 to i64
+  %t27 = or i64 %t26, -1688849860263936
+  %t28 = bitcast i64 %t27 to double
+  br label %b6
+b5:
+  %t29 = bitcast double %t21 to i64
+  %t30 = call i64 @jsrt_not(i64 %t29)
+  %t31 = bitcast i64 %t30 to double
+  br label %b6
+b6:
+  %t32 = phi double [ %t28, %b4 ], [ %t31, %b5 ]
+  store double %t32, ptr %s0
+  %t33 = load double, ptr %s0
+  %t34 = bitcast double %t33 to i64
+  %t35 = icmp ule i64 %t34, -2251799813685248
+  %t36 = and i1 true, %t35
+  br i1 %t36, label %b7, label %b8
+b7:
+  %t37 = fcmp one double %t33, 0.0
+  br label %b9
+b8:
+  %t38 = bitcast double %t33 to i64
+  %t39 = call i64 @jsrt_truthy(i64 %t38)
+  %t40 = icmp ne i64 %t39, 0
+  br label %b9
+b9:
+  %t41 = phi i1 [ %t37, %b7 ], [ %t40, %b8 ]
+  br i1 %t41, label %LL1, label %b10
+b10:
+  store double 0x0000000000000000, ptr %s0
+  %t42 = load double, ptr %s0
+  ret double %t42
+LL1:
+  br label %LL2
+LL2:
+  %t43 = load double, ptr %v.n
+  store double %t43, ptr %s0
+  %t44 = load double, ptr %v.n
+  store double %t44, ptr %s1
+  store double 0x3FF0000000000000, ptr %s2
+  %t45 = load double, ptr %s1
+  %t46 = load double, ptr %s2
+  %t47 = bitcast double %t45 to i64
+  %t48 = icmp ule i64 %t47, -2251799813685248
+  %t49 = and i1 true, %t48
+  %t50 = bitcast double %t46 to i64
+  %t51 = icmp ule i64 %t50, -2251799813685248
+  %t52 = and i1 %t49, %t51
+  br i1 %t52, label %b11, label %b12
+b11:
+  %t53 = fsub double %t45, %t46
+  br label %b13
+b12:
+  %t54 = bitcast double %t45 to i64
+  %t55 = bitcast double %t46 to i64
+  %t56 = call i64 @jsrt_sub(i64 %t54, i64 %t55)
+  %t57 = bitcast i64 %t56 to double
+  br label %b13
+b13:
+  %t58 = phi double [ %t53, %b11 ], [ %t57, %b12 ]
+  store double %t58, ptr %s1
+  %t59 = load double, ptr %s1
+  %t60 = call double @sum_to(double %t59)
+  store double %t60, ptr %s1
+  %t61 = load double, ptr %s0
+  %t62 = load double, ptr %s1
+  %t63 = bitcast double %t61 to i64
+  %t64 = icmp ule i64 %t63, -2251799813685248
+  %t65 = and i1 true, %t64
+  %t66 = bitcast double %t62 to i64
+  %t67 = icmp ule i64 %t66, -2251799813685248
+  %t68 = and i1 %t65, %t67
+  br i1 %t68, label %b14, label %b15
+b14:
+  %t69 = fadd double %t61, %t62
+  br label %b16
+b15:
+  %t70 = bitcast double %t61 to i64
+  %t71 = bitcast double %t62 to i64
+  %t72 = call i64 @jsrt_add(i64 %t70, i64 %t71)
+  %t73 = bitcast i64 %t72 to double
+  br label %b16
+b16:
+  %t74 = phi double [ %t69, %b14 ], [ %t73, %b15 ]
+  store double %t74, ptr %s0
+  %t75 = load double, ptr %s0
+  ret double %t75
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  store double 0x4008000000000000, ptr %s0
+  %t1 = load double, ptr %s0
+  %t2 = bitcast double %t1 to i64
+  %t3 = icmp ule i64 %t2, -2251799813685248
+  %t4 = and i1 true, %t3
+  br i1 %t4, label %b1, label %b2
+b1:
+  %t5 = fneg double %t1
+  br label %b3
+b2:
+  %t6 = bitcast double %t1 to i64
+  %t7 = call i64 @jsrt_neg(i64 %t6)
+  %t8 = bitcast i64 %t7 to double
+  br label %b3
+b3:
+  %t9 = phi double [ %t5, %b1 ], [ %t8, %b2 ]
+  store double %t9, ptr %s0
+  %t10 = load double, ptr %s0
+  %t11 = call double @sign(double %t10)
+  store double %t11, ptr %s0
+  %t12 = load double, ptr %s0
+  %t13 = bitcast double %t12 to i64
+  call void @jsrt_print_value(i64 %t13, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t14 = load double, ptr %s0
+  %t15 = call double @sign(double %t14)
+  store double %t15, ptr %s0
+  %t16 = load double, ptr %s0
+  %t17 = bitcast double %t16 to i64
+  call void @jsrt_print_value(i64 %t17, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4020000000000000, ptr %s0
+  %t18 = load double, ptr %s0
+  %t19 = call double @sign(double %t18)
+  store double %t19, ptr %s0
+  %t20 = load double, ptr %s0
+  %t21 = bitcast double %t20 to i64
+  call void @jsrt_print_value(i64 %t21, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4059000000000000, ptr %s0
+  %t22 = load double, ptr %s0
+  %t23 = call double @sum_to(double %t22)
+  store double %t23, ptr %s0
+  %t24 = load double, ptr %s0
+  %t25 = bitcast double %t24 to i64
+  call void @jsrt_print_value(i64 %t25, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4014000000000000, ptr %s0
+  %t26 = load double, ptr %s0
+  %t27 = call double @sum_to(double %t26)
+  store double %t27, ptr %s0
+  %t28 = load double, ptr %s0
+  ret double %t28
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+@.str.0 = private unnamed_addr constant [9 x i8] c"\6E\65\67\61\74\69\76\65\00"
+@.str.1 = private unnamed_addr constant [5 x i8] c"\7A\65\72\6F\00"
+@.str.2 = private unnamed_addr constant [9 x i8] c"\70\6F\73\69\74\69\76\65\00"
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_eq(i64, i64)
+declare i64 @jsrt_lt(i64, i64)
+declare i64 @jsrt_neg(i64)
+declare i64 @jsrt_not(i64)
+declare i64 @jsrt_sub(i64, i64)
+declare i64 @jsrt_truthy(i64)
@@ -0,0 +1,528 @@
+	.text
+	.globl sign
+	.type sign, @function
+sign:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD0(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsign.1
+	cmp %rdx, %rcx
+	ja .Lsign.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsign.2
+.Lsign.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lsign.2:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsign.3
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsign.4
+.Lsign.3:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lsign.4:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsign.5
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lsign_L1
+	jmp .Lsign.6
+.Lsign.5:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lsign_L1
+.Lsign.6:
+	leaq .LC0(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	jmp .Lsign_L2
+.Lsign_L1:
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsign.7
+	cmp %rdx, %rcx
+	ja .Lsign.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsign.8
+.Lsign.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lsign.8:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsign.9
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsign.10
+.Lsign.9:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lsign.10:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsign.11
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lsign_L3
+	jmp .Lsign.12
+.Lsign.11:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lsign_L3
+.Lsign.12:
+	leaq .LC1(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	jmp .Lsign_L4
+.Lsign_L3:
+.Lsign_L4:
+.Lsign_L2:
+	leaq .LC2(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl sum_to
+	.type sum_to, @function
+sum_to:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.13
+	cmp %rdx, %rcx
+	ja .Lsum_to.13
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsum_to.14
+.Lsum_to.13:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lsum_to.14:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.15
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsum_to.16
+.Lsum_to.15:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lsum_to.16:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.17
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lsum_to_L1
+	jmp .Lsum_to.18
+.Lsum_to.17:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lsum_to_L1
+.Lsum_to.18:
+	mov .LCD3(%rip), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	jmp .Lsum_to_L2
+.Lsum_to_L1:
+.Lsum_to_L2:
+	mov -8(%rbp), %rax
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.19
+	cmp %rdx, %rcx
+	ja .Lsum_to.19
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	subsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lsum_to.20
+.Lsum_to.19:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_sub
+	mov %rbx, %rsp
+.Lsum_to.20:
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sum_to
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.21
+	cmp %rdx, %rcx
+	ja .Lsum_to.21
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lsum_to.22
+.Lsum_to.21:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lsum_to.22:
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD5(%rip), %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sign
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD6(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sign
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD7(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sign
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD8(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sum_to
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD9(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sum_to
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+.LC0:
+	.asciz "negative"
+.LC1:
+	.asciz "zero"
+.LC2:
+	.asciz "positive"
+	.p2align 3
+.LCD0:
+	.double 0
+.LCD1:
+	.double 0
+.LCD2:
+	.double 0
+.LCD3:
+	.double 0
+.LCD4:
+	.double 1
+.LCD5:
+	.double 3
+.LCD6:
+	.double 0
+.LCD7:
+	.double 8
+.LCD8:
+	.double 100
+.LCD9:
+	.double 5
+	.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,1611 @@
+	.text
+	.global nothing
+	.type nothing, %function
+	.p2align 2
+nothing:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	mov x0, #0xfffc000000000000
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	adrp x0, .LC0
+	add x0, x0, :lo12:.LC0
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.1
+	cmp x1, x9
+	b.hi .Lmain.1
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.2
+.Lmain.1:
+	bl jsrt_eq
+.Lmain.2:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	bl nothing
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.3
+	cmp x1, x9
+	b.hi .Lmain.3
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.4
+.Lmain.3:
+	bl jsrt_eq
+.Lmain.4:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.5
+	cmp x1, x9
+	b.hi .Lmain.5
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.6
+.Lmain.5:
+	bl jsrt_eq
+.Lmain.6:
+	str x0, [sp, #-16]!
+	bl nothing
+	str x0, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.7
+	cmp x1, x9
+	b.hi .Lmain.7
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.8
+.Lmain.7:
+	bl jsrt_eq
+.Lmain.8:
+	str x0, [sp, #-16]!
+	adrp x0, .LC1
+	add x0, x0, :lo12:.LC1
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.9
+	cmp x1, x9
+	b.hi .Lmain.9
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.10
+.Lmain.9:
+	bl jsrt_eq
+.Lmain.10:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffa000000000000
+	orr x0, x0, #1
+	str x0, [sp, #-16]!
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.11
+	cmp x1, x9
+	b.hi .Lmain.11
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.12
+.Lmain.11:
+	bl jsrt_eq
+.Lmain.12:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #80]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #96
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	adrp x0, .LC2
+	add x0, x0, :lo12:.LC2
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.13
+	cmp x1, x9
+	b.hi .Lmain.13
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.14
+.Lmain.13:
+	bl jsrt_strict_eq
+.Lmain.14:
+	str x0, [sp, #-16]!
+	adrp x0, .LC3
+	add x0, x0, :lo12:.LC3
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC4
+	add x0, x0, :lo12:.LC4
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.15
+	cmp x1, x9
+	b.hi .Lmain.15
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.16
+.Lmain.15:
+	bl jsrt_strict_eq
+.Lmain.16:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.17
+	cmp x1, x9
+	b.hi .Lmain.17
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.18
+.Lmain.17:
+	bl jsrt_strict_eq
+.Lmain.18:
+	str x0, [sp, #-16]!
+	bl nothing
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.19
+	cmp x1, x9
+	b.hi .Lmain.19
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ne
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.20
+.Lmain.19:
+	bl jsrt_ne
+.Lmain.20:
+	str x0, [sp, #-16]!
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	adrp x0, .LC5
+	add x0, x0, :lo12:.LC5
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.21
+	cmp x1, x9
+	b.hi .Lmain.21
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ne
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.22
+.Lmain.21:
+	bl jsrt_ne
+.Lmain.22:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #80
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	adrp x0, .LC6
+	add x0, x0, :lo12:.LC6
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.23
+	cmp x1, x9
+	b.hi .Lmain.23
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.24
+.Lmain.23:
+	bl jsrt_lt
+.Lmain.24:
+	str x0, [sp, #-16]!
+	adrp x0, .LC7
+	add x0, x0, :lo12:.LC7
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC8
+	add x0, x0, :lo12:.LC8
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.25
+	cmp x1, x9
+	b.hi .Lmain.25
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.26
+.Lmain.25:
+	bl jsrt_lt
+.Lmain.26:
+	str x0, [sp, #-16]!
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	adrp x0, .LC9
+	add x0, x0, :lo12:.LC9
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.27
+	cmp x1, x9
+	b.hi .Lmain.27
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.28
+.Lmain.27:
+	bl jsrt_lt
+.Lmain.28:
+	str x0, [sp, #-16]!
+	adrp x0, .LC10
+	add x0, x0, :lo12:.LC10
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.29
+	cmp x1, x9
+	b.hi .Lmain.29
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.30
+.Lmain.29:
+	bl jsrt_lt
+.Lmain.30:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	adrp x0, .LCD10
+	ldr d0, [x0, :lo12:.LCD10]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.31
+	cmp x1, x9
+	b.hi .Lmain.31
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.32
+.Lmain.31:
+	bl jsrt_lt
+.Lmain.32:
+	str x0, [sp, #-16]!
+	bl nothing
+	str x0, [sp, #-16]!
+	adrp x0, .LCD11
+	ldr d0, [x0, :lo12:.LCD11]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.33
+	cmp x1, x9
+	b.hi .Lmain.33
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.34
+.Lmain.33:
+	bl jsrt_lt
+.Lmain.34:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #80]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #96
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD12
+	ldr d0, [x0, :lo12:.LCD12]
+	str d0, [sp, #-16]!
+	adrp x0, .LC11
+	add x0, x0, :lo12:.LC11
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.35
+	cmp x1, x9
+	b.hi .Lmain.35
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ge
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.36
+.Lmain.35:
+	bl jsrt_ge
+.Lmain.36:
+	str x0, [sp, #-16]!
+	adrp x0, .LC12
+	add x0, x0, :lo12:.LC12
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC13
+	add x0, x0, :lo12:.LC13
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.37
+	cmp x1, x9
+	b.hi .Lmain.37
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, gt
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.38
+.Lmain.37:
+	bl jsrt_gt
+.Lmain.38:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffa000000000000
+	orr x0, x0, #1
+	str x0, [sp, #-16]!
+	adrp x0, .LCD13
+	ldr d0, [x0, :lo12:.LCD13]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.39
+	cmp x1, x9
+	b.hi .Lmain.39
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, gt
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.40
+.Lmain.39:
+	bl jsrt_gt
+.Lmain.40:
+	str x0, [sp, #-16]!
+	adrp x0, .LC14
+	add x0, x0, :lo12:.LC14
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD14
+	ldr d0, [x0, :lo12:.LCD14]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.41
+	cmp x1, x9
+	b.hi .Lmain.41
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ls
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.42
+.Lmain.41:
+	bl jsrt_le
+.Lmain.42:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #64
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD15
+	ldr d0, [x0, :lo12:.LCD15]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD16
+	ldr d0, [x0, :lo12:.LCD16]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD17
+	ldr d0, [x0, :lo12:.LCD17]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD18
+	ldr d0, [x0, :lo12:.LCD18]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD19
+	ldr d0, [x0, :lo12:.LCD19]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD20
+	ldr d0, [x0, :lo12:.LCD20]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LC15
+	add x0, x0, :lo12:.LC15
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC16
+	add x0, x0, :lo12:.LC16
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.43
+	cmp x1, x9
+	b.hi .Lmain.43
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	b .Lmain.44
+.Lmain.43:
+	bl jsrt_div
+.Lmain.44:
+	str x0, [sp, #-16]!
+	adrp x0, .LC17
+	add x0, x0, :lo12:.LC17
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD21
+	ldr d0, [x0, :lo12:.LCD21]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.45
+	cmp x1, x9
+	b.hi .Lmain.45
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	b .Lmain.46
+.Lmain.45:
+	bl jsrt_div
+.Lmain.46:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffa000000000000
+	orr x0, x0, #1
+	str x0, [sp, #-16]!
+	adrp x0, .LCD22
+	ldr d0, [x0, :lo12:.LCD22]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.47
+	cmp x1, x9
+	b.hi .Lmain.47
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.48
+.Lmain.47:
+	bl jsrt_add
+.Lmain.48:
+	str x0, [sp, #-16]!
+	adrp x0, .LC18
+	add x0, x0, :lo12:.LC18
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD23
+	ldr d0, [x0, :lo12:.LCD23]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.49
+	cmp x1, x9
+	b.hi .Lmain.49
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.50
+.Lmain.49:
+	bl jsrt_add
+.Lmain.50:
+	str x0, [sp, #-16]!
+	mov x0, #0xfffb000000000000
+	str x0, [sp, #-16]!
+	adrp x0, .LCD24
+	ldr d0, [x0, :lo12:.LCD24]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.51
+	cmp x1, x9
+	b.hi .Lmain.51
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.52
+.Lmain.51:
+	bl jsrt_add
+.Lmain.52:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #112]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #96]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #80]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #128
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC19
+	add x0, x0, :lo12:.LC19
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD25
+	ldr d0, [x0, :lo12:.LCD25]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.53
+	cmp x1, x9
+	b.hi .Lmain.53
+	fmov d0, x0
+	fmov d1, x1
+	fsub d0, d0, d1
+	fmov x0, d0
+	b .Lmain.54
+.Lmain.53:
+	bl jsrt_sub
+.Lmain.54:
+	str x0, [sp, #-16]!
+	adrp x0, .LC20
+	add x0, x0, :lo12:.LC20
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC21
+	add x0, x0, :lo12:.LC21
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.55
+	cmp x1, x9
+	b.hi .Lmain.55
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.56
+.Lmain.55:
+	bl jsrt_mul
+.Lmain.56:
+	str x0, [sp, #-16]!
+	adrp x0, .LC22
+	add x0, x0, :lo12:.LC22
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD26
+	ldr d0, [x0, :lo12:.LCD26]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	bl jsrt_mod
+	str x0, [sp, #-16]!
+	adrp x0, .LC23
+	add x0, x0, :lo12:.LC23
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.57
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	b .Lmain.58
+.Lmain.57:
+	bl jsrt_neg
+.Lmain.58:
+	str x0, [sp, #-16]!
+	bl nothing
+	str x0, [sp, #-16]!
+	adrp x0, .LCD27
+	ldr d0, [x0, :lo12:.LCD27]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.59
+	cmp x1, x9
+	b.hi .Lmain.59
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.60
+.Lmain.59:
+	bl jsrt_add
+.Lmain.60:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #80
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC24
+	add x0, x0, :lo12:.LC24
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD28
+	ldr d0, [x0, :lo12:.LCD28]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.61
+	cmp x1, x9
+	b.hi .Lmain.61
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.62
+.Lmain.61:
+	bl jsrt_mul
+.Lmain.62:
+	str x0, [sp, #-16]!
+	adrp x0, .LC25
+	add x0, x0, :lo12:.LC25
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD29
+	ldr d0, [x0, :lo12:.LCD29]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.63
+	cmp x1, x9
+	b.hi .Lmain.63
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.64
+.Lmain.63:
+	bl jsrt_mul
+.Lmain.64:
+	str x0, [sp, #-16]!
+	adrp x0, .LC26
+	add x0, x0, :lo12:.LC26
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD30
+	ldr d0, [x0, :lo12:.LCD30]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.65
+	cmp x1, x9
+	b.hi .Lmain.65
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.66
+.Lmain.65:
+	bl jsrt_mul
+.Lmain.66:
+	str x0, [sp, #-16]!
+	adrp x0, .LC27
+	add x0, x0, :lo12:.LC27
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD31
+	ldr d0, [x0, :lo12:.LCD31]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.67
+	cmp x1, x9
+	b.hi .Lmain.67
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.68
+.Lmain.67:
+	bl jsrt_mul
+.Lmain.68:
+	str x0, [sp, #-16]!
+	adrp x0, .LC28
+	add x0, x0, :lo12:.LC28
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD32
+	ldr d0, [x0, :lo12:.LCD32]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.69
+	cmp x1, x9
+	b.hi .Lmain.69
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.70
+.Lmain.69:
+	bl jsrt_mul
+.Lmain.70:
+	str x0, [sp, #-16]!
+	adrp x0, .LC29
+	add x0, x0, :lo12:.LC29
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD33
+	ldr d0, [x0, :lo12:.LCD33]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.71
+	cmp x1, x9
+	b.hi .Lmain.71
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.72
+.Lmain.71:
+	bl jsrt_mul
+.Lmain.72:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #80]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #96
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC30
+	add x0, x0, :lo12:.LC30
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD34
+	ldr d0, [x0, :lo12:.LCD34]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.73
+	cmp x1, x9
+	b.hi .Lmain.73
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.74
+.Lmain.73:
+	bl jsrt_mul
+.Lmain.74:
+	str x0, [sp, #-16]!
+	adrp x0, .LC31
+	add x0, x0, :lo12:.LC31
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD35
+	ldr d0, [x0, :lo12:.LCD35]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.75
+	cmp x1, x9
+	b.hi .Lmain.75
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.76
+.Lmain.75:
+	bl jsrt_mul
+.Lmain.76:
+	str x0, [sp, #-16]!
+	adrp x0, .LC32
+	add x0, x0, :lo12:.LC32
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD36
+	ldr d0, [x0, :lo12:.LCD36]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.77
+	cmp x1, x9
+	b.hi .Lmain.77
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.78
+.Lmain.77:
+	bl jsrt_mul
+.Lmain.78:
+	str x0, [sp, #-16]!
+	adrp x0, .LC33
+	add x0, x0, :lo12:.LC33
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD37
+	ldr d0, [x0, :lo12:.LCD37]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.79
+	cmp x1, x9
+	b.hi .Lmain.79
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.80
+.Lmain.79:
+	bl jsrt_mul
+.Lmain.80:
+	str x0, [sp, #-16]!
+	adrp x0, .LC34
+	add x0, x0, :lo12:.LC34
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD38
+	ldr d0, [x0, :lo12:.LCD38]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.81
+	cmp x1, x9
+	b.hi .Lmain.81
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.82
+.Lmain.81:
+	bl jsrt_mul
+.Lmain.82:
+	str x0, [sp, #-16]!
+	adrp x0, .LC35
+	add x0, x0, :lo12:.LC35
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD39
+	ldr d0, [x0, :lo12:.LCD39]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.83
+	cmp x1, x9
+	b.hi .Lmain.83
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.84
+.Lmain.83:
+	bl jsrt_mul
+.Lmain.84:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #80]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #96
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC36
+	add x0, x0, :lo12:.LC36
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD40
+	ldr d0, [x0, :lo12:.LCD40]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.85
+	cmp x1, x9
+	b.hi .Lmain.85
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.86
+.Lmain.85:
+	bl jsrt_mul
+.Lmain.86:
+	str x0, [sp, #-16]!
+	adrp x0, .LC37
+	add x0, x0, :lo12:.LC37
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD41
+	ldr d0, [x0, :lo12:.LCD41]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.87
+	cmp x1, x9
+	b.hi .Lmain.87
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.88
+.Lmain.87:
+	bl jsrt_mul
+.Lmain.88:
+	str x0, [sp, #-16]!
+	adrp x0, .LC38
+	add x0, x0, :lo12:.LC38
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD42
+	ldr d0, [x0, :lo12:.LCD42]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.89
+	cmp x1, x9
+	b.hi .Lmain.89
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.90
+.Lmain.89:
+	bl jsrt_mul
+.Lmain.90:
+	str x0, [sp, #-16]!
+	adrp x0, .LC39
+	add x0, x0, :lo12:.LC39
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD43
+	ldr d0, [x0, :lo12:.LCD43]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.91
+	cmp x1, x9
+	b.hi .Lmain.91
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.92
+.Lmain.91:
+	bl jsrt_mul
+.Lmain.92:
+	str x0, [sp, #-16]!
+	adrp x0, .LC40
+	add x0, x0, :lo12:.LC40
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD44
+	ldr d0, [x0, :lo12:.LCD44]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.93
+	cmp x1, x9
+	b.hi .Lmain.93
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	b .Lmain.94
+.Lmain.93:
+	bl jsrt_mul
+.Lmain.94:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #64]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #80
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD45
+	ldr d0, [x0, :lo12:.LCD45]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+.LC0:
+	.asciz "1"
+.LC1:
+	.asciz ""
+.LC2:
+	.asciz "1"
+.LC3:
+	.asciz "a"
+.LC4:
+	.asciz "a"
+.LC5:
+	.asciz "2"
+.LC6:
+	.asciz "2"
+.LC7:
+	.asciz "10"
+.LC8:
+	.asciz "9"
+.LC9:
+	.asciz "9"
+.LC10:
+	.asciz "a"
+.LC11:
+	.asciz "2"
+.LC12:
+	.asciz "b"
+.LC13:
+	.asciz "a"
+.LC14:
+	.asciz "3"
+.LC15:
+	.asciz "6"
+.LC16:
+	.asciz "3"
+.LC17:
+	.asciz "x"
+.LC18:
+	.asciz "1"
+.LC19:
+	.asciz "8"
+.LC20:
+	.asciz "2"
+.LC21:
+	.asciz "4"
+.LC22:
+	.asciz "7"
+.LC23:
+	.asciz "5"
+.LC24:
+	.asciz " 12 "
+.LC25:
+	.asciz "0x1f"
+.LC26:
+	.asciz "0b101"
+.LC27:
+	.asciz "0o17"
+.LC28:
+	.asciz "-Infinity"
+.LC29:
+	.asciz "1e3"
+.LC30:
+	.asciz ".5"
+.LC31:
+	.asciz "5."
+.LC32:
+	.asciz "1e"
+.LC33:
+	.asciz "-0x10"
+.LC34:
+	.asciz "1.5e-3"
+.LC35:
+	.asciz "inf"
+.LC36:
+	.asciz "1e400"
+.LC37:
+	.asciz "1e-400"
+.LC38:
+	.asciz "\011\012"
+.LC39:
+	.asciz "+7"
+.LC40:
+	.asciz "0.1"
+	.p2align 3
+.LCD0:
+	.double 1
+.LCD1:
+	.double 0
+.LCD2:
+	.double 0
+.LCD3:
+	.double 0
+.LCD4:
+	.double 1
+.LCD5:
+	.double 1
+.LCD6:
+	.double 1
+.LCD7:
+	.double 1
+.LCD8:
+	.double 10
+.LCD9:
+	.double 1
+.LCD10:
+	.double 1
+.LCD11:
+	.double 1
+.LCD12:
+	.double 2
+.LCD13:
+	.double 0
+.LCD14:
+	.double 2
+.LCD15:
+	.double 1
+.LCD16:
+	.double 0
+.LCD17:
+	.double 1
+.LCD18:
+	.double 0
+.LCD19:
+	.double 0
+.LCD20:
+	.double 0
+.LCD21:
+	.double 2
+.LCD22:
+	.double 1
+.LCD23:
+	.double 1
+.LCD24:
+	.double 1
+.LCD25:
+	.double 3
+.LCD26:
+	.double 4
+.LCD27:
+	.double 1
+.LCD28:
+	.double 1
+.LCD29:
+	.double 1
+.LCD30:
+	.double 1
+.LCD31:
+	.double 1
+.LCD32:
+	.double 1
+.LCD33:
+	.double 1
+.LCD34:
+	.double 1
+.LCD35:
+	.double 1
+.LCD36:
+	.double 1
+.LCD37:
+	.double 1
+.LCD38:
+	.double 1
+.LCD39:
+	.double 1
+.LCD40:
+	.double 1
+.LCD41:
+	.double 1
+.LCD42:
+	.double 1
+.LCD43:
+	.double 1
+.LCD44:
+	.double 3
+.LCD45:
+	.double 0
+	.section .note.GNU-stack,"",%progbits
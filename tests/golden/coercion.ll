@@ -0,0 +1,1625 @@
+; Generated by js-compiler
+
+define double @nothing() {
+entry:
+  ret double 0xFFFC000000000000
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %s2 = alloca double
+  %s3 = alloca double
+  %s4 = alloca double
+  %s5 = alloca double
+  %s6 = alloca double
+  %s7 = alloca double
+  %s8 = alloca double
+  store double 0x3FF0000000000000, ptr %s0
+  %t1 = ptrtoint ptr @.str.0 to i64
+  %t2 = or i64 %t1, -1970324836974592
+  %t3 = bitcast i64 %t2 to double
+  store double %t3, ptr %s1
+  %t4 = load double, ptr %s0
+  %t5 = load double, ptr %s1
+  %t6 = bitcast double %t4 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 true, %t7
+  %t9 = bitcast double %t5 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 %t8, %t10
+  br i1 %t11, label %b1, label %b2
+b1:
+  %t12 = fcmp oeq double %t4, %t5
+  %t13 = zext i1 %t12 to i64
+  %t14 = or i64 %t13, -1688849860263936
+  %t15 = bitcast i64 %t14 to double
+  br label %b3
+b2:
+  %t16 = bitcast double %t4 to i64
+  %t17 = bitcast double %t5 to i64
+  %t18 = call i64 @jsrt_eq(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  store double 0xFFFB000000000000, ptr %s1
+  %t21 = call double @nothing()
+  store double %t21, ptr %s2
+  %t22 = load double, ptr %s1
+  %t23 = load double, ptr %s2
+  %t24 = bitcast double %t22 to i64
+  %t25 = icmp ule i64 %t24, -2251799813685248
+  %t26 = and i1 true, %t25
+  %t27 = bitcast double %t23 to i64
+  %t28 = icmp ule i64 %t27, -2251799813685248
+  %t29 = and i1 %t26, %t28
+  br i1 %t29, label %b4, label %b5
+b4:
+  %t30 = fcmp oeq double %t22, %t23
+  %t31 = zext i1 %t30 to i64
+  %t32 = or i64 %t31, -1688849860263936
+  %t33 = bitcast i64 %t32 to double
+  br label %b6
+b5:
+  %t34 = bitcast double %t22 to i64
+  %t35 = bitcast double %t23 to i64
+  %t36 = call i64 @jsrt_eq(i64 %t34, i64 %t35)
+  %t37 = bitcast i64 %t36 to double
+  br label %b6
+b6:
+  %t38 = phi double [ %t33, %b4 ], [ %t37, %b5 ]
+  store double %t38, ptr %s1
+  store double 0xFFFB000000000000, ptr %s2
+  store double 0x0000000000000000, ptr %s3
+  %t39 = load double, ptr %s2
+  %t40 = load double, ptr %s3
+  %t41 = bitcast double %t39 to i64
+  %t42 = icmp ule i64 %t41, -2251799813685248
+  %t43 = and i1 true, %t42
+  %t44 = bitcast double %t40 to i64
+  %t45 = icmp ule i64 %t44, -2251799813685248
+  %t46 = and i1 %t43, %t45
+  br i1 %t46, label %b7, label %b8
+b7:
+  %t47 = fcmp oeq double %t39, %t40
+  %t48 = zext i1 %t47 to i64
+  %t49 = or i64 %t48, -1688849860263936
+  %t50 = bitcast i64 %t49 to double
+  br label %b9
+b8:
+  %t51 = bitcast double %t39 to i64
+  %t52 = bitcast double %t40 to i64
+  %t53 = call i64 @jsrt_eq(i64 %t51, i64 %t52)
+  %t54 = bitcast i64 %t53 to double
+  br label %b9
+b9:
+  %t55 = phi double [ %t50, %b7 ], [ %t54, %b8 ]
+  store double %t55, ptr %s2
+  %t56 = call double @nothing()
+  store double %t56, ptr %s3
+  store double 0x0000000000000000, ptr %s4
+  %t57 = load double, ptr %s3
+  %t58 = load double, ptr %s4
+  %t59 = bitcast double %t57 to i64
+  %t60 = icmp ule i64 %t59, -2251799813685248
+  %t61 = and i1 true, %t60
+  %t62 = bitcast double %t58 to i64
+  %t63 = icmp ule i64 %t62, -2251799813685248
+  %t64 = and i1 %t61, %t63
+  br i1 %t64, label %b10, label %b11
+b10:
+  %t65 = fcmp oeq double %t57, %t58
+  %t66 = zext i1 %t65 to i64
+  %t67 = or i64 %t66, -1688849860263936
+  %t68 = bitcast i64 %t67 to double
+  br label %b12
+b11:
+  %t69 = bitcast double %t57 to i64
+  %t70 = bitcast double %t58 to i64
+  %t71 = call i64 @jsrt_eq(i64 %t69, i64 %t70)
+  %t72 = bitcast i64 %t71 to double
+  br label %b12
+b12:
+  %t73 = phi double [ %t68, %b10 ], [ %t72, %b11 ]
+  store double %t73, ptr %s3
+  %t74 = ptrtoint ptr @.str.1 to i64
+  %t75 = or i64 %t74, -1970324836974592
+  %t76 = bitcast i64 %t75 to double
+  store double %t76, ptr %s4
+  store double 0x0000000000000000, ptr %s5
+  %t77 = load double, ptr %s4
+  %t78 = load double, ptr %s5
+  %t79 = bitcast double %t77 to i64
+  %t80 = icmp ule i64 %t79, -2251799813685248
+  %t81 = and i1 true, %t80
+  %t82 = bitcast double %t78 to i64
+  %t83 = icmp ule i64 %t82, -2251799813685248
+  %t84 = and i1 %t81, %t83
+  br i1 %t84, label %b13, label %b14
+b13:
+  %t85 = fcmp oeq double %t77, %t78
+  %t86 = zext i1 %t85 to i64
+  %t87 = or i64 %t86, -1688849860263936
+  %t88 = bitcast i64 %t87 to double
+  br label %b15
+b14:
+  %t89 = bitcast double %t77 to i64
+  %t90 = bitcast double %t78 to i64
+  %t91 = call i64 @jsrt_eq(i64 %t89, i64 %t90)
+  %t92 = bitcast i64 %t91 to double
+  br label %b15
+b15:
+  %t93 = phi double [ %t88, %b13 ], [ %t92, %b14 ]
+  store double %t93, ptr %s4
+  store double 0xFFFA000000000001, ptr %s5
+  store double 0x3FF0000000000000, ptr %s6
+  %t94 = load double, ptr %s5
+  %t95 = load double, ptr %s6
+  %t96 = bitcast double %t94 to i64
+  %t97 = icmp ule i64 %t96, -2251799813685248
+  %t98 = and i1 true, %t97
+  %t99 = bitcast double %t95 to i64
+  %t100 = icmp ule i64 %t99, -2251799813685248
+  %t101 = and i1 %t98, %t100
+  br i1 %t101, label %b16, label %b17
+b16:
+  %t102 = fcmp oeq double %t94, %t95
+  %t103 = zext i1 %t102 to i64
+  %t104 = or i64 %t103, -1688849860263936
+  %t105 = bitcast i64 %t104 to double
+  br label %b18
+b17:
+  %t106 = bitcast double %t94 to i64
+  %t107 = bitcast double %t95 to i64
+  %t108 = call i64 @jsrt_eq(i64 %t106, i64 %t107)
+  %t109 = bitcast i64 %t108 to double
+  br label %b18
+b18:
+  %t110 = phi double [ %t105, %b16 ], [ %t109, %b17 ]
+  store double %t110, ptr %s5
+  %t111 = load double, ptr %s0
+  %t112 = load double, ptr %s1
+  %t113 = load double, ptr %s2
+  %t114 = load double, ptr %s3
+  %t115 = load double, ptr %s4
+  %t116 = load double, ptr %s5
+  %t117 = bitcast double %t111 to i64
+  call void @jsrt_print_value(i64 %t117, i32 32)
+  %t118 = bitcast double %t112 to i64
+  call void @jsrt_print_value(i64 %t118, i32 32)
+  %t119 = bitcast double %t113 to i64
+  call void @jsrt_print_value(i64 %t119, i32 32)
+  %t120 = bitcast double %t114 to i64
+  call void @jsrt_print_value(i64 %t120, i32 32)
+  %t121 = bitcast double %t115 to i64
+  call void @jsrt_print_value(i64 %t121, i32 32)
+  %t122 = bitcast double %t116 to i64
+  call void @jsrt_print_value(i64 %t122, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  %t123 = ptrtoint ptr @.str.2 to i64
+  %t124 = or i64 %t123, -1970324836974592
+  %t125 = bitcast i64 %t124 to double
+  store double %t125, ptr %s1
+  %t126 = load double, ptr %s0
+  %t127 = load double, ptr %s1
+  %t128 = bitcast double %t126 to i64
+  %t129 = icmp ule i64 %t128, -2251799813685248
+  %t130 = and i1 true, %t129
+  %t131 = bitcast double %t127 to i64
+  %t132 = icmp ule i64 %t131, -2251799813685248
+  %t133 = and i1 %t130, %t132
+  br i1 %t133, label %b19, label %b20
+b19:
+  %t134 = fcmp oeq double %t126, %t127
+  %t135 = zext i1 %t134 to i64
+  %t136 = or i64 %t135, -1688849860263936
+  %t137 = bitcast i64 %t136 to double
+  br label %b21
+b20:
+  %t138 = bitcast double %t126 to i64
+  %t139 = bitcast double %t127 to i64
+  %t140 = call i64 @jsrt_strict_eq(i64 %t138, i64 %t139)
+  %t141 = bitcast i64 %t140 to double
+  br label %b21
+b21:
+  %t142 = phi double [ %t137, %b19 ], [ %t141, %b20 ]
+  store double %t142, ptr %s0
+  %t143 = ptrtoint ptr @.str.3 to i64
+  %t144 = or i64 %t143, -1970324836974592
+  %t145 = bitcast i64 %t144 to double
+  store double %t145, ptr %s1
+  %t146 = ptrtoint ptr @.str.4 to i64
+  %t147 = or i64 %t146, -1970324836974592
+  %t148 = bitcast i64 %t147 to double
+  store double %t148, ptr %s2
+  %t149 = load double, ptr %s1
+  %t150 = load double, ptr %s2
+  %t151 = bitcast double %t149 to i64
+  %t152 = icmp ule i64 %t151, -2251799813685248
+  %t153 = and i1 true, %t152
+  %t154 = bitcast double %t150 to i64
+  %t155 = icmp ule i64 %t154, -2251799813685248
+  %t156 = and i1 %t153, %t155
+  br i1 %t156, label %b22, label %b23
+b22:
+  %t157 = fcmp oeq double %t149, %t150
+  %t158 = zext i1 %t157 to i64
+  %t159 = or i64 %t158, -1688849860263936
+  %t160 = bitcast i64 %t159 to double
+  br label %b24
+b23:
+  %t161 = bitcast double %t149 to i64
+  %t162 = bitcast double %t150 to i64
+  %t163 = call i64 @jsrt_strict_eq(i64 %t161, i64 %t162)
+  %t164 = bitcast i64 %t163 to double
+  br label %b24
+b24:
+  %t165 = phi double [ %t160, %b22 ], [ %t164, %b23 ]
+  store double %t165, ptr %s1
+  store double 0xFFFB000000000000, ptr %s2
+  store double 0xFFFB000000000000, ptr %s3
+  %t166 = load double, ptr %s2
+  %t167 = load double, ptr %s3
+  %t168 = bitcast double %t166 to i64
+  %t169 = icmp ule i64 %t168, -2251799813685248
+  %t170 = and i1 true, %t169
+  %t171 = bitcast double %t167 to i64
+  %t172 = icmp ule i64 %t171, -2251799813685248
+  %t173 = and i1 %t170, %t172
+  br i1 %t173, label %b25, label %b26
+b25:
+  %t174 = fcmp oeq double %t166, %t167
+  %t175 = zext i1 %t174 to i64
+  %t176 = or i64 %t175, -1688849860263936
+  %t177 = bitcast i64 %t176 to double
+  br label %b27
+b26:
+  %t178 = bitcast double %t166 to i64
+  %t179 = bitcast double %t167 to i64
+  %t180 = call i64 @jsrt_strict_eq(i64 %t178, i64 %t179)
+  %t181 = bitcast i64 %t180 to double
+  br label %b27
+b27:
+  %t182 = phi double [ %t177, %b25 ], [ %t181, %b26 ]
+  store double %t182, ptr %s2
+  %t183 = call double @nothing()
+  store double %t183, ptr %s3
+  store double 0xFFFB000000000000, ptr %s4
+  %t184 = load double, ptr %s3
+  %t185 = load double, ptr %s4
+  %t186 = bitcast double %t184 to i64
+  %t187 = icmp ule i64 %t186, -2251799813685248
+  %t188 = and i1 true, %t187
+  %t189 = bitcast double %t185 to i64
+  %t190 = icmp ule i64 %t189, -2251799813685248
+  %t191 = and i1 %t188, %t190
+  br i1 %t191, label %b28, label %b29
+b28:
+  %t192 = fcmp une double %t184, %t185
+  %t193 = zext i1 %t192 to i64
+  %t194 = or i64 %t193, -1688849860263936
+  %t195 = bitcast i64 %t194 to double
+  br label %b30
+b29:
+  %t196 = bitcast double %t184 to i64
+  %t197 = bitcast double %t185 to i64
+  %t198 = call i64 @jsrt_ne(i64 %t196, i64 %t197)
+  %t199 = bitcast i64 %t198 to double
+  br label %b30
+b30:
+  %t200 = phi double [ %t195, %b28 ], [ %t199, %b29 ]
+  store double %t200, ptr %s3
+  store double 0x3FF0000000000000, ptr %s4
+  %t201 = ptrtoint ptr @.str.5 to i64
+  %t202 = or i64 %t201, -1970324836974592
+  %t203 = bitcast i64 %t202 to double
+  store double %t203, ptr %s5
+  %t204 = load double, ptr %s4
+  %t205 = load double, ptr %s5
+  %t206 = bitcast double %t204 to i64
+  %t207 = icmp ule i64 %t206, -2251799813685248
+  %t208 = and i1 true, %t207
+  %t209 = bitcast double %t205 to i64
+  %t210 = icmp ule i64 %t209, -2251799813685248
+  %t211 = and i1 %t208, %t210
+  br i1 %t211, label %b31, label %b32
+b31:
+  %t212 = fcmp une double %t204, %t205
+  %t213 = zext i1 %t212 to i64
+  %t214 = or i64 %t213, -1688849860263936
+  %t215 = bitcast i64 %t214 to double
+  br label %b33
+b32:
+  %t216 = bitcast double %t204 to i64
+  %t217 = bitcast double %t205 to i64
+  %t218 = call i64 @jsrt_ne(i64 %t216, i64 %t217)
+  %t219 = bitcast i64 %t218 to double
+  br label %b33
+b33:
+  %t220 = phi double [ %t215, %b31 ], [ %t219, %b32 ]
+  store double %t220, ptr %s4
+  %t221 = load double, ptr %s0
+  %t222 = load double, ptr %s1
+  %t223 = load double, ptr %s2
+  %t224 = load double, ptr %s3
+  %t225 = load double, ptr %s4
+  %t226 = bitcast double %t221 to i64
+  call void @jsrt_print_value(i64 %t226, i32 32)
+  %t227 = bitcast double %t222 to i64
+  call void @jsrt_print_value(i64 %t227, i32 32)
+  %t228 = bitcast double %t223 to i64
+  call void @jsrt_print_value(i64 %t228, i32 32)
+  %t229 = bitcast double %t224 to i64
+  call void @jsrt_print_value(i64 %t229, i32 32)
+  %t230 = bitcast double %t225 to i64
+  call void @jsrt_print_value(i64 %t230, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  %t231 = ptrtoint ptr @.str.6 to i64
+  %t232 = or i64 %t231, -1970324836974592
+  %t233 = bitcast i64 %t232 to double
+  store double %t233, ptr %s1
+  %t234 = load double, ptr %s0
+  %t235 = load double, ptr %s1
+  %t236 = bitcast double %t234 to i64
+  %t237 = icmp ule i64 %t236, -2251799813685248
+  %t238 = and i1 true, %t237
+  %t239 = bitcast double %t235 to i64
+  %t240 = icmp ule i64 %t239, -2251799813685248
+  %t241 = and i1 %t238, %t240
+  br i1 %t241, label %b34, label %b35
+b34:
+  %t242 = fcmp olt double %t234, %t235
+  %t243 = zext i1 %t242 to i64
+  %t244 = or i64 %t243, -1688849860263936
+  %t245 = bitcast i64 %t244 to double
+  br label %b36
+b35:
+  %t246 = bitcast double %t234 to i64
+  %t247 = bitcast double %t235 to i64
+  %t248 = call i64 @jsrt_lt(i64 %t246, i64 %t247)
+  %t249 = bitcast i64 %t248 to double
+  br label %b36
+b36:
+  %t250 = phi double [ %t245, %b34 ], [ %t249, %b35 ]
+  store double %t250, ptr %s0
+  %t251 = ptrtoint ptr @.str.7 to i64
+  %t252 = or i64 %t251, -1970324836974592
+  %t253 = bitcast i64 %t252 to double
+  store double %t253, ptr %s1
+  %t254 = ptrtoint ptr @.str.8 to i64
+  %t255 = or i64 %t254, -1970324836974592
+  %t256 = bitcast i64 %t255 to double
+  store double %t256, ptr %s2
+  %t257 = load double, ptr %s1
+  %t258 = load double, ptr %s2
+  %t259 = bitcast double %t257 to i64
+  %t260 = icmp ule i64 %t259, -2251799813685248
+  %t261 = and i1 true, %t260
+  %t262 = bitcast double %t258 to i64
+  %t263 = icmp ule i64 %t262, -2251799813685248
+  %t264 = and i1 %t261, %t263
+  br i1 %t264, label %b37, label %b38
+b37:
+  %t265 = fcmp olt double %t257, %t258
+  %t266 = zext i1 %t265 to i64
+  %t267 = or i64 %t266, -1688849860263936
+  %t268 = bitcast i64 %t267 to double
+  br label %b39
+b38:
+  %t269 = bitcast double %t257 to i64
+  %t270 = bitcast double %t258 to i64
+  %t271 = call i64 @jsrt_lt(i64 %t269, i64 %t270)
+  %t272 = bitcast i64 %t271 to double
+  br label %b39
+b39:
+  %t273 = phi double [ %t268, %b37 ], [ %t272, %b38 ]
+  store double %t273, ptr %s1
+  store double 0x4024000000000000, ptr %s2
+  %t274 = ptrtoint ptr @.str.9 to i64
+  %t275 = or i64 %t274, -1970324836974592
+  %t276 = bitcast i64 %t275 to double
+  store double %t276, ptr %s3
+  %t277 = load double, ptr %s2
+  %t278 = load double, ptr %s3
+  %t279 = bitcast double %t277 to i64
+  %t280 = icmp ule i64 %t279, -2251799813685248
+  %t281 = and i1 true, %t280
+  %t282 = bitcast double %t278 to i64
+  %t283 = icmp ule i64 %t282, -2251799813685248
+  %t284 = and i1 %t281, %t283
+  br i1 %t284, label %b40, label %b41
+b40:
+  %t285 = fcmp olt double %t277, %t278
+  %t286 = zext i1 %t285 to i64
+  %t287 = or i64 %t286, -1688849860263936
+  %t288 = bitcast i64 %t287 to double
+  br label %b42
+b41:
+  %t289 = bitcast double %t277 to i64
+  %t290 = bitcast double %t278 to i64
+  %t291 = call i64 @jsrt_lt(i64 %t289, i64 %t290)
+  %t292 = bitcast i64 %t291 to double
+  br label %b42
+b42:
+  %t293 = phi double [ %t288, %b40 ], [ %t292, %b41 ]
+  store double %t293, ptr %s2
+  %t294 = ptrtoint ptr @.str.10 to i64
+  %t295 = or i64 %t294, -1970324836974592
+  %t296 = bitcast i64 %t295 to double
+  store double %t296, ptr %s3
+  store double 0x3FF0000000000000, ptr %s4
+  %t297 = load double, ptr %s3
+  %t298 = load double, ptr %s4
+  %t299 = bitcast double %t297 to i64
+  %t300 = icmp ule i64 %t299, -2251799813685248
+  %t301 = and i1 true, %t300
+  %t302 = bitcast double %t298 to i64
+  %t303 = icmp ule i64 %t302, -2251799813685248
+  %t304 = and i1 %t301, %t303
+  br i1 %t304, label %b43, label %b44
+b43:
+  %t305 = fcmp olt double %t297, %t298
+  %t306 = zext i1 %t305 to i64
+  %t307 = or i64 %t306, -1688849860263936
+  %t308 = bitcast i64 %t307 to double
+  br label %b45
+b44:
+  %t309 = bitcast double %t297 to i64
+  %t310 = bitcast double %t298 to i64
+  %t311 = call i64 @jsrt_lt(i64 %t309, i64 %t310)
+  %t312 = bitcast i64 %t311 to double
+  br label %b45
+b45:
+  %t313 = phi double [ %t308, %b43 ], [ %t312, %b44 ]
+  store double %t313, ptr %s3
+  store double 0xFFFB000000000000, ptr %s4
+  store double 0x3FF0000000000000, ptr %s5
+  %t314 = load double, ptr %s4
+  %t315 = load double, ptr %s5
+  %t316 = bitcast double %t314 to i64
+  %t317 = icmp ule i64 %t316, -2251799813685248
+  %t318 = and i1 true, %t317
+  %t319 = bitcast double %t315 to i64
+  %t320 = icmp ule i64 %t319, -2251799813685248
+  %t321 = and i1 %t318, %t320
+  br i1 %t321, label %b46, label %b47
+b46:
+  %t322 = fcmp olt double %t314, %t315
+  %t323 = zext i1 %t322 to i64
+  %t324 = or i64 %t323, -1688849860263936
+  %t325 = bitcast i64 %t324 to double
+  br label %b48
+b47:
+  %t326 = bitcast double %t314 to i64
+  %t327 = bitcast double %t315 to i64
+  %t328 = call i64 @jsrt_lt(i64 %t326, i64 %t327)
+  %t329 = bitcast i64 %t328 to double
+  br label %b48
+b48:
+  %t330 = phi double [ %t325, %b46 ], [ %t329, %b47 ]
+  store double %t330, ptr %s4
+  %t331 = call double @nothing()
+  store double %t331, ptr %s5
+  store double 0x3FF0000000000000, ptr %s6
+  %t332 = load double, ptr %s5
+  %t333 = load double, ptr %s6
+  %t334 = bitcast double %t332 to i64
+  %t335 = icmp ule i64 %t334, -2251799813685248
+  %t336 = and i1 true, %t335
+  %t337 = bitcast double %t333 to i64
+  %t338 = icmp ule i64 %t337, -2251799813685248
+  %t339 = and i1 %t336, %t338
+  br i1 %t339, label %b49, label %b50
+b49:
+  %t340 = fcmp olt double %t332, %t333
+  %t341 = zext i1 %t340 to i64
+  %t342 = or i64 %t341, -1688849860263936
+  %t343 = bitcast i64 %t342 to double
+  br label %b51
+b50:
+  %t344 = bitcast double %t332 to i64
+  %t345 = bitcast double %t333 to i64
+  %t346 = call i64 @jsrt_lt(i64 %t344, i64 %t345)
+  %t347 = bitcast i64 %t346 to double
+  br label %b51
+b51:
+  %t348 = phi double [ %t343, %b49 ], [ %t347, %b50 ]
+  store double %t348, ptr %s5
+  %t349 = load double, ptr %s0
+  %t350 = load double, ptr %s1
+  %t351 = load double, ptr %s2
+  %t352 = load double, ptr %s3
+  %t353 = load double, ptr %s4
+  %t354 = load double, ptr %s5
+  %t355 = bitcast double %t349 to i64
+  call void @jsrt_print_value(i64 %t355, i32 32)
+  %t356 = bitcast double %t350 to i64
+  call void @jsrt_print_value(i64 %t356, i32 32)
+  %t357 = bitcast double %t351 to i64
+  call void @jsrt_print_value(i64 %t357, i32 32)
+  %t358 = bitcast double %t352 to i64
+  call void @jsrt_print_value(i64 %t358, i32 32)
+  %t359 = bitcast double %t353 to i64
+  call void @jsrt_print_value(i64 %t359, i32 32)
+  %t360 = bitcast double %t354 to i64
+  call void @jsrt_print_value(i64 %t360, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4000000000000000, ptr %s0
+  %t361 = ptrtoint ptr @.str.11 to i64
+  %t362 = or i64 %t361, -1970324836974592
+  %t363 = bitcast i64 %t362 to double
+  store double %t363, ptr %s1
+  %t364 = load double, ptr %s0
+  %t365 = load double, ptr %s1
+  %t366 = bitcast double %t364 to i64
+  %t367 = icmp ule i64 %t366, -2251799813685248
+  %t368 = and i1 true, %t367
+  %t369 = bitcast double %t365 to i64
+  %t370 = icmp ule i64 %t369, -2251799813685248
+  %t371 = and i1 %t368, %t370
+  br i1 %t371, label %b52, label %b53
+b52:
+  %t372 = fcmp oge double %t364, %t365
+  %t373 = zext i1 %t372 to i64
+  %t374 = or i64 %t373, -1688849860263936
+  %t375 = bitcast i64 %t374 to double
+  br label %b54
+b53:
+  %t376 = bitcast double %t364 to i64
+  %t377 = bitcast double %t365 to i64
+  %t378 = call i64 @jsrt_ge(i64 %t376, i64 %t377)
+  %t379 = bitcast i64 %t378 to double
+  br label %b54
+b54:
+  %t380 = phi double [ %t375, %b52 ], [ %t379, %b53 ]
+  store double %t380, ptr %s0
+  %t381 = ptrtoint ptr @.str.12 to i64
+  %t382 = or i64 %t381, -1970324836974592
+  %t383 = bitcast i64 %t382 to double
+  store double %t383, ptr %s1
+  %t384 = ptrtoint ptr @.str.13 to i64
+  %t385 = or i64 %t384, -1970324836974592
+  %t386 = bitcast i64 %t385 to double
+  store double %t386, ptr %s2
+  %t387 = load double, ptr %s1
+  %t388 = load double, ptr %s2
+  %t389 = bitcast double %t387 to i64
+  %t390 = icmp ule i64 %t389, -2251799813685248
+  %t391 = and i1 true, %t390
+  %t392 = bitcast double %t388 to i64
+  %t393 = icmp ule i64 %t392, -2251799813685248
+  %t394 = and i1 %t391, %t393
+  br i1 %t394, label %b55, label %b56
+b55:
+  %t395 = fcmp ogt double %t387, %t388
+  %t396 = zext i1 %t395 to i64
+  %t397 = or i64 %t396, -1688849860263936
+  %t398 = bitcast i64 %t397 to double
+  br label %b57
+b56:
+  %t399 = bitcast double %t387 to i64
+  %t400 = bitcast double %t388 to i64
+  %t401 = call i64 @jsrt_gt(i64 %t399, i64 %t400)
+  %t402 = bitcast i64 %t401 to double
+  br label %b57
+b57:
+  %t403 = phi double [ %t398, %b55 ], [ %t402, %b56 ]
+  store double %t403, ptr %s1
+  store double 0xFFFA000000000001, ptr %s2
+  store double 0x0000000000000000, ptr %s3
+  %t404 = load double, ptr %s2
+  %t405 = load double, ptr %s3
+  %t406 = bitcast double %t404 to i64
+  %t407 = icmp ule i64 %t406, -2251799813685248
+  %t408 = and i1 true, %t407
+  %t409 = bitcast double %t405 to i64
+  %t410 = icmp ule i64 %t409, -2251799813685248
+  %t411 = and i1 %t408, %t410
+  br i1 %t411, label %b58, label %b59
+b58:
+  %t412 = fcmp ogt double %t404, %t405
+  %t413 = zext i1 %t412 to i64
+  %t414 = or i64 %t413, -1688849860263936
+  %t415 = bitcast i64 %t414 to double
+  br label %b60
+b59:
+  %t416 = bitcast double %t404 to i64
+  %t417 = bitcast double %t405 to i64
+  %t418 = call i64 @jsrt_gt(i64 %t416, i64 %t417)
+  %t419 = bitcast i64 %t418 to double
+  br label %b60
+b60:
+  %t420 = phi double [ %t415, %b58 ], [ %t419, %b59 ]
+  store double %t420, ptr %s2
+  %t421 = ptrtoint ptr @.str.14 to i64
+  %t422 = or i64 %t421, -1970324836974592
+  %t423 = bitcast i64 %t422 to double
+  store double %t423, ptr %s3
+  store double 0x4000000000000000, ptr %s4
+  %t424 = load double, ptr %s3
+  %t425 = load double, ptr %s4
+  %t426 = bitcast double %t424 to i64
+  %t427 = icmp ule i64 %t426, -2251799813685248
+  %t428 = and i1 true, %t427
+  %t429 = bitcast double %t425 to i64
+  %t430 = icmp ule i64 %t429, -2251799813685248
+  %t431 = and i1 %t428, %t430
+  br i1 %t431, label %b61, label %b62
+b61:
+  %t432 = fcmp ole double %t424, %t425
+  %t433 = zext i1 %t432 to i64
+  %t434 = or i64 %t433, -1688849860263936
+  %t435 = bitcast i64 %t434 to double
+  br label %b63
+b62:
+  %t436 = bitcast double %t424 to i64
+  %t437 = bitcast double %t425 to i64
+  %t438 = call i64 @jsrt_le(i64 %t436, i64 %t437)
+  %t439 = bitcast i64 %t438 to double
+  br label %b63
+b63:
+  %t440 = phi double [ %t435, %b61 ], [ %t439, %b62 ]
+  store double %t440, ptr %s3
+  %t441 = load double, ptr %s0
+  %t442 = load double, ptr %s1
+  %t443 = load double, ptr %s2
+  %t444 = load double, ptr %s3
+  %t445 = bitcast double %t441 to i64
+  call void @jsrt_print_value(i64 %t445, i32 32)
+  %t446 = bitcast double %t442 to i64
+  call void @jsrt_print_value(i64 %t446, i32 32)
+  %t447 = bitcast double %t443 to i64
+  call void @jsrt_print_value(i64 %t447, i32 32)
+  %t448 = bitcast double %t444 to i64
+  call void @jsrt_print_value(i64 %t448, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t449 = load double, ptr %s0
+  %t450 = load double, ptr %s1
+  %t451 = bitcast double %t449 to i64
+  %t452 = icmp ule i64 %t451, -2251799813685248
+  %t453 = and i1 true, %t452
+  %t454 = bitcast double %t450 to i64
+  %t455 = icmp ule i64 %t454, -2251799813685248
+  %t456 = and i1 %t453, %t455
+  br i1 %t456, label %b64, label %b65
+b64:
+  %t457 = fdiv double %t449, %t450
+  br label %b66
+b65:
+  %t458 = bitcast double %t449 to i64
+  %t459 = bitcast double %t450 to i64
+  %t460 = call i64 @jsrt_div(i64 %t458, i64 %t459)
+  %t461 = bitcast i64 %t460 to double
+  br label %b66
+b66:
+  %t462 = phi double [ %t457, %b64 ], [ %t461, %b65 ]
+  store double %t462, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t463 = load double, ptr %s1
+  %t464 = bitcast double %t463 to i64
+  %t465 = icmp ule i64 %t464, -2251799813685248
+  %t466 = and i1 true, %t465
+  br i1 %t466, label %b67, label %b68
+b67:
+  %t467 = fneg double %t463
+  br label %b69
+b68:
+  %t468 = bitcast double %t463 to i64
+  %t469 = call i64 @jsrt_neg(i64 %t468)
+  %t470 = bitcast i64 %t469 to double
+  br label %b69
+b69:
+  %t471 = phi double [ %t467, %b67 ], [ %t470, %b68 ]
+  store double %t471, ptr %s1
+  store double 0x0000000000000000, ptr %s2
+  %t472 = load double, ptr %s1
+  %t473 = load double, ptr %s2
+  %t474 = bitcast double %t472 to i64
+  %t475 = icmp ule i64 %t474, -2251799813685248
+  %t476 = and i1 true, %t475
+  %t477 = bitcast double %t473 to i64
+  %t478 = icmp ule i64 %t477, -2251799813685248
+  %t479 = and i1 %t476, %t478
+  br i1 %t479, label %b70, label %b71
+b70:
+  %t480 = fdiv double %t472, %t473
+  br label %b72
+b71:
+  %t481 = bitcast double %t472 to i64
+  %t482 = bitcast double %t473 to i64
+  %t483 = call i64 @jsrt_div(i64 %t481, i64 %t482)
+  %t484 = bitcast i64 %t483 to double
+  br label %b72
+b72:
+  %t485 = phi double [ %t480, %b70 ], [ %t484, %b71 ]
+  store double %t485, ptr %s1
+  store double 0x0000000000000000, ptr %s2
+  store double 0x0000000000000000, ptr %s3
+  %t486 = load double, ptr %s2
+  %t487 = load double, ptr %s3
+  %t488 = bitcast double %t486 to i64
+  %t489 = icmp ule i64 %t488, -2251799813685248
+  %t490 = and i1 true, %t489
+  %t491 = bitcast double %t487 to i64
+  %t492 = icmp ule i64 %t491, -2251799813685248
+  %t493 = and i1 %t490, %t492
+  br i1 %t493, label %b73, label %b74
+b73:
+  %t494 = fdiv double %t486, %t487
+  br label %b75
+b74:
+  %t495 = bitcast double %t486 to i64
+  %t496 = bitcast double %t487 to i64
+  %t497 = call i64 @jsrt_div(i64 %t495, i64 %t496)
+  %t498 = bitcast i64 %t497 to double
+  br label %b75
+b75:
+  %t499 = phi double [ %t494, %b73 ], [ %t498, %b74 ]
+  store double %t499, ptr %s2
+  %t500 = ptrtoint ptr @.str.15 to i64
+  %t501 = or i64 %t500, -1970324836974592
+  %t502 = bitcast i64 %t501 to double
+  store double %t502, ptr %s3
+  %t503 = ptrtoint ptr @.str.16 to i64
+  %t504 = or i64 %t503, -1970324836974592
+  %t505 = bitcast i64 %t504 to double
+  store double %t505, ptr %s4
+  %t506 = load double, ptr %s3
+  %t507 = load double, ptr %s4
+  %t508 = bitcast double %t506 to i64
+  %t509 = icmp ule i64 %t508, -2251799813685248
+  %t510 = and i1 true, %t509
+  %t511 = bitcast double %t507 to i64
+  %t512 = icmp ule i64 %t511, -2251799813685248
+  %t513 = and i1 %t510, %t512
+  br i1 %t513, label %b76, label %b77
+b76:
+  %t514 = fdiv double %t506, %t507
+  br label %b78
+b77:
+  %t515 = bitcast double %t506 to i64
+  %t516 = bitcast double %t507 to i64
+  %t517 = call i64 @jsrt_div(i64 %t515, i64 %t516)
+  %t518 = bitcast i64 %t517 to double
+  br label %b78
+b78:
+  %t519 = phi double [ %t514, %b76 ], [ %t518, %b77 ]
+  store double %t519, ptr %s3
+  %t520 = ptrtoint ptr @.str.17 to i64
+  %t521 = or i64 %t520, -1970324836974592
+  %t522 = bitcast i64 %t521 to double
+  store double %t522, ptr %s4
+  store double 0x4000000000000000, ptr %s5
+  %t523 = load double, ptr %s4
+  %t524 = load double, ptr %s5
+  %t525 = bitcast double %t523 to i64
+  %t526 = icmp ule i64 %t525, -2251799813685248
+  %t527 = and i1 true, %t526
+  %t528 = bitcast double %t524 to i64
+  %t529 = icmp ule i64 %t528, -2251799813685248
+  %t530 = and i1 %t527, %t529
+  br i1 %t530, label %b79, label %b80
+b79:
+  %t531 = fdiv double %t523, %t524
+  br label %b81
+b80:
+  %t532 = bitcast double %t523 to i64
+  %t533 = bitcast double %t524 to i64
+  %t534 = call i64 @jsrt_div(i64 %t532, i64 %t533)
+  %t535 = bitcast i64 %t534 to double
+  br label %b81
+b81:
+  %t536 = phi double [ %t531, %b79 ], [ %t535, %b80 ]
+  store double %t536, ptr %s4
+  store double 0xFFFA000000000001, ptr %s5
+  store double 0x3FF0000000000000, ptr %s6
+  %t537 = load double, ptr %s5
+  %t538 = load double, ptr %s6
+  %t539 = bitcast double %t537 to i64
+  %t540 = icmp ule i64 %t539, -2251799813685248
+  %t541 = and i1 true, %t540
+  %t542 = bitcast double %t538 to i64
+  %t543 = icmp ule i64 %t542, -2251799813685248
+  %t544 = and i1 %t541, %t543
+  br i1 %t544, label %b82, label %b83
+b82:
+  %t545 = fadd double %t537, %t538
+  br label %b84
+b83:
+  %t546 = bitcast double %t537 to i64
+  %t547 = bitcast double %t538 to i64
+  %t548 = call i64 @jsrt_add(i64 %t546, i64 %t547)
+  %t549 = bitcast i64 %t548 to double
+  br label %b84
+b84:
+  %t550 = phi double [ %t545, %b82 ], [ %t549, %b83 ]
+  store double %t550, ptr %s5
+  %t551 = ptrtoint ptr @.str.18 to i64
+  %t552 = or i64 %t551, -1970324836974592
+  %t553 = bitcast i64 %t552 to double
+  store double %t553, ptr %s6
+  store double 0x3FF0000000000000, ptr %s7
+  %t554 = load double, ptr %s6
+  %t555 = load double, ptr %s7
+  %t556 = bitcast double %t554 to i64
+  %t557 = icmp ule i64 %t556, -2251799813685248
+  %t558 = and i1 true, %t557
+  %t559 = bitcast double %t555 to i64
+  %t560 = icmp ule i64 %t559, -2251799813685248
+  %t561 = and i1 %t558, %t560
+  br i1 %t561, label %b85, label %b86
+b85:
+  %t562 = fadd double %t554, %t555
+  br label %b87
+b86:
+  %t563 = bitcast double %t554 to i64
+  %t564 = bitcast double %t555 to i64
+  %t565 = call i64 @jsrt_add(i64 %t563, i64 %t564)
+  %t566 = bitcast i64 %t565 to double
+  br label %b87
+b87:
+  %t567 = phi double [ %t562, %b85 ], [ %t566, %b86 ]
+  store double %t567, ptr %s6
+  store double 0xFFFB000000000000, ptr %s7
+  store double 0x3FF0000000000000, ptr %s8
+  %t568 = load double, ptr %s7
+  %t569 = load double, ptr %s8
+  %t570 = bitcast double %t568 to i64
+  %t571 = icmp ule i64 %t570, -2251799813685248
+  %t572 = and i1 true, %t571
+  %t573 = bitcast double %t569 to i64
+  %t574 = icmp ule i64 %t573, -2251799813685248
+  %t575 = and i1 %t572, %t574
+  br i1 %t575, label %b88, label %b89
+b88:
+  %t576 = fadd double %t568, %t569
+  br label %b90
+b89:
+  %t577 = bitcast double %t568 to i64
+  %t578 = bitcast double %t569 to i64
+  %t579 = call i64 @jsrt_add(i64 %t577, i64 %t578)
+  %t580 = bitcast i64 %t579 to double
+  br label %b90
+b90:
+  %t581 = phi double [ %t576, %b88 ], [ %t580, %b89 ]
+  store double %t581, ptr %s7
+  %t582 = load double, ptr %s0
+  %t583 = load double, ptr %s1
+  %t584 = load double, ptr %s2
+  %t585 = load double, ptr %s3
+  %t586 = load double, ptr %s4
+  %t587 = load double, ptr %s5
+  %t588 = load double, ptr %s6
+  %t589 = load double, ptr %s7
+  %t590 = bitcast double %t582 to i64
+  call void @jsrt_print_value(i64 %t590, i32 32)
+  %t591 = bitcast double %t583 to i64
+  call void @jsrt_print_value(i64 %t591, i32 32)
+  %t592 = bitcast double %t584 to i64
+  call void @jsrt_print_value(i64 %t592, i32 32)
+  %t593 = bitcast double %t585 to i64
+  call void @jsrt_print_value(i64 %t593, i32 32)
+  %t594 = bitcast double %t586 to i64
+  call void @jsrt_print_value(i64 %t594, i32 32)
+  %t595 = bitcast double %t587 to i64
+  call void @jsrt_print_value(i64 %t595, i32 32)
+  %t596 = bitcast double %t588 to i64
+  call void @jsrt_print_value(i64 %t596, i32 32)
+  %t597 = bitcast double %t589 to i64
+  call void @jsrt_print_value(i64 %t597, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t598 = ptrtoint ptr @.str.19 to i64
+  %t599 = or i64 %t598, -1970324836974592
+  %t600 = bitcast i64 %t599 to double
+  store double %t600, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t601 = load double, ptr %s0
+  %t602 = load double, ptr %s1
+  %t603 = bitcast double %t601 to i64
+  %t604 = icmp ule i64 %t603, -2251799813685248
+  %t605 = and i1 true, %t604
+  %t606 = bitcast double %t602 to i64
+  %t607 = icmp ule i64 %t606, -2251799813685248
+  %t608 = and i1 %t605, %t607
+  br i1 %t608, label %b91, label %b92
+b91:
+  %t609 = fsub double %t601, %t602
+  br label %b93
+b92:
+  %t610 = bitcast double %t601 to i64
+  %t611 = bitcast double %t602 to i64
+  %t612 = call i64 @jsrt_sub(i64 %t610, i64 %t611)
+  %t613 = bitcast i64 %t612 to double
+  br label %b93
+b93:
+  %t614 = phi double [ %t609, %b91 ], [ %t613, %b92 ]
+  store double %t614, ptr %s0
+  %t615 = ptrtoint ptr @.str.20 to i64
+  %t616 = or i64 %t615, -1970324836974592
+  %t617 = bitcast i64 %t616 to double
+  store double %t617, ptr %s1
+  %t618 = ptrtoint ptr @.str.21 to i64
+  %t619 = or i64 %t618, -1970324836974592
+  %t620 = bitcast i64 %t619 to double
+  store double %t620, ptr %s2
+  %t621 = load double, ptr %s1
+  %t622 = load double, ptr %s2
+  %t623 = bitcast double %t621 to i64
+  %t624 = icmp ule i64 %t623, -2251799813685248
+  %t625 = and i1 true, %t624
+  %t626 = bitcast double %t622 to i64
+  %t627 = icmp ule i64 %t626, -2251799813685248
+  %t628 = and i1 %t625, %t627
+  br i1 %t628, label %b94, label %b95
+b94:
+  %t629 = fmul double %t621, %t622
+  br label %b96
+b95:
+  %t630 = bitcast double %t621 to i64
+  %t631 = bitcast double %t622 to i64
+  %t632 = call i64 @jsrt_mul(i64 %t630, i64 %t631)
+  %t633 = bitcast i64 %t632 to double
+  br label %b96
+b96:
+  %t634 = phi double [ %t629, %b94 ], [ %t633, %b95 ]
+  store double %t634, ptr %s1
+  %t635 = ptrtoint ptr @.str.22 to i64
+  %t636 = or i64 %t635, -1970324836974592
+  %t637 = bitcast i64 %t636 to double
+  store double %t637, ptr %s2
+  store double 0x4010000000000000, ptr %s3
+  %t638 = load double, ptr %s2
+  %t639 = load double, ptr %s3
+  %t640 = bitcast double %t638 to i64
+  %t641 = icmp ule i64 %t640, -2251799813685248
+  %t642 = and i1 true, %t641
+  %t643 = bitcast double %t639 to i64
+  %t644 = icmp ule i64 %t643, -2251799813685248
+  %t645 = and i1 %t642, %t644
+  br i1 %t645, label %b97, label %b98
+b97:
+  %t646 = frem double %t638, %t639
+  br label %b99
+b98:
+  %t647 = bitcast double %t638 to i64
+  %t648 = bitcast double %t639 to i64
+  %t649 = call i64 @jsrt_mod(i64 %t647, i64 %t648)
+  %t650 = bitcast i64 %t649 to double
+  br label %b99
+b99:
+  %t651 = phi double [ %t646, %b97 ], [ %t650, %b98 ]
+  store double %t651, ptr %s2
+  %t652 = ptrtoint ptr @.str.23 to i64
+  %t653 = or i64 %t652, -1970324836974592
+  %t654 = bitcast i64 %t653 to double
+  store double %t654, ptr %s3
+  %t655 = load double, ptr %s3
+  %t656 = bitcast double %t655 to i64
+  %t657 = icmp ule i64 %t656, -2251799813685248
+  %t658 = and i1 true, %t657
+  br i1 %t658, label %b100, label %b101
+b100:
+  %t659 = fneg double %t655
+  br label %b102
+b101:
+  %t660 = bitcast double %t655 to i64
+  %t661 = call i64 @jsrt_neg(i64 %t660)
+  %t662 = bitcast i64 %t661 to double
+  br label %b102
+b102:
+  %t663 = phi double [ %t659, %b100 ], [ %t662, %b101 ]
+  store double %t663, ptr %s3
+  %t664 = call double @nothing()
+  store double %t664, ptr %s4
+  store double 0x3FF0000000000000, ptr %s5
+  %t665 = load double, ptr %s4
+  %t666 = load double, ptr %s5
+  %t667 = bitcast double %t665 to i64
+  %t668 = icmp ule i64 %t667, -2251799813685248
+  %t669 = and i1 true, %t668
+  %t670 = bitcast double %t666 to i64
+  %t671 = icmp ule i64 %t670, -2251799813685248
+  %t672 = and i1 %t669, %t671
+  br i1 %t672, label %b103, label %b104
+b103:
+  %t673 = fadd double %t665, %t666
+  br label %b105
+b104:
+  %t674 = bitcast double %t665 to i64
+  %t675 = bitcast double %t666 to i64
+  %t676 = call i64 @jsrt_add(i64 %t674, i64 %t675)
+  %t677 = bitcast i64 %t676 to double
+  br label %b105
+b105:
+  %t678 = phi double [ %t673, %b103 ], [ %t677, %b104 ]
+  store double %t678, ptr %s4
+  %t679 = load double, ptr %s0
+  %t680 = load double, ptr %s1
+  %t681 = load double, ptr %s2
+  %t682 = load double, ptr %s3
+  %t683 = load double, ptr %s4
+  %t684 = bitcast double %t679 to i64
+  call void @jsrt_print_value(i64 %t684, i32 32)
+  %t685 = bitcast double %t680 to i64
+  call void @jsrt_print_value(i64 %t685, i32 32)
+  %t686 = bitcast double %t681 to i64
+  call void @jsrt_print_value(i64 %t686, i32 32)
+  %t687 = bitcast double %t682 to i64
+  call void @jsrt_print_value(i64 %t687, i32 32)
+  %t688 = bitcast double %t683 to i64
+  call void @jsrt_print_value(i64 %t688, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t689 = ptrtoint ptr @.str.24 to i64
+  %t690 = or i64 %t689, -1970324836974592
+  %t691 = bitcast i64 %t690 to double
+  store double %t691, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t692 = load double, ptr %s0
+  %t693 = load double, ptr %s1
+  %t694 = bitcast double %t692 to i64
+  %t695 = icmp ule i64 %t694, -2251799813685248
+  %t696 = and i1 true, %t695
+  %t697 = bitcast double %t693 to i64
+  %t698 = icmp ule i64 %t697, -2251799813685248
+  %t699 = and i1 %t696, %t698
+  br i1 %t699, label %b106, label %b107
+b106:
+  %t700 = fmul double %t692, %t693
+  br label %b108
+b107:
+  %t701 = bitcast double %t692 to i64
+  %t702 = bitcast double %t693 to i64
+  %t703 = call i64 @jsrt_mul(i64 %t701, i64 %t702)
+  %t704 = bitcast i64 %t703 to double
+  br label %b108
+b108:
+  %t705 = phi double [ %t700, %b106 ], [ %t704, %b107 ]
+  store double %t705, ptr %s0
+  %t706 = ptrtoint ptr @.str.25 to i64
+  %t707 = or i64 %t706, -1970324836974592
+  %t708 = bitcast i64 %t707 to double
+  store double %t708, ptr %s1
+  store double 0x3FF0000000000000, ptr %s2
+  %t709 = load double, ptr %s1
+  %t710 = load double, ptr %s2
+  %t711 = bitcast double %t709 to i64
+  %t712 = icmp ule i64 %t711, -2251799813685248
+  %t713 = and i1 true, %t712
+  %t714 = bitcast double %t710 to i64
+  %t715 = icmp ule i64 %t714, -2251799813685248
+  %t716 = and i1 %t713, %t715
+  br i1 %t716, label %b109, label %b110
+b109:
+  %t717 = fmul double %t709, %t710
+  br label %b111
+b110:
+  %t718 = bitcast double %t709 to i64
+  %t719 = bitcast double %t710 to i64
+  %t720 = call i64 @jsrt_mul(i64 %t718, i64 %t719)
+  %t721 = bitcast i64 %t720 to double
+  br label %b111
+b111:
+  %t722 = phi double [ %t717, %b109 ], [ %t721, %b110 ]
+  store double %t722, ptr %s1
+  %t723 = ptrtoint ptr @.str.26 to i64
+  %t724 = or i64 %t723, -1970324836974592
+  %t725 = bitcast i64 %t724 to double
+  store double %t725, ptr %s2
+  store double 0x3FF0000000000000, ptr %s3
+  %t726 = load double, ptr %s2
+  %t727 = load double, ptr %s3
+  %t728 = bitcast double %t726 to i64
+  %t729 = icmp ule i64 %t728, -2251799813685248
+  %t730 = and i1 true, %t729
+  %t731 = bitcast double %t727 to i64
+  %t732 = icmp ule i64 %t731, -2251799813685248
+  %t733 = and i1 %t730, %t732
+  br i1 %t733, label %b112, label %b113
+b112:
+  %t734 = fmul double %t726, %t727
+  br label %b114
+b113:
+  %t735 = bitcast double %t726 to i64
+  %t736 = bitcast double %t727 to i64
+  %t737 = call i64 @jsrt_mul(i64 %t735, i64 %t736)
+  %t738 = bitcast i64 %t737 to double
+  br label %b114
+b114:
+  %t739 = phi double [ %t734, %b112 ], [ %t738, %b113 ]
+  store double %t739, ptr %s2
+  %t740 = ptrtoint ptr @.str.27 to i64
+  %t741 = or i64 %t740, -1970324836974592
+  %t742 = bitcast i64 %t741 to double
+  store double %t742, ptr %s3
+  store double 0x3FF0000000000000, ptr %s4
+  %t743 = load double, ptr %s3
+  %t744 = load double, ptr %s4
+  %t745 = bitcast double %t743 to i64
+  %t746 = icmp ule i64 %t745, -2251799813685248
+  %t747 = and i1 true, %t746
+  %t748 = bitcast double %t744 to i64
+  %t749 = icmp ule i64 %t748, -2251799813685248
+  %t750 = and i1 %t747, %t749
+  br i1 %t750, label %b115, label %b116
+b115:
+  %t751 = fmul double %t743, %t744
+  br label %b117
+b116:
+  %t752 = bitcast double %t743 to i64
+  %t753 = bitcast double %t744 to i64
+  %t754 = call i64 @jsrt_mul(i64 %t752, i64 %t753)
+  %t755 = bitcast i64 %t754 to double
+  br label %b117
+b117:
+  %t756 = phi double [ %t751, %b115 ], [ %t755, %b116 ]
+  store double %t756, ptr %s3
+  %t757 = ptrtoint ptr @.str.28 to i64
+  %t758 = or i64 %t757, -1970324836974592
+  %t759 = bitcast i64 %t758 to double
+  store double %t759, ptr %s4
+  store double 0x3FF0000000000000, ptr %s5
+  %t760 = load double, ptr %s4
+  %t761 = load double, ptr %s5
+  %t762 = bitcast double %t760 to i64
+  %t763 = icmp ule i64 %t762, -2251799813685248
+  %t764 = and i1 true, %t763
+  %t765 = bitcast double %t761 to i64
+  %t766 = icmp ule i64 %t765, -2251799813685248
+  %t767 = and i1 %t764, %t766
+  br i1 %t767, label %b118, label %b119
+b118:
+  %t768 = fmul double %t760, %t761
+  br label %b120
+b119:
+  %t769 = bitcast double %t760 to i64
+  %t770 = bitcast double %t761 to i64
+  %t771 = call i64 @jsrt_mul(i64 %t769, i64 %t770)
+  %t772 = bitcast i64 %t771 to double
+  br label %b120
+b120:
+  %t773 = phi double [ %t768, %b118 ], [ %t772, %b119 ]
+  store double %t773, ptr %s4
+  %t774 = ptrtoint ptr @.str.29 to i64
+  %t775 = or i64 %t774, -1970324836974592
+  %t776 = bitcast i64 %t775 to double
+  store double %t776, ptr %s5
+  store double 0x3FF0000000000000, ptr %s6
+  %t777 = load double, ptr %s5
+  %t778 = load double, ptr %s6
+  %t779 = bitcast double %t777 to i64
+  %t780 = icmp ule i64 %t779, -2251799813685248
+  %t781 = and i1 true, %t780
+  %t782 = bitcast double %t778 to i64
+  %t783 = icmp ule i64 %t782, -2251799813685248
+  %t784 = and i1 %t781, %t783
+  br i1 %t784, label %b121, label %b122
+b121:
+  %t785 = fmul double %t777, %t778
+  br label %b123
+b122:
+  %t786 = bitcast double %t777 to i64
+  %t787 = bitcast double %t778 to i64
+  %t788 = call i64 @jsrt_mul(i64 %t786, i64 %t787)
+  %t789 = bitcast i64 %t788 to double
+  br label %b123
+b123:
+  %t790 = phi double [ %t785, %b121 ], [ %t789, %b122 ]
+  store double %t790, ptr %s5
+  %t791 = load double, ptr %s0
+  %t792 = load double, ptr %s1
+  %t793 = load double, ptr %s2
+  %t794 = load double, ptr %s3
+  %t795 = load double, ptr %s4
+  %t796 = load double, ptr %s5
+  %t797 = bitcast double %t791 to i64
+  call void @jsrt_print_value(i64 %t797, i32 32)
+  %t798 = bitcast double %t792 to i64
+  call void @jsrt_print_value(i64 %t798, i32 32)
+  %t799 = bitcast double %t793 to i64
+  call void @jsrt_print_value(i64 %t799, i32 32)
+  %t800 = bitcast double %t794 to i64
+  call void @jsrt_print_value(i64 %t800, i32 32)
+  %t801 = bitcast double %t795 to i64
+  call void @jsrt_print_value(i64 %t801, i32 32)
+  %t802 = bitcast double %t796 to i64
+  call void @jsrt_print_value(i64 %t802, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t803 = ptrtoint ptr @.str.30 to i64
+  %t804 = or i64 %t803, -1970324836974592
+  %t805 = bitcast i64 %t804 to double
+  store double %t805, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t806 = load double, ptr %s0
+  %t807 = load double, ptr %s1
+  %t808 = bitcast double %t806 to i64
+  %t809 = icmp ule i64 %t808, -2251799813685248
+  %t810 = and i1 true, %t809
+  %t811 = bitcast double %t807 to i64
+  %t812 = icmp ule i64 %t811, -2251799813685248
+  %t813 = and i1 %t810, %t812
+  br i1 %t813, label %b124, label %b125
+b124:
+  %t814 = fmul double %t806, %t807
+  br label %b126
+b125:
+  %t815 = bitcast double %t806 to i64
+  %t816 = bitcast double %t807 to i64
+  %t817 = call i64 @jsrt_mul(i64 %t815, i64 %t816)
+  %t818 = bitcast i64 %t817 to double
+  br label %b126
+b126:
+  %t819 = phi double [ %t814, %b124 ], [ %t818, %b125 ]
+  store double %t819, ptr %s0
+  %t820 = ptrtoint ptr @.str.31 to i64
+  %t821 = or i64 %t820, -1970324836974592
+  %t822 = bitcast i64 %t821 to double
+  store double %t822, ptr %s1
+  store double 0x3FF0000000000000, ptr %s2
+  %t823 = load double, ptr %s1
+  %t824 = load double, ptr %s2
+  %t825 = bitcast double %t823 to i64
+  %t826 = icmp ule i64 %t825, -2251799813685248
+  %t827 = and i1 true, %t826
+  %t828 = bitcast double %t824 to i64
+  %t829 = icmp ule i64 %t828, -2251799813685248
+  %t830 = and i1 %t827, %t829
+  br i1 %t830, label %b127, label %b128
+b127:
+  %t831 = fmul double %t823, %t824
+  br label %b129
+b128:
+  %t832 = bitcast double %t823 to i64
+  %t833 = bitcast double %t824 to i64
+  %t834 = call i64 @jsrt_mul(i64 %t832, i64 %t833)
+  %t835 = bitcast i64 %t834 to double
+  br label %b129
+b129:
+  %t836 = phi double [ %t831, %b127 ], [ %t835, %b128 ]
+  store double %t836, ptr %s1
+  %t837 = ptrtoint ptr @.str.32 to i64
+  %t838 = or i64 %t837, -1970324836974592
+  %t839 = bitcast i64 %t838 to double
+  store double %t839, ptr %s2
+  store double 0x3FF0000000000000, ptr %s3
+  %t840 = load double, ptr %s2
+  %t841 = load double, ptr %s3
+  %t842 = bitcast double %t840 to i64
+  %t843 = icmp ule i64 %t842, -2251799813685248
+  %t844 = and i1 true, %t843
+  %t845 = bitcast double %t841 to i64
+  %t846 = icmp ule i64 %t845, -2251799813685248
+  %t847 = and i1 %t844, %t846
+  br i1 %t847, label %b130, label %b131
+b130:
+  %t848 = fmul double %t840, %t841
+  br label %b132
+b131:
+  %t849 = bitcast double %t840 to i64
+  %t850 = bitcast double %t841 to i64
+  %t851 = call i64 @jsrt_mul(i64 %t849, i64 %t850)
+  %t852 = bitcast i64 %t851 to double
+  br label %b132
+b132:
+  %t853 = phi double [ %t848, %b130 ], [ %t852, %b131 ]
+  store double %t853, ptr %s2
+  %t854 = ptrtoint ptr @.str.33 to i64
+  %t855 = or i64 %t854, -1970324836974592
+  %t856 = bitcast i64 %t855 to double
+  store double %t856, ptr %s3
+  store double 0x3FF0000000000000, ptr %s4
+  %t857 = load double, ptr %s3
+  %t858 = load double, ptr %s4
+  %t859 = bitcast double %t857 to i64
+  %t860 = icmp ule i64 %t859, -2251799813685248
+  %t861 = and i1 true, %t860
+  %t862 = bitcast double %t858 to i64
+  %t863 = icmp ule i64 %t862, -2251799813685248
+  %t864 = and i1 %t861, %t863
+  br i1 %t864, label %b133, label %b134
+b133:
+  %t865 = fmul double %t857, %t858
+  br label %b135
+b134:
+  %t866 = bitcast double %t857 to i64
+  %t867 = bitcast double %t858 to i64
+  %t868 = call i64 @jsrt_mul(i64 %t866, i64 %t867)
+  %t869 = bitcast i64 %t868 to double
+  br label %b135
+b135:
+  %t870 = phi double [ %t865, %b133 ], [ %t869, %b134 ]
+  store double %t870, ptr %s3
+  %t871 = ptrtoint ptr @.str.34 to i64
+  %t872 = or i64 %t871, -1970324836974592
+  %t873 = bitcast i64 %t872 to double
+  store double %t873, ptr %s4
+  store double 0x3FF0000000000000, ptr %s5
+  %t874 = load double, ptr %s4
+  %t875 = load double, ptr %s5
+  %t876 = bitcast double %t874 to i64
+  %t877 = icmp ule i64 %t876, -2251799813685248
+  %t878 = and i1 true, %t877
+  %t879 = bitcast double %t875 to i64
+  %t880 = icmp ule i64 %t879, -2251799813685248
+  %t881 = and i1 %t878, %t880
+  br i1 %t881, label %b136, label %b137
+b136:
+  %t882 = fmul double %t874, %t875
+  br label %b138
+b137:
+  %t883 = bitcast double %t874 to i64
+  %t884 = bitcast double %t875 to i64
+  %t885 = call i64 @jsrt_mul(i64 %t883, i64 %t884)
+  %t886 = bitcast i64 %t885 to double
+  br label %b138
+b138:
+  %t887 = phi double [ %t882, %b136 ], [ %t886, %b137 ]
+  store double %t887, ptr %s4
+  %t888 = ptrtoint ptr @.str.35 to i64
+  %t889 = or i64 %t888, -1970324836974592
+  %t890 = bitcast i64 %t889 to double
+  store double %t890, ptr %s5
+  store double 0x3FF0000000000000, ptr %s6
+  %t891 = load double, ptr %s5
+  %t892 = load double, ptr %s6
+  %t893 = bitcast double %t891 to i64
+  %t894 = icmp ule i64 %t893, -2251799813685248
+  %t895 = and i1 true, %t894
+  %t896 = bitcast double %t892 to i64
+  %t897 = icmp ule i64 %t896, -2251799813685248
+  %t898 = and i1 %t895, %t897
+  br i1 %t898, label %b139, label %b140
+b139:
+  %t899 = fmul double %t891, %t892
+  br label %b141
+b140:
+  %t900 = bitcast double %t891 to i64
+  %t901 = bitcast double %t892 to i64
+  %t902 = call i64 @jsrt_mul(i64 %t900, i64 %t901)
+  %t903 = bitcast i64 %t902 to double
+  br label %b141
+b141:
+  %t904 = phi double [ %t899, %b139 ], [ %t903, %b140 ]
+  store double %t904, ptr %s5
+  %t905 = load double, ptr %s0
+  %t906 = load double, ptr %s1
+  %t907 = load double, ptr %s2
+  %t908 = load double, ptr %s3
+  %t909 = load double, ptr %s4
+  %t910 = load double, ptr %s5
+  %t911 = bitcast double %t905 to i64
+  call void @jsrt_print_value(i64 %t911, i32 32)
+  %t912 = bitcast double %t906 to i64
+  call void @jsrt_print_value(i64 %t912, i32 32)
+  %t913 = bitcast double %t907 to i64
+  call void @jsrt_print_value(i64 %t913, i32 32)
+  %t914 = bitcast double %t908 to i64
+  call void @jsrt_print_value(i64 %t914, i32 32)
+  %t915 = bitcast double %t909 to i64
+  call void @jsrt_print_value(i64 %t915, i32 32)
+  %t916 = bitcast double %t910 to i64
+  call void @jsrt_print_value(i64 %t916, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t917 = ptrtoint ptr @.str.36 to i64
+  %t918 = or i64 %t917, -1970324836974592
+  %t919 = bitcast i64 %t918 to double
+  store double %t919, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t920 = load double, ptr %s0
+  %t921 = load double, ptr %s1
+  %t922 = bitcast double %t920 to i64
+  %t923 = icmp ule i64 %t922, -2251799813685248
+  %t924 = and i1 true, %t923
+  %t925 = bitcast double %t921 to i64
+  %t926 = icmp ule i64 %t925, -2251799813685248
+  %t927 = and i1 %t924, %t926
+  br i1 %t927, label %b142, label %b143
+b142:
+  %t928 = fmul double %t920, %t921
+  br label %b144
+b143:
+  %t929 = bitcast double %t920 to i64
+  %t930 = bitcast double %t921 to i64
+  %t931 = call i64 @jsrt_mul(i64 %t929, i64 %t930)
+  %t932 = bitcast i64 %t931 to double
+  br label %b144
+b144:
+  %t933 = phi double [ %t928, %b142 ], [ %t932, %b143 ]
+  store double %t933, ptr %s0
+  %t934 = ptrtoint ptr @.str.37 to i64
+  %t935 = or i64 %t934, -1970324836974592
+  %t936 = bitcast i64 %t935 to double
+  store double %t936, ptr %s1
+  store double 0x3FF0000000000000, ptr %s2
+  %t937 = load double, ptr %s1
+  %t938 = load double, ptr %s2
+  %t939 = bitcast double %t937 to i64
+  %t940 = icmp ule i64 %t939, -2251799813685248
+  %t941 = and i1 true, %t940
+  %t942 = bitcast double %t938 to i64
+  %t943 = icmp ule i64 %t942, -2251799813685248
+  %t944 = and i1 %t941, %t943
+  br i1 %t944, label %b145, label %b146
+b145:
+  %t945 = fmul double %t937, %t938
+  br label %b147
+b146:
+  %t946 = bitcast double %t937 to i64
+  %t947 = bitcast double %t938 to i64
+  %t948 = call i64 @jsrt_mul(i64 %t946, i64 %t947)
+  %t949 = bitcast i64 %t948 to double
+  br label %b147
+b147:
+  %t950 = phi double [ %t945, %b145 ], [ %t949, %b146 ]
+  store double %t950, ptr %s1
+  %t951 = ptrtoint ptr @.str.38 to i64
+  %t952 = or i64 %t951, -1970324836974592
+  %t953 = bitcast i64 %t952 to double
+  store double %t953, ptr %s2
+  store double 0x3FF0000000000000, ptr %s3
+  %t954 = load double, ptr %s2
+  %t955 = load double, ptr %s3
+  %t956 = bitcast double %t954 to i64
+  %t957 = icmp ule i64 %t956, -2251799813685248
+  %t958 = and i1 true, %t957
+  %t959 = bitcast double %t955 to i64
+  %t960 = icmp ule i64 %t959, -2251799813685248
+  %t961 = and i1 %t958, %t960
+  br i1 %t961, label %b148, label %b149
+b148:
+  %t962 = fmul double %t954, %t955
+  br label %b150
+b149:
+  %t963 = bitcast double %t954 to i64
+  %t964 = bitcast double %t955 to i64
+  %t965 = call i64 @jsrt_mul(i64 %t963, i64 %t964)
+  %t966 = bitcast i64 %t965 to double
+  br label %b150
+b150:
+  %t967 = phi double [ %t962, %b148 ], [ %t966, %b149 ]
+  store double %t967, ptr %s2
+  %t968 = ptrtoint ptr @.str.39 to i64
+  %t969 = or i64 %t968, -1970324836974592
+  %t970 = bitcast i64 %t969 to double
+  store double %t970, ptr %s3
+  store double 0x3FF0000000000000, ptr %s4
+  %t971 = load double, ptr %s3
+  %t972 = load double, ptr %s4
+  %t973 = bitcast double %t971 to i64
+  %t974 = icmp ule i64 %t973, -2251799813685248
+  %t975 = and i1 true, %t974
+  %t976 = bitcast double %t972 to i64
+  %t977 = icmp ule i64 %t976, -2251799813685248
+  %t978 = and i1 %t975, %t977
+  br i1 %t978, label %b151, label %b152
+b151:
+  %t979 = fmul double %t971, %t972
+  br label %b153
+b152:
+  %t980 = bitcast double %t971 to i64
+  %t981 = bitcast double %t972 to i64
+  %t982 = call i64 @jsrt_mul(i64 %t980, i64 %t981)
+  %t983 = bitcast i64 %t982 to double
+  br label %b153
+b153:
+  %t984 = phi double [ %t979, %b151 ], [ %t983, %b152 ]
+  store double %t984, ptr %s3
+  %t985 = ptrtoint ptr @.str.40 to i64
+  %t986 = or i64 %t985, -1970324836974592
+  %t987 = bitcast i64 %t986 to double
+  store double %t987, ptr %s4
+  store double 0x4008000000000000, ptr %s5
+  %t988 = load double, ptr %s4
+  %t989 = load double, ptr %s5
+  %t990 = bitcast double %t988 to i64
+  %t991 = icmp ule i64 %t990, -2251799813685248
+  %t992 = and i1 true, %t991
+  %t993 = bitcast double %t989 to i64
+  %t994 = icmp ule i64 %t993, -2251799813685248
+  %t995 = and i1 %t992, %t994
+  br i1 %t995, label %b154, label %b155
+b154:
+  %t996 = fmul double %t988, %t989
+  br label %b156
+b155:
+  %t997 = bitcast double %t988 to i64
+  %t998 = bitcast double %t989 to i64
+  %t999 = call i64 @jsrt_mul(i64 %t997, i64 %t998)
+  %t1000 = bitcast i64 %t999 to double
+  br label %b156
+b156:
+  %t1001 = phi double [ %t996, %b154 ], [ %t1000, %b155 ]
+  store double %t1001, ptr %s4
+  %t1002 = load double, ptr %s0
+  %t1003 = load double, ptr %s1
+  %t1004 = load double, ptr %s2
+  %t1005 = load double, ptr %s3
+  %t1006 = load double, ptr %s4
+  %t1007 = bitcast double %t1002 to i64
+  call void @jsrt_print_value(i64 %t1007, i32 32)
+  %t1008 = bitcast double %t1003 to i64
+  call void @jsrt_print_value(i64 %t1008, i32 32)
+  %t1009 = bitcast double %t1004 to i64
+  call void @jsrt_print_value(i64 %t1009, i32 32)
+  %t1010 = bitcast double %t1005 to i64
+  call void @jsrt_print_value(i64 %t1010, i32 32)
+  %t1011 = bitcast double %t1006 to i64
+  call void @jsrt_print_value(i64 %t1011, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t1012 = load double, ptr %s0
+  ret double %t1012
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+@.str.0 = private unnamed_addr constant [2 x i8] c"\31\00"
+@.str.1 = private unnamed_addr constant [1 x i8] c"\00"
+@.str.2 = private unnamed_addr constant [2 x i8] c"\31\00"
+@.str.3 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.4 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.5 = private unnamed_addr constant [2 x i8] c"\32\00"
+@.str.6 = private unnamed_addr constant [2 x i8] c"\32\00"
+@.str.7 = private unnamed_addr constant [3 x i8] c"\31\30\00"
+@.str.8 = private unnamed_addr constant [2 x i8] c"\39\00"
+@.str.9 = private unnamed_addr constant [2 x i8] c"\39\00"
+@.str.10 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.11 = private unnamed_addr constant [2 x i8] c"\32\00"
+@.str.12 = private unnamed_addr constant [2 x i8] c"\62\00"
+@.str.13 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.14 = private unnamed_addr constant [2 x i8] c"\33\00"
+@.str.15 = private unnamed_addr constant [2 x i8] c"\36\00"
+@.str.16 = private unnamed_addr constant [2 x i8] c"\33\00"
+@.str.17 = private unnamed_addr constant [2 x i8] c"\78\00"
+@.str.18 = private unnamed_addr constant [2 x i8] c"\31\00"
+@.str.19 = private unnamed_addr constant [2 x i8] c"\38\00"
+@.str.20 = private unnamed_addr constant [2 x i8] c"\32\00"
+@.str.21 = private unnamed_addr constant [2 x i8] c"\34\00"
+@.str.22 = private unnamed_addr constant [2 x i8] c"\37\00"
+@.str.23 = private unnamed_addr constant [2 x i8] c"\35\00"
+@.str.24 = private unnamed_addr constant [5 x i8] c"\20\31\32\20\00"
+@.str.25 = private unnamed_addr constant [5 x i8] c"\30\78\31\66\00"
+@.str.26 = private unnamed_addr constant [6 x i8] c"\30\62\31\30\31\00"
+@.str.27 = private unnamed_addr constant [5 x i8] c"\30\6F\31\37\00"
+@.str.28 = private unnamed_addr constant [10 x i8] c"\2D\49\6E\66\69\6E\69\74\79\00"
+@.str.29 = private unnamed_addr constant [4 x i8] c"\31\65\33\00"
+@.str.30 = private unnamed_addr constant [3 x i8] c"\2E\35\00"
+@.str.31 = private unnamed_addr constant [3 x i8] c"\35\2E\00"
+@.str.32 = private unnamed_addr constant [3 x i8] c"\31\65\00"
+@.str.33 = private unnamed_addr constant [6 x i8] c"\2D\30\78\31\30\00"
+@.str.34 = private unnamed_addr constant [7 x i8] c"\31\2E\35\65\2D\33\00"
+@.str.35 = private unnamed_addr constant [4 x i8] c"\69\6E\66\00"
+@.str.36 = private unnamed_addr constant [6 x i8] c"\31\65\34\30\30\00"
+@.str.37 = private unnamed_addr constant [7 x i8] c"\31\65\2D\34\30\30\00"
+@.str.38 = private unnamed_addr constant [3 x i8] c"\09\0A\00"
+@.str.39 = private unnamed_addr constant [3 x i8] c"\2B\37\00"
+@.str.40 = private unnamed_addr constant [4 x i8] c"\30\2E\31\00"
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_div(i64, i64)
+declare i64 @jsrt_eq(i64, i64)
+declare i64 @jsrt_ge(i64, i64)
+declare i64 @jsrt_gt(i64, i64)
+declare i64 @jsrt_le(i64, i64)
+declare i64 @jsrt_lt(i64, i64)
+declare i64 @jsrt_mod(i64, i64)
+declare i64 @jsrt_mul(i64, i64)
+declare i64 @jsrt_ne(i64, i64)
+declare i64 @jsrt_neg(i64)
+declare i64 @jsrt_strict_eq(i64, i64)
+declare i64 @jsrt_sub(i64, i64)
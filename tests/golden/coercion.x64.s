@@ -0,0 +1,1978 @@
+	.text
+	.globl nothing
+	.type nothing, @function
+nothing:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movabs $18445618173802708992, %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD0(%rip), %rax
+	push %rax
+	leaq .LC0(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.1
+	cmp %rdx, %rcx
+	ja .Lmain.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.2
+.Lmain.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.2:
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call nothing
+	mov %rbx, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.3
+	cmp %rdx, %rcx
+	ja .Lmain.3
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.4
+.Lmain.3:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.4:
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.5
+	cmp %rdx, %rcx
+	ja .Lmain.5
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.6
+.Lmain.5:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.6:
+	push %rax
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call nothing
+	mov %rbx, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.7
+	cmp %rdx, %rcx
+	ja .Lmain.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.8
+.Lmain.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.8:
+	push %rax
+	leaq .LC1(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD3(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.9
+	cmp %rdx, %rcx
+	ja .Lmain.9
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.10
+.Lmain.9:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.10:
+	push %rax
+	movabs $18445055223849287681, %rax
+	push %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.11
+	cmp %rdx, %rcx
+	ja .Lmain.11
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.12
+.Lmain.11:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.12:
+	push %rax
+	mov 40(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $48, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD5(%rip), %rax
+	push %rax
+	leaq .LC2(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.13
+	cmp %rdx, %rcx
+	ja .Lmain.13
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.14
+.Lmain.13:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lmain.14:
+	push %rax
+	leaq .LC3(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC4(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.15
+	cmp %rdx, %rcx
+	ja .Lmain.15
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.16
+.Lmain.15:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lmain.16:
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.17
+	cmp %rdx, %rcx
+	ja .Lmain.17
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.18
+.Lmain.17:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lmain.18:
+	push %rax
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call nothing
+	mov %rbx, %rsp
+	movq %xmm0, %rax
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.19
+	cmp %rdx, %rcx
+	ja .Lmain.19
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setne %al
+	setp %cl
+	or %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.20
+.Lmain.19:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_ne
+	mov %rbx, %rsp
+.Lmain.20:
+	push %rax
+	mov .LCD6(%rip), %rax
+	push %rax
+	leaq .LC5(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.21
+	cmp %rdx, %rcx
+	ja .Lmain.21
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setne %al
+	setp %cl
+	or %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.22
+.Lmain.21:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_ne
+	mov %rbx, %rsp
+.Lmain.22:
+	push %rax
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $40, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD7(%rip), %rax
+	push %rax
+	leaq .LC6(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.23
+	cmp %rdx, %rcx
+	ja .Lmain.23
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.24
+.Lmain.23:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.24:
+	push %rax
+	leaq .LC7(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC8(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.25
+	cmp %rdx, %rcx
+	ja .Lmain.25
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.26
+.Lmain.25:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.26:
+	push %rax
+	mov .LCD8(%rip), %rax
+	push %rax
+	leaq .LC9(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.27
+	cmp %rdx, %rcx
+	ja .Lmain.27
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.28
+.Lmain.27:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.28:
+	push %rax
+	leaq .LC10(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD9(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.29
+	cmp %rdx, %rcx
+	ja .Lmain.29
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.30
+.Lmain.29:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.30:
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	mov .LCD10(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.31
+	cmp %rdx, %rcx
+	ja .Lmain.31
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.32
+.Lmain.31:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.32:
+	push %rax
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call nothing
+	mov %rbx, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD11(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.33
+	cmp %rdx, %rcx
+	ja .Lmain.33
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.34
+.Lmain.33:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.34:
+	push %rax
+	mov 40(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $48, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD12(%rip), %rax
+	push %rax
+	leaq .LC11(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.35
+	cmp %rdx, %rcx
+	ja .Lmain.35
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.36
+.Lmain.35:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_ge
+	mov %rbx, %rsp
+.Lmain.36:
+	push %rax
+	leaq .LC12(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC13(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.37
+	cmp %rdx, %rcx
+	ja .Lmain.37
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.38
+.Lmain.37:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_gt
+	mov %rbx, %rsp
+.Lmain.38:
+	push %rax
+	movabs $18445055223849287681, %rax
+	push %rax
+	mov .LCD13(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.39
+	cmp %rdx, %rcx
+	ja .Lmain.39
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.40
+.Lmain.39:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_gt
+	mov %rbx, %rsp
+.Lmain.40:
+	push %rax
+	leaq .LC14(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD14(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.41
+	cmp %rdx, %rcx
+	ja .Lmain.41
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.42
+.Lmain.41:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_le
+	mov %rbx, %rsp
+.Lmain.42:
+	push %rax
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $32, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD15(%rip), %rax
+	push %rax
+	mov .LCD16(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD17(%rip), %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov .LCD18(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD19(%rip), %rax
+	push %rax
+	mov .LCD20(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	leaq .LC15(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC16(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.43
+	cmp %rdx, %rcx
+	ja .Lmain.43
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.44
+.Lmain.43:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_div
+	mov %rbx, %rsp
+.Lmain.44:
+	push %rax
+	leaq .LC17(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD21(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.45
+	cmp %rdx, %rcx
+	ja .Lmain.45
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.46
+.Lmain.45:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_div
+	mov %rbx, %rsp
+.Lmain.46:
+	push %rax
+	movabs $18445055223849287681, %rax
+	push %rax
+	mov .LCD22(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.47
+	cmp %rdx, %rcx
+	ja .Lmain.47
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.48
+.Lmain.47:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.48:
+	push %rax
+	leaq .LC18(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD23(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.49
+	cmp %rdx, %rcx
+	ja .Lmain.49
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.50
+.Lmain.49:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.50:
+	push %rax
+	movabs $18445336698825998336, %rax
+	push %rax
+	mov .LCD24(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.51
+	cmp %rdx, %rcx
+	ja .Lmain.51
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.52
+.Lmain.51:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.52:
+	push %rax
+	mov 56(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 48(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 40(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $64, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC19(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD25(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.53
+	cmp %rdx, %rcx
+	ja .Lmain.53
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	subsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.54
+.Lmain.53:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_sub
+	mov %rbx, %rsp
+.Lmain.54:
+	push %rax
+	leaq .LC20(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC21(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.55
+	cmp %rdx, %rcx
+	ja .Lmain.55
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.56
+.Lmain.55:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.56:
+	push %rax
+	leaq .LC22(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD26(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mod
+	mov %rbx, %rsp
+	push %rax
+	leaq .LC23(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.57
+	btc $63, %rax
+	jmp .Lmain.58
+.Lmain.57:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_neg
+	mov %rbx, %rsp
+.Lmain.58:
+	push %rax
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call nothing
+	mov %rbx, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD27(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.59
+	cmp %rdx, %rcx
+	ja .Lmain.59
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.60
+.Lmain.59:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.60:
+	push %rax
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $40, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC24(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD28(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.61
+	cmp %rdx, %rcx
+	ja .Lmain.61
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.62
+.Lmain.61:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.62:
+	push %rax
+	leaq .LC25(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD29(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.63
+	cmp %rdx, %rcx
+	ja .Lmain.63
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.64
+.Lmain.63:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.64:
+	push %rax
+	leaq .LC26(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD30(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.65
+	cmp %rdx, %rcx
+	ja .Lmain.65
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.66
+.Lmain.65:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.66:
+	push %rax
+	leaq .LC27(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD31(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.67
+	cmp %rdx, %rcx
+	ja .Lmain.67
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.68
+.Lmain.67:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.68:
+	push %rax
+	leaq .LC28(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD32(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.69
+	cmp %rdx, %rcx
+	ja .Lmain.69
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.70
+.Lmain.69:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.70:
+	push %rax
+	leaq .LC29(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD33(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.71
+	cmp %rdx, %rcx
+	ja .Lmain.71
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.72
+.Lmain.71:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.72:
+	push %rax
+	mov 40(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $48, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC30(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD34(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.73
+	cmp %rdx, %rcx
+	ja .Lmain.73
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.74
+.Lmain.73:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.74:
+	push %rax
+	leaq .LC31(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD35(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.75
+	cmp %rdx, %rcx
+	ja .Lmain.75
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.76
+.Lmain.75:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.76:
+	push %rax
+	leaq .LC32(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD36(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.77
+	cmp %rdx, %rcx
+	ja .Lmain.77
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.78
+.Lmain.77:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.78:
+	push %rax
+	leaq .LC33(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD37(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.79
+	cmp %rdx, %rcx
+	ja .Lmain.79
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.80
+.Lmain.79:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.80:
+	push %rax
+	leaq .LC34(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD38(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.81
+	cmp %rdx, %rcx
+	ja .Lmain.81
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.82
+.Lmain.81:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.82:
+	push %rax
+	leaq .LC35(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD39(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.83
+	cmp %rdx, %rcx
+	ja .Lmain.83
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.84
+.Lmain.83:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.84:
+	push %rax
+	mov 40(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $48, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC36(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD40(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.85
+	cmp %rdx, %rcx
+	ja .Lmain.85
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.86
+.Lmain.85:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.86:
+	push %rax
+	leaq .LC37(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD41(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.87
+	cmp %rdx, %rcx
+	ja .Lmain.87
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.88
+.Lmain.87:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.88:
+	push %rax
+	leaq .LC38(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD42(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.89
+	cmp %rdx, %rcx
+	ja .Lmain.89
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.90
+.Lmain.89:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.90:
+	push %rax
+	leaq .LC39(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD43(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.91
+	cmp %rdx, %rcx
+	ja .Lmain.91
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.92
+.Lmain.91:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.92:
+	push %rax
+	leaq .LC40(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD44(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.93
+	cmp %rdx, %rcx
+	ja .Lmain.93
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.94
+.Lmain.93:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mul
+	mov %rbx, %rsp
+.Lmain.94:
+	push %rax
+	mov 32(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $40, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD45(%rip), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+.LC0:
+	.asciz "1"
+.LC1:
+	.asciz ""
+.LC2:
+	.asciz "1"
+.LC3:
+	.asciz "a"
+.LC4:
+	.asciz "a"
+.LC5:
+	.asciz "2"
+.LC6:
+	.asciz "2"
+.LC7:
+	.asciz "10"
+.LC8:
+	.asciz "9"
+.LC9:
+	.asciz "9"
+.LC10:
+	.asciz "a"
+.LC11:
+	.asciz "2"
+.LC12:
+	.asciz "b"
+.LC13:
+	.asciz "a"
+.LC14:
+	.asciz "3"
+.LC15:
+	.asciz "6"
+.LC16:
+	.asciz "3"
+.LC17:
+	.asciz "x"
+.LC18:
+	.asciz "1"
+.LC19:
+	.asciz "8"
+.LC20:
+	.asciz "2"
+.LC21:
+	.asciz "4"
+.LC22:
+	.asciz "7"
+.LC23:
+	.asciz "5"
+.LC24:
+	.asciz " 12 "
+.LC25:
+	.asciz "0x1f"
+.LC26:
+	.asciz "0b101"
+.LC27:
+	.asciz "0o17"
+.LC28:
+	.asciz "-Infinity"
+.LC29:
+	.asciz "1e3"
+.LC30:
+	.asciz ".5"
+.LC31:
+	.asciz "5."
+.LC32:
+	.asciz "1e"
+.LC33:
+	.asciz "-0x10"
+.LC34:
+	.asciz "1.5e-3"
+.LC35:
+	.asciz "inf"
+.LC36:
+	.asciz "1e400"
+.LC37:
+	.asciz "1e-400"
+.LC38:
+	.asciz "\011\012"
+.LC39:
+	.asciz "+7"
+.LC40:
+	.asciz "0.1"
+	.p2align 3
+.LCD0:
+	.double 1
+.LCD1:
+	.double 0
+.LCD2:
+	.double 0
+.LCD3:
+	.double 0
+.LCD4:
+	.double 1
+.LCD5:
+	.double 1
+.LCD6:
+	.double 1
+.LCD7:
+	.double 1
+.LCD8:
+	.double 10
+.LCD9:
+	.double 1
+.LCD10:
+	.double 1
+.LCD11:
+	.double 1
+.LCD12:
+	.double 2
+.LCD13:
+	.double 0
+.LCD14:
+	.double 2
+.LCD15:
+	.double 1
+.LCD16:
+	.double 0
+.LCD17:
+	.double 1
+.LCD18:
+	.double 0
+.LCD19:
+	.double 0
+.LCD20:
+	.double 0
+.LCD21:
+	.double 2
+.LCD22:
+	.double 1
+.LCD23:
+	.double 1
+.LCD24:
+	.double 1
+.LCD25:
+	.double 3
+.LCD26:
+	.double 4
+.LCD27:
+	.double 1
+.LCD28:
+	.double 1
+.LCD29:
+	.double 1
+.LCD30:
+	.double 1
+.LCD31:
+	.double 1
+.LCD32:
+	.double 1
+.LCD33:
+	.double 1
+.LCD34:
+	.double 1
+.LCD35:
+	.double 1
+.LCD36:
+	.double 1
+.LCD37:
+	.double 1
+.LCD38:
+	.double 1
+.LCD39:
+	.double 1
+.LCD40:
+	.double 1
+.LCD41:
+	.double 1
+.LCD42:
+	.double 1
+.LCD43:
+	.double 1
+.LCD44:
+	.double 3
+.LCD45:
+	.double 0
+	.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,238 @@
+	.text
+	.global fibonacci
+	.type fibonacci, %function
+	.p2align 2
+fibonacci:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfibonacci.1
+	cmp x1, x9
+	b.hi .Lfibonacci.1
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ls
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lfibonacci.2
+.Lfibonacci.1:
+	bl jsrt_le
+.Lfibonacci.2:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfibonacci.3
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lfibonacci.4
+.Lfibonacci.3:
+	bl jsrt_not
+.Lfibonacci.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfibonacci.5
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lfibonacci_L1
+	b .Lfibonacci.6
+.Lfibonacci.5:
+	bl jsrt_truthy
+	cbnz x0, .Lfibonacci_L1
+.Lfibonacci.6:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	b .Lfibonacci_L2
+.Lfibonacci_L1:
+.Lfibonacci_L2:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfibonacci.7
+	cmp x1, x9
+	b.hi .Lfibonacci.7
+	fmov d0, x0
+	fmov d1, x1
+	fsub d0, d0, d1
+	fmov x0, d0
+	b .Lfibonacci.8
+.Lfibonacci.7:
+	bl jsrt_sub
+.Lfibonacci.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl fibonacci
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfibonacci.9
+	cmp x1, x9
+	b.hi .Lfibonacci.9
+	fmov d0, x0
+	fmov d1, x1
+	fsub d0, d0, d1
+	fmov x0, d0
+	b .Lfibonacci.10
+.Lfibonacci.9:
+	bl jsrt_sub
+.Lfibonacci.10:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl fibonacci
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfibonacci.11
+	cmp x1, x9
+	b.hi .Lfibonacci.11
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lfibonacci.12
+.Lfibonacci.11:
+	bl jsrt_add
+.Lfibonacci.12:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl fibonacci
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl fibonacci
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl fibonacci
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+	.p2align 3
+.LCD0:
+	.double 1
+.LCD1:
+	.double 1
+.LCD2:
+	.double 2
+.LCD3:
+	.double 10
+.LCD4:
+	.double 15
+.LCD5:
+	.double 10
+	.section .note.GNU-stack,"",%progbits
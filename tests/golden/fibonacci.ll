@@ -0,0 +1,205 @@
+; Generated by js-compiler
+
+define double @fibonacci(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %s2 = alloca double
+  %v.n = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  %t3 = load double, ptr %v.n
+  store double %t3, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t4 = load double, ptr %s0
+  %t5 = load double, ptr %s1
+  %t6 = bitcast double %t4 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 true, %t7
+  %t9 = bitcast double %t5 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 %t8, %t10
+  br i1 %t11, label %b1, label %b2
+b1:
+  %t12 = fcmp ole double %t4, %t5
+  %t13 = zext i1 %t12 to i64
+  %t14 = or i64 %t13, -1688849860263936
+  %t15 = bitcast i64 %t14 to double
+  br label %b3
+b2:
+  %t16 = bitcast double %t4 to i64
+  %t17 = bitcast double %t5 to i64
+  %t18 = call i64 @jsrt_le(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  %t21 = load double, ptr %s0
+  %t22 = bitcast double %t21 to i64
+  %t23 = icmp ule i64 %t22, -2251799813685248
+  %t24 = and i1 true, %t23
+  br i1 %t24, label %b4, label %b5
+b4:
+  %t25 = fcmp ueq double %t21, 0.0
+  %t26 = zext i1 %t25 to i64
+  %t27 = or i64 %t26, -1688849860263936
+  %t28 = bitcast i64 %t27 to double
+  br label %b6
+b5:
+  %t29 = bitcast double %t21 to i64
+  %t30 = call i64 @jsrt_not(i64 %t29)
+  %t31 = bitcast i64 %t30 to double
+  br label %b6
+b6:
+  %t32 = phi double [ %t28, %b4 ], [ %t31, %b5 ]
+  store double %t32, ptr %s0
+  %t33 = load double, ptr %s0
+  %t34 = bitcast double %t33 to i64
+  %t35 = icmp ule i64 %t34, -2251799813685248
+  %t36 = and i1 true, %t35
+  br i1 %t36, label %b7, label %b8
+b7:
+  %t37 = fcmp one double %t33, 0.0
+  br label %b9
+b8:
+  %t38 = bitcast double %t33 to i64
+  %t39 = call i64 @jsrt_truthy(i64 %t38)
+  %t40 = icmp ne i64 %t39, 0
+  br label %b9
+b9:
+  %t41 = phi i1 [ %t37, %b7 ], [ %t40, %b8 ]
+  br i1 %t41, label %LL1, label %b10
+b10:
+  %t42 = load double, ptr %v.n
+  store double %t42, ptr %s0
+  %t43 = load double, ptr %s0
+  ret double %t43
+LL1:
+  br label %LL2
+LL2:
+  %t44 = load double, ptr %v.n
+  store double %t44, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t45 = load double, ptr %s0
+  %t46 = load double, ptr %s1
+  %t47 = bitcast double %t45 to i64
+  %t48 = icmp ule i64 %t47, -2251799813685248
+  %t49 = and i1 true, %t48
+  %t50 = bitcast double %t46 to i64
+  %t51 = icmp ule i64 %t50, -2251799813685248
+  %t52 = and i1 %t49, %t51
+  br i1 %t52, label %b11, label %b12
+b11:
+  %t53 = fsub double %t45, %t46
+  br label %b13
+b12:
+  %t54 = bitcast double %t45 to i64
+  %t55 = bitcast double %t46 to i64
+  %t56 = call i64 @jsrt_sub(i64 %t54, i64 %t55)
+  %t57 = bitcast i64 %t56 to double
+  br label %b13
+b13:
+  %t58 = phi double [ %t53, %b11 ], [ %t57, %b12 ]
+  store double %t58, ptr %s0
+  %t59 = load double, ptr %s0
+  %t60 = call double @fibonacci(double %t59)
+  store double %t60, ptr %s0
+  %t61 = load double, ptr %v.n
+  store double %t61, ptr %s1
+  store double 0x4000000000000000, ptr %s2
+  %t62 = load double, ptr %s1
+  %t63 = load double, ptr %s2
+  %t64 = bitcast double %t62 to i64
+  %t65 = icmp ule i64 %t64, -2251799813685248
+  %t66 = and i1 true, %t65
+  %t67 = bitcast double %t63 to i64
+  %t68 = icmp ule i64 %t67, -2251799813685248
+  %t69 = and i1 %t66, %t68
+  br i1 %t69, label %b14, label %b15
+b14:
+  %t70 = fsub double %t62, %t63
+  br label %b16
+b15:
+  %t71 = bitcast double %t62 to i64
+  %t72 = bitcast double %t63 to i64
+  %t73 = call i64 @jsrt_sub(i64 %t71, i64 %t72)
+  %t74 = bitcast i64 %t73 to double
+  br label %b16
+b16:
+  %t75 = phi double [ %t70, %b14 ], [ %t74, %b15 ]
+  store double %t75, ptr %s1
+  %t76 = load double, ptr %s1
+  %t77 = call double @fibonacci(double %t76)
+  store double %t77, ptr %s1
+  %t78 = load double, ptr %s0
+  %t79 = load double, ptr %s1
+  %t80 = bitcast double %t78 to i64
+  %t81 = icmp ule i64 %t80, -2251799813685248
+  %t82 = and i1 true, %t81
+  %t83 = bitcast double %t79 to i64
+  %t84 = icmp ule i64 %t83, -2251799813685248
+  %t85 = and i1 %t82, %t84
+  br i1 %t85, label %b17, label %b18
+b17:
+  %t86 = fadd double %t78, %t79
+  br label %b19
+b18:
+  %t87 = bitcast double %t78 to i64
+  %t88 = bitcast double %t79 to i64
+  %t89 = call i64 @jsrt_add(i64 %t87, i64 %t88)
+  %t90 = bitcast i64 %t89 to double
+  br label %b19
+b19:
+  %t91 = phi double [ %t86, %b17 ], [ %t90, %b18 ]
+  store double %t91, ptr %s0
+  %t92 = load double, ptr %s0
+  ret double %t92
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  store double 0x4024000000000000, ptr %s0
+  %t1 = load double, ptr %s0
+  %t2 = call double @fibonacci(double %t1)
+  store double %t2, ptr %s0
+  %t3 = load double, ptr %s0
+  %t4 = bitcast double %t3 to i64
+  call void @jsrt_print_value(i64 %t4, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x402E000000000000, ptr %s0
+  %t5 = load double, ptr %s0
+  %t6 = call double @fibonacci(double %t5)
+  store double %t6, ptr %s0
+  %t7 = load double, ptr %s0
+  %t8 = bitcast double %t7 to i64
+  call void @jsrt_print_value(i64 %t8, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4024000000000000, ptr %s0
+  %t9 = load double, ptr %s0
+  %t10 = call double @fibonacci(double %t9)
+  store double %t10, ptr %s0
+  %t11 = load double, ptr %s0
+  ret double %t11
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_le(i64, i64)
+declare i64 @jsrt_not(i64)
+declare i64 @jsrt_sub(i64, i64)
+declare i64 @jsrt_truthy(i64)
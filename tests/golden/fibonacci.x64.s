@@ -0,0 +1,300 @@
+	.text
+	.globl fibonacci
+	.type fibonacci, @function
+fibonacci:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD0(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfibonacci.1
+	cmp %rdx, %rcx
+	ja .Lfibonacci.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lfibonacci.2
+.Lfibonacci.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_le
+	mov %rbx, %rsp
+.Lfibonacci.2:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfibonacci.3
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lfibonacci.4
+.Lfibonacci.3:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lfibonacci.4:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfibonacci.5
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lfibonacci_L1
+	jmp .Lfibonacci.6
+.Lfibonacci.5:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lfibonacci_L1
+.Lfibonacci.6:
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	jmp .Lfibonacci_L2
+.Lfibonacci_L1:
+.Lfibonacci_L2:
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfibonacci.7
+	cmp %rdx, %rcx
+	ja .Lfibonacci.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	subsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lfibonacci.8
+.Lfibonacci.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_sub
+	mov %rbx, %rsp
+.Lfibonacci.8:
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call fibonacci
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfibonacci.9
+	cmp %rdx, %rcx
+	ja .Lfibonacci.9
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	subsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lfibonacci.10
+.Lfibonacci.9:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_sub
+	mov %rbx, %rsp
+.Lfibonacci.10:
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call fibonacci
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfibonacci.11
+	cmp %rdx, %rcx
+	ja .Lfibonacci.11
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lfibonacci.12
+.Lfibonacci.11:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lfibonacci.12:
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD3(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call fibonacci
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call fibonacci
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD5(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call fibonacci
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+	.p2align 3
+.LCD0:
+	.double 1
+.LCD1:
+	.double 1
+.LCD2:
+	.double 2
+.LCD3:
+	.double 10
+.LCD4:
+	.double 15
+.LCD5:
+	.double 10
+	.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,426 @@
+	.text
+	.global sum_to
+	.type sum_to, %function
+	.p2align 2
+sum_to:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #32
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-24]
+.Lsum_to_L1:
+	ldr x0, [fp, #-24]
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.1
+	cmp x1, x9
+	b.hi .Lsum_to.1
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ls
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsum_to.2
+.Lsum_to.1:
+	bl jsrt_le
+.Lsum_to.2:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.3
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lsum_to.4
+.Lsum_to.3:
+	bl jsrt_not
+.Lsum_to.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lsum_to.5
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lsum_to_L2
+	b .Lsum_to.6
+.Lsum_to.5:
+	bl jsrt_truthy
+	cbnz x0, .Lsum_to_L2
+.Lsum_to.6:
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-24]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+	ldr x0, [fp, #-24]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-24]
+	b .Lsum_to_L1
+.Lsum_to_L2:
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global first_square_over
+	.type first_square_over, %function
+	.p2align 2
+first_square_over:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+.Lfirst_square_over_L1:
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfirst_square_over.7
+	cmp x1, x9
+	b.hi .Lfirst_square_over.7
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, gt
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lfirst_square_over.8
+.Lfirst_square_over.7:
+	bl jsrt_gt
+.Lfirst_square_over.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfirst_square_over.9
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lfirst_square_over.10
+.Lfirst_square_over.9:
+	bl jsrt_not
+.Lfirst_square_over.10:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lfirst_square_over.11
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lfirst_square_over_L3
+	b .Lfirst_square_over.12
+.Lfirst_square_over.11:
+	bl jsrt_truthy
+	cbnz x0, .Lfirst_square_over_L3
+.Lfirst_square_over.12:
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	b .Lfirst_square_over_L4
+.Lfirst_square_over_L3:
+.Lfirst_square_over_L4:
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+	b .Lfirst_square_over_L1
+.Lfirst_square_over_L2:
+	mov x0, #0xfffc000000000000
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sum_to
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl first_square_over
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+.Lmain_L1:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.13
+	cmp x1, x9
+	b.hi .Lmain.13
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.14
+.Lmain.13:
+	bl jsrt_lt
+.Lmain.14:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.15
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.16
+.Lmain.15:
+	bl jsrt_not
+.Lmain.16:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.17
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lmain_L2
+	b .Lmain.18
+.Lmain.17:
+	bl jsrt_truthy
+	cbnz x0, .Lmain_L2
+.Lmain.18:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.19
+	cmp x1, x9
+	b.hi .Lmain.19
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.20
+.Lmain.19:
+	bl jsrt_add
+.Lmain.20:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	b .Lmain_L1
+.Lmain_L2:
+	adrp x0, .LCD10
+	ldr d0, [x0, :lo12:.LCD10]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl sum_to
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+	.p2align 3
+.LCD0:
+	.double 0
+.LCD1:
+	.double 1
+.LCD2:
+	.double 1
+.LCD3:
+	.double 0
+.LCD4:
+	.double 1
+.LCD5:
+	.double 10
+.LCD6:
+	.double 50
+.LCD7:
+	.double 0
+.LCD8:
+	.double 3
+.LCD9:
+	.double 1
+.LCD10:
+	.double 4
+	.section .note.GNU-stack,"",%progbits
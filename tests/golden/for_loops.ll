@@ -0,0 +1,437 @@
+; Generated by js-compiler
+
+define double @sum_to(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.n = alloca double
+  %v.total = alloca double
+  %v.i = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  store double 0x0000000000000000, ptr %s0
+  %t3 = load double, ptr %s0
+  store double %t3, ptr %v.total
+  store double 0x3FF0000000000000, ptr %s0
+  %t4 = load double, ptr %s0
+  store double %t4, ptr %v.i
+  br label %LL1
+LL1:
+  %t5 = load double, ptr %v.i
+  store double %t5, ptr %s0
+  %t6 = load double, ptr %v.n
+  store double %t6, ptr %s1
+  %t7 = load double, ptr %s0
+  %t8 = load double, ptr %s1
+  %t9 = bitcast double %t7 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 true, %t10
+  %t12 = bitcast double %t8 to i64
+  %t13 = icmp ule i64 %t12, -2251799813685248
+  %t14 = and i1 %t11, %t13
+  br i1 %t14, label %b1, label %b2
+b1:
+  %t15 = fcmp ole double %t7, %t8
+  %t16 = zext i1 %t15 to i64
+  %t17 = or i64 %t16, -1688849860263936
+  %t18 = bitcast i64 %t17 to double
+  br label %b3
+b2:
+  %t19 = bitcast double %t7 to i64
+  %t20 = bitcast double %t8 to i64
+  %t21 = call i64 @jsrt_le(i64 %t19, i64 %t20)
+  %t22 = bitcast i64 %t21 to double
+  br label %b3
+b3:
+  %t23 = phi double [ %t18, %b1 ], [ %t22, %b2 ]
+  store double %t23, ptr %s0
+  %t24 = load double, ptr %s0
+  %t25 = bitcast double %t24 to i64
+  %t26 = icmp ule i64 %t25, -2251799813685248
+  %t27 = and i1 true, %t26
+  br i1 %t27, label %b4, label %b5
+b4:
+  %t28 = fcmp ueq double %t24, 0.0
+  %t29 = zext i1 %t28 to i64
+  %t30 = or i64 %t29, -1688849860263936
+  %t31 = bitcast i64 %t30 to double
+  br label %b6
+b5:
+  %t32 = bitcast double %t24 to i64
+  %t33 = call i64 @jsrt_not(i64 %t32)
+  %t34 = bitcast i64 %t33 to double
+  br label %b6
+b6:
+  %t35 = phi double [ %t31, %b4 ], [ %t34, %b5 ]
+  store double %t35, ptr %s0
+  %t36 = load double, ptr %s0
+  %t37 = bitcast double %t36 to i64
+  %t38 = icmp ule i64 %t37, -2251799813685248
+  %t39 = and i1 true, %t38
+  br i1 %t39, label %b7, label %b8
+b7:
+  %t40 = fcmp one double %t36, 0.0
+  br label %b9
+b8:
+  %t41 = bitcast double %t36 to i64
+  %t42 = call i64 @jsrt_truthy(i64 %t41)
+  %t43 = icmp ne i64 %t42, 0
+  br label %b9
+b9:
+  %t44 = phi i1 [ %t40, %b7 ], [ %t43, %b8 ]
+  br i1 %t44, label %LL2, label %b10
+b10:
+  %t45 = load double, ptr %v.total
+  store double %t45, ptr %s0
+  %t46 = load double, ptr %v.i
+  store double %t46, ptr %s1
+  %t47 = load double, ptr %s0
+  %t48 = load double, ptr %s1
+  %t49 = bitcast double %t47 to i64
+  %t50 = icmp ule i64 %t49, -2251799813685248
+  %t51 = and i1 true, %t50
+  %t52 = bitcast double %t48 to i64
+  %t53 = icmp ule i64 %t52, -2251799813685248
+  %t54 = and i1 %t51, %t53
+  br i1 %t54, label %b11, label %b12
+b11:
+  %t55 = fadd double %t47, %t48
+  br label %b13
+b12:
+  %t56 = bitcast double %t47 to i64
+  %t57 = bitcast double %t48 to i64
+  %t58 = call i64 @jsrt_add(i64 %t56, i64 %t57)
+  %t59 = bitcast i64 %t58 to double
+  br label %b13
+b13:
+  %t60 = phi double [ %t55, %b11 ], [ %t59, %b12 ]
+  store double %t60, ptr %s0
+  %t61 = load double, ptr %s0
+  store double %t61, ptr %v.total
+  %t62 = load double, ptr %v.i
+  store double %t62, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t63 = load double, ptr %s0
+  %t64 = load double, ptr %s1
+  %t65 = bitcast double %t63 to i64
+  %t66 = icmp ule i64 %t65, -2251799813685248
+  %t67 = and i1 true, %t66
+  %t68 = bitcast double %t64 to i64
+  %t69 = icmp ule i64 %t68, -2251799813685248
+  %t70 = and i1 %t67, %t69
+  br i1 %t70, label %b14, label %b15
+b14:
+  %t71 = fadd double %t63, %t64
+  br label %b16
+b15:
+  %t72 = bitcast double %t63 to i64
+  %t73 = bitcast double %t64 to i64
+  %t74 = call i64 @jsrt_add(i64 %t72, i64 %t73)
+  %t75 = bitcast i64 %t74 to double
+  br label %b16
+b16:
+  %t76 = phi double [ %t71, %b14 ], [ %t75, %b15 ]
+  store double %t76, ptr %s0
+  %t77 = load double, ptr %s0
+  store double %t77, ptr %v.i
+  br label %LL1
+LL2:
+  %t78 = load double, ptr %v.total
+  store double %t78, ptr %s0
+  %t79 = load double, ptr %s0
+  ret double %t79
+}
+
+define double @first_square_over(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.n = alloca double
+  %v.i = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  store double 0x0000000000000000, ptr %s0
+  %t3 = load double, ptr %s0
+  store double %t3, ptr %v.i
+  br label %LL1
+LL1:
+  %t4 = load double, ptr %v.i
+  store double %t4, ptr %s0
+  %t5 = load double, ptr %v.i
+  store double %t5, ptr %s1
+  %t6 = load double, ptr %s0
+  %t7 = load double, ptr %s1
+  %t8 = bitcast double %t6 to i64
+  %t9 = icmp ule i64 %t8, -2251799813685248
+  %t10 = and i1 true, %t9
+  %t11 = bitcast double %t7 to i64
+  %t12 = icmp ule i64 %t11, -2251799813685248
+  %t13 = and i1 %t10, %t12
+  br i1 %t13, label %b1, label %b2
+b1:
+  %t14 = fmul double %t6, %t7
+  br label %b3
+b2:
+  %t15 = bitcast double %t6 to i64
+  %t16 = bitcast double %t7 to i64
+  %t17 = call i64 @jsrt_mul(i64 %t15, i64 %t16)
+  %t18 = bitcast i64 %t17 to double
+  br label %b3
+b3:
+  %t19 = phi double [ %t14, %b1 ], [ %t18, %b2 ]
+  store double %t19, ptr %s0
+  %t20 = load double, ptr %v.n
+  store double %t20, ptr %s1
+  %t21 = load double, ptr %s0
+  %t22 = load double, ptr %s1
+  %t23 = bitcast double %t21 to i64
+  %t24 = icmp ule i64 %t23, -2251799813685248
+  %t25 = and i1 true, %t24
+  %t26 = bitcast double %t22 to i64
+  %t27 = icmp ule i64 %t26, -2251799813685248
+  %t28 = and i1 %t25, %t27
+  br i1 %t28, label %b4, label %b5
+b4:
+  %t29 = fcmp ogt double %t21, %t22
+  %t30 = zext i1 %t29 to i64
+  %t31 = or i64 %t30, -1688849860263936
+  %t32 = bitcast i64 %t31 to double
+  br label %b6
+b5:
+  %t33 = bitcast double %t21 to i64
+  %t34 = bitcast double %t22 to i64
+  %t35 = call i64 @jsrt_gt(i64 %t33, i64 %t34)
+  %t36 = bitcast i64 %t35 to double
+  br label %b6
+b6:
+  %t37 = phi double [ %t32, %b4 ], [ %t36, %b5 ]
+  store double %t37, ptr %s0
+  %t38 = load double, ptr %s0
+  %t39 = bitcast double %t38 to i64
+  %t40 = icmp ule i64 %t39, -2251799813685248
+  %t41 = and i1 true, %t40
+  br i1 %t41, label %b7, label %b8
+b7:
+  %t42 = fcmp ueq double %t38, 0.0
+  %t43 = zext i1 %t42 to i64
+  %t44 = or i64 %t43, -1688849860263936
+  %t45 = bitcast i64 %t44 to double
+  br label %b9
+b8:
+  %t46 = bitcast double %t38 to i64
+  %t47 = call i64 @jsrt_not(i64 %t46)
+  %t48 = bitcast i64 %t47 to double
+  br label %b9
+b9:
+  %t49 = phi double [ %t45, %b7 ], [ %t48, %b8 ]
+  store double %t49, ptr %s0
+  %t50 = load double, ptr %s0
+  %t51 = bitcast double %t50 to i64
+  %t52 = icmp ule i64 %t51, -2251799813685248
+  %t53 = and i1 true, %t52
+  br i1 %t53, label %b10, label %b11
+b10:
+  %t54 = fcmp one double %t50, 0.0
+  br label %b12
+b11:
+  %t55 = bitcast double %t50 to i64
+  %t56 = call i64 @jsrt_truthy(i64 %t55)
+  %t57 = icmp ne i64 %t56, 0
+  br label %b12
+b12:
+  %t58 = phi i1 [ %t54, %b10 ], [ %t57, %b11 ]
+  br i1 %t58, label %LL3, label %b13
+b13:
+  %t59 = load double, ptr %v.i
+  store double %t59, ptr %s0
+  %t60 = load double, ptr %s0
+  ret double %t60
+LL3:
+  br label %LL4
+LL4:
+  %t61 = load double, ptr %v.i
+  store double %t61, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t62 = load double, ptr %s0
+  %t63 = load double, ptr %s1
+  %t64 = bitcast double %t62 to i64
+  %t65 = icmp ule i64 %t64, -2251799813685248
+  %t66 = and i1 true, %t65
+  %t67 = bitcast double %t63 to i64
+  %t68 = icmp ule i64 %t67, -2251799813685248
+  %t69 = and i1 %t66, %t68
+  br i1 %t69, label %b14, label %b15
+b14:
+  %t70 = fadd double %t62, %t63
+  br label %b16
+b15:
+  %t71 = bitcast double %t62 to i64
+  %t72 = bitcast double %t63 to i64
+  %t73 = call i64 @jsrt_add(i64 %t71, i64 %t72)
+  %t74 = bitcast i64 %t73 to double
+  br label %b16
+b16:
+  %t75 = phi double [ %t70, %b14 ], [ %t74, %b15 ]
+  store double %t75, ptr %s0
+  %t76 = load double, ptr %s0
+  store double %t76, ptr %v.i
+  br label %LL1
+LL2:
+  ret double 0xFFFC000000000000
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.i = alloca double
+  store double 0x4024000000000000, ptr %s0
+  %t1 = load double, ptr %s0
+  %t2 = call double @sum_to(double %t1)
+  store double %t2, ptr %s0
+  %t3 = load double, ptr %s0
+  %t4 = bitcast double %t3 to i64
+  call void @jsrt_print_value(i64 %t4, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4049000000000000, ptr %s0
+  %t5 = load double, ptr %s0
+  %t6 = call double @first_square_over(double %t5)
+  store double %t6, ptr %s0
+  %t7 = load double, ptr %s0
+  %t8 = bitcast double %t7 to i64
+  call void @jsrt_print_value(i64 %t8, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t9 = load double, ptr %s0
+  store double %t9, ptr %v.i
+  br label %LL1
+LL1:
+  %t10 = load double, ptr %v.i
+  store double %t10, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t11 = load double, ptr %s0
+  %t12 = load double, ptr %s1
+  %t13 = bitcast double %t11 to i64
+  %t14 = icmp ule i64 %t13, -2251799813685248
+  %t15 = and i1 true, %t14
+  %t16 = bitcast double %t12 to i64
+  %t17 = icmp ule i64 %t16, -2251799813685248
+  %t18 = and i1 %t15, %t17
+  br i1 %t18, label %b1, label %b2
+b1:
+  %t19 = fcmp olt double %t11, %t12
+  %t20 = zext i1 %t19 to i64
+  %t21 = or i64 %t20, -1688849860263936
+  %t22 = bitcast i64 %t21 to double
+  br label %b3
+b2:
+  %t23 = bitcast double %t11 to i64
+  %t24 = bitcast double %t12 to i64
+  %t25 = call i64 @jsrt_lt(i64 %t23, i64 %t24)
+  %t26 = bitcast i64 %t25 to double
+  br label %b3
+b3:
+  %t27 = phi double [ %t22, %b1 ], [ %t26, %b2 ]
+  store double %t27, ptr %s0
+  %t28 = load double, ptr %s0
+  %t29 = bitcast double %t28 to i64
+  %t30 = icmp ule i64 %t29, -2251799813685248
+  %t31 = and i1 true, %t30
+  br i1 %t31, label %b4, label %b5
+b4:
+  %t32 = fcmp ueq double %t28, 0.0
+  %t33 = zext i1 %t32 to i64
+  %t34 = or i64 %t33, -1688849860263936
+  %t35 = bitcast i64 %t34 to double
+  br label %b6
+b5:
+  %t36 = bitcast double %t28 to i64
+  %t37 = call i64 @jsrt_not(i64 %t36)
+  %t38 = bitcast i64 %t37 to double
+  br label %b6
+b6:
+  %t39 = phi double [ %t35, %b4 ], [ %t38, %b5 ]
+  store double %t39, ptr %s0
+  %t40 = load double, ptr %s0
+  %t41 = bitcast double %t40 to i64
+  %t42 = icmp ule i64 %t41, -2251799813685248
+  %t43 = and i1 true, %t42
+  br i1 %t43, label %b7, label %b8
+b7:
+  %t44 = fcmp one double %t40, 0.0
+  br label %b9
+b8:
+  %t45 = bitcast double %t40 to i64
+  %t46 = call i64 @jsrt_truthy(i64 %t45)
+  %t47 = icmp ne i64 %t46, 0
+  br label %b9
+b9:
+  %t48 = phi i1 [ %t44, %b7 ], [ %t47, %b8 ]
+  br i1 %t48, label %LL2, label %b10
+b10:
+  %t49 = load double, ptr %v.i
+  store double %t49, ptr %s0
+  %t50 = load double, ptr %s0
+  %t51 = bitcast double %t50 to i64
+  call void @jsrt_print_value(i64 %t51, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t52 = load double, ptr %v.i
+  store double %t52, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t53 = load double, ptr %s0
+  %t54 = load double, ptr %s1
+  %t55 = bitcast double %t53 to i64
+  %t56 = icmp ule i64 %t55, -2251799813685248
+  %t57 = and i1 true, %t56
+  %t58 = bitcast double %t54 to i64
+  %t59 = icmp ule i64 %t58, -2251799813685248
+  %t60 = and i1 %t57, %t59
+  br i1 %t60, label %b11, label %b12
+b11:
+  %t61 = fadd double %t53, %t54
+  br label %b13
+b12:
+  %t62 = bitcast double %t53 to i64
+  %t63 = bitcast double %t54 to i64
+  %t64 = call i64 @jsrt_add(i64 %t62, i64 %t63)
+  %t65 = bitcast i64 %t64 to double
+  br label %b13
+b13:
+  %t66 = phi double [ %t61, %b11 ], [ %t65, %b12 ]
+  store double %t66, ptr %s0
+  %t67 = load double, ptr %s0
+  store double %t67, ptr %v.i
+  br label %LL1
+LL2:
+  store double 0x4010000000000000, ptr %s0
+  %t68 = load double, ptr %s0
+  %t69 = call double @sum_to(double %t68)
+  store double %t69, ptr %s0
+  %t70 = load double, ptr %s0
+  ret double %t70
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_gt(i64, i64)
+declare i64 @jsrt_le(i64, i64)
+declare i64 @jsrt_lt(i64, i64)
+declare i64 @jsrt_mul(i64, i64)
+declare i64 @jsrt_not(i64)
+declare i64 @jsrt_truthy(i64)
@@ -0,0 +1,495 @@
+	.text
+	.globl sum_to
+	.type sum_to, @function
+sum_to:
+	push %rbp
+	mov %rsp, %rbp
+	sub $32, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov .LCD0(%rip), %rax
+	push %rax
+	pop %rax
+	mov %rax, -16(%rbp)
+	mov .LCD1(%rip), %rax
+	push %rax
+	pop %rax
+	mov %rax, -24(%rbp)
+.Lsum_to_L1:
+	mov -24(%rbp), %rax
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.1
+	cmp %rdx, %rcx
+	ja .Lsum_to.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsum_to.2
+.Lsum_to.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_le
+	mov %rbx, %rsp
+.Lsum_to.2:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.3
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lsum_to.4
+.Lsum_to.3:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lsum_to.4:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lsum_to.5
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lsum_to_L2
+	jmp .Lsum_to.6
+.Lsum_to.5:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lsum_to_L2
+.Lsum_to.6:
+	mov -16(%rbp), %rax
+	push %rax
+	mov -24(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	mov %rax, -16(%rbp)
+	mov -24(%rbp), %rax
+	push %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	mov %rax, -24(%rbp)
+	jmp .Lsum_to_L1
+.Lsum_to_L2:
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl first_square_over
+	.type first_square_over, @function
+first_square_over:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov .LCD3(%rip), %rax
+	push %rax
+	pop %rax
+	mov %rax, -16(%rbp)
+.Lfirst_square_over_L1:
+	mov -16(%rbp), %rax
+	push %rax
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfirst_square_over.7
+	cmp %rdx, %rcx
+	ja .Lfirst_square_over.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lfirst_square_over.8
+.Lfirst_square_over.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_gt
+	mov %rbx, %rsp
+.Lfirst_square_over.8:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfirst_square_over.9
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lfirst_square_over.10
+.Lfirst_square_over.9:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lfirst_square_over.10:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lfirst_square_over.11
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lfirst_square_over_L3
+	jmp .Lfirst_square_over.12
+.Lfirst_square_over.11:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lfirst_square_over_L3
+.Lfirst_square_over.12:
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	jmp .Lfirst_square_over_L4
+.Lfirst_square_over_L3:
+.Lfirst_square_over_L4:
+	mov -16(%rbp), %rax
+	push %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	mov %rax, -16(%rbp)
+	jmp .Lfirst_square_over_L1
+.Lfirst_square_over_L2:
+	movabs $18445618173802708992, %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD5(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sum_to
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD6(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call first_square_over
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD7(%rip), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+.Lmain_L1:
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD8(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.13
+	cmp %rdx, %rcx
+	ja .Lmain.13
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.14
+.Lmain.13:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.14:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.15
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.16
+.Lmain.15:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lmain.16:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.17
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	jne .Lmain_L2
+	jmp .Lmain.18
+.Lmain.17:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_truthy
+	mov %rbx, %rsp
+	cmp $0, %rax
+	jne .Lmain_L2
+.Lmain.18:
+	mov -8(%rbp), %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD9(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.19
+	cmp %rdx, %rcx
+	ja .Lmain.19
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.20
+.Lmain.19:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.20:
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	jmp .Lmain_L1
+.Lmain_L2:
+	mov .LCD10(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call sum_to
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+	.p2align 3
+.LCD0:
+	.double 0
+.LCD1:
+	.double 1
+.LCD2:
+	.double 1
+.LCD3:
+	.double 0
+.LCD4:
+	.double 1
+.LCD5:
+	.double 10
+.LCD6:
+	.double 50
+.LCD7:
+	.double 0
+.LCD8:
+	.double 3
+.LCD9:
+	.double 1
+.LCD10:
+	.double 4
+	.section .note.GNU-stack,"",@progbits
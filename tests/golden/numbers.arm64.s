@@ -0,0 +1,436 @@
+	.text
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD10
+	ldr d0, [x0, :lo12:.LCD10]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD11
+	ldr d0, [x0, :lo12:.LCD11]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD12
+	ldr d0, [x0, :lo12:.LCD12]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD13
+	ldr d0, [x0, :lo12:.LCD13]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD14
+	ldr d0, [x0, :lo12:.LCD14]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD15
+	ldr d0, [x0, :lo12:.LCD15]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD16
+	ldr d0, [x0, :lo12:.LCD16]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD17
+	ldr d0, [x0, :lo12:.LCD17]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD18
+	ldr d0, [x0, :lo12:.LCD18]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD19
+	ldr d0, [x0, :lo12:.LCD19]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD20
+	ldr d0, [x0, :lo12:.LCD20]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD21
+	ldr d0, [x0, :lo12:.LCD21]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD22
+	ldr d0, [x0, :lo12:.LCD22]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD23
+	ldr d0, [x0, :lo12:.LCD23]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD24
+	ldr d0, [x0, :lo12:.LCD24]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD25
+	ldr d0, [x0, :lo12:.LCD25]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD26
+	ldr d0, [x0, :lo12:.LCD26]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD27
+	ldr d0, [x0, :lo12:.LCD27]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD28
+	ldr d0, [x0, :lo12:.LCD28]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD29
+	ldr d0, [x0, :lo12:.LCD29]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD30
+	ldr d0, [x0, :lo12:.LCD30]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD31
+	ldr d0, [x0, :lo12:.LCD31]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fmul d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD32
+	ldr d0, [x0, :lo12:.LCD32]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+	.p2align 3
+.LCD0:
+	.double 100
+.LCD1:
+	.double 610
+.LCD2:
+	.double 10
+.LCD3:
+	.double 42
+.LCD4:
+	.double 0.1
+.LCD5:
+	.double 0.2
+.LCD6:
+	.double 1
+.LCD7:
+	.double 3
+.LCD8:
+	.double 1.5
+.LCD9:
+	.double 0.000025
+.LCD10:
+	.double 2
+.LCD11:
+	.double 0.0001
+.LCD12:
+	.double 1000000
+.LCD13:
+	.double 1000000
+.LCD14:
+	.double 1000000
+.LCD15:
+	.double 1000
+.LCD16:
+	.double 1000000
+.LCD17:
+	.double 1000000
+.LCD18:
+	.double 1000000
+.LCD19:
+	.double 100
+.LCD20:
+	.double 1
+.LCD21:
+	.double 10000000
+.LCD22:
+	.double 15
+.LCD23:
+	.double 100000000
+.LCD24:
+	.double 0.000001
+.LCD25:
+	.double 1.5
+.LCD26:
+	.double 1000000
+.LCD27:
+	.double 1000000
+.LCD28:
+	.double 1000000
+.LCD29:
+	.double 1000000
+.LCD30:
+	.double 1000000
+.LCD31:
+	.double 1000000
+.LCD32:
+	.double 0
+	.section .note.GNU-stack,"",%progbits
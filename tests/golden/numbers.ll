@@ -0,0 +1,540 @@
+; Generated by js-compiler
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  store double 0x4059000000000000, ptr %s0
+  %t1 = load double, ptr %s0
+  %t2 = bitcast double %t1 to i64
+  call void @jsrt_print_value(i64 %t2, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4083100000000000, ptr %s0
+  store double 0x4024000000000000, ptr %s1
+  %t3 = load double, ptr %s0
+  %t4 = load double, ptr %s1
+  %t5 = bitcast double %t3 to i64
+  %t6 = icmp ule i64 %t5, -2251799813685248
+  %t7 = and i1 true, %t6
+  %t8 = bitcast double %t4 to i64
+  %t9 = icmp ule i64 %t8, -2251799813685248
+  %t10 = and i1 %t7, %t9
+  br i1 %t10, label %b1, label %b2
+b1:
+  %t11 = fmul double %t3, %t4
+  br label %b3
+b2:
+  %t12 = bitcast double %t3 to i64
+  %t13 = bitcast double %t4 to i64
+  %t14 = call i64 @jsrt_mul(i64 %t12, i64 %t13)
+  %t15 = bitcast i64 %t14 to double
+  br label %b3
+b3:
+  %t16 = phi double [ %t11, %b1 ], [ %t15, %b2 ]
+  store double %t16, ptr %s0
+  %t17 = load double, ptr %s0
+  %t18 = bitcast double %t17 to i64
+  call void @jsrt_print_value(i64 %t18, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4045000000000000, ptr %s0
+  %t19 = load double, ptr %s0
+  %t20 = bitcast double %t19 to i64
+  %t21 = icmp ule i64 %t20, -2251799813685248
+  %t22 = and i1 true, %t21
+  br i1 %t22, label %b4, label %b5
+b4:
+  %t23 = fneg double %t19
+  br label %b6
+b5:
+  %t24 = bitcast double %t19 to i64
+  %t25 = call i64 @jsrt_neg(i64 %t24)
+  %t26 = bitcast i64 %t25 to double
+  br label %b6
+b6:
+  %t27 = phi double [ %t23, %b4 ], [ %t26, %b5 ]
+  store double %t27, ptr %s0
+  %t28 = load double, ptr %s0
+  %t29 = bitcast double %t28 to i64
+  call void @jsrt_print_value(i64 %t29, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FB999999999999A, ptr %s0
+  store double 0x3FC999999999999A, ptr %s1
+  %t30 = load double, ptr %s0
+  %t31 = load double, ptr %s1
+  %t32 = bitcast double %t30 to i64
+  %t33 = icmp ule i64 %t32, -2251799813685248
+  %t34 = and i1 true, %t33
+  %t35 = bitcast double %t31 to i64
+  %t36 = icmp ule i64 %t35, -2251799813685248
+  %t37 = and i1 %t34, %t36
+  br i1 %t37, label %b7, label %b8
+b7:
+  %t38 = fadd double %t30, %t31
+  br label %b9
+b8:
+  %t39 = bitcast double %t30 to i64
+  %t40 = bitcast double %t31 to i64
+  %t41 = call i64 @jsrt_add(i64 %t39, i64 %t40)
+  %t42 = bitcast i64 %t41 to double
+  br label %b9
+b9:
+  %t43 = phi double [ %t38, %b7 ], [ %t42, %b8 ]
+  store double %t43, ptr %s0
+  %t44 = load double, ptr %s0
+  %t45 = bitcast double %t44 to i64
+  call void @jsrt_print_value(i64 %t45, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t46 = load double, ptr %s0
+  %t47 = load double, ptr %s1
+  %t48 = bitcast double %t46 to i64
+  %t49 = icmp ule i64 %t48, -2251799813685248
+  %t50 = and i1 true, %t49
+  %t51 = bitcast double %t47 to i64
+  %t52 = icmp ule i64 %t51, -2251799813685248
+  %t53 = and i1 %t50, %t52
+  br i1 %t53, label %b10, label %b11
+b10:
+  %t54 = fdiv double %t46, %t47
+  br label %b12
+b11:
+  %t55 = bitcast double %t46 to i64
+  %t56 = bitcast double %t47 to i64
+  %t57 = call i64 @jsrt_div(i64 %t55, i64 %t56)
+  %t58 = bitcast i64 %t57 to double
+  br label %b12
+b12:
+  %t59 = phi double [ %t54, %b10 ], [ %t58, %b11 ]
+  store double %t59, ptr %s0
+  %t60 = load double, ptr %s0
+  %t61 = bitcast double %t60 to i64
+  call void @jsrt_print_value(i64 %t61, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF8000000000000, ptr %s0
+  %t62 = load double, ptr %s0
+  %t63 = bitcast double %t62 to i64
+  %t64 = icmp ule i64 %t63, -2251799813685248
+  %t65 = and i1 true, %t64
+  br i1 %t65, label %b13, label %b14
+b13:
+  %t66 = fneg double %t62
+  br label %b15
+b14:
+  %t67 = bitcast double %t62 to i64
+  %t68 = call i64 @jsrt_neg(i64 %t67)
+  %t69 = bitcast i64 %t68 to double
+  br label %b15
+b15:
+  %t70 = phi double [ %t66, %b13 ], [ %t69, %b14 ]
+  store double %t70, ptr %s0
+  %t71 = load double, ptr %s0
+  %t72 = bitcast double %t71 to i64
+  call void @jsrt_print_value(i64 %t72, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3EFA36E2EB1C432D, ptr %s0
+  %t73 = load double, ptr %s0
+  %t74 = bitcast double %t73 to i64
+  call void @jsrt_print_value(i64 %t74, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4000000000000000, ptr %s0
+  store double 0x3F1A36E2EB1C432D, ptr %s1
+  %t75 = load double, ptr %s0
+  %t76 = load double, ptr %s1
+  %t77 = bitcast double %t75 to i64
+  %t78 = icmp ule i64 %t77, -2251799813685248
+  %t79 = and i1 true, %t78
+  %t80 = bitcast double %t76 to i64
+  %t81 = icmp ule i64 %t80, -2251799813685248
+  %t82 = and i1 %t79, %t81
+  br i1 %t82, label %b16, label %b17
+b16:
+  %t83 = fdiv double %t75, %t76
+  br label %b18
+b17:
+  %t84 = bitcast double %t75 to i64
+  %t85 = bitcast double %t76 to i64
+  %t86 = call i64 @jsrt_div(i64 %t84, i64 %t85)
+  %t87 = bitcast i64 %t86 to double
+  br label %b18
+b18:
+  %t88 = phi double [ %t83, %b16 ], [ %t87, %b17 ]
+  store double %t88, ptr %s0
+  %t89 = load double, ptr %s0
+  %t90 = bitcast double %t89 to i64
+  call void @jsrt_print_value(i64 %t90, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x412E848000000000, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t91 = load double, ptr %s0
+  %t92 = load double, ptr %s1
+  %t93 = bitcast double %t91 to i64
+  %t94 = icmp ule i64 %t93, -2251799813685248
+  %t95 = and i1 true, %t94
+  %t96 = bitcast double %t92 to i64
+  %t97 = icmp ule i64 %t96, -2251799813685248
+  %t98 = and i1 %t95, %t97
+  br i1 %t98, label %b19, label %b20
+b19:
+  %t99 = fmul double %t91, %t92
+  br label %b21
+b20:
+  %t100 = bitcast double %t91 to i64
+  %t101 = bitcast double %t92 to i64
+  %t102 = call i64 @jsrt_mul(i64 %t100, i64 %t101)
+  %t103 = bitcast i64 %t102 to double
+  br label %b21
+b21:
+  %t104 = phi double [ %t99, %b19 ], [ %t103, %b20 ]
+  store double %t104, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t105 = load double, ptr %s0
+  %t106 = load double, ptr %s1
+  %t107 = bitcast double %t105 to i64
+  %t108 = icmp ule i64 %t107, -2251799813685248
+  %t109 = and i1 true, %t108
+  %t110 = bitcast double %t106 to i64
+  %t111 = icmp ule i64 %t110, -2251799813685248
+  %t112 = and i1 %t109, %t111
+  br i1 %t112, label %b22, label %b23
+b22:
+  %t113 = fmul double %t105, %t106
+  br label %b24
+b23:
+  %t114 = bitcast double %t105 to i64
+  %t115 = bitcast double %t106 to i64
+  %t116 = call i64 @jsrt_mul(i64 %t114, i64 %t115)
+  %t117 = bitcast i64 %t116 to double
+  br label %b24
+b24:
+  %t118 = phi double [ %t113, %b22 ], [ %t117, %b23 ]
+  store double %t118, ptr %s0
+  store double 0x408F400000000000, ptr %s1
+  %t119 = load double, ptr %s0
+  %t120 = load double, ptr %s1
+  %t121 = bitcast double %t119 to i64
+  %t122 = icmp ule i64 %t121, -2251799813685248
+  %t123 = and i1 true, %t122
+  %t124 = bitcast double %t120 to i64
+  %t125 = icmp ule i64 %t124, -2251799813685248
+  %t126 = and i1 %t123, %t125
+  br i1 %t126, label %b25, label %b26
+b25:
+  %t127 = fmul double %t119, %t120
+  br label %b27
+b26:
+  %t128 = bitcast double %t119 to i64
+  %t129 = bitcast double %t120 to i64
+  %t130 = call i64 @jsrt_mul(i64 %t128, i64 %t129)
+  %t131 = bitcast i64 %t130 to double
+  br label %b27
+b27:
+  %t132 = phi double [ %t127, %b25 ], [ %t131, %b26 ]
+  store double %t132, ptr %s0
+  %t133 = load double, ptr %s0
+  %t134 = bitcast double %t133 to i64
+  call void @jsrt_print_value(i64 %t134, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x412E848000000000, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t135 = load double, ptr %s0
+  %t136 = load double, ptr %s1
+  %t137 = bitcast double %t135 to i64
+  %t138 = icmp ule i64 %t137, -2251799813685248
+  %t139 = and i1 true, %t138
+  %t140 = bitcast double %t136 to i64
+  %t141 = icmp ule i64 %t140, -2251799813685248
+  %t142 = and i1 %t139, %t141
+  br i1 %t142, label %b28, label %b29
+b28:
+  %t143 = fmul double %t135, %t136
+  br label %b30
+b29:
+  %t144 = bitcast double %t135 to i64
+  %t145 = bitcast double %t136 to i64
+  %t146 = call i64 @jsrt_mul(i64 %t144, i64 %t145)
+  %t147 = bitcast i64 %t146 to double
+  br label %b30
+b30:
+  %t148 = phi double [ %t143, %b28 ], [ %t147, %b29 ]
+  store double %t148, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t149 = load double, ptr %s0
+  %t150 = load double, ptr %s1
+  %t151 = bitcast double %t149 to i64
+  %t152 = icmp ule i64 %t151, -2251799813685248
+  %t153 = and i1 true, %t152
+  %t154 = bitcast double %t150 to i64
+  %t155 = icmp ule i64 %t154, -2251799813685248
+  %t156 = and i1 %t153, %t155
+  br i1 %t156, label %b31, label %b32
+b31:
+  %t157 = fmul double %t149, %t150
+  br label %b33
+b32:
+  %t158 = bitcast double %t149 to i64
+  %t159 = bitcast double %t150 to i64
+  %t160 = call i64 @jsrt_mul(i64 %t158, i64 %t159)
+  %t161 = bitcast i64 %t160 to double
+  br label %b33
+b33:
+  %t162 = phi double [ %t157, %b31 ], [ %t161, %b32 ]
+  store double %t162, ptr %s0
+  store double 0x4059000000000000, ptr %s1
+  %t163 = load double, ptr %s0
+  %t164 = load double, ptr %s1
+  %t165 = bitcast double %t163 to i64
+  %t166 = icmp ule i64 %t165, -2251799813685248
+  %t167 = and i1 true, %t166
+  %t168 = bitcast double %t164 to i64
+  %t169 = icmp ule i64 %t168, -2251799813685248
+  %t170 = and i1 %t167, %t169
+  br i1 %t170, label %b34, label %b35
+b34:
+  %t171 = fmul double %t163, %t164
+  br label %b36
+b35:
+  %t172 = bitcast double %t163 to i64
+  %t173 = bitcast double %t164 to i64
+  %t174 = call i64 @jsrt_mul(i64 %t172, i64 %t173)
+  %t175 = bitcast i64 %t174 to double
+  br label %b36
+b36:
+  %t176 = phi double [ %t171, %b34 ], [ %t175, %b35 ]
+  store double %t176, ptr %s0
+  %t177 = load double, ptr %s0
+  %t178 = bitcast double %t177 to i64
+  call void @jsrt_print_value(i64 %t178, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  store double 0x416312D000000000, ptr %s1
+  %t179 = load double, ptr %s0
+  %t180 = load double, ptr %s1
+  %t181 = bitcast double %t179 to i64
+  %t182 = icmp ule i64 %t181, -2251799813685248
+  %t183 = and i1 true, %t182
+  %t184 = bitcast double %t180 to i64
+  %t185 = icmp ule i64 %t184, -2251799813685248
+  %t186 = and i1 %t183, %t185
+  br i1 %t186, label %b37, label %b38
+b37:
+  %t187 = fdiv double %t179, %t180
+  br label %b39
+b38:
+  %t188 = bitcast double %t179 to i64
+  %t189 = bitcast double %t180 to i64
+  %t190 = call i64 @jsrt_div(i64 %t188, i64 %t189)
+  %t191 = bitcast i64 %t190 to double
+  br label %b39
+b39:
+  %t192 = phi double [ %t187, %b37 ], [ %t191, %b38 ]
+  store double %t192, ptr %s0
+  %t193 = load double, ptr %s0
+  %t194 = bitcast double %t193 to i64
+  call void @jsrt_print_value(i64 %t194, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x402E000000000000, ptr %s0
+  %t195 = load double, ptr %s0
+  %t196 = bitcast double %t195 to i64
+  %t197 = icmp ule i64 %t196, -2251799813685248
+  %t198 = and i1 true, %t197
+  br i1 %t198, label %b40, label %b41
+b40:
+  %t199 = fneg double %t195
+  br label %b42
+b41:
+  %t200 = bitcast double %t195 to i64
+  %t201 = call i64 @jsrt_neg(i64 %t200)
+  %t202 = bitcast i64 %t201 to double
+  br label %b42
+b42:
+  %t203 = phi double [ %t199, %b40 ], [ %t202, %b41 ]
+  store double %t203, ptr %s0
+  store double 0x4197D78400000000, ptr %s1
+  %t204 = load double, ptr %s0
+  %t205 = load double, ptr %s1
+  %t206 = bitcast double %t204 to i64
+  %t207 = icmp ule i64 %t206, -2251799813685248
+  %t208 = and i1 true, %t207
+  %t209 = bitcast double %t205 to i64
+  %t210 = icmp ule i64 %t209, -2251799813685248
+  %t211 = and i1 %t208, %t210
+  br i1 %t211, label %b43, label %b44
+b43:
+  %t212 = fdiv double %t204, %t205
+  br label %b45
+b44:
+  %t213 = bitcast double %t204 to i64
+  %t214 = bitcast double %t205 to i64
+  %t215 = call i64 @jsrt_div(i64 %t213, i64 %t214)
+  %t216 = bitcast i64 %t215 to double
+  br label %b45
+b45:
+  %t217 = phi double [ %t212, %b43 ], [ %t216, %b44 ]
+  store double %t217, ptr %s0
+  %t218 = load double, ptr %s0
+  %t219 = bitcast double %t218 to i64
+  call void @jsrt_print_value(i64 %t219, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3EB0C6F7A0B5ED8D, ptr %s0
+  %t220 = load double, ptr %s0
+  %t221 = bitcast double %t220 to i64
+  call void @jsrt_print_value(i64 %t221, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF8000000000000, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t222 = load double, ptr %s0
+  %t223 = load double, ptr %s1
+  %t224 = bitcast double %t222 to i64
+  %t225 = icmp ule i64 %t224, -2251799813685248
+  %t226 = and i1 true, %t225
+  %t227 = bitcast double %t223 to i64
+  %t228 = icmp ule i64 %t227, -2251799813685248
+  %t229 = and i1 %t226, %t228
+  br i1 %t229, label %b46, label %b47
+b46:
+  %t230 = fmul double %t222, %t223
+  br label %b48
+b47:
+  %t231 = bitcast double %t222 to i64
+  %t232 = bitcast double %t223 to i64
+  %t233 = call i64 @jsrt_mul(i64 %t231, i64 %t232)
+  %t234 = bitcast i64 %t233 to double
+  br label %b48
+b48:
+  %t235 = phi double [ %t230, %b46 ], [ %t234, %b47 ]
+  store double %t235, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t236 = load double, ptr %s0
+  %t237 = load double, ptr %s1
+  %t238 = bitcast double %t236 to i64
+  %t239 = icmp ule i64 %t238, -2251799813685248
+  %t240 = and i1 true, %t239
+  %t241 = bitcast double %t237 to i64
+  %t242 = icmp ule i64 %t241, -2251799813685248
+  %t243 = and i1 %t240, %t242
+  br i1 %t243, label %b49, label %b50
+b49:
+  %t244 = fmul double %t236, %t237
+  br label %b51
+b50:
+  %t245 = bitcast double %t236 to i64
+  %t246 = bitcast double %t237 to i64
+  %t247 = call i64 @jsrt_mul(i64 %t245, i64 %t246)
+  %t248 = bitcast i64 %t247 to double
+  br label %b51
+b51:
+  %t249 = phi double [ %t244, %b49 ], [ %t248, %b50 ]
+  store double %t249, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t250 = load double, ptr %s0
+  %t251 = load double, ptr %s1
+  %t252 = bitcast double %t250 to i64
+  %t253 = icmp ule i64 %t252, -2251799813685248
+  %t254 = and i1 true, %t253
+  %t255 = bitcast double %t251 to i64
+  %t256 = icmp ule i64 %t255, -2251799813685248
+  %t257 = and i1 %t254, %t256
+  br i1 %t257, label %b52, label %b53
+b52:
+  %t258 = fmul double %t250, %t251
+  br label %b54
+b53:
+  %t259 = bitcast double %t250 to i64
+  %t260 = bitcast double %t251 to i64
+  %t261 = call i64 @jsrt_mul(i64 %t259, i64 %t260)
+  %t262 = bitcast i64 %t261 to double
+  br label %b54
+b54:
+  %t263 = phi double [ %t258, %b52 ], [ %t262, %b53 ]
+  store double %t263, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t264 = load double, ptr %s0
+  %t265 = load double, ptr %s1
+  %t266 = bitcast double %t264 to i64
+  %t267 = icmp ule i64 %t266, -2251799813685248
+  %t268 = and i1 true, %t267
+  %t269 = bitcast double %t265 to i64
+  %t270 = icmp ule i64 %t269, -2251799813685248
+  %t271 = and i1 %t268, %t270
+  br i1 %t271, label %b55, label %b56
+b55:
+  %t272 = fmul double %t264, %t265
+  br label %b57
+b56:
+  %t273 = bitcast double %t264 to i64
+  %t274 = bitcast double %t265 to i64
+  %t275 = call i64 @jsrt_mul(i64 %t273, i64 %t274)
+  %t276 = bitcast i64 %t275 to double
+  br label %b57
+b57:
+  %t277 = phi double [ %t272, %b55 ], [ %t276, %b56 ]
+  store double %t277, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t278 = load double, ptr %s0
+  %t279 = load double, ptr %s1
+  %t280 = bitcast double %t278 to i64
+  %t281 = icmp ule i64 %t280, -2251799813685248
+  %t282 = and i1 true, %t281
+  %t283 = bitcast double %t279 to i64
+  %t284 = icmp ule i64 %t283, -2251799813685248
+  %t285 = and i1 %t282, %t284
+  br i1 %t285, label %b58, label %b59
+b58:
+  %t286 = fmul double %t278, %t279
+  br label %b60
+b59:
+  %t287 = bitcast double %t278 to i64
+  %t288 = bitcast double %t279 to i64
+  %t289 = call i64 @jsrt_mul(i64 %t287, i64 %t288)
+  %t290 = bitcast i64 %t289 to double
+  br label %b60
+b60:
+  %t291 = phi double [ %t286, %b58 ], [ %t290, %b59 ]
+  store double %t291, ptr %s0
+  store double 0x412E848000000000, ptr %s1
+  %t292 = load double, ptr %s0
+  %t293 = load double, ptr %s1
+  %t294 = bitcast double %t292 to i64
+  %t295 = icmp ule i64 %t294, -2251799813685248
+  %t296 = and i1 true, %t295
+  %t297 = bitcast double %t293 to i64
+  %t298 = icmp ule i64 %t297, -2251799813685248
+  %t299 = and i1 %t296, %t298
+  br i1 %t299, label %b61, label %b62
+b61:
+  %t300 = fmul double %t292, %t293
+  br label %b63
+b62:
+  %t301 = bitcast double %t292 to i64
+  %t302 = bitcast double %t293 to i64
+  %t303 = call i64 @jsrt_mul(i64 %t301, i64 %t302)
+  %t304 = bitcast i64 %t303 to double
+  br label %b63
+b63:
+  %t305 = phi double [ %t300, %b61 ], [ %t304, %b62 ]
+  store double %t305, ptr %s0
+  %t306 = load double, ptr %s0
+  %t307 = bitcast double %t306 to i64
+  call void @jsrt_print_value(i64 %t307, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t308 = load double, ptr %s0
+  ret double %t308
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_div(i64, i64)
+declare i64 @jsrt_mul(i64, i64)
+declare i64 @jsrt_neg(i64)
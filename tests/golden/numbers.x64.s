@@ -0,0 +1,445 @@
+	.text
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD0(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD3(%rip), %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	mov .LCD5(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD6(%rip), %rax
+	push %rax
+	mov .LCD7(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD8(%rip), %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD9(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD10(%rip), %rax
+	push %rax
+	mov .LCD11(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD12(%rip), %rax
+	push %rax
+	mov .LCD13(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD14(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD15(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD16(%rip), %rax
+	push %rax
+	mov .LCD17(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD18(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD19(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD20(%rip), %rax
+	push %rax
+	mov .LCD21(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD22(%rip), %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov .LCD23(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	divsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD24(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD25(%rip), %rax
+	push %rax
+	mov .LCD26(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD27(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD28(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD29(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD30(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov .LCD31(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	mulsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD32(%rip), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+	.p2align 3
+.LCD0:
+	.double 100
+.LCD1:
+	.double 610
+.LCD2:
+	.double 10
+.LCD3:
+	.double 42
+.LCD4:
+	.double 0.1
+.LCD5:
+	.double 0.2
+.LCD6:
+	.double 1
+.LCD7:
+	.double 3
+.LCD8:
+	.double 1.5
+.LCD9:
+	.double 0.000025
+.LCD10:
+	.double 2
+.LCD11:
+	.double 0.0001
+.LCD12:
+	.double 1000000
+.LCD13:
+	.double 1000000
+.LCD14:
+	.double 1000000
+.LCD15:
+	.double 1000
+.LCD16:
+	.double 1000000
+.LCD17:
+	.double 1000000
+.LCD18:
+	.double 1000000
+.LCD19:
+	.double 100
+.LCD20:
+	.double 1
+.LCD21:
+	.double 10000000
+.LCD22:
+	.double 15
+.LCD23:
+	.double 100000000
+.LCD24:
+	.double 0.000001
+.LCD25:
+	.double 1.5
+.LCD26:
+	.double 1000000
+.LCD27:
+	.double 1000000
+.LCD28:
+	.double 1000000
+.LCD29:
+	.double 1000000
+.LCD30:
+	.double 1000000
+.LCD31:
+	.double 1000000
+.LCD32:
+	.double 0
+	.section .note.GNU-stack,"",@progbits
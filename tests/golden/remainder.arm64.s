@@ -0,0 +1,324 @@
+	.text
+	.global is_even
+	.type is_even, %function
+	.p2align 2
+is_even:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	bl jsrt_mod
+	str x0, [sp, #-16]!
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lis_even.1
+	cmp x1, x9
+	b.hi .Lis_even.1
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lis_even.2
+.Lis_even.1:
+	bl jsrt_strict_eq
+.Lis_even.2:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	bl jsrt_mod
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fneg d0, d0
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	bl jsrt_mod
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	bl jsrt_mod
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl is_even
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl is_even
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD10
+	ldr d0, [x0, :lo12:.LCD10]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD11
+	ldr d0, [x0, :lo12:.LCD11]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ne
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC0
+	add x0, x0, :lo12:.LC0
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC1
+	add x0, x0, :lo12:.LC1
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.3
+	cmp x1, x9
+	b.hi .Lmain.3
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.4
+.Lmain.3:
+	bl jsrt_strict_eq
+.Lmain.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.5
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.6
+.Lmain.5:
+	bl jsrt_not
+.Lmain.6:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD12
+	ldr d0, [x0, :lo12:.LCD12]
+	str d0, [sp, #-16]!
+	adrp x0, .LC2
+	add x0, x0, :lo12:.LC2
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.7
+	cmp x1, x9
+	b.hi .Lmain.7
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.8
+.Lmain.7:
+	bl jsrt_strict_eq
+.Lmain.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD13
+	ldr d0, [x0, :lo12:.LCD13]
+	str d0, [sp, #-16]!
+	adrp x0, .LCD14
+	ldr d0, [x0, :lo12:.LCD14]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	bl jsrt_mod
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+.LC0:
+	.asciz "a"
+.LC1:
+	.asciz "b"
+.LC2:
+	.asciz "1"
+	.p2align 3
+.LCD0:
+	.double 2
+.LCD1:
+	.double 0
+.LCD2:
+	.double 7
+.LCD3:
+	.double 3
+.LCD4:
+	.double 7
+.LCD5:
+	.double 3
+.LCD6:
+	.double 5.5
+.LCD7:
+	.double 2
+.LCD8:
+	.double 4
+.LCD9:
+	.double 7
+.LCD10:
+	.double 3
+.LCD11:
+	.double 4
+.LCD12:
+	.double 1
+.LCD13:
+	.double 17
+.LCD14:
+	.double 5
+	.section .note.GNU-stack,"",%progbits
@@ -0,0 +1,347 @@
+; Generated by js-compiler
+
+define double @is_even(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.n = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  %t3 = load double, ptr %v.n
+  store double %t3, ptr %s0
+  store double 0x4000000000000000, ptr %s1
+  %t4 = load double, ptr %s0
+  %t5 = load double, ptr %s1
+  %t6 = bitcast double %t4 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 true, %t7
+  %t9 = bitcast double %t5 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 %t8, %t10
+  br i1 %t11, label %b1, label %b2
+b1:
+  %t12 = frem double %t4, %t5
+  br label %b3
+b2:
+  %t13 = bitcast double %t4 to i64
+  %t14 = bitcast double %t5 to i64
+  %t15 = call i64 @jsrt_mod(i64 %t13, i64 %t14)
+  %t16 = bitcast i64 %t15 to double
+  br label %b3
+b3:
+  %t17 = phi double [ %t12, %b1 ], [ %t16, %b2 ]
+  store double %t17, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t18 = load double, ptr %s0
+  %t19 = load double, ptr %s1
+  %t20 = bitcast double %t18 to i64
+  %t21 = icmp ule i64 %t20, -2251799813685248
+  %t22 = and i1 true, %t21
+  %t23 = bitcast double %t19 to i64
+  %t24 = icmp ule i64 %t23, -2251799813685248
+  %t25 = and i1 %t22, %t24
+  br i1 %t25, label %b4, label %b5
+b4:
+  %t26 = fcmp oeq double %t18, %t19
+  %t27 = zext i1 %t26 to i64
+  %t28 = or i64 %t27, -1688849860263936
+  %t29 = bitcast i64 %t28 to double
+  br label %b6
+b5:
+  %t30 = bitcast double %t18 to i64
+  %t31 = bitcast double %t19 to i64
+  %t32 = call i64 @jsrt_strict_eq(i64 %t30, i64 %t31)
+  %t33 = bitcast i64 %t32 to double
+  br label %b6
+b6:
+  %t34 = phi double [ %t29, %b4 ], [ %t33, %b5 ]
+  store double %t34, ptr %s0
+  %t35 = load double, ptr %s0
+  ret double %t35
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  store double 0x401C000000000000, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t1 = load double, ptr %s0
+  %t2 = load double, ptr %s1
+  %t3 = bitcast double %t1 to i64
+  %t4 = icmp ule i64 %t3, -2251799813685248
+  %t5 = and i1 true, %t4
+  %t6 = bitcast double %t2 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 %t5, %t7
+  br i1 %t8, label %b1, label %b2
+b1:
+  %t9 = frem double %t1, %t2
+  br label %b3
+b2:
+  %t10 = bitcast double %t1 to i64
+  %t11 = bitcast double %t2 to i64
+  %t12 = call i64 @jsrt_mod(i64 %t10, i64 %t11)
+  %t13 = bitcast i64 %t12 to double
+  br label %b3
+b3:
+  %t14 = phi double [ %t9, %b1 ], [ %t13, %b2 ]
+  store double %t14, ptr %s0
+  %t15 = load double, ptr %s0
+  %t16 = bitcast double %t15 to i64
+  call void @jsrt_print_value(i64 %t16, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x401C000000000000, ptr %s0
+  %t17 = load double, ptr %s0
+  %t18 = bitcast double %t17 to i64
+  %t19 = icmp ule i64 %t18, -2251799813685248
+  %t20 = and i1 true, %t19
+  br i1 %t20, label %b4, label %b5
+b4:
+  %t21 = fneg double %t17
+  br label %b6
+b5:
+  %t22 = bitcast double %t17 to i64
+  %t23 = call i64 @jsrt_neg(i64 %t22)
+  %t24 = bitcast i64 %t23 to double
+  br label %b6
+b6:
+  %t25 = phi double [ %t21, %b4 ], [ %t24, %b5 ]
+  store double %t25, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t26 = load double, ptr %s0
+  %t27 = load double, ptr %s1
+  %t28 = bitcast double %t26 to i64
+  %t29 = icmp ule i64 %t28, -2251799813685248
+  %t30 = and i1 true, %t29
+  %t31 = bitcast double %t27 to i64
+  %t32 = icmp ule i64 %t31, -2251799813685248
+  %t33 = and i1 %t30, %t32
+  br i1 %t33, label %b7, label %b8
+b7:
+  %t34 = frem double %t26, %t27
+  br label %b9
+b8:
+  %t35 = bitcast double %t26 to i64
+  %t36 = bitcast double %t27 to i64
+  %t37 = call i64 @jsrt_mod(i64 %t35, i64 %t36)
+  %t38 = bitcast i64 %t37 to double
+  br label %b9
+b9:
+  %t39 = phi double [ %t34, %b7 ], [ %t38, %b8 ]
+  store double %t39, ptr %s0
+  %t40 = load double, ptr %s0
+  %t41 = bitcast double %t40 to i64
+  call void @jsrt_print_value(i64 %t41, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4016000000000000, ptr %s0
+  store double 0x4000000000000000, ptr %s1
+  %t42 = load double, ptr %s0
+  %t43 = load double, ptr %s1
+  %t44 = bitcast double %t42 to i64
+  %t45 = icmp ule i64 %t44, -2251799813685248
+  %t46 = and i1 true, %t45
+  %t47 = bitcast double %t43 to i64
+  %t48 = icmp ule i64 %t47, -2251799813685248
+  %t49 = and i1 %t46, %t48
+  br i1 %t49, label %b10, label %b11
+b10:
+  %t50 = frem double %t42, %t43
+  br label %b12
+b11:
+  %t51 = bitcast double %t42 to i64
+  %t52 = bitcast double %t43 to i64
+  %t53 = call i64 @jsrt_mod(i64 %t51, i64 %t52)
+  %t54 = bitcast i64 %t53 to double
+  br label %b12
+b12:
+  %t55 = phi double [ %t50, %b10 ], [ %t54, %b11 ]
+  store double %t55, ptr %s0
+  %t56 = load double, ptr %s0
+  %t57 = bitcast double %t56 to i64
+  call void @jsrt_print_value(i64 %t57, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4010000000000000, ptr %s0
+  %t58 = load double, ptr %s0
+  %t59 = call double @is_even(double %t58)
+  store double %t59, ptr %s0
+  %t60 = load double, ptr %s0
+  %t61 = bitcast double %t60 to i64
+  call void @jsrt_print_value(i64 %t61, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x401C000000000000, ptr %s0
+  %t62 = load double, ptr %s0
+  %t63 = call double @is_even(double %t62)
+  store double %t63, ptr %s0
+  %t64 = load double, ptr %s0
+  %t65 = bitcast double %t64 to i64
+  call void @jsrt_print_value(i64 %t65, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4008000000000000, ptr %s0
+  store double 0x4010000000000000, ptr %s1
+  %t66 = load double, ptr %s0
+  %t67 = load double, ptr %s1
+  %t68 = bitcast double %t66 to i64
+  %t69 = icmp ule i64 %t68, -2251799813685248
+  %t70 = and i1 true, %t69
+  %t71 = bitcast double %t67 to i64
+  %t72 = icmp ule i64 %t71, -2251799813685248
+  %t73 = and i1 %t70, %t72
+  br i1 %t73, label %b13, label %b14
+b13:
+  %t74 = fcmp une double %t66, %t67
+  %t75 = zext i1 %t74 to i64
+  %t76 = or i64 %t75, -1688849860263936
+  %t77 = bitcast i64 %t76 to double
+  br label %b15
+b14:
+  %t78 = bitcast double %t66 to i64
+  %t79 = bitcast double %t67 to i64
+  %t80 = call i64 @jsrt_ne(i64 %t78, i64 %t79)
+  %t81 = bitcast i64 %t80 to double
+  br label %b15
+b15:
+  %t82 = phi double [ %t77, %b13 ], [ %t81, %b14 ]
+  store double %t82, ptr %s0
+  %t83 = load double, ptr %s0
+  %t84 = bitcast double %t83 to i64
+  call void @jsrt_print_value(i64 %t84, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t85 = ptrtoint ptr @.str.0 to i64
+  %t86 = or i64 %t85, -1970324836974592
+  %t87 = bitcast i64 %t86 to double
+  store double %t87, ptr %s0
+  %t88 = ptrtoint ptr @.str.1 to i64
+  %t89 = or i64 %t88, -1970324836974592
+  %t90 = bitcast i64 %t89 to double
+  store double %t90, ptr %s1
+  %t91 = load double, ptr %s0
+  %t92 = load double, ptr %s1
+  %t93 = bitcast double %t91 to i64
+  %t94 = icmp ule i64 %t93, -2251799813685248
+  %t95 = and i1 true, %t94
+  %t96 = bitcast double %t92 to i64
+  %t97 = icmp ule i64 %t96, -2251799813685248
+  %t98 = and i1 %t95, %t97
+  br i1 %t98, label %b16, label %b17
+b16:
+  %t99 = fcmp oeq double %t91, %t92
+  %t100 = zext i1 %t99 to i64
+  %t101 = or i64 %t100, -1688849860263936
+  %t102 = bitcast i64 %t101 to double
+  br label %b18
+b17:
+  %t103 = bitcast double %t91 to i64
+  %t104 = bitcast double %t92 to i64
+  %t105 = call i64 @jsrt_strict_eq(i64 %t103, i64 %t104)
+  %t106 = bitcast i64 %t105 to double
+  br label %b18
+b18:
+  %t107 = phi double [ %t102, %b16 ], [ %t106, %b17 ]
+  store double %t107, ptr %s0
+  %t108 = load double, ptr %s0
+  %t109 = bitcast double %t108 to i64
+  %t110 = icmp ule i64 %t109, -2251799813685248
+  %t111 = and i1 true, %t110
+  br i1 %t111, label %b19, label %b20
+b19:
+  %t112 = fcmp ueq double %t108, 0.0
+  %t113 = zext i1 %t112 to i64
+  %t114 = or i64 %t113, -1688849860263936
+  %t115 = bitcast i64 %t114 to double
+  br label %b21
+b20:
+  %t116 = bitcast double %t108 to i64
+  %t117 = call i64 @jsrt_not(i64 %t116)
+  %t118 = bitcast i64 %t117 to double
+  br label %b21
+b21:
+  %t119 = phi double [ %t115, %b19 ], [ %t118, %b20 ]
+  store double %t119, ptr %s0
+  %t120 = load double, ptr %s0
+  %t121 = bitcast double %t120 to i64
+  call void @jsrt_print_value(i64 %t121, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  %t122 = ptrtoint ptr @.str.2 to i64
+  %t123 = or i64 %t122, -1970324836974592
+  %t124 = bitcast i64 %t123 to double
+  store double %t124, ptr %s1
+  %t125 = load double, ptr %s0
+  %t126 = load double, ptr %s1
+  %t127 = bitcast double %t125 to i64
+  %t128 = icmp ule i64 %t127, -2251799813685248
+  %t129 = and i1 true, %t128
+  %t130 = bitcast double %t126 to i64
+  %t131 = icmp ule i64 %t130, -2251799813685248
+  %t132 = and i1 %t129, %t131
+  br i1 %t132, label %b22, label %b23
+b22:
+  %t133 = fcmp oeq double %t125, %t126
+  %t134 = zext i1 %t133 to i64
+  %t135 = or i64 %t134, -1688849860263936
+  %t136 = bitcast i64 %t135 to double
+  br label %b24
+b23:
+  %t137 = bitcast double %t125 to i64
+  %t138 = bitcast double %t126 to i64
+  %t139 = call i64 @jsrt_strict_eq(i64 %t137, i64 %t138)
+  %t140 = bitcast i64 %t139 to double
+  br label %b24
+b24:
+  %t141 = phi double [ %t136, %b22 ], [ %t140, %b23 ]
+  store double %t141, ptr %s0
+  %t142 = load double, ptr %s0
+  %t143 = bitcast double %t142 to i64
+  call void @jsrt_print_value(i64 %t143, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x4031000000000000, ptr %s0
+  store double 0x4014000000000000, ptr %s1
+  %t144 = load double, ptr %s0
+  %t145 = load double, ptr %s1
+  %t146 = bitcast double %t144 to i64
+  %t147 = icmp ule i64 %t146, -2251799813685248
+  %t148 = and i1 true, %t147
+  %t149 = bitcast double %t145 to i64
+  %t150 = icmp ule i64 %t149, -2251799813685248
+  %t151 = and i1 %t148, %t150
+  br i1 %t151, label %b25, label %b26
+b25:
+  %t152 = frem double %t144, %t145
+  br label %b27
+b26:
+  %t153 = bitcast double %t144 to i64
+  %t154 = bitcast double %t145 to i64
+  %t155 = call i64 @jsrt_mod(i64 %t153, i64 %t154)
+  %t156 = bitcast i64 %t155 to double
+  br label %b27
+b27:
+  %t157 = phi double [ %t152, %b25 ], [ %t156, %b26 ]
+  store double %t157, ptr %s0
+  %t158 = load double, ptr %s0
+  ret double %t158
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+@.str.0 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.1 = private unnamed_addr constant [2 x i8] c"\62\00"
+@.str.2 = private unnamed_addr constant [2 x i8] c"\31\00"
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_mod(i64, i64)
+declare i64 @jsrt_ne(i64, i64)
+declare i64 @jsrt_neg(i64)
+declare i64 @jsrt_not(i64)
+declare i64 @jsrt_strict_eq(i64, i64)
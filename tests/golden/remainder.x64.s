@@ -0,0 +1,401 @@
+	.text
+	.globl is_even
+	.type is_even, @function
+is_even:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	mov .LCD0(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mod
+	mov %rbx, %rsp
+	push %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lis_even.1
+	cmp %rdx, %rcx
+	ja .Lis_even.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lis_even.2
+.Lis_even.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lis_even.2:
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	mov .LCD2(%rip), %rax
+	push %rax
+	mov .LCD3(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mod
+	mov %rbx, %rsp
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD4(%rip), %rax
+	push %rax
+	pop %rax
+	btc $63, %rax
+	push %rax
+	mov .LCD5(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mod
+	mov %rbx, %rsp
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD6(%rip), %rax
+	push %rax
+	mov .LCD7(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mod
+	mov %rbx, %rsp
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD8(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call is_even
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD9(%rip), %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call is_even
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD10(%rip), %rax
+	push %rax
+	mov .LCD11(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setne %al
+	setp %cl
+	or %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC0(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC1(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.3
+	cmp %rdx, %rcx
+	ja .Lmain.3
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.4
+.Lmain.3:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lmain.4:
+	push %rax
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.5
+	movq %rax, %xmm0
+	xorpd %xmm1, %xmm1
+	ucomisd %xmm1, %xmm0
+	sete %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.6
+.Lmain.5:
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_not
+	mov %rbx, %rsp
+.Lmain.6:
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD12(%rip), %rax
+	push %rax
+	leaq .LC2(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.7
+	cmp %rdx, %rcx
+	ja .Lmain.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.8
+.Lmain.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lmain.8:
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD13(%rip), %rax
+	push %rax
+	mov .LCD14(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_mod
+	mov %rbx, %rsp
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+.LC0:
+	.asciz "a"
+.LC1:
+	.asciz "b"
+.LC2:
+	.asciz "1"
+	.p2align 3
+.LCD0:
+	.double 2
+.LCD1:
+	.double 0
+.LCD2:
+	.double 7
+.LCD3:
+	.double 3
+.LCD4:
+	.double 7
+.LCD5:
+	.double 3
+.LCD6:
+	.double 5.5
+.LCD7:
+	.double 2
+.LCD8:
+	.double 4
+.LCD9:
+	.double 7
+.LCD10:
+	.double 3
+.LCD11:
+	.double 4
+.LCD12:
+	.double 1
+.LCD13:
+	.double 17
+.LCD14:
+	.double 5
+	.section .note.GNU-stack,"",@progbits
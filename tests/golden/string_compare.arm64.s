@@ -0,0 +1,415 @@
+	.text
+	.global join
+	.type join, %function
+	.p2align 2
+join:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	str x1, [fp, #-16]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Ljoin.1
+	cmp x1, x9
+	b.hi .Ljoin.1
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Ljoin.2
+.Ljoin.1:
+	bl jsrt_add
+.Ljoin.2:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LC0
+	add x0, x0, :lo12:.LC0
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC1
+	add x0, x0, :lo12:.LC1
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #16]
+	ldr x1, [sp, #0]
+	bl join
+	add sp, sp, #32
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LC2
+	add x0, x0, :lo12:.LC2
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.3
+	cmp x1, x9
+	b.hi .Lmain.3
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.4
+.Lmain.3:
+	bl jsrt_eq
+.Lmain.4:
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LC3
+	add x0, x0, :lo12:.LC3
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.5
+	cmp x1, x9
+	b.hi .Lmain.5
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ne
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.6
+.Lmain.5:
+	bl jsrt_ne
+.Lmain.6:
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LC4
+	add x0, x0, :lo12:.LC4
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.7
+	cmp x1, x9
+	b.hi .Lmain.7
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.8
+.Lmain.7:
+	bl jsrt_strict_eq
+.Lmain.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #48
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC5
+	add x0, x0, :lo12:.LC5
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC6
+	add x0, x0, :lo12:.LC6
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.9
+	cmp x1, x9
+	b.hi .Lmain.9
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.10
+.Lmain.9:
+	bl jsrt_lt
+.Lmain.10:
+	str x0, [sp, #-16]!
+	adrp x0, .LC7
+	add x0, x0, :lo12:.LC7
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC8
+	add x0, x0, :lo12:.LC8
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.11
+	cmp x1, x9
+	b.hi .Lmain.11
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, gt
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.12
+.Lmain.11:
+	bl jsrt_gt
+.Lmain.12:
+	str x0, [sp, #-16]!
+	adrp x0, .LC9
+	add x0, x0, :lo12:.LC9
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC10
+	add x0, x0, :lo12:.LC10
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.13
+	cmp x1, x9
+	b.hi .Lmain.13
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ls
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.14
+.Lmain.13:
+	bl jsrt_le
+.Lmain.14:
+	str x0, [sp, #-16]!
+	adrp x0, .LC11
+	add x0, x0, :lo12:.LC11
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC12
+	add x0, x0, :lo12:.LC12
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.15
+	cmp x1, x9
+	b.hi .Lmain.15
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ge
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.16
+.Lmain.15:
+	bl jsrt_ge
+.Lmain.16:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #48]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #32]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #64
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	adrp x0, .LC13
+	add x0, x0, :lo12:.LC13
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #16]
+	ldr x1, [sp, #0]
+	bl join
+	add sp, sp, #32
+	str x0, [sp, #-16]!
+	adrp x0, .LC14
+	add x0, x0, :lo12:.LC14
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.17
+	cmp x1, x9
+	b.hi .Lmain.17
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.18
+.Lmain.17:
+	bl jsrt_eq
+.Lmain.18:
+	str x0, [sp, #-16]!
+	adrp x0, .LC15
+	add x0, x0, :lo12:.LC15
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	mov x0, #0xfffa000000000000
+	orr x0, x0, #1
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #16]
+	ldr x1, [sp, #0]
+	bl join
+	add sp, sp, #32
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #16]
+	mov w1, #32
+	bl jsrt_print_value
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #32
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+.LC0:
+	.asciz "ab"
+.LC1:
+	.asciz "c"
+.LC2:
+	.asciz "abc"
+.LC3:
+	.asciz "abc"
+.LC4:
+	.asciz "abc"
+.LC5:
+	.asciz "apple"
+.LC6:
+	.asciz "banana"
+.LC7:
+	.asciz "b"
+.LC8:
+	.asciz "a"
+.LC9:
+	.asciz "abc"
+.LC10:
+	.asciz "abd"
+.LC11:
+	.asciz "b"
+.LC12:
+	.asciz "c"
+.LC13:
+	.asciz "2"
+.LC14:
+	.asciz "12"
+.LC15:
+	.asciz "x"
+	.p2align 3
+.LCD0:
+	.double 1
+.LCD1:
+	.double 0
+	.section .note.GNU-stack,"",%progbits
@@ -0,0 +1,404 @@
+; Generated by js-compiler
+
+define double @join(double %p0, double %p1) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.a = alloca double
+  %v.b = alloca double
+  store double %p0, ptr %v.a
+  store double %p1, ptr %v.b
+  %t1 = load double, ptr %v.a
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.a
+  %t3 = load double, ptr %v.b
+  store double %t3, ptr %s0
+  %t4 = load double, ptr %s0
+  store double %t4, ptr %v.b
+  %t5 = load double, ptr %v.a
+  store double %t5, ptr %s0
+  %t6 = load double, ptr %v.b
+  store double %t6, ptr %s1
+  %t7 = load double, ptr %s0
+  %t8 = load double, ptr %s1
+  %t9 = bitcast double %t7 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 true, %t10
+  %t12 = bitcast double %t8 to i64
+  %t13 = icmp ule i64 %t12, -2251799813685248
+  %t14 = and i1 %t11, %t13
+  br i1 %t14, label %b1, label %b2
+b1:
+  %t15 = fadd double %t7, %t8
+  br label %b3
+b2:
+  %t16 = bitcast double %t7 to i64
+  %t17 = bitcast double %t8 to i64
+  %t18 = call i64 @jsrt_add(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  %t21 = load double, ptr %s0
+  ret double %t21
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %s2 = alloca double
+  %s3 = alloca double
+  %s4 = alloca double
+  %v.word = alloca double
+  %t1 = ptrtoint ptr @.str.0 to i64
+  %t2 = or i64 %t1, -1970324836974592
+  %t3 = bitcast i64 %t2 to double
+  store double %t3, ptr %s0
+  %t4 = ptrtoint ptr @.str.1 to i64
+  %t5 = or i64 %t4, -1970324836974592
+  %t6 = bitcast i64 %t5 to double
+  store double %t6, ptr %s1
+  %t7 = load double, ptr %s0
+  %t8 = load double, ptr %s1
+  %t9 = call double @join(double %t7, double %t8)
+  store double %t9, ptr %s0
+  %t10 = load double, ptr %s0
+  store double %t10, ptr %v.word
+  %t11 = load double, ptr %v.word
+  store double %t11, ptr %s0
+  %t12 = ptrtoint ptr @.str.2 to i64
+  %t13 = or i64 %t12, -1970324836974592
+  %t14 = bitcast i64 %t13 to double
+  store double %t14, ptr %s1
+  %t15 = load double, ptr %s0
+  %t16 = load double, ptr %s1
+  %t17 = bitcast double %t15 to i64
+  %t18 = icmp ule i64 %t17, -2251799813685248
+  %t19 = and i1 true, %t18
+  %t20 = bitcast double %t16 to i64
+  %t21 = icmp ule i64 %t20, -2251799813685248
+  %t22 = and i1 %t19, %t21
+  br i1 %t22, label %b1, label %b2
+b1:
+  %t23 = fcmp oeq double %t15, %t16
+  %t24 = zext i1 %t23 to i64
+  %t25 = or i64 %t24, -1688849860263936
+  %t26 = bitcast i64 %t25 to double
+  br label %b3
+b2:
+  %t27 = bitcast double %t15 to i64
+  %t28 = bitcast double %t16 to i64
+  %t29 = call i64 @jsrt_eq(i64 %t27, i64 %t28)
+  %t30 = bitcast i64 %t29 to double
+  br label %b3
+b3:
+  %t31 = phi double [ %t26, %b1 ], [ %t30, %b2 ]
+  store double %t31, ptr %s0
+  %t32 = load double, ptr %v.word
+  store double %t32, ptr %s1
+  %t33 = ptrtoint ptr @.str.3 to i64
+  %t34 = or i64 %t33, -1970324836974592
+  %t35 = bitcast i64 %t34 to double
+  store double %t35, ptr %s2
+  %t36 = load double, ptr %s1
+  %t37 = load double, ptr %s2
+  %t38 = bitcast double %t36 to i64
+  %t39 = icmp ule i64 %t38, -2251799813685248
+  %t40 = and i1 true, %t39
+  %t41 = bitcast double %t37 to i64
+  %t42 = icmp ule i64 %t41, -2251799813685248
+  %t43 = and i1 %t40, %t42
+  br i1 %t43, label %b4, label %b5
+b4:
+  %t44 = fcmp une double %t36, %t37
+  %t45 = zext i1 %t44 to i64
+  %t46 = or i64 %t45, -1688849860263936
+  %t47 = bitcast i64 %t46 to double
+  br label %b6
+b5:
+  %t48 = bitcast double %t36 to i64
+  %t49 = bitcast double %t37 to i64
+  %t50 = call i64 @jsrt_ne(i64 %t48, i64 %t49)
+  %t51 = bitcast i64 %t50 to double
+  br label %b6
+b6:
+  %t52 = phi double [ %t47, %b4 ], [ %t51, %b5 ]
+  store double %t52, ptr %s1
+  %t53 = load double, ptr %v.word
+  store double %t53, ptr %s2
+  %t54 = ptrtoint ptr @.str.4 to i64
+  %t55 = or i64 %t54, -1970324836974592
+  %t56 = bitcast i64 %t55 to double
+  store double %t56, ptr %s3
+  %t57 = load double, ptr %s2
+  %t58 = load double, ptr %s3
+  %t59 = bitcast double %t57 to i64
+  %t60 = icmp ule i64 %t59, -2251799813685248
+  %t61 = and i1 true, %t60
+  %t62 = bitcast double %t58 to i64
+  %t63 = icmp ule i64 %t62, -2251799813685248
+  %t64 = and i1 %t61, %t63
+  br i1 %t64, label %b7, label %b8
+b7:
+  %t65 = fcmp oeq double %t57, %t58
+  %t66 = zext i1 %t65 to i64
+  %t67 = or i64 %t66, -1688849860263936
+  %t68 = bitcast i64 %t67 to double
+  br label %b9
+b8:
+  %t69 = bitcast double %t57 to i64
+  %t70 = bitcast double %t58 to i64
+  %t71 = call i64 @jsrt_strict_eq(i64 %t69, i64 %t70)
+  %t72 = bitcast i64 %t71 to double
+  br label %b9
+b9:
+  %t73 = phi double [ %t68, %b7 ], [ %t72, %b8 ]
+  store double %t73, ptr %s2
+  %t74 = load double, ptr %s0
+  %t75 = load double, ptr %s1
+  %t76 = load double, ptr %s2
+  %t77 = bitcast double %t74 to i64
+  call void @jsrt_print_value(i64 %t77, i32 32)
+  %t78 = bitcast double %t75 to i64
+  call void @jsrt_print_value(i64 %t78, i32 32)
+  %t79 = bitcast double %t76 to i64
+  call void @jsrt_print_value(i64 %t79, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t80 = ptrtoint ptr @.str.5 to i64
+  %t81 = or i64 %t80, -1970324836974592
+  %t82 = bitcast i64 %t81 to double
+  store double %t82, ptr %s0
+  %t83 = ptrtoint ptr @.str.6 to i64
+  %t84 = or i64 %t83, -1970324836974592
+  %t85 = bitcast i64 %t84 to double
+  store double %t85, ptr %s1
+  %t86 = load double, ptr %s0
+  %t87 = load double, ptr %s1
+  %t88 = bitcast double %t86 to i64
+  %t89 = icmp ule i64 %t88, -2251799813685248
+  %t90 = and i1 true, %t89
+  %t91 = bitcast double %t87 to i64
+  %t92 = icmp ule i64 %t91, -2251799813685248
+  %t93 = and i1 %t90, %t92
+  br i1 %t93, label %b10, label %b11
+b10:
+  %t94 = fcmp olt double %t86, %t87
+  %t95 = zext i1 %t94 to i64
+  %t96 = or i64 %t95, -1688849860263936
+  %t97 = bitcast i64 %t96 to double
+  br label %b12
+b11:
+  %t98 = bitcast double %t86 to i64
+  %t99 = bitcast double %t87 to i64
+  %t100 = call i64 @jsrt_lt(i64 %t98, i64 %t99)
+  %t101 = bitcast i64 %t100 to double
+  br label %b12
+b12:
+  %t102 = phi double [ %t97, %b10 ], [ %t101, %b11 ]
+  store double %t102, ptr %s0
+  %t103 = ptrtoint ptr @.str.7 to i64
+  %t104 = or i64 %t103, -1970324836974592
+  %t105 = bitcast i64 %t104 to double
+  store double %t105, ptr %s1
+  %t106 = ptrtoint ptr @.str.8 to i64
+  %t107 = or i64 %t106, -1970324836974592
+  %t108 = bitcast i64 %t107 to double
+  store double %t108, ptr %s2
+  %t109 = load double, ptr %s1
+  %t110 = load double, ptr %s2
+  %t111 = bitcast double %t109 to i64
+  %t112 = icmp ule i64 %t111, -2251799813685248
+  %t113 = and i1 true, %t112
+  %t114 = bitcast double %t110 to i64
+  %t115 = icmp ule i64 %t114, -2251799813685248
+  %t116 = and i1 %t113, %t115
+  br i1 %t116, label %b13, label %b14
+b13:
+  %t117 = fcmp ogt double %t109, %t110
+  %t118 = zext i1 %t117 to i64
+  %t119 = or i64 %t118, -1688849860263936
+  %t120 = bitcast i64 %t119 to double
+  br label %b15
+b14:
+  %t121 = bitcast double %t109 to i64
+  %t122 = bitcast double %t110 to i64
+  %t123 = call i64 @jsrt_gt(i64 %t121, i64 %t122)
+  %t124 = bitcast i64 %t123 to double
+  br label %b15
+b15:
+  %t125 = phi double [ %t120, %b13 ], [ %t124, %b14 ]
+  store double %t125, ptr %s1
+  %t126 = ptrtoint ptr @.str.9 to i64
+  %t127 = or i64 %t126, -1970324836974592
+  %t128 = bitcast i64 %t127 to double
+  store double %t128, ptr %s2
+  %t129 = ptrtoint ptr @.str.10 to i64
+  %t130 = or i64 %t129, -1970324836974592
+  %t131 = bitcast i64 %t130 to double
+  store double %t131, ptr %s3
+  %t132 = load double, ptr %s2
+  %t133 = load double, ptr %s3
+  %t134 = bitcast double %t132 to i64
+  %t135 = icmp ule i64 %t134, -2251799813685248
+  %t136 = and i1 true, %t135
+  %t137 = bitcast double %t133 to i64
+  %t138 = icmp ule i64 %t137, -2251799813685248
+  %t139 = and i1 %t136, %t138
+  br i1 %t139, label %b16, label %b17
+b16:
+  %t140 = fcmp ole double %t132, %t133
+  %t141 = zext i1 %t140 to i64
+  %t142 = or i64 %t141, -1688849860263936
+  %t143 = bitcast i64 %t142 to double
+  br label %b18
+b17:
+  %t144 = bitcast double %t132 to i64
+  %t145 = bitcast double %t133 to i64
+  %t146 = call i64 @jsrt_le(i64 %t144, i64 %t145)
+  %t147 = bitcast i64 %t146 to double
+  br label %b18
+b18:
+  %t148 = phi double [ %t143, %b16 ], [ %t147, %b17 ]
+  store double %t148, ptr %s2
+  %t149 = ptrtoint ptr @.str.11 to i64
+  %t150 = or i64 %t149, -1970324836974592
+  %t151 = bitcast i64 %t150 to double
+  store double %t151, ptr %s3
+  %t152 = ptrtoint ptr @.str.12 to i64
+  %t153 = or i64 %t152, -1970324836974592
+  %t154 = bitcast i64 %t153 to double
+  store double %t154, ptr %s4
+  %t155 = load double, ptr %s3
+  %t156 = load double, ptr %s4
+  %t157 = bitcast double %t155 to i64
+  %t158 = icmp ule i64 %t157, -2251799813685248
+  %t159 = and i1 true, %t158
+  %t160 = bitcast double %t156 to i64
+  %t161 = icmp ule i64 %t160, -2251799813685248
+  %t162 = and i1 %t159, %t161
+  br i1 %t162, label %b19, label %b20
+b19:
+  %t163 = fcmp oge double %t155, %t156
+  %t164 = zext i1 %t163 to i64
+  %t165 = or i64 %t164, -1688849860263936
+  %t166 = bitcast i64 %t165 to double
+  br label %b21
+b20:
+  %t167 = bitcast double %t155 to i64
+  %t168 = bitcast double %t156 to i64
+  %t169 = call i64 @jsrt_ge(i64 %t167, i64 %t168)
+  %t170 = bitcast i64 %t169 to double
+  br label %b21
+b21:
+  %t171 = phi double [ %t166, %b19 ], [ %t170, %b20 ]
+  store double %t171, ptr %s3
+  %t172 = load double, ptr %s0
+  %t173 = load double, ptr %s1
+  %t174 = load double, ptr %s2
+  %t175 = load double, ptr %s3
+  %t176 = bitcast double %t172 to i64
+  call void @jsrt_print_value(i64 %t176, i32 32)
+  %t177 = bitcast double %t173 to i64
+  call void @jsrt_print_value(i64 %t177, i32 32)
+  %t178 = bitcast double %t174 to i64
+  call void @jsrt_print_value(i64 %t178, i32 32)
+  %t179 = bitcast double %t175 to i64
+  call void @jsrt_print_value(i64 %t179, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF0000000000000, ptr %s0
+  %t180 = ptrtoint ptr @.str.13 to i64
+  %t181 = or i64 %t180, -1970324836974592
+  %t182 = bitcast i64 %t181 to double
+  store double %t182, ptr %s1
+  %t183 = load double, ptr %s0
+  %t184 = load double, ptr %s1
+  %t185 = call double @join(double %t183, double %t184)
+  store double %t185, ptr %s0
+  %t186 = ptrtoint ptr @.str.14 to i64
+  %t187 = or i64 %t186, -1970324836974592
+  %t188 = bitcast i64 %t187 to double
+  store double %t188, ptr %s1
+  %t189 = load double, ptr %s0
+  %t190 = load double, ptr %s1
+  %t191 = bitcast double %t189 to i64
+  %t192 = icmp ule i64 %t191, -2251799813685248
+  %t193 = and i1 true, %t192
+  %t194 = bitcast double %t190 to i64
+  %t195 = icmp ule i64 %t194, -2251799813685248
+  %t196 = and i1 %t193, %t195
+  br i1 %t196, label %b22, label %b23
+b22:
+  %t197 = fcmp oeq double %t189, %t190
+  %t198 = zext i1 %t197 to i64
+  %t199 = or i64 %t198, -1688849860263936
+  %t200 = bitcast i64 %t199 to double
+  br label %b24
+b23:
+  %t201 = bitcast double %t189 to i64
+  %t202 = bitcast double %t190 to i64
+  %t203 = call i64 @jsrt_eq(i64 %t201, i64 %t202)
+  %t204 = bitcast i64 %t203 to double
+  br label %b24
+b24:
+  %t205 = phi double [ %t200, %b22 ], [ %t204, %b23 ]
+  store double %t205, ptr %s0
+  %t206 = ptrtoint ptr @.str.15 to i64
+  %t207 = or i64 %t206, -1970324836974592
+  %t208 = bitcast i64 %t207 to double
+  store double %t208, ptr %s1
+  store double 0xFFFA000000000001, ptr %s2
+  %t209 = load double, ptr %s1
+  %t210 = load double, ptr %s2
+  %t211 = call double @join(double %t209, double %t210)
+  store double %t211, ptr %s1
+  %t212 = load double, ptr %s0
+  %t213 = load double, ptr %s1
+  %t214 = bitcast double %t212 to i64
+  call void @jsrt_print_value(i64 %t214, i32 32)
+  %t215 = bitcast double %t213 to i64
+  call void @jsrt_print_value(i64 %t215, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t216 = load double, ptr %s0
+  ret double %t216
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+@.str.0 = private unnamed_addr constant [3 x i8] c"\61\62\00"
+@.str.1 = private unnamed_addr constant [2 x i8] c"\63\00"
+@.str.2 = private unnamed_addr constant [4 x i8] c"\61\62\63\00"
+@.str.3 = private unnamed_addr constant [4 x i8] c"\61\62\63\00"
+@.str.4 = private unnamed_addr constant [4 x i8] c"\61\62\63\00"
+@.str.5 = private unnamed_addr constant [6 x i8] c"\61\70\70\6C\65\00"
+@.str.6 = private unnamed_addr constant [7 x i8] c"\62\61\6E\61\6E\61\00"
+@.str.7 = private unnamed_addr constant [2 x i8] c"\62\00"
+@.str.8 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.9 = private unnamed_addr constant [4 x i8] c"\61\62\63\00"
+@.str.10 = private unnamed_addr constant [4 x i8] c"\61\62\64\00"
+@.str.11 = private unnamed_addr constant [2 x i8] c"\62\00"
+@.str.12 = private unnamed_addr constant [2 x i8] c"\63\00"
+@.str.13 = private unnamed_addr constant [2 x i8] c"\32\00"
+@.str.14 = private unnamed_addr constant [3 x i8] c"\31\32\00"
+@.str.15 = private unnamed_addr constant [2 x i8] c"\78\00"
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_eq(i64, i64)
+declare i64 @jsrt_ge(i64, i64)
+declare i64 @jsrt_gt(i64, i64)
+declare i64 @jsrt_le(i64, i64)
+declare i64 @jsrt_lt(i64, i64)
+declare i64 @jsrt_ne(i64, i64)
+declare i64 @jsrt_strict_eq(i64, i64)
@@ -0,0 +1,510 @@
+	.text
+	.globl join
+	.type join, @function
+join:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	movq %xmm1, %rax
+	mov %rax, -16(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -16(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	mov -16(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Ljoin.1
+	cmp %rdx, %rcx
+	ja .Ljoin.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Ljoin.2
+.Ljoin.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Ljoin.2:
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	leaq .LC0(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC1(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov 8(%rsp), %rax
+	movq %rax, %xmm0
+	mov 0(%rsp), %rax
+	movq %rax, %xmm1
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call join
+	mov %rbx, %rsp
+	add $16, %rsp
+	movq %xmm0, %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	leaq .LC2(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.3
+	cmp %rdx, %rcx
+	ja .Lmain.3
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.4
+.Lmain.3:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.4:
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	leaq .LC3(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.5
+	cmp %rdx, %rcx
+	ja .Lmain.5
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setne %al
+	setp %cl
+	or %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.6
+.Lmain.5:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_ne
+	mov %rbx, %rsp
+.Lmain.6:
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	leaq .LC4(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.7
+	cmp %rdx, %rcx
+	ja .Lmain.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.8
+.Lmain.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_strict_eq
+	mov %rbx, %rsp
+.Lmain.8:
+	push %rax
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $24, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC5(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC6(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.9
+	cmp %rdx, %rcx
+	ja .Lmain.9
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.10
+.Lmain.9:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_lt
+	mov %rbx, %rsp
+.Lmain.10:
+	push %rax
+	leaq .LC7(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC8(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.11
+	cmp %rdx, %rcx
+	ja .Lmain.11
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	seta %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.12
+.Lmain.11:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_gt
+	mov %rbx, %rsp
+.Lmain.12:
+	push %rax
+	leaq .LC9(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC10(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.13
+	cmp %rdx, %rcx
+	ja .Lmain.13
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm0, %xmm1
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.14
+.Lmain.13:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_le
+	mov %rbx, %rsp
+.Lmain.14:
+	push %rax
+	leaq .LC11(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC12(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.15
+	cmp %rdx, %rcx
+	ja .Lmain.15
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	setae %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.16
+.Lmain.15:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_ge
+	mov %rbx, %rsp
+.Lmain.16:
+	push %rax
+	mov 24(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 16(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $32, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD0(%rip), %rax
+	push %rax
+	leaq .LC13(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov 8(%rsp), %rax
+	movq %rax, %xmm0
+	mov 0(%rsp), %rax
+	movq %rax, %xmm1
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call join
+	mov %rbx, %rsp
+	add $16, %rsp
+	movq %xmm0, %rax
+	push %rax
+	leaq .LC14(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.17
+	cmp %rdx, %rcx
+	ja .Lmain.17
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.18
+.Lmain.17:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.18:
+	push %rax
+	leaq .LC15(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	movabs $18445055223849287681, %rax
+	push %rax
+	mov 8(%rsp), %rax
+	movq %rax, %xmm0
+	mov 0(%rsp), %rax
+	movq %rax, %xmm1
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call join
+	mov %rbx, %rsp
+	add $16, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 8(%rsp), %rdi
+	mov $32, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $16, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+.LC0:
+	.asciz "ab"
+.LC1:
+	.asciz "c"
+.LC2:
+	.asciz "abc"
+.LC3:
+	.asciz "abc"
+.LC4:
+	.asciz "abc"
+.LC5:
+	.asciz "apple"
+.LC6:
+	.asciz "banana"
+.LC7:
+	.asciz "b"
+.LC8:
+	.asciz "a"
+.LC9:
+	.asciz "abc"
+.LC10:
+	.asciz "abd"
+.LC11:
+	.asciz "b"
+.LC12:
+	.asciz "c"
+.LC13:
+	.asciz "2"
+.LC14:
+	.asciz "12"
+.LC15:
+	.asciz "x"
+	.p2align 3
+.LCD0:
+	.double 1
+.LCD1:
+	.double 0
+	.section .note.GNU-stack,"",@progbits
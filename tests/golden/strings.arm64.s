@@ -0,0 +1,238 @@
+	.text
+	.global greet
+	.type greet, %function
+	.p2align 2
+greet:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	adrp x0, .LC0
+	add x0, x0, :lo12:.LC0
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lgreet.1
+	cmp x1, x9
+	b.hi .Lgreet.1
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lgreet.2
+.Lgreet.1:
+	bl jsrt_add
+.Lgreet.2:
+	str x0, [sp, #-16]!
+	adrp x0, .LC1
+	add x0, x0, :lo12:.LC1
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lgreet.3
+	cmp x1, x9
+	b.hi .Lgreet.3
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lgreet.4
+.Lgreet.3:
+	bl jsrt_add
+.Lgreet.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LC2
+	add x0, x0, :lo12:.LC2
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl greet
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC3
+	add x0, x0, :lo12:.LC3
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.5
+	cmp x1, x9
+	b.hi .Lmain.5
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.6
+.Lmain.5:
+	bl jsrt_add
+.Lmain.6:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	adrp x0, .LC4
+	add x0, x0, :lo12:.LC4
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.7
+	cmp x1, x9
+	b.hi .Lmain.7
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	b .Lmain.8
+.Lmain.7:
+	bl jsrt_add
+.Lmain.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LC5
+	add x0, x0, :lo12:.LC5
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	adrp x0, .LC6
+	add x0, x0, :lo12:.LC6
+	mov x9, #0xfff9000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.9
+	cmp x1, x9
+	b.hi .Lmain.9
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, eq
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.10
+.Lmain.9:
+	bl jsrt_eq
+.Lmain.10:
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+.LC0:
+	.asciz "Hello, "
+.LC1:
+	.asciz "!"
+.LC2:
+	.asciz "world"
+.LC3:
+	.asciz "n="
+.LC4:
+	.asciz " apples"
+.LC5:
+	.asciz "a"
+.LC6:
+	.asciz "a"
+	.p2align 3
+.LCD0:
+	.double 42
+.LCD1:
+	.double 1.5
+.LCD2:
+	.double 0
+	.section .note.GNU-stack,"",%progbits
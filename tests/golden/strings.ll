@@ -0,0 +1,205 @@
+; Generated by js-compiler
+
+define double @greet(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.name = alloca double
+  store double %p0, ptr %v.name
+  %t1 = load double, ptr %v.name
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.name
+  %t3 = ptrtoint ptr @.str.0 to i64
+  %t4 = or i64 %t3, -1970324836974592
+  %t5 = bitcast i64 %t4 to double
+  store double %t5, ptr %s0
+  %t6 = load double, ptr %v.name
+  store double %t6, ptr %s1
+  %t7 = load double, ptr %s0
+  %t8 = load double, ptr %s1
+  %t9 = bitcast double %t7 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 true, %t10
+  %t12 = bitcast double %t8 to i64
+  %t13 = icmp ule i64 %t12, -2251799813685248
+  %t14 = and i1 %t11, %t13
+  br i1 %t14, label %b1, label %b2
+b1:
+  %t15 = fadd double %t7, %t8
+  br label %b3
+b2:
+  %t16 = bitcast double %t7 to i64
+  %t17 = bitcast double %t8 to i64
+  %t18 = call i64 @jsrt_add(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  %t21 = ptrtoint ptr @.str.1 to i64
+  %t22 = or i64 %t21, -1970324836974592
+  %t23 = bitcast i64 %t22 to double
+  store double %t23, ptr %s1
+  %t24 = load double, ptr %s0
+  %t25 = load double, ptr %s1
+  %t26 = bitcast double %t24 to i64
+  %t27 = icmp ule i64 %t26, -2251799813685248
+  %t28 = and i1 true, %t27
+  %t29 = bitcast double %t25 to i64
+  %t30 = icmp ule i64 %t29, -2251799813685248
+  %t31 = and i1 %t28, %t30
+  br i1 %t31, label %b4, label %b5
+b4:
+  %t32 = fadd double %t24, %t25
+  br label %b6
+b5:
+  %t33 = bitcast double %t24 to i64
+  %t34 = bitcast double %t25 to i64
+  %t35 = call i64 @jsrt_add(i64 %t33, i64 %t34)
+  %t36 = bitcast i64 %t35 to double
+  br label %b6
+b6:
+  %t37 = phi double [ %t32, %b4 ], [ %t36, %b5 ]
+  store double %t37, ptr %s0
+  %t38 = load double, ptr %s0
+  ret double %t38
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %t1 = ptrtoint ptr @.str.2 to i64
+  %t2 = or i64 %t1, -1970324836974592
+  %t3 = bitcast i64 %t2 to double
+  store double %t3, ptr %s0
+  %t4 = load double, ptr %s0
+  %t5 = call double @greet(double %t4)
+  store double %t5, ptr %s0
+  %t6 = load double, ptr %s0
+  %t7 = bitcast double %t6 to i64
+  call void @jsrt_print_value(i64 %t7, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t8 = ptrtoint ptr @.str.3 to i64
+  %t9 = or i64 %t8, -1970324836974592
+  %t10 = bitcast i64 %t9 to double
+  store double %t10, ptr %s0
+  store double 0x4045000000000000, ptr %s1
+  %t11 = load double, ptr %s0
+  %t12 = load double, ptr %s1
+  %t13 = bitcast double %t11 to i64
+  %t14 = icmp ule i64 %t13, -2251799813685248
+  %t15 = and i1 true, %t14
+  %t16 = bitcast double %t12 to i64
+  %t17 = icmp ule i64 %t16, -2251799813685248
+  %t18 = and i1 %t15, %t17
+  br i1 %t18, label %b1, label %b2
+b1:
+  %t19 = fadd double %t11, %t12
+  br label %b3
+b2:
+  %t20 = bitcast double %t11 to i64
+  %t21 = bitcast double %t12 to i64
+  %t22 = call i64 @jsrt_add(i64 %t20, i64 %t21)
+  %t23 = bitcast i64 %t22 to double
+  br label %b3
+b3:
+  %t24 = phi double [ %t19, %b1 ], [ %t23, %b2 ]
+  store double %t24, ptr %s0
+  %t25 = load double, ptr %s0
+  %t26 = bitcast double %t25 to i64
+  call void @jsrt_print_value(i64 %t26, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x3FF8000000000000, ptr %s0
+  %t27 = ptrtoint ptr @.str.4 to i64
+  %t28 = or i64 %t27, -1970324836974592
+  %t29 = bitcast i64 %t28 to double
+  store double %t29, ptr %s1
+  %t30 = load double, ptr %s0
+  %t31 = load double, ptr %s1
+  %t32 = bitcast double %t30 to i64
+  %t33 = icmp ule i64 %t32, -2251799813685248
+  %t34 = and i1 true, %t33
+  %t35 = bitcast double %t31 to i64
+  %t36 = icmp ule i64 %t35, -2251799813685248
+  %t37 = and i1 %t34, %t36
+  br i1 %t37, label %b4, label %b5
+b4:
+  %t38 = fadd double %t30, %t31
+  br label %b6
+b5:
+  %t39 = bitcast double %t30 to i64
+  %t40 = bitcast double %t31 to i64
+  %t41 = call i64 @jsrt_add(i64 %t39, i64 %t40)
+  %t42 = bitcast i64 %t41 to double
+  br label %b6
+b6:
+  %t43 = phi double [ %t38, %b4 ], [ %t42, %b5 ]
+  store double %t43, ptr %s0
+  %t44 = load double, ptr %s0
+  %t45 = bitcast double %t44 to i64
+  call void @jsrt_print_value(i64 %t45, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t46 = ptrtoint ptr @.str.5 to i64
+  %t47 = or i64 %t46, -1970324836974592
+  %t48 = bitcast i64 %t47 to double
+  store double %t48, ptr %s0
+  %t49 = ptrtoint ptr @.str.6 to i64
+  %t50 = or i64 %t49, -1970324836974592
+  %t51 = bitcast i64 %t50 to double
+  store double %t51, ptr %s1
+  %t52 = load double, ptr %s0
+  %t53 = load double, ptr %s1
+  %t54 = bitcast double %t52 to i64
+  %t55 = icmp ule i64 %t54, -2251799813685248
+  %t56 = and i1 true, %t55
+  %t57 = bitcast double %t53 to i64
+  %t58 = icmp ule i64 %t57, -2251799813685248
+  %t59 = and i1 %t56, %t58
+  br i1 %t59, label %b7, label %b8
+b7:
+  %t60 = fcmp oeq double %t52, %t53
+  %t61 = zext i1 %t60 to i64
+  %t62 = or i64 %t61, -1688849860263936
+  %t63 = bitcast i64 %t62 to double
+  br label %b9
+b8:
+  %t64 = bitcast double %t52 to i64
+  %t65 = bitcast double %t53 to i64
+  %t66 = call i64 @jsrt_eq(i64 %t64, i64 %t65)
+  %t67 = bitcast i64 %t66 to double
+  br label %b9
+b9:
+  %t68 = phi double [ %t63, %b7 ], [ %t67, %b8 ]
+  store double %t68, ptr %s0
+  %t69 = load double, ptr %s0
+  %t70 = bitcast double %t69 to i64
+  call void @jsrt_print_value(i64 %t70, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t71 = load double, ptr %s0
+  ret double %t71
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+@.str.0 = private unnamed_addr constant [8 x i8] c"\48\65\6C\6C\6F\2C\20\00"
+@.str.1 = private unnamed_addr constant [2 x i8] c"\21\00"
+@.str.2 = private unnamed_addr constant [6 x i8] c"\77\6F\72\6C\64\00"
+@.str.3 = private unnamed_addr constant [3 x i8] c"\6E\3D\00"
+@.str.4 = private unnamed_addr constant [8 x i8] c"\20\61\70\70\6C\65\73\00"
+@.str.5 = private unnamed_addr constant [2 x i8] c"\61\00"
+@.str.6 = private unnamed_addr constant [2 x i8] c"\61\00"
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_eq(i64, i64)
@@ -0,0 +1,280 @@
+	.text
+	.globl greet
+	.type greet, @function
+greet:
+	push %rbp
+	mov %rsp, %rbp
+	sub $16, %rsp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	movq %xmm0, %rax
+	mov %rax, -8(%rbp)
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rax
+	mov %rax, -8(%rbp)
+	leaq .LC0(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov -8(%rbp), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lgreet.1
+	cmp %rdx, %rcx
+	ja .Lgreet.1
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lgreet.2
+.Lgreet.1:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lgreet.2:
+	push %rax
+	leaq .LC1(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lgreet.3
+	cmp %rdx, %rcx
+	ja .Lgreet.3
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lgreet.4
+.Lgreet.3:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lgreet.4:
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl js_main
+	.type js_main, @function
+js_main:
+	push %rbp
+	mov %rsp, %rbp
+	push %rbx
+	push %r12
+	push %r13
+	push %r14
+	push %r15
+	leaq .LC2(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov 0(%rsp), %rax
+	movq %rax, %xmm0
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call greet
+	mov %rbx, %rsp
+	add $8, %rsp
+	movq %xmm0, %rax
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC3(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	mov .LCD0(%rip), %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.5
+	cmp %rdx, %rcx
+	ja .Lmain.5
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.6
+.Lmain.5:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.6:
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD1(%rip), %rax
+	push %rax
+	leaq .LC4(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.7
+	cmp %rdx, %rcx
+	ja .Lmain.7
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	addsd %xmm1, %xmm0
+	movq %xmm0, %rax
+	jmp .Lmain.8
+.Lmain.7:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_add
+	mov %rbx, %rsp
+.Lmain.8:
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	leaq .LC5(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	leaq .LC6(%rip), %rax
+	movabs $18444773748872577024, %rcx
+	or %rcx, %rax
+	push %rax
+	pop %rcx
+	pop %rax
+	movabs $18444492273895866368, %rdx
+	cmp %rdx, %rax
+	ja .Lmain.9
+	cmp %rdx, %rcx
+	ja .Lmain.9
+	movq %rcx, %xmm1
+	movq %rax, %xmm0
+	ucomisd %xmm1, %xmm0
+	sete %al
+	setnp %cl
+	and %cl, %al
+	movzx %al, %eax
+	movabs $18445055223849287680, %rcx
+	or %rcx, %rax
+	jmp .Lmain.10
+.Lmain.9:
+	mov %rcx, %rsi
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_eq
+	mov %rbx, %rsp
+.Lmain.10:
+	push %rax
+	mov 0(%rsp), %rdi
+	mov $10, %rsi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_print_value
+	mov %rbx, %rsp
+	add $8, %rsp
+	movabs $18445618173802708992, %rax
+	push %rax
+	pop %rax
+	mov .LCD2(%rip), %rax
+	push %rax
+	pop %rax
+	movq %rax, %xmm0
+	pop %r15
+	pop %r14
+	pop %r13
+	pop %r12
+	pop %rbx
+	mov %rbp, %rsp
+	pop %rbp
+	ret
+	.globl main
+	.type main, @function
+main:
+	push %rbp
+	mov %rsp, %rbp
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call js_main
+	mov %rbx, %rsp
+	mov %rax, %rdi
+	mov %rsp, %rbx
+	and $-16, %rsp
+	call jsrt_exit
+	mov %rbx, %rsp
+	.data
+.LC0:
+	.asciz "Hello, "
+.LC1:
+	.asciz "!"
+.LC2:
+	.asciz "world"
+.LC3:
+	.asciz "n="
+.LC4:
+	.asciz " apples"
+.LC5:
+	.asciz "a"
+.LC6:
+	.asciz "a"
+	.p2align 3
+.LCD0:
+	.double 42
+.LCD1:
+	.double 1.5
+.LCD2:
+	.double 0
+	.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,289 @@
+; Generated by js-compiler
+
+define double @nothing() {
+entry:
+  ret double 0xFFFC000000000000
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %s2 = alloca double
+  %s3 = alloca double
+  %s4 = alloca double
+  store double 0xFFFB000000000000, ptr %s0
+  %t1 = load double, ptr %s0
+  %t2 = bitcast double %t1 to i64
+  call void @jsrt_print_value(i64 %t2, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t3 = call double @nothing()
+  store double %t3, ptr %s0
+  %t4 = load double, ptr %s0
+  %t5 = bitcast double %t4 to i64
+  call void @jsrt_print_value(i64 %t5, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t6 = load double, ptr %s0
+  %t7 = load double, ptr %s1
+  %t8 = bitcast double %t6 to i64
+  %t9 = icmp ule i64 %t8, -2251799813685248
+  %t10 = and i1 true, %t9
+  %t11 = bitcast double %t7 to i64
+  %t12 = icmp ule i64 %t11, -2251799813685248
+  %t13 = and i1 %t10, %t12
+  br i1 %t13, label %b1, label %b2
+b1:
+  %t14 = fdiv double %t6, %t7
+  br label %b3
+b2:
+  %t15 = bitcast double %t6 to i64
+  %t16 = bitcast double %t7 to i64
+  %t17 = call i64 @jsrt_div(i64 %t15, i64 %t16)
+  %t18 = bitcast i64 %t17 to double
+  br label %b3
+b3:
+  %t19 = phi double [ %t14, %b1 ], [ %t18, %b2 ]
+  store double %t19, ptr %s0
+  %t20 = load double, ptr %s0
+  %t21 = bitcast double %t20 to i64
+  call void @jsrt_print_value(i64 %t21, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0xFFFB000000000000, ptr %s0
+  store double 0xFFFA000000000001, ptr %s1
+  store double 0xFFFA000000000000, ptr %s2
+  %t22 = ptrtoint ptr @.str.0 to i64
+  %t23 = or i64 %t22, -1970324836974592
+  %t24 = bitcast i64 %t23 to double
+  store double %t24, ptr %s3
+  %t25 = load double, ptr %s0
+  %t26 = load double, ptr %s1
+  %t27 = load double, ptr %s2
+  %t28 = load double, ptr %s3
+  %t29 = bitcast double %t25 to i64
+  call void @jsrt_print_value(i64 %t29, i32 32)
+  %t30 = bitcast double %t26 to i64
+  call void @jsrt_print_value(i64 %t30, i32 32)
+  %t31 = bitcast double %t27 to i64
+  call void @jsrt_print_value(i64 %t31, i32 32)
+  %t32 = bitcast double %t28 to i64
+  call void @jsrt_print_value(i64 %t32, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t33 = ptrtoint ptr @.str.1 to i64
+  %t34 = or i64 %t33, -1970324836974592
+  %t35 = bitcast i64 %t34 to double
+  store double %t35, ptr %s0
+  store double 0xFFFB000000000000, ptr %s1
+  %t36 = load double, ptr %s0
+  %t37 = load double, ptr %s1
+  %t38 = bitcast double %t36 to i64
+  %t39 = icmp ule i64 %t38, -2251799813685248
+  %t40 = and i1 true, %t39
+  %t41 = bitcast double %t37 to i64
+  %t42 = icmp ule i64 %t41, -2251799813685248
+  %t43 = and i1 %t40, %t42
+  br i1 %t43, label %b4, label %b5
+b4:
+  %t44 = fadd double %t36, %t37
+  br label %b6
+b5:
+  %t45 = bitcast double %t36 to i64
+  %t46 = bitcast double %t37 to i64
+  %t47 = call i64 @jsrt_add(i64 %t45, i64 %t46)
+  %t48 = bitcast i64 %t47 to double
+  br label %b6
+b6:
+  %t49 = phi double [ %t44, %b4 ], [ %t48, %b5 ]
+  store double %t49, ptr %s0
+  %t50 = call double @nothing()
+  store double %t50, ptr %s1
+  %t51 = ptrtoint ptr @.str.2 to i64
+  %t52 = or i64 %t51, -1970324836974592
+  %t53 = bitcast i64 %t52 to double
+  store double %t53, ptr %s2
+  %t54 = load double, ptr %s1
+  %t55 = load double, ptr %s2
+  %t56 = bitcast double %t54 to i64
+  %t57 = icmp ule i64 %t56, -2251799813685248
+  %t58 = and i1 true, %t57
+  %t59 = bitcast double %t55 to i64
+  %t60 = icmp ule i64 %t59, -2251799813685248
+  %t61 = and i1 %t58, %t60
+  br i1 %t61, label %b7, label %b8
+b7:
+  %t62 = fadd double %t54, %t55
+  br label %b9
+b8:
+  %t63 = bitcast double %t54 to i64
+  %t64 = bitcast double %t55 to i64
+  %t65 = call i64 @jsrt_add(i64 %t63, i64 %t64)
+  %t66 = bitcast i64 %t65 to double
+  br label %b9
+b9:
+  %t67 = phi double [ %t62, %b7 ], [ %t66, %b8 ]
+  store double %t67, ptr %s1
+  %t68 = load double, ptr %s0
+  %t69 = load double, ptr %s1
+  %t70 = bitcast double %t68 to i64
+  call void @jsrt_print_value(i64 %t70, i32 32)
+  %t71 = bitcast double %t69 to i64
+  call void @jsrt_print_value(i64 %t71, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0xFFFB000000000000, ptr %s0
+  store double 0xFFFB000000000000, ptr %s1
+  %t72 = load double, ptr %s0
+  %t73 = load double, ptr %s1
+  %t74 = bitcast double %t72 to i64
+  %t75 = icmp ule i64 %t74, -2251799813685248
+  %t76 = and i1 true, %t75
+  %t77 = bitcast double %t73 to i64
+  %t78 = icmp ule i64 %t77, -2251799813685248
+  %t79 = and i1 %t76, %t78
+  br i1 %t79, label %b10, label %b11
+b10:
+  %t80 = fcmp oeq double %t72, %t73
+  %t81 = zext i1 %t80 to i64
+  %t82 = or i64 %t81, -1688849860263936
+  %t83 = bitcast i64 %t82 to double
+  br label %b12
+b11:
+  %t84 = bitcast double %t72 to i64
+  %t85 = bitcast double %t73 to i64
+  %t86 = call i64 @jsrt_eq(i64 %t84, i64 %t85)
+  %t87 = bitcast i64 %t86 to double
+  br label %b12
+b12:
+  %t88 = phi double [ %t83, %b10 ], [ %t87, %b11 ]
+  store double %t88, ptr %s0
+  %t89 = call double @nothing()
+  store double %t89, ptr %s1
+  %t90 = call double @nothing()
+  store double %t90, ptr %s2
+  %t91 = load double, ptr %s1
+  %t92 = load double, ptr %s2
+  %t93 = bitcast double %t91 to i64
+  %t94 = icmp ule i64 %t93, -2251799813685248
+  %t95 = and i1 true, %t94
+  %t96 = bitcast double %t92 to i64
+  %t97 = icmp ule i64 %t96, -2251799813685248
+  %t98 = and i1 %t95, %t97
+  br i1 %t98, label %b13, label %b14
+b13:
+  %t99 = fcmp oeq double %t91, %t92
+  %t100 = zext i1 %t99 to i64
+  %t101 = or i64 %t100, -1688849860263936
+  %t102 = bitcast i64 %t101 to double
+  br label %b15
+b14:
+  %t103 = bitcast double %t91 to i64
+  %t104 = bitcast double %t92 to i64
+  %t105 = call i64 @jsrt_eq(i64 %t103, i64 %t104)
+  %t106 = bitcast i64 %t105 to double
+  br label %b15
+b15:
+  %t107 = phi double [ %t102, %b13 ], [ %t106, %b14 ]
+  store double %t107, ptr %s1
+  store double 0x0000000000000000, ptr %s2
+  store double 0x0000000000000000, ptr %s3
+  %t108 = load double, ptr %s2
+  %t109 = load double, ptr %s3
+  %t110 = bitcast double %t108 to i64
+  %t111 = icmp ule i64 %t110, -2251799813685248
+  %t112 = and i1 true, %t111
+  %t113 = bitcast double %t109 to i64
+  %t114 = icmp ule i64 %t113, -2251799813685248
+  %t115 = and i1 %t112, %t114
+  br i1 %t115, label %b16, label %b17
+b16:
+  %t116 = fdiv double %t108, %t109
+  br label %b18
+b17:
+  %t117 = bitcast double %t108 to i64
+  %t118 = bitcast double %t109 to i64
+  %t119 = call i64 @jsrt_div(i64 %t117, i64 %t118)
+  %t120 = bitcast i64 %t119 to double
+  br label %b18
+b18:
+  %t121 = phi double [ %t116, %b16 ], [ %t120, %b17 ]
+  store double %t121, ptr %s2
+  store double 0x0000000000000000, ptr %s3
+  store double 0x0000000000000000, ptr %s4
+  %t122 = load double, ptr %s3
+  %t123 = load double, ptr %s4
+  %t124 = bitcast double %t122 to i64
+  %t125 = icmp ule i64 %t124, -2251799813685248
+  %t126 = and i1 true, %t125
+  %t127 = bitcast double %t123 to i64
+  %t128 = icmp ule i64 %t127, -2251799813685248
+  %t129 = and i1 %t126, %t128
+  br i1 %t129, label %b19, label %b20
+b19:
+  %t130 = fdiv double %t122, %t123
+  br label %b21
+b20:
+  %t131 = bitcast double %t122 to i64
+  %t132 = bitcast double %t123 to i64
+  %t133 = call i64 @jsrt_div(i64 %t131, i64 %t132)
+  %t134 = bitcast i64 %t133 to double
+  br label %b21
+b21:
+  %t135 = phi double [ %t130, %b19 ], [ %t134, %b20 ]
+  store double %t135, ptr %s3
+  %t136 = load double, ptr %s2
+  %t137 = load double, ptr %s3
+  %t138 = bitcast double %t136 to i64
+  %t139 = icmp ule i64 %t138, -2251799813685248
+  %t140 = and i1 true, %t139
+  %t141 = bitcast double %t137 to i64
+  %t142 = icmp ule i64 %t141, -2251799813685248
+  %t143 = and i1 %t140, %t142
+  br i1 %t143, label %b22, label %b23
+b22:
+  %t144 = fcmp oeq double %t136, %t137
+  %t145 = zext i1 %t144 to i64
+  %t146 = or i64 %t145, -1688849860263936
+  %t147 = bitcast i64 %t146 to double
+  br label %b24
+b23:
+  %t148 = bitcast double %t136 to i64
+  %t149 = bitcast double %t137 to i64
+  %t150 = call i64 @jsrt_eq(i64 %t148, i64 %t149)
+  %t151 = bitcast i64 %t150 to double
+  br label %b24
+b24:
+  %t152 = phi double [ %t147, %b22 ], [ %t151, %b23 ]
+  store double %t152, ptr %s2
+  %t153 = load double, ptr %s0
+  %t154 = load double, ptr %s1
+  %t155 = load double, ptr %s2
+  %t156 = bitcast double %t153 to i64
+  call void @jsrt_print_value(i64 %t156, i32 32)
+  %t157 = bitcast double %t154 to i64
+  call void @jsrt_print_value(i64 %t157, i32 32)
+  %t158 = bitcast double %t155 to i64
+  call void @jsrt_print_value(i64 %t158, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t159 = load double, ptr %s0
+  ret double %t159
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+@.str.0 = private unnamed_addr constant [5 x i8] c"\74\65\78\74\00"
+@.str.1 = private unnamed_addr constant [4 x i8] c"\69\73\20\00"
+@.str.2 = private unnamed_addr constant [2 x i8] c"\21\00"
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_div(i64, i64)
+declare i64 @jsrt_eq(i64, i64)
@@ -0,0 +1,425 @@
+	.text
+	.global count_down
+	.type count_down, %function
+	.p2align 2
+count_down:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+.Lcount_down_L1:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD0
+	ldr d0, [x0, :lo12:.LCD0]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lcount_down.1
+	cmp x1, x9
+	b.hi .Lcount_down.1
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, gt
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lcount_down.2
+.Lcount_down.1:
+	bl jsrt_gt
+.Lcount_down.2:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lcount_down.3
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lcount_down.4
+.Lcount_down.3:
+	bl jsrt_not
+.Lcount_down.4:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lcount_down.5
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lcount_down_L2
+	b .Lcount_down.6
+.Lcount_down.5:
+	bl jsrt_truthy
+	cbnz x0, .Lcount_down_L2
+.Lcount_down.6:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD1
+	ldr d0, [x0, :lo12:.LCD1]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lcount_down.7
+	cmp x1, x9
+	b.hi .Lcount_down.7
+	fmov d0, x0
+	fmov d1, x1
+	fsub d0, d0, d1
+	fmov x0, d0
+	b .Lcount_down.8
+.Lcount_down.7:
+	bl jsrt_sub
+.Lcount_down.8:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	b .Lcount_down_L1
+.Lcount_down_L2:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global digits
+	.type digits, %function
+	.p2align 2
+digits:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	adrp x0, .LCD2
+	ldr d0, [x0, :lo12:.LCD2]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+.Ldigits_L1:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD3
+	ldr d0, [x0, :lo12:.LCD3]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Ldigits.9
+	cmp x1, x9
+	b.hi .Ldigits.9
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, ge
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Ldigits.10
+.Ldigits.9:
+	bl jsrt_ge
+.Ldigits.10:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Ldigits.11
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Ldigits.12
+.Ldigits.11:
+	bl jsrt_not
+.Ldigits.12:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Ldigits.13
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Ldigits_L2
+	b .Ldigits.14
+.Ldigits.13:
+	bl jsrt_truthy
+	cbnz x0, .Ldigits_L2
+.Ldigits.14:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD4
+	ldr d0, [x0, :lo12:.LCD4]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Ldigits.15
+	cmp x1, x9
+	b.hi .Ldigits.15
+	fmov d0, x0
+	fmov d1, x1
+	fdiv d0, d0, d1
+	fmov x0, d0
+	b .Ldigits.16
+.Ldigits.15:
+	bl jsrt_div
+.Ldigits.16:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD5
+	ldr d0, [x0, :lo12:.LCD5]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-16]
+	b .Ldigits_L1
+.Ldigits_L2:
+	ldr x0, [fp, #-16]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global js_main
+	.type js_main, %function
+	.p2align 2
+js_main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	sub sp, sp, #16
+	stp x19, x20, [sp, #-16]!
+	stp x21, x22, [sp, #-16]!
+	stp x23, x24, [sp, #-16]!
+	stp x25, x26, [sp, #-16]!
+	stp x27, x28, [sp, #-16]!
+	adrp x0, .LCD6
+	ldr d0, [x0, :lo12:.LCD6]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl count_down
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD7
+	ldr d0, [x0, :lo12:.LCD7]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl count_down
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD8
+	ldr d0, [x0, :lo12:.LCD8]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl digits
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD9
+	ldr d0, [x0, :lo12:.LCD9]
+	str d0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	bl digits
+	add sp, sp, #16
+	str x0, [sp, #-16]!
+	ldr x0, [sp, #0]
+	mov w1, #10
+	bl jsrt_print_value
+	add sp, sp, #16
+	mov x0, #0xfffc000000000000
+	str x0, [sp, #-16]!
+	add sp, sp, #16
+	adrp x0, .LCD10
+	ldr d0, [x0, :lo12:.LCD10]
+	str d0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+.Lmain_L1:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD11
+	ldr d0, [x0, :lo12:.LCD11]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fcmp d0, d1
+	cset x0, mi
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.17
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	eor x0, x0, #1
+	mov x9, #0xfffa000000000000
+	orr x0, x0, x9
+	b .Lmain.18
+.Lmain.17:
+	bl jsrt_not
+.Lmain.18:
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	mov x9, #0xfff8000000000000
+	cmp x0, x9
+	b.hi .Lmain.19
+	fmov d0, x0
+	fcmp d0, #0.0
+	cset x0, ne
+	csel x0, x0, xzr, vc
+	cbnz x0, .Lmain_L2
+	b .Lmain.20
+.Lmain.19:
+	bl jsrt_truthy
+	cbnz x0, .Lmain_L2
+.Lmain.20:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	adrp x0, .LCD12
+	ldr d0, [x0, :lo12:.LCD12]
+	str d0, [sp, #-16]!
+	ldr x1, [sp], #16
+	ldr x0, [sp], #16
+	fmov d0, x0
+	fmov d1, x1
+	fadd d0, d0, d1
+	fmov x0, d0
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	str x0, [fp, #-8]
+	b .Lmain_L1
+.Lmain_L2:
+	ldr x0, [fp, #-8]
+	str x0, [sp, #-16]!
+	ldr x0, [sp], #16
+	ldp x27, x28, [sp], #16
+	ldp x25, x26, [sp], #16
+	ldp x23, x24, [sp], #16
+	ldp x21, x22, [sp], #16
+	ldp x19, x20, [sp], #16
+	mov sp, fp
+	ldp fp, lr, [sp], #16
+	ret
+	.global main
+	.type main, %function
+	.p2align 2
+main:
+	stp fp, lr, [sp, #-16]!
+	mov fp, sp
+	bl js_main
+	bl jsrt_exit
+	.data
+	.p2align 3
+.LCD0:
+	.double 0
+.LCD1:
+	.double 1
+.LCD2:
+	.double 1
+.LCD3:
+	.double 10
+.LCD4:
+	.double 10
+.LCD5:
+	.double 1
+.LCD6:
+	.double 3
+.LCD7:
+	.double 0
+.LCD8:
+	.double 7
+.LCD9:
+	.double 12345
+.LCD10:
+	.double 0
+.LCD11:
+	.double 3
+.LCD12:
+	.double 1
+	.section .note.GNU-stack,"",%progbits
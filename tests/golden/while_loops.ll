@@ -0,0 +1,414 @@
+; Generated by js-compiler
+
+define double @count_down(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.n = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  br label %LL1
+LL1:
+  %t3 = load double, ptr %v.n
+  store double %t3, ptr %s0
+  store double 0x0000000000000000, ptr %s1
+  %t4 = load double, ptr %s0
+  %t5 = load double, ptr %s1
+  %t6 = bitcast double %t4 to i64
+  %t7 = icmp ule i64 %t6, -2251799813685248
+  %t8 = and i1 true, %t7
+  %t9 = bitcast double %t5 to i64
+  %t10 = icmp ule i64 %t9, -2251799813685248
+  %t11 = and i1 %t8, %t10
+  br i1 %t11, label %b1, label %b2
+b1:
+  %t12 = fcmp ogt double %t4, %t5
+  %t13 = zext i1 %t12 to i64
+  %t14 = or i64 %t13, -1688849860263936
+  %t15 = bitcast i64 %t14 to double
+  br label %b3
+b2:
+  %t16 = bitcast double %t4 to i64
+  %t17 = bitcast double %t5 to i64
+  %t18 = call i64 @jsrt_gt(i64 %t16, i64 %t17)
+  %t19 = bitcast i64 %t18 to double
+  br label %b3
+b3:
+  %t20 = phi double [ %t15, %b1 ], [ %t19, %b2 ]
+  store double %t20, ptr %s0
+  %t21 = load double, ptr %s0
+  %t22 = bitcast double %t21 to i64
+  %t23 = icmp ule i64 %t22, -2251799813685248
+  %t24 = and i1 true, %t23
+  br i1 %t24, label %b4, label %b5
+b4:
+  %t25 = fcmp ueq double %t21, 0.0
+  %t26 = zext i1 %t25 to i64
+  %t27 = or i64 %t26, -1688849860263936
+  %t28 = bitcast i64 %t27 to double
+  br label %b6
+b5:
+  %t29 = bitcast double %t21 to i64
+  %t30 = call i64 @jsrt_not(i64 %t29)
+  %t31 = bitcast i64 %t30 to double
+  br label %b6
+b6:
+  %t32 = phi double [ %t28, %b4 ], [ %t31, %b5 ]
+  store double %t32, ptr %s0
+  %t33 = load double, ptr %s0
+  %t34 = bitcast double %t33 to i64
+  %t35 = icmp ule i64 %t34, -2251799813685248
+  %t36 = and i1 true, %t35
+  br i1 %t36, label %b7, label %b8
+b7:
+  %t37 = fcmp one double %t33, 0.0
+  br label %b9
+b8:
+  %t38 = bitcast double %t33 to i64
+  %t39 = call i64 @jsrt_truthy(i64 %t38)
+  %t40 = icmp ne i64 %t39, 0
+  br label %b9
+b9:
+  %t41 = phi i1 [ %t37, %b7 ], [ %t40, %b8 ]
+  br i1 %t41, label %LL2, label %b10
+b10:
+  %t42 = load double, ptr %v.n
+  store double %t42, ptr %s0
+  %t43 = load double, ptr %s0
+  %t44 = bitcast double %t43 to i64
+  call void @jsrt_print_value(i64 %t44, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  %t45 = load double, ptr %v.n
+  store double %t45, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t46 = load double, ptr %s0
+  %t47 = load double, ptr %s1
+  %t48 = bitcast double %t46 to i64
+  %t49 = icmp ule i64 %t48, -2251799813685248
+  %t50 = and i1 true, %t49
+  %t51 = bitcast double %t47 to i64
+  %t52 = icmp ule i64 %t51, -2251799813685248
+  %t53 = and i1 %t50, %t52
+  br i1 %t53, label %b11, label %b12
+b11:
+  %t54 = fsub double %t46, %t47
+  br label %b13
+b12:
+  %t55 = bitcast double %t46 to i64
+  %t56 = bitcast double %t47 to i64
+  %t57 = call i64 @jsrt_sub(i64 %t55, i64 %t56)
+  %t58 = bitcast i64 %t57 to double
+  br label %b13
+b13:
+  %t59 = phi double [ %t54, %b11 ], [ %t58, %b12 ]
+  store double %t59, ptr %s0
+  %t60 = load double, ptr %s0
+  store double %t60, ptr %v.n
+  br label %LL1
+LL2:
+  %t61 = load double, ptr %v.n
+  store double %t61, ptr %s0
+  %t62 = load double, ptr %s0
+  ret double %t62
+}
+
+define double @digits(double %p0) {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.n = alloca double
+  %v.count = alloca double
+  store double %p0, ptr %v.n
+  %t1 = load double, ptr %v.n
+  store double %t1, ptr %s0
+  %t2 = load double, ptr %s0
+  store double %t2, ptr %v.n
+  store double 0x3FF0000000000000, ptr %s0
+  %t3 = load double, ptr %s0
+  store double %t3, ptr %v.count
+  br label %LL1
+LL1:
+  %t4 = load double, ptr %v.n
+  store double %t4, ptr %s0
+  store double 0x4024000000000000, ptr %s1
+  %t5 = load double, ptr %s0
+  %t6 = load double, ptr %s1
+  %t7 = bitcast double %t5 to i64
+  %t8 = icmp ule i64 %t7, -2251799813685248
+  %t9 = and i1 true, %t8
+  %t10 = bitcast double %t6 to i64
+  %t11 = icmp ule i64 %t10, -2251799813685248
+  %t12 = and i1 %t9, %t11
+  br i1 %t12, label %b1, label %b2
+b1:
+  %t13 = fcmp oge double %t5, %t6
+  %t14 = zext i1 %t13 to i64
+  %t15 = or i64 %t14, -1688849860263936
+  %t16 = bitcast i64 %t15 to double
+  br label %b3
+b2:
+  %t17 = bitcast double %t5 to i64
+  %t18 = bitcast double %t6 to i64
+  %t19 = call i64 @jsrt_ge(i64 %t17, i64 %t18)
+  %t20 = bitcast i64 %t19 to double
+  br label %b3
+b3:
+  %t21 = phi double [ %t16, %b1 ], [ %t20, %b2 ]
+  store double %t21, ptr %s0
+  %t22 = load double, ptr %s0
+  %t23 = bitcast double %t22 to i64
+  %t24 = icmp ule i64 %t23, -2251799813685248
+  %t25 = and i1 true, %t24
+  br i1 %t25, label %b4, label %b5
+b4:
+  %t26 = fcmp ueq double %t22, 0.0
+  %t27 = zext i1 %t26 to i64
+  %t28 = or i64 %t27, -1688849860263936
+  %t29 = bitcast i64 %t28 to double
+  br label %b6
+b5:
+  %t30 = bitcast double %t22 to i64
+  %t31 = call i64 @jsrt_not(i64 %t30)
+  %t32 = bitcast i64 %t31 to double
+  br label %b6
+b6:
+  %t33 = phi double [ %t29, %b4 ], [ %t32, %b5 ]
+  store double %t33, ptr %s0
+  %t34 = load double, ptr %s0
+  %t35 = bitcast double %t34 to i64
+  %t36 = icmp ule i64 %t35, -2251799813685248
+  %t37 = and i1 true, %t36
+  br i1 %t37, label %b7, label %b8
+b7:
+  %t38 = fcmp one double %t34, 0.0
+  br label %b9
+b8:
+  %t39 = bitcast double %t34 to i64
+  %t40 = call i64 @jsrt_truthy(i64 %t39)
+  %t41 = icmp ne i64 %t40, 0
+  br label %b9
+b9:
+  %t42 = phi i1 [ %t38, %b7 ], [ %t41, %b8 ]
+  br i1 %t42, label %LL2, label %b10
+b10:
+  %t43 = load double, ptr %v.n
+  store double %t43, ptr %s0
+  store double 0x4024000000000000, ptr %s1
+  %t44 = load double, ptr %s0
+  %t45 = load double, ptr %s1
+  %t46 = bitcast double %t44 to i64
+  %t47 = icmp ule i64 %t46, -2251799813685248
+  %t48 = and i1 true, %t47
+  %t49 = bitcast double %t45 to i64
+  %t50 = icmp ule i64 %t49, -2251799813685248
+  %t51 = and i1 %t48, %t50
+  br i1 %t51, label %b11, label %b12
+b11:
+  %t52 = fdiv double %t44, %t45
+  br label %b13
+b12:
+  %t53 = bitcast double %t44 to i64
+  %t54 = bitcast double %t45 to i64
+  %t55 = call i64 @jsrt_div(i64 %t53, i64 %t54)
+  %t56 = bitcast i64 %t55 to double
+  br label %b13
+b13:
+  %t57 = phi double [ %t52, %b11 ], [ %t56, %b12 ]
+  store double %t57, ptr %s0
+  %t58 = load double, ptr %s0
+  store double %t58, ptr %v.n
+  %t59 = load double, ptr %v.count
+  store double %t59, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t60 = load double, ptr %s0
+  %t61 = load double, ptr %s1
+  %t62 = bitcast double %t60 to i64
+  %t63 = icmp ule i64 %t62, -2251799813685248
+  %t64 = and i1 true, %t63
+  %t65 = bitcast double %t61 to i64
+  %t66 = icmp ule i64 %t65, -2251799813685248
+  %t67 = and i1 %t64, %t66
+  br i1 %t67, label %b14, label %b15
+b14:
+  %t68 = fadd double %t60, %t61
+  br label %b16
+b15:
+  %t69 = bitcast double %t60 to i64
+  %t70 = bitcast double %t61 to i64
+  %t71 = call i64 @jsrt_add(i64 %t69, i64 %t70)
+  %t72 = bitcast i64 %t71 to double
+  br label %b16
+b16:
+  %t73 = phi double [ %t68, %b14 ], [ %t72, %b15 ]
+  store double %t73, ptr %s0
+  %t74 = load double, ptr %s0
+  store double %t74, ptr %v.count
+  br label %LL1
+LL2:
+  %t75 = load double, ptr %v.count
+  store double %t75, ptr %s0
+  %t76 = load double, ptr %s0
+  ret double %t76
+}
+
+define double @js_main() {
+entry:
+  %s0 = alloca double
+  %s1 = alloca double
+  %v.i = alloca double
+  store double 0x4008000000000000, ptr %s0
+  %t1 = load double, ptr %s0
+  %t2 = call double @count_down(double %t1)
+  store double %t2, ptr %s0
+  %t3 = load double, ptr %s0
+  %t4 = bitcast double %t3 to i64
+  call void @jsrt_print_value(i64 %t4, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t5 = load double, ptr %s0
+  %t6 = call double @count_down(double %t5)
+  store double %t6, ptr %s0
+  %t7 = load double, ptr %s0
+  %t8 = bitcast double %t7 to i64
+  call void @jsrt_print_value(i64 %t8, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x401C000000000000, ptr %s0
+  %t9 = load double, ptr %s0
+  %t10 = call double @digits(double %t9)
+  store double %t10, ptr %s0
+  %t11 = load double, ptr %s0
+  %t12 = bitcast double %t11 to i64
+  call void @jsrt_print_value(i64 %t12, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x40C81C8000000000, ptr %s0
+  %t13 = load double, ptr %s0
+  %t14 = call double @digits(double %t13)
+  store double %t14, ptr %s0
+  %t15 = load double, ptr %s0
+  %t16 = bitcast double %t15 to i64
+  call void @jsrt_print_value(i64 %t16, i32 10)
+  store double 0xFFFC000000000000, ptr %s0
+  store double 0x0000000000000000, ptr %s0
+  %t17 = load double, ptr %s0
+  store double %t17, ptr %v.i
+  br label %LL1
+LL1:
+  %t18 = load double, ptr %v.i
+  store double %t18, ptr %s0
+  store double 0x4008000000000000, ptr %s1
+  %t19 = load double, ptr %s0
+  %t20 = load double, ptr %s1
+  %t21 = bitcast double %t19 to i64
+  %t22 = icmp ule i64 %t21, -2251799813685248
+  %t23 = and i1 true, %t22
+  %t24 = bitcast double %t20 to i64
+  %t25 = icmp ule i64 %t24, -2251799813685248
+  %t26 = and i1 %t23, %t25
+  br i1 %t26, label %b1, label %b2
+b1:
+  %t27 = fcmp olt double %t19, %t20
+  %t28 = zext i1 %t27 to i64
+  %t29 = or i64 %t28, -1688849860263936
+  %t30 = bitcast i64 %t29 to double
+  br label %b3
+b2:
+  %t31 = bitcast double %t19 to i64
+  %t32 = bitcast double %t20 to i64
+  %t33 = call i64 @jsrt_lt(i64 %t31, i64 %t32)
+  %t34 = bitcast i64 %t33 to double
+  br label %b3
+b3:
+  %t35 = phi double [ %t30, %b1 ], [ %t34, %b2 ]
+  store double %t35, ptr %s0
+  %t36 = load double, ptr %s0
+  %t37 = bitcast double %t36 to i64
+  %t38 = icmp ule i64 %t37, -2251799813685248
+  %t39 = and i1 true, %t38
+  br i1 %t39, label %b4, label %b5
+b4:
+  %t40 = fcmp ueq double %t36, 0.0
+  %t41 = zext i1 %t40 to i64
+  %t42 = or i64 %t41, -1688849860263936
+  %t43 = bitcast i64 %t42 to double
+  br label %b6
+b5:
+  %t44 = bitcast double %t36 to i64
+  %t45 = call i64 @jsrt_not(i64 %t44)
+  %t46 = bitcast i64 %t45 to double
+  br label %b6
+b6:
+  %t47 = phi double [ %t43, %b4 ], [ %t46, %b5 ]
+  store double %t47, ptr %s0
+  %t48 = load double, ptr %s0
+  %t49 = bitcast double %t48 to i64
+  %t50 = icmp ule i64 %t49, -2251799813685248
+  %t51 = and i1 true, %t50
+  br i1 %t51, label %b7, label %b8
+b7:
+  %t52 = fcmp one double %t48, 0.0
+  br label %b9
+b8:
+  %t53 = bitcast double %t48 to i64
+  %t54 = call i64 @jsrt_truthy(i64 %t53)
+  %t55 = icmp ne i64 %t54, 0
+  br label %b9
+b9:
+  %t56 = phi i1 [ %t52, %b7 ], [ %t55, %b8 ]
+  br i1 %t56, label %LL2, label %b10
+b10:
+  %t57 = load double, ptr %v.i
+  store double %t57, ptr %s0
+  store double 0x3FF0000000000000, ptr %s1
+  %t58 = load double, ptr %s0
+  %t59 = load double, ptr %s1
+  %t60 = bitcast double %t58 to i64
+  %t61 = icmp ule i64 %t60, -2251799813685248
+  %t62 = and i1 true, %t61
+  %t63 = bitcast double %t59 to i64
+  %t64 = icmp ule i64 %t63, -2251799813685248
+  %t65 = and i1 %t62, %t64
+  br i1 %t65, label %b11, label %b12
+b11:
+  %t66 = fadd double %t58, %t59
+  br label %b13
+b12:
+  %t67 = bitcast double %t58 to i64
+  %t68 = bitcast double %t59 to i64
+  %t69 = call i64 @jsrt_add(i64 %t67, i64 %t68)
+  %t70 = bitcast i64 %t69 to double
+  br label %b13
+b13:
+  %t71 = phi double [ %t66, %b11 ], [ %t70, %b12 ]
+  store double %t71, ptr %s0
+  %t72 = load double, ptr %s0
+  store double %t72, ptr %v.i
+  br label %LL1
+LL2:
+  %t73 = load double, ptr %v.i
+  store double %t73, ptr %s0
+  %t74 = load double, ptr %s0
+  ret double %t74
+}
+
+define i32 @main() {
+entry:
+  %result = call double @js_main()
+  %bits = bitcast double %result to i64
+  call void @jsrt_exit(i64 %bits)
+  unreachable
+}
+
+declare void @jsrt_print_value(i64, i32)
+declare void @jsrt_print_newline()
+declare void @jsrt_exit(i64)
+declare i64 @jsrt_add(i64, i64)
+declare i64 @jsrt_div(i64, i64)
+declare i64 @jsrt_ge(i64, i64)
+declare i64 @jsrt_gt(i64, i64)
+declare i64 @jsrt_lt(i64, i64)
+declare i64 @jsrt_not(i64)
+declare i64 @jsrt_sub(i64, i64)
+declare i64 @jsrt_truthy(i64)